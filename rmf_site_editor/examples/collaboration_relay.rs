@@ -0,0 +1,104 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+//! A minimal relay for collaborative editing. Every text message that an
+//! editor sends is forwarded to all the other editors that are connected.
+//! Merging concurrent edits is left to the editors themselves.
+//!
+//! Run it with
+//! `cargo run --example collaboration_relay --features collaboration -- [address]`
+
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
+use std::{
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tungstenite::Message;
+
+type Clients = Arc<Mutex<Vec<(usize, Sender<String>)>>>;
+
+fn main() {
+    let address = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:9002".to_owned());
+    let listener = TcpListener::bind(&address).expect("Unable to bind the relay address");
+    println!("Relaying edits on ws://{address}");
+
+    let clients: Clients = Default::default();
+    for (id, stream) in listener.incoming().enumerate() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let (tx, rx) = crossbeam_channel::unbounded();
+        clients.lock().unwrap().push((id, tx));
+        let clients = clients.clone();
+        std::thread::spawn(move || {
+            serve_client(id, stream, rx, &clients);
+            clients.lock().unwrap().retain(|(other, _)| *other != id);
+            println!("Editor {id} disconnected");
+        });
+    }
+}
+
+fn serve_client(id: usize, stream: TcpStream, outgoing: Receiver<String>, clients: &Clients) {
+    let Ok(mut socket) = tungstenite::accept(stream) else {
+        return;
+    };
+    // Wait for incoming messages in short intervals so that messages from
+    // other editors can be forwarded in between.
+    if socket
+        .get_ref()
+        .set_read_timeout(Some(Duration::from_millis(20)))
+        .is_err()
+    {
+        return;
+    }
+    println!("Editor {id} connected");
+
+    loop {
+        loop {
+            match outgoing.try_recv() {
+                Ok(text) => {
+                    if socket.send(Message::Text(text)).is_err() {
+                        return;
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return,
+            }
+        }
+
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                for (other, tx) in clients.lock().unwrap().iter() {
+                    if *other != id {
+                        let _ = tx.send(text.clone());
+                    }
+                }
+            }
+            Ok(Message::Close(_)) => return,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(err))
+                if matches!(
+                    err.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) => {}
+            Err(_) => return,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use librmf_site_editor::{
+    bevy_egui::egui::Ui,
+    site::{LevelExtension, LevelExtensionPlugin, Pose},
+    SiteEditor,
+};
+use serde::{Deserialize, Serialize};
+
+/// A site-specific kind of entity. Every access point that is placed on a
+/// level gets saved under the `my_company/wifi_access_point` key of the level.
+#[derive(Component, Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct WifiAccessPoint {
+    ssid: String,
+    pose: Pose,
+}
+
+impl LevelExtension for WifiAccessPoint {
+    const KEY: &'static str = "my_company/wifi_access_point";
+    const LABEL: &'static str = "Wifi Access Point";
+
+    fn spawn(&self, entity: &mut EntityCommands) {
+        entity.insert(SpatialBundle::from_transform(self.pose.transform()));
+    }
+
+    fn inspect(&mut self, ui: &mut Ui) -> bool {
+        ui.horizontal(|ui| {
+            ui.label("SSID");
+            ui.text_edit_singleline(&mut self.ssid).changed()
+        })
+        .inner
+    }
+}
+
+/// Lets embed site editor in our application with our own kind of entity
+fn main() {
+    App::new()
+        .add_plugins((
+            SiteEditor::default(),
+            LevelExtensionPlugin::<WifiAccessPoint>::default(),
+        ))
+        .run();
+}
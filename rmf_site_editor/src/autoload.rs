@@ -22,6 +22,9 @@ use std::path::PathBuf;
 pub struct Autoload {
     pub filename: Option<PathBuf>,
     pub import: Option<PathBuf>,
+    /// A map that should be fetched from a URL, e.g. a map hosted next to the
+    /// web build of the editor
+    pub url: Option<String>,
 }
 
 impl Autoload {
@@ -29,6 +32,15 @@ impl Autoload {
         Autoload {
             filename: Some(filename),
             import,
+            url: None,
+        }
+    }
+
+    pub fn url(url: String) -> Self {
+        Autoload {
+            filename: None,
+            import: None,
+            url: Some(url),
         }
     }
 }
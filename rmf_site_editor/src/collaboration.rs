@@ -0,0 +1,488 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+//! Collaborative editing of a site between several editors. Every editor
+//! connects to a relay over a WebSocket and sends the edits that its user
+//! makes. The relay forwards every message to all the other editors. Edits
+//! that happen concurrently are merged with a last-writer-wins rule for each
+//! property of each element, see [`EditMerger`].
+//!
+//! Elements are identified by their [`SiteID`], so every editor needs to open
+//! the same version of the site before connecting. Only edits to elements that
+//! already exist are shared: moving anchors, renaming elements, changing poses,
+//! and deleting elements. Creating new elements is not shared, and a new
+//! element does not get a [`SiteID`] until the site is saved, so edits to it
+//! stay local as well. A warning is logged whenever such an element is created
+//! while connected.
+//!
+//! Remote edits are applied through the same [`Change`] and [`Delete`] events
+//! as local edits, so everything that depends on the edited element is kept
+//! up to date.
+//!
+//! An example relay can be found in `examples/collaboration_relay.rs`.
+
+use crate::{
+    site::{
+        Anchor, Category, Change, ChangePlugin, Delete, NameInSite, Pose, SiteID, SiteUpdateSet,
+    },
+    widgets::prelude::*,
+    AppState, CurrentWorkspace,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{CollapsingHeader, Grid, TextEdit, Ui};
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
+use rmf_site_format::{EditMerger, EditMessage, EditedProperty, SiteEdit};
+use std::{collections::HashSet, time::Duration};
+use tungstenite::{stream::MaybeTlsStream, Message};
+
+/// How long the connection thread waits for incoming messages before it
+/// checks for outgoing messages again.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+const DEFAULT_RELAY_URL: &str = "ws://localhost:9002";
+
+#[derive(Default)]
+pub struct CollaborationPlugin {}
+
+impl Plugin for CollaborationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Collaboration>()
+            .add_plugins((
+                ChangePlugin::<Anchor>::default(),
+                PropertiesTilePlugin::<ViewCollaboration>::new(),
+            ))
+            .add_systems(
+                Update,
+                (
+                    receive_remote_edits,
+                    send_local_edits,
+                    warn_unshared_elements,
+                )
+                    .chain()
+                    .run_if(AppState::in_displaying_mode()),
+            )
+            .add_systems(
+                First,
+                send_local_deletions
+                    .before(SiteUpdateSet::Deletion)
+                    .run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CollaborationStatus {
+    Disconnected,
+    Connecting,
+    Connected,
+    Failed(String),
+}
+
+#[derive(Resource)]
+pub struct Collaboration {
+    pub relay_url: String,
+    /// Name of this editor. This must be different for every editor that is
+    /// connected to the relay.
+    pub author: String,
+    pub status: CollaborationStatus,
+    connection: Option<RelayConnection>,
+    merger: EditMerger,
+    /// Properties that were just changed by remote edits. These changes must
+    /// not be sent back to the relay.
+    remote_changes: HashSet<(Entity, EditedProperty)>,
+}
+
+impl Default for Collaboration {
+    fn default() -> Self {
+        Self {
+            relay_url: DEFAULT_RELAY_URL.to_owned(),
+            author: format!("editor-{}", std::process::id()),
+            status: CollaborationStatus::Disconnected,
+            connection: None,
+            merger: EditMerger::default(),
+            remote_changes: HashSet::new(),
+        }
+    }
+}
+
+impl Collaboration {
+    pub fn is_active(&self) -> bool {
+        self.connection.is_some()
+    }
+
+    pub fn connect(&mut self) {
+        let (outgoing_tx, outgoing_rx) = crossbeam_channel::unbounded();
+        let (incoming_tx, incoming_rx) = crossbeam_channel::unbounded();
+        let url = self.relay_url.clone();
+        std::thread::spawn(move || run_relay_connection(url, outgoing_rx, incoming_tx));
+        self.connection = Some(RelayConnection {
+            outgoing: outgoing_tx,
+            incoming: incoming_rx,
+        });
+        self.status = CollaborationStatus::Connecting;
+    }
+
+    /// Dropping the outgoing channel tells the connection thread to close
+    /// the socket.
+    pub fn disconnect(&mut self) {
+        self.connection = None;
+        self.status = CollaborationStatus::Disconnected;
+        self.remote_changes.clear();
+    }
+
+    fn send(&mut self, edit: SiteEdit) {
+        let Some(connection) = &self.connection else {
+            return;
+        };
+        let message = self.merger.stamp(&self.author, edit);
+        match message.to_json() {
+            Ok(text) => {
+                let _ = connection.outgoing.send(text);
+            }
+            Err(err) => error!("Unable to serialize edit for the relay: {err}"),
+        }
+    }
+}
+
+struct RelayConnection {
+    outgoing: Sender<String>,
+    incoming: Receiver<RelayEvent>,
+}
+
+enum RelayEvent {
+    Connected,
+    Message(String),
+    Closed(String),
+}
+
+fn run_relay_connection(url: String, outgoing: Receiver<String>, incoming: Sender<RelayEvent>) {
+    let mut socket = match tungstenite::connect(url.as_str()) {
+        Ok((socket, _)) => socket,
+        Err(err) => {
+            let _ = incoming.send(RelayEvent::Closed(format!(
+                "Unable to connect to {url}: {err}"
+            )));
+            return;
+        }
+    };
+    if let MaybeTlsStream::Plain(stream) = socket.get_mut() {
+        if let Err(err) = stream.set_read_timeout(Some(POLL_INTERVAL)) {
+            let _ = incoming.send(RelayEvent::Closed(format!(
+                "Unable to configure connection: {err}"
+            )));
+            return;
+        }
+    }
+    let _ = incoming.send(RelayEvent::Connected);
+
+    loop {
+        loop {
+            match outgoing.try_recv() {
+                Ok(text) => {
+                    if let Err(err) = socket.send(Message::Text(text)) {
+                        let _ = incoming.send(RelayEvent::Closed(err.to_string()));
+                        return;
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    let _ = socket.close(None);
+                    return;
+                }
+            }
+        }
+
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                if incoming.send(RelayEvent::Message(text)).is_err() {
+                    return;
+                }
+            }
+            Ok(Message::Close(_)) => {
+                let _ = incoming.send(RelayEvent::Closed("Relay closed the connection".into()));
+                return;
+            }
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(err))
+                if matches!(
+                    err.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) => {}
+            Err(err) => {
+                let _ = incoming.send(RelayEvent::Closed(err.to_string()));
+                return;
+            }
+        }
+    }
+}
+
+/// Find the element of the current site that has a site ID.
+fn find_element(
+    site_id: u32,
+    root: Entity,
+    elements: &Query<(Entity, &SiteID)>,
+    parents: &Query<&Parent>,
+) -> Option<Entity> {
+    elements
+        .iter()
+        .filter(|(_, id)| id.0 == site_id)
+        .map(|(e, _)| e)
+        .find(|e| AncestorIter::new(parents, *e).any(|p| p == root))
+}
+
+fn receive_remote_edits(
+    mut collaboration: ResMut<Collaboration>,
+    current_workspace: Res<CurrentWorkspace>,
+    elements: Query<(Entity, &SiteID)>,
+    parents: Query<&Parent>,
+    mut change_anchor: EventWriter<Change<Anchor>>,
+    mut change_name: EventWriter<Change<NameInSite>>,
+    mut change_pose: EventWriter<Change<Pose>>,
+    mut delete: EventWriter<Delete>,
+) {
+    let collaboration = &mut *collaboration;
+    let Some(connection) = &collaboration.connection else {
+        return;
+    };
+    let mut closed = None;
+    let mut messages = Vec::new();
+    for event in connection.incoming.try_iter() {
+        match event {
+            RelayEvent::Connected => collaboration.status = CollaborationStatus::Connected,
+            RelayEvent::Message(text) => messages.push(text),
+            RelayEvent::Closed(reason) => closed = Some(reason),
+        }
+    }
+
+    for text in messages {
+        let message = match EditMessage::from_json(&text) {
+            Ok(message) => message,
+            Err(err) => {
+                warn!("Ignoring invalid message from the relay: {err}");
+                continue;
+            }
+        };
+        if message.author == collaboration.author || !collaboration.merger.accept(&message) {
+            continue;
+        }
+        let Some(root) = current_workspace.root else {
+            continue;
+        };
+        let element = message.edit.element();
+        let Some(e) = find_element(element, root, &elements, &parents) else {
+            warn!(
+                "Received an edit from {} for element #{element} that does not exist in this site",
+                message.author,
+            );
+            continue;
+        };
+        collaboration
+            .remote_changes
+            .insert((e, message.edit.property()));
+        match message.edit {
+            SiteEdit::Anchor { anchor, .. } => {
+                change_anchor.send(Change::new(anchor, e));
+            }
+            SiteEdit::Name { name, .. } => {
+                change_name.send(Change::new(NameInSite(name), e));
+            }
+            SiteEdit::Pose { pose, .. } => {
+                change_pose.send(Change::new(pose, e));
+            }
+            SiteEdit::Delete { .. } => {
+                delete.send(Delete::new(e));
+            }
+        }
+    }
+
+    if let Some(reason) = closed {
+        collaboration.connection = None;
+        collaboration.remote_changes.clear();
+        collaboration.status = CollaborationStatus::Failed(reason);
+    }
+}
+
+fn send_local_edits(
+    mut collaboration: ResMut<Collaboration>,
+    current_workspace: Res<CurrentWorkspace>,
+    parents: Query<&Parent>,
+    anchors: Query<(Entity, &SiteID, Ref<Anchor>), Changed<Anchor>>,
+    names: Query<(Entity, &SiteID, Ref<NameInSite>), Changed<NameInSite>>,
+    poses: Query<(Entity, &SiteID, Ref<Pose>), Changed<Pose>>,
+) {
+    if !collaboration.is_active() {
+        return;
+    }
+    let Some(root) = current_workspace.root else {
+        return;
+    };
+    let in_site = |e: Entity| AncestorIter::new(&parents, e).any(|p| p == root);
+
+    let mut edits = Vec::new();
+    for (e, id, anchor) in &anchors {
+        if !anchor.is_added() && in_site(e) {
+            edits.push((
+                e,
+                SiteEdit::Anchor {
+                    element: id.0,
+                    anchor: anchor.clone(),
+                },
+            ));
+        }
+    }
+    for (e, id, name) in &names {
+        if !name.is_added() && in_site(e) {
+            edits.push((
+                e,
+                SiteEdit::Name {
+                    element: id.0,
+                    name: name.0.clone(),
+                },
+            ));
+        }
+    }
+    for (e, id, pose) in &poses {
+        if !pose.is_added() && in_site(e) {
+            edits.push((
+                e,
+                SiteEdit::Pose {
+                    element: id.0,
+                    pose: *pose,
+                },
+            ));
+        }
+    }
+
+    for (e, edit) in edits {
+        if collaboration.remote_changes.remove(&(e, edit.property())) {
+            continue;
+        }
+        collaboration.send(edit);
+    }
+}
+
+fn send_local_deletions(
+    mut collaboration: ResMut<Collaboration>,
+    mut deletions: EventReader<Delete>,
+    site_ids: Query<&SiteID>,
+) {
+    if !collaboration.is_active() {
+        deletions.clear();
+        return;
+    }
+    for deletion in deletions.read() {
+        if collaboration
+            .remote_changes
+            .remove(&(deletion.element, EditedProperty::Existence))
+        {
+            continue;
+        }
+        if let Ok(id) = site_ids.get(deletion.element) {
+            collaboration.send(SiteEdit::Delete { element: id.0 });
+        }
+    }
+}
+
+/// New elements cannot be identified by the other editors, so let the user
+/// know that they are not shared.
+fn warn_unshared_elements(
+    collaboration: Res<Collaboration>,
+    current_workspace: Res<CurrentWorkspace>,
+    parents: Query<&Parent>,
+    new_elements: Query<(Entity, &Category), (Added<Category>, Without<SiteID>)>,
+) {
+    if !collaboration.is_active() {
+        return;
+    }
+    let Some(root) = current_workspace.root else {
+        return;
+    };
+    for (e, category) in &new_elements {
+        if AncestorIter::new(&parents, e).any(|p| p == root) {
+            warn!(
+                "The new {} {e:?} is not shared with the other editors. Only \
+                edits to elements that existed when connecting are shared.",
+                category.label(),
+            );
+        }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewCollaboration<'w> {
+    collaboration: ResMut<'w, Collaboration>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w> WidgetSystem<Tile> for ViewCollaboration<'w> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Collaboration")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w> ViewCollaboration<'w> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let active = self.collaboration.is_active();
+        Grid::new("collaboration").num_columns(2).show(ui, |ui| {
+            ui.label("Relay");
+            ui.add_enabled(
+                !active,
+                TextEdit::singleline(&mut self.collaboration.relay_url),
+            );
+            ui.end_row();
+
+            ui.label("Name")
+                .on_hover_text("Every editor connected to the relay needs a different name");
+            ui.add_enabled(
+                !active,
+                TextEdit::singleline(&mut self.collaboration.author),
+            );
+            ui.end_row();
+        });
+
+        match &self.collaboration.status {
+            CollaborationStatus::Disconnected => ui.label("Not connected"),
+            CollaborationStatus::Connecting => ui.label("Connecting..."),
+            CollaborationStatus::Connected => ui.label("Connected"),
+            CollaborationStatus::Failed(reason) => ui.label(format!("Disconnected: {reason}")),
+        };
+
+        if active {
+            if ui.button("Disconnect").clicked() {
+                self.collaboration.disconnect();
+            }
+        } else if ui
+            .button("Connect")
+            .on_hover_text(
+                "Every editor must open the same version of the site before connecting. \
+                Moving anchors, renaming, changing poses, and deleting are shared, \
+                but new elements are not.",
+            )
+            .clicked()
+        {
+            self.collaboration.connect();
+        }
+    }
+}
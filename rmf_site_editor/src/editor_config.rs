@@ -0,0 +1,222 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+//! State of the editor that is remembered between sessions, such as the
+//! files that were opened recently and the layout of the window. Unlike
+//! [`EditorSettings`](crate::settings::EditorSettings), this is not meant to
+//! be edited by hand, so the file is only ever written by the editor.
+
+use crate::site::DefaultFile;
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowMoved, WindowPosition, WindowResized},
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// How many files are kept in the list of recent files
+pub const MAX_RECENT_FILES: usize = 10;
+
+/// How long to wait after a change before writing the config file, so that
+/// resizing the window does not write the file on every frame.
+const WRITE_DELAY_SECS: f32 = 1.0;
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct EditorConfig {
+    /// Files that were opened or saved recently, most recent first
+    pub recent_files: Vec<PathBuf>,
+    /// The folder that file dialogs start in
+    pub last_open_dir: Option<PathBuf>,
+    pub window: Option<WindowLayout>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WindowLayout {
+    /// Logical size of the window
+    pub width: f32,
+    pub height: f32,
+    /// Physical position of the top left corner of the window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<[i32; 2]>,
+}
+
+impl EditorConfig {
+    /// Move a file to the top of the list of recent files and start file
+    /// dialogs in its folder from now on.
+    pub fn add_recent_file(&mut self, path: &Path) {
+        self.recent_files.retain(|p| p != path);
+        self.recent_files.insert(0, path.to_owned());
+        self.recent_files.truncate(MAX_RECENT_FILES);
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            self.last_open_dir = Some(parent.to_owned());
+        }
+    }
+
+    pub fn remove_recent_file(&mut self, path: &Path) {
+        self.recent_files.retain(|p| p != path);
+    }
+}
+
+/// Keeps track of where the config is stored and whether it needs to be
+/// written.
+#[derive(Resource, Debug)]
+pub struct EditorConfigFile {
+    pub path: Option<PathBuf>,
+    dirty: bool,
+    timer: Timer,
+}
+
+impl Default for EditorConfigFile {
+    fn default() -> Self {
+        let path = dirs::config_dir().map(|mut p| {
+            p.push("open-robotics");
+            p.push("rmf_site_editor");
+            p.push("editor_config.yaml");
+            p
+        });
+        Self {
+            path,
+            dirty: false,
+            timer: Timer::from_seconds(WRITE_DELAY_SECS, TimerMode::Once),
+        }
+    }
+}
+
+impl EditorConfigFile {
+    fn read(&self) -> Option<Result<EditorConfig, String>> {
+        let path = self.path.as_ref()?;
+        let data = std::fs::read(path).ok()?;
+        Some(serde_yaml::from_slice(&data).map_err(|err| err.to_string()))
+    }
+
+    fn write(&self, config: &EditorConfig) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Err("there is no configuration folder".to_owned());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let contents = serde_yaml::to_string(config).map_err(|err| err.to_string())?;
+        std::fs::write(path, contents).map_err(|err| err.to_string())
+    }
+}
+
+pub struct EditorConfigPlugin;
+
+impl Plugin for EditorConfigPlugin {
+    fn build(&self, app: &mut App) {
+        let config_file = EditorConfigFile::default();
+        let config = match config_file.read() {
+            Some(Ok(config)) => config,
+            Some(Err(err)) => {
+                warn!("Unable to parse the editor config file, starting fresh: {err}");
+                EditorConfig::default()
+            }
+            None => EditorConfig::default(),
+        };
+
+        app.insert_resource(config)
+            .insert_resource(config_file)
+            .add_systems(Startup, restore_window_layout)
+            .add_systems(
+                Update,
+                (
+                    record_recent_files,
+                    record_window_layout,
+                    write_editor_config,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn restore_window_layout(
+    config: Res<EditorConfig>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Some(layout) = &config.window else {
+        return;
+    };
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+    window.resolution.set(layout.width, layout.height);
+    if let Some([x, y]) = layout.position {
+        window.position = WindowPosition::At(IVec2::new(x, y));
+    }
+}
+
+fn record_recent_files(
+    mut config: ResMut<EditorConfig>,
+    default_files: Query<&DefaultFile, Changed<DefaultFile>>,
+) {
+    for file in &default_files {
+        if config.recent_files.first() != Some(&file.0) {
+            config.add_recent_file(&file.0);
+        }
+    }
+}
+
+fn record_window_layout(
+    mut config: ResMut<EditorConfig>,
+    mut resized: EventReader<WindowResized>,
+    mut moved: EventReader<WindowMoved>,
+    windows: Query<(Entity, &Window), With<PrimaryWindow>>,
+) {
+    let Ok((primary, window)) = windows.get_single() else {
+        resized.clear();
+        moved.clear();
+        return;
+    };
+    let resized = resized.read().any(|r| r.window == primary);
+    let moved_to = moved
+        .read()
+        .filter(|m| m.entity == primary)
+        .last()
+        .map(|m| [m.position.x, m.position.y]);
+    if !resized && moved_to.is_none() {
+        return;
+    }
+    let position = moved_to.or_else(|| config.window.as_ref().and_then(|w| w.position));
+    let layout = WindowLayout {
+        width: window.width(),
+        height: window.height(),
+        position,
+    };
+    if config.window.as_ref() != Some(&layout) {
+        config.window = Some(layout);
+    }
+}
+
+fn write_editor_config(
+    time: Res<Time>,
+    config: Res<EditorConfig>,
+    mut config_file: ResMut<EditorConfigFile>,
+) {
+    if config.is_changed() && !config.is_added() {
+        config_file.dirty = true;
+        config_file.timer.reset();
+    }
+    if !config_file.dirty || !config_file.timer.tick(time.delta()).finished() {
+        return;
+    }
+    config_file.dirty = false;
+    if let Err(err) = config_file.write(&config) {
+        warn!("Unable to save the editor config: {err}");
+    }
+}
@@ -33,6 +33,8 @@ pub struct InteractionAssets {
     pub camera_control_orbit_material: Handle<StandardMaterial>,
     pub camera_control_pan_material: Handle<StandardMaterial>,
     pub arrow_mesh: Handle<Mesh>,
+    pub rotation_ring_mesh: Handle<Mesh>,
+    pub scale_handle_mesh: Handle<Mesh>,
     pub point_light_socket_mesh: Handle<Mesh>,
     pub point_light_shine_mesh: Handle<Mesh>,
     pub spot_light_cover_mesh: Handle<Mesh>,
@@ -45,6 +47,7 @@ pub struct InteractionAssets {
     pub y_axis_materials: GizmoMaterialSet,
     pub z_axis_materials: GizmoMaterialSet,
     pub z_plane_materials: GizmoMaterialSet,
+    pub scale_materials: GizmoMaterialSet,
     pub lift_doormat_available_materials: GizmoMaterialSet,
     pub lift_doormat_unavailable_materials: GizmoMaterialSet,
     pub centimeter_finite_grid: Vec<(Handle<Polyline>, Handle<PolylineMaterial>)>,
@@ -217,6 +220,65 @@ impl InteractionAssets {
         cue.drag = Some(drag_parent);
     }
 
+    /// Add arrows for moving a model along its own X and Y axes, a ring for
+    /// rotating it about Z, and a handle for scaling it uniformly.
+    pub fn add_model_gizmos(&self, commands: &mut Commands, model: Entity) -> Entity {
+        let drag_parent = commands
+            .spawn(SpatialBundle::default())
+            .insert(ModelGizmoRoot)
+            .insert(VisualCue::no_outline().irregular().always_xray())
+            .set_parent(model)
+            .id();
+
+        let scale = 0.5;
+        let offset = 1.2;
+        for (m, p, r) in [
+            (
+                self.x_axis_materials.clone(),
+                Vec3::new(offset, 0., 0.),
+                Quat::from_rotation_y(90_f32.to_radians()),
+            ),
+            (
+                self.x_axis_materials.clone(),
+                Vec3::new(-offset, 0., 0.),
+                Quat::from_rotation_y(-90_f32.to_radians()),
+            ),
+            (
+                self.y_axis_materials.clone(),
+                Vec3::new(0., offset, 0.),
+                Quat::from_rotation_x(-90_f32.to_radians()),
+            ),
+            (
+                self.y_axis_materials.clone(),
+                Vec3::new(0., -offset, 0.),
+                Quat::from_rotation_x(90_f32.to_radians()),
+            ),
+        ] {
+            self.make_draggable_axis(commands, model, drag_parent, m, p, r, scale);
+        }
+
+        commands
+            .spawn(PbrBundle {
+                mesh: self.rotation_ring_mesh.clone(),
+                material: self.z_axis_materials.passive.clone(),
+                ..default()
+            })
+            .insert(DragRotationBundle::new(model).with_materials(self.z_axis_materials.clone()))
+            .set_parent(drag_parent);
+
+        commands
+            .spawn(PbrBundle {
+                mesh: self.scale_handle_mesh.clone(),
+                material: self.scale_materials.passive.clone(),
+                transform: Transform::from_xyz(0.75, 0.75, 0.),
+                ..default()
+            })
+            .insert(DragScaleBundle::new(model).with_materials(self.scale_materials.clone()))
+            .set_parent(drag_parent);
+
+        drag_parent
+    }
+
     pub fn lift_doormat_materials(&self, available: bool) -> GizmoMaterialSet {
         if available {
             self.lift_doormat_available_materials.clone()
@@ -236,6 +298,8 @@ impl FromWorld for InteractionAssets {
             ..Default::default()
         }));
         let arrow_mesh = meshes.add(make_cylinder_arrow_mesh());
+        let rotation_ring_mesh = meshes.add(make_ring(0.9, 1.0, 64).into());
+        let scale_handle_mesh = meshes.add(make_box(0.12, 0.12, 0.12).into());
         let point_light_socket_mesh = meshes.add(
             make_cylinder(0.06, 0.02)
                 .transform_by(Affine3A::from_translation(0.04 * Vec3::Z))
@@ -345,6 +409,7 @@ impl FromWorld for InteractionAssets {
         let y_axis_materials = GizmoMaterialSet::make_y_axis(&mut materials);
         let z_axis_materials = GizmoMaterialSet::make_z_axis(&mut materials);
         let z_plane_materials = GizmoMaterialSet::make_z_plane(&mut materials);
+        let scale_materials = GizmoMaterialSet::make_scale(&mut materials);
         let lift_doormat_available_materials = GizmoMaterialSet {
             passive: materials.add(StandardMaterial {
                 base_color: Color::rgba(0.1, 0.9, 0.1, 0.1),
@@ -430,6 +495,8 @@ impl FromWorld for InteractionAssets {
             camera_control_orbit_material,
             camera_control_pan_material,
             arrow_mesh,
+            rotation_ring_mesh,
+            scale_handle_mesh,
             point_light_socket_mesh,
             point_light_shine_mesh,
             spot_light_cover_mesh,
@@ -442,6 +509,7 @@ impl FromWorld for InteractionAssets {
             y_axis_materials,
             z_axis_materials,
             z_plane_materials,
+            scale_materials,
             lift_doormat_available_materials,
             lift_doormat_unavailable_materials,
             centimeter_finite_grid,
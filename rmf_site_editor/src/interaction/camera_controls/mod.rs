@@ -14,7 +14,11 @@
  * limitations under the License.
  *
 */
-use crate::interaction::{InteractionAssets, PickingBlockers};
+use crate::{
+    interaction::{InteractionAssets, PickingBlockers},
+    settings::{EditorSettings, SaveEditorSettings},
+    site::WalkthroughMode,
+};
 use bevy::{
     core_pipeline::{
         clear_color::ClearColorConfig, core_3d::Camera3dBundle, tonemapping::Tonemapping,
@@ -35,6 +39,9 @@ use cursor::{update_cursor_command, CursorCommand};
 mod keyboard;
 use keyboard::{update_keyboard_command, KeyboardCommand};
 
+mod transition;
+use transition::ProjectionTransition;
+
 /// RenderLayers are used to inform cameras which entities they should render.
 /// The General render layer is for things that should be visible to all
 /// cameras.
@@ -100,18 +107,44 @@ impl ProjectionMode {
 }
 
 #[derive(Event)]
-pub struct ChangeProjectionMode(pub ProjectionMode);
+pub struct ChangeProjectionMode {
+    pub mode: ProjectionMode,
+    /// True if the user asked for this projection. The change is animated
+    /// and the projection is remembered for the next session. Tools that
+    /// need a certain projection should set this to false so that the camera
+    /// switches right away and the choice of the user is left alone.
+    pub user_choice: bool,
+}
 
 impl ChangeProjectionMode {
     pub fn to_perspective() -> ChangeProjectionMode {
-        ChangeProjectionMode(ProjectionMode::Perspective)
+        ChangeProjectionMode {
+            mode: ProjectionMode::Perspective,
+            user_choice: true,
+        }
     }
 
     pub fn to_orthographic() -> ChangeProjectionMode {
-        ChangeProjectionMode(ProjectionMode::Orthographic)
+        ChangeProjectionMode {
+            mode: ProjectionMode::Orthographic,
+            user_choice: true,
+        }
+    }
+
+    pub fn for_tool(self) -> ChangeProjectionMode {
+        ChangeProjectionMode {
+            user_choice: false,
+            ..self
+        }
     }
 }
 
+/// Send this event to move the active camera so that it looks at a point of
+/// the site without changing its orientation or zoom. The point also becomes
+/// the orbit center of the perspective camera.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct FocusCamera(pub Vec3);
+
 #[derive(Debug, Clone, Reflect, Resource)]
 pub struct CameraControls {
     mode: ProjectionMode,
@@ -414,20 +447,89 @@ fn camera_controls(
     headlight_toggle: Res<HeadlightToggle>,
     picking_blockers: Res<PickingBlockers>,
     mut change_mode: EventReader<ChangeProjectionMode>,
+    mut transition: Local<Option<ProjectionTransition>>,
+    time: Res<Time>,
+    mut settings: ResMut<EditorSettings>,
+    mut save_settings: EventWriter<SaveEditorSettings>,
+    walkthrough: Res<WalkthroughMode>,
 ) {
-    if let Some(mode) = change_mode.read().last() {
-        controls.use_mode(
-            mode.0,
-            &mut bevy_cameras,
-            &mut visibility,
-            headlight_toggle.0,
-        );
+    if let Some(change) = change_mode.read().last() {
+        // A new request always starts from a settled camera
+        if let Some(previous) = transition.take() {
+            finish_transition(
+                &previous,
+                &mut controls,
+                &mut cameras,
+                &mut bevy_cameras,
+                &mut visibility,
+                headlight_toggle.0,
+            );
+        }
+
+        if change.user_choice {
+            let orthographic = change.mode.is_orthographic();
+            if settings.camera.orthographic != orthographic {
+                settings.camera.orthographic = orthographic;
+                save_settings.send(SaveEditorSettings);
+            }
+        }
+
+        let duration = settings.camera.transition_duration;
+        if change.user_choice && duration > 0.0 && change.mode != controls.mode() {
+            *transition = start_transition(
+                change.mode,
+                duration,
+                &mut controls,
+                &mut cameras,
+                &mut bevy_cameras,
+                &mut visibility,
+                headlight_toggle.0,
+            );
+        } else {
+            controls.use_mode(
+                change.mode,
+                &mut bevy_cameras,
+                &mut visibility,
+                headlight_toggle.0,
+            );
+        }
     }
 
     if headlight_toggle.is_changed() {
         controls.toggle_lights(headlight_toggle.0, &mut visibility);
     }
 
+    if walkthrough.active {
+        // The walkthrough moves the perspective camera on its own
+        drop_camera_inputs(&mut cursor_command, &mut keyboard_command);
+        return;
+    }
+
+    if let Some(current) = transition.as_mut() {
+        // Camera inputs are dropped while the animation is playing so they
+        // do not pile up and get applied all at once afterwards.
+        drop_camera_inputs(&mut cursor_command, &mut keyboard_command);
+
+        let tf = current.advance(time.delta_seconds());
+        if let Ok((_, mut persp_tf)) = cameras.get_mut(controls.perspective_camera_entities[0]) {
+            *persp_tf = tf;
+        }
+
+        if current.is_finished() {
+            if let Some(finished) = transition.take() {
+                finish_transition(
+                    &finished,
+                    &mut controls,
+                    &mut cameras,
+                    &mut bevy_cameras,
+                    &mut visibility,
+                    headlight_toggle.0,
+                );
+            }
+        }
+        return;
+    }
+
     // give input priority to ui elements
     if picking_blockers.ui {
         return;
@@ -495,6 +597,139 @@ fn camera_controls(
     }
 }
 
+fn drop_camera_inputs(cursor_command: &mut CursorCommand, keyboard_command: &mut KeyboardCommand) {
+    cursor_command.take_translation_delta();
+    cursor_command.take_rotation_delta();
+    cursor_command.take_fov_delta();
+    cursor_command.take_scale_delta();
+    keyboard_command.take_translation_delta();
+    keyboard_command.take_rotation_delta();
+    keyboard_command.take_fov_delta();
+    keyboard_command.take_scale_delta();
+}
+
+/// Begin animating the perspective camera towards the requested projection.
+/// Returns None if there is nothing to animate, in which case the projection
+/// is changed right away.
+fn start_transition(
+    mode: ProjectionMode,
+    duration: f32,
+    controls: &mut CameraControls,
+    cameras: &mut Query<(&mut Projection, &mut Transform)>,
+    bevy_cameras: &mut Query<&mut Camera>,
+    visibility: &mut Query<&mut Visibility>,
+    headlights_on: bool,
+) -> Option<ProjectionTransition> {
+    let persp = cameras
+        .get(controls.perspective_camera_entities[0])
+        .ok()
+        .and_then(|(proj, tf)| match proj {
+            Projection::Perspective(proj) => Some((proj.fov, *tf)),
+            _ => None,
+        });
+    let ortho = cameras
+        .get(controls.orthographic_camera_entities[0])
+        .ok()
+        .and_then(|(proj, tf)| match proj {
+            Projection::Orthographic(proj) => Some((proj.scale, *tf)),
+            _ => None,
+        });
+    let (Some((fov, persp_tf)), Some((scale, ortho_tf))) = (persp, ortho) else {
+        controls.use_mode(mode, bevy_cameras, visibility, headlights_on);
+        return None;
+    };
+
+    match mode {
+        ProjectionMode::Perspective => {
+            let (transition, start) =
+                ProjectionTransition::to_perspective(&ortho_tf, scale, fov, duration);
+            if let Ok((_, mut tf)) = cameras.get_mut(controls.perspective_camera_entities[0]) {
+                *tf = start;
+            }
+            controls.use_mode(mode, bevy_cameras, visibility, headlights_on);
+            Some(transition)
+        }
+        ProjectionMode::Orthographic => Some(ProjectionTransition::to_orthographic(
+            &persp_tf, &ortho_tf, fov, duration,
+        )),
+    }
+}
+
+/// Put the cameras where the transition ends and activate the camera of the
+/// projection that it was heading towards.
+fn finish_transition(
+    transition: &ProjectionTransition,
+    controls: &mut CameraControls,
+    cameras: &mut Query<(&mut Projection, &mut Transform)>,
+    bevy_cameras: &mut Query<&mut Camera>,
+    visibility: &mut Query<&mut Visibility>,
+    headlights_on: bool,
+) {
+    if let Ok((_, mut tf)) = cameras.get_mut(controls.perspective_camera_entities[0]) {
+        *tf = transition.end();
+    }
+
+    if let Some((ortho_tf, scale)) = transition.orthographic() {
+        if let Ok((mut proj, mut tf)) = cameras.get_mut(controls.orthographic_camera_entities[0]) {
+            *tf = ortho_tf;
+            if let Projection::Orthographic(proj) = proj.as_mut() {
+                proj.scale = scale;
+            }
+            let proj = proj.clone();
+            for child in &controls.orthographic_camera_entities[1..] {
+                if let Ok((mut child_proj, _)) = cameras.get_mut(*child) {
+                    *child_proj = proj.clone();
+                }
+            }
+        }
+    }
+
+    controls.use_mode(transition.target, bevy_cameras, visibility, headlights_on);
+}
+
+/// Start the editor with the projection that the user chose last time.
+fn apply_saved_projection(
+    settings: Res<EditorSettings>,
+    mut controls: ResMut<CameraControls>,
+    mut bevy_cameras: Query<&mut Camera>,
+    mut visibility: Query<&mut Visibility>,
+    headlight_toggle: Res<HeadlightToggle>,
+) {
+    if settings.camera.orthographic {
+        controls.use_mode(
+            ProjectionMode::Orthographic,
+            &mut bevy_cameras,
+            &mut visibility,
+            headlight_toggle.0,
+        );
+    }
+}
+
+fn focus_camera(
+    mut requests: EventReader<FocusCamera>,
+    mut controls: ResMut<CameraControls>,
+    mut transforms: Query<&mut Transform, With<Projection>>,
+) {
+    let Some(FocusCamera(target)) = requests.read().last().copied() else {
+        return;
+    };
+
+    let Ok(mut tf) = transforms.get_mut(controls.active_camera()) else {
+        return;
+    };
+    let forward = tf.forward();
+    if forward.z < -1e-3 {
+        // Slide the camera parallel to the plane of the target so that the
+        // distance to what it is looking at stays the same.
+        let t = (target.z - tf.translation.z) / forward.z;
+        let looking_at = tf.translation + t * forward;
+        tf.translation += target - looking_at;
+    } else {
+        tf.translation = target - MIN_SELECTION_DIST * forward;
+    }
+    controls.orbit_center = Some(target);
+}
+
 fn update_orbit_center_marker(
     controls: Res<CameraControls>,
     keyboard_command: Res<KeyboardCommand>,
@@ -547,6 +782,7 @@ impl Plugin for CameraControlsPlugin {
             .init_resource::<KeyboardCommand>()
             .init_resource::<HeadlightToggle>()
             .add_event::<ChangeProjectionMode>()
+            .add_event::<FocusCamera>()
             .add_systems(
                 Update,
                 (
@@ -556,6 +792,7 @@ impl Plugin for CameraControlsPlugin {
                 )
                     .chain(),
             )
-            .add_systems(Update, update_orbit_center_marker);
+            .add_systems(Startup, apply_saved_projection)
+            .add_systems(Update, (focus_camera, update_orbit_center_marker));
     }
 }
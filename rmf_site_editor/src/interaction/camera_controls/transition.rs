@@ -0,0 +1,169 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use super::{ProjectionMode, MAX_SCALE, MIN_SCALE};
+use bevy::prelude::*;
+
+/// How far above the ground the perspective camera looks down after
+/// switching out of the orthographic view.
+const PERSPECTIVE_PITCH: f32 = std::f32::consts::FRAC_PI_4;
+
+/// How far in front of the camera the view is centered when the perspective
+/// camera is not looking at the ground.
+const FALLBACK_DISTANCE: f32 = 10.0;
+
+/// An animation of the perspective camera that moves between a top-down view
+/// that lines up with the orthographic camera and a regular perspective view.
+#[derive(Debug, Clone)]
+pub(super) struct ProjectionTransition {
+    pub(super) target: ProjectionMode,
+    from: Transform,
+    to: Transform,
+    elapsed: f32,
+    duration: f32,
+    /// The transform and scale that the orthographic camera is given once an
+    /// animation towards the orthographic view is finished.
+    orthographic: Option<(Transform, f32)>,
+}
+
+impl ProjectionTransition {
+    /// Start moving the perspective camera away from the view of the
+    /// orthographic camera. The perspective camera should be activated right
+    /// away and placed at the returned starting transform.
+    pub(super) fn to_perspective(
+        ortho_tf: &Transform,
+        ortho_scale: f32,
+        fov: f32,
+        duration: f32,
+    ) -> (Self, Transform) {
+        let center = Vec3::new(ortho_tf.translation.x, ortho_tf.translation.y, 0.0);
+        let distance = top_down_distance(ortho_scale, fov);
+        let from = Transform {
+            translation: center + distance * Vec3::Z,
+            rotation: ortho_tf.rotation,
+            ..default()
+        };
+
+        let heading = ground_direction(ortho_tf.up()).unwrap_or(Vec3::Y);
+        let to = Transform::from_translation(
+            center - distance * PERSPECTIVE_PITCH.cos() * heading
+                + distance * PERSPECTIVE_PITCH.sin() * Vec3::Z,
+        )
+        .looking_at(center, Vec3::Z);
+
+        let transition = Self {
+            target: ProjectionMode::Perspective,
+            from,
+            to,
+            elapsed: 0.0,
+            duration,
+            orthographic: None,
+        };
+        (transition, from)
+    }
+
+    /// Start moving the perspective camera into a top-down view above the
+    /// point that it is looking at. The orthographic camera takes over once
+    /// the animation is finished.
+    pub(super) fn to_orthographic(
+        persp_tf: &Transform,
+        ortho_tf: &Transform,
+        fov: f32,
+        duration: f32,
+    ) -> Self {
+        let forward = persp_tf.forward();
+        let center = if forward.z < -1e-3 {
+            let t = -persp_tf.translation.z / forward.z;
+            persp_tf.translation + t * forward
+        } else {
+            let ahead = ground_direction(forward).unwrap_or(Vec3::Y);
+            let p = persp_tf.translation + FALLBACK_DISTANCE * ahead;
+            Vec3::new(p.x, p.y, 0.0)
+        };
+        let distance = (persp_tf.translation - center).length().max(1.0);
+        let heading = ground_direction(forward)
+            .or_else(|| ground_direction(persp_tf.up()))
+            .unwrap_or(Vec3::Y);
+
+        let to =
+            Transform::from_translation(center + distance * Vec3::Z).looking_at(center, heading);
+        let ortho_final = Transform::from_xyz(center.x, center.y, ortho_tf.translation.z)
+            .looking_at(center, heading);
+        let scale = (2.0 * distance * (fov / 2.0).tan()).clamp(MIN_SCALE, MAX_SCALE);
+
+        Self {
+            target: ProjectionMode::Orthographic,
+            from: *persp_tf,
+            to,
+            elapsed: 0.0,
+            duration,
+            orthographic: Some((ortho_final, scale)),
+        }
+    }
+
+    /// Move the animation forward and get the transform that the perspective
+    /// camera should have now.
+    pub(super) fn advance(&mut self, delta: f32) -> Transform {
+        self.elapsed += delta;
+        self.current()
+    }
+
+    pub(super) fn current(&self) -> Transform {
+        let t = smoothstep(self.fraction());
+        Transform {
+            translation: self.from.translation.lerp(self.to.translation, t),
+            rotation: self.from.rotation.slerp(self.to.rotation, t),
+            scale: self.from.scale,
+        }
+    }
+
+    /// The final transform of the perspective camera.
+    pub(super) fn end(&self) -> Transform {
+        self.to
+    }
+
+    /// The orthographic view that takes over at the end of the animation, if
+    /// the animation is heading towards the orthographic view.
+    pub(super) fn orthographic(&self) -> Option<(Transform, f32)> {
+        self.orthographic
+    }
+
+    pub(super) fn is_finished(&self) -> bool {
+        self.fraction() >= 1.0
+    }
+
+    fn fraction(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        (self.elapsed / self.duration).clamp(0.0, 1.0)
+    }
+}
+
+/// How far a perspective camera needs to be from the ground to see as much of
+/// it as an orthographic camera with the given scale.
+fn top_down_distance(ortho_scale: f32, fov: f32) -> f32 {
+    (ortho_scale / 2.0) / (fov / 2.0).tan()
+}
+
+fn ground_direction(v: Vec3) -> Option<Vec3> {
+    Vec3::new(v.x, v.y, 0.0).try_normalize()
+}
+
+fn smoothstep(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
@@ -0,0 +1,294 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::Select,
+    site::{
+        Anchor, Category, Change, ChangeDependent, Delete, Dependents, Edge, HumanLaneMarker,
+        JoinLanes, LaneMarker, Location, LocationTag, LocationTags, NameInSite, NameOfSite, Point,
+        ReverseLanes, SetHumanLanes, SplitLane,
+    },
+};
+use bevy::{
+    ecs::{system::Command, world::EntityRef},
+    prelude::*,
+};
+
+/// What an [`EntityAction`] gets applied to.
+#[derive(Debug, Clone, Copy)]
+pub struct ActionTarget {
+    pub entity: Entity,
+    /// The point on the ground where the user asked for the action, if the
+    /// request came from the scene.
+    pub position: Option<Vec3>,
+}
+
+impl ActionTarget {
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            position: None,
+        }
+    }
+
+    pub fn at(mut self, position: Vec3) -> Self {
+        self.position = Some(position);
+        self
+    }
+}
+
+/// An operation that can be applied to a single entity. Actions are offered
+/// in the context menu of an entity, and any other part of the editor can
+/// trigger them with [`RunEntityAction`].
+#[derive(Debug, Clone, Copy)]
+pub struct EntityAction {
+    /// Identifies the action and is shown to the user
+    pub label: &'static str,
+    pub applies_to: fn(EntityRef) -> bool,
+    pub run: fn(&mut World, ActionTarget),
+}
+
+/// Every action that has been added, in the order they are offered.
+#[derive(Default, Resource)]
+pub struct EntityActions {
+    actions: Vec<EntityAction>,
+}
+
+impl EntityActions {
+    pub fn iter(&self) -> impl Iterator<Item = &EntityAction> {
+        self.actions.iter()
+    }
+
+    pub fn get(&self, label: &str) -> Option<&EntityAction> {
+        self.actions.iter().find(|action| action.label == label)
+    }
+
+    /// The actions that can be applied to an entity.
+    pub fn available_for(&self, world: &World, entity: Entity) -> Vec<EntityAction> {
+        let Some(entity_ref) = world.get_entity(entity) else {
+            return Vec::new();
+        };
+        self.actions
+            .iter()
+            .filter(|action| (action.applies_to)(entity_ref))
+            .copied()
+            .collect()
+    }
+}
+
+pub trait EntityActionExt {
+    fn add_entity_action(&mut self, action: EntityAction) -> &mut Self;
+}
+
+impl EntityActionExt for App {
+    fn add_entity_action(&mut self, action: EntityAction) -> &mut Self {
+        let mut actions = self
+            .world
+            .get_resource_or_insert_with(EntityActions::default);
+        if let Some(existing) = actions.actions.iter_mut().find(|a| a.label == action.label) {
+            *existing = action;
+        } else {
+            actions.actions.push(action);
+        }
+        self
+    }
+}
+
+/// Send this event to apply the action with the given label.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct RunEntityAction {
+    pub label: &'static str,
+    pub target: ActionTarget,
+}
+
+#[derive(Default)]
+pub struct EntityActionPlugin {}
+
+impl Plugin for EntityActionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EntityActions>()
+            .add_event::<RunEntityAction>()
+            .add_systems(Update, run_entity_actions)
+            .add_entity_action(EntityAction {
+                label: "Properties",
+                applies_to: |e| e.contains::<Category>() || e.contains::<Anchor>(),
+                run: |world, target| {
+                    world.send_event(Select::new(Some(target.entity)));
+                },
+            })
+            .add_entity_action(EntityAction {
+                label: "Delete",
+                applies_to: |e| {
+                    (e.contains::<Category>() || e.contains::<Anchor>())
+                        && !e.contains::<NameOfSite>()
+                },
+                run: |world, target| {
+                    world.send_event(Delete::new(target.entity));
+                },
+            })
+            .add_entity_action(EntityAction {
+                label: "Convert to Human Lane",
+                applies_to: |e| e.contains::<LaneMarker>() && !e.contains::<HumanLaneMarker>(),
+                run: |world, target| {
+                    world.send_event(SetHumanLanes {
+                        lanes: vec![target.entity],
+                        human: true,
+                    });
+                },
+            })
+            .add_entity_action(EntityAction {
+                label: "Convert to Robot Lane",
+                applies_to: |e| e.contains::<LaneMarker>() && e.contains::<HumanLaneMarker>(),
+                run: |world, target| {
+                    world.send_event(SetHumanLanes {
+                        lanes: vec![target.entity],
+                        human: false,
+                    });
+                },
+            })
+            .add_entity_action(EntityAction {
+                label: "Reverse Lane",
+                applies_to: |e| e.contains::<LaneMarker>(),
+                run: |world, target| {
+                    world.send_event(ReverseLanes {
+                        lanes: vec![target.entity],
+                    });
+                },
+            })
+            .add_entity_action(EntityAction {
+                label: "Split Lane Here",
+                applies_to: |e| e.contains::<LaneMarker>(),
+                run: split_lane,
+            })
+            .add_entity_action(EntityAction {
+                label: "Join Lanes",
+                applies_to: |e| {
+                    e.contains::<Anchor>() && e.get::<Dependents>().is_some_and(|d| d.len() == 2)
+                },
+                run: |world, target| {
+                    world.send_event(JoinLanes::new(target.entity));
+                },
+            })
+            .add_entity_action(EntityAction {
+                label: "Set as Charger",
+                applies_to: |e| {
+                    e.contains::<Anchor>()
+                        || e.get::<LocationTags>()
+                            .is_some_and(|tags| !tags.contains(&LocationTag::Charger))
+                },
+                run: set_as_charger,
+            });
+    }
+}
+
+fn run_entity_actions(world: &mut World) {
+    let requests: Vec<RunEntityAction> = world
+        .resource_mut::<Events<RunEntityAction>>()
+        .drain()
+        .collect();
+    for request in requests {
+        let Some(action) = world
+            .resource::<EntityActions>()
+            .get(request.label)
+            .copied()
+        else {
+            warn!("There is no action named [{}]", request.label);
+            continue;
+        };
+        let applies = world
+            .get_entity(request.target.entity)
+            .is_some_and(|e| (action.applies_to)(e));
+        if !applies {
+            warn!(
+                "Unable to {} for {:?}",
+                action.label.to_lowercase(),
+                request.target.entity
+            );
+            continue;
+        }
+        (action.run)(world, request.target);
+    }
+}
+
+/// Split a lane where the user clicked on it, or in the middle if the action
+/// was not requested from the scene.
+fn split_lane(world: &mut World, target: ActionTarget) {
+    let at = match target.position {
+        Some(p) => p.truncate(),
+        None => {
+            let Some(edge) = world.get::<Edge<Entity>>(target.entity) else {
+                return;
+            };
+            let position = |e: Entity| {
+                world
+                    .get::<GlobalTransform>(e)
+                    .map(|tf| tf.translation().truncate())
+            };
+            let (Some(p0), Some(p1)) = (position(edge.start()), position(edge.end())) else {
+                return;
+            };
+            (p0 + p1) / 2.0
+        }
+    };
+    world.send_event(SplitLane {
+        lane: target.entity,
+        at,
+    });
+}
+
+/// Tag a location as a charger. Anchors become chargers by tagging the
+/// location that is on them, or by adding a new location if they have none.
+fn set_as_charger(world: &mut World, target: ActionTarget) {
+    let location = if world.get::<LocationTags>(target.entity).is_some() {
+        Some(target.entity)
+    } else {
+        world
+            .get::<Dependents>(target.entity)
+            .and_then(|deps| {
+                deps.iter()
+                    .find(|dep| world.get::<LocationTags>(**dep).is_some())
+            })
+            .copied()
+    };
+
+    if let Some(location) = location {
+        let Some(tags) = world.get::<LocationTags>(location) else {
+            return;
+        };
+        if !tags.contains(&LocationTag::Charger) {
+            let mut tags = tags.clone();
+            tags.push(LocationTag::Charger);
+            world.send_event(Change::new(tags, location));
+        }
+        return;
+    }
+
+    let mut names = world.query::<&NameInSite>();
+    let taken: Vec<String> = names.iter(world).map(|n| n.0.clone()).collect();
+    let name = (1..)
+        .map(|n| format!("charger_{n}"))
+        .find(|name| !taken.contains(name))
+        .unwrap_or_default();
+
+    let anchor = target.entity;
+    let mut location = Location::from(Point(anchor));
+    location.name = NameInSite(name);
+    location.tags = LocationTags(vec![LocationTag::Charger]);
+    // Orphaned locations get assigned to the current site automatically
+    let e = world.spawn(location).id();
+    ChangeDependent::add(anchor, e).apply(world);
+}
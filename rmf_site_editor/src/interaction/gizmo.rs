@@ -76,6 +76,14 @@ impl GizmoMaterialSet {
         }
     }
 
+    pub fn make_scale(materials: &mut Mut<Assets<StandardMaterial>>) -> Self {
+        Self {
+            passive: materials.add(Color::rgb(0.9, 0.7, 0.).into()),
+            hover: materials.add(Color::rgb(1.0, 0.85, 0.4).into()),
+            drag: materials.add(Color::rgb(0.6, 0.45, 0.).into()),
+        }
+    }
+
     pub fn make_z_plane(materials: &mut Mut<Assets<StandardMaterial>>) -> Self {
         Self {
             passive: materials.add(Color::rgba(0., 0., 1., 0.6).into()),
@@ -401,6 +409,7 @@ pub fn update_drag_motions(
     mut cursor_motion: EventReader<CursorMoved>,
     mut move_to: EventWriter<MoveTo>,
     primary_window: Query<&Window, With<PrimaryWindow>>,
+    snapping: Snapping,
 ) {
     if let GizmoState::Dragging(dragging) = *drag_state {
         let cursor_position = match cursor_motion.read().last() {
@@ -489,9 +498,11 @@ pub fn update_drag_motions(
 
                 let t = (initial.click_point - ray.origin()).dot(n_p) / denom;
                 let delta = ray.position(t) - initial.click_point;
-                let tf_goal = initial
-                    .tf_for_entity_global
-                    .with_translation(initial.tf_for_entity_global.translation + delta);
+                let mut goal = initial.tf_for_entity_global.translation + delta;
+                if snapping.is_anchor(draggable.for_entity) {
+                    goal = snapping.snap_position(goal);
+                }
+                let tf_goal = initial.tf_for_entity_global.with_translation(goal);
                 move_to.send(MoveTo {
                     entity: draggable.for_entity,
                     transform: Transform::from_matrix(
@@ -0,0 +1,285 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{
+        CurrentEditDrawing, InteractionState, IntersectGroundPlaneParams, PickingBlockers,
+        SelectionBlockers, Snapping,
+    },
+    settings::EditorSettings,
+    site::*,
+    widgets::CanvasTooltips,
+};
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::{Input as UserInput, *},
+};
+use rmf_site_format::{Distance, Edge, Measurement, PixelsPerMeter, WallMarker};
+use std::borrow::Cow;
+
+/// Points that are closer than this to a wall get snapped onto the wall.
+pub const MEASURE_WALL_SNAP_DISTANCE: f32 = 0.2;
+
+/// A transient tool for measuring the distance between two points. Nothing
+/// gets saved unless the result is converted into a [`Measurement`] of the
+/// drawing that is being edited.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct MeasureTool {
+    pub active: bool,
+    /// The first point that was clicked
+    pub start: Option<Vec3>,
+    /// The second point that was clicked. While this is empty, the distance
+    /// is measured up to the cursor.
+    pub end: Option<Vec3>,
+    /// Where the cursor currently is, after snapping
+    pub hover: Option<Vec3>,
+}
+
+impl MeasureTool {
+    /// The distance that is currently being shown, in meters.
+    pub fn distance(&self) -> Option<f32> {
+        let start = self.start?;
+        let end = self.end.or(self.hover)?;
+        Some(start.truncate().distance(end.truncate()))
+    }
+
+    fn clear(&mut self) {
+        self.start = None;
+        self.end = None;
+    }
+}
+
+/// Send this event to turn the finished measurement into a [`Measurement`] of
+/// the drawing that is currently being edited.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct StoreMeasureTool;
+
+pub struct MeasureToolPlugin;
+
+impl Plugin for MeasureToolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MeasureTool>()
+            .add_event::<StoreMeasureTool>()
+            .add_systems(
+                Update,
+                (
+                    handle_measure_tool_keys,
+                    update_measure_tool,
+                    store_measure_tool,
+                )
+                    .chain()
+                    .run_if(in_state(InteractionState::Enable)),
+            );
+    }
+}
+
+fn handle_measure_tool_keys(
+    keyboard_input: Res<UserInput<KeyCode>>,
+    settings: Res<EditorSettings>,
+    picking_blockers: Res<PickingBlockers>,
+    mut tool: ResMut<MeasureTool>,
+    mut selection_blockers: ResMut<SelectionBlockers>,
+    mut store: EventWriter<StoreMeasureTool>,
+) {
+    if picking_blockers.ui {
+        return;
+    }
+
+    if settings
+        .keybindings
+        .measure_tool
+        .just_pressed(&keyboard_input)
+    {
+        tool.active = !tool.active;
+        tool.clear();
+        if tool.active {
+            info!(
+                "Measuring: click two points to see the distance between them, press \
+                Enter while editing a drawing to keep it as a measurement, or press \
+                Escape to stop"
+            );
+        }
+    } else if tool.active && keyboard_input.just_pressed(KeyCode::Escape) {
+        if tool.start.is_some() {
+            tool.clear();
+        } else {
+            tool.active = false;
+        }
+    } else if tool.active && keyboard_input.just_pressed(KeyCode::Return) {
+        store.send(StoreMeasureTool);
+    }
+
+    // Clicks belong to the tool while it is active
+    if selection_blockers.measuring != tool.active {
+        selection_blockers.measuring = tool.active;
+    }
+    if !tool.active {
+        tool.hover = None;
+    }
+}
+
+#[derive(SystemParam)]
+struct MeasureWalls<'w, 's> {
+    current_level: Res<'w, CurrentLevel>,
+    walls: Query<'w, 's, (Entity, &'static Edge<Entity>, &'static Parent), With<WallMarker>>,
+    anchors: AnchorParams<'w, 's>,
+}
+
+impl<'w, 's> MeasureWalls<'w, 's> {
+    /// The closest point on a wall of the current level, if one is close
+    /// enough to snap onto.
+    fn snap(&self, p: Vec2) -> Option<Vec2> {
+        let mut nearest: Option<(f32, Vec2)> = None;
+        for (e, edge, parent) in &self.walls {
+            if self.current_level.0 != Some(parent.get()) {
+                continue;
+            }
+            let (Ok(p0), Ok(p1)) = (
+                self.anchors
+                    .point_in_parent_frame_of(edge.start(), Category::Wall, e),
+                self.anchors
+                    .point_in_parent_frame_of(edge.end(), Category::Wall, e),
+            ) else {
+                continue;
+            };
+            let (a, b) = (p0.truncate(), p1.truncate());
+            let ab = b - a;
+            let t = if ab.length_squared() > 1e-8 {
+                ((p - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let q = a + t * ab;
+            let dist = q.distance(p);
+            if dist <= MEASURE_WALL_SNAP_DISTANCE && nearest.map_or(true, |(d, _)| dist < d) {
+                nearest = Some((dist, q));
+            }
+        }
+        nearest.map(|(_, q)| q)
+    }
+}
+
+fn update_measure_tool(
+    mut tool: ResMut<MeasureTool>,
+    mouse_buttons: Res<UserInput<MouseButton>>,
+    picking_blockers: Res<PickingBlockers>,
+    intersect_ground_params: IntersectGroundPlaneParams,
+    snapping: Snapping,
+    walls: MeasureWalls,
+    mut gizmos: Gizmos,
+    tooltips: Option<ResMut<CanvasTooltips>>,
+) {
+    if !tool.active {
+        return;
+    }
+
+    let hover = intersect_ground_params
+        .ground_plane_intersection()
+        .map(|tf| {
+            let snapped = snapping.snap(tf.translation);
+            if snapped.anchor.is_some() {
+                return snapped.position;
+            }
+            match walls.snap(tf.translation.truncate()) {
+                Some(q) => q.extend(tf.translation.z),
+                None => snapped.position,
+            }
+        });
+    tool.hover = hover;
+
+    if mouse_buttons.just_pressed(MouseButton::Left) && !picking_blockers.ui {
+        if let Some(p) = hover {
+            if tool.start.is_none() || tool.end.is_some() {
+                tool.start = Some(p);
+                tool.end = None;
+            } else {
+                tool.end = Some(p);
+            }
+        }
+    }
+
+    // Lift the lines a little so they are not hidden by the floor
+    let lift = 0.01 * Vec3::Z;
+    if let Some(start) = tool.start {
+        gizmos.sphere(start + lift, Quat::IDENTITY, 0.05, Color::YELLOW);
+        if let Some(end) = tool.end.or(tool.hover) {
+            gizmos.line(start + lift, end + lift, Color::YELLOW);
+            gizmos.sphere(end + lift, Quat::IDENTITY, 0.05, Color::YELLOW);
+        }
+    } else if let Some(hover) = tool.hover {
+        gizmos.sphere(hover + lift, Quat::IDENTITY, 0.05, Color::YELLOW);
+    }
+
+    if let Some(mut tooltips) = tooltips {
+        match tool.distance() {
+            Some(distance) => {
+                tooltips.add(Cow::Owned(format!("{distance:.3} m")));
+                if tool.end.is_some() {
+                    tooltips.add(Cow::Borrowed("Click to start a new measurement"));
+                }
+            }
+            None => tooltips.add(Cow::Borrowed("Click to start measuring")),
+        }
+    }
+}
+
+fn store_measure_tool(
+    mut commands: Commands,
+    mut requests: EventReader<StoreMeasureTool>,
+    mut tool: ResMut<MeasureTool>,
+    current_drawing: Res<CurrentEditDrawing>,
+    drawings: Query<(&GlobalTransform, &PixelsPerMeter)>,
+) {
+    if requests.read().last().is_none() {
+        return;
+    }
+    let (Some(start), Some(end)) = (tool.start, tool.end) else {
+        warn!("Click two points before storing the measurement");
+        return;
+    };
+    let Some(drawing) = current_drawing.target().as_ref().map(|c| c.drawing) else {
+        warn!("A measurement can only be stored while a drawing is being edited");
+        return;
+    };
+    let Ok((drawing_tf, ppm)) = drawings.get(drawing) else {
+        error!("Cannot find the pixels per meter of the current drawing");
+        return;
+    };
+
+    // Measurement anchors live in the pixel coordinates of the drawing
+    let inv_tf = drawing_tf.affine().inverse();
+    let ppm = ppm.0;
+    let mut spawn_anchor = |p: Vec3| {
+        let local = inv_tf.transform_point3(p);
+        commands
+            .spawn(AnchorBundle::new([local.x, local.y].into()))
+            .insert(Transform::from_scale(Vec3::new(ppm, ppm, 1.0)))
+            .set_parent(drawing)
+            .id()
+    };
+    let a0 = spawn_anchor(start);
+    let a1 = spawn_anchor(end);
+
+    let distance = start.truncate().distance(end.truncate());
+    let mut measurement: Measurement<Entity> = Edge::new(a0, a1).into();
+    measurement.distance = Distance(Some(distance));
+    let e = commands.spawn(measurement).id();
+    commands.add(ChangeDependent::add(a0, e));
+    commands.add(ChangeDependent::add(a1, e));
+    info!("Stored a measurement of {distance:.3} m");
+    tool.clear();
+}
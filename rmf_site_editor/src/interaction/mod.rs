@@ -19,6 +19,7 @@ use crate::site::{
     update_anchor_transforms, CollisionMeshMarker, CurrentEditDrawing, CurrentLevel, DoorMarker,
     FiducialMarker, FloorMarker, LaneMarker, LiftCabin, LiftCabinDoorMarker, LocationTags,
     MeasurementMarker, SiteUpdateSet, ToggleLiftDoorAvailability, VisualMeshMarker, WallMarker,
+    ZoneMarker,
 };
 
 pub mod anchor;
@@ -39,6 +40,9 @@ pub use cursor::*;
 pub mod edge;
 pub use edge::*;
 
+pub mod entity_action;
+pub use entity_action::*;
+
 pub mod gizmo;
 pub use gizmo::*;
 
@@ -54,6 +58,9 @@ pub use lift::*;
 pub mod light;
 pub use light::*;
 
+pub mod measure_tool;
+pub use measure_tool::*;
+
 pub mod model;
 pub use model::*;
 
@@ -81,6 +88,15 @@ pub use preview::*;
 pub mod select;
 pub use select::*;
 
+pub mod snapping;
+pub use snapping::*;
+
+pub mod spatial_index;
+pub use spatial_index::*;
+
+pub mod tool_mode;
+pub use tool_mode::*;
+
 pub mod visual_cue;
 pub use visual_cue::*;
 
@@ -155,6 +171,8 @@ impl Plugin for InteractionPlugin {
             .init_resource::<GizmoState>()
             .init_resource::<CurrentEditDrawing>()
             .init_resource::<CurrentLevel>()
+            .init_resource::<SnapOrigin>()
+            .init_resource::<SnapTarget>()
             .insert_resource(HighlightAnchors(false))
             .add_event::<ChangePick>()
             .add_event::<MoveTo>()
@@ -174,88 +192,98 @@ impl Plugin for InteractionPlugin {
                 CategoryVisibilityPlugin::<CollisionMeshMarker>::visible(false),
                 CategoryVisibilityPlugin::<MeasurementMarker>::visible(true),
                 CategoryVisibilityPlugin::<WallMarker>::visible(true),
+                CategoryVisibilityPlugin::<ZoneMarker>::visible(true),
             ))
-            .add_plugins((CameraControlsPlugin, ModelPreviewPlugin));
+            .add_plugins((CameraControlsPlugin, ModelPreviewPlugin, SpatialIndexPlugin));
 
         if !self.headless {
-            app.add_plugins(SelectionPlugin::default())
-                .add_systems(
-                    Update,
-                    (
-                        make_lift_doormat_gizmo,
-                        update_doormats_for_level_change,
-                        update_picking_cam,
-                        update_physical_light_visual_cues,
-                        make_selectable_entities_pickable,
-                        update_anchor_visual_cues.after(SelectionServiceStages::Select),
-                        update_popups.after(SelectionServiceStages::Select),
-                        update_unassigned_anchor_cues,
-                        update_anchor_proximity_xray.after(SelectionServiceStages::PickFlush),
-                        remove_deleted_supports_from_visual_cues,
-                        on_highlight_anchors_change,
-                    )
-                        .run_if(in_state(InteractionState::Enable)),
-                )
-                // Split the above because of a compile error when the tuple is too large
-                .add_systems(
-                    Update,
-                    (
-                        update_model_instance_visual_cues.after(SelectionServiceStages::Select),
-                        update_lane_visual_cues.after(SelectionServiceStages::Select),
-                        update_edge_visual_cues.after(SelectionServiceStages::Select),
-                        update_point_visual_cues.after(SelectionServiceStages::Select),
-                        update_path_visual_cues.after(SelectionServiceStages::Select),
-                        update_outline_visualization.after(SelectionServiceStages::Select),
-                        update_highlight_visualization.after(SelectionServiceStages::Select),
-                        update_cursor_hover_visualization.after(SelectionServiceStages::Select),
-                        update_gizmo_click_start.after(SelectionServiceStages::Select),
-                        update_gizmo_release,
-                        update_drag_motions
-                            .after(update_gizmo_click_start)
-                            .after(update_gizmo_release),
-                        handle_lift_doormat_clicks.after(update_gizmo_click_start),
-                        manage_previews,
-                        update_physical_camera_preview,
-                        dirty_changed_lifts,
-                        handle_preview_window_close,
-                    )
-                        .run_if(in_state(InteractionState::Enable)),
+            app.add_plugins((
+                SelectionPlugin::default(),
+                MeasureToolPlugin,
+                ToolModePlugin::default(),
+                EntityActionPlugin::default(),
+            ))
+            .add_systems(
+                Update,
+                (
+                    make_lift_doormat_gizmo,
+                    update_doormats_for_level_change,
+                    update_picking_cam,
+                    update_physical_light_visual_cues,
+                    make_selectable_entities_pickable,
+                    update_anchor_visual_cues.after(SelectionServiceStages::Select),
+                    update_model_gizmos.after(SelectionServiceStages::Select),
+                    update_popups.after(SelectionServiceStages::Select),
+                    update_unassigned_anchor_cues,
+                    update_anchor_proximity_xray.after(SelectionServiceStages::PickFlush),
+                    remove_deleted_supports_from_visual_cues,
+                    on_highlight_anchors_change,
                 )
-                .add_systems(
-                    PostUpdate,
-                    (
-                        add_anchor_visual_cues,
-                        remove_interaction_for_subordinate_anchors,
-                        add_lane_visual_cues,
-                        add_edge_visual_cues,
-                        add_point_visual_cues,
-                        add_path_visual_cues,
-                        add_outline_visualization,
-                        add_highlight_visualization,
-                        add_cursor_hover_visualization,
-                        add_physical_light_visual_cues,
-                        add_popups,
-                    )
-                        .run_if(in_state(InteractionState::Enable))
-                        .in_set(InteractionUpdateSet::AddVisuals),
+                    .run_if(in_state(InteractionState::Enable)),
+            )
+            // Split the above because of a compile error when the tuple is too large
+            .add_systems(
+                Update,
+                (
+                    update_model_instance_visual_cues.after(SelectionServiceStages::Select),
+                    update_lane_visual_cues.after(SelectionServiceStages::Select),
+                    update_edge_visual_cues.after(SelectionServiceStages::Select),
+                    update_point_visual_cues.after(SelectionServiceStages::Select),
+                    update_path_visual_cues.after(SelectionServiceStages::Select),
+                    update_outline_visualization.after(SelectionServiceStages::Select),
+                    update_highlight_visualization.after(SelectionServiceStages::Select),
+                    update_cursor_hover_visualization.after(SelectionServiceStages::Select),
+                    update_gizmo_click_start.after(SelectionServiceStages::Select),
+                    update_gizmo_release,
+                    update_drag_motions
+                        .after(update_gizmo_click_start)
+                        .after(update_gizmo_release),
+                    handle_lift_doormat_clicks.after(update_gizmo_click_start),
+                    (update_model_rotation_drag, update_model_scale_drag)
+                        .after(update_gizmo_click_start)
+                        .after(update_gizmo_release),
+                    manage_previews,
+                    update_physical_camera_preview,
+                    dirty_changed_lifts,
+                    handle_preview_window_close,
                 )
-                .add_systems(
-                    Update,
-                    propagate_visual_cues
-                        .run_if(in_state(InteractionState::Enable))
-                        .in_set(InteractionUpdateSet::ProcessVisuals),
+                    .run_if(in_state(InteractionState::Enable)),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    add_anchor_visual_cues,
+                    remove_interaction_for_subordinate_anchors,
+                    add_lane_visual_cues,
+                    add_edge_visual_cues,
+                    add_point_visual_cues,
+                    add_path_visual_cues,
+                    add_outline_visualization,
+                    add_highlight_visualization,
+                    add_cursor_hover_visualization,
+                    add_physical_light_visual_cues,
+                    add_popups,
                 )
-                .add_systems(OnExit(InteractionState::Enable), hide_cursor)
-                .add_systems(
-                    PostUpdate,
-                    (
-                        move_anchor.before(update_anchor_transforms),
-                        move_pose,
-                        make_gizmos_pickable,
-                    )
-                        .run_if(in_state(InteractionState::Enable)),
+                    .run_if(in_state(InteractionState::Enable))
+                    .in_set(InteractionUpdateSet::AddVisuals),
+            )
+            .add_systems(
+                Update,
+                propagate_visual_cues
+                    .run_if(in_state(InteractionState::Enable))
+                    .in_set(InteractionUpdateSet::ProcessVisuals),
+            )
+            .add_systems(OnExit(InteractionState::Enable), hide_cursor)
+            .add_systems(
+                PostUpdate,
+                (
+                    move_anchor.before(update_anchor_transforms),
+                    move_pose,
+                    make_gizmos_pickable,
                 )
-                .add_systems(First, update_picked);
+                    .run_if(in_state(InteractionState::Enable)),
+            )
+            .add_systems(First, update_picked);
         }
     }
 }
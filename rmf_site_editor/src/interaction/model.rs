@@ -17,6 +17,7 @@
 
 use crate::{interaction::*, site::*};
 use bevy::prelude::*;
+use bevy_mod_raycast::primitives::Primitive3d;
 
 pub fn update_model_instance_visual_cues(
     model_descriptions: Query<
@@ -58,3 +59,196 @@ pub fn update_model_instance_visual_cues(
         // TODO(@xiyuoh) support task-based visual cues
     }
 }
+
+/// Component added to a model instance while it shows its transform gizmo,
+/// pointing to the root entity of the gizmo.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ModelGizmo(pub Entity);
+
+/// Marks the root entity of a model transform gizmo. Model loading skips these
+/// so the gizmo keeps its own drag behavior when the model gets reloaded.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ModelGizmoRoot;
+
+/// The gizmo rotates its model about the global Z axis.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DragRotation;
+
+#[derive(Bundle)]
+pub struct DragRotationBundle {
+    pub gizmo: Gizmo,
+    pub draggable: Draggable,
+    pub rotation: DragRotation,
+    pub selectable: Selectable,
+}
+
+impl DragRotationBundle {
+    pub fn new(for_entity: Entity) -> Self {
+        Self {
+            gizmo: Gizmo::new(),
+            draggable: Draggable::new(for_entity),
+            rotation: DragRotation,
+            selectable: Selectable::new(for_entity),
+        }
+    }
+
+    pub fn with_materials(mut self, materials: GizmoMaterialSet) -> Self {
+        self.gizmo = self.gizmo.with_materials(materials);
+        self
+    }
+}
+
+/// The gizmo scales its model uniformly. Scale is a property of the model
+/// description, so every instance of the same description is scaled together.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct DragScale {
+    /// The scale of the description when the current drag began
+    initial: Option<Vec3>,
+}
+
+#[derive(Bundle)]
+pub struct DragScaleBundle {
+    pub gizmo: Gizmo,
+    pub draggable: Draggable,
+    pub scale: DragScale,
+    pub selectable: Selectable,
+}
+
+impl DragScaleBundle {
+    pub fn new(for_entity: Entity) -> Self {
+        Self {
+            gizmo: Gizmo::new(),
+            draggable: Draggable::new(for_entity),
+            scale: DragScale::default(),
+            selectable: Selectable::new(for_entity),
+        }
+    }
+
+    pub fn with_materials(mut self, materials: GizmoMaterialSet) -> Self {
+        self.gizmo = self.gizmo.with_materials(materials);
+        self
+    }
+}
+
+/// Rotation gizmos snap to this increment while shift is held.
+const ROTATION_SNAP: f32 = 15_f32 * std::f32::consts::PI / 180.0;
+
+pub fn update_model_gizmos(
+    mut commands: Commands,
+    models: Query<
+        (Entity, &Selected, Option<&ModelGizmo>),
+        (
+            With<ModelMarker>,
+            Without<Group>,
+            Without<Pending>,
+            Without<Preview>,
+            Changed<Selected>,
+        ),
+    >,
+    interaction_assets: Res<InteractionAssets>,
+) {
+    for (e, selected, gizmo) in &models {
+        match (selected.is_selected, gizmo) {
+            (true, None) => {
+                let root = interaction_assets.add_model_gizmos(&mut commands, e);
+                commands.entity(e).insert(ModelGizmo(root));
+            }
+            (false, Some(gizmo)) => {
+                commands.entity(gizmo.0).despawn_recursive();
+                commands.entity(e).remove::<ModelGizmo>();
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn update_model_rotation_drag(
+    rotations: Query<&Draggable, With<DragRotation>>,
+    drag_state: Res<GizmoState>,
+    intersect: IntersectGroundPlaneParams,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut move_to: EventWriter<MoveTo>,
+) {
+    let GizmoState::Dragging(dragging) = *drag_state else {
+        return;
+    };
+    let Ok(draggable) = rotations.get(dragging) else {
+        return;
+    };
+    let Some(initial) = &draggable.drag else {
+        return;
+    };
+    let Some(p) = intersect.primitive_intersection(Primitive3d::Plane {
+        point: initial.click_point,
+        normal: Vec3::Z,
+    }) else {
+        return;
+    };
+
+    let center = initial.tf_for_entity_global.translation;
+    let v0 = (initial.click_point - center).truncate();
+    let v1 = (p.translation - center).truncate();
+    if v0.length() < 1e-3 || v1.length() < 1e-3 {
+        return;
+    }
+    let mut angle = v0.angle_between(v1);
+    if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        angle = (angle / ROTATION_SNAP).round() * ROTATION_SNAP;
+    }
+    let rotation = Quat::from_rotation_z(angle) * initial.tf_for_entity_global.rotation;
+    let tf_goal = initial.tf_for_entity_global.with_rotation(rotation);
+    move_to.send(MoveTo {
+        entity: draggable.for_entity,
+        transform: Transform::from_matrix(
+            (initial.tf_for_entity_parent_inv * tf_goal.compute_affine()).into(),
+        ),
+    });
+}
+
+pub fn update_model_scale_drag(
+    mut scales: Query<(&mut DragScale, &Draggable)>,
+    intersect: IntersectGroundPlaneParams,
+    affiliations: Query<&Affiliation<Entity>>,
+    description_scales: Query<&ModelProperty<Scale>>,
+    mut change_scale: EventWriter<Change<ModelProperty<Scale>>>,
+) {
+    for (mut drag_scale, draggable) in &mut scales {
+        let Some(initial) = &draggable.drag else {
+            if drag_scale.initial.is_some() {
+                drag_scale.initial = None;
+            }
+            continue;
+        };
+        let Some(description) = affiliations
+            .get(draggable.for_entity)
+            .ok()
+            .and_then(|a| a.0)
+        else {
+            continue;
+        };
+        let initial_scale = *drag_scale.initial.get_or_insert_with(|| {
+            description_scales
+                .get(description)
+                .map(|s| s.0 .0)
+                .unwrap_or(Vec3::ONE)
+        });
+        let Some(p) = intersect.primitive_intersection(Primitive3d::Plane {
+            point: initial.click_point,
+            normal: Vec3::Z,
+        }) else {
+            continue;
+        };
+
+        let center = initial.tf_for_entity_global.translation;
+        let d0 = (initial.click_point - center).truncate().length();
+        let d1 = (p.translation - center).truncate().length();
+        if d0 < 1e-3 {
+            continue;
+        }
+        let ratio = (d1 / d0).max(0.01);
+        let scale = ModelProperty(Scale(initial_scale * ratio));
+        if description_scales.get(description).ok() != Some(&scale) {
+            change_scale.send(Change::new(scale, description).or_insert());
+        }
+    }
+}
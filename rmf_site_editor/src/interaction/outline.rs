@@ -20,7 +20,7 @@ use bevy::render::view::RenderLayers;
 use bevy_mod_outline::{OutlineBundle, OutlineMode, OutlineRenderLayers, OutlineVolume};
 use rmf_site_format::{
     DoorType, FiducialMarker, FloorMarker, LiftCabin, LightKind, LocationTags, MeasurementMarker,
-    ModelMarker, PhysicalCameraProperties, PrimitiveShape, WallMarker,
+    ModelMarker, PhysicalCameraProperties, PrimitiveShape, WallMarker, ZoneMarker,
 };
 use smallvec::SmallVec;
 
@@ -118,6 +118,7 @@ pub fn add_outline_visualization(
             Added<LightKind>,
             Added<LocationTags>,
             Added<PrimitiveShape>,
+            Added<ZoneMarker>,
         )>,
     >,
 ) {
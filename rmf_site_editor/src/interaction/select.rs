@@ -46,6 +46,9 @@ use create_path::*;
 pub mod create_point;
 use create_point::*;
 
+pub mod multi_select;
+pub use multi_select::*;
+
 pub mod place_object;
 pub use place_object::*;
 
@@ -104,6 +107,7 @@ impl Plugin for SelectionPlugin {
             InspectorServicePlugin::default(),
             AnchorSelectionPlugin::default(),
             ObjectPlacementPlugin::default(),
+            MultiSelectionPlugin::default(),
         ));
 
         let inspector_service = app.world.resource::<InspectorService>().inspector_service;
@@ -196,14 +200,15 @@ pub fn build_selection_workflow(
             .connect(trim.input);
 
         // After we open the gate it is safe to inject the user-requested selecion
-        // service. Once that service finishes, we will trigger the inspector to
-        // resume.
+        // service. Once that service finishes, we will go back to the select
+        // tool and trigger the inspector to resume.
         open_gate
             .output
             .chain(builder)
             .map_block(|r: RunSelector| (r.input, r.selector))
             .then_injection()
             .trigger()
+            .then(return_to_select_tool.into_blocking_callback())
             .connect(inspector.input);
 
         // This workflow only makes sense to run in serial.
@@ -368,17 +373,22 @@ pub struct Hover(pub Option<Entity>);
 pub struct SelectionBlockers {
     /// An entity is being dragged
     pub dragging: bool,
+    /// The measure tool is using the clicks
+    pub measuring: bool,
 }
 
 impl SelectionBlockers {
     pub fn blocking(&self) -> bool {
-        self.dragging
+        self.dragging || self.measuring
     }
 }
 
 impl Default for SelectionBlockers {
     fn default() -> Self {
-        SelectionBlockers { dragging: false }
+        SelectionBlockers {
+            dragging: false,
+            measuring: false,
+        }
     }
 }
 
@@ -516,6 +526,11 @@ impl Plugin for InspectorServicePlugin {
             inspector_cursor_transform
                 .configure(|config: SystemConfigs| config.in_set(SelectionServiceStages::Pick)),
         );
+        let rubber_band_select = app.spawn_continuous_service(
+            Update,
+            rubber_band_select
+                .configure(|config: SystemConfigs| config.in_set(SelectionServiceStages::Select)),
+        );
         let selection_update = app.spawn_service(selection_update);
         let keyboard_just_pressed = app
             .world
@@ -528,6 +543,10 @@ impl Plugin for InspectorServicePlugin {
                 .clone_chain(builder)
                 .then(inspector_cursor_transform)
                 .unused();
+            fork_input
+                .clone_chain(builder)
+                .then(rubber_band_select)
+                .unused();
             fork_input
                 .clone_chain(builder)
                 .then_node(keyboard_just_pressed)
@@ -844,7 +863,48 @@ pub fn selection_update(
     }): BlockingServiceInput<Select>,
     mut selected: Query<&mut Selected>,
     mut selection: ResMut<Selection>,
+    mut multi_selection: ResMut<MultiSelection>,
+    keyboard_input: Res<Input<KeyCode>>,
 ) {
+    if keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        // Ctrl+click toggles whether an element is part of the group selection
+        // and leaves the rest of the group alone.
+        let Some(candidate) = new_selection.map(|s| s.candidate) else {
+            return;
+        };
+
+        let mut group = multi_selection.0.clone();
+        group.extend(selection.0);
+        let primary = if group.remove(&candidate) {
+            selection.0.filter(|e| *e != candidate)
+        } else {
+            group.insert(candidate);
+            Some(candidate)
+        };
+        set_multi_selection(
+            group,
+            primary,
+            &mut multi_selection,
+            &mut selection,
+            &mut selected,
+        );
+        return;
+    }
+
+    if !multi_selection.is_empty() {
+        let new_selection: std::collections::BTreeSet<_> =
+            new_selection.map(|s| s.candidate).into_iter().collect();
+        let primary = new_selection.iter().next().copied();
+        set_multi_selection(
+            new_selection,
+            primary,
+            &mut multi_selection,
+            &mut selection,
+            &mut selected,
+        );
+        return;
+    }
+
     if selection.0 != new_selection.map(|s| s.candidate) {
         if let Some(previous_selection) = selection.0 {
             if let Ok(mut selected) = selected.get_mut(previous_selection) {
@@ -877,6 +937,7 @@ pub fn clear_hover_select(
     mut hovering: ResMut<Hovering>,
     mut selected: Query<&mut Selected>,
     mut selection: ResMut<Selection>,
+    mut multi_selection: ResMut<MultiSelection>,
 ) {
     if let Some(previous_hovering) = hovering.0.take() {
         if let Ok(mut hovered) = hovered.get_mut(previous_hovering) {
@@ -889,6 +950,12 @@ pub fn clear_hover_select(
             selected.is_selected = false;
         }
     }
+
+    for previous_selection in std::mem::take(&mut multi_selection.0) {
+        if let Ok(mut selected) = selected.get_mut(previous_selection) {
+            selected.is_selected = false;
+        }
+    }
 }
 
 /// Update the virtual cursor (dagger and circle) transform while in inspector mode
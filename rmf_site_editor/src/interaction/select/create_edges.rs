@@ -153,6 +153,38 @@ impl PreviewEdge {
             .despawn_recursive();
         Ok(())
     }
+
+    /// Stop extending the current chain of edges and go back to choosing the
+    /// start point of a new edge. Any edges that were already completed are
+    /// kept.
+    fn finish_chain(&mut self, edge: &mut Edge<Entity>, cursor: &Cursor, commands: &mut Commands) {
+        for anchor in edge.array() {
+            commands.add(ChangeDependent::remove(anchor, self.edge));
+        }
+        if self.provisional_start {
+            if let Some(start) = commands.get_entity(edge.start()) {
+                start.despawn_recursive();
+            }
+        }
+
+        *edge.left_mut() = cursor.level_anchor_placement;
+        *edge.right_mut() = cursor.level_anchor_placement;
+        self.side = Side::start();
+        self.provisional_start = false;
+        commands.add(ChangeDependent::add(
+            cursor.level_anchor_placement,
+            self.edge,
+        ));
+    }
+}
+
+/// Check if two entities are at the same position. When `b` is the cursor,
+/// this happens when the user clicks twice without moving it.
+fn is_same_position(a: Entity, b: Entity, transforms: &Query<&GlobalTransform>) -> bool {
+    let (Ok(tf_a), Ok(tf_b)) = (transforms.get(a), transforms.get(b)) else {
+        return false;
+    };
+    tf_a.translation().distance(tf_b.translation()) < 1e-4
 }
 
 pub enum EdgeContinuity {
@@ -241,6 +273,8 @@ pub fn on_select_for_create_edges(
     mut edges: Query<&mut Edge<Entity>>,
     mut commands: Commands,
     cursor: Res<Cursor>,
+    mut snap_origin: ResMut<SnapOrigin>,
+    transforms: Query<&GlobalTransform>,
 ) -> SelectionNodeResult {
     let mut access = access.get_mut(&key).or_broken_buffer()?;
     let state = access.newest_mut().or_broken_state()?;
@@ -271,6 +305,23 @@ pub fn on_select_for_create_edges(
             Side::Right => {
                 // We are finishing the edge
                 let mut edge = edges.get_mut(preview.edge).or_broken_query()?;
+                let repeated_click = edge.left() == anchor
+                    || (selection.provisional
+                        && is_same_position(edge.left(), cursor.frame, &transforms));
+                if repeated_click && matches!(state.continuity, EdgeContinuity::Continuous) {
+                    // Clicking the end of the chain again (e.g. a double-click)
+                    // finishes the chain, the same as pressing Esc.
+                    if selection.provisional {
+                        commands
+                            .get_entity(anchor)
+                            .or_broken_query()?
+                            .despawn_recursive();
+                    }
+                    preview.finish_chain(&mut edge, &cursor, &mut commands);
+                    snap_origin.0 = None;
+                    return Ok(());
+                }
+
                 if edge.left() == anchor {
                     // The user is trying to use the same point for the start
                     // and end of an edge. Issue a warning and exit early.
@@ -298,6 +349,7 @@ pub fn on_select_for_create_edges(
                     EdgeContinuity::Separate => {
                         // Start drawing a new edge from a blank slate with the
                         // next selection
+                        snap_origin.0 = None;
                         state.initialize_preview(cursor.level_anchor_placement, &mut commands);
                     }
                     EdgeContinuity::Continuous => {
@@ -336,6 +388,7 @@ pub fn on_keyboard_for_create_edges(
     mut access: BufferAccessMut<CreateEdges>,
     mut edges: Query<&'static mut Edge<Entity>>,
     cursor: Res<Cursor>,
+    mut snap_origin: ResMut<SnapOrigin>,
     mut commands: Commands,
 ) -> SelectionNodeResult {
     if !matches!(button, KeyCode::Escape) {
@@ -354,24 +407,8 @@ pub fn on_keyboard_for_create_edges(
             // current edge without exiting the edge creation workflow so the
             // user can choose a different start point.
             let mut edge = edges.get_mut(preview.edge).or_broken_query()?;
-            for anchor in edge.array() {
-                commands.add(ChangeDependent::remove(anchor, preview.edge));
-            }
-            if preview.provisional_start {
-                commands
-                    .get_entity(edge.start())
-                    .or_broken_query()?
-                    .despawn_recursive();
-            }
-
-            *edge.left_mut() = cursor.level_anchor_placement;
-            *edge.right_mut() = cursor.level_anchor_placement;
-            preview.side = Side::start();
-            preview.provisional_start = false;
-            commands.add(ChangeDependent::add(
-                cursor.level_anchor_placement,
-                preview.edge,
-            ));
+            preview.finish_chain(&mut edge, &cursor, &mut commands);
+            snap_origin.0 = None;
         } else {
             // We are selecting for the first point in the edge. If the user has
             // pressed Esc then that means they want to stop creating edges
@@ -137,6 +137,14 @@ pub fn create_path_with_texture<T: Bundle + From<Path<Entity>>>(
         .id()
 }
 
+pub fn create_path_without_texture<T: Bundle + From<Path<Entity>>>(
+    path: Path<Entity>,
+    commands: &mut Commands,
+) -> Entity {
+    let new_bundle: T = path.into();
+    commands.spawn((new_bundle, Pending)).id()
+}
+
 pub fn create_path_setup(
     In(key): In<BufferKey<CreatePath>>,
     mut access: BufferAccessMut<CreatePath>,
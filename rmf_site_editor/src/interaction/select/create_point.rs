@@ -71,6 +71,18 @@ impl CreatePoint {
         }
     }
 
+    /// Create lone anchors, such as the vertices of a graph that will be
+    /// connected later. Each chosen anchor is kept without any point attached
+    /// to it.
+    pub fn new_vertices(scope: AnchorScope) -> Self {
+        Self {
+            spawn_point: spawn_vertex_placeholder,
+            point: None,
+            repeating: true,
+            scope,
+        }
+    }
+
     pub fn create_new_point(&mut self, anchor: Entity, commands: &mut Commands) {
         let point = Point(anchor);
         let point = (self.spawn_point)(point, commands);
@@ -93,6 +105,26 @@ fn create_point<T: Bundle + From<Point<Entity>>>(
     commands.spawn((new_bundle, Pending)).id()
 }
 
+/// Marks the point that follows the cursor while lone anchors are being
+/// placed. It only exists so that the usual point creation workflow can be
+/// reused, and gets removed as soon as its anchor has been chosen.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct VertexPlaceholder;
+
+fn spawn_vertex_placeholder(point: Point<Entity>, commands: &mut Commands) -> Entity {
+    commands.spawn((point, VertexPlaceholder, Pending)).id()
+}
+
+pub fn release_vertex_placeholders(
+    placeholders: Query<(Entity, &Point<Entity>), (With<VertexPlaceholder>, Without<Pending>)>,
+    mut commands: Commands,
+) {
+    for (e, point) in &placeholders {
+        commands.add(ChangeDependent::remove(point.0, e));
+        commands.entity(e).despawn_recursive();
+    }
+}
+
 pub fn create_point_setup(
     In(key): In<BufferKey<CreatePoint>>,
     mut access: BufferAccessMut<CreatePoint>,
@@ -0,0 +1,361 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::*,
+    site::{Anchor, Category, Delete, Edge, Path, Point, Pose, Subordinate},
+};
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::{egui, EguiContexts};
+use bevy_impulse::*;
+use rmf_site_format::{Angle, Pending};
+use std::collections::BTreeSet;
+
+/// How far (in logical pixels) the cursor needs to be dragged before a
+/// rubber-band selection is recognized instead of a regular click.
+const RUBBER_BAND_THRESHOLD: f32 = 4.0;
+
+/// Used as a resource to keep track of every element that belongs to a group
+/// selection. This is empty unless more than one element is selected, in
+/// which case the [`Selection`] resource holds whichever member of the group
+/// was selected most recently.
+#[derive(Default, Debug, Clone, Deref, DerefMut, Resource)]
+pub struct MultiSelection(pub BTreeSet<Entity>);
+
+/// Used as a resource to track an ongoing rubber-band selection in screen
+/// coordinates.
+#[derive(Default, Debug, Clone, Copy, Resource)]
+pub struct RubberBand {
+    pub start: Option<Vec2>,
+    pub current: Option<Vec2>,
+}
+
+impl RubberBand {
+    pub fn rect(&self) -> Option<Rect> {
+        let (start, current) = (self.start?, self.current?);
+        if start.distance(current) < RUBBER_BAND_THRESHOLD {
+            return None;
+        }
+        Some(Rect::from_corners(start, current))
+    }
+}
+
+/// Send this event to move or rotate every element in the current group
+/// selection at once. Anchors that are shared between multiple selected
+/// elements are only moved once.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct TransformMultiSelection {
+    /// Translation to apply to the group
+    pub translation: Vec2,
+    /// Rotation to apply to the group around the center of its anchors
+    pub yaw: Angle,
+}
+
+/// Send this event to delete every element in the current group selection.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct DeleteMultiSelection;
+
+#[derive(Default)]
+pub struct MultiSelectionPlugin {}
+
+impl Plugin for MultiSelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MultiSelection>()
+            .init_resource::<RubberBand>()
+            .add_event::<TransformMultiSelection>()
+            .add_event::<DeleteMultiSelection>()
+            .add_systems(
+                Update,
+                (
+                    transform_multi_selection,
+                    delete_multi_selection,
+                    forget_despawned_multi_selection,
+                ),
+            )
+            .add_systems(
+                Update,
+                draw_rubber_band.after(SelectionServiceStages::Select),
+            );
+    }
+}
+
+/// Replace the current group selection with a new set of elements. If the set
+/// has fewer than two members this becomes an ordinary single selection.
+pub fn set_multi_selection(
+    new_selection: BTreeSet<Entity>,
+    primary: Option<Entity>,
+    multi_selection: &mut MultiSelection,
+    selection: &mut Selection,
+    selected: &mut Query<&mut Selected>,
+) {
+    for e in multi_selection.iter().chain(selection.0.iter()) {
+        if !new_selection.contains(e) {
+            if let Ok(mut selected) = selected.get_mut(*e) {
+                selected.is_selected = false;
+            }
+        }
+    }
+
+    for e in &new_selection {
+        if let Ok(mut selected) = selected.get_mut(*e) {
+            if !selected.is_selected {
+                selected.is_selected = true;
+            }
+        }
+    }
+
+    selection.0 = primary
+        .filter(|p| new_selection.contains(p))
+        .or_else(|| new_selection.iter().next_back().copied());
+    multi_selection.0 = if new_selection.len() > 1 {
+        new_selection
+    } else {
+        BTreeSet::new()
+    };
+}
+
+/// A continuous service that lets the user hold Shift and drag the left mouse
+/// button to select every selectable element inside of a screen-space box.
+/// Holding Ctrl as well will add the boxed elements to the current selection
+/// instead of replacing it.
+pub fn rubber_band_select(
+    In(ContinuousService { key }): ContinuousServiceInput<(), ()>,
+    orders: ContinuousQuery<(), ()>,
+    mut rubber_band: ResMut<RubberBand>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    primary_windows: Query<&Window, With<PrimaryWindow>>,
+    camera_controls: Res<CameraControls>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    selectables: Query<
+        (&Selectable, &GlobalTransform, &InheritedVisibility),
+        (Without<Preview>, Without<Pending>),
+    >,
+    blockers: Option<Res<PickingBlockers>>,
+    selection_blockers: Res<SelectionBlockers>,
+    mut multi_selection: ResMut<MultiSelection>,
+    mut selection: ResMut<Selection>,
+    mut selected: Query<&mut Selected>,
+) {
+    let Some(orders) = orders.view(&key) else {
+        return;
+    };
+
+    if orders.is_empty() {
+        *rubber_band = RubberBand::default();
+        return;
+    }
+
+    let Some(cursor) = primary_windows
+        .get_single()
+        .ok()
+        .and_then(|w| w.cursor_position())
+    else {
+        return;
+    };
+
+    let shifting = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let blocked = blockers.filter(|x| x.blocking()).is_some() || selection_blockers.blocking();
+    if mouse_button_input.just_pressed(MouseButton::Left) && shifting && !blocked {
+        rubber_band.start = Some(cursor);
+    }
+
+    if rubber_band.start.is_none() {
+        return;
+    }
+    rubber_band.current = Some(cursor);
+
+    if !mouse_button_input.just_released(MouseButton::Left) {
+        return;
+    }
+
+    let band = *rubber_band;
+    *rubber_band = RubberBand::default();
+    let Some(rect) = band.rect() else {
+        // The cursor barely moved, so treat this as an ordinary click
+        return;
+    };
+
+    let Ok((camera, camera_tf)) = cameras.get(camera_controls.active_camera()) else {
+        return;
+    };
+
+    let additive = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let mut new_selection = if additive {
+        let mut current = multi_selection.0.clone();
+        current.extend(selection.0);
+        current
+    } else {
+        BTreeSet::new()
+    };
+
+    for (selectable, tf, visibility) in &selectables {
+        if !selectable.is_selectable || !visibility.get() {
+            continue;
+        }
+
+        let Some(p) = camera.world_to_viewport(camera_tf, tf.translation()) else {
+            continue;
+        };
+
+        if rect.contains(p) {
+            new_selection.insert(selectable.element);
+        }
+    }
+
+    let primary = selection.0;
+    set_multi_selection(
+        new_selection,
+        primary,
+        &mut multi_selection,
+        &mut selection,
+        &mut selected,
+    );
+}
+
+fn draw_rubber_band(
+    rubber_band: Res<RubberBand>,
+    mut egui_context: EguiContexts,
+    primary_windows: Query<Entity, With<PrimaryWindow>>,
+) {
+    let Some(rect) = rubber_band.rect() else {
+        return;
+    };
+
+    let Some(ctx) = primary_windows
+        .get_single()
+        .ok()
+        .and_then(|w| egui_context.try_ctx_for_window_mut(w))
+    else {
+        return;
+    };
+
+    let rect = egui::Rect::from_min_max(
+        egui::pos2(rect.min.x, rect.min.y),
+        egui::pos2(rect.max.x, rect.max.y),
+    );
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("rubber_band_selection"),
+    ));
+    let color = egui::Color32::from_rgb(80, 160, 255);
+    painter.rect_filled(rect, 0.0, color.gamma_multiply(0.1));
+    painter.rect_stroke(rect, 0.0, egui::Stroke::new(1.0, color));
+}
+
+fn transform_multi_selection(
+    mut transforms: EventReader<TransformMultiSelection>,
+    multi_selection: Res<MultiSelection>,
+    selection: Res<Selection>,
+    mut anchors: Query<&mut Anchor, Without<Subordinate>>,
+    mut poses: Query<&mut Pose, Without<Anchor>>,
+    edges: Query<&Edge<Entity>>,
+    points: Query<&Point<Entity>>,
+    paths: Query<&Path<Entity>>,
+) {
+    for transform in transforms.read() {
+        let members: BTreeSet<Entity> = if multi_selection.is_empty() {
+            selection.0.into_iter().collect()
+        } else {
+            multi_selection.0.clone()
+        };
+
+        // Gather every anchor and pose that the group depends on so that an
+        // anchor shared by several selected edges only gets moved once.
+        let mut moving_anchors = BTreeSet::new();
+        let mut moving_poses = BTreeSet::new();
+        for e in &members {
+            if anchors.contains(*e) {
+                moving_anchors.insert(*e);
+            } else if let Ok(edge) = edges.get(*e) {
+                moving_anchors.extend(edge.array());
+            } else if let Ok(point) = points.get(*e) {
+                moving_anchors.insert(point.0);
+            } else if let Ok(path) = paths.get(*e) {
+                moving_anchors.extend(path.0.iter().copied());
+            } else if poses.contains(*e) {
+                moving_poses.insert(*e);
+            }
+        }
+        moving_anchors.retain(|e| anchors.contains(*e));
+
+        let positions: Vec<Vec2> = moving_anchors
+            .iter()
+            .filter_map(|e| anchors.get(*e).ok())
+            .map(|a| Vec2::from(a.translation_for_category(Category::General)))
+            .chain(
+                moving_poses
+                    .iter()
+                    .filter_map(|e| poses.get(*e).ok())
+                    .map(|p| Vec2::new(p.trans[0], p.trans[1])),
+            )
+            .collect();
+        if positions.is_empty() {
+            continue;
+        }
+        let center = positions.iter().sum::<Vec2>() / positions.len() as f32;
+        let rotation = Mat2::from_angle(transform.yaw.radians());
+        let apply = |p: Vec2| rotation * (p - center) + center + transform.translation;
+
+        for e in &moving_anchors {
+            let Ok(mut anchor) = anchors.get_mut(*e) else {
+                continue;
+            };
+            let mut tf = anchor.local_transform(Category::General);
+            let p = apply(tf.translation.truncate());
+            tf.translation.x = p.x;
+            tf.translation.y = p.y;
+            anchor.move_to(&tf);
+        }
+
+        for e in &moving_poses {
+            let Ok(mut pose) = poses.get_mut(*e) else {
+                continue;
+            };
+            let p = apply(Vec2::new(pose.trans[0], pose.trans[1]));
+            pose.trans[0] = p.x;
+            pose.trans[1] = p.y;
+            pose.rot.apply_yaw(transform.yaw);
+        }
+    }
+}
+
+fn delete_multi_selection(
+    mut requests: EventReader<DeleteMultiSelection>,
+    multi_selection: Res<MultiSelection>,
+    mut delete: EventWriter<Delete>,
+) {
+    if requests.read().last().is_none() {
+        return;
+    }
+
+    for e in multi_selection.iter() {
+        delete.send(Delete::new(*e));
+    }
+}
+
+fn forget_despawned_multi_selection(
+    mut multi_selection: ResMut<MultiSelection>,
+    exists: Query<()>,
+) {
+    if multi_selection.iter().any(|e| !exists.contains(*e)) {
+        multi_selection.retain(|e| exists.contains(*e));
+        if multi_selection.len() < 2 {
+            multi_selection.clear();
+        }
+    }
+}
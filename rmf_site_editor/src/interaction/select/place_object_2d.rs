@@ -153,6 +153,7 @@ pub fn place_object_2d_find_placement(
     intersect_ground_params: IntersectGroundPlaneParams,
     mouse_button_input: Res<UserInput<MouseButton>>,
     blockers: Option<Res<PickingBlockers>>,
+    snapping: Snapping,
 ) {
     let Some(mut orders) = orders.get_mut(&key) else {
         return;
@@ -166,7 +167,8 @@ pub fn place_object_2d_find_placement(
     // similar to how they can for the 3D object placement workflow. Either we
     // need to introduce parent frames to the 2D sites or just don't bother with
     // parenting.
-    if let Some(intersection) = intersect_ground_params.ground_plane_intersection() {
+    if let Some(mut intersection) = intersect_ground_params.ground_plane_intersection() {
+        intersection.translation = snapping.snap_position(intersection.translation);
         match transforms.get_mut(cursor.frame) {
             Ok(mut transform) => {
                 *transform = intersection;
@@ -19,7 +19,7 @@ use bevy::prelude::*;
 use bevy_impulse::*;
 
 use crate::{interaction::select::*, site::CurrentLevel};
-use rmf_site_format::{Fiducial, Floor, LevelElevation, Location, Path, Point};
+use rmf_site_format::{Fiducial, Floor, LevelElevation, Location, Path, Point, Zone};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Resource)]
 pub enum AnchorScope {
@@ -45,6 +45,7 @@ impl Plugin for AnchorSelectionPlugin {
         let helpers = AnchorSelectionHelpers::from_app(app);
         let services = AnchorSelectionServices::from_app(&helpers, app);
         app.init_resource::<HiddenSelectAnchorEntities>()
+            .add_systems(Update, release_vertex_placeholders)
             .insert_resource(AnchorScope::General)
             .insert_resource(helpers)
             .insert_resource(services);
@@ -175,10 +176,34 @@ impl<'w, 's> AnchorSelection<'w, 's> {
         );
     }
 
+    pub fn create_zone(&mut self) {
+        self.create_path::<Zone<Entity>>(
+            create_path_without_texture::<Zone<Entity>>,
+            3,
+            false,
+            true,
+            AnchorScope::General,
+        );
+    }
+
     pub fn create_location(&mut self) {
         self.create_point::<Location<Entity>>(false, AnchorScope::General);
     }
 
+    pub fn create_vertices(&mut self) {
+        let state = self
+            .commands
+            .spawn(SelectorInput(CreatePoint::new_vertices(
+                AnchorScope::General,
+            )))
+            .id();
+
+        self.send(RunSelector {
+            selector: self.services.create_point,
+            input: Some(state),
+        });
+    }
+
     pub fn create_site_fiducial(&mut self) {
         self.create_point::<Fiducial<Entity>>(false, AnchorScope::Site);
     }
@@ -516,8 +541,12 @@ pub fn cleanup_anchor_selection(
     mut anchor_scope: ResMut<AnchorScope>,
     mut highlight: ResMut<HighlightAnchors>,
     mut gizmo_blockers: ResMut<GizmoBlockers>,
+    mut snap_origin: ResMut<SnapOrigin>,
+    mut snap_target: ResMut<SnapTarget>,
 ) {
     cursor.remove_mode(SELECT_ANCHOR_MODE_LABEL, &mut visibility);
+    snap_origin.0 = None;
+    snap_target.0 = None;
     set_visibility(cursor.site_anchor_placement, &mut visibility, false);
     set_visibility(cursor.level_anchor_placement, &mut visibility, false);
     for e in hidden_anchors.drawing_anchors.drain() {
@@ -576,6 +605,8 @@ pub struct AnchorFilter<'w, 's> {
     parents: Query<'w, 's, &'static Parent>,
     levels: Query<'w, 's, (), With<LevelElevation>>,
     current_level: Res<'w, CurrentLevel>,
+    snap_target: Res<'w, SnapTarget>,
+    snap_origin: ResMut<'w, SnapOrigin>,
 }
 
 impl<'w, 's> SelectionFilter for AnchorFilter<'w, 's> {
@@ -590,7 +621,11 @@ impl<'w, 's> SelectionFilter for AnchorFilter<'w, 's> {
     }
 
     fn on_click(&mut self, hovered: Hover) -> Option<Select> {
-        if let Some(candidate) = hovered.0.and_then(|e| self.filter_target(e)) {
+        // Merge into an existing anchor when the cursor is snapped onto one
+        // instead of creating a near-duplicate anchor.
+        let hovered = hovered.0.or(self.snap_target.0);
+        if let Some(candidate) = hovered.and_then(|e| self.filter_target(e)) {
+            self.snap_origin.0 = Some(candidate);
             return Some(Select::new(Some(candidate)));
         }
 
@@ -647,6 +682,7 @@ impl<'w, 's> SelectionFilter for AnchorFilter<'w, 's> {
             }
         };
 
+        self.snap_origin.0 = Some(new_anchor);
         Some(Select::provisional(new_anchor))
     }
 }
@@ -738,6 +774,8 @@ pub fn select_anchor_cursor_transform(
     cursor: Res<Cursor>,
     mut transforms: Query<&mut Transform>,
     intersect_ground_params: IntersectGroundPlaneParams,
+    snapping: Snapping,
+    mut snap_target: ResMut<SnapTarget>,
 ) {
     let Some(orders) = orders.view(&key) else {
         return;
@@ -761,5 +799,9 @@ pub fn select_anchor_cursor_transform(
         }
     };
 
-    *transform = Transform::from_translation(intersection.translation);
+    let snapped = snapping.snap(intersection.translation);
+    if snap_target.0 != snapped.anchor {
+        snap_target.0 = snapped.anchor;
+    }
+    *transform = Transform::from_translation(snapped.position);
 }
@@ -0,0 +1,280 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{CameraControls, Preview, SpatialIndex},
+    settings::EditorSettings,
+    site::{Anchor, Pending},
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// The anchor that the edge currently being drawn starts from. Angle snapping
+/// is measured around this anchor.
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct SnapOrigin(pub Option<Entity>);
+
+/// The existing anchor that the cursor is currently snapped onto, if any.
+/// Clicking while snapped onto an anchor will select that anchor instead of
+/// creating a new one right on top of it.
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct SnapTarget(pub Option<Entity>);
+
+/// The result of snapping a point.
+#[derive(Debug, Clone, Copy)]
+pub struct Snapped {
+    pub position: Vec3,
+    /// The anchor that the point was snapped onto
+    pub anchor: Option<Entity>,
+}
+
+/// Applies the [`SnapSettings`](crate::settings::SnapSettings) to points that
+/// are being placed or dragged.
+#[derive(SystemParam)]
+pub struct Snapping<'w, 's> {
+    settings: Res<'w, EditorSettings>,
+    origin: Res<'w, SnapOrigin>,
+    anchors: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static GlobalTransform,
+            &'static InheritedVisibility,
+        ),
+        (With<Anchor>, Without<Preview>, Without<Pending>),
+    >,
+    transforms: Query<'w, 's, &'static GlobalTransform>,
+    index: Res<'w, SpatialIndex>,
+    camera_controls: Res<'w, CameraControls>,
+    cameras: Query<'w, 's, (&'static Camera, &'static GlobalTransform)>,
+}
+
+impl<'w, 's> Snapping<'w, 's> {
+    /// Snap a point in world coordinates that is about to be placed.
+    pub fn snap(&self, p: Vec3) -> Snapped {
+        let snapping = &self.settings.snapping;
+        if snapping.vertex {
+            if let Some((anchor, position)) = self.nearest_anchor(p) {
+                return Snapped {
+                    position,
+                    anchor: Some(anchor),
+                };
+            }
+        }
+
+        let origin = self
+            .origin
+            .0
+            .and_then(|origin| self.transforms.get(origin).ok())
+            .map(|tf| tf.translation().truncate());
+
+        let grid = snapping.grid.then_some(snapping.grid_spacing);
+        let flat = match origin {
+            Some(origin) if snapping.angle => {
+                snap_angle(origin, p.truncate(), snapping.angle_increment, grid)
+            }
+            _ => match grid {
+                Some(spacing) => snap_to_grid(p.truncate(), spacing),
+                None => p.truncate(),
+            },
+        };
+
+        Snapped {
+            position: flat.extend(p.z),
+            anchor: None,
+        }
+    }
+
+    /// Snap a position that should never be merged into an anchor, such as a
+    /// dragged anchor or a model being placed. Only grid snapping applies,
+    /// because snapping a dragged anchor onto another anchor would leave two
+    /// anchors in the same place.
+    pub fn snap_position(&self, p: Vec3) -> Vec3 {
+        let snapping = &self.settings.snapping;
+        if !snapping.grid {
+            return p;
+        }
+        snap_to_grid(p.truncate(), snapping.grid_spacing).extend(p.z)
+    }
+
+    pub fn is_anchor(&self, e: Entity) -> bool {
+        self.anchors.contains(e)
+    }
+
+    /// Find the visible anchor that is closest to `p` on the screen, as long
+    /// as it is within the vertex snapping threshold.
+    fn nearest_anchor(&self, p: Vec3) -> Option<(Entity, Vec3)> {
+        let (camera, camera_tf) = self
+            .cameras
+            .get(self.camera_controls.active_camera())
+            .ok()?;
+        let p_screen = camera.world_to_viewport(camera_tf, p)?;
+        let threshold = self.settings.snapping.vertex_threshold;
+
+        // Turn the threshold into a distance on the ground near the point so
+        // only the anchors nearby need to be measured.
+        let right = camera_tf.right();
+        let one_meter = camera.world_to_viewport(camera_tf, p + right)?;
+        let pixels_per_meter = one_meter.distance(p_screen).max(1e-6);
+        let radius = Vec2::splat(threshold / pixels_per_meter);
+        let p_flat = p.truncate();
+        let nearby = self
+            .index
+            .anchors
+            .query_rect(p_flat - radius, p_flat + radius);
+
+        let mut nearest: Option<(f32, Entity, Vec3)> = None;
+        for (e, tf, visibility) in self.anchors.iter_many(&nearby) {
+            if !visibility.get() {
+                continue;
+            }
+            let Some(a_screen) = camera.world_to_viewport(camera_tf, tf.translation()) else {
+                continue;
+            };
+            let dist = a_screen.distance(p_screen);
+            if dist > threshold {
+                continue;
+            }
+            if nearest.map_or(true, |(d, _, _)| dist < d) {
+                nearest = Some((dist, e, tf.translation()));
+            }
+        }
+
+        nearest.map(|(_, e, p)| (e, p))
+    }
+}
+
+/// Round a point to the nearest intersection of a square grid.
+pub fn snap_to_grid(p: Vec2, spacing: f32) -> Vec2 {
+    if spacing <= 0.0 {
+        return p;
+    }
+    (p / spacing).round() * spacing
+}
+
+/// Rotate `p` around `origin` so the direction from `origin` is a multiple of
+/// `increment` degrees. If a grid spacing is given, the distance from
+/// `origin` is rounded to a multiple of it.
+pub fn snap_angle(origin: Vec2, p: Vec2, increment: f32, grid: Option<f32>) -> Vec2 {
+    let delta = p - origin;
+    if delta.length() < 1e-6 || increment <= 0.0 {
+        return p;
+    }
+    let increment = increment.to_radians();
+    let angle = (delta.y.atan2(delta.x) / increment).round() * increment;
+    let direction = Vec2::new(angle.cos(), angle.sin());
+    let mut distance = delta.dot(direction);
+    if let Some(spacing) = grid.filter(|s| *s > 0.0) {
+        distance = (distance / spacing).round() * spacing;
+    }
+    origin + distance * direction
+}
+
+#[cfg(test)]
+fn assert_close(actual: Vec2, expected: Vec2) {
+    assert!(
+        actual.abs_diff_eq(expected, 1e-5),
+        "{actual} is not close to {expected}"
+    );
+}
+
+#[test]
+fn test_snap_to_grid_with_negative_coordinates() {
+    assert_close(
+        snap_to_grid(Vec2::new(-1.3, -0.7), 0.5),
+        Vec2::new(-1.5, -0.5),
+    );
+    assert_close(snap_to_grid(Vec2::new(-0.2, 0.2), 0.5), Vec2::ZERO);
+    assert_close(
+        snap_to_grid(Vec2::new(-12.04, 7.96), 0.1),
+        Vec2::new(-12.0, 8.0),
+    );
+}
+
+#[test]
+fn test_snap_to_grid_at_cell_boundaries() {
+    assert_close(snap_to_grid(Vec2::new(0.24, -0.24), 0.5), Vec2::ZERO);
+    assert_close(
+        snap_to_grid(Vec2::new(0.26, -0.26), 0.5),
+        Vec2::new(0.5, -0.5),
+    );
+    // Points halfway between two lines go away from zero
+    assert_close(
+        snap_to_grid(Vec2::new(0.25, -0.25), 0.5),
+        Vec2::new(0.5, -0.5),
+    );
+    assert_close(
+        snap_to_grid(Vec2::new(-1.5, 2.0), 0.5),
+        Vec2::new(-1.5, 2.0),
+    );
+    // Without a spacing nothing gets snapped
+    assert_close(
+        snap_to_grid(Vec2::new(0.26, -0.26), 0.0),
+        Vec2::new(0.26, -0.26),
+    );
+}
+
+#[test]
+fn test_snap_angle_wraps_around() {
+    // Just above and just below the negative x axis, where the angle jumps
+    // between 180 and -180 degrees
+    assert_close(
+        snap_angle(Vec2::ZERO, Vec2::new(-2.0, 0.05), 45.0, None),
+        Vec2::new(-2.0, 0.0),
+    );
+    assert_close(
+        snap_angle(Vec2::ZERO, Vec2::new(-2.0, -0.05), 45.0, None),
+        Vec2::new(-2.0, 0.0),
+    );
+    // Just below the positive x axis
+    assert_close(
+        snap_angle(Vec2::ZERO, Vec2::new(2.0, -0.3), 90.0, None),
+        Vec2::new(2.0, 0.0),
+    );
+    assert_close(
+        snap_angle(Vec2::new(1.0, 1.0), Vec2::new(3.0, 1.1), 45.0, None),
+        Vec2::new(3.0, 1.0),
+    );
+}
+
+#[test]
+fn test_snap_angle_with_grid() {
+    let diagonal = 2.0 * std::f32::consts::FRAC_1_SQRT_2;
+    assert_close(
+        snap_angle(Vec2::ZERO, Vec2::new(1.3, 1.2), 45.0, Some(0.5)),
+        Vec2::new(diagonal, diagonal),
+    );
+    assert_close(
+        snap_angle(
+            Vec2::new(-3.0, -3.0),
+            Vec2::new(-3.0, -5.1),
+            90.0,
+            Some(1.0),
+        ),
+        Vec2::new(-3.0, -5.0),
+    );
+    // A point on the origin has no direction to snap
+    assert_close(
+        snap_angle(
+            Vec2::new(-3.0, -3.0),
+            Vec2::new(-3.0, -3.0),
+            90.0,
+            Some(1.0),
+        ),
+        Vec2::new(-3.0, -3.0),
+    );
+}
@@ -0,0 +1,425 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    aabb::update_bounds,
+    interaction::{CameraControls, Selectable, SiteRaycastSet},
+    site::Anchor,
+};
+use bevy::{
+    prelude::*,
+    render::{primitives::Aabb, view::VisibilitySystems},
+    transform::TransformSystem,
+    utils::{HashMap, HashSet},
+    window::PrimaryWindow,
+};
+use bevy_mod_raycast::deferred::RaycastMesh;
+
+/// Width of each cell of the spatial hashes, in meters.
+pub const SPATIAL_INDEX_CELL_SIZE: f32 = 2.0;
+
+/// Entities whose footprint covers more cells than this are not hashed.
+/// They are always treated as candidates instead.
+const MAX_FOOTPRINT_CELLS: i64 = 4096;
+
+/// Rays that run almost parallel to the ground are only followed this far,
+/// in meters.
+const MAX_RAY_LENGTH: f32 = 1000.0;
+
+/// A uniform grid over the XY plane of the world which keeps track of which
+/// entities overlap each cell, so that looking up the entities near a point
+/// or along a ray does not need to visit every entity of the site.
+#[derive(Debug, Clone)]
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<IVec2, HashSet<Entity>>,
+    footprints: HashMap<Entity, [IVec2; 2]>,
+    unbounded: HashSet<Entity>,
+    /// The range of heights that has been covered by anything inserted so
+    /// far. This never shrinks.
+    z_range: Option<[f32; 2]>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: Default::default(),
+            footprints: Default::default(),
+            unbounded: Default::default(),
+            z_range: None,
+        }
+    }
+
+    fn cell(&self, p: Vec2) -> IVec2 {
+        (p / self.cell_size).floor().as_ivec2()
+    }
+
+    pub fn contains(&self, e: Entity) -> bool {
+        self.footprints.contains_key(&e) || self.unbounded.contains(&e)
+    }
+
+    pub fn len(&self) -> usize {
+        self.footprints.len() + self.unbounded.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Insert or move an entity that occupies the box from `min` to `max`.
+    pub fn insert(&mut self, e: Entity, min: Vec3, max: Vec3) {
+        let footprint = [self.cell(min.truncate()), self.cell(max.truncate())];
+        if self.footprints.get(&e) == Some(&footprint) {
+            return;
+        }
+        self.remove(e);
+
+        self.z_range = Some(match self.z_range {
+            Some([z0, z1]) => [z0.min(min.z), z1.max(max.z)],
+            None => [min.z, max.z],
+        });
+
+        let [lower, upper] = footprint;
+        let size = upper - lower + IVec2::ONE;
+        if size.x as i64 * size.y as i64 > MAX_FOOTPRINT_CELLS {
+            self.unbounded.insert(e);
+            return;
+        }
+
+        for x in lower.x..=upper.x {
+            for y in lower.y..=upper.y {
+                self.cells.entry(IVec2::new(x, y)).or_default().insert(e);
+            }
+        }
+        self.footprints.insert(e, footprint);
+    }
+
+    pub fn remove(&mut self, e: Entity) {
+        self.unbounded.remove(&e);
+        let Some([lower, upper]) = self.footprints.remove(&e) else {
+            return;
+        };
+        for x in lower.x..=upper.x {
+            for y in lower.y..=upper.y {
+                let cell = IVec2::new(x, y);
+                if let Some(entities) = self.cells.get_mut(&cell) {
+                    entities.remove(&e);
+                    if entities.is_empty() {
+                        self.cells.remove(&cell);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Every entity whose footprint might overlap the rectangle from `min`
+    /// to `max`.
+    pub fn query_rect(&self, min: Vec2, max: Vec2) -> HashSet<Entity> {
+        let [lower, upper] = [self.cell(min), self.cell(max)];
+        let mut found = self.unbounded.clone();
+        for x in lower.x..=upper.x {
+            for y in lower.y..=upper.y {
+                if let Some(entities) = self.cells.get(&IVec2::new(x, y)) {
+                    found.extend(entities.iter().copied());
+                }
+            }
+        }
+        found
+    }
+
+    /// Every entity whose footprint might be crossed by a ray. Cells within
+    /// `padding` cells of the ray are included as well.
+    pub fn query_ray(&self, origin: Vec3, direction: Vec3, padding: i32) -> HashSet<Entity> {
+        let mut found = self.unbounded.clone();
+        let Some([z0, z1]) = self.z_range else {
+            return found;
+        };
+
+        // Only the part of the ray that passes through the heights of the
+        // indexed entities needs to be searched.
+        let (t0, t1) = if direction.z.abs() < 1e-6 {
+            if origin.z < z0 || z1 < origin.z {
+                return found;
+            }
+            (0.0, MAX_RAY_LENGTH)
+        } else {
+            let ta = (z0 - origin.z) / direction.z;
+            let tb = (z1 - origin.z) / direction.z;
+            (ta.min(tb).max(0.0), ta.max(tb))
+        };
+        if t1 < t0 {
+            return found;
+        }
+        let t1 = t1.min(t0 + MAX_RAY_LENGTH);
+
+        let a = (origin + t0 * direction).truncate();
+        let b = (origin + t1 * direction).truncate();
+        let limit = (2.0 * MAX_RAY_LENGTH / self.cell_size) as usize;
+        for cell in traverse_cells(a / self.cell_size, b / self.cell_size, limit) {
+            for dx in -padding..=padding {
+                for dy in -padding..=padding {
+                    if let Some(entities) = self.cells.get(&(cell + IVec2::new(dx, dy))) {
+                        found.extend(entities.iter().copied());
+                    }
+                }
+            }
+        }
+        found
+    }
+}
+
+/// The unit grid cells that a line segment passes through, from the cell of
+/// `a` to the cell of `b`.
+fn traverse_cells(a: Vec2, b: Vec2, limit: usize) -> Vec<IVec2> {
+    let mut cell = a.floor().as_ivec2();
+    let end = b.floor().as_ivec2();
+    let d = b - a;
+    let step = |d: f32| {
+        if d > 0.0 {
+            1
+        } else if d < 0.0 {
+            -1
+        } else {
+            0
+        }
+    };
+    let step = IVec2::new(step(d.x), step(d.y));
+    // The fraction of the segment at which it crosses into the next cell
+    // along each axis, and how much that fraction grows per cell.
+    let first_crossing = |c: i32, s: i32, a: f32, d: f32| {
+        if s == 0 {
+            f32::INFINITY
+        } else {
+            ((c + (s > 0) as i32) as f32 - a) / d
+        }
+    };
+    let mut t_max = Vec2::new(
+        first_crossing(cell.x, step.x, a.x, d.x),
+        first_crossing(cell.y, step.y, a.y, d.y),
+    );
+    let t_delta = Vec2::new(
+        if step.x == 0 {
+            f32::INFINITY
+        } else {
+            1.0 / d.x.abs()
+        },
+        if step.y == 0 {
+            f32::INFINITY
+        } else {
+            1.0 / d.y.abs()
+        },
+    );
+
+    let mut cells = vec![cell];
+    while cell != end && cells.len() < limit && t_max.min_element() <= 1.0 {
+        if t_max.x < t_max.y {
+            cell.x += step.x;
+            t_max.x += t_delta.x;
+        } else {
+            cell.y += step.y;
+            t_max.y += t_delta.y;
+        }
+        cells.push(cell);
+    }
+    cells
+}
+
+/// Spatial hashes of the pickable meshes and the anchors of every open site.
+/// Picking only raycasts against the meshes that are near the cursor ray, and
+/// vertex snapping only measures the anchors near the cursor.
+#[derive(Resource, Debug, Clone)]
+pub struct SpatialIndex {
+    pub pickables: SpatialHash,
+    pub anchors: SpatialHash,
+    /// Indexed pickables that currently have a [`RaycastMesh`]
+    raycastable: HashSet<Entity>,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self {
+            pickables: SpatialHash::new(SPATIAL_INDEX_CELL_SIZE),
+            anchors: SpatialHash::new(SPATIAL_INDEX_CELL_SIZE),
+            raycastable: Default::default(),
+        }
+    }
+}
+
+pub struct SpatialIndexPlugin;
+
+impl Plugin for SpatialIndexPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialIndex>().add_systems(
+            PostUpdate,
+            (update_spatial_index, update_pick_candidates)
+                .chain()
+                .after(TransformSystem::TransformPropagate)
+                .after(VisibilitySystems::CalculateBounds)
+                .after(update_bounds),
+        );
+    }
+}
+
+fn world_aabb(aabb: &Aabb, tf: &GlobalTransform) -> (Vec3, Vec3) {
+    let center = Vec3::from(aabb.center);
+    let half = Vec3::from(aabb.half_extents);
+    let mut min = Vec3::splat(f32::INFINITY);
+    let mut max = Vec3::splat(f32::NEG_INFINITY);
+    for corner in [
+        Vec3::new(-1.0, -1.0, -1.0),
+        Vec3::new(-1.0, -1.0, 1.0),
+        Vec3::new(-1.0, 1.0, -1.0),
+        Vec3::new(-1.0, 1.0, 1.0),
+        Vec3::new(1.0, -1.0, -1.0),
+        Vec3::new(1.0, -1.0, 1.0),
+        Vec3::new(1.0, 1.0, -1.0),
+        Vec3::new(1.0, 1.0, 1.0),
+    ] {
+        let p = tf.transform_point(center + corner * half);
+        min = min.min(p);
+        max = max.max(p);
+    }
+    (min, max)
+}
+
+pub fn update_spatial_index(
+    mut index: ResMut<SpatialIndex>,
+    changed_pickables: Query<
+        (
+            Entity,
+            &Aabb,
+            &GlobalTransform,
+            Has<RaycastMesh<SiteRaycastSet>>,
+        ),
+        (
+            With<Selectable>,
+            Or<(Changed<GlobalTransform>, Changed<Aabb>)>,
+        ),
+    >,
+    changed_anchors: Query<(Entity, &GlobalTransform), (With<Anchor>, Changed<GlobalTransform>)>,
+    mut removed_selectables: RemovedComponents<Selectable>,
+    mut removed_aabbs: RemovedComponents<Aabb>,
+    mut removed_anchors: RemovedComponents<Anchor>,
+) {
+    for e in removed_selectables.read().chain(removed_aabbs.read()) {
+        index.pickables.remove(e);
+        index.raycastable.remove(&e);
+    }
+    for e in removed_anchors.read() {
+        index.anchors.remove(e);
+    }
+
+    for (e, aabb, tf, raycastable) in &changed_pickables {
+        let (min, max) = world_aabb(aabb, tf);
+        index.pickables.insert(e, min, max);
+        if raycastable {
+            index.raycastable.insert(e);
+        }
+    }
+
+    for (e, tf) in &changed_anchors {
+        let p = tf.translation();
+        index.anchors.insert(e, p, p);
+    }
+}
+
+/// Only the pickable meshes near the cursor ray keep their [`RaycastMesh`],
+/// so the raycast for hovering and clicking does not need to test every mesh
+/// of the site. Pickables that are not in the index are not affected.
+pub fn update_pick_candidates(
+    mut commands: Commands,
+    mut index: ResMut<SpatialIndex>,
+    camera_controls: Res<CameraControls>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    primary_windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    let ray = primary_windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .and_then(|cursor| {
+            let (camera, camera_tf) = cameras.get(camera_controls.active_camera()).ok()?;
+            camera.viewport_to_world(camera_tf, cursor)
+        });
+
+    // Pad the search by a cell since the ray used for picking will be
+    // computed from the cursor position of the next frame.
+    let candidates = match ray {
+        Some(ray) => index.pickables.query_ray(ray.origin, ray.direction, 1),
+        None => HashSet::new(),
+    };
+
+    let dropped: Vec<Entity> = index
+        .raycastable
+        .iter()
+        .filter(|e| !candidates.contains(*e))
+        .copied()
+        .collect();
+    for e in dropped {
+        index.raycastable.remove(&e);
+        commands.add(move |world: &mut World| {
+            if let Some(mut e_mut) = world.get_entity_mut(e) {
+                e_mut.remove::<RaycastMesh<SiteRaycastSet>>();
+            }
+        });
+    }
+
+    for e in candidates {
+        if index.raycastable.insert(e) {
+            commands.add(move |world: &mut World| {
+                if let Some(mut e_mut) = world.get_entity_mut(e) {
+                    e_mut.insert(RaycastMesh::<SiteRaycastSet>::default());
+                }
+            });
+        }
+    }
+}
+
+#[test]
+fn test_spatial_hash_insert_query_and_move() {
+    let mut hash = SpatialHash::new(1.0);
+    let e = Entity::from_raw(0);
+    hash.insert(e, Vec3::new(0.5, 0.5, 0.0), Vec3::new(1.5, 0.5, 1.0));
+    assert!(hash.contains(e));
+    assert_eq!(hash.len(), 1);
+    assert!(hash
+        .query_rect(Vec2::new(1.2, 0.2), Vec2::new(1.8, 0.8))
+        .contains(&e));
+    assert!(hash
+        .query_rect(Vec2::new(5.0, 5.0), Vec2::new(6.0, 6.0))
+        .is_empty());
+    assert!(hash
+        .query_ray(Vec3::new(1.0, 0.5, 10.0), Vec3::NEG_Z, 0)
+        .contains(&e));
+
+    // Moving the entity should drop it from the cells that it left
+    hash.insert(e, Vec3::new(5.5, 5.5, 0.0), Vec3::new(5.5, 5.5, 0.0));
+    assert_eq!(hash.len(), 1);
+    assert!(hash
+        .query_rect(Vec2::new(0.0, 0.0), Vec2::new(2.0, 1.0))
+        .is_empty());
+    assert!(hash
+        .query_rect(Vec2::new(5.0, 5.0), Vec2::new(6.0, 6.0))
+        .contains(&e));
+    assert!(hash
+        .query_ray(Vec3::new(1.0, 0.5, 10.0), Vec3::NEG_Z, 0)
+        .is_empty());
+
+    hash.remove(e);
+    assert!(hash.is_empty());
+}
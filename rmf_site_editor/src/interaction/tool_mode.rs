@@ -0,0 +1,270 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{AnchorSelection, PickingBlockers, RunSelector},
+    settings::{EditorSettings, KeyBinding, KeyBindings},
+    AppState,
+};
+use bevy::{
+    ecs::system::SystemId,
+    prelude::{Input as UserInput, *},
+};
+use bevy_impulse::*;
+
+/// The tool that mouse input in the scene currently belongs to. Only one tool
+/// can be active at a time: switching to a new tool stops the previous one,
+/// and the mode goes back to [`ToolMode::Select`] when a tool finishes, e.g.
+/// when the user presses Esc.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Resource)]
+pub enum ToolMode {
+    /// Hover and click objects to inspect them
+    #[default]
+    Select,
+    AddVertex,
+    AddLane,
+    AddWall,
+    AddMeasurement,
+    AddModel,
+    AddDoor,
+    AddFloor,
+    /// A tool that was added by a downstream plugin
+    Custom(&'static str),
+}
+
+/// Send this event to switch to a different tool.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct SetToolMode(pub ToolMode);
+
+/// How a tool gets presented to the user.
+#[derive(Debug, Clone, Copy)]
+pub struct ToolModeInfo {
+    pub mode: ToolMode,
+    /// Text of the toolbar button
+    pub icon: &'static str,
+    pub label: &'static str,
+    /// The tool is only offered while the app is in one of these states
+    pub app_states: &'static [AppState],
+    /// The key binding that switches to this tool, if it has one
+    pub shortcut: Option<fn(&KeyBindings) -> &KeyBinding>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RegisteredToolMode {
+    pub info: ToolModeInfo,
+    /// Run when the user switches to this tool. This typically sends a
+    /// [`RunSelector`] so that the tool takes over mouse input.
+    pub start: SystemId,
+}
+
+/// Every tool that can be switched to, in the order they are shown in the
+/// toolbar. Use [`ToolModeExt::add_tool_mode`] to add new tools.
+#[derive(Default, Resource)]
+pub struct ToolModes {
+    tools: Vec<RegisteredToolMode>,
+}
+
+impl ToolModes {
+    pub fn iter(&self) -> impl Iterator<Item = &RegisteredToolMode> {
+        self.tools.iter()
+    }
+
+    pub fn get(&self, mode: ToolMode) -> Option<&RegisteredToolMode> {
+        self.tools.iter().find(|tool| tool.info.mode == mode)
+    }
+}
+
+pub trait ToolModeExt {
+    /// Add a tool that can be switched to from the toolbar. The `start`
+    /// system is run each time the user switches to the tool.
+    fn add_tool_mode<M>(
+        &mut self,
+        info: ToolModeInfo,
+        start: impl IntoSystem<(), (), M> + 'static,
+    ) -> &mut Self;
+}
+
+impl ToolModeExt for App {
+    fn add_tool_mode<M>(
+        &mut self,
+        info: ToolModeInfo,
+        start: impl IntoSystem<(), (), M> + 'static,
+    ) -> &mut Self {
+        let start = self.world.register_system(start);
+        let mut tools = self.world.get_resource_or_insert_with(ToolModes::default);
+        if let Some(tool) = tools.tools.iter_mut().find(|t| t.info.mode == info.mode) {
+            *tool = RegisteredToolMode { info, start };
+        } else {
+            tools.tools.push(RegisteredToolMode { info, start });
+        }
+        self
+    }
+}
+
+#[derive(Resource, Clone, Copy)]
+pub struct ToolModeServices {
+    /// A selector that finishes as soon as it starts, which hands mouse
+    /// input back to the inspector.
+    pub stop_tool: Service<Option<Entity>, ()>,
+}
+
+#[derive(Default)]
+pub struct ToolModePlugin {}
+
+impl Plugin for ToolModePlugin {
+    fn build(&self, app: &mut App) {
+        let stop_tool = app.spawn_service(stop_tool.into_blocking_service());
+        app.init_resource::<ToolMode>()
+            .init_resource::<ToolModes>()
+            .insert_resource(ToolModeServices { stop_tool })
+            .add_event::<SetToolMode>()
+            .add_systems(Update, (handle_tool_mode_keys, switch_tool_mode).chain());
+
+        app.add_tool_mode(
+            ToolModeInfo {
+                mode: ToolMode::Select,
+                icon: "⬉",
+                label: "Select",
+                app_states: &[AppState::SiteEditor, AppState::SiteDrawingEditor],
+                shortcut: Some(|keys| &keys.select_tool),
+            },
+            start_select_tool,
+        )
+        .add_tool_mode(
+            ToolModeInfo {
+                mode: ToolMode::AddVertex,
+                icon: "•",
+                label: "Vertex: click to add each vertex, then press Esc to finish",
+                app_states: &[AppState::SiteEditor],
+                shortcut: Some(|keys| &keys.add_vertex_tool),
+            },
+            |mut anchor_selection: AnchorSelection| anchor_selection.create_vertices(),
+        )
+        .add_tool_mode(
+            ToolModeInfo {
+                mode: ToolMode::AddLane,
+                icon: "↔",
+                label: "Lane",
+                app_states: &[AppState::SiteEditor],
+                shortcut: Some(|keys| &keys.add_lane_tool),
+            },
+            |mut anchor_selection: AnchorSelection| anchor_selection.create_lanes(),
+        )
+        .add_tool_mode(
+            ToolModeInfo {
+                mode: ToolMode::AddWall,
+                icon: "■",
+                label: "Wall: click to add each corner, then double-click or press Esc to finish",
+                app_states: &[AppState::SiteEditor],
+                shortcut: Some(|keys| &keys.add_wall_tool),
+            },
+            |mut anchor_selection: AnchorSelection| anchor_selection.create_walls(),
+        )
+        .add_tool_mode(
+            ToolModeInfo {
+                mode: ToolMode::AddMeasurement,
+                icon: "📏",
+                label: "Measurement",
+                app_states: &[AppState::SiteDrawingEditor],
+                shortcut: Some(|keys| &keys.add_measurement_tool),
+            },
+            |mut anchor_selection: AnchorSelection| anchor_selection.create_measurements(),
+        )
+        .add_tool_mode(
+            ToolModeInfo {
+                mode: ToolMode::AddDoor,
+                icon: "🚪",
+                label: "Door",
+                app_states: &[AppState::SiteEditor],
+                shortcut: Some(|keys| &keys.add_door_tool),
+            },
+            |mut anchor_selection: AnchorSelection| anchor_selection.create_door(),
+        )
+        .add_tool_mode(
+            ToolModeInfo {
+                mode: ToolMode::AddFloor,
+                icon: "✏",
+                label: "Floor",
+                app_states: &[AppState::SiteEditor],
+                shortcut: Some(|keys| &keys.add_floor_tool),
+            },
+            |mut anchor_selection: AnchorSelection| anchor_selection.create_floor(),
+        );
+    }
+}
+
+fn stop_tool(In(_): In<Option<Entity>>) {}
+
+fn start_select_tool(services: Res<ToolModeServices>, mut run_selector: EventWriter<RunSelector>) {
+    run_selector.send(RunSelector {
+        selector: services.stop_tool,
+        input: None,
+    });
+}
+
+fn handle_tool_mode_keys(
+    keyboard_input: Res<UserInput<KeyCode>>,
+    settings: Res<EditorSettings>,
+    picking_blockers: Res<PickingBlockers>,
+    app_state: Res<State<AppState>>,
+    tools: Res<ToolModes>,
+    mut set_tool_mode: EventWriter<SetToolMode>,
+) {
+    if picking_blockers.ui {
+        return;
+    }
+
+    for tool in tools.iter() {
+        if !tool.info.app_states.contains(app_state.get()) {
+            continue;
+        }
+        let Some(shortcut) = tool.info.shortcut else {
+            continue;
+        };
+        if shortcut(&settings.keybindings).just_pressed(&keyboard_input) {
+            set_tool_mode.send(SetToolMode(tool.info.mode));
+        }
+    }
+}
+
+fn switch_tool_mode(
+    mut requests: EventReader<SetToolMode>,
+    mut current: ResMut<ToolMode>,
+    tools: Res<ToolModes>,
+    mut commands: Commands,
+) {
+    // Only the last request matters since each tool stops the previous one
+    let Some(SetToolMode(mode)) = requests.read().last().copied() else {
+        return;
+    };
+    let Some(tool) = tools.get(mode) else {
+        warn!("Unable to switch to tool {mode:?} because it was never added");
+        return;
+    };
+    *current = mode;
+    commands.run_system(tool.start);
+}
+
+/// Used by the selection workflow to go back to the select tool whenever the
+/// active tool finishes.
+pub fn return_to_select_tool(In(_): In<()>, current: Option<ResMut<ToolMode>>) {
+    if let Some(mut current) = current {
+        if *current != ToolMode::Select {
+            *current = ToolMode::Select;
+        }
+    }
+}
@@ -15,12 +15,20 @@
  *
 */
 
-use crate::site::ChangePlugin;
+use crate::{
+    interaction::Preview,
+    site::{
+        level_of_element, Affiliation, Anchor, ChangePlugin, Edge, LevelElevation, NameInSite,
+        Path, Point, Pose,
+    },
+    CurrentWorkspace,
+};
 use bevy::{
+    ecs::system::SystemParam,
     prelude::*,
-    utils::{HashMap, Uuid},
+    utils::{HashMap, HashSet, Uuid},
 };
-use rmf_site_format::{FilteredIssueKinds, FilteredIssues, IssueKey};
+use rmf_site_format::{FilteredIssueKinds, FilteredIssues, IssueKey, Pending};
 
 #[derive(Component, Debug, Clone)]
 pub struct Issue {
@@ -33,6 +41,11 @@ pub struct Issue {
 
 pub trait RegisterIssueType {
     fn add_issue_type(&mut self, type_uuid: &Uuid, name: &str) -> &mut Self;
+
+    /// Register a type of issue that is always found among the elements of a
+    /// single level. When only part of a workspace changed, these issues are
+    /// only checked again for the changed elements and their levels.
+    fn add_level_issue_type(&mut self, type_uuid: &Uuid, name: &str) -> &mut Self;
 }
 
 impl RegisterIssueType for App {
@@ -43,12 +56,132 @@ impl RegisterIssueType for App {
         issue_dictionary.insert(type_uuid.clone(), name.into());
         self
     }
+
+    fn add_level_issue_type(&mut self, type_uuid: &Uuid, name: &str) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with::<LevelIssueKinds>(Default::default)
+            .insert(type_uuid.clone());
+        self.add_issue_type(type_uuid, name)
+    }
 }
 
 /// Used as an event to request validation of a workspace
 #[derive(Deref, DerefMut, Event)]
 pub struct ValidateWorkspace(pub Entity);
 
+/// Used as an event to request validation of only the parts of a workspace
+/// that changed since it was last validated. Issues of the types registered
+/// with [`RegisterIssueType::add_level_issue_type`] are only checked again if
+/// they involve a changed element or an element of a changed level. All other
+/// issues compare elements across the whole workspace, like duplicated names,
+/// so they are checked again in full.
+#[derive(Event, Debug, Clone)]
+pub struct ValidateChanges {
+    pub root: Entity,
+    pub elements: HashSet<Entity>,
+    /// The levels that the changed elements belong to
+    pub levels: HashSet<Entity>,
+}
+
+impl ValidateChanges {
+    /// Whether issues that involve this element need to be checked again,
+    /// given the level that the element belongs to.
+    pub fn affects(&self, element: Entity, level: Option<Entity>) -> bool {
+        self.elements.contains(&element) || level.is_some_and(|l| self.levels.contains(&l))
+    }
+}
+
+/// A request that a validator needs to answer
+pub enum ValidationRequest<'a> {
+    Workspace(Entity),
+    Changes(&'a ValidateChanges),
+}
+
+impl<'a> ValidationRequest<'a> {
+    pub fn root(&self) -> Entity {
+        match self {
+            Self::Workspace(root) => *root,
+            Self::Changes(changes) => changes.root,
+        }
+    }
+
+    /// Whether issues that involve this element need to be checked, given the
+    /// level that the element belongs to.
+    pub fn affects(&self, element: Entity, level: Option<Entity>) -> bool {
+        match self {
+            Self::Workspace(_) => true,
+            Self::Changes(changes) => changes.affects(element, level),
+        }
+    }
+
+    /// Whether all elements of this level need to be checked
+    pub fn includes_level(&self, level: Entity) -> bool {
+        match self {
+            Self::Workspace(_) => true,
+            Self::Changes(changes) => changes.levels.contains(&level),
+        }
+    }
+}
+
+/// Used by validators to read both full and partial validation requests.
+#[derive(SystemParam)]
+pub struct ValidationRequests<'w, 's> {
+    workspaces: EventReader<'w, 's, ValidateWorkspace>,
+    changes: EventReader<'w, 's, ValidateChanges>,
+}
+
+impl<'w, 's> ValidationRequests<'w, 's> {
+    /// All requests, for validators of the issue types that are found within
+    /// a level.
+    pub fn read(&mut self) -> Vec<ValidationRequest<'_>> {
+        let mut requests: Vec<_> = self
+            .workspaces
+            .read()
+            .map(|root| ValidationRequest::Workspace(**root))
+            .collect();
+        requests.extend(self.changes.read().map(ValidationRequest::Changes));
+        requests
+    }
+
+    /// The workspaces that need to be checked in full, for validators of the
+    /// issue types that compare elements across the whole workspace.
+    pub fn roots(&mut self) -> Vec<Entity> {
+        self.read().iter().map(|request| request.root()).collect()
+    }
+}
+
+/// The issue types that are always found among the elements of a single level
+#[derive(Default, Resource, Deref, DerefMut)]
+pub struct LevelIssueKinds(HashSet<Uuid>);
+
+/// Used as a resource to validate the current workspace in the background
+/// whenever its content changes, so that issues show up while they are being
+/// created instead of only when someone remembers to press Validate. Only the
+/// elements that changed and their levels are validated again.
+#[derive(Resource, Debug, Clone)]
+pub struct AutoValidation {
+    pub enabled: bool,
+    /// Seconds to wait after the most recent change before validating. This
+    /// keeps validation from running on every frame while something is being
+    /// dragged around.
+    pub delay: f32,
+    /// When the most recent change that has not been validated yet was seen
+    last_change: Option<f32>,
+    /// The elements that changed since the last validation
+    changed: HashSet<Entity>,
+}
+
+impl Default for AutoValidation {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            delay: 0.5,
+            last_change: None,
+            changed: HashSet::new(),
+        }
+    }
+}
+
 // Maps a uuid to the issue name
 #[derive(Default, Resource, Deref, DerefMut)]
 pub struct IssueDictionary(HashMap<Uuid, String>);
@@ -59,11 +192,18 @@ pub struct IssuePlugin;
 impl Plugin for IssuePlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<ValidateWorkspace>()
+            .add_event::<ValidateChanges>()
             .add_plugins((
                 ChangePlugin::<FilteredIssues<Entity>>::default(),
                 ChangePlugin::<FilteredIssueKinds>::default(),
             ))
             .init_resource::<IssueDictionary>()
+            .init_resource::<LevelIssueKinds>()
+            .init_resource::<AutoValidation>()
+            .add_systems(
+                Update,
+                (detect_workspace_changes, run_auto_validation).chain(),
+            )
             .add_systems(PostUpdate, clear_old_issues_on_new_validate_event);
     }
 }
@@ -71,8 +211,12 @@ impl Plugin for IssuePlugin {
 pub fn clear_old_issues_on_new_validate_event(
     mut commands: Commands,
     mut validate_events: EventReader<ValidateWorkspace>,
+    mut change_events: EventReader<ValidateChanges>,
     children: Query<&Children>,
-    issues: Query<Entity, With<Issue>>,
+    issues: Query<&Issue>,
+    level_issue_kinds: Res<LevelIssueKinds>,
+    parents: Query<&Parent>,
+    levels: Query<(), With<LevelElevation>>,
 ) {
     for root in validate_events.read() {
         let Ok(children) = children.get(**root) else {
@@ -84,4 +228,148 @@ pub fn clear_old_issues_on_new_validate_event(
             }
         }
     }
+
+    for changes in change_events.read() {
+        let Ok(children) = children.get(changes.root) else {
+            continue;
+        };
+        for e in children {
+            let Ok(issue) = issues.get(*e) else {
+                continue;
+            };
+            let affected = !level_issue_kinds.contains(&issue.key.kind)
+                || issue.key.entities.iter().any(|element| {
+                    let level = std::iter::once(*element)
+                        .chain(AncestorIter::new(&parents, *element))
+                        .find(|p| levels.contains(*p));
+                    changes.affects(*element, level)
+                });
+            if affected {
+                commands.entity(*e).despawn_recursive();
+            }
+        }
+    }
+}
+
+/// Watch for any change to the elements that validators look at. Previews and
+/// pending placements are ignored since they are not part of the workspace yet.
+pub fn detect_workspace_changes(
+    changed: Query<
+        Entity,
+        (
+            Or<(
+                Changed<Anchor>,
+                Changed<Edge<Entity>>,
+                Changed<Point<Entity>>,
+                Changed<Path<Entity>>,
+                Changed<Pose>,
+                Changed<NameInSite>,
+                Changed<Affiliation<Entity>>,
+            )>,
+            Without<Preview>,
+            Without<Pending>,
+        ),
+    >,
+    mut removed_anchors: RemovedComponents<Anchor>,
+    mut removed_names: RemovedComponents<NameInSite>,
+    mut removed_pending: RemovedComponents<Pending>,
+    mut auto_validation: ResMut<AutoValidation>,
+    time: Res<Time>,
+) {
+    let count = auto_validation.changed.len();
+    auto_validation.changed.extend(
+        changed
+            .iter()
+            .chain(removed_anchors.read())
+            .chain(removed_names.read())
+            .chain(removed_pending.read()),
+    );
+    if auto_validation.changed.len() > count {
+        auto_validation.last_change = Some(time.elapsed_seconds());
+    }
+}
+
+pub fn run_auto_validation(
+    mut auto_validation: ResMut<AutoValidation>,
+    current_workspace: Res<CurrentWorkspace>,
+    mut validate_changes: EventWriter<ValidateChanges>,
+    time: Res<Time>,
+    parents: Query<&Parent>,
+    levels: Query<(), With<LevelElevation>>,
+    anchors: Query<(
+        Option<&Point<Entity>>,
+        Option<&Edge<Entity>>,
+        Option<&Path<Entity>>,
+    )>,
+) {
+    if !auto_validation.enabled {
+        return;
+    }
+    let Some(last_change) = auto_validation.last_change else {
+        return;
+    };
+    if time.elapsed_seconds() - last_change < auto_validation.delay {
+        return;
+    }
+    auto_validation.last_change = None;
+    let elements = std::mem::take(&mut auto_validation.changed);
+    if let Some(root) = current_workspace.root {
+        let changed_levels = elements
+            .iter()
+            .filter_map(|e| level_of_element(*e, &parents, &levels, &anchors))
+            .collect();
+        validate_changes.send(ValidateChanges {
+            root,
+            elements,
+            levels: changed_levels,
+        });
+    }
+}
+
+#[test]
+fn test_validating_changes_keeps_issues_of_unchanged_levels() {
+    use bevy::ecs::system::RunSystemOnce;
+
+    const LEVEL_ISSUE: Uuid = Uuid::from_u128(1);
+    const WORKSPACE_ISSUE: Uuid = Uuid::from_u128(2);
+
+    let mut world = World::new();
+    world.init_resource::<Events<ValidateWorkspace>>();
+    world.init_resource::<Events<ValidateChanges>>();
+    let mut level_issue_kinds = LevelIssueKinds::default();
+    level_issue_kinds.insert(LEVEL_ISSUE);
+    world.insert_resource(level_issue_kinds);
+
+    let root = world.spawn_empty().id();
+    let changed_level = world.spawn(LevelElevation(0.0)).set_parent(root).id();
+    let other_level = world.spawn(LevelElevation(3.0)).set_parent(root).id();
+    let changed = world.spawn_empty().set_parent(changed_level).id();
+    let unchanged = world.spawn_empty().set_parent(other_level).id();
+    let mut spawn_issue = |kind: Uuid, entity: Entity| {
+        world
+            .spawn(Issue {
+                key: IssueKey {
+                    entities: [entity].into(),
+                    kind,
+                },
+                brief: String::new(),
+                hint: String::new(),
+            })
+            .set_parent(root)
+            .id()
+    };
+    let changed_issue = spawn_issue(LEVEL_ISSUE, changed);
+    let unchanged_issue = spawn_issue(LEVEL_ISSUE, unchanged);
+    let workspace_issue = spawn_issue(WORKSPACE_ISSUE, unchanged);
+
+    world.send_event(ValidateChanges {
+        root,
+        elements: [changed].into_iter().collect(),
+        levels: [changed_level].into_iter().collect(),
+    });
+    world.run_system_once(clear_old_issues_on_new_validate_event);
+
+    assert!(world.get_entity(changed_issue).is_none());
+    assert!(world.get_entity(unchanged_issue).is_some());
+    assert!(world.get_entity(workspace_issue).is_none());
 }
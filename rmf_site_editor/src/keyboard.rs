@@ -16,11 +16,18 @@
 */
 
 use crate::{
-    interaction::{ChangeProjectionMode, Selection},
-    site::{AlignSiteDrawings, Delete},
+    interaction::{
+        ChangeProjectionMode, DeleteMultiSelection, MultiSelection, PickingBlockers, Selection,
+    },
+    settings::EditorSettings,
+    site::{
+        AlignSiteDrawings, CopySelection, CurrentLevel, Delete, LevelElevation, PasteClipboard,
+        ReverseLanes, SurveyMode, WalkthroughMode,
+    },
     CreateNewWorkspace, CurrentWorkspace, WorkspaceLoader, WorkspaceSaver,
 };
 use bevy::{
+    ecs::system::SystemParam,
     prelude::{Input as UserInput, *},
     window::PrimaryWindow,
 };
@@ -41,7 +48,7 @@ pub struct KeyboardInputPlugin;
 impl Plugin for KeyboardInputPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<DebugMode>()
-            .add_systems(Last, handle_keyboard_input);
+            .add_systems(Last, (handle_keyboard_input, handle_level_keys));
 
         let keyboard_just_pressed =
             app.spawn_continuous_service(Last, keyboard_just_pressed_stream);
@@ -52,11 +59,23 @@ impl Plugin for KeyboardInputPlugin {
     }
 }
 
+/// Events for editing the current selection, grouped together to keep the
+/// keyboard system under the system parameter limit.
+#[derive(SystemParam)]
+struct EditEvents<'w> {
+    delete: EventWriter<'w, Delete>,
+    delete_multi_selection: EventWriter<'w, DeleteMultiSelection>,
+    copy_selection: EventWriter<'w, CopySelection>,
+    paste_clipboard: EventWriter<'w, PasteClipboard>,
+    reverse_lanes: EventWriter<'w, ReverseLanes>,
+}
+
 fn handle_keyboard_input(
     keyboard_input: Res<UserInput<KeyCode>>,
     selection: Res<Selection>,
+    multi_selection: Res<MultiSelection>,
     mut egui_context: EguiContexts,
-    mut delete: EventWriter<Delete>,
+    mut edit: EditEvents,
     mut new_workspace: EventWriter<CreateNewWorkspace>,
     mut change_camera_mode: EventWriter<ChangeProjectionMode>,
     mut debug_mode: ResMut<DebugMode>,
@@ -65,6 +84,9 @@ fn handle_keyboard_input(
     primary_windows: Query<Entity, With<PrimaryWindow>>,
     mut workspace_loader: WorkspaceLoader,
     mut workspace_saver: WorkspaceSaver,
+    settings: Res<EditorSettings>,
+    survey_mode: Res<SurveyMode>,
+    walkthrough: Res<WalkthroughMode>,
 ) {
     let Some(egui_context) = primary_windows
         .get_single()
@@ -81,57 +103,136 @@ fn handle_keyboard_input(
         return;
     }
 
-    if keyboard_input.just_pressed(KeyCode::F2) {
+    let keys = &settings.keybindings;
+    // The walkthrough keeps control of the camera until it is turned off
+    if !walkthrough.active && keys.orthographic_view.just_pressed(&keyboard_input) {
         change_camera_mode.send(ChangeProjectionMode::to_orthographic());
     }
 
-    if keyboard_input.just_pressed(KeyCode::F3) {
+    if !walkthrough.active && keys.perspective_view.just_pressed(&keyboard_input) {
         change_camera_mode.send(ChangeProjectionMode::to_perspective());
     }
 
-    if keyboard_input.just_pressed(KeyCode::Delete) || keyboard_input.just_pressed(KeyCode::Back) {
-        if let Some(selection) = selection.0 {
-            delete.send(Delete::new(selection));
+    if keys.delete.just_pressed(&keyboard_input) {
+        if !multi_selection.is_empty() {
+            edit.delete_multi_selection.send(DeleteMultiSelection);
+        } else if let Some(selection) = selection.0 {
+            edit.delete.send(Delete::new(selection));
         } else {
             warn!("No selected entity to delete");
         }
     }
 
-    if keyboard_input.just_pressed(KeyCode::D) {
+    // Anything in the selection that is not a lane gets ignored
+    if keys.reverse_lanes.just_pressed(&keyboard_input) {
+        let lanes = if multi_selection.is_empty() {
+            selection.0.into_iter().collect()
+        } else {
+            multi_selection.iter().copied().collect()
+        };
+        edit.reverse_lanes.send(ReverseLanes { lanes });
+    }
+
+    // The quick-add keys of survey mode and the walking keys of the
+    // walkthrough take priority over the debug toggle
+    if !survey_mode.active
+        && !walkthrough.active
+        && keys.toggle_debug_mode.just_pressed(&keyboard_input)
+    {
         debug_mode.0 = !debug_mode.0;
         info!("Toggling debug mode: {debug_mode:?}");
     }
 
-    // Ctrl keybindings
-    if keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
-        if keyboard_input.just_pressed(KeyCode::S) {
-            if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
-                workspace_saver.save_to_dialog();
-            } else {
-                workspace_saver.save_to_default_file();
-            }
-        }
+    if keys.save_as.just_pressed(&keyboard_input) {
+        workspace_saver.save_to_dialog();
+    }
 
-        if keyboard_input.just_pressed(KeyCode::T) {
-            if let Some(site) = current_workspace.root {
-                align_site.send(AlignSiteDrawings(site));
-            }
-        }
+    if keys.save.just_pressed(&keyboard_input) {
+        workspace_saver.save_to_default_file();
+    }
 
-        if keyboard_input.just_pressed(KeyCode::E) {
-            workspace_saver.export_sdf_to_dialog();
+    if keys.align_drawings.just_pressed(&keyboard_input) {
+        if let Some(site) = current_workspace.root {
+            align_site.send(AlignSiteDrawings(site));
         }
+    }
 
-        // TODO(luca) pop up a confirmation prompt if the current file is not saved, or create a
-        // gui to switch between open workspaces
-        if keyboard_input.just_pressed(KeyCode::N) {
-            new_workspace.send(CreateNewWorkspace);
-        }
+    if keys.export_sdf.just_pressed(&keyboard_input) {
+        workspace_saver.export_sdf_to_dialog();
+    }
 
-        if keyboard_input.just_pressed(KeyCode::O) {
-            workspace_loader.load_from_dialog();
-        }
+    // TODO(luca) pop up a confirmation prompt if the current file is not saved, or create a
+    // gui to switch between open workspaces
+    if keys.new_workspace.just_pressed(&keyboard_input) {
+        new_workspace.send(CreateNewWorkspace);
+    }
+
+    if keys.open.just_pressed(&keyboard_input) {
+        workspace_loader.load_from_dialog();
+    }
+
+    if keys.copy.just_pressed(&keyboard_input) {
+        edit.copy_selection.send(CopySelection);
+    }
+
+    if keys.paste.just_pressed(&keyboard_input) {
+        edit.paste_clipboard
+            .send(PasteClipboard { at_cursor: true });
+    }
+
+    // Pasting in place is useful for repeating a layout on another floor
+    if keys.paste_in_place.just_pressed(&keyboard_input) {
+        edit.paste_clipboard
+            .send(PasteClipboard { at_cursor: false });
+    }
+
+    if keys.duplicate.just_pressed(&keyboard_input) {
+        edit.copy_selection.send(CopySelection);
+        edit.paste_clipboard
+            .send(PasteClipboard { at_cursor: true });
+    }
+}
+
+/// Step through the levels of the current site in order of elevation.
+fn handle_level_keys(
+    keyboard_input: Res<UserInput<KeyCode>>,
+    settings: Res<EditorSettings>,
+    picking_blockers: Option<Res<PickingBlockers>>,
+    current_workspace: Res<CurrentWorkspace>,
+    levels: Query<(Entity, &LevelElevation, &Parent)>,
+    mut current_level: ResMut<CurrentLevel>,
+) {
+    if picking_blockers.is_some_and(|blockers| blockers.ui) {
+        return;
+    }
+
+    let keys = &settings.keybindings;
+    let step: isize = if keys.level_up.just_pressed(&keyboard_input) {
+        1
+    } else if keys.level_down.just_pressed(&keyboard_input) {
+        -1
+    } else {
+        return;
+    };
+
+    let mut ordered: Vec<_> = levels
+        .iter()
+        .filter(|(_, _, parent)| Some(parent.get()) == current_workspace.root)
+        .map(|(e, elevation, _)| (elevation.0, e))
+        .collect();
+    ordered.sort_by(|(h_a, e_a), (h_b, e_b)| h_a.total_cmp(h_b).then(e_a.cmp(e_b)));
+
+    let Some(index) = ordered
+        .iter()
+        .position(|(_, e)| Some(*e) == current_level.0)
+    else {
+        return;
+    };
+    let next = index as isize + step;
+    if next < 0 || next >= ordered.len() as isize {
+        return;
     }
+    current_level.0 = Some(ordered[next as usize].1);
 }
 
 pub fn keyboard_just_pressed_stream(
@@ -42,6 +42,21 @@ use workspace::*;
 
 pub mod sdf_loader;
 
+pub mod settings;
+use settings::EditorSettingsPlugin;
+
+pub mod editor_config;
+use editor_config::EditorConfigPlugin;
+
+#[cfg(target_arch = "wasm32")]
+pub mod web;
+
+#[cfg(feature = "collaboration")]
+pub mod collaboration;
+
+#[cfg(feature = "ros2")]
+pub mod ros2_bridge;
+
 pub mod site_asset_io;
 use sdf_loader::*;
 
@@ -55,6 +70,7 @@ use site::{OSMViewPlugin, SitePlugin};
 use site_asset_io::SiteAssetIoPlugin;
 
 pub mod osm_slippy_map;
+pub mod replay;
 use bevy::render::{
     render_resource::{AddressMode, SamplerDescriptor},
     settings::{WgpuFeatures, WgpuSettings},
@@ -64,7 +80,8 @@ pub use osm_slippy_map::*;
 
 #[cfg_attr(not(target_arch = "wasm32"), derive(Parser))]
 pub struct CommandLineArgs {
-    /// Filename of a Site (.site.ron) or Building (.building.yaml) file to load.
+    /// Filename of a Site (.site.ron), Building (.building.yaml), or traffic-editor Project
+    /// (.project.yaml) file to load.
     /// Exclude this argument to get the main menu.
     pub filename: Option<String>,
     /// Name of a Site (.site.ron) file to import on top of the base FILENAME.
@@ -73,6 +90,38 @@ pub struct CommandLineArgs {
     /// Run in headless mode and export the loaded site to the requested path.
     #[cfg_attr(not(target_arch = "wasm32"), arg(long))]
     pub headless_export: Option<String>,
+    /// Record the edits of the session into a replay file that can be attached to bug reports.
+    #[cfg_attr(not(target_arch = "wasm32"), arg(long))]
+    pub record: Option<String>,
+    /// Play back a replay file. The map that the replay was recorded with is loaded instead of
+    /// FILENAME.
+    #[cfg_attr(not(target_arch = "wasm32"), arg(long))]
+    pub replay: Option<String>,
+    /// Run a rhai script that edits FILENAME and save the result without
+    /// opening the editor.
+    #[cfg(feature = "scripting")]
+    #[cfg_attr(not(target_arch = "wasm32"), arg(long, requires = "filename"))]
+    pub script: Option<String>,
+    /// Where to save the site that was edited by --script. By default the
+    /// edited site replaces FILENAME.
+    #[cfg(feature = "scripting")]
+    #[cfg_attr(not(target_arch = "wasm32"), arg(long, requires = "script"))]
+    pub script_output: Option<String>,
+    /// Run in headless mode and render a top-down PNG thumbnail of every
+    /// level of FILENAME into the requested folder.
+    #[cfg_attr(not(target_arch = "wasm32"), arg(long, requires = "filename"))]
+    pub thumbnails: Option<String>,
+    /// Width and height in pixels of the images rendered by --thumbnails.
+    #[cfg_attr(
+        not(target_arch = "wasm32"),
+        arg(long, default_value_t = 1024, requires = "thumbnails")
+    )]
+    pub thumbnail_size: u32,
+    /// Compute the statistics of FILENAME, such as lane lengths and floor
+    /// areas, and save them to the requested JSON file without opening the
+    /// editor.
+    #[cfg_attr(not(target_arch = "wasm32"), arg(long, requires = "filename"))]
+    pub stats: Option<String>,
 }
 
 #[derive(Clone, Default, Eq, PartialEq, Debug, Hash, States)]
@@ -96,27 +145,133 @@ impl AppState {
 pub fn run(command_line_args: Vec<String>) {
     let mut app = App::new();
     let mut _headless_export = None;
+    let mut _headless_thumbnails = None;
 
     #[cfg(not(target_arch = "wasm32"))]
     {
         let command_line_args = CommandLineArgs::parse_from(command_line_args);
-        if let Some(path) = command_line_args.filename {
+        #[cfg(feature = "scripting")]
+        if let (Some(script), Some(path)) = (&command_line_args.script, &command_line_args.filename)
+        {
+            let output = command_line_args.script_output.as_ref().unwrap_or(path);
+            if let Err(err) = run_script_on_file(script.as_ref(), path.as_ref(), output.as_ref()) {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+            return;
+        }
+        if let (Some(output), Some(path)) = (&command_line_args.stats, &command_line_args.filename)
+        {
+            if let Err(err) = write_stats_of_file(path.as_ref(), output.as_ref()) {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+            return;
+        }
+        if let Some(replay) = &command_line_args.replay {
+            let (player, initial_map) = match replay::ReplayPlayer::open(replay.as_ref()) {
+                Ok(opened) => opened,
+                Err(err) => {
+                    eprintln!("Unable to open replay [{replay}]: {err}");
+                    return;
+                }
+            };
+            if let Some(initial_map) = initial_map {
+                match initial_map.prepare() {
+                    Ok(path) => {
+                        app.insert_resource(Autoload::file(path, None));
+                    }
+                    Err(err) => {
+                        eprintln!("Unable to prepare the map of replay [{replay}]: {err}");
+                        return;
+                    }
+                }
+            }
+            app.insert_resource(player);
+        } else if let Some(path) = &command_line_args.filename {
             app.insert_resource(Autoload::file(
                 path.into(),
                 command_line_args.import.map(Into::into),
             ));
         }
+        if let Some(record) = &command_line_args.record {
+            let initial_map = command_line_args
+                .filename
+                .as_ref()
+                .filter(|_| command_line_args.replay.is_none())
+                .map(std::path::Path::new);
+            match replay::ReplayRecorder::new(record.as_ref(), initial_map) {
+                Ok(recorder) => {
+                    app.insert_resource(recorder);
+                }
+                Err(err) => {
+                    eprintln!("Unable to record the session into [{record}]: {err}");
+                }
+            }
+        }
         _headless_export = command_line_args.headless_export;
+        _headless_thumbnails = command_line_args
+            .thumbnails
+            .map(|folder| (folder, command_line_args.thumbnail_size));
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        if let Some(url) = web::map_url_from_query() {
+            app.insert_resource(Autoload::url(url));
+        }
     }
 
-    app.add_plugins(SiteEditor::default().headless_export(_headless_export));
+    app.add_plugins(
+        SiteEditor::default()
+            .headless_export(_headless_export)
+            .headless_thumbnails(_headless_thumbnails),
+    );
     app.run();
 }
 
+/// Run a script on a site file and save the edited site.
+#[cfg(all(feature = "scripting", not(target_arch = "wasm32")))]
+fn run_script_on_file(
+    script: &std::path::Path,
+    input: &std::path::Path,
+    output: &std::path::Path,
+) -> Result<(), String> {
+    let script = std::fs::read_to_string(script)
+        .map_err(|err| format!("Unable to read script {script:?}: {err}"))?;
+    let site = site::load_map(input).map_err(|err| err.to_string())?;
+    let outcome = site.run_script(&script).map_err(|err| err.to_string())?;
+    for line in &outcome.output {
+        println!("{line}");
+    }
+    site::save_map(&outcome.site, output).map_err(|err| err.to_string())?;
+    println!(
+        "Changed {} elements and saved the site to {output:?}",
+        outcome.changes.count()
+    );
+    Ok(())
+}
+
+/// Compute the statistics of a site file and save them as JSON.
+#[cfg(not(target_arch = "wasm32"))]
+fn write_stats_of_file(input: &std::path::Path, output: &std::path::Path) -> Result<(), String> {
+    let site = site::load_map(input).map_err(|err| err.to_string())?;
+    let file = std::fs::File::create(output)
+        .map_err(|err| format!("Unable to create {output:?}: {err}"))?;
+    site.map_stats()
+        .to_writer_json(file)
+        .map_err(|err| err.to_string())?;
+    println!("Saved the statistics of {input:?} to {output:?}");
+    Ok(())
+}
+
 #[derive(Default)]
 pub struct SiteEditor {
     /// Contains Some(path) if the site editor is running in headless mode exporting its site.
     headless_export: Option<String>,
+    /// Contains Some((folder, size)) if the site editor is running in headless mode rendering
+    /// thumbnails of its levels.
+    headless_thumbnails: Option<(String, u32)>,
 }
 
 impl SiteEditor {
@@ -128,6 +283,15 @@ impl SiteEditor {
         self.headless_export = export_to_file;
         self
     }
+
+    pub fn headless_thumbnails(mut self, thumbnails: Option<(String, u32)>) -> Self {
+        self.headless_thumbnails = thumbnails;
+        self
+    }
+
+    fn is_headless(&self) -> bool {
+        self.headless_export.is_some() || self.headless_thumbnails.is_some()
+    }
 }
 
 impl Plugin for SiteEditor {
@@ -136,7 +300,7 @@ impl Plugin for SiteEditor {
         let headless = {
             #[cfg(not(target_arch = "wasm32"))]
             {
-                self.headless_export.is_some()
+                self.is_headless()
             }
             #[cfg(target_arch = "wasm32")]
             {
@@ -197,9 +361,11 @@ impl Plugin for SiteEditor {
                 LogHistoryPlugin,
                 AabbUpdatePlugin,
                 EguiPlugin,
+                EditorSettingsPlugin,
+                EditorConfigPlugin,
                 KeyboardInputPlugin,
                 SitePlugin,
-                InteractionPlugin::new().headless(self.headless_export.is_some()),
+                InteractionPlugin::new().headless(self.is_headless()),
                 AnimationPlugin,
                 OccupancyPlugin,
                 WorkspacePlugin,
@@ -207,10 +373,17 @@ impl Plugin for SiteEditor {
                 bevy_impulse::ImpulsePlugin::default(),
             ));
 
-        if self.headless_export.is_none() {
+        if !self.is_headless() {
             app.add_plugins((StandardUiPlugin::default(), MainMenuPlugin))
                 // Note order matters, plugins that edit the menus must be initialized after the UI
-                .add_plugins((site::ViewMenuPlugin, OSMViewPlugin, SiteWireframePlugin));
+                .add_plugins((site::ViewMenuPlugin, OSMViewPlugin, SiteWireframePlugin))
+                .add_plugins(replay::ReplayPlugin);
+
+            #[cfg(feature = "collaboration")]
+            app.add_plugins(collaboration::CollaborationPlugin::default());
+
+            #[cfg(feature = "ros2")]
+            app.add_plugins(ros2_bridge::Ros2BridgePlugin::default());
         }
 
         // Ref https://github.com/bevyengine/bevy/issues/10877. The default behavior causes issues
@@ -227,6 +400,15 @@ impl Plugin for SiteEditor {
             ));
             app.insert_resource(site::HeadlessSdfExportState::new(path));
             app.add_systems(Last, site::headless_sdf_export);
+        } else if let Some((folder, size)) = &self.headless_thumbnails {
+            app.add_plugins(ScheduleRunnerPlugin::run_loop(
+                std::time::Duration::from_secs_f64(1.0 / 30.0),
+            ));
+            app.insert_resource(site::HeadlessThumbnailState::new(
+                folder,
+                UVec2::splat(*size),
+            ));
+            app.add_systems(Last, site::headless_thumbnails);
         }
     }
 }
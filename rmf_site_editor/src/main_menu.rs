@@ -16,7 +16,10 @@
 */
 
 use super::demo_world::*;
-use crate::{AppState, Autoload, WorkspaceData, WorkspaceLoader};
+use crate::{
+    editor_config::EditorConfig, widgets::new_map_wizard::NewMapWizard, AppState, Autoload,
+    WorkspaceData, WorkspaceLoader,
+};
 use bevy::{app::AppExit, prelude::*, window::PrimaryWindow};
 use bevy_egui::{egui, EguiContexts};
 
@@ -27,6 +30,8 @@ fn egui_ui(
     mut _app_state: ResMut<State<AppState>>,
     autoload: Option<ResMut<Autoload>>,
     primary_windows: Query<Entity, With<PrimaryWindow>>,
+    mut config: ResMut<EditorConfig>,
+    mut wizard: Option<ResMut<NewMapWizard>>,
 ) {
     if let Some(mut autoload) = autoload {
         #[cfg(not(target_arch = "wasm32"))]
@@ -35,6 +40,14 @@ fn egui_ui(
                 workspace_loader.load_from_path(filename);
             }
         }
+        if let Some(url) = autoload.url.take() {
+            workspace_loader.load_from_url(url);
+        }
+        return;
+    }
+
+    if wizard.as_ref().is_some_and(|w| w.show) {
+        // The wizard takes the place of the welcome screen while it is open
         return;
     }
 
@@ -55,17 +68,65 @@ fn egui_ui(
             ui.heading("Welcome to The RMF Site Editor!");
             ui.add_space(10.);
 
+            if !config.recent_files.is_empty() {
+                ui.label("Recent maps");
+                let mut open = None;
+                let mut forget = None;
+                egui::Grid::new("recent_maps")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        for path in &config.recent_files {
+                            let name = path
+                                .file_name()
+                                .map(|n| n.to_string_lossy().into_owned())
+                                .unwrap_or_else(|| path.to_string_lossy().into_owned());
+                            let exists = path.exists();
+                            let hover = if exists {
+                                path.to_string_lossy().into_owned()
+                            } else {
+                                format!("{} no longer exists", path.display())
+                            };
+                            if ui
+                                .add_enabled(exists, egui::Button::new(name))
+                                .on_hover_text(&hover)
+                                .on_disabled_hover_text(&hover)
+                                .clicked()
+                            {
+                                open = Some(path.clone());
+                            }
+                            if ui
+                                .small_button("✖")
+                                .on_hover_text("Remove from recent maps")
+                                .clicked()
+                            {
+                                forget = Some(path.clone());
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(path) = forget {
+                    config.remove_recent_file(&path);
+                }
+                if let Some(path) = open {
+                    workspace_loader.load_from_path(path);
+                }
+                ui.add_space(10.);
+            }
+
             ui.horizontal(|ui| {
-                if ui.button("View demo map").clicked() {
-                    workspace_loader.load_from_data(WorkspaceData::LegacyBuilding(demo_office()));
+                if ui.button("New map…").clicked() {
+                    match &mut wizard {
+                        Some(wizard) => wizard.open(),
+                        None => workspace_loader.create_empty_from_dialog(),
+                    }
                 }
 
-                if ui.button("Open a file").clicked() {
+                if ui.button("Open…").clicked() {
                     workspace_loader.load_from_dialog();
                 }
 
-                if ui.button("Create new file").clicked() {
-                    workspace_loader.create_empty_from_dialog();
+                if ui.button("View demo map").clicked() {
+                    workspace_loader.load_from_data(WorkspaceData::LegacyBuilding(demo_office()));
                 }
             });
 
@@ -0,0 +1,583 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+//! Record the edits of an editor session into a replay file and play them
+//! back later, so that bugs can be reproduced by attaching the replay to an
+//! issue.
+//!
+//! A replay starts with a header that holds the map that was opened from the
+//! command line, followed by one line of json for every frame that had edits.
+//! The edits are the [`Change`] events of the components registered with
+//! [`RecordReplayChanges`], along with the [`MoveTo`] and [`Delete`] events.
+//! Elements are written as their [`SiteID`], so the replay does not depend on
+//! how entities were allocated. Each frame is flushed right away so that a
+//! replay survives a panic of the editor.
+//!
+//! Playing back a replay sends the same events again, one recorded frame per
+//! update, so the result does not depend on the timing of the original input
+//! or on the size of the window. Elements that were created
+//! during the recorded session have no [`SiteID`] until the site is saved,
+//! so their edits are left out of the replay with a warning.
+
+use crate::{
+    interaction::MoveTo,
+    site::{Change, Delete, SiteID},
+    CurrentWorkspace,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashSet};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    vec::IntoIter,
+};
+
+/// Version of the replay format that is written by this editor
+pub const REPLAY_FORMAT_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReplayHeader {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_map: Option<ReplayMap>,
+}
+
+/// The map that was opened when the recording started.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReplayMap {
+    pub path: PathBuf,
+    pub data: Vec<u8>,
+}
+
+impl ReplayMap {
+    /// Get a path that the map can be loaded from. The original file is used
+    /// as long as it has not changed, so that relative asset paths keep
+    /// working. Otherwise the recorded map is written into a temporary
+    /// folder.
+    pub fn prepare(&self) -> std::io::Result<PathBuf> {
+        if std::fs::read(&self.path).is_ok_and(|data| data == self.data) {
+            return Ok(self.path.clone());
+        }
+        let folder = std::env::temp_dir().join("rmf_site_editor_replay");
+        std::fs::create_dir_all(&folder)?;
+        let file_name = self
+            .path
+            .file_name()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| "map.site.ron".into());
+        let path = folder.join(file_name);
+        std::fs::write(&path, &self.data)?;
+        Ok(path)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ReplayFrame {
+    pub edits: Vec<ReplayEdit>,
+}
+
+/// An element of the site that was opened for the replay
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayElement {
+    /// The root entity of the site
+    Site,
+    /// An element with this [`SiteID`]
+    Id(u32),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayEdit {
+    Change {
+        element: ReplayElement,
+        /// Name that the component was registered with
+        component: String,
+        value: serde_json::Value,
+        #[serde(default)]
+        allow_insert: bool,
+    },
+    MoveTo {
+        element: ReplayElement,
+        translation: [f32; 3],
+        rotation: [f32; 4],
+        scale: [f32; 3],
+    },
+    Delete {
+        element: ReplayElement,
+        and_dependents: bool,
+    },
+}
+
+/// Insert this resource to record the session into a replay file.
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    /// Taken when the header gets written with the first frame
+    initial_map: Option<Option<ReplayMap>>,
+    failed: bool,
+    /// Edits of the current frame
+    edits: Vec<ReplayEdit>,
+    /// Entities whose edits could not be recorded, so that the warning about
+    /// them is only given once
+    skipped: HashSet<Entity>,
+}
+
+impl ReplayRecorder {
+    /// Start a replay file at `path`. The map at `initial_map` is stored in
+    /// the replay so that it can be played back on another machine.
+    pub fn new(path: &Path, initial_map: Option<&Path>) -> std::io::Result<Self> {
+        let initial_map = match initial_map {
+            Some(map) => Some(ReplayMap {
+                path: map.canonicalize().unwrap_or_else(|_| map.to_path_buf()),
+                data: std::fs::read(map)?,
+            }),
+            None => None,
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer: BufWriter::new(File::create(path)?),
+            initial_map: Some(initial_map),
+            failed: false,
+            edits: Vec::new(),
+            skipped: HashSet::new(),
+        })
+    }
+
+    fn write_line<T: Serialize>(&mut self, value: &T) {
+        if self.failed {
+            return;
+        }
+        let result = serde_json::to_writer(&mut self.writer, value)
+            .map_err(std::io::Error::from)
+            .and_then(|_| self.writer.write_all(b"\n"))
+            .and_then(|_| self.writer.flush());
+        if let Err(err) = result {
+            error!(
+                "Stopped recording the session into {}: {err}",
+                self.path.display()
+            );
+            self.failed = true;
+        }
+    }
+
+    /// Record an edit of `entity` if it can be found again when the replay
+    /// is played back.
+    fn record(
+        &mut self,
+        entity: Entity,
+        elements: &ReplayElements,
+        edit: impl FnOnce(ReplayElement) -> ReplayEdit,
+    ) {
+        match elements.element_of(entity) {
+            Some(element) => self.edits.push(edit(element)),
+            None => {
+                if self.skipped.insert(entity) {
+                    warn!(
+                        "Edits of {entity:?} are left out of the replay because it has no \
+                        SiteID yet. Save the site to record edits of new elements."
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Insert this resource to play back a replay file. The map of the replay
+/// needs to be loaded separately, see [`ReplayPlayer::open`].
+#[derive(Resource)]
+pub struct ReplayPlayer {
+    frames: IntoIter<ReplayFrame>,
+    /// The frame whose edits are being sent in this update
+    current: Option<ReplayFrame>,
+}
+
+impl ReplayPlayer {
+    /// Read a replay file, giving back the player along with the map that
+    /// the replay starts from.
+    pub fn open(path: &Path) -> Result<(Self, Option<ReplayMap>), String> {
+        let file = File::open(path).map_err(|err| err.to_string())?;
+        let mut lines = BufReader::new(file).lines();
+        let header = lines
+            .next()
+            .ok_or_else(|| "the replay is empty".to_owned())?
+            .map_err(|err| err.to_string())?;
+        let header: ReplayHeader = serde_json::from_str(&header).map_err(|err| err.to_string())?;
+        if header.version != REPLAY_FORMAT_VERSION {
+            return Err(format!(
+                "the replay was made with format version [{}] but only version [{}] can be \
+                played back",
+                header.version, REPLAY_FORMAT_VERSION,
+            ));
+        }
+
+        let mut frames = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let line = line.map_err(|err| err.to_string())?;
+            match serde_json::from_str(&line) {
+                Ok(frame) => frames.push(frame),
+                // The last frame is incomplete if the editor crashed in the
+                // middle of writing it.
+                Err(err) => {
+                    eprintln!("Ignoring the replay after frame {i}: {err}");
+                    break;
+                }
+            }
+        }
+
+        let player = Self {
+            frames: frames.into_iter(),
+            current: None,
+        };
+        Ok((player, header.initial_map))
+    }
+
+    fn current_edits(&self) -> impl Iterator<Item = &ReplayEdit> {
+        self.current.iter().flat_map(|frame| frame.edits.iter())
+    }
+}
+
+/// Finds the elements of edits in the current workspace
+#[derive(SystemParam)]
+struct ReplayElements<'w, 's> {
+    current_workspace: Res<'w, CurrentWorkspace>,
+    site_ids: Query<'w, 's, (Entity, &'static SiteID)>,
+    parents: Query<'w, 's, &'static Parent>,
+}
+
+impl<'w, 's> ReplayElements<'w, 's> {
+    fn element_of(&self, entity: Entity) -> Option<ReplayElement> {
+        let root = self.current_workspace.root?;
+        if entity == root {
+            return Some(ReplayElement::Site);
+        }
+        let (_, id) = self.site_ids.get(entity).ok()?;
+        AncestorIter::new(&self.parents, entity)
+            .any(|p| p == root)
+            .then_some(ReplayElement::Id(id.0))
+    }
+
+    fn entity_of(&self, element: ReplayElement) -> Option<Entity> {
+        let root = self.current_workspace.root?;
+        let ReplayElement::Id(id) = element else {
+            return Some(root);
+        };
+        self.site_ids
+            .iter()
+            .find(|(e, site_id)| {
+                site_id.0 == id && AncestorIter::new(&self.parents, *e).any(|p| p == root)
+            })
+            .map(|(e, _)| e)
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ReplaySet {
+    Record,
+    Play,
+}
+
+/// Name of a component in replays
+#[derive(Resource)]
+struct ReplayComponent<T> {
+    name: &'static str,
+    _ignore: PhantomData<fn(T)>,
+}
+
+pub trait RecordReplayChanges {
+    /// Record the [`Change`] events of a component into replays under `name`
+    /// and send them again when a replay is played back.
+    fn record_replay_changes<T>(&mut self, name: &'static str) -> &mut Self
+    where
+        T: Component + Clone + Debug + Serialize + DeserializeOwned;
+}
+
+impl RecordReplayChanges for App {
+    fn record_replay_changes<T>(&mut self, name: &'static str) -> &mut Self
+    where
+        T: Component + Clone + Debug + Serialize + DeserializeOwned,
+    {
+        self.insert_resource(ReplayComponent::<T> {
+            name,
+            _ignore: PhantomData,
+        })
+        .add_systems(
+            Last,
+            record_changes::<T>
+                .in_set(ReplaySet::Record)
+                .run_if(resource_exists::<ReplayRecorder>()),
+        )
+        .add_systems(
+            Update,
+            play_changes::<T>
+                .in_set(ReplaySet::Play)
+                .run_if(resource_exists::<ReplayPlayer>()),
+        )
+    }
+}
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        use rmf_site_format::*;
+        app.add_systems(
+            PreUpdate,
+            advance_replay.run_if(resource_exists::<ReplayPlayer>()),
+        )
+        .add_systems(
+            Update,
+            play_moves_and_deletions
+                .in_set(ReplaySet::Play)
+                .run_if(resource_exists::<ReplayPlayer>()),
+        )
+        .add_systems(
+            Last,
+            (
+                record_moves_and_deletions.in_set(ReplaySet::Record),
+                write_replay_frame.after(ReplaySet::Record),
+            )
+                .run_if(resource_exists::<ReplayRecorder>()),
+        )
+        .record_replay_changes::<NameOfSite>("name_of_site")
+        .record_replay_changes::<NameInSite>("name_in_site")
+        .record_replay_changes::<Pose>("pose")
+        .record_replay_changes::<Scale>("scale")
+        .record_replay_changes::<Distance>("distance")
+        .record_replay_changes::<Motion>("motion")
+        .record_replay_changes::<ReverseLane>("reverse_lane")
+        .record_replay_changes::<DoorType>("door_type")
+        .record_replay_changes::<DoorTiming>("door_timing")
+        .record_replay_changes::<LiftTiming>("lift_timing")
+        .record_replay_changes::<LevelElevation>("level_elevation")
+        .record_replay_changes::<FlattenedOffset>("flattened_offset")
+        .record_replay_changes::<AssetSource>("asset_source")
+        .record_replay_changes::<Texture>("texture")
+        .record_replay_changes::<PixelsPerMeter>("pixels_per_meter")
+        .record_replay_changes::<DrawingSourceInfo>("drawing_source")
+        .record_replay_changes::<Rectification>("rectification")
+        .record_replay_changes::<PaperSpace>("paper_space")
+        .record_replay_changes::<PhysicalCameraProperties>("physical_camera_properties")
+        .record_replay_changes::<LightKind>("light_kind")
+        .record_replay_changes::<LocationTags>("location_tags")
+        .record_replay_changes::<LayerVisibility>("layer_visibility")
+        .record_replay_changes::<PreferredSemiTransparency>("preferred_semi_transparency")
+        .record_replay_changes::<WallHeight>("wall_height")
+        .record_replay_changes::<WallAlpha>("wall_alpha");
+    }
+}
+
+fn record_changes<T: Component + Clone + Debug + Serialize>(
+    mut recorder: ResMut<ReplayRecorder>,
+    component: Res<ReplayComponent<T>>,
+    mut changes: EventReader<Change<T>>,
+    elements: ReplayElements,
+) {
+    for change in changes.read() {
+        let value = match serde_json::to_value(&change.to_value) {
+            Ok(value) => value,
+            Err(err) => {
+                error!("Unable to record a change of {}: {err}", component.name);
+                continue;
+            }
+        };
+        recorder.record(change.for_element, &elements, |element| {
+            ReplayEdit::Change {
+                element,
+                component: component.name.to_owned(),
+                value,
+                allow_insert: change.allow_insert,
+            }
+        });
+    }
+}
+
+fn record_moves_and_deletions(
+    mut recorder: ResMut<ReplayRecorder>,
+    mut moves: EventReader<MoveTo>,
+    mut deletions: EventReader<Delete>,
+    elements: ReplayElements,
+) {
+    for move_to in moves.read() {
+        let tf = move_to.transform;
+        recorder.record(move_to.entity, &elements, |element| ReplayEdit::MoveTo {
+            element,
+            translation: tf.translation.to_array(),
+            rotation: tf.rotation.to_array(),
+            scale: tf.scale.to_array(),
+        });
+    }
+    for delete in deletions.read() {
+        recorder.record(delete.element, &elements, |element| ReplayEdit::Delete {
+            element,
+            and_dependents: delete.and_dependents,
+        });
+    }
+}
+
+fn write_replay_frame(mut recorder: ResMut<ReplayRecorder>) {
+    if let Some(initial_map) = recorder.initial_map.take() {
+        recorder.write_line(&ReplayHeader {
+            version: REPLAY_FORMAT_VERSION,
+            initial_map,
+        });
+        info!("Recording the session into {}", recorder.path.display());
+    }
+
+    if recorder.edits.is_empty() {
+        return;
+    }
+    let frame = ReplayFrame {
+        edits: std::mem::take(&mut recorder.edits),
+    };
+    recorder.write_line(&frame);
+}
+
+/// Move on to the next frame of edits once the map of the replay is open.
+fn advance_replay(
+    mut commands: Commands,
+    mut player: ResMut<ReplayPlayer>,
+    current_workspace: Res<CurrentWorkspace>,
+    children: Query<&Children>,
+) {
+    let Some(root) = current_workspace.root else {
+        return;
+    };
+    if player.current.is_none() && children.get(root).is_err() {
+        // The site is still being loaded
+        return;
+    }
+
+    player.current = player.frames.next();
+    if player.current.is_none() {
+        info!("Finished playing back the replay");
+        commands.remove_resource::<ReplayPlayer>();
+    }
+}
+
+fn play_changes<T: Component + Clone + Debug + DeserializeOwned>(
+    player: Res<ReplayPlayer>,
+    component: Res<ReplayComponent<T>>,
+    mut changes: EventWriter<Change<T>>,
+    elements: ReplayElements,
+) {
+    for edit in player.current_edits() {
+        let ReplayEdit::Change {
+            element,
+            component: name,
+            value,
+            allow_insert,
+        } = edit
+        else {
+            continue;
+        };
+        if name != component.name {
+            continue;
+        }
+        let Some(entity) = elements.entity_of(*element) else {
+            warn!("Unable to find {element:?} to replay a change of {name}");
+            continue;
+        };
+        match serde_json::from_value::<T>(value.clone()) {
+            Ok(to_value) => {
+                changes.send(Change {
+                    to_value,
+                    for_element: entity,
+                    allow_insert: *allow_insert,
+                });
+            }
+            Err(err) => {
+                warn!("Unable to replay a change of {name}: {err}");
+            }
+        }
+    }
+}
+
+fn play_moves_and_deletions(
+    player: Res<ReplayPlayer>,
+    mut moves: EventWriter<MoveTo>,
+    mut deletions: EventWriter<Delete>,
+    elements: ReplayElements,
+) {
+    for edit in player.current_edits() {
+        match edit {
+            ReplayEdit::Change { .. } => {}
+            ReplayEdit::MoveTo {
+                element,
+                translation,
+                rotation,
+                scale,
+            } => {
+                let Some(entity) = elements.entity_of(*element) else {
+                    warn!("Unable to find {element:?} to replay moving it");
+                    continue;
+                };
+                moves.send(MoveTo {
+                    entity,
+                    transform: Transform {
+                        translation: Vec3::from_array(*translation),
+                        rotation: Quat::from_array(*rotation),
+                        scale: Vec3::from_array(*scale),
+                    },
+                });
+            }
+            ReplayEdit::Delete {
+                element,
+                and_dependents,
+            } => {
+                let Some(entity) = elements.entity_of(*element) else {
+                    warn!("Unable to find {element:?} to replay deleting it");
+                    continue;
+                };
+                deletions.send(Delete {
+                    element: entity,
+                    and_dependents: *and_dependents,
+                });
+            }
+        }
+    }
+}
+
+#[test]
+fn test_replay_elements_are_found_by_site_id() {
+    use bevy::ecs::system::SystemState;
+
+    let mut world = World::new();
+    let root = world.spawn_empty().id();
+    let level = world.spawn(SiteID(1)).set_parent(root).id();
+    let anchor = world.spawn(SiteID(3)).set_parent(level).id();
+    let other_root = world.spawn_empty().id();
+    world.spawn(SiteID(3)).set_parent(other_root);
+    let new_element = world.spawn_empty().set_parent(level).id();
+    world.insert_resource(CurrentWorkspace {
+        root: Some(root),
+        display: true,
+    });
+
+    let mut state: SystemState<ReplayElements> = SystemState::new(&mut world);
+    let elements = state.get(&world);
+    assert_eq!(elements.element_of(root), Some(ReplayElement::Site));
+    assert_eq!(elements.element_of(anchor), Some(ReplayElement::Id(3)));
+    assert_eq!(elements.element_of(new_element), None);
+    assert_eq!(elements.entity_of(ReplayElement::Id(3)), Some(anchor));
+    assert_eq!(elements.entity_of(ReplayElement::Site), Some(root));
+    assert_eq!(elements.entity_of(ReplayElement::Id(7)), None);
+}
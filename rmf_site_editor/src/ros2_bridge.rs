@@ -0,0 +1,494 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+//! A bridge to ROS 2 that lets the editor act as a minimal live site viewer.
+//! The current site is published as an `rmf_building_map_msgs/BuildingMap`
+//! whenever it is opened or saved, and the robots of every fleet that
+//! publishes on `fleet_states` are drawn on the level that they report.
+//!
+//! This needs a sourced ROS 2 environment with the RMF messages installed
+//! when building with the `ros2` feature.
+
+use crate::{
+    site::{
+        generate_site, old_default_material, Category, DoorType, ExportOptions, ExportSettings,
+        LevelElevation, NameInSite, SaveSite, Site, Swing,
+    },
+    AppState, CurrentWorkspace,
+};
+use bevy::{prelude::*, render::mesh::shape::Box as BoxShape};
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
+use futures_lite::{future, StreamExt};
+use r2r::{
+    rmf_building_map_msgs::msg::{
+        BuildingMap, Door as DoorMsg, Graph, GraphEdge, GraphNode, Level as LevelMsg,
+        Lift as LiftMsg, Param,
+    },
+    rmf_fleet_msgs::msg::FleetState,
+    QosProfile,
+};
+use rmf_site_format::legacy::nav_graph::NavGraph;
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+const NODE_NAME: &str = "rmf_site_editor";
+const MAP_TOPIC: &str = "/map";
+const FLEET_STATES_TOPIC: &str = "/fleet_states";
+const SPIN_INTERVAL: Duration = Duration::from_millis(20);
+
+const ROBOT_MARKER_SIZE: [f32; 3] = [0.7, 0.45, 0.3];
+
+// Values of the constants in rmf_building_map_msgs
+const DOOR_TYPE_UNDEFINED: u8 = 0;
+const DOOR_TYPE_SINGLE_SLIDING: u8 = 1;
+const DOOR_TYPE_DOUBLE_SLIDING: u8 = 2;
+const DOOR_TYPE_SINGLE_SWING: u8 = 5;
+const DOOR_TYPE_DOUBLE_SWING: u8 = 6;
+const EDGE_TYPE_UNIDIRECTIONAL: u8 = 1;
+const PARAM_TYPE_STRING: u32 = 1;
+const PARAM_TYPE_DOUBLE: u32 = 3;
+const PARAM_TYPE_BOOL: u32 = 4;
+
+#[derive(Default)]
+pub struct Ros2BridgePlugin {}
+
+impl Plugin for Ros2BridgePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Ros2Bridge>()
+            .init_resource::<LiveRobotAssets>()
+            .add_systems(
+                Update,
+                (
+                    request_building_map,
+                    publish_building_map,
+                    receive_fleet_states,
+                    update_live_robots,
+                )
+                    .chain()
+                    .run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+/// The latest state of a robot that was reported by its fleet.
+#[derive(Debug, Clone)]
+pub struct LiveRobot {
+    pub level: String,
+    /// Position in the coordinates of the site
+    pub position: Vec2,
+    pub yaw: f32,
+}
+
+#[derive(Resource)]
+pub struct Ros2Bridge {
+    /// Robots of each fleet, keyed by (fleet name, robot name)
+    pub robots: HashMap<(String, String), LiveRobot>,
+    publish_requested: bool,
+    maps: Sender<BuildingMap>,
+    fleet_states: Receiver<FleetState>,
+}
+
+impl Ros2Bridge {
+    /// Publish the current site again on the next update.
+    pub fn request_publish(&mut self) {
+        self.publish_requested = true;
+    }
+}
+
+impl FromWorld for Ros2Bridge {
+    fn from_world(_: &mut World) -> Self {
+        let (maps_tx, maps_rx) = crossbeam_channel::unbounded();
+        let (states_tx, states_rx) = crossbeam_channel::unbounded();
+        std::thread::spawn(move || {
+            if let Err(err) = run_bridge(maps_rx, states_tx) {
+                error!("The ROS 2 bridge stopped: {err}");
+            }
+        });
+        Self {
+            robots: HashMap::new(),
+            publish_requested: false,
+            maps: maps_tx,
+            fleet_states: states_rx,
+        }
+    }
+}
+
+/// The ROS 2 node lives on its own thread because it needs to be spun
+/// independently of the frame rate of the editor.
+fn run_bridge(maps: Receiver<BuildingMap>, fleet_states: Sender<FleetState>) -> r2r::Result<()> {
+    let ctx = r2r::Context::create()?;
+    let mut node = r2r::Node::create(ctx, NODE_NAME, "")?;
+    // Late joiners such as schedule visualizers need to receive the last map
+    // that was published.
+    let publisher = node.create_publisher::<BuildingMap>(
+        MAP_TOPIC,
+        QosProfile::default().keep_last(1).transient_local(),
+    )?;
+    let mut subscription =
+        node.subscribe::<FleetState>(FLEET_STATES_TOPIC, QosProfile::default())?;
+
+    loop {
+        node.spin_once(SPIN_INTERVAL);
+        loop {
+            match maps.try_recv() {
+                Ok(map) => publisher.publish(&map)?,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return Ok(()),
+            }
+        }
+        while let Some(Some(state)) = future::block_on(future::poll_once(subscription.next())) {
+            if fleet_states.send(state).is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn request_building_map(
+    mut bridge: ResMut<Ros2Bridge>,
+    current_workspace: Res<CurrentWorkspace>,
+    mut saves: EventReader<SaveSite>,
+) {
+    if current_workspace.is_changed() || saves.read().count() > 0 {
+        bridge.request_publish();
+    }
+}
+
+fn publish_building_map(world: &mut World) {
+    if !world.resource::<Ros2Bridge>().publish_requested {
+        return;
+    }
+    world.resource_mut::<Ros2Bridge>().publish_requested = false;
+    let Some(root) = world.resource::<CurrentWorkspace>().root else {
+        return;
+    };
+    match generate_site(world, root) {
+        Ok(site) => {
+            let _ = world
+                .resource::<Ros2Bridge>()
+                .maps
+                .send(building_map_message(&site));
+        }
+        Err(err) => error!("Unable to publish the building map: {err}"),
+    }
+}
+
+fn receive_fleet_states(
+    mut bridge: ResMut<Ros2Bridge>,
+    current_workspace: Res<CurrentWorkspace>,
+    export_settings: Query<&ExportSettings>,
+) {
+    let options = current_workspace
+        .root
+        .and_then(|root| export_settings.get(root).ok())
+        .map(|settings| settings.nav_graph.clone())
+        .unwrap_or_default();
+    let bridge = &mut *bridge;
+    for state in bridge.fleet_states.try_iter() {
+        for robot in state.robots {
+            let location = robot.location;
+            bridge.robots.insert(
+                (state.name.clone(), robot.name),
+                LiveRobot {
+                    level: location.level_name,
+                    position: site_point(&options, [location.x, location.y]),
+                    yaw: location.yaw,
+                },
+            );
+        }
+    }
+}
+
+/// Marks the visual of a robot that was reported by a fleet.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct LiveRobotMarker {
+    pub fleet: String,
+    pub robot: String,
+}
+
+#[derive(Resource)]
+pub struct LiveRobotAssets {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for LiveRobotAssets {
+    fn from_world(world: &mut World) -> Self {
+        let [x, y, z] = ROBOT_MARKER_SIZE;
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Mesh::from(BoxShape::new(x, y, z)));
+        let material = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(old_default_material(Color::rgb(0.1, 0.8, 0.3)));
+        Self { mesh, material }
+    }
+}
+
+/// Robot markers are children of their levels, so they only show up while
+/// the level that the robot is on is displayed.
+fn update_live_robots(
+    mut commands: Commands,
+    bridge: Res<Ros2Bridge>,
+    assets: Res<LiveRobotAssets>,
+    current_workspace: Res<CurrentWorkspace>,
+    levels: Query<(Entity, &NameInSite, &Parent), With<LevelElevation>>,
+    mut markers: Query<(Entity, &LiveRobotMarker, &Parent, &mut Transform)>,
+) {
+    let Some(root) = current_workspace.root else {
+        return;
+    };
+    let level_named = |name: &str| {
+        levels
+            .iter()
+            .find(|(_, n, parent)| parent.get() == root && n.0 == name)
+            .map(|(e, ..)| e)
+    };
+    let transform = |robot: &LiveRobot| Transform {
+        translation: robot.position.extend(ROBOT_MARKER_SIZE[2] / 2.0),
+        rotation: Quat::from_rotation_z(robot.yaw),
+        ..default()
+    };
+
+    let mut shown = HashSet::new();
+    for (e, marker, parent, mut tf) in &mut markers {
+        let key = (marker.fleet.clone(), marker.robot.clone());
+        let robot = bridge.robots.get(&key);
+        match robot.and_then(|r| level_named(&r.level).map(|level| (r, level))) {
+            Some((robot, level)) => {
+                if parent.get() != level {
+                    commands.entity(e).set_parent(level);
+                }
+                *tf = transform(robot);
+                shown.insert(key);
+            }
+            None => commands.entity(e).despawn_recursive(),
+        }
+    }
+
+    for ((fleet, name), robot) in &bridge.robots {
+        if shown.contains(&(fleet.clone(), name.clone())) {
+            continue;
+        }
+        let Some(level) = level_named(&robot.level) else {
+            continue;
+        };
+        commands
+            .spawn((
+                PbrBundle {
+                    mesh: assets.mesh.clone(),
+                    material: assets.material.clone(),
+                    transform: transform(robot),
+                    ..default()
+                },
+                LiveRobotMarker {
+                    fleet: fleet.clone(),
+                    robot: name.clone(),
+                },
+            ))
+            .set_parent(level);
+    }
+}
+
+/// Fleets report positions in the coordinates of the nav graphs that they
+/// were given, so undo the export options of the nav graphs.
+fn site_point(options: &ExportOptions, p: [f32; 2]) -> Vec2 {
+    let [ox, oy, _] = options.origin;
+    Vec2::new(p[0] / options.scale + ox, p[1] / options.scale + oy)
+}
+
+/// The inverse of [`site_point`].
+fn map_point(options: &ExportOptions, p: [f32; 2]) -> [f32; 2] {
+    let [ox, oy, _] = options.origin;
+    [options.scale * (p[0] - ox), options.scale * (p[1] - oy)]
+}
+
+fn bool_param(name: &str) -> Param {
+    Param {
+        name: name.to_owned(),
+        type_: PARAM_TYPE_BOOL,
+        value_bool: true,
+        ..Default::default()
+    }
+}
+
+fn string_param(name: &str, value: &str) -> Param {
+    Param {
+        name: name.to_owned(),
+        type_: PARAM_TYPE_STRING,
+        value_string: value.to_owned(),
+        ..Default::default()
+    }
+}
+
+fn door_message(name: &str, endpoints: [[f32; 2]; 2], kind: &DoorType) -> DoorMsg {
+    let swing = |swing: &Swing| match swing {
+        Swing::Forward(angle) => (angle.radians(), 1),
+        Swing::Backward(angle) => (angle.radians(), -1),
+        Swing::Both { forward, .. } => (forward.radians(), 1),
+    };
+    let (door_type, (motion_range, motion_direction)) = match kind {
+        DoorType::SingleSliding(_) => (DOOR_TYPE_SINGLE_SLIDING, (0.0, 1)),
+        DoorType::DoubleSliding(_) => (DOOR_TYPE_DOUBLE_SLIDING, (0.0, 1)),
+        DoorType::SingleSwing(door) => (DOOR_TYPE_SINGLE_SWING, swing(&door.swing)),
+        DoorType::DoubleSwing(door) => (DOOR_TYPE_DOUBLE_SWING, swing(&door.swing)),
+        DoorType::Model(_) => (DOOR_TYPE_UNDEFINED, (0.0, 1)),
+    };
+    let [[v1_x, v1_y], [v2_x, v2_y]] = endpoints;
+    DoorMsg {
+        name: name.to_owned(),
+        v1_x,
+        v1_y,
+        v2_x,
+        v2_y,
+        door_type,
+        motion_range,
+        motion_direction,
+    }
+}
+
+/// Convert a site into the message that RMF uses to share building maps.
+/// Coordinates follow the export options of the nav graphs, so they match
+/// the nav graphs that fleets were given. Floor images and wall graphs are
+/// left empty.
+pub fn building_map_message(site: &Site) -> BuildingMap {
+    let options = &site.properties.export_settings.nav_graph;
+    let graphs = NavGraph::from_site(site);
+
+    let mut levels = Vec::new();
+    for level in site.levels.values() {
+        let level_name = &level.properties.name.0;
+        let mut doors = Vec::new();
+        for door in level.doors.values() {
+            let (Some(v0), Some(v1)) = (
+                site.get_anchor(door.anchors.start()),
+                site.get_anchor(door.anchors.end()),
+            ) else {
+                continue;
+            };
+            let endpoints = [
+                map_point(options, v0.translation_for_category(Category::Door)),
+                map_point(options, v1.translation_for_category(Category::Door)),
+            ];
+            doors.push(door_message(&door.name.0, endpoints, &door.kind));
+        }
+
+        let mut nav_graphs = Vec::new();
+        for (graph_name, graph) in &graphs {
+            let Some(nav_level) = graph.levels.get(level_name) else {
+                continue;
+            };
+            let vertices = nav_level
+                .vertices
+                .iter()
+                .map(|v| {
+                    let mut params = Vec::new();
+                    for (flag, name) in [
+                        (v.2.is_charger, "is_charger"),
+                        (v.2.is_holding_point, "is_holding_point"),
+                        (v.2.is_parking_spot, "is_parking_spot"),
+                    ] {
+                        if flag {
+                            params.push(bool_param(name));
+                        }
+                    }
+                    if let Some(lift) = &v.2.lift {
+                        params.push(string_param("lift_name", lift));
+                    }
+                    GraphNode {
+                        x: v.0,
+                        y: v.1,
+                        name: v.2.name.clone(),
+                        params,
+                    }
+                })
+                .collect();
+            let edges = nav_level
+                .lanes
+                .iter()
+                .map(|lane| {
+                    let mut params = vec![Param {
+                        name: "speed_limit".to_owned(),
+                        type_: PARAM_TYPE_DOUBLE,
+                        value_float: lane.2.speed_limit,
+                        ..Default::default()
+                    }];
+                    if let Some(door) = &lane.2.door_name {
+                        params.push(string_param("door_name", door));
+                    }
+                    if let Some(dock) = &lane.2.dock_name {
+                        params.push(string_param("dock_name", dock));
+                    }
+                    GraphEdge {
+                        v1_idx: lane.0 as u32,
+                        v2_idx: lane.1 as u32,
+                        params,
+                        edge_type: EDGE_TYPE_UNIDIRECTIONAL,
+                    }
+                })
+                .collect();
+            nav_graphs.push(Graph {
+                name: graph_name.clone(),
+                vertices,
+                edges,
+                ..Default::default()
+            });
+        }
+
+        levels.push(LevelMsg {
+            name: level_name.clone(),
+            elevation: level.properties.elevation.0,
+            doors,
+            nav_graphs,
+            ..Default::default()
+        });
+    }
+
+    // Every nav graph describes the same lifts, so the first one is enough.
+    let level_names: Vec<String> = site
+        .levels
+        .values()
+        .map(|l| l.properties.name.0.clone())
+        .collect();
+    let lifts = graphs
+        .first()
+        .map(|(_, graph)| {
+            graph
+                .lifts
+                .iter()
+                .map(|(name, lift)| LiftMsg {
+                    name: name.clone(),
+                    levels: level_names.clone(),
+                    ref_x: lift.position[0],
+                    ref_y: lift.position[1],
+                    ref_yaw: lift.position[2],
+                    width: lift.dims[0],
+                    depth: lift.dims[1],
+                    ..Default::default()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    BuildingMap {
+        name: site.properties.name.0.clone(),
+        levels,
+        lifts,
+    }
+}
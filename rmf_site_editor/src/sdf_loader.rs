@@ -28,7 +28,7 @@ use sdformat_rs::{SdfGeometry, SdfPose, Vector3d};
 use crate::site::{CollisionMeshMarker, VisualMeshMarker};
 use rmf_site_format::{
     Angle, AssetSource, Category, IsStatic, Model, ModelMarker, NameInSite, Pose, PrimitiveShape,
-    Rotation, Scale,
+    Rotation, Scale, ZOffset,
 };
 
 pub struct SdfPlugin;
@@ -43,6 +43,7 @@ impl Plugin for SdfPlugin {
             .register_type::<AssetSource>()
             .register_type::<Pose>()
             .register_type::<IsStatic>()
+            .register_type::<ZOffset>()
             .register_type::<Scale>()
             .register_type::<ModelMarker>()
             .register_type::<VisualMeshMarker>()
@@ -202,7 +203,7 @@ fn spawn_geometry<'a, 'b>(
                     pose,
                     is_static: IsStatic(is_static),
                     scale: parse_scale(&mesh.scale),
-                    marker: ModelMarker,
+                    ..Default::default()
                 })
                 .id(),
         ),
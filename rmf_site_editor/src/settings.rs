@@ -0,0 +1,750 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+//! User preferences of the editor. These are stored in a settings file that
+//! is watched while the editor runs, so any change to the file is applied
+//! right away without needing to restart.
+
+use crate::site::{Category, SiteAssets};
+use bevy::prelude::{Input as UserInput, *};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf, time::SystemTime};
+
+/// How often the settings file is checked for changes
+const WATCH_PERIOD_SECS: f32 = 1.0;
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct EditorSettings {
+    pub colors: ColorSettings,
+    pub keybindings: KeyBindings,
+    pub snapping: SnapSettings,
+    pub camera: CameraSettings,
+    /// Local folders that the asset gallery searches for models. Every
+    /// subfolder that contains a `model.sdf` file is listed as a model.
+    pub model_folders: Vec<PathBuf>,
+    /// Manifests of materials that can be assigned to floors and walls, in
+    /// addition to the materials that ship with the editor.
+    pub material_libraries: Vec<PathBuf>,
+    pub layers: LayerSettings,
+    /// The unit that lengths are shown and entered in. Sites are always
+    /// saved in meters.
+    pub units: LengthUnit,
+    pub backups: BackupSettings,
+}
+
+/// Colors are sRGB components in the range [0, 1].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ColorSettings {
+    pub background: [f32; 3],
+    pub hover: [f32; 3],
+    pub select: [f32; 3],
+    pub hover_select: [f32; 3],
+}
+
+impl Default for ColorSettings {
+    fn default() -> Self {
+        Self {
+            background: [0.0, 0.0, 0.0],
+            hover: [0.3, 1.0, 1.0],
+            select: [1.0, 0.3, 1.0],
+            hover_select: [1.0, 0.0, 0.3],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SnapSettings {
+    /// Snap placed and dragged points onto a grid
+    pub grid: bool,
+    /// Distance between grid lines, in meters
+    pub grid_spacing: f32,
+    /// Snap the direction of new lanes and walls to multiples of an angle
+    pub angle: bool,
+    /// The angle increment, in degrees
+    pub angle_increment: f32,
+    /// Snap onto existing anchors that are close to the cursor
+    pub vertex: bool,
+    /// How close the cursor needs to be to an anchor, in logical pixels
+    pub vertex_threshold: f32,
+}
+
+impl Default for SnapSettings {
+    fn default() -> Self {
+        Self {
+            grid: false,
+            grid_spacing: 0.5,
+            angle: false,
+            angle_increment: 45.0,
+            vertex: true,
+            vertex_threshold: 10.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct CameraSettings {
+    /// The projection that was chosen most recently. The editor starts with
+    /// the same projection that it was last used with.
+    pub orthographic: bool,
+    /// How long switching between the orthographic and perspective views is
+    /// animated, in seconds. Zero switches right away.
+    pub transition_duration: f32,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            orthographic: false,
+            transition_duration: 0.6,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct BackupSettings {
+    /// How many backups are kept of each site file. The file that is about
+    /// to be overwritten by a save becomes `.bak`, the previous `.bak`
+    /// becomes `.bak1`, and so on. Zero turns backups off.
+    pub depth: usize,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self { depth: 3 }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LengthUnit {
+    #[default]
+    Meters,
+    Feet,
+}
+
+impl LengthUnit {
+    pub const ALL: [LengthUnit; 2] = [LengthUnit::Meters, LengthUnit::Feet];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Meters => "Meters",
+            Self::Feet => "Feet",
+        }
+    }
+
+    /// The suffix to put after a length in this unit, including a leading
+    /// space.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Self::Meters => " m",
+            Self::Feet => " ft",
+        }
+    }
+
+    /// How many of this unit fit into one meter
+    pub fn per_meter(&self) -> f64 {
+        match self {
+            Self::Meters => 1.0,
+            Self::Feet => 1.0 / 0.3048,
+        }
+    }
+
+    pub fn from_meters(&self, meters: f32) -> f32 {
+        (meters as f64 * self.per_meter()) as f32
+    }
+
+    /// Write a length with a fixed number of decimals, followed by the unit.
+    pub fn format(&self, meters: f32, decimals: usize) -> String {
+        format!("{:.*}{}", decimals, self.from_meters(meters), self.suffix())
+    }
+}
+
+/// Whether the elements of one type are hidden or locked on a level. Hidden
+/// elements cannot be picked either.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct LayerToggle {
+    pub hidden: bool,
+    pub locked: bool,
+}
+
+impl LayerToggle {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The layer toggles of each level, keyed by the name of the level so they
+/// carry over into the next session.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct LayerSettings(pub BTreeMap<String, BTreeMap<Category, LayerToggle>>);
+
+impl LayerSettings {
+    pub fn get(&self, level: &str, category: Category) -> LayerToggle {
+        self.0
+            .get(level)
+            .and_then(|toggles| toggles.get(&category))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn set(&mut self, level: &str, category: Category, toggle: LayerToggle) {
+        if toggle.is_default() {
+            if let Some(toggles) = self.0.get_mut(level) {
+                toggles.remove(&category);
+                if toggles.is_empty() {
+                    self.0.remove(level);
+                }
+            }
+        } else {
+            self.0
+                .entry(level.to_owned())
+                .or_default()
+                .insert(category, toggle);
+        }
+    }
+}
+
+/// A key along with the modifiers that need to be held down for it. This is
+/// written in settings files as text, e.g. `"Ctrl+Shift+V"`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyChord {
+    pub key: KeyCode,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyChord {
+    pub const fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    pub const fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    pub const fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    pub const fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    /// The chord that was just pressed, if any. Modifier keys on their own do
+    /// not count as a chord, and only keys that can be written into the
+    /// settings file are considered.
+    pub fn from_just_pressed(input: &UserInput<KeyCode>) -> Option<Self> {
+        let key = input
+            .get_just_pressed()
+            .find(|key| KEY_NAMES.iter().any(|(_, k)| k == *key))?;
+        Some(Self {
+            key: *key,
+            ctrl: input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]),
+            shift: input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+            alt: input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]),
+        })
+    }
+
+    /// Check if the key was just pressed while exactly the modifiers of this
+    /// chord are held down.
+    pub fn just_pressed(&self, input: &UserInput<KeyCode>) -> bool {
+        let ctrl = input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+        let shift = input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        let alt = input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+        input.just_pressed(self.key) && ctrl == self.ctrl && shift == self.shift && alt == self.alt
+    }
+}
+
+impl std::fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        let name = KEY_NAMES
+            .iter()
+            .find(|(_, key)| *key == self.key)
+            .map(|(name, _)| *name)
+            .unwrap_or("?");
+        write!(f, "{name}")
+    }
+}
+
+impl From<KeyChord> for String {
+    fn from(value: KeyChord) -> Self {
+        value.to_string()
+    }
+}
+
+impl TryFrom<String> for KeyChord {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut parts: Vec<&str> = value.split('+').map(str::trim).collect();
+        let Some(key_name) = parts.pop() else {
+            return Err("empty key binding".to_owned());
+        };
+        let key = KEY_NAMES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key_name))
+            .map(|(_, key)| *key)
+            .ok_or_else(|| format!("unknown key [{key_name}] in [{value}]"))?;
+        let mut chord = KeyChord::new(key);
+        for modifier in parts {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => chord.ctrl = true,
+                "shift" => chord.shift = true,
+                "alt" => chord.alt = true,
+                other => return Err(format!("unknown modifier [{other}] in [{value}]")),
+            }
+        }
+        Ok(chord)
+    }
+}
+
+/// One or more key chords that trigger the same action.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(transparent)]
+pub struct KeyBinding(pub Vec<KeyChord>);
+
+impl KeyBinding {
+    pub fn just_pressed(&self, input: &UserInput<KeyCode>) -> bool {
+        self.0.iter().any(|chord| chord.just_pressed(input))
+    }
+
+    /// Parse a comma-separated list of chords, e.g. `"Delete, Backspace"`.
+    /// An empty string gives a binding that is never triggered.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        text.split(',')
+            .map(str::trim)
+            .filter(|chord| !chord.is_empty())
+            .map(|chord| KeyChord::try_from(chord.to_owned()))
+            .collect::<Result<Vec<_>, _>>()
+            .map(KeyBinding)
+    }
+}
+
+impl From<KeyChord> for KeyBinding {
+    fn from(value: KeyChord) -> Self {
+        KeyBinding(vec![value])
+    }
+}
+
+impl std::fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, chord) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{chord}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub save: KeyBinding,
+    pub save_as: KeyBinding,
+    pub open: KeyBinding,
+    pub new_workspace: KeyBinding,
+    pub export_sdf: KeyBinding,
+    pub align_drawings: KeyBinding,
+    pub delete: KeyBinding,
+    pub copy: KeyBinding,
+    pub paste: KeyBinding,
+    pub paste_in_place: KeyBinding,
+    pub duplicate: KeyBinding,
+    pub orthographic_view: KeyBinding,
+    pub perspective_view: KeyBinding,
+    pub toggle_debug_mode: KeyBinding,
+    pub toggle_survey_mode: KeyBinding,
+    pub quick_add_door: KeyBinding,
+    pub quick_add_charger: KeyBinding,
+    pub quick_add_parking_spot: KeyBinding,
+    pub toggle_walkthrough: KeyBinding,
+    pub measure_tool: KeyBinding,
+    pub select_tool: KeyBinding,
+    pub add_vertex_tool: KeyBinding,
+    pub add_lane_tool: KeyBinding,
+    pub add_wall_tool: KeyBinding,
+    pub add_measurement_tool: KeyBinding,
+    pub add_model_tool: KeyBinding,
+    pub add_door_tool: KeyBinding,
+    pub add_floor_tool: KeyBinding,
+    pub level_up: KeyBinding,
+    pub level_down: KeyBinding,
+    pub reverse_lanes: KeyBinding,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            save: KeyChord::new(KeyCode::S).ctrl().into(),
+            save_as: KeyChord::new(KeyCode::S).ctrl().shift().into(),
+            open: KeyChord::new(KeyCode::O).ctrl().into(),
+            new_workspace: KeyChord::new(KeyCode::N).ctrl().into(),
+            export_sdf: KeyChord::new(KeyCode::E).ctrl().into(),
+            align_drawings: KeyChord::new(KeyCode::T).ctrl().into(),
+            delete: KeyBinding(vec![
+                KeyChord::new(KeyCode::Delete),
+                KeyChord::new(KeyCode::Back),
+            ]),
+            copy: KeyChord::new(KeyCode::C).ctrl().into(),
+            paste: KeyChord::new(KeyCode::V).ctrl().into(),
+            paste_in_place: KeyChord::new(KeyCode::V).ctrl().shift().into(),
+            duplicate: KeyChord::new(KeyCode::D).ctrl().into(),
+            orthographic_view: KeyChord::new(KeyCode::F2).into(),
+            perspective_view: KeyChord::new(KeyCode::F3).into(),
+            toggle_debug_mode: KeyChord::new(KeyCode::D).into(),
+            toggle_survey_mode: KeyChord::new(KeyCode::F4).into(),
+            quick_add_door: KeyChord::new(KeyCode::D).into(),
+            quick_add_charger: KeyChord::new(KeyCode::C).into(),
+            quick_add_parking_spot: KeyChord::new(KeyCode::P).into(),
+            toggle_walkthrough: KeyChord::new(KeyCode::F5).into(),
+            measure_tool: KeyChord::new(KeyCode::M).into(),
+            select_tool: KeyChord::new(KeyCode::S).alt().into(),
+            add_vertex_tool: KeyChord::new(KeyCode::V).alt().into(),
+            add_lane_tool: KeyChord::new(KeyCode::L).alt().into(),
+            add_wall_tool: KeyChord::new(KeyCode::W).alt().into(),
+            add_measurement_tool: KeyChord::new(KeyCode::M).alt().into(),
+            add_model_tool: KeyChord::new(KeyCode::O).alt().into(),
+            add_door_tool: KeyChord::new(KeyCode::D).alt().into(),
+            add_floor_tool: KeyChord::new(KeyCode::F).alt().into(),
+            level_up: KeyChord::new(KeyCode::PageUp).into(),
+            level_down: KeyChord::new(KeyCode::PageDown).into(),
+            reverse_lanes: KeyChord::new(KeyCode::R).into(),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Every binding paired with a human-friendly name, in the order they
+    /// should be displayed.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&'static str, &mut KeyBinding)> {
+        [
+            ("Save", &mut self.save),
+            ("Save As", &mut self.save_as),
+            ("Open", &mut self.open),
+            ("New Workspace", &mut self.new_workspace),
+            ("Export SDF", &mut self.export_sdf),
+            ("Align Drawings", &mut self.align_drawings),
+            ("Delete", &mut self.delete),
+            ("Copy", &mut self.copy),
+            ("Paste", &mut self.paste),
+            ("Paste In Place", &mut self.paste_in_place),
+            ("Duplicate", &mut self.duplicate),
+            ("Orthographic View", &mut self.orthographic_view),
+            ("Perspective View", &mut self.perspective_view),
+            ("Toggle Debug Mode", &mut self.toggle_debug_mode),
+            ("Toggle Survey Mode", &mut self.toggle_survey_mode),
+            ("Quick Add Door", &mut self.quick_add_door),
+            ("Quick Add Charger", &mut self.quick_add_charger),
+            ("Quick Add Parking Spot", &mut self.quick_add_parking_spot),
+            ("Toggle Walkthrough", &mut self.toggle_walkthrough),
+            ("Measure Tool", &mut self.measure_tool),
+            ("Select Tool", &mut self.select_tool),
+            ("Add Vertex Tool", &mut self.add_vertex_tool),
+            ("Add Lane Tool", &mut self.add_lane_tool),
+            ("Add Wall Tool", &mut self.add_wall_tool),
+            ("Add Measurement Tool", &mut self.add_measurement_tool),
+            ("Add Model Tool", &mut self.add_model_tool),
+            ("Add Door Tool", &mut self.add_door_tool),
+            ("Add Floor Tool", &mut self.add_floor_tool),
+            ("Level Up", &mut self.level_up),
+            ("Level Down", &mut self.level_down),
+            ("Reverse Lanes", &mut self.reverse_lanes),
+        ]
+        .into_iter()
+    }
+}
+
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("A", KeyCode::A),
+    ("B", KeyCode::B),
+    ("C", KeyCode::C),
+    ("D", KeyCode::D),
+    ("E", KeyCode::E),
+    ("F", KeyCode::F),
+    ("G", KeyCode::G),
+    ("H", KeyCode::H),
+    ("I", KeyCode::I),
+    ("J", KeyCode::J),
+    ("K", KeyCode::K),
+    ("L", KeyCode::L),
+    ("M", KeyCode::M),
+    ("N", KeyCode::N),
+    ("O", KeyCode::O),
+    ("P", KeyCode::P),
+    ("Q", KeyCode::Q),
+    ("R", KeyCode::R),
+    ("S", KeyCode::S),
+    ("T", KeyCode::T),
+    ("U", KeyCode::U),
+    ("V", KeyCode::V),
+    ("W", KeyCode::W),
+    ("X", KeyCode::X),
+    ("Y", KeyCode::Y),
+    ("Z", KeyCode::Z),
+    ("0", KeyCode::Key0),
+    ("1", KeyCode::Key1),
+    ("2", KeyCode::Key2),
+    ("3", KeyCode::Key3),
+    ("4", KeyCode::Key4),
+    ("5", KeyCode::Key5),
+    ("6", KeyCode::Key6),
+    ("7", KeyCode::Key7),
+    ("8", KeyCode::Key8),
+    ("9", KeyCode::Key9),
+    ("F1", KeyCode::F1),
+    ("F2", KeyCode::F2),
+    ("F3", KeyCode::F3),
+    ("F4", KeyCode::F4),
+    ("F5", KeyCode::F5),
+    ("F6", KeyCode::F6),
+    ("F7", KeyCode::F7),
+    ("F8", KeyCode::F8),
+    ("F9", KeyCode::F9),
+    ("F10", KeyCode::F10),
+    ("F11", KeyCode::F11),
+    ("F12", KeyCode::F12),
+    ("Delete", KeyCode::Delete),
+    ("Backspace", KeyCode::Back),
+    ("Escape", KeyCode::Escape),
+    ("Enter", KeyCode::Return),
+    ("Space", KeyCode::Space),
+    ("Tab", KeyCode::Tab),
+    ("Insert", KeyCode::Insert),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+];
+
+/// Send this event to write the current [`EditorSettings`] into the settings
+/// file.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct SaveEditorSettings;
+
+/// Keeps track of the settings file so it can be reloaded whenever it
+/// changes on disk.
+#[derive(Resource, Debug)]
+pub struct EditorSettingsFile {
+    pub path: Option<PathBuf>,
+    last_modified: Option<SystemTime>,
+    timer: Timer,
+}
+
+impl Default for EditorSettingsFile {
+    fn default() -> Self {
+        let path = dirs::config_dir().map(|mut p| {
+            p.push("open-robotics");
+            p.push("rmf_site_editor");
+            p.push("settings.yaml");
+            p
+        });
+        Self {
+            path,
+            last_modified: None,
+            timer: Timer::from_seconds(WATCH_PERIOD_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+impl EditorSettingsFile {
+    fn modified(&self) -> Option<SystemTime> {
+        let path = self.path.as_ref()?;
+        std::fs::metadata(path).ok()?.modified().ok()
+    }
+
+    fn read(&self) -> Option<Result<EditorSettings, String>> {
+        let path = self.path.as_ref()?;
+        let data = std::fs::read(path).ok()?;
+        Some(serde_yaml::from_slice(&data).map_err(|err| err.to_string()))
+    }
+
+    fn write(&self, settings: &EditorSettings) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Err("there is no configuration folder".to_owned());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let contents = serde_yaml::to_string(settings).map_err(|err| err.to_string())?;
+        std::fs::write(path, contents).map_err(|err| err.to_string())
+    }
+}
+
+pub struct EditorSettingsPlugin;
+
+impl Plugin for EditorSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        let settings_file = EditorSettingsFile::default();
+        let settings = match settings_file.read() {
+            Some(Ok(settings)) => settings,
+            Some(Err(err)) => {
+                error!("Unable to parse the settings file, using defaults instead: {err}");
+                EditorSettings::default()
+            }
+            None => EditorSettings::default(),
+        };
+        let last_modified = settings_file.modified();
+
+        app.insert_resource(settings)
+            .insert_resource(EditorSettingsFile {
+                last_modified,
+                ..settings_file
+            })
+            .add_event::<SaveEditorSettings>()
+            .add_systems(
+                Update,
+                (
+                    watch_settings_file,
+                    save_editor_settings,
+                    apply_color_settings,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn watch_settings_file(
+    time: Res<Time>,
+    mut settings_file: ResMut<EditorSettingsFile>,
+    mut settings: ResMut<EditorSettings>,
+) {
+    if !settings_file.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let modified = settings_file.modified();
+    if modified.is_none() || modified == settings_file.last_modified {
+        return;
+    }
+    settings_file.last_modified = modified;
+
+    match settings_file.read() {
+        Some(Ok(new_settings)) => {
+            if *settings != new_settings {
+                info!("Applying changes from the settings file");
+                *settings = new_settings;
+            }
+        }
+        Some(Err(err)) => {
+            error!("Unable to parse the settings file, keeping the current settings: {err}");
+        }
+        None => {}
+    }
+}
+
+fn save_editor_settings(
+    mut requests: EventReader<SaveEditorSettings>,
+    mut settings_file: ResMut<EditorSettingsFile>,
+    settings: Res<EditorSettings>,
+) {
+    if requests.read().last().is_none() {
+        return;
+    }
+
+    match settings_file.write(&settings) {
+        Ok(()) => {
+            // Avoid reloading the file that we just wrote
+            settings_file.last_modified = settings_file.modified();
+            if let Some(path) = &settings_file.path {
+                info!("Saved settings to {}", path.display());
+            }
+        }
+        Err(err) => {
+            error!("Unable to save settings: {err}");
+        }
+    }
+}
+
+fn apply_color_settings(
+    settings: Res<EditorSettings>,
+    site_assets: Option<Res<SiteAssets>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut clear_color: ResMut<ClearColor>,
+) {
+    let assets_added = site_assets.as_ref().is_some_and(|a| a.is_added());
+    if !settings.is_changed() && !assets_added {
+        return;
+    }
+
+    let colors = &settings.colors;
+    let to_color = |[r, g, b]: [f32; 3]| Color::rgb(r, g, b);
+    clear_color.0 = to_color(colors.background);
+
+    let Some(site_assets) = site_assets else {
+        return;
+    };
+    for (handles, color) in [
+        (
+            [
+                &site_assets.hover_material,
+                &site_assets.hover_anchor_material,
+            ],
+            colors.hover,
+        ),
+        (
+            [
+                &site_assets.select_material,
+                &site_assets.select_anchor_material,
+            ],
+            colors.select,
+        ),
+        (
+            [
+                &site_assets.hover_select_material,
+                &site_assets.hover_select_anchor_material,
+            ],
+            colors.hover_select,
+        ),
+    ] {
+        for handle in handles {
+            if let Some(material) = materials.get_mut(handle) {
+                material.base_color = to_color(color);
+            }
+        }
+    }
+}
@@ -925,6 +925,71 @@ pub(crate) fn make_flat_square_mesh(extent: f32) -> MeshBuffer {
     return make_flat_rect_mesh(extent, extent);
 }
 
+/// A unit square along the x axis that is broken into evenly spaced dashes.
+/// `fill` is the fraction of each dash period that is filled in.
+pub(crate) fn make_dashed_flat_square_mesh(dashes: usize, fill: f32) -> MeshBuffer {
+    let period = 1.0 / dashes as f32;
+    let mut mesh = MeshBuffer::empty();
+    for i in 0..dashes {
+        let x = -0.5 + (i as f32 + 0.5) * period;
+        mesh = mesh.merge_with(
+            make_flat_rect_mesh(fill * period, 1.0)
+                .transform_by(Affine3A::from_translation([x, 0.0, 0.0].into())),
+        );
+    }
+    mesh
+}
+
+/// A flat chevron that points along the x axis and fits inside of a unit
+/// square. `thickness` is the width of each arm of the chevron.
+pub(crate) fn make_flat_chevron_mesh(thickness: f32) -> MeshBuffer {
+    let angle = 40_f32.to_radians();
+    let arm = 0.5 / angle.cos();
+    let tip = Vec3::new(0.5, 0.0, 0.0);
+    let mut mesh = MeshBuffer::empty();
+    for side in [-1.0, 1.0] {
+        let rotation = Quat::from_rotation_z(side * angle);
+        let center = tip - rotation * Vec3::new(arm / 2.0, 0.0, 0.0);
+        mesh = mesh.merge_with(
+            make_flat_rect_mesh(arm, thickness)
+                .transform_by(Affine3A::from_rotation_translation(rotation, center)),
+        );
+    }
+    mesh
+}
+
+/// A flat strip of constant width that follows a path, such as the points of
+/// a curved lane. The strip faces up.
+pub(crate) fn make_flat_strip_mesh(points: &[Vec3], width: f32) -> MeshBuffer {
+    let n = points.len();
+    if n < 2 {
+        return MeshBuffer::empty();
+    }
+
+    let mut positions = Vec::with_capacity(2 * n);
+    for (i, p) in points.iter().enumerate() {
+        let prev = points[i.saturating_sub(1)];
+        let next = points[(i + 1).min(n - 1)];
+        let side = (next - prev)
+            .truncate()
+            .normalize_or_zero()
+            .perp()
+            .extend(0.0);
+        positions.push((*p + width / 2.0 * side).to_array());
+        positions.push((*p - width / 2.0 * side).to_array());
+    }
+    let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+
+    let mut indices = Vec::with_capacity(6 * (n - 1));
+    for i in 0..(n as u32 - 1) {
+        let (left, right) = (2 * i, 2 * i + 1);
+        let (next_left, next_right) = (2 * i + 2, 2 * i + 3);
+        indices.extend([right, next_right, next_left, right, next_left, left]);
+    }
+
+    MeshBuffer::new(positions, normals, indices)
+}
+
 pub(crate) fn make_flat_rect_mesh(x_size: f32, y_size: f32) -> MeshBuffer {
     let x = x_size / 2.0;
     let y = y_size / 2.0;
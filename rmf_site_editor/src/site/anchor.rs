@@ -15,11 +15,11 @@
  *
 */
 
-use crate::{site::*, Issue, ValidateWorkspace};
+use crate::{site::*, Issue, ValidationRequests};
 use bevy::{ecs::system::Command, prelude::*, render::primitives::Sphere, utils::Uuid};
 use itertools::Itertools;
 use rmf_site_format::{Anchor, LevelElevation, LiftCabin};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Bundle, Debug)]
 pub struct AnchorBundle {
@@ -184,7 +184,7 @@ pub const UNCONNECTED_ANCHORS_ISSUE_UUID: Uuid =
 // each other but not connected
 pub fn check_for_close_unconnected_anchors(
     mut commands: Commands,
-    mut validate_events: EventReader<ValidateWorkspace>,
+    mut validate_events: ValidationRequests,
     parents: Query<&Parent>,
     anchors: AnchorParams,
     anchor_entities: Query<Entity, With<Anchor>>,
@@ -195,31 +195,49 @@ pub fn check_for_close_unconnected_anchors(
                         review if this is intended and, if it is, suppress the issue";
     // TODO(luca) make this configurable
     const DISTANCE_THRESHOLD: f32 = 0.2;
-    for root in validate_events.read() {
+    for request in validate_events.read() {
+        let root = request.root();
+        let level_anchors: Vec<(Entity, Entity)> = anchor_entities
+            .iter()
+            .filter_map(|e| {
+                AncestorIter::new(&parents, e)
+                    .find(|p| levels.get(*p).is_ok())
+                    .filter(|level| AncestorIter::new(&parents, *level).any(|p| p == root))
+                    .map(|level| (e, level))
+            })
+            .collect();
+        // Only levels that have an anchor whose issues need to be checked
+        // again are looked at.
+        let checked_levels: HashSet<Entity> = level_anchors
+            .iter()
+            .filter(|(e, level)| request.affects(*e, Some(*level)))
+            .map(|(_, level)| *level)
+            .collect();
         // Key is level id, value is vector of (Entity, Global tf's position)
         let mut anchor_poses: HashMap<Entity, Vec<(Entity, Vec3)>> = HashMap::new();
-        for e in &anchor_entities {
-            if let Some(level) = AncestorIter::new(&parents, e).find(|p| levels.get(*p).is_ok()) {
-                if AncestorIter::new(&parents, level).any(|p| p == **root) {
-                    // Level that belongs to requested workspace
-                    let poses = anchor_poses.entry(level).or_default();
-                    poses.push((
-                        e,
-                        match anchors.point_in_parent_frame_of(e, Category::General, level) {
-                            Ok(p) => p,
-                            Err(err) => {
-                                error!("Failed fetching anchor pose {:?}", err);
-                                continue;
-                            }
-                        },
-                    ));
-                }
+        for (e, level) in level_anchors {
+            if !checked_levels.contains(&level) {
+                continue;
             }
+            let poses = anchor_poses.entry(level).or_default();
+            poses.push((
+                e,
+                match anchors.point_in_parent_frame_of(e, Category::General, level) {
+                    Ok(p) => p,
+                    Err(err) => {
+                        error!("Failed fetching anchor pose {:?}", err);
+                        continue;
+                    }
+                },
+            ));
         }
         // Now find close unconnected pairs, sadly n^2 problem for anchors, unless we use better
         // data structures that sort in space
-        for values in anchor_poses.values() {
+        for (level, values) in &anchor_poses {
             for ((e0, p0), (e1, p1)) in values.iter().tuple_combinations() {
+                if !request.affects(*e0, Some(*level)) && !request.affects(*e1, Some(*level)) {
+                    continue;
+                }
                 if p0.distance(*p1) < DISTANCE_THRESHOLD {
                     let mut edge_found = false;
                     if let (Ok(d0), Ok(d1)) = (dependents.get(*e0), dependents.get(*e1)) {
@@ -238,7 +256,7 @@ pub fn check_for_close_unconnected_anchors(
                             hint: ISSUE_HINT.to_string(),
                         };
                         let id = commands.spawn(issue).id();
-                        commands.entity(**root).add_child(id);
+                        commands.entity(root).add_child(id);
                     }
                 }
             }
@@ -40,14 +40,18 @@ pub(crate) fn add_site_icons(app: &mut App) {
 pub struct SiteAssets {
     pub lift_floor_material: Handle<StandardMaterial>,
     pub lane_mid_mesh: Handle<Mesh>,
+    pub human_lane_mid_mesh: Handle<Mesh>,
     pub lane_mid_outline: Handle<Mesh>,
     pub lane_end_mesh: Handle<Mesh>,
     pub lane_end_outline: Handle<Mesh>,
+    pub lane_arrow_mesh: Handle<Mesh>,
     pub box_mesh: Handle<Mesh>,
     pub location_mesh: Handle<Mesh>,
     pub fiducial_mesh: Handle<Mesh>,
     pub physical_camera_mesh: Handle<Mesh>,
     pub unassigned_lane_material: Handle<StandardMaterial>,
+    pub human_lane_material: Handle<StandardMaterial>,
+    pub lane_arrow_material: Handle<StandardMaterial>,
     pub passive_anchor_material: Handle<StandardMaterial>,
     pub unassigned_anchor_material: Handle<StandardMaterial>,
     pub hover_anchor_material: Handle<StandardMaterial>,
@@ -72,6 +76,10 @@ pub struct SiteAssets {
     pub location_tag_mesh: Handle<Mesh>,
     pub charger_material: Handle<StandardMaterial>,
     pub holding_point_material: Handle<StandardMaterial>,
+    pub assembly_point_material: Handle<StandardMaterial>,
+    pub dispenser_material: Handle<StandardMaterial>,
+    pub ingestor_material: Handle<StandardMaterial>,
+    pub spawn_point_material: Handle<StandardMaterial>,
     pub parking_material: Handle<StandardMaterial>,
 }
 
@@ -110,6 +118,12 @@ impl FromWorld for SiteAssets {
             .unwrap();
         let unassigned_lane_material =
             materials.add(old_default_material(Color::rgb(0.1, 0.1, 0.1)));
+        let human_lane_material = materials.add(old_default_material(Color::rgb(0.95, 0.55, 0.1)));
+        let lane_arrow_material = materials.add(StandardMaterial {
+            base_color: Color::rgb(0.95, 0.95, 0.95),
+            unlit: true,
+            ..default()
+        });
         let select_color = Color::rgb(1., 0.3, 1.);
         let hover_color = Color::rgb(0.3, 1., 1.);
         let hover_select_color = Color::rgb(1.0, 0.0, 0.3);
@@ -180,6 +194,12 @@ impl FromWorld for SiteAssets {
         let charger_material = materials.add(old_default_material_t(charger_texture));
         let holding_point_material = materials.add(old_default_material_t(holding_point_texture));
         let parking_material = materials.add(old_default_material_t(parking_texture));
+        let assembly_point_material =
+            materials.add(old_default_material(Color::rgb(0.1, 0.75, 0.25)));
+        let dispenser_material = materials.add(old_default_material(Color::rgb(0.95, 0.55, 0.1)));
+        let ingestor_material = materials.add(old_default_material(Color::rgb(0.6, 0.25, 0.85)));
+        let spawn_point_material =
+            materials.add(old_default_material(Color::rgb(0.15, 0.65, 0.95)));
 
         let mut meshes = world.get_resource_mut::<Assets<Mesh>>().unwrap();
         let level_anchor_mesh = meshes.add(
@@ -199,6 +219,7 @@ impl FromWorld for SiteAssets {
             ..Default::default()
         }));
         let lane_mid_mesh = meshes.add(make_flat_square_mesh(1.0).into());
+        let human_lane_mid_mesh = meshes.add(make_dashed_flat_square_mesh(5, 0.6).into());
         let lane_mid_outline = meshes.add(make_flat_rect_mesh(1.0, 1.125).into());
         let lane_end_mesh = meshes.add(
             make_flat_disk(
@@ -220,6 +241,7 @@ impl FromWorld for SiteAssets {
             )
             .into(),
         );
+        let lane_arrow_mesh = meshes.add(make_flat_chevron_mesh(0.25).into());
         let box_mesh = meshes.add(
             Mesh::from(shape::Box::new(1., 1., 1.))
                 .with_generated_outline_normals()
@@ -257,14 +279,18 @@ impl FromWorld for SiteAssets {
             site_anchor_mesh,
             lift_floor_material,
             lane_mid_mesh,
+            human_lane_mid_mesh,
             lane_mid_outline,
             lane_end_mesh,
             lane_end_outline,
+            lane_arrow_mesh,
             box_mesh,
             location_mesh,
             fiducial_mesh,
             physical_camera_mesh,
             unassigned_lane_material,
+            human_lane_material,
+            lane_arrow_material,
             hover_anchor_material,
             select_anchor_material,
             hover_select_anchor_material,
@@ -286,6 +312,10 @@ impl FromWorld for SiteAssets {
             location_tag_mesh,
             charger_material,
             holding_point_material,
+            assembly_point_material,
+            dispenser_material,
+            ingestor_material,
+            spawn_point_material,
             parking_material,
         }
     }
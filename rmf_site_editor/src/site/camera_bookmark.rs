@@ -0,0 +1,194 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{CameraControls, ChangeProjectionMode, PickingBlockers, ProjectionMode},
+    site::*,
+    AppState,
+};
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::{Input as UserInput, *},
+    window::PrimaryWindow,
+};
+use bevy_egui::EguiContexts;
+
+/// Number keys that jump to the first nine bookmarks of the current level
+const BOOKMARK_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+/// Send this event to save the current view of the camera as a named
+/// bookmark of the current level. Bookmarks are saved into the site file as
+/// the user camera poses of the level.
+#[derive(Event, Debug, Clone)]
+pub struct AddCameraBookmark {
+    pub name: String,
+}
+
+/// Send this event to move the camera to the pose of a bookmark.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct JumpToCameraBookmark(pub Entity);
+
+pub struct CameraBookmarkPlugin;
+
+impl Plugin for CameraBookmarkPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AddCameraBookmark>()
+            .add_event::<JumpToCameraBookmark>()
+            .add_systems(
+                Update,
+                (
+                    handle_camera_bookmark_keys,
+                    add_camera_bookmarks,
+                    jump_to_camera_bookmarks,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::SiteEditor)),
+            );
+    }
+}
+
+#[derive(SystemParam)]
+pub struct CameraBookmarks<'w, 's> {
+    current_level: Res<'w, CurrentLevel>,
+    children: Query<'w, 's, &'static Children>,
+    bookmarks: Query<'w, 's, (&'static NameInSite, &'static Pose), With<UserCameraPoseMarker>>,
+}
+
+impl<'w, 's> CameraBookmarks<'w, 's> {
+    /// The bookmarks of the current level in the order they are listed, which
+    /// is also the order of their number keys.
+    pub fn of_current_level(&self) -> Vec<(Entity, &NameInSite, &Pose)> {
+        let Some(children) = self
+            .current_level
+            .0
+            .and_then(|level| self.children.get(level).ok())
+        else {
+            return Vec::new();
+        };
+        children
+            .iter()
+            .filter_map(|c| {
+                let (name, pose) = self.bookmarks.get(*c).ok()?;
+                Some((*c, name, pose))
+            })
+            .collect()
+    }
+}
+
+fn handle_camera_bookmark_keys(
+    keyboard_input: Res<UserInput<KeyCode>>,
+    picking_blockers: Res<PickingBlockers>,
+    walkthrough: Res<WalkthroughMode>,
+    bookmarks: CameraBookmarks,
+    mut jump: EventWriter<JumpToCameraBookmark>,
+    mut egui_context: EguiContexts,
+    primary_windows: Query<Entity, With<PrimaryWindow>>,
+) {
+    if picking_blockers.ui || walkthrough.active {
+        return;
+    }
+    // Number keys that are typed into a text field are not shortcuts
+    let Some(egui_context) = primary_windows
+        .get_single()
+        .ok()
+        .and_then(|w| egui_context.try_ctx_for_window_mut(w))
+    else {
+        return;
+    };
+    if egui_context.wants_keyboard_input() {
+        return;
+    }
+    let Some(index) = BOOKMARK_KEYS
+        .iter()
+        .position(|key| keyboard_input.just_pressed(*key))
+    else {
+        return;
+    };
+    if keyboard_input.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::AltLeft,
+        KeyCode::AltRight,
+    ]) {
+        return;
+    }
+
+    if let Some((e, _, _)) = bookmarks.of_current_level().get(index) {
+        jump.send(JumpToCameraBookmark(*e));
+    }
+}
+
+fn add_camera_bookmarks(
+    mut commands: Commands,
+    mut requests: EventReader<AddCameraBookmark>,
+    current_level: Res<CurrentLevel>,
+    controls: Res<CameraControls>,
+    global_tfs: Query<&GlobalTransform>,
+) {
+    for request in requests.read() {
+        let Some(level) = current_level.0 else {
+            warn!("Unable to add a camera bookmark because there is no current level");
+            continue;
+        };
+        let Ok(tf) = global_tfs.get(controls.active_camera()) else {
+            continue;
+        };
+        let pose: Pose = tf.compute_transform().into();
+        commands
+            .spawn(UserCameraPose {
+                pose,
+                name: NameInSite(request.name.clone()),
+                marker: UserCameraPoseMarker,
+            })
+            .set_parent(level);
+        info!("Added camera bookmark [{}]", request.name);
+    }
+}
+
+fn jump_to_camera_bookmarks(
+    mut requests: EventReader<JumpToCameraBookmark>,
+    poses: Query<&Pose, With<UserCameraPoseMarker>>,
+    controls: Res<CameraControls>,
+    mut transforms: Query<&mut Transform, With<Projection>>,
+    mut change_mode: EventWriter<ChangeProjectionMode>,
+) {
+    let Some(JumpToCameraBookmark(bookmark)) = requests.read().last() else {
+        return;
+    };
+    let Ok(pose) = poses.get(*bookmark) else {
+        return;
+    };
+
+    // Bookmarks are always shown with the perspective camera since an
+    // orthographic view cannot show an arbitrary pose.
+    if let Ok(mut tf) = transforms.get_mut(controls.perspective_camera_entities[0]) {
+        *tf = pose.transform();
+    }
+    if controls.mode() != ProjectionMode::Perspective {
+        change_mode.send(ChangeProjectionMode::to_perspective().for_tool());
+    }
+}
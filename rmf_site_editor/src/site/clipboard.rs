@@ -0,0 +1,458 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{IntersectGroundPlaneParams, MultiSelection, Selection},
+    site::*,
+    CurrentWorkspace,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use std::collections::{BTreeMap, HashSet};
+
+/// Send this event to copy the current selection into the [`Clipboard`].
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CopySelection;
+
+/// Send this event to paste the contents of the [`Clipboard`] into the current
+/// level of the current site.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PasteClipboard {
+    /// If true, the pasted elements will be centered on the cursor. Otherwise
+    /// they will have the same coordinates that they were copied from, which
+    /// is useful for repeating the same layout on every floor.
+    pub at_cursor: bool,
+}
+
+/// A lane, wall, or door that was copied, with its anchors stored as indices into
+/// [`Clipboard::anchors`].
+#[derive(Clone, Debug)]
+pub struct ClipboardEdge<T> {
+    pub anchors: [usize; 2],
+    pub properties: T,
+}
+
+/// Holds copies of site elements that can be pasted into any level of any
+/// open site. Pasted elements always receive brand new entities.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct Clipboard {
+    /// The site that the elements were copied from
+    pub source_site: Option<Entity>,
+    pub anchors: Vec<Vec2>,
+    pub lanes: Vec<ClipboardEdge<(Motion, ReverseLane, AssociatedGraphs<Entity>, LaneCurve)>>,
+    pub walls: Vec<ClipboardEdge<(Affiliation<Entity>, WallHeight, WallAlpha)>>,
+    pub doors: Vec<ClipboardEdge<(NameInSite, DoorType, DoorTiming)>>,
+    pub models: Vec<ModelInstance<Entity>>,
+}
+
+impl Clipboard {
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty() && self.models.is_empty()
+    }
+
+    pub fn center(&self) -> Vec2 {
+        let points: Vec<Vec2> = self
+            .anchors
+            .iter()
+            .copied()
+            .chain(
+                self.models
+                    .iter()
+                    .map(|m| Vec2::new(m.pose.trans[0], m.pose.trans[1])),
+            )
+            .collect();
+        if points.is_empty() {
+            return Vec2::ZERO;
+        }
+        points.iter().sum::<Vec2>() / points.len() as f32
+    }
+}
+
+pub struct ClipboardPlugin;
+
+impl Plugin for ClipboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Clipboard>()
+            .add_event::<CopySelection>()
+            .add_event::<PasteClipboard>()
+            .add_systems(
+                Update,
+                (copy_selection, paste_clipboard)
+                    .chain()
+                    .run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+#[derive(SystemParam)]
+pub(crate) struct CopyParams<'w, 's> {
+    selection: Res<'w, Selection>,
+    multi_selection: Res<'w, MultiSelection>,
+    anchors: Query<'w, 's, (&'static Anchor, &'static GlobalTransform)>,
+    levels: Query<'w, 's, &'static GlobalTransform, With<LevelElevation>>,
+    lanes: Query<
+        'w,
+        's,
+        (
+            &'static Edge<Entity>,
+            &'static Motion,
+            &'static ReverseLane,
+            &'static AssociatedGraphs<Entity>,
+            Option<&'static LaneCurve>,
+        ),
+        With<LaneMarker>,
+    >,
+    walls: Query<
+        'w,
+        's,
+        (
+            &'static Edge<Entity>,
+            &'static Affiliation<Entity>,
+            &'static WallHeight,
+            &'static WallAlpha,
+        ),
+        With<WallMarker>,
+    >,
+    doors: Query<
+        'w,
+        's,
+        (
+            &'static Edge<Entity>,
+            &'static NameInSite,
+            &'static DoorType,
+            Option<&'static DoorTiming>,
+        ),
+        With<DoorMarker>,
+    >,
+    models: Query<
+        'w,
+        's,
+        (
+            &'static NameInSite,
+            &'static Pose,
+            &'static Affiliation<Entity>,
+        ),
+        (With<ModelMarker>, With<InstanceMarker>),
+    >,
+    sites: Query<'w, 's, (), With<NameOfSite>>,
+    parents: Query<'w, 's, &'static Parent>,
+}
+
+impl<'w, 's> CopyParams<'w, 's> {
+    fn anchor_index(
+        &self,
+        anchor: Entity,
+        indices: &mut BTreeMap<Entity, usize>,
+        positions: &mut Vec<Vec2>,
+    ) -> Option<usize> {
+        if let Some(index) = indices.get(&anchor) {
+            return Some(*index);
+        }
+        let (anchor_data, tf) = self.anchors.get(anchor).ok()?;
+        // Positions are kept in the frame of the level so that pasting does
+        // not pick up the flattened offset of the level or the pixel scale of
+        // a drawing that the anchor belongs to.
+        let level_tf =
+            AncestorIter::new(&self.parents, anchor).find_map(|p| self.levels.get(p).ok());
+        let p = match level_tf {
+            Some(level_tf) => level_tf
+                .affine()
+                .inverse()
+                .transform_point3(tf.translation())
+                .truncate(),
+            None => Vec2::from(anchor_data.translation_for_category(Category::General)),
+        };
+        let index = positions.len();
+        positions.push(p);
+        indices.insert(anchor, index);
+        Some(index)
+    }
+
+    fn edge_indices(
+        &self,
+        edge: &Edge<Entity>,
+        indices: &mut BTreeMap<Entity, usize>,
+        positions: &mut Vec<Vec2>,
+    ) -> Option<[usize; 2]> {
+        let [a0, a1] = edge.array();
+        Some([
+            self.anchor_index(a0, indices, positions)?,
+            self.anchor_index(a1, indices, positions)?,
+        ])
+    }
+}
+
+impl<'w, 's> CopyParams<'w, 's> {
+    /// Copy the current selection, or return None if nothing that is selected
+    /// can be copied.
+    pub(crate) fn copy_selection(&self) -> Option<Clipboard> {
+        let members: Vec<Entity> = if self.multi_selection.is_empty() {
+            self.selection.0.into_iter().collect()
+        } else {
+            self.multi_selection.iter().copied().collect()
+        };
+
+        if members.is_empty() {
+            warn!("Nothing is selected, so nothing was copied");
+            return None;
+        }
+
+        let mut clipboard = Clipboard::default();
+        clipboard.source_site = members
+            .iter()
+            .find_map(|e| AncestorIter::new(&self.parents, *e).find(|p| self.sites.contains(*p)));
+
+        let mut indices = BTreeMap::new();
+        let mut positions = Vec::new();
+        for e in members {
+            if let Ok((edge, forward, reverse, graphs, curve)) = self.lanes.get(e) {
+                let Some(anchors) = self.edge_indices(edge, &mut indices, &mut positions) else {
+                    continue;
+                };
+                clipboard.lanes.push(ClipboardEdge {
+                    anchors,
+                    properties: (
+                        forward.clone(),
+                        reverse.clone(),
+                        graphs.clone(),
+                        curve.copied().unwrap_or_default(),
+                    ),
+                });
+            } else if let Ok((edge, texture, height, alpha)) = self.walls.get(e) {
+                let Some(anchors) = self.edge_indices(edge, &mut indices, &mut positions) else {
+                    continue;
+                };
+                clipboard.walls.push(ClipboardEdge {
+                    anchors,
+                    properties: (texture.clone(), *height, *alpha),
+                });
+            } else if let Ok((edge, name, kind, timing)) = self.doors.get(e) {
+                let Some(anchors) = self.edge_indices(edge, &mut indices, &mut positions) else {
+                    continue;
+                };
+                clipboard.doors.push(ClipboardEdge {
+                    anchors,
+                    properties: (
+                        name.clone(),
+                        kind.clone(),
+                        timing.copied().unwrap_or_default(),
+                    ),
+                });
+            } else if let Ok((name, pose, description)) = self.models.get(e) {
+                clipboard.models.push(ModelInstance {
+                    name: name.clone(),
+                    pose: pose.clone(),
+                    description: description.clone(),
+                    ..Default::default()
+                });
+            } else if self.anchors.contains(e) {
+                self.anchor_index(e, &mut indices, &mut positions);
+            }
+        }
+        clipboard.anchors = positions;
+
+        if clipboard.is_empty() {
+            warn!("None of the selected elements can be copied");
+            return None;
+        }
+
+        Some(clipboard)
+    }
+}
+
+fn copy_selection(
+    mut requests: EventReader<CopySelection>,
+    params: CopyParams,
+    mut clipboard: ResMut<Clipboard>,
+) {
+    if requests.read().last().is_none() {
+        return;
+    }
+
+    let Some(copied) = params.copy_selection() else {
+        return;
+    };
+
+    info!(
+        "Copied {} anchors, {} lanes, {} walls, {} doors, and {} models",
+        copied.anchors.len(),
+        copied.lanes.len(),
+        copied.walls.len(),
+        copied.doors.len(),
+        copied.models.len(),
+    );
+    *clipboard = copied;
+}
+
+#[derive(SystemParam)]
+struct PasteParams<'w, 's> {
+    commands: Commands<'w, 's>,
+    model_loader: ModelLoader<'w, 's>,
+    current_workspace: Res<'w, CurrentWorkspace>,
+    current_level: Res<'w, CurrentLevel>,
+    intersect_ground_params: IntersectGroundPlaneParams<'w, 's>,
+    door_names: Query<'w, 's, &'static NameInSite, With<DoorMarker>>,
+    level_transforms: Query<'w, 's, &'static GlobalTransform, With<LevelElevation>>,
+}
+
+impl<'w, 's> PasteParams<'w, 's> {
+    /// Spawn new copies of everything inside the clipboard into the current
+    /// level.
+    fn paste(&mut self, clipboard: &Clipboard, at_cursor: bool) {
+        if clipboard.is_empty() {
+            warn!("The clipboard is empty, so there is nothing to paste");
+            return;
+        }
+
+        let Some(level) = self.current_level.0 else {
+            warn!("Unable to paste because there is no current level");
+            return;
+        };
+
+        let offset = if at_cursor {
+            // The clipboard is in the frame of a level, so bring the cursor
+            // into the frame of the level that is being pasted into.
+            let to_level = self
+                .level_transforms
+                .get(level)
+                .map(|tf| tf.affine().inverse())
+                .unwrap_or_default();
+            match self.intersect_ground_params.ground_plane_intersection() {
+                Some(tf) => {
+                    to_level.transform_point3(tf.translation).truncate() - clipboard.center()
+                }
+                None => Vec2::ZERO,
+            }
+        } else {
+            Vec2::ZERO
+        };
+
+        // References to graphs, textures, and model descriptions only make sense
+        // inside of the site that they were copied from.
+        let same_site =
+            clipboard.source_site.is_some() && clipboard.source_site == self.current_workspace.root;
+
+        // Orphaned anchors, lanes, walls, and doors will be assigned to the
+        // current level and site automatically.
+        let anchors: Vec<Entity> = clipboard
+            .anchors
+            .iter()
+            .map(|p| {
+                let p = *p + offset;
+                self.commands
+                    .spawn(AnchorBundle::new(Anchor::Translate2D([p.x, p.y])))
+                    .id()
+            })
+            .collect();
+
+        for lane in &clipboard.lanes {
+            let [a0, a1] = lane.anchors.map(|i| anchors[i]);
+            let (forward, reverse, graphs, curve) = lane.properties.clone();
+            let graphs = if same_site {
+                graphs
+            } else {
+                AssociatedGraphs::All
+            };
+            let e = self
+                .commands
+                .spawn(Lane {
+                    anchors: Edge::new(a0, a1),
+                    forward,
+                    reverse,
+                    graphs,
+                    evacuation: Default::default(),
+                    curve,
+                    marker: LaneMarker,
+                })
+                .id();
+            self.commands.add(ChangeDependent::add(a0, e));
+            self.commands.add(ChangeDependent::add(a1, e));
+        }
+
+        for wall in &clipboard.walls {
+            let [a0, a1] = wall.anchors.map(|i| anchors[i]);
+            let (texture, height, alpha) = wall.properties.clone();
+            let mut e = self.commands.spawn(Wall {
+                anchors: Edge::new(a0, a1),
+                texture: if same_site {
+                    texture
+                } else {
+                    Affiliation(None)
+                },
+                height,
+                alpha,
+                marker: WallMarker,
+            });
+            if !same_site {
+                e.insert(TextureNeedsAssignment);
+            }
+            let e = e.id();
+            self.commands.add(ChangeDependent::add(a0, e));
+            self.commands.add(ChangeDependent::add(a1, e));
+        }
+
+        // RMF requires door names to be unique, so pasted doors get a suffix
+        let mut door_names: HashSet<String> = self.door_names.iter().map(|n| n.0.clone()).collect();
+        for door in &clipboard.doors {
+            let [a0, a1] = door.anchors.map(|i| anchors[i]);
+            let (name, kind, timing) = door.properties.clone();
+            let unique_name = unique_door_name(&name.0, |n| door_names.contains(n));
+            door_names.insert(unique_name.clone());
+            let e = self
+                .commands
+                .spawn(Door {
+                    anchors: Edge::new(a0, a1),
+                    name: NameInSite(unique_name),
+                    kind,
+                    timing,
+                    emergency_exit: Default::default(),
+                    marker: DoorMarker,
+                })
+                .id();
+            self.commands.add(ChangeDependent::add(a0, e));
+            self.commands.add(ChangeDependent::add(a1, e));
+        }
+
+        if !same_site && !clipboard.models.is_empty() {
+            warn!(
+                "Skipping {} models because their descriptions belong to a \
+                different site",
+                clipboard.models.len(),
+            );
+        } else {
+            for model in &clipboard.models {
+                let mut model = model.clone();
+                model.pose.trans[0] += offset.x;
+                model.pose.trans[1] += offset.y;
+                self.model_loader
+                    .spawn_model_instance(level, model)
+                    .insert(Category::Model);
+            }
+        }
+    }
+}
+
+fn paste_clipboard(
+    mut requests: EventReader<PasteClipboard>,
+    clipboard: Res<Clipboard>,
+    mut params: PasteParams,
+) {
+    let Some(request) = requests.read().last().copied() else {
+        return;
+    };
+
+    params.paste(&clipboard, request.at_cursor);
+}
@@ -0,0 +1,250 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, AppState};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use std::collections::HashSet;
+
+/// How high above the floor the connectivity overlay is drawn
+const CONNECTIVITY_OVERLAY_HEIGHT: f32 = 0.08;
+
+/// How well the robot lanes of one nav graph on a level are connected.
+#[derive(Debug, Clone, Default)]
+pub struct GraphConnectivity {
+    /// The nav graph that was analyzed, or None for all lanes of the level
+    pub nav_graph: Option<Entity>,
+    /// Groups of waypoints where every waypoint can reach every other one,
+    /// largest first. A graph that robots can drive around freely has only
+    /// one of these.
+    pub components: Vec<Vec<Entity>>,
+    /// The two waypoints of every lane, along with the component that each
+    /// of them belongs to
+    pub lanes: Vec<[(Entity, usize); 2]>,
+    /// Locations of the graph that are not on any of its lanes
+    pub isolated_locations: Vec<Entity>,
+}
+
+impl GraphConnectivity {
+    pub fn is_connected(&self) -> bool {
+        self.components.len() <= 1 && self.isolated_locations.is_empty()
+    }
+
+    /// Every component except for the largest one. Robots that are in the
+    /// largest component cannot get to any of these and back.
+    pub fn unreachable_regions(&self) -> &[Vec<Entity>] {
+        self.components.get(1..).unwrap_or(&[])
+    }
+}
+
+/// Use this to analyze how well the lanes of a level are connected.
+#[derive(SystemParam)]
+pub struct AnalyzeConnectivity<'w, 's> {
+    lanes: TrafficLanes<'w, 's>,
+    anchors: Query<'w, 's, (&'static Anchor, &'static Parent)>,
+    nav_graphs: Query<'w, 's, Entity, With<NavGraphMarker>>,
+    locations: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Point<Entity>,
+            &'static AssociatedGraphs<Entity>,
+        ),
+        With<LocationTags>,
+    >,
+}
+
+impl<'w, 's> AnalyzeConnectivity<'w, 's> {
+    /// Analyze the lanes of a level that belong to a nav graph, or every lane
+    /// of the level if no nav graph is given.
+    pub fn analyze(&self, level: Entity, nav_graph: Option<Entity>) -> GraphConnectivity {
+        let traffic = LevelTrafficGraph::new(level, nav_graph, &self.lanes, &self.anchors);
+        let components = traffic.graph.strongly_connected_components();
+        let mut component_of = vec![0; traffic.waypoints.len()];
+        for (c, members) in components.iter().enumerate() {
+            for m in members {
+                component_of[*m] = c;
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let lanes = traffic
+            .graph
+            .lanes
+            .iter()
+            // Bidirectional lanes show up in both directions but only need to
+            // be reported once.
+            .filter(|lane| seen.insert((lane.start.min(lane.end), lane.start.max(lane.end))))
+            .map(|lane| [lane.start, lane.end].map(|i| (traffic.waypoints[i], component_of[i])))
+            .collect();
+
+        let isolated_locations = self
+            .locations
+            .iter()
+            .filter(|(_, point, graphs)| {
+                nav_graph.map_or(true, |g| graphs.includes(g))
+                    && self
+                        .anchors
+                        .get(point.0)
+                        .is_ok_and(|(_, parent)| parent.get() == level)
+                    && !traffic.nodes.contains_key(&point.0)
+            })
+            .map(|(e, _, _)| e)
+            .collect();
+
+        GraphConnectivity {
+            nav_graph,
+            components: components
+                .into_iter()
+                .map(|members| members.into_iter().map(|i| traffic.waypoints[i]).collect())
+                .collect(),
+            lanes,
+            isolated_locations,
+        }
+    }
+
+    /// Analyze every nav graph of the site on a level.
+    pub fn analyze_all(&self, level: Entity) -> Vec<GraphConnectivity> {
+        self.nav_graphs
+            .iter()
+            .map(|g| self.analyze(level, Some(g)))
+            .collect()
+    }
+}
+
+/// Colors the waypoints and lanes of the current level by the component
+/// that they belong to.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct ConnectivityOverlay {
+    pub enabled: bool,
+    /// The nav graph to color, or None for all lanes of the level
+    pub nav_graph: Option<Entity>,
+}
+
+/// Analyze and visualize how well the nav graphs of a site are connected.
+#[derive(Default)]
+pub struct ConnectivityPlugin;
+
+impl Plugin for ConnectivityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConnectivityOverlay>().add_systems(
+            Update,
+            draw_connectivity_overlay.run_if(AppState::in_displaying_mode()),
+        );
+    }
+}
+
+/// The color used for a component in the connectivity overlay.
+pub fn connectivity_color(component: usize) -> Color {
+    // Step around the color wheel by the golden angle so that neighboring
+    // components stand apart
+    Color::hsl((component as f32 * 137.5) % 360.0, 0.8, 0.5)
+}
+
+fn draw_connectivity_overlay(
+    overlay: Res<ConnectivityOverlay>,
+    current_level: Res<CurrentLevel>,
+    analyze: AnalyzeConnectivity,
+    transforms: Query<&GlobalTransform>,
+    mut gizmos: Gizmos,
+) {
+    if !overlay.enabled {
+        return;
+    }
+    let Some(level) = current_level.0 else {
+        return;
+    };
+    let connectivity = analyze.analyze(level, overlay.nav_graph);
+    let position = |e: Entity| -> Option<Vec3> {
+        let p = transforms.get(e).ok()?.translation();
+        Some(p + Vec3::Z * CONNECTIVITY_OVERLAY_HEIGHT)
+    };
+
+    for (c, members) in connectivity.components.iter().enumerate() {
+        let color = connectivity_color(c);
+        for p in members.iter().filter_map(|e| position(*e)) {
+            gizmos.circle(p, Vec3::Z, 0.25, color);
+        }
+    }
+    for [(a, ca), (b, cb)] in &connectivity.lanes {
+        let (Some(p0), Some(p1)) = (position(*a), position(*b)) else {
+            continue;
+        };
+        // Lanes between components can only be driven one way
+        let color = if ca == cb {
+            connectivity_color(*ca)
+        } else {
+            Color::GRAY
+        };
+        gizmos.line(p0, p1, color);
+    }
+    for p in connectivity
+        .isolated_locations
+        .iter()
+        .filter_map(|e| transforms.get(*e).ok())
+    {
+        gizmos.circle(
+            p.translation() + Vec3::Z * CONNECTIVITY_OVERLAY_HEIGHT,
+            Vec3::Z,
+            0.4,
+            Color::RED,
+        );
+    }
+}
+
+#[test]
+fn test_connectivity_of_level_lanes() {
+    use bevy::ecs::system::SystemState;
+
+    let mut world = World::new();
+    let site = world.spawn_empty().id();
+    let level = world.spawn_empty().set_parent(site).id();
+    let anchors: Vec<Entity> = [[0.0, 0.0], [3.0, 0.0], [6.0, 0.0], [9.0, 0.0]]
+        .into_iter()
+        .map(|p| world.spawn(Anchor::Translate2D(p)).set_parent(level).id())
+        .collect();
+    for (start, end, reverse) in [
+        (anchors[0], anchors[1], ReverseLane::Same),
+        // A one-way lane splits the graph into two components
+        (anchors[1], anchors[2], ReverseLane::Disable),
+    ] {
+        world
+            .spawn((
+                LaneMarker,
+                Edge::new(start, end),
+                Motion::default(),
+                reverse,
+                AssociatedGraphs::<Entity>::All,
+            ))
+            .set_parent(site);
+    }
+    let location = world
+        .spawn((
+            Point(anchors[3]),
+            AssociatedGraphs::<Entity>::All,
+            LocationTags(Vec::new()),
+        ))
+        .set_parent(site)
+        .id();
+
+    let mut state: SystemState<AnalyzeConnectivity> = SystemState::new(&mut world);
+    let connectivity = state.get(&world).analyze(level, None);
+    assert_eq!(connectivity.components.len(), 2);
+    assert_eq!(connectivity.lanes.len(), 2);
+    assert_eq!(connectivity.isolated_locations, vec![location]);
+    assert!(!connectivity.is_connected());
+}
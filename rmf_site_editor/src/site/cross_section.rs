@@ -0,0 +1,178 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, AppState};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// The line that the site is currently being cut along, if any.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct CrossSection {
+    pub line: Option<SectionLine>,
+}
+
+/// Everything on one level that gets cut by a [`SectionLine`]. All
+/// positions are distances along the line, in meters.
+#[derive(Debug, Clone, Default)]
+pub struct SectionOfLevel {
+    pub level: Entity,
+    pub name: String,
+    pub elevation: f32,
+    /// The parts of the line that are above a floor
+    pub floors: Vec<[f32; 2]>,
+    pub walls: Vec<(f32, Entity)>,
+    pub doors: Vec<(f32, Entity)>,
+}
+
+/// A lift shaft that gets cut by a [`SectionLine`]. Shafts are shown through
+/// every level of the site so they can be checked for vertical alignment.
+#[derive(Debug, Clone, Default)]
+pub struct SectionOfLift {
+    pub lift: Entity,
+    pub name: String,
+    pub intervals: Vec<[f32; 2]>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SiteSection {
+    /// Levels sorted from lowest to highest
+    pub levels: Vec<SectionOfLevel>,
+    pub lifts: Vec<SectionOfLift>,
+}
+
+/// Use this to cut a site along a [`SectionLine`].
+#[derive(SystemParam)]
+pub struct CutCrossSection<'w, 's> {
+    children: Query<'w, 's, &'static Children>,
+    levels: Query<'w, 's, (&'static NameInSite, &'static LevelElevation)>,
+    walls: Query<'w, 's, &'static Edge<Entity>, (With<WallMarker>, Without<Pending>)>,
+    doors: Query<'w, 's, &'static Edge<Entity>, (With<DoorType>, Without<Pending>)>,
+    floors: Query<'w, 's, &'static Path<Entity>, (With<FloorMarker>, Without<Pending>)>,
+    lifts: Query<
+        'w,
+        's,
+        (
+            &'static NameInSite,
+            &'static LiftCabin<Entity>,
+            &'static GlobalTransform,
+        ),
+        Without<Pending>,
+    >,
+    anchors: Query<'w, 's, &'static Anchor>,
+}
+
+impl<'w, 's> CutCrossSection<'w, 's> {
+    pub fn cut(&self, site: Entity, line: &SectionLine) -> SiteSection {
+        let mut section = SiteSection::default();
+        let Ok(site_children) = self.children.get(site) else {
+            return section;
+        };
+        let position = |anchor: Entity| -> Option<Vec2> {
+            let p = self
+                .anchors
+                .get(anchor)
+                .ok()?
+                .translation_for_category(Category::General);
+            Some(Vec2::from(p))
+        };
+        let crossing = |edge: &Edge<Entity>| -> Option<f32> {
+            line.crossing(position(edge.start())?, position(edge.end())?)
+        };
+
+        for site_child in site_children {
+            if let Ok((name, elevation)) = self.levels.get(*site_child) {
+                let mut level = SectionOfLevel {
+                    level: *site_child,
+                    name: name.0.clone(),
+                    elevation: elevation.0,
+                    ..Default::default()
+                };
+                for child in self.children.get(*site_child).into_iter().flatten() {
+                    if let Ok(edge) = self.walls.get(*child) {
+                        level.walls.extend(crossing(edge).map(|d| (d, *child)));
+                    } else if let Ok(edge) = self.doors.get(*child) {
+                        level.doors.extend(crossing(edge).map(|d| (d, *child)));
+                    } else if let Ok(path) = self.floors.get(*child) {
+                        let polygon: Option<Vec<Vec2>> =
+                            path.0.iter().map(|a| position(*a)).collect();
+                        if let Some(polygon) = polygon {
+                            level.floors.extend(line.intervals_inside(&polygon));
+                        }
+                    }
+                }
+                section.levels.push(level);
+            } else if let Ok((name, cabin, tf)) = self.lifts.get(*site_child) {
+                let LiftCabin::Rect(cabin) = cabin;
+                let aabb = cabin.aabb();
+                let (c, h) = (aabb.center, aabb.half_extents);
+                let corners: Vec<Vec2> = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]]
+                    .into_iter()
+                    .map(|[sx, sy]| {
+                        let local = Vec3::new(c.x + sx * h.x, c.y + sy * h.y, 0.0);
+                        tf.transform_point(local).truncate()
+                    })
+                    .collect();
+                let intervals = line.intervals_inside(&corners);
+                if !intervals.is_empty() {
+                    section.lifts.push(SectionOfLift {
+                        lift: *site_child,
+                        name: name.0.clone(),
+                        intervals,
+                    });
+                }
+            }
+        }
+
+        section
+            .levels
+            .sort_by(|a, b| a.elevation.total_cmp(&b.elevation));
+        section
+    }
+}
+
+/// Cut the site along a line to check the vertical alignment of its levels.
+#[derive(Default)]
+pub struct CrossSectionPlugin;
+
+impl Plugin for CrossSectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CrossSection>().add_systems(
+            Update,
+            draw_cross_section_line.run_if(AppState::in_displaying_mode()),
+        );
+    }
+}
+
+fn draw_cross_section_line(
+    cross_section: Res<CrossSection>,
+    current_level: Res<CurrentLevel>,
+    levels: Query<&GlobalTransform>,
+    mut gizmos: Gizmos,
+) {
+    let Some(line) = &cross_section.line else {
+        return;
+    };
+    let level_tf = current_level
+        .0
+        .and_then(|l| levels.get(l).ok())
+        .copied()
+        .unwrap_or_default();
+    let p0 = level_tf.transform_point(line.start.extend(0.1));
+    let p1 = level_tf.transform_point(line.end.extend(0.1));
+    gizmos.line(p0, p1, Color::FUCHSIA);
+    // Mark which end is the start of the section
+    gizmos.circle(p0, Vec3::Z, 0.2, Color::FUCHSIA);
+}
@@ -0,0 +1,261 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, AppState, CurrentWorkspace};
+use bevy::{prelude::*, render::mesh::shape::Capsule};
+use std::collections::HashMap;
+
+/// The longest step that the preview takes at once. Longer frames are split
+/// into several steps so that agents do not tunnel through each other.
+const MAX_PREVIEW_STEP: f32 = 0.1;
+const AGENT_MESH_RADIUS: f32 = 0.25;
+const AGENT_MESH_DEPTH: f32 = 1.2;
+
+/// Send this event to start or stop previewing the crowd simulation of the
+/// current site on the current level.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ControlCrowdPreview {
+    Start,
+    Stop,
+}
+
+/// The crowd simulation preview that is currently running, if any.
+#[derive(Resource)]
+pub struct CrowdPreviewState {
+    pub preview: Option<CrowdPreview>,
+    /// The level that the preview is running on
+    pub level: Option<Entity>,
+    pub paused: bool,
+    /// How many times faster than real time the preview runs
+    pub speed: f32,
+}
+
+impl Default for CrowdPreviewState {
+    fn default() -> Self {
+        Self {
+            preview: None,
+            level: None,
+            paused: false,
+            speed: 1.0,
+        }
+    }
+}
+
+impl CrowdPreviewState {
+    pub fn is_running(&self) -> bool {
+        self.preview.is_some()
+    }
+}
+
+/// Marks the visual of an agent of the crowd preview. The value is the index
+/// of the agent inside of the preview.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct CrowdPreviewAgent(pub usize);
+
+#[derive(Resource)]
+pub struct CrowdPreviewAssets {
+    pub agent_mesh: Handle<Mesh>,
+    pub walking_material: Handle<StandardMaterial>,
+    pub blocked_material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for CrowdPreviewAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let agent_mesh = meshes.add(Mesh::from(Capsule {
+            radius: AGENT_MESH_RADIUS,
+            depth: AGENT_MESH_DEPTH,
+            ..default()
+        }));
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let walking_material = materials.add(old_default_material(Color::rgb(0.2, 0.6, 0.9)));
+        let blocked_material = materials.add(old_default_material(Color::rgb(0.9, 0.1, 0.1)));
+        Self {
+            agent_mesh,
+            walking_material,
+            blocked_material,
+        }
+    }
+}
+
+/// Animate the agents of a crowd simulation inside the editor so that
+/// bottlenecks and unreachable goal sets can be found before exporting.
+#[derive(Default)]
+pub struct CrowdPreviewPlugin;
+
+impl Plugin for CrowdPreviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ControlCrowdPreview>()
+            .init_resource::<CrowdPreviewState>()
+            .init_resource::<CrowdPreviewAssets>()
+            .add_systems(
+                Update,
+                (control_crowd_preview, update_crowd_preview)
+                    .chain()
+                    .run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+fn control_crowd_preview(
+    mut commands: Commands,
+    mut control: EventReader<ControlCrowdPreview>,
+    mut state: ResMut<CrowdPreviewState>,
+    current_workspace: Res<CurrentWorkspace>,
+    current_level: Res<CurrentLevel>,
+    crowd_sims: Query<&CrowdSim<Entity>>,
+    human_lanes: Query<(&Edge<Entity>, &ReverseLane), With<HumanLaneMarker>>,
+    anchors: Query<(&Anchor, &Parent)>,
+    agents: Query<Entity, With<CrowdPreviewAgent>>,
+    assets: Res<CrowdPreviewAssets>,
+) {
+    let Some(control) = control.read().last() else {
+        return;
+    };
+
+    for agent in &agents {
+        commands.entity(agent).despawn_recursive();
+    }
+    state.preview = None;
+    state.level = None;
+
+    if *control == ControlCrowdPreview::Stop {
+        return;
+    }
+    let (Some(site), Some(level)) = (current_workspace.root, current_level.0) else {
+        return;
+    };
+    let Ok(crowd_sim) = crowd_sims.get(site) else {
+        return;
+    };
+
+    // Anchors may belong to the level or be shared by the whole site.
+    let anchor_position = |anchor: Entity, category: Category| -> Option<Vec2> {
+        let (anchor, parent) = anchors.get(anchor).ok()?;
+        if parent.get() != level && parent.get() != site {
+            return None;
+        }
+        Some(Vec2::from(anchor.translation_for_category(category)))
+    };
+
+    let mut points = Vec::new();
+    let mut nodes: HashMap<Entity, usize> = HashMap::new();
+    let mut connections = Vec::new();
+    // Lanes are children of the site, so the anchors of a lane decide which
+    // level it is on.
+    for (edge, reverse) in &human_lanes {
+        let mut node = |anchor: Entity| -> Option<usize> {
+            if let Some(index) = nodes.get(&anchor) {
+                return Some(*index);
+            }
+            let p = anchor_position(anchor, Category::Lane)?;
+            points.push(p);
+            nodes.insert(anchor, points.len() - 1);
+            Some(points.len() - 1)
+        };
+        let (Some(start), Some(end)) = (node(edge.start()), node(edge.end())) else {
+            continue;
+        };
+        connections.push((start, end, !matches!(reverse, ReverseLane::Disable)));
+    }
+    let graph = WalkGraph::new(points, connections);
+
+    let preview = CrowdPreview::new(
+        crowd_sim,
+        graph,
+        |anchor| anchor_position(anchor, Category::General),
+        site.to_bits(),
+    );
+    for (i, agent) in preview.agents.iter().enumerate() {
+        commands
+            .spawn((
+                PbrBundle {
+                    mesh: assets.agent_mesh.clone(),
+                    material: assets.walking_material.clone(),
+                    transform: agent_transform(agent),
+                    ..default()
+                },
+                CrowdPreviewAgent(i),
+            ))
+            .set_parent(level);
+    }
+    state.preview = Some(preview);
+    state.level = Some(level);
+}
+
+fn update_crowd_preview(
+    mut state: ResMut<CrowdPreviewState>,
+    mut control: EventWriter<ControlCrowdPreview>,
+    current_level: Res<CurrentLevel>,
+    time: Res<Time>,
+    assets: Res<CrowdPreviewAssets>,
+    mut agents: Query<(
+        &CrowdPreviewAgent,
+        &mut Transform,
+        &mut Handle<StandardMaterial>,
+    )>,
+) {
+    if !state.is_running() {
+        return;
+    }
+    if state.level != current_level.0 {
+        // The preview only covers one level, so stop it when the user moves
+        // to a different one.
+        control.send(ControlCrowdPreview::Stop);
+        return;
+    }
+
+    let state = &mut *state;
+    let Some(preview) = &mut state.preview else {
+        return;
+    };
+    if !state.paused {
+        let mut remaining = time.delta_seconds() * state.speed;
+        while remaining > 0.0 {
+            let dt = remaining.min(MAX_PREVIEW_STEP);
+            preview.step(dt);
+            remaining -= dt;
+        }
+    }
+
+    for (agent, mut tf, mut material) in &mut agents {
+        let Some(agent) = preview.agents.get(agent.0) else {
+            continue;
+        };
+        *tf = agent_transform(agent);
+        let expected = if agent.is_blocked() {
+            &assets.blocked_material
+        } else {
+            &assets.walking_material
+        };
+        if *material != *expected {
+            *material = expected.clone();
+        }
+    }
+}
+
+fn agent_transform(agent: &PreviewAgent) -> Transform {
+    let height = AGENT_MESH_DEPTH + 2.0 * AGENT_MESH_RADIUS;
+    // The capsule mesh stands along the Y axis, so it is rotated upright and
+    // scaled sideways to match the radius of the agent.
+    let s = agent.radius / AGENT_MESH_RADIUS;
+    Transform {
+        translation: agent.position.extend(height / 2.0),
+        rotation: Quat::from_rotation_x(std::f32::consts::FRAC_PI_2),
+        scale: Vec3::new(s, 1.0, s),
+    }
+}
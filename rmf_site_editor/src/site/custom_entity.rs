@@ -0,0 +1,138 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{interaction::Selectable, site::*, AppState};
+use bevy::prelude::*;
+
+/// Add support for custom kinds of entities. Each kind is described by a
+/// [`CustomEntitySchema`] that belongs to the site, and its entities are
+/// rendered with the style of their schema.
+pub struct CustomEntityPlugin;
+
+impl Plugin for CustomEntityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            ChangePlugin::<CustomFields>::default(),
+            ChangePlugin::<CustomEntitySchemas>::default(),
+        ))
+        .add_systems(
+            PostUpdate,
+            update_custom_entity_visuals
+                .run_if(AppState::in_displaying_mode())
+                .in_set(SiteUpdateSet::BetweenVisibilityAndTransform),
+        );
+    }
+}
+
+/// Render custom entities with the style of their schema and make sure their
+/// fields match the schema.
+fn update_custom_entity_visuals(
+    mut commands: Commands,
+    changed_entities: Query<Entity, (With<CustomEntityMarker>, Changed<CustomEntityKind>)>,
+    all_entities: Query<Entity, With<CustomEntityMarker>>,
+    changed_schemas: Query<(), Changed<CustomEntitySchemas>>,
+    mut entities: Query<(
+        &CustomEntityKind,
+        &Pose,
+        &mut CustomFields,
+        Option<&Handle<Mesh>>,
+        Option<&Handle<StandardMaterial>>,
+    )>,
+    schemas: Query<&CustomEntitySchemas>,
+    parents: Query<&Parent>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // When any schema changes we restyle every custom entity, which is cheap
+    // since schemas are only changed by loading a schema file.
+    let targets: Vec<Entity> = if changed_schemas.is_empty() {
+        changed_entities.iter().collect()
+    } else {
+        all_entities.iter().collect()
+    };
+
+    for e in targets {
+        let Ok((kind, pose, mut fields, mesh_handle, material_handle)) = entities.get_mut(e) else {
+            continue;
+        };
+        let schema = AncestorIter::new(&parents, e)
+            .find_map(|p| schemas.get(p).ok())
+            .and_then(|schemas| schemas.get(&kind.0));
+        let style = match schema {
+            Some(schema) => {
+                // Only touch the fields when they need to change so that we
+                // do not trigger change detection for every entity.
+                let mut conformed = fields.clone();
+                schema.conform(&mut conformed);
+                if conformed != *fields {
+                    *fields = conformed;
+                }
+                schema.style.clone()
+            }
+            None => {
+                warn!(
+                    "No schema was found for the custom entity kind [{}], it will be \
+                    rendered with the default style",
+                    kind.0
+                );
+                CustomEntityStyle::default()
+            }
+        };
+
+        let size = style.size;
+        let mesh = match style.shape {
+            CustomEntityShape::Sphere => Mesh::from(shape::UVSphere {
+                radius: size / 2.0,
+                ..default()
+            }),
+            CustomEntityShape::Box => Mesh::from(shape::Box::new(size, size, size)),
+            CustomEntityShape::Cylinder => Mesh::from(shape::Cylinder {
+                radius: size / 2.0,
+                height: size,
+                ..default()
+            }),
+        };
+        let [r, g, b, a] = style.color;
+        let material = StandardMaterial {
+            base_color: Color::rgba(r, g, b, a),
+            alpha_mode: if a < 1.0 {
+                AlphaMode::Blend
+            } else {
+                AlphaMode::Opaque
+            },
+            ..default()
+        };
+
+        // Restyled entities reuse the assets that they already have
+        if let (Some(mesh_handle), Some(material_handle)) = (mesh_handle, material_handle) {
+            meshes.insert(mesh_handle, mesh);
+            materials.insert(material_handle, material);
+            continue;
+        }
+
+        commands
+            .entity(e)
+            .insert(PbrBundle {
+                mesh: meshes.add(mesh),
+                material: materials.add(material),
+                transform: pose.transform(),
+                ..default()
+            })
+            .insert(Selectable::new(e))
+            .insert(Category::CustomEntity);
+    }
+}
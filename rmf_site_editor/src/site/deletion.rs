@@ -29,7 +29,7 @@ use bevy::{
     prelude::*,
 };
 use rmf_site_format::{Edge, Path, Point};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 // TODO(MXG): Use this module to implement the deletion buffer. The role of the
 // deletion buffer will be to preserve deleted entities so that they can be
@@ -169,7 +169,7 @@ fn handle_deletion_requests(
         });
 
     let (_, mut params) = state.get_mut(world);
-    for delete in pending_delete.iter() {
+    for delete in order_deletions(pending_delete, &params.dependents) {
         if delete.and_dependents {
             recursive_dependent_delete(delete.element, &mut params);
         } else {
@@ -179,6 +179,44 @@ fn handle_deletion_requests(
     state.apply(world);
 }
 
+/// Put a batch of deletions in an order where each element comes after every
+/// element of the batch that depends on it. Otherwise deleting a selection of
+/// anchors and the lanes between them would refuse to delete the anchors
+/// because the lanes still depend on them.
+fn order_deletions(
+    pending_delete: HashSet<Delete>,
+    dependents: &Query<&mut Dependents>,
+) -> Vec<Delete> {
+    let batch: BTreeMap<Entity, Delete> = pending_delete
+        .into_iter()
+        .map(|delete| (delete.element, delete))
+        .collect();
+    let mut visited = HashSet::new();
+    let mut ordered = Vec::new();
+    for element in batch.keys() {
+        // Each element is pushed once all of its dependents have been pushed
+        let mut stack = vec![(*element, false)];
+        while let Some((e, expanded)) = stack.pop() {
+            if expanded {
+                ordered.push(batch[&e]);
+                continue;
+            }
+            if !visited.insert(e) {
+                continue;
+            }
+            stack.push((e, true));
+            if let Ok(deps) = dependents.get(e) {
+                for dep in deps.iter() {
+                    if batch.contains_key(dep) && !visited.contains(dep) {
+                        stack.push((*dep, false));
+                    }
+                }
+            }
+        }
+    }
+    ordered
+}
+
 fn cautious_delete(element: Entity, params: &mut DeletionParams) {
     let mut all_descendents = HashSet::new();
     let mut queue = Vec::new();
@@ -290,7 +328,9 @@ fn cautious_delete(element: Entity, params: &mut DeletionParams) {
 }
 
 fn recursive_dependent_delete(element: Entity, params: &mut DeletionParams) {
-    let mut all_to_delete = HashSet::new();
+    // Despawn in a consistent order so that the entities which get recycled
+    // afterwards do not depend on hashing.
+    let mut all_to_delete = BTreeSet::new();
     let mut queue = Vec::new();
     queue.push(element);
     while let Some(top) = queue.pop() {
@@ -344,7 +384,7 @@ fn recursive_dependent_delete(element: Entity, params: &mut DeletionParams) {
     perform_deletions(all_to_delete, params);
 }
 
-fn perform_deletions(all_to_delete: HashSet<Entity>, params: &mut DeletionParams) {
+fn perform_deletions(all_to_delete: BTreeSet<Entity>, params: &mut DeletionParams) {
     for e in all_to_delete.iter().copied() {
         // TODO(MXG): Consider refactoring some of this bookkeeping to separate
         // systems that use the RemovedComponents system parameter.
@@ -415,3 +455,27 @@ fn perform_deletions(all_to_delete: HashSet<Entity>, params: &mut DeletionParams
         params.commands.entity(e).despawn();
     }
 }
+
+#[test]
+fn test_deletions_are_ordered_with_dependents_first() {
+    let mut world = World::new();
+    let lane = world.spawn_empty().id();
+    let other_lane = world.spawn_empty().id();
+    let start = world.spawn(Dependents::single(lane)).id();
+    let end = world
+        .spawn(Dependents(BTreeSet::from_iter([lane, other_lane])))
+        .id();
+
+    let pending: HashSet<Delete> = [end, start, lane].map(Delete::new).into_iter().collect();
+    let mut state: SystemState<Query<&mut Dependents>> = SystemState::new(&mut world);
+    let ordered: Vec<Entity> = order_deletions(pending, &state.get_mut(&mut world))
+        .into_iter()
+        .map(|delete| delete.element)
+        .collect();
+
+    // The other lane is not being deleted, so it does not get added
+    assert_eq!(ordered.len(), 3);
+    let position = |e: Entity| ordered.iter().position(|o| *o == e).unwrap();
+    assert!(position(lane) < position(start));
+    assert!(position(lane) < position(end));
+}
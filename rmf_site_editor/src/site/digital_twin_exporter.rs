@@ -0,0 +1,163 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use bevy_gltf_export::{export_meshes, CompressGltfOptions, MeshData};
+
+use std::path::Path;
+
+use crate::site::{DoorSegments, FloorSegments, VisualMeshMarker};
+use rmf_site_format::{
+    digital_twin_level_file, digital_twin_level_node, ExportOptions, LevelElevation, ModelMarker,
+    NameInSite, WallMarker,
+};
+
+/// Write one glTF file for each level of the site. Walls, floors, doors, and
+/// the visuals of models are merged into a single node per level with their
+/// materials included, and every vertex is transformed by the export options.
+pub fn collect_digital_twin_meshes(
+    world: &mut World,
+    site: Entity,
+    folder: &Path,
+    options: &ExportOptions,
+) -> Result<(), String> {
+    let mut state: SystemState<(
+        Query<&Children>,
+        Query<(&NameInSite, &LevelElevation, &Children)>,
+        Query<Entity, With<WallMarker>>,
+        Query<&FloorSegments>,
+        Query<&DoorSegments>,
+        Query<Entity, With<ModelMarker>>,
+        Query<(), With<VisualMeshMarker>>,
+        Query<(&Handle<Mesh>, &Handle<StandardMaterial>)>,
+        Query<&GlobalTransform>,
+    )> = SystemState::new(world);
+    let (
+        q_children,
+        q_levels,
+        q_walls,
+        q_floors,
+        q_doors,
+        q_models,
+        q_visuals,
+        q_pbr,
+        q_global_tfs,
+    ) = state.get(world);
+
+    let image_assets = world.resource::<Assets<Image>>();
+    let mesh_assets = world.resource::<Assets<Mesh>>();
+    let material_assets = world.resource::<Assets<StandardMaterial>>();
+
+    let get_mesh_and_material = |entity: Entity| -> Option<(&Mesh, &StandardMaterial)> {
+        let (mesh, material) = q_pbr.get(entity).ok()?;
+        Some((mesh_assets.get(mesh)?, material_assets.get(material)?))
+    };
+
+    // Everything that gets exported is moved from site coordinates into the
+    // coordinates that were requested for the export.
+    let export_tf = Transform {
+        rotation: options.rotation(),
+        scale: Vec3::splat(options.scale),
+        ..Default::default()
+    }
+    .mul_transform(Transform::from_translation(-Vec3::from(options.origin)));
+
+    let Ok(site_children) = q_children.get(site) else {
+        return Ok(());
+    };
+    for site_child in site_children.iter() {
+        let Ok((level_name, elevation, children)) = q_levels.get(*site_child) else {
+            continue;
+        };
+        let level_tf = export_tf.mul_transform(Transform::from_xyz(0.0, 0.0, **elevation));
+        // Meshes of models and doors are placed by their global transforms,
+        // which do not include the elevation of the level.
+        let placed_tf = |entity: Entity| -> Option<Transform> {
+            let tf = q_global_tfs.get(entity).ok()?.compute_transform();
+            Some(level_tf.mul_transform(tf))
+        };
+
+        let mut visual_data = Vec::new();
+        for child in children.iter() {
+            if let Ok(wall) = q_walls.get(*child) {
+                if let Some((mesh, material)) = get_mesh_and_material(wall) {
+                    visual_data.push(MeshData {
+                        mesh,
+                        material: Some(material),
+                        transform: Some(level_tf),
+                    });
+                }
+            } else if let Ok(floor) = q_floors.get(*child) {
+                if let Some((mesh, material)) = get_mesh_and_material(floor.mesh) {
+                    visual_data.push(MeshData {
+                        mesh,
+                        material: Some(material),
+                        transform: Some(level_tf),
+                    });
+                }
+            } else if let Ok(segments) = q_doors.get(*child) {
+                for entity in segments.body.entities().iter() {
+                    let (Some((mesh, material)), Some(tf)) =
+                        (get_mesh_and_material(*entity), placed_tf(*entity))
+                    else {
+                        continue;
+                    };
+                    visual_data.push(MeshData {
+                        mesh,
+                        material: Some(material),
+                        transform: Some(tf),
+                    });
+                }
+            } else if let Ok(model) = q_models.get(*child) {
+                for model_child in DescendantIter::new(&q_children, model) {
+                    if !q_visuals.contains(model_child) {
+                        continue;
+                    }
+                    for entity in DescendantIter::new(&q_children, model_child) {
+                        let (Some((mesh, material)), Some(tf)) =
+                            (get_mesh_and_material(entity), placed_tf(entity))
+                        else {
+                            continue;
+                        };
+                        visual_data.push(MeshData {
+                            mesh,
+                            material: Some(material),
+                            transform: Some(tf),
+                        });
+                    }
+                }
+            }
+        }
+
+        let path = folder.join(digital_twin_level_file(level_name));
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let image_getter = |id: &Handle<Image>| image_assets.get(id).cloned();
+        let meshes = export_meshes(
+            visual_data,
+            Some(digital_twin_level_node(level_name)),
+            image_getter,
+            CompressGltfOptions::default(),
+        )
+        .map_err(|e| e.to_string())?;
+        let bytes = meshes.to_bytes().map_err(|e| e.to_string())?;
+        std::fs::write(path, bytes).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
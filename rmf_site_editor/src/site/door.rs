@@ -489,14 +489,14 @@ pub const DUPLICATED_DOOR_NAME_ISSUE_UUID: Uuid =
 // generate an issue if that is the case
 pub fn check_for_duplicated_door_names(
     mut commands: Commands,
-    mut validate_events: EventReader<ValidateWorkspace>,
+    mut validate_events: ValidationRequests,
     parents: Query<&Parent>,
     door_names: Query<(Entity, &NameInSite), With<DoorMarker>>,
 ) {
-    for root in validate_events.read() {
+    for root in validate_events.roots() {
         let mut names: HashMap<String, BTreeSet<Entity>> = HashMap::new();
         for (e, name) in &door_names {
-            if AncestorIter::new(&parents, e).any(|p| p == **root) {
+            if AncestorIter::new(&parents, e).any(|p| p == root) {
                 let entities_with_name = names.entry(name.0.clone()).or_default();
                 entities_with_name.insert(e);
             }
@@ -513,8 +513,34 @@ pub fn check_for_duplicated_door_names(
                            name, rename the affected doors".to_string()
                 };
                 let id = commands.spawn(issue).id();
-                commands.entity(**root).add_child(id);
+                commands.entity(root).add_child(id);
             }
         }
     }
 }
+
+/// RMF requires door names to be unique, so give a new door a suffix if
+/// `name` is already `taken` by another door.
+pub fn unique_door_name(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    let mut unique_name = name.to_owned();
+    let mut suffix = 1;
+    while taken(&unique_name) {
+        unique_name = format!("{name}_{suffix}");
+        suffix += 1;
+    }
+    unique_name
+}
+
+#[test]
+fn test_unique_door_name_adds_the_first_free_suffix() {
+    let taken: BTreeSet<&str> = ["door", "door_1", "lobby_door_1"].into_iter().collect();
+    assert_eq!(
+        unique_door_name("office_door", |n| taken.contains(n)),
+        "office_door"
+    );
+    assert_eq!(unique_door_name("door", |n| taken.contains(n)), "door_2");
+    assert_eq!(
+        unique_door_name("lobby_door_1", |n| taken.contains(n)),
+        "lobby_door_1_1"
+    );
+}
@@ -0,0 +1,201 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, AppState, Issue, ValidationRequests};
+use bevy::{prelude::*, utils::Uuid};
+
+/// Lanes with an anchor closer than this to a door, in meters, are considered
+/// to already have a waypoint in the doorway.
+pub const DOORWAY_TOLERANCE: f32 = 0.2;
+
+/// Send this event to fix a lane that crosses a door without a waypoint in
+/// the doorway. An anchor is placed in the middle of the door, or an existing
+/// anchor there is reused, and the lane is split into two lanes that meet at
+/// that anchor.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct InsertDoorway {
+    pub lane: Entity,
+    pub door: Entity,
+}
+
+#[derive(Default)]
+pub struct DoorwayPlugin;
+
+impl Plugin for DoorwayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<InsertDoorway>().add_systems(
+            Update,
+            insert_doorways.run_if(AppState::in_displaying_mode()),
+        );
+    }
+}
+
+/// Get the positions of the anchors of a door along with its level.
+fn door_endpoints(
+    edge: &Edge<Entity>,
+    anchors: &Query<(&Anchor, &Parent)>,
+) -> Option<(Entity, Vec2, Vec2)> {
+    let (start, parent) = anchors.get(edge.start()).ok()?;
+    let (end, _) = anchors.get(edge.end()).ok()?;
+    Some((
+        parent.get(),
+        Vec2::from(start.translation_for_category(Category::Door)),
+        Vec2::from(end.translation_for_category(Category::Door)),
+    ))
+}
+
+fn distance_to_segment(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = if ab.length_squared() > 0.0 {
+        ((p - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    p.distance(a + t * ab)
+}
+
+/// Check whether the lane `[l0, l1]` passes through the door `[d0, d1]`
+/// without either of its anchors being in the doorway.
+pub fn lane_crosses_door(l0: Vec2, l1: Vec2, d0: Vec2, d1: Vec2) -> bool {
+    if distance_to_segment(l0, d0, d1) < DOORWAY_TOLERANCE
+        || distance_to_segment(l1, d0, d1) < DOORWAY_TOLERANCE
+    {
+        return false;
+    }
+    let r = l1 - l0;
+    let s = d1 - d0;
+    let det = r.perp_dot(s);
+    if det.abs() < 1e-6 {
+        return false;
+    }
+    let t = (d0 - l0).perp_dot(s) / det;
+    let u = (d0 - l0).perp_dot(r) / det;
+    (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)
+}
+
+/// Unique UUID to identify issue of lanes that cross doors without a waypoint
+pub const LANE_CROSSES_DOOR_ISSUE_UUID: Uuid =
+    Uuid::from_u128(0x5d2c8e0e4b1f4a7f9a3c6e21d8b7f410u128);
+
+// When triggered by a validation request event, check if there are lanes that pass through a
+// door without a waypoint in the doorway and generate an issue if that is the case
+pub fn check_for_lanes_crossing_doors(
+    mut commands: Commands,
+    mut validate_events: ValidationRequests,
+    parents: Query<&Parent>,
+    lanes: Query<(Entity, &Edge<Entity>), With<LaneMarker>>,
+    doors: Query<(Entity, &Edge<Entity>, &NameInSite), With<DoorMarker>>,
+    anchors: Query<(&Anchor, &Parent)>,
+    levels: Query<(), With<LevelElevation>>,
+) {
+    const ISSUE_HINT: &str = "RMF only requests a door to open when a lane ends or starts in its \
+                        doorway. Use the button below to insert a waypoint in the middle of the \
+                        door and split the lane there";
+    for request in validate_events.read() {
+        let root = request.root();
+        let level_doors: Vec<_> = doors
+            .iter()
+            .filter(|(e, ..)| AncestorIter::new(&parents, *e).any(|p| p == root))
+            .filter_map(|(e, edge, name)| {
+                door_endpoints(edge, &anchors).map(|(level, d0, d1)| (e, name, level, d0, d1))
+            })
+            .collect();
+        for (lane, edge) in &lanes {
+            if !AncestorIter::new(&parents, lane).any(|p| p == root) {
+                continue;
+            }
+            let Some((level, l0, l1)) = lane_endpoints(edge, &anchors, &levels) else {
+                continue;
+            };
+            for (door, name, door_level, d0, d1) in &level_doors {
+                if *door_level != level
+                    || (!request.affects(lane, None) && !request.affects(*door, Some(level)))
+                    || !lane_crosses_door(l0, l1, *d0, *d1)
+                {
+                    continue;
+                }
+                let issue = Issue {
+                    key: IssueKey {
+                        entities: [lane, *door].into(),
+                        kind: LANE_CROSSES_DOOR_ISSUE_UUID,
+                    },
+                    brief: format!("Lane crosses door {} without a waypoint", name.0),
+                    hint: ISSUE_HINT.to_string(),
+                };
+                let id = commands.spawn(issue).id();
+                commands.entity(root).add_child(id);
+            }
+        }
+    }
+}
+
+fn insert_doorways(
+    mut commands: Commands,
+    mut requests: EventReader<InsertDoorway>,
+    lanes: Query<(LaneProperties, Has<HumanLaneMarker>), With<LaneMarker>>,
+    doors: Query<&Edge<Entity>, With<DoorMarker>>,
+    anchors: Query<(&Anchor, &Parent)>,
+    levels: Query<(), With<LevelElevation>>,
+    children: Query<&Children>,
+    mut delete: EventWriter<Delete>,
+) {
+    for InsertDoorway { lane, door } in requests.read() {
+        let Ok((properties, human)) = lanes.get(*lane) else {
+            continue;
+        };
+        let lane_parent = properties.4.get();
+        let original = lane_from_properties(properties);
+        let Ok(door_edge) = doors.get(*door) else {
+            continue;
+        };
+        let Some((level, l0, l1)) = lane_endpoints(&original.anchors, &anchors, &levels) else {
+            continue;
+        };
+        let Some((door_level, d0, d1)) = door_endpoints(door_edge, &anchors) else {
+            continue;
+        };
+        if level != door_level || !lane_crosses_door(l0, l1, d0, d1) {
+            continue;
+        }
+
+        let doorway = (d0 + d1) / 2.0;
+        let existing = children.get(level).ok().and_then(|children| {
+            children.iter().copied().find(|child| {
+                anchors.get(*child).is_ok_and(|(anchor, _)| {
+                    Vec2::from(anchor.translation_for_category(Category::Lane)).distance(doorway)
+                        < DOORWAY_TOLERANCE
+                })
+            })
+        });
+        let anchor = existing.unwrap_or_else(|| {
+            commands
+                .spawn(AnchorBundle::new(doorway.to_array().into()))
+                .set_parent(level)
+                .id()
+        });
+
+        let (_, pieces) = split_lane_at(&original, l0, l1, doorway, anchor);
+        for piece in pieces {
+            let mut new_lane = commands.spawn(piece);
+            new_lane.set_parent(lane_parent);
+            if human {
+                new_lane.insert(HumanLaneMarker);
+            }
+        }
+        delete.send(Delete::new(*lane));
+    }
+}
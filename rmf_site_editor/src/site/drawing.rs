@@ -17,16 +17,19 @@
 
 use crate::{
     interaction::Selectable,
-    shapes::make_flat_rect_mesh,
+    shapes::{make_flat_rect_mesh, MeshBuffer},
     site::{
-        get_current_workspace_path, Anchor, DefaultFile, FiducialMarker, GlobalDrawingVisibility,
-        LayerVisibility, MeasurementMarker, MeasurementSegment, RecencyRank,
-        DEFAULT_MEASUREMENT_OFFSET, FLOOR_LAYER_START,
+        get_current_workspace_path, Anchor, Change, DefaultFile, FiducialMarker,
+        GlobalDrawingVisibility, LayerVisibility, MeasurementMarker, MeasurementSegment,
+        RecencyRank, DEFAULT_MEASUREMENT_OFFSET, FLOOR_LAYER_START,
     },
     CurrentWorkspace,
 };
 use bevy::{asset::LoadState, math::Affine3A, prelude::*};
-use rmf_site_format::{AssetSource, Category, DrawingProperties, PixelsPerMeter, Pose};
+use rmf_site_format::{
+    AssetSource, Category, DrawingProperties, DrawingSourceInfo, NameInSite, PixelsPerMeter, Pose,
+    Rectification,
+};
 use std::path::PathBuf;
 
 #[derive(Bundle, Debug, Clone)]
@@ -66,11 +69,81 @@ pub struct DrawingSegments {
     leaf: Entity,
 }
 
+/// Size of the image of a drawing in pixels, kept so the mesh of the drawing
+/// can be regenerated when its rectification changes.
+#[derive(Debug, Clone, Copy, Component, Deref)]
+pub struct DrawingImageSize(Vec2);
+
+/// How many cells each side of a rectified drawing is divided into. The
+/// homography is only applied at the vertices, so finer grids follow the
+/// perspective warp more closely.
+const RECTIFIED_DRAWING_CELLS: u32 = 16;
+
+/// The image of a drawing no longer matches the [`DrawingSourceInfo`] that
+/// was recorded when it was calibrated. This holds what the loaded image
+/// looks like, so it can be accepted as the new source.
+#[derive(Debug, Clone, Component, Deref)]
+pub struct DrawingSourceMismatch(pub DrawingSourceInfo);
+
 // We need to keep track of the drawing data until the image is loaded
 // since we will need to scale the mesh according to the size of the image
 #[derive(Component, Deref, DerefMut)]
 pub struct LoadingDrawing(Handle<Image>);
 
+fn make_drawing_mesh(
+    size: Vec2,
+    rectification: Option<&Rectification>,
+    pixels_per_meter: &PixelsPerMeter,
+) -> Mesh {
+    let Some(homography) = rectification.and_then(|r| r.homography()) else {
+        // We set this up so that the origin of the drawing is in the top left corner
+        return make_flat_rect_mesh(size.x, size.y)
+            .transform_by(Affine3A::from_translation(Vec3::new(
+                size.x / 2.0,
+                -size.y / 2.0,
+                0.0,
+            )))
+            .into();
+    };
+
+    // The homography gives real coordinates in meters, but the drawing
+    // entity is scaled down to pixels, so the mesh is scaled back up.
+    let n = RECTIFIED_DRAWING_CELLS;
+    let mut positions = Vec::new();
+    let mut uv = Vec::new();
+    for j in 0..=n {
+        for i in 0..=n {
+            let s = i as f32 / n as f32;
+            let t = j as f32 / n as f32;
+            let [x, y] = homography.apply([s * size.x, t * size.y]);
+            positions.push([x * pixels_per_meter.0, y * pixels_per_meter.0, 0.0]);
+            uv.push([s, t]);
+        }
+    }
+
+    // Render both sides, the same as the unrectified mesh
+    let vertex_count = positions.len() as u32;
+    let mut normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+    normals.extend(vec![[0.0, 0.0, -1.0]; positions.len()]);
+    positions.extend_from_within(..);
+    uv.extend_from_within(..);
+
+    let mut indices = Vec::new();
+    let index = |i: u32, j: u32| j * (n + 1) + i;
+    for j in 0..n {
+        for i in 0..n {
+            let (a, b) = (index(i, j), index(i + 1, j));
+            let (c, d) = (index(i + 1, j + 1), index(i, j + 1));
+            indices.extend([d, c, b, d, b, a]);
+            indices.extend([d, b, c, d, a, b].map(|k| k + vertex_count));
+        }
+    }
+
+    MeshBuffer::new(positions, normals, indices)
+        .with_uv(uv)
+        .into()
+}
+
 fn drawing_layer_height(rank: Option<&RecencyRank<DrawingMarker>>) -> f32 {
     rank.map(|r| r.proportion() * (FLOOR_LAYER_START - DRAWING_LAYER_START) + DRAWING_LAYER_START)
         .unwrap_or(DRAWING_LAYER_START)
@@ -129,6 +202,7 @@ pub fn handle_loaded_drawing(
         &AssetSource,
         &Pose,
         &PixelsPerMeter,
+        Option<&Rectification>,
         &LoadingDrawing,
         Option<&LayerVisibility>,
         Option<&Parent>,
@@ -140,7 +214,7 @@ pub fn handle_loaded_drawing(
     segments: Query<&DrawingSegments>,
     default_drawing_vis: Query<&GlobalDrawingVisibility>,
 ) {
-    for (entity, source, pose, pixels_per_meter, handle, vis, parent, rank) in
+    for (entity, source, pose, pixels_per_meter, rectification, handle, vis, parent, rank) in
         loading_drawings.iter()
     {
         let Some(load_state) = asset_server.get_load_state(handle.id()) else {
@@ -150,14 +224,12 @@ pub fn handle_loaded_drawing(
         match load_state {
             LoadState::Loaded => {
                 let img = assets.get(&handle.0).unwrap();
-                let width = img.texture_descriptor.size.width as f32;
-                let height = img.texture_descriptor.size.height as f32;
-
-                // We set this up so that the origin of the drawing is in the top left corner
-                let mesh = make_flat_rect_mesh(width, height).transform_by(
-                    Affine3A::from_translation(Vec3::new(width / 2.0, -height / 2.0, 0.0)),
+                let size = Vec2::new(
+                    img.texture_descriptor.size.width as f32,
+                    img.texture_descriptor.size.height as f32,
                 );
-                let mesh = mesh_assets.add(mesh.into());
+                let mesh =
+                    mesh_assets.add(make_drawing_mesh(size, rectification, pixels_per_meter));
                 let default = parent
                     .map(|p| default_drawing_vis.get(p.get()).ok())
                     .flatten();
@@ -203,6 +275,7 @@ pub fn handle_loaded_drawing(
                     // Put a handle for the material into the main entity
                     // so that we can modify it during interactions.
                     .insert(material)
+                    .insert(DrawingImageSize(size))
                     .remove::<LoadingDrawing>();
             }
             LoadState::Failed => {
@@ -246,6 +319,67 @@ pub fn update_drawing_rank(
     }
 }
 
+/// Compare the image of each drawing that gets loaded against the source info
+/// that was recorded for it. Drawings that never had their source recorded
+/// get it recorded now.
+pub fn check_drawing_source_info(
+    mut commands: Commands,
+    drawings: Query<
+        (
+            Entity,
+            &NameInSite,
+            &AssetSource,
+            &DrawingImageSize,
+            Option<&DrawingSourceInfo>,
+        ),
+        Or<(Changed<DrawingImageSize>, Changed<DrawingSourceInfo>)>,
+    >,
+    current_workspace: Res<CurrentWorkspace>,
+    site_files: Query<&DefaultFile>,
+    mut change_source_info: EventWriter<Change<DrawingSourceInfo>>,
+) {
+    if drawings.is_empty() {
+        return;
+    }
+
+    let file_path = get_current_workspace_path(current_workspace, site_files).unwrap_or_default();
+    for (e, name, source, size, recorded) in &drawings {
+        // Only local files can be read back to compute their checksum
+        let checksum = match source {
+            AssetSource::Local(filename) => std::fs::read(file_path.with_file_name(filename))
+                .ok()
+                .map(|bytes| DrawingSourceInfo::checksum_of(&bytes)),
+            _ => None,
+        };
+        let observed = DrawingSourceInfo {
+            checksum,
+            size: Some([size.x as u32, size.y as u32]),
+            dpi: recorded.and_then(|r| r.dpi),
+        };
+
+        let Some(recorded) = recorded.filter(|r| !r.is_unrecorded()) else {
+            change_source_info.send(Change::new(observed, e).or_insert());
+            commands.entity(e).remove::<DrawingSourceMismatch>();
+            continue;
+        };
+
+        match recorded.mismatch(&observed) {
+            Some(reason) => {
+                warn!(
+                    "The image of drawing [{}] does not match the one it was calibrated \
+                    with: {reason}. Its scale and alignment may no longer be accurate, \
+                    so consider editing the drawing to calibrate it again.",
+                    name.0,
+                );
+                commands.entity(e).insert(DrawingSourceMismatch(observed));
+            }
+            None => {
+                commands.entity(e).remove::<DrawingSourceMismatch>();
+            }
+        }
+    }
+}
+
 pub fn update_drawing_pixels_per_meter(
     mut changed_drawings: Query<(&mut Transform, &PixelsPerMeter), Changed<PixelsPerMeter>>,
 ) {
@@ -254,6 +388,30 @@ pub fn update_drawing_pixels_per_meter(
     }
 }
 
+pub fn update_drawing_rectification(
+    changed_drawings: Query<
+        (
+            &DrawingSegments,
+            &DrawingImageSize,
+            &Rectification,
+            &PixelsPerMeter,
+        ),
+        Or<(Changed<Rectification>, Changed<PixelsPerMeter>)>,
+    >,
+    mut mesh_handles: Query<&mut Handle<Mesh>>,
+    mut mesh_assets: ResMut<Assets<Mesh>>,
+) {
+    for (segments, size, rectification, pixels_per_meter) in &changed_drawings {
+        if let Ok(mut handle) = mesh_handles.get_mut(segments.leaf) {
+            *handle = mesh_assets.add(make_drawing_mesh(
+                **size,
+                Some(rectification),
+                pixels_per_meter,
+            ));
+        }
+    }
+}
+
 pub fn update_drawing_children_to_pixel_coordinates(
     mut commands: Commands,
     changed_drawings: Query<
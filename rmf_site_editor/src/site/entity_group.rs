@@ -0,0 +1,100 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{interaction::Selectable, site::*, CurrentWorkspace};
+use bevy::prelude::*;
+
+/// Marks an element that is hidden because a group that it belongs to is
+/// hidden.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct HiddenByGroup;
+
+/// Marks a selectable mesh that cannot be selected because the element that
+/// it belongs to is in a locked group.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct LockedByGroup;
+
+/// Keep the visibility and selectability of the members of logical groups in
+/// sync with whether their groups are hidden or locked.
+#[derive(Default)]
+pub struct EntityGroupPlugin;
+
+impl Plugin for EntityGroupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_entity_group_flags);
+    }
+}
+
+fn update_entity_group_flags(
+    mut commands: Commands,
+    current_workspace: Res<CurrentWorkspace>,
+    entity_groups: Query<Ref<EntityGroups<Entity>>>,
+    new_selectables: Query<(), Added<Selectable>>,
+    mut visibilities: Query<&mut Visibility>,
+    hidden_by_group: Query<Entity, With<HiddenByGroup>>,
+    hidden_by_layer: Query<(), With<HiddenByLayer>>,
+    mut selectables: Query<(
+        Entity,
+        &mut Selectable,
+        Has<LockedByGroup>,
+        Has<LockedByLayer>,
+    )>,
+) {
+    let groups = current_workspace
+        .root
+        .and_then(|root| entity_groups.get(root).ok());
+    let groups_changed = groups.as_ref().is_some_and(|g| g.is_changed());
+    if !groups_changed && !current_workspace.is_changed() && new_selectables.is_empty() {
+        return;
+    }
+    let flags = groups.map(|g| g.flags()).unwrap_or_default();
+
+    for e in &hidden_by_group {
+        if flags.hidden.contains(&e) {
+            continue;
+        }
+        if !hidden_by_layer.contains(e) {
+            if let Ok(mut visibility) = visibilities.get_mut(e) {
+                *visibility = Visibility::Inherited;
+            }
+        }
+        commands.entity(e).remove::<HiddenByGroup>();
+    }
+    for e in &flags.hidden {
+        if hidden_by_group.contains(*e) {
+            continue;
+        }
+        let Ok(mut visibility) = visibilities.get_mut(*e) else {
+            continue;
+        };
+        *visibility = Visibility::Hidden;
+        commands.entity(*e).insert(HiddenByGroup);
+    }
+
+    for (e, mut selectable, locked, locked_by_layer) in &mut selectables {
+        let lock = flags.locked.contains(&selectable.element);
+        if lock && !locked {
+            selectable.is_selectable = false;
+            commands.entity(e).insert(LockedByGroup);
+        } else if !lock && locked {
+            if !locked_by_layer {
+                selectable.is_selectable = true;
+            }
+            commands.entity(e).remove::<LockedByGroup>();
+        }
+    }
+}
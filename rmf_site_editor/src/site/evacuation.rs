@@ -0,0 +1,114 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, AppState};
+use bevy::prelude::*;
+
+/// How high above the floor the evacuation layer is drawn
+const EVACUATION_LAYER_HEIGHT: f32 = 0.1;
+
+const EVACUATION_LAYER_COLOR: Color = Color::rgb(0.1, 0.8, 0.25);
+
+/// Add emergency exits, evacuation routes, and an overlay that shows them
+/// along with the assembly points of the current level.
+pub struct EvacuationPlugin;
+
+impl Plugin for EvacuationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EvacuationLayer>()
+            .add_plugins((
+                ChangePlugin::<EmergencyExit>::default(),
+                ChangePlugin::<EvacuationRoute>::default(),
+            ))
+            .add_systems(
+                Update,
+                draw_evacuation_layer.run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+/// Whether the evacuation layer is drawn over the current level.
+#[derive(Resource, Default)]
+pub struct EvacuationLayer {
+    pub visible: bool,
+}
+
+fn draw_evacuation_layer(
+    layer: Res<EvacuationLayer>,
+    current_level: Res<CurrentLevel>,
+    lanes: Query<(&Edge<Entity>, &EvacuationRoute, &ReverseLane), With<LaneMarker>>,
+    doors: Query<(&Edge<Entity>, &EmergencyExit, &Parent), With<DoorMarker>>,
+    locations: Query<(&Point<Entity>, &LocationTags)>,
+    anchors: Query<(&GlobalTransform, &Parent), With<Anchor>>,
+    mut gizmos: Gizmos,
+) {
+    if !layer.visible {
+        return;
+    }
+    let Some(level) = current_level.0 else {
+        return;
+    };
+    let position = |e: Entity| -> Option<Vec3> {
+        let (tf, parent) = anchors.get(e).ok()?;
+        if parent.get() != level {
+            return None;
+        }
+        Some(tf.translation() + Vec3::Z * EVACUATION_LAYER_HEIGHT)
+    };
+
+    for (edge, route, reverse) in &lanes {
+        if !route.0 {
+            continue;
+        }
+        let (Some(p0), Some(p1)) = (position(edge.start()), position(edge.end())) else {
+            continue;
+        };
+        gizmos.line(p0, p1, EVACUATION_LAYER_COLOR);
+        if matches!(reverse, ReverseLane::Disable) {
+            // Point out which way one-way routes must be walked
+            let mid = (p0 + p1) / 2.0;
+            let back = (p0 - p1).normalize_or_zero() * 0.3;
+            let side = back.cross(Vec3::Z) * 0.5;
+            gizmos.line(mid, mid + back + side, EVACUATION_LAYER_COLOR);
+            gizmos.line(mid, mid + back - side, EVACUATION_LAYER_COLOR);
+        }
+    }
+
+    for (edge, exit, parent) in &doors {
+        if !exit.0 || parent.get() != level {
+            continue;
+        }
+        let (Some(p0), Some(p1)) = (position(edge.left()), position(edge.right())) else {
+            continue;
+        };
+        let normal = (p1 - p0).cross(Vec3::Z).normalize_or_zero() * 0.1;
+        for offset in [-normal, Vec3::ZERO, normal] {
+            gizmos.line(p0 + offset, p1 + offset, EVACUATION_LAYER_COLOR);
+        }
+    }
+
+    for (point, tags) in &locations {
+        if !tags.iter().any(|t| t.is_assembly_point()) {
+            continue;
+        }
+        let Some(p) = position(point.0) else {
+            continue;
+        };
+        gizmos.circle(p, Vec3::Z, 0.5, EVACUATION_LAYER_COLOR);
+        gizmos.circle(p, Vec3::Z, 0.7, EVACUATION_LAYER_COLOR);
+    }
+}
@@ -0,0 +1,192 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, widgets::MainInspector, AppState};
+use bevy::{
+    ecs::system::{EntityCommands, SystemState},
+    prelude::*,
+};
+use bevy_egui::egui::Ui;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{collections::BTreeMap, fmt::Debug};
+
+/// Implement this for a component to turn it into a new kind of entity that
+/// can be placed on levels. Add a [`LevelExtensionPlugin`] for the component
+/// and its entities will be loaded from and saved into site files under
+/// [`LevelExtension::KEY`] without any changes to the save and load code.
+///
+/// Entities of the kind need to be children of a level. Spawning an entity
+/// with the component is all it takes to have it saved.
+pub trait LevelExtension:
+    Component + Clone + Debug + PartialEq + Serialize + DeserializeOwned
+{
+    /// Where the entities get saved in site files. This must be namespaced,
+    /// e.g. `my_company/wifi_access_point`.
+    const KEY: &'static str;
+    /// Name of the kind of entity that is displayed to users
+    const LABEL: &'static str;
+
+    /// Called for every entity of this kind when it is loaded or spawned,
+    /// e.g. to add a visual or make it selectable.
+    fn spawn(&self, entity: &mut EntityCommands) {
+        let _ = entity;
+    }
+
+    /// Show widgets in the inspector that edit the value. Return true if the
+    /// value was changed.
+    fn inspect(&mut self, ui: &mut Ui) -> bool {
+        ui.label(format!("{self:?}"));
+        false
+    }
+}
+
+/// Marks an entity whose kind was added by a [`LevelExtension`].
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct LevelExtensionMarker;
+
+type SaveExtensionFn = fn(&mut World, Entity) -> BTreeMap<u32, serde_json::Value>;
+
+/// Keeps track of how to save every kind of entity that was registered by a
+/// [`LevelExtensionPlugin`].
+#[derive(Resource, Default, Clone)]
+pub struct LevelExtensionRegistry {
+    savers: Vec<(&'static str, SaveExtensionFn)>,
+}
+
+impl LevelExtensionRegistry {
+    pub fn keys(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.savers.iter().map(|(key, _)| *key)
+    }
+}
+
+pub struct LevelExtensionPlugin<T: LevelExtension> {
+    _ignore: std::marker::PhantomData<T>,
+}
+
+impl<T: LevelExtension> Default for LevelExtensionPlugin<T> {
+    fn default() -> Self {
+        Self {
+            _ignore: Default::default(),
+        }
+    }
+}
+
+impl<T: LevelExtension> Plugin for LevelExtensionPlugin<T> {
+    fn build(&self, app: &mut App) {
+        assert!(
+            is_namespaced_extension_key(T::KEY),
+            "The key [{}] of a level extension needs a namespace, e.g. my_company/{}",
+            T::KEY,
+            T::KEY,
+        );
+        let mut registry = app
+            .world
+            .get_resource_or_insert_with(LevelExtensionRegistry::default);
+        assert!(
+            registry.keys().all(|key| key != T::KEY),
+            "The level extension key [{}] was registered twice",
+            T::KEY,
+        );
+        registry.savers.push((T::KEY, save_level_extension::<T>));
+
+        app.add_plugins(ChangePlugin::<T>::default()).add_systems(
+            Update,
+            (load_level_extension::<T>, spawn_level_extension::<T>)
+                .chain()
+                .run_if(AppState::in_displaying_mode()),
+        );
+
+        // Headless exports do not have an inspector
+        if app.world.contains_resource::<MainInspector>() {
+            app.add_plugins(crate::widgets::InspectionPlugin::<
+                crate::widgets::InspectLevelExtension<T>,
+            >::new());
+        }
+    }
+}
+
+/// Spawn the entities of this kind that were loaded with a level.
+fn load_level_extension<T: LevelExtension>(
+    mut commands: Commands,
+    mut levels: Query<(Entity, &mut Extensions), Changed<Extensions>>,
+) {
+    for (level, mut extensions) in &mut levels {
+        if !extensions.contains_key(T::KEY) {
+            continue;
+        }
+        for (id, value) in extensions.take::<T>(T::KEY) {
+            match value {
+                Ok(value) => {
+                    commands.spawn((value, SiteID(id))).set_parent(level);
+                }
+                Err(err) => error!(
+                    "Unable to load {} #{id}, it will be removed from the site: {err}",
+                    T::LABEL,
+                ),
+            }
+        }
+    }
+}
+
+fn spawn_level_extension<T: LevelExtension>(
+    mut commands: Commands,
+    new_entities: Query<(Entity, &T), Added<T>>,
+) {
+    for (e, value) in &new_entities {
+        let mut entity = commands.entity(e);
+        entity.insert((LevelExtensionMarker, Category::CustomEntity));
+        value.spawn(&mut entity);
+    }
+}
+
+fn save_level_extension<T: LevelExtension>(
+    world: &mut World,
+    level: Entity,
+) -> BTreeMap<u32, serde_json::Value> {
+    let mut state: SystemState<Query<(&T, &SiteID, &Parent), Without<Pending>>> =
+        SystemState::new(world);
+    let entities = state.get(world);
+    let mut saved = BTreeMap::new();
+    for (value, id, parent) in &entities {
+        if parent.get() != level {
+            continue;
+        }
+        match serde_json::to_value(value) {
+            Ok(value) => {
+                saved.insert(id.0, value);
+            }
+            Err(err) => error!("Unable to save {} #{}: {err}", T::LABEL, id.0),
+        }
+    }
+    saved
+}
+
+/// Data of extensions that no plugin was registered for is kept on each level
+/// so that it can be saved again unchanged.
+pub(crate) fn generate_level_extensions(world: &mut World, level: Entity) -> Extensions {
+    let mut extensions = world.get::<Extensions>(level).cloned().unwrap_or_default();
+    let Some(registry) = world.get_resource::<LevelExtensionRegistry>().cloned() else {
+        return extensions;
+    };
+    for (key, save) in registry.savers {
+        let saved = save(world, level);
+        if !saved.is_empty() {
+            extensions.0.insert(key.to_owned(), saved);
+        }
+    }
+    extensions
+}
@@ -17,7 +17,7 @@
 
 use crate::interaction::VisualCue;
 use crate::site::*;
-use crate::{Issue, ValidateWorkspace};
+use crate::{Issue, ValidationRequests};
 use bevy::{prelude::*, utils::Uuid};
 use std::collections::HashMap;
 
@@ -313,16 +313,22 @@ pub const FIDUCIAL_WITHOUT_AFFILIATION_ISSUE_UUID: Uuid =
 // generate an issue if that is the case
 pub fn check_for_fiducials_without_affiliation(
     mut commands: Commands,
-    mut validate_events: EventReader<ValidateWorkspace>,
+    mut validate_events: ValidationRequests,
     parents: Query<&Parent>,
     fiducial_affiliations: Query<(Entity, &Affiliation<Entity>), With<FiducialMarker>>,
+    levels: Query<(), With<LevelElevation>>,
 ) {
     const ISSUE_HINT: &str = "Fiducial affiliations are used by the site editor to map matching \
                             fiducials between different floors or drawings and calculate their \
                             relative transform, fiducials without affiliation are ignored";
-    for root in validate_events.read() {
+    for request in validate_events.read() {
+        let root = request.root();
         for (e, affiliation) in &fiducial_affiliations {
-            if AncestorIter::new(&parents, e).any(|p| p == **root) {
+            let level = AncestorIter::new(&parents, e).find(|p| levels.contains(*p));
+            if !request.affects(e, level) {
+                continue;
+            }
+            if AncestorIter::new(&parents, e).any(|p| p == root) {
                 if affiliation.0.is_none() {
                     let issue = Issue {
                         key: IssueKey {
@@ -333,7 +339,7 @@ pub fn check_for_fiducials_without_affiliation(
                         hint: ISSUE_HINT.to_string(),
                     };
                     let id = commands.spawn(issue).id();
-                    commands.entity(**root).add_child(id);
+                    commands.entity(root).add_child(id);
                 }
             }
         }
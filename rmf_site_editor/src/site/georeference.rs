@@ -2,16 +2,20 @@ use bevy::{asset::AssetPath, prelude::*, window::PrimaryWindow};
 use bevy_egui::{egui, EguiContexts};
 use bevy_mod_raycast::primitives::rays::Ray3d;
 use camera_controls::{CameraControls, ProjectionMode};
-use rmf_site_format::{GeographicComponent, GeographicOffset};
-use std::collections::HashSet;
+use rmf_site_format::{
+    geojson_points, Anchor, GeographicComponent, GeographicOffset, GeographicReferences,
+    LevelElevation, NameInSite, Similarity2,
+};
+use std::{collections::HashSet, path::PathBuf};
 use utm::*;
 
 use crate::{
     generate_map_tiles,
     interaction::camera_controls,
+    site::AnchorBundle,
     widgets::menu_bar::{Menu, MenuDisabled, MenuEvent, MenuItem, ToolMenu, ViewMenu},
     workspace::CurrentWorkspace,
-    OSMTile,
+    AppState, OSMTile,
 };
 
 const MAX_ZOOM: i32 = 19;
@@ -361,6 +365,209 @@ pub fn latlon_to_world(lat: f32, lon: f32, anchor: (f32, f32)) -> Vec3 {
     )
 }
 
+/// Converts site coordinates into WGS84 coordinates. The conversion is fit to
+/// the geographic reference points of the site when there are any, otherwise
+/// the geographic offset of the site is used as the origin.
+pub struct SiteProjection {
+    zone: u8,
+    zone_letter: char,
+    /// Maps site coordinates into UTM easting and northing
+    to_utm: Similarity2,
+}
+
+impl SiteProjection {
+    pub fn new(
+        offset: Option<&GeographicOffset>,
+        references: &GeographicReferences,
+    ) -> Option<Self> {
+        let (lat, lon) = match references.0.first() {
+            Some(reference) => (reference.latitude, reference.longitude),
+            None => {
+                let anchor = offset?.anchor;
+                (anchor.0 as f64, anchor.1 as f64)
+            }
+        };
+        let zone = lat_lon_to_zone_number(lat, lon);
+        let zone_letter = lat_to_zone_letter(lat)?;
+        let to_utm_pair = |lat: f64, lon: f64| {
+            let (northing, easting, _) = to_utm_wgs84(lat, lon, zone);
+            [easting, northing]
+        };
+
+        let to_utm = match references.0.as_slice() {
+            [] => Similarity2::from_translation(to_utm_pair(lat, lon)),
+            [reference] => {
+                let [e, n] = to_utm_pair(reference.latitude, reference.longitude);
+                let [x, y] = reference.position;
+                Similarity2::from_translation([e - x as f64, n - y as f64])
+            }
+            references => {
+                let pairs: Vec<_> = references
+                    .iter()
+                    .map(|r| {
+                        let [x, y] = r.position;
+                        ([x as f64, y as f64], to_utm_pair(r.latitude, r.longitude))
+                    })
+                    .collect();
+                Similarity2::fit(&pairs)?
+            }
+        };
+
+        Some(Self {
+            zone,
+            zone_letter,
+            to_utm,
+        })
+    }
+
+    /// Get the `[longitude, latitude]` of a point in site coordinates.
+    pub fn lon_lat(&self, p: [f32; 2]) -> Option<[f64; 2]> {
+        let [easting, northing] = self.to_utm.apply([p[0] as f64, p[1] as f64]);
+        let (lat, lon) =
+            wsg84_utm_to_lat_lon(easting, northing, self.zone, self.zone_letter).ok()?;
+        Some([lon, lat])
+    }
+
+    /// Get the site coordinates of a WGS84 latitude and longitude. Points far
+    /// outside of the UTM zone of the site lose accuracy.
+    pub fn site_position(&self, latitude: f64, longitude: f64) -> Option<[f32; 2]> {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return None;
+        }
+        let (northing, easting, _) = to_utm_wgs84(latitude, longitude, self.zone);
+        let [x, y] = self.to_utm.inverse()?.apply([easting, northing]);
+        Some([x as f32, y as f32])
+    }
+}
+
+/// Get the projection of a site from its geographic offset and reference
+/// points, if it has either of them.
+pub fn site_projection(
+    site: Entity,
+    geography: &Query<(&GeographicComponent, Option<&GeographicReferences>)>,
+) -> Option<SiteProjection> {
+    let (offset, references) = geography.get(site).ok()?;
+    SiteProjection::new(
+        offset.0.as_ref(),
+        references.unwrap_or(&GeographicReferences::default()),
+    )
+}
+
+/// Add a vertex to a level at a WGS84 latitude and longitude.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct AddGeographicVertex {
+    pub site: Entity,
+    pub level: Entity,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Add a vertex for every point of a GeoJSON file. Points whose `level`
+/// property names a level of the site are placed on that level, and every
+/// other point is placed on `level`.
+#[derive(Event, Clone, Debug)]
+pub struct ImportGeoJsonVertices {
+    pub site: Entity,
+    pub level: Entity,
+    pub from_file: PathBuf,
+}
+
+#[derive(Default)]
+pub struct GeographicVerticesPlugin;
+
+impl Plugin for GeographicVerticesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AddGeographicVertex>()
+            .add_event::<ImportGeoJsonVertices>()
+            .add_systems(
+                Update,
+                (add_geographic_vertices, import_geojson_vertices)
+                    .run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+fn add_geographic_vertices(
+    mut commands: Commands,
+    mut requests: EventReader<AddGeographicVertex>,
+    geography: Query<(&GeographicComponent, Option<&GeographicReferences>)>,
+) {
+    for request in requests.read() {
+        let Some(projection) = site_projection(request.site, &geography) else {
+            warn!(
+                "Unable to add a vertex by latitude and longitude: the site is not georeferenced"
+            );
+            continue;
+        };
+        let Some(position) = projection.site_position(request.latitude, request.longitude) else {
+            warn!(
+                "Unable to place a vertex at {}°, {}°",
+                request.latitude, request.longitude
+            );
+            continue;
+        };
+        commands
+            .spawn(AnchorBundle::new(Anchor::Translate2D(position)))
+            .set_parent(request.level);
+    }
+}
+
+fn import_geojson_vertices(
+    mut commands: Commands,
+    mut requests: EventReader<ImportGeoJsonVertices>,
+    geography: Query<(&GeographicComponent, Option<&GeographicReferences>)>,
+    levels: Query<(Entity, &NameInSite, &Parent), With<LevelElevation>>,
+) {
+    for request in requests.read() {
+        let Some(projection) = site_projection(request.site, &geography) else {
+            warn!("Unable to import vertices: the site is not georeferenced");
+            continue;
+        };
+        let value: serde_json::Value = match std::fs::read(&request.from_file)
+            .map_err(|err| err.to_string())
+            .and_then(|data| serde_json::from_slice(&data).map_err(|err| err.to_string()))
+        {
+            Ok(value) => value,
+            Err(err) => {
+                error!(
+                    "Unable to read GeoJSON from {}: {err}",
+                    request.from_file.display()
+                );
+                continue;
+            }
+        };
+
+        let level_named = |name: &str| {
+            levels
+                .iter()
+                .find(|(_, level_name, parent)| {
+                    parent.get() == request.site && level_name.0 == name
+                })
+                .map(|(e, ..)| e)
+        };
+        let mut imported = 0;
+        for point in geojson_points(&value) {
+            let [lon, lat] = point.lon_lat;
+            let Some(position) = projection.site_position(lat, lon) else {
+                continue;
+            };
+            let level = point
+                .level
+                .as_deref()
+                .and_then(level_named)
+                .unwrap_or(request.level);
+            commands
+                .spawn(AnchorBundle::new(Anchor::Translate2D(position)))
+                .set_parent(level);
+            imported += 1;
+        }
+        info!(
+            "Imported {imported} vertices from {}",
+            request.from_file.display()
+        );
+    }
+}
+
 #[derive(Default)]
 pub struct RenderSettings {
     prev_anchor: (f32, f32),
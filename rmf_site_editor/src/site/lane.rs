@@ -16,9 +16,9 @@
 */
 
 use crate::site::*;
-use crate::{CurrentWorkspace, Issue, ValidateWorkspace};
+use crate::{CurrentWorkspace, Issue, ValidationRequests};
 use bevy::{prelude::*, utils::Uuid};
-use rmf_site_format::{Edge, LaneMarker};
+use rmf_site_format::{Edge, HumanLaneMarker, LaneMarker};
 use std::collections::{BTreeSet, HashMap};
 
 pub const SELECTED_LANE_OFFSET: f32 = 0.001;
@@ -65,6 +65,22 @@ fn should_display_lane(
     graphs.should_display(associated)
 }
 
+/// Human lanes keep the visibility of their graphs but always use their own
+/// material so they stand out from robot lanes.
+fn lane_display_style(
+    associated: &AssociatedGraphs<Entity>,
+    is_human: bool,
+    graphs: &GraphSelect,
+    assets: &SiteAssets,
+) -> (Handle<StandardMaterial>, f32) {
+    let (material, height) = graphs.display_style(associated);
+    if is_human {
+        (assets.human_lane_material.clone(), height)
+    } else {
+        (material, height)
+    }
+}
+
 pub fn assign_orphan_nav_elements_to_site(
     mut commands: Commands,
     elements: Query<
@@ -86,7 +102,15 @@ pub fn assign_orphan_nav_elements_to_site(
 
 pub fn add_lane_visuals(
     mut commands: Commands,
-    lanes: Query<(Entity, &Edge<Entity>, &AssociatedGraphs<Entity>), Added<LaneMarker>>,
+    lanes: Query<
+        (
+            Entity,
+            &Edge<Entity>,
+            &AssociatedGraphs<Entity>,
+            Has<HumanLaneMarker>,
+        ),
+        Added<LaneMarker>,
+    >,
     graphs: GraphSelect,
     anchors: AnchorParams,
     parents: Query<&Parent>,
@@ -95,14 +119,15 @@ pub fn add_lane_visuals(
     assets: Res<SiteAssets>,
     current_level: Res<CurrentLevel>,
 ) {
-    for (e, edge, associated_graphs) in &lanes {
+    for (e, edge, associated_graphs, is_human) in &lanes {
         for anchor in &edge.array() {
             if let Ok(mut deps) = dependents.get_mut(*anchor) {
                 deps.insert(e);
             }
         }
 
-        let (lane_material, height) = graphs.display_style(associated_graphs);
+        let (lane_material, height) =
+            lane_display_style(associated_graphs, is_human, &graphs, &assets);
         let visibility = if should_display_lane(
             edge,
             associated_graphs,
@@ -163,9 +188,14 @@ pub fn add_lane_visuals(
             assets.lane_end_outline.clone(),
         );
 
+        let mid_mesh = if is_human {
+            assets.human_lane_mid_mesh.clone()
+        } else {
+            assets.lane_mid_mesh.clone()
+        };
         let (mid, mid_outline) = spawn_lane_mesh_and_outline(
             line_stroke_transform(&start_anchor, &end_anchor, LANE_WIDTH),
-            assets.lane_mid_mesh.clone(),
+            mid_mesh,
             assets.lane_mid_outline.clone(),
         );
 
@@ -300,6 +330,7 @@ pub fn update_visibility_for_lanes(
             &AssociatedGraphs<Entity>,
             &LaneSegments,
             &mut Visibility,
+            Has<HumanLaneMarker>,
         ),
         (With<LaneMarker>, Without<NavGraphMarker>),
     >,
@@ -308,7 +339,12 @@ pub fn update_visibility_for_lanes(
     current_level: Res<CurrentLevel>,
     graphs: GraphSelect,
     lanes_with_changed_association: Query<
-        (Entity, &AssociatedGraphs<Entity>, &LaneSegments),
+        (
+            Entity,
+            &AssociatedGraphs<Entity>,
+            &LaneSegments,
+            Has<HumanLaneMarker>,
+        ),
         (With<LaneMarker>, Changed<AssociatedGraphs<Entity>>),
     >,
     mut materials: Query<&mut Handle<StandardMaterial>, Without<NavGraphMarker>>,
@@ -321,11 +357,12 @@ pub fn update_visibility_for_lanes(
         ),
     >,
     mut removed: RemovedComponents<NavGraphMarker>,
+    assets: Res<SiteAssets>,
 ) {
     let graph_change = !graph_changed_visibility.is_empty() || removed.read().next().is_some();
     let update_all = current_level.is_changed() || graph_change;
     if update_all {
-        for (edge, associated, _, mut visibility) in &mut lanes {
+        for (edge, associated, _, mut visibility, _) in &mut lanes {
             let new_visibility = if should_display_lane(
                 edge,
                 associated,
@@ -343,8 +380,8 @@ pub fn update_visibility_for_lanes(
             }
         }
     } else {
-        for (e, _, _) in &lanes_with_changed_association {
-            if let Ok((edge, associated, _, mut visibility)) = lanes.get_mut(e) {
+        for (e, _, _, _) in &lanes_with_changed_association {
+            if let Ok((edge, associated, _, mut visibility, _)) = lanes.get_mut(e) {
                 let new_visibility = if should_display_lane(
                     edge,
                     associated,
@@ -365,8 +402,8 @@ pub fn update_visibility_for_lanes(
     }
 
     if graph_change {
-        for (_, associated_graphs, segments, _) in &lanes {
-            let (mat, height) = graphs.display_style(associated_graphs);
+        for (_, associated_graphs, segments, _, is_human) in &lanes {
+            let (mat, height) = lane_display_style(associated_graphs, is_human, &graphs, &assets);
             for e in segments.iter() {
                 if let Ok(mut m) = materials.get_mut(e) {
                     *m = mat.clone();
@@ -378,8 +415,8 @@ pub fn update_visibility_for_lanes(
             }
         }
     } else {
-        for (_, associated_graphs, segments) in &lanes_with_changed_association {
-            let (mat, height) = graphs.display_style(associated_graphs);
+        for (_, associated_graphs, segments, is_human) in &lanes_with_changed_association {
+            let (mat, height) = lane_display_style(associated_graphs, is_human, &graphs, &assets);
             for e in segments.iter() {
                 if let Ok(mut m) = materials.get_mut(e) {
                     *m = mat.clone();
@@ -393,6 +430,92 @@ pub fn update_visibility_for_lanes(
     }
 }
 
+/// Convert lanes into human lanes, or back into robot lanes.
+#[derive(Debug, Clone, Event)]
+pub struct SetHumanLanes {
+    pub lanes: Vec<Entity>,
+    pub human: bool,
+}
+
+pub fn handle_set_human_lanes(
+    mut commands: Commands,
+    mut requests: EventReader<SetHumanLanes>,
+    lanes: Query<Has<HumanLaneMarker>, With<LaneMarker>>,
+) {
+    for request in requests.read() {
+        for lane in &request.lanes {
+            let Ok(is_human) = lanes.get(*lane) else {
+                continue;
+            };
+            if is_human == request.human {
+                continue;
+            }
+            if request.human {
+                commands.entity(*lane).insert(HumanLaneMarker);
+            } else {
+                commands.entity(*lane).remove::<HumanLaneMarker>();
+            }
+        }
+    }
+}
+
+/// Flip the direction of lanes. The motions of lanes that have a different
+/// motion in reverse are swapped along with their anchors, so every motion
+/// keeps applying to the same physical direction of travel.
+#[derive(Debug, Clone, Event)]
+pub struct ReverseLanes {
+    pub lanes: Vec<Entity>,
+}
+
+pub fn handle_reverse_lanes(
+    mut requests: EventReader<ReverseLanes>,
+    mut lanes: Query<(&mut Edge<Entity>, &mut Motion, &mut ReverseLane), With<LaneMarker>>,
+) {
+    for request in requests.read() {
+        for lane in &request.lanes {
+            let Ok((mut edge, mut forward, mut reverse)) = lanes.get_mut(*lane) else {
+                continue;
+            };
+            *edge = Edge::new(edge.end(), edge.start());
+            if let ReverseLane::Different(backward) = reverse.as_mut() {
+                std::mem::swap(forward.as_mut(), backward);
+            }
+        }
+    }
+}
+
+pub fn update_human_lane_visuals(
+    lanes: Query<(
+        &AssociatedGraphs<Entity>,
+        &LaneSegments,
+        Has<HumanLaneMarker>,
+    )>,
+    added: Query<Entity, (With<LaneSegments>, Added<HumanLaneMarker>)>,
+    mut removed: RemovedComponents<HumanLaneMarker>,
+    mut pbr: Query<(&mut Handle<Mesh>, &mut Handle<StandardMaterial>)>,
+    graphs: GraphSelect,
+    assets: Res<SiteAssets>,
+) {
+    for e in added.iter().chain(removed.read()) {
+        let Ok((associated, segments, is_human)) = lanes.get(e) else {
+            continue;
+        };
+        let (material, _) = lane_display_style(associated, is_human, &graphs, &assets);
+        for segment in segments.iter() {
+            if let Ok((_, mut m)) = pbr.get_mut(segment) {
+                *m = material.clone();
+            }
+        }
+        if let Ok((mut mesh, _)) = pbr.get_mut(segments.mid) {
+            *mesh = if is_human {
+                assets.human_lane_mid_mesh.clone()
+            } else {
+                assets.lane_mid_mesh.clone()
+            };
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Event)]
 pub struct ConsiderAssociatedGraph {
     pub graph: Option<Entity>,
@@ -424,7 +547,7 @@ pub const DUPLICATED_DOCK_NAME_ISSUE_UUID: Uuid =
 // generate an issue if that is the case
 pub fn check_for_duplicated_dock_names(
     mut commands: Commands,
-    mut validate_events: EventReader<ValidateWorkspace>,
+    mut validate_events: ValidationRequests,
     parents: Query<&Parent>,
     lane_properties: Query<(Entity, &Motion, Option<&ReverseLane>), With<LaneMarker>>,
 ) {
@@ -432,10 +555,10 @@ pub fn check_for_duplicated_dock_names(
                         the robots. Duplicated dock names would make such behavior ambiguous as \
                         it would be triggered in different parts of the map, rename the docks to \
                         be unique";
-    for root in validate_events.read() {
+    for root in validate_events.roots() {
         let mut names: HashMap<String, BTreeSet<Entity>> = HashMap::new();
         for (e, motion, reverse) in &lane_properties {
-            if AncestorIter::new(&parents, e).any(|p| p == **root) {
+            if AncestorIter::new(&parents, e).any(|p| p == root) {
                 if let Some(dock) = &motion.dock {
                     let entities_with_name = names.entry(dock.name.clone()).or_default();
                     entities_with_name.insert(e);
@@ -459,7 +582,7 @@ pub fn check_for_duplicated_dock_names(
                                 hint: ISSUE_HINT.to_string(),
                             };
                             let id = commands.spawn(issue).id();
-                            commands.entity(**root).add_child(id);
+                            commands.entity(root).add_child(id);
                         }
                     }
                 }
@@ -476,7 +599,7 @@ pub fn check_for_duplicated_dock_names(
                     hint: ISSUE_HINT.to_string(),
                 };
                 let id = commands.spawn(issue).id();
-                commands.entity(**root).add_child(id);
+                commands.entity(root).add_child(id);
             }
         }
     }
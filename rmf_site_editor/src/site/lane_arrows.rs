@@ -0,0 +1,244 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{interaction::CameraControls, site::*, AppState};
+use bevy::prelude::*;
+use rmf_site_format::{Edge, LaneMarker};
+
+/// Arrowheads are never drawn smaller than this, no matter how far the camera
+/// is zoomed in.
+const MIN_ARROW_SIZE: f32 = 0.5 * LANE_WIDTH;
+
+/// Height of the arrowheads above the lane mesh, to avoid z-fighting.
+const ARROW_HEIGHT: f32 = 0.000_5;
+
+/// How the travel direction of lanes gets drawn. Arrowheads keep roughly the
+/// same size on screen while the camera zooms, so the direction of a lane
+/// stays readable when the whole site is in view.
+#[derive(Resource, Debug, Clone)]
+pub struct LaneArrowDisplay {
+    pub show: bool,
+    /// Repeat chevrons along the whole length of one-way lanes instead of
+    /// drawing a single arrowhead at their middle.
+    pub one_way_chevrons: bool,
+    /// Size of an arrowhead on screen, in pixels
+    pub size: f32,
+    /// Distance between repeated chevrons on screen, in pixels
+    pub spacing: f32,
+}
+
+impl Default for LaneArrowDisplay {
+    fn default() -> Self {
+        Self {
+            show: true,
+            one_way_chevrons: false,
+            size: 16.0,
+            spacing: 80.0,
+        }
+    }
+}
+
+/// The arrowhead entities that are currently drawn on a lane.
+#[derive(Component, Debug, Clone, Default)]
+pub struct LaneArrows {
+    pub arrows: Vec<Entity>,
+}
+
+#[derive(Default)]
+pub struct LaneArrowsPlugin;
+
+impl Plugin for LaneArrowsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LaneArrowDisplay>().add_systems(
+            Update,
+            (add_lane_arrows, update_lane_arrows)
+                .chain()
+                .run_if(AppState::in_displaying_mode()),
+        );
+    }
+}
+
+fn add_lane_arrows(
+    mut commands: Commands,
+    new_lanes: Query<Entity, (With<LaneMarker>, Added<LaneSegments>)>,
+) {
+    for e in &new_lanes {
+        commands.entity(e).insert(LaneArrows::default());
+    }
+}
+
+fn update_lane_arrows(
+    mut commands: Commands,
+    display: Res<LaneArrowDisplay>,
+    mut lanes: Query<
+        (
+            Entity,
+            &Edge<Entity>,
+            &LaneSegments,
+            Option<&ReverseLane>,
+            Option<&LaneCurve>,
+            &mut LaneArrows,
+        ),
+        With<LaneMarker>,
+    >,
+    anchors: AnchorParams,
+    global_tfs: Query<&GlobalTransform>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    camera_controls: Res<CameraControls>,
+    mut transforms: Query<&mut Transform>,
+    assets: Res<SiteAssets>,
+) {
+    let camera = cameras.get(camera_controls.active_camera()).ok();
+    for (e, edge, segments, reverse, curve, mut arrows) in &mut lanes {
+        let placements = if display.show {
+            let (Ok(start), Ok(end)) = (
+                anchors.point_in_parent_frame_of(edge.start(), Category::Lane, e),
+                anchors.point_in_parent_frame_of(edge.end(), Category::Lane, e),
+            ) else {
+                continue;
+            };
+            let meters_per_pixel = camera
+                .zip(global_tfs.get(segments.mid).ok())
+                .and_then(|((camera, camera_tf), mid_tf)| {
+                    meters_per_pixel(camera, camera_tf, mid_tf.translation())
+                })
+                .unwrap_or(0.0);
+            let one_way = matches!(reverse, Some(ReverseLane::Disable));
+            match curve.filter(|c| !c.is_straight()) {
+                Some(curve) => {
+                    curved_arrow_placements(curve, start, end, one_way, meters_per_pixel, &display)
+                }
+                None => arrow_placements(start, end, one_way, meters_per_pixel, &display),
+            }
+        } else {
+            Vec::new()
+        };
+
+        while arrows.arrows.len() > placements.len() {
+            if let Some(arrow) = arrows.arrows.pop() {
+                commands.entity(arrow).despawn_recursive();
+            }
+        }
+
+        for (i, placement) in placements.into_iter().enumerate() {
+            if let Some(arrow) = arrows.arrows.get(i) {
+                if let Ok(mut tf) = transforms.get_mut(*arrow) {
+                    tf.set_if_neq(placement);
+                }
+            } else {
+                let arrow = commands
+                    .spawn(PbrBundle {
+                        mesh: assets.lane_arrow_mesh.clone(),
+                        material: assets.lane_arrow_material.clone(),
+                        transform: placement,
+                        ..default()
+                    })
+                    .set_parent(segments.layer)
+                    .id();
+                arrows.arrows.push(arrow);
+            }
+        }
+    }
+}
+
+/// How many meters one pixel covers at a point of the scene, measured
+/// sideways from the point of view of the camera.
+fn meters_per_pixel(camera: &Camera, camera_tf: &GlobalTransform, p: Vec3) -> Option<f32> {
+    let p0 = camera.world_to_viewport(camera_tf, p)?;
+    let p1 = camera.world_to_viewport(camera_tf, p + camera_tf.right())?;
+    let pixels_per_meter = p0.distance(p1);
+    (pixels_per_meter > f32::EPSILON).then(|| 1.0 / pixels_per_meter)
+}
+
+/// Where the arrowheads of a lane should be drawn, in the frame of the lane's
+/// layer. One-way lanes get arrowheads that point from the start to the end,
+/// while two-way lanes get a pair of arrowheads that point away from each
+/// other.
+fn arrow_placements(
+    start: Vec3,
+    end: Vec3,
+    one_way: bool,
+    meters_per_pixel: f32,
+    display: &LaneArrowDisplay,
+) -> Vec<Transform> {
+    let length = (end - start).truncate().length();
+    if length < MIN_ARROW_SIZE {
+        return Vec::new();
+    }
+
+    let dir = (end - start).truncate() / length;
+    let yaw = dir.y.atan2(dir.x);
+    let size = (display.size * meters_per_pixel)
+        .max(MIN_ARROW_SIZE)
+        .min(0.5 * length);
+    let arrow = |s: f32, yaw: f32| Transform {
+        translation: start.lerp(end, s / length) + ARROW_HEIGHT * Vec3::Z,
+        rotation: Quat::from_rotation_z(yaw),
+        scale: Vec3::splat(size),
+    };
+
+    if !one_way {
+        let offset = 0.35 * size;
+        return vec![
+            arrow(0.5 * length + offset, yaw),
+            arrow(0.5 * length - offset, yaw + std::f32::consts::PI),
+        ];
+    }
+
+    if !display.one_way_chevrons {
+        return vec![arrow(0.5 * length, yaw)];
+    }
+
+    let spacing = (display.spacing * meters_per_pixel).max(2.0 * size);
+    let count = ((length / spacing).floor() as usize).max(1);
+    let step = length / count as f32;
+    (0..count)
+        .map(|i| arrow((i as f32 + 0.5) * step, yaw))
+        .collect()
+}
+
+/// Arrowheads of a curved lane are laid out as if the curve was straightened
+/// out, and then bent onto the curve so they follow its direction.
+fn curved_arrow_placements(
+    curve: &LaneCurve,
+    start: Vec3,
+    end: Vec3,
+    one_way: bool,
+    meters_per_pixel: f32,
+    display: &LaneArrowDisplay,
+) -> Vec<Transform> {
+    let (p0, p1) = (start.truncate(), end.truncate());
+    let chord = (p1 - p0).normalize_or_zero();
+    if chord == Vec2::ZERO {
+        return Vec::new();
+    }
+    let chord_yaw = chord.y.atan2(chord.x);
+    let length = curve.length(p0, p1);
+    let straightened_end = (p0 + length * chord).extend(end.z);
+    arrow_placements(start, straightened_end, one_way, meters_per_pixel, display)
+        .into_iter()
+        .map(|mut tf| {
+            let t = (tf.translation.truncate() - p0).dot(chord) / length;
+            let tangent = curve.tangent(p0, p1, t);
+            let yaw =
+                tf.rotation.to_euler(EulerRot::ZYX).0 - chord_yaw + tangent.y.atan2(tangent.x);
+            tf.translation = curve.point(p0, p1, t).extend(tf.translation.z);
+            tf.rotation = Quat::from_rotation_z(yaw);
+            tf
+        })
+        .collect()
+}
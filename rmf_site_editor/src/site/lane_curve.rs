@@ -0,0 +1,148 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{interaction::Selectable, shapes::make_flat_strip_mesh, site::*};
+use bevy::prelude::*;
+
+/// How many straight pieces are used to draw a curved lane.
+const CURVE_DRAW_SEGMENTS: usize = 32;
+
+/// The mesh that draws a curved lane in place of its straight middle
+/// segment.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LaneCurveMesh(pub Entity);
+
+#[derive(Default)]
+pub struct LaneCurvePlugin;
+
+impl Plugin for LaneCurvePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ChangePlugin::<LaneCurve>::default())
+            .add_systems(
+                Update,
+                (update_lane_curve_visuals, update_lane_curve_materials)
+                    .chain()
+                    .run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+fn update_lane_curve_visuals(
+    mut commands: Commands,
+    lanes: Query<
+        (
+            Entity,
+            &Edge<Entity>,
+            &LaneCurve,
+            &LaneSegments,
+            Option<&LaneCurveMesh>,
+        ),
+        With<LaneMarker>,
+    >,
+    changed_lanes: Query<
+        Entity,
+        (
+            With<LaneMarker>,
+            Or<(
+                Changed<LaneCurve>,
+                Changed<Edge<Entity>>,
+                Added<LaneSegments>,
+            )>,
+        ),
+    >,
+    changed_anchors: Query<
+        &Dependents,
+        (
+            With<Anchor>,
+            Or<(Changed<Anchor>, Changed<GlobalTransform>)>,
+        ),
+    >,
+    anchors: AnchorParams,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mesh_handles: Query<&mut Handle<Mesh>>,
+    mut visibilities: Query<&mut Visibility>,
+) {
+    let changed = changed_lanes
+        .iter()
+        .chain(changed_anchors.iter().flat_map(|deps| deps.iter().copied()));
+    for e in changed {
+        let Ok((e, edge, curve, segments, curve_mesh)) = lanes.get(e) else {
+            continue;
+        };
+
+        if curve.is_straight() {
+            if let Some(LaneCurveMesh(curve_mesh)) = curve_mesh {
+                commands.entity(*curve_mesh).despawn_recursive();
+                commands.entity(e).remove::<LaneCurveMesh>();
+            }
+            if let Ok(mut visibility) = visibilities.get_mut(segments.mid) {
+                visibility.set_if_neq(Visibility::Inherited);
+            }
+            continue;
+        }
+
+        let (Ok(p0), Ok(p1)) = (
+            anchors.point_in_parent_frame_of(edge.start(), Category::Lane, e),
+            anchors.point_in_parent_frame_of(edge.end(), Category::Lane, e),
+        ) else {
+            continue;
+        };
+        let points: Vec<Vec3> = curve
+            .polyline(p0.truncate(), p1.truncate(), CURVE_DRAW_SEGMENTS)
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| p.extend(p0.z.lerp(p1.z, i as f32 / CURVE_DRAW_SEGMENTS as f32)))
+            .collect();
+        let mesh = meshes.add(make_flat_strip_mesh(&points, LANE_WIDTH).into());
+
+        match curve_mesh.and_then(|m| mesh_handles.get_mut(m.0).ok()) {
+            Some(mut handle) => {
+                *handle = mesh;
+            }
+            None => {
+                let curve_mesh = commands
+                    .spawn(PbrBundle { mesh, ..default() })
+                    .insert(Selectable::new(e))
+                    .set_parent(segments.layer)
+                    .id();
+                commands.entity(e).insert(LaneCurveMesh(curve_mesh));
+            }
+        }
+        // The straight middle segment would cut across the curve
+        if let Ok(mut visibility) = visibilities.get_mut(segments.mid) {
+            visibility.set_if_neq(Visibility::Hidden);
+        }
+    }
+}
+
+/// Curves are drawn with the same material as the middle segment of the
+/// lane so they follow its highlighting and graph colors.
+fn update_lane_curve_materials(
+    lanes: Query<(&LaneSegments, &LaneCurveMesh)>,
+    mut materials: Query<&mut Handle<StandardMaterial>>,
+) {
+    for (segments, LaneCurveMesh(curve_mesh)) in &lanes {
+        let Ok(material) = materials.get(segments.mid).cloned() else {
+            continue;
+        };
+        if let Ok(mut curve_material) = materials.get_mut(*curve_mesh) {
+            if *curve_material != material {
+                *curve_material = material;
+            }
+        }
+    }
+}
@@ -0,0 +1,324 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, AppState};
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Send this event to split lanes that are longer than `spacing` into a chain
+/// of evenly spaced lanes. Finer lanes give the traffic schedule more
+/// granularity along long corridors.
+#[derive(Event, Clone, Debug)]
+pub struct DensifyLanes {
+    pub lanes: Vec<Entity>,
+    /// The longest that any lane of a chain may be, in meters
+    pub spacing: f32,
+}
+
+/// Send this event to undo [`DensifyLanes`]. Chains of lanes that travel in
+/// the same direction with the same properties are merged into one lane
+/// wherever the anchors between them are within `tolerance` meters of a
+/// straight line and nothing else depends on those anchors.
+#[derive(Event, Clone, Debug)]
+pub struct SparsifyLanes {
+    pub lanes: Vec<Entity>,
+    pub tolerance: f32,
+}
+
+#[derive(Default)]
+pub struct LaneDensityPlugin;
+
+impl Plugin for LaneDensityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DensifyLanes>()
+            .add_event::<SparsifyLanes>()
+            .add_systems(
+                Update,
+                (densify_lanes, sparsify_lanes).run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+pub(crate) type LaneProperties<'a> = (
+    &'a Edge<Entity>,
+    &'a Motion,
+    &'a ReverseLane,
+    &'a AssociatedGraphs<Entity>,
+    &'a Parent,
+    Option<&'a EvacuationRoute>,
+    Option<&'a LaneCurve>,
+);
+
+/// The motion of a segment of a chain. Only the segment at the end of the
+/// motion keeps the dock.
+pub(crate) fn segment_motion(motion: &Motion, keep_dock: bool) -> Motion {
+    let mut motion = motion.clone();
+    if !keep_dock {
+        motion.dock = None;
+    }
+    motion
+}
+
+pub(crate) fn segment_reverse(reverse: &ReverseLane, keep_dock: bool) -> ReverseLane {
+    match reverse {
+        ReverseLane::Different(motion) => ReverseLane::Different(segment_motion(motion, keep_dock)),
+        other => other.clone(),
+    }
+}
+
+/// Get the positions of the anchors of a lane if both of them are on the
+/// same level, along with that level.
+pub(crate) fn lane_endpoints(
+    edge: &Edge<Entity>,
+    anchors: &Query<(&Anchor, &Parent)>,
+    levels: &Query<(), With<LevelElevation>>,
+) -> Option<(Entity, Vec2, Vec2)> {
+    let (start, start_parent) = anchors.get(edge.start()).ok()?;
+    let (end, end_parent) = anchors.get(edge.end()).ok()?;
+    let level = start_parent.get();
+    if level != end_parent.get() || !levels.contains(level) {
+        return None;
+    }
+    Some((
+        level,
+        Vec2::from(start.translation_for_category(Category::Lane)),
+        Vec2::from(end.translation_for_category(Category::Lane)),
+    ))
+}
+
+fn densify_lanes(
+    mut commands: Commands,
+    mut densify: EventReader<DensifyLanes>,
+    lanes: Query<LaneProperties, With<LaneMarker>>,
+    anchors: Query<(&Anchor, &Parent)>,
+    levels: Query<(), With<LevelElevation>>,
+    mut delete: EventWriter<Delete>,
+) {
+    for DensifyLanes {
+        lanes: requested,
+        spacing,
+    } in densify.read()
+    {
+        if *spacing <= 0.0 {
+            warn!("Unable to densify lanes with a spacing of {spacing}");
+            continue;
+        }
+
+        for lane in requested {
+            let Ok((edge, forward, reverse, graphs, lane_parent, evacuation, curve)) =
+                lanes.get(*lane)
+            else {
+                continue;
+            };
+            let Some((level, p0, p1)) = lane_endpoints(edge, &anchors, &levels) else {
+                continue;
+            };
+            let curve = curve.copied().unwrap_or_default();
+            let segments = (curve.length(p0, p1) / spacing).ceil() as usize;
+            if segments <= 1 {
+                continue;
+            }
+
+            // Curved lanes are split into pieces that follow the same curve
+            let mut chain = vec![edge.start()];
+            let mut pieces = Vec::new();
+            let mut remaining = curve;
+            let (mut start, mut t_start) = (p0, 0.0);
+            for i in 1..segments {
+                let t = i as f32 / segments as f32;
+                let (piece, p, rest) = remaining.split(start, p1, (t - t_start) / (1.0 - t_start));
+                let anchor = commands
+                    .spawn(AnchorBundle::new(p.to_array().into()))
+                    .set_parent(level)
+                    .id();
+                chain.push(anchor);
+                pieces.push(piece);
+                (remaining, start, t_start) = (rest, p, t);
+            }
+            chain.push(edge.end());
+            pieces.push(remaining);
+
+            for (i, (pair, curve)) in chain.windows(2).zip(pieces).enumerate() {
+                commands
+                    .spawn(Lane {
+                        anchors: Edge::new(pair[0], pair[1]),
+                        forward: segment_motion(forward, i + 1 == segments),
+                        reverse: segment_reverse(reverse, i == 0),
+                        graphs: graphs.clone(),
+                        evacuation: evacuation.copied().unwrap_or_default(),
+                        curve,
+                        marker: LaneMarker,
+                    })
+                    .set_parent(lane_parent.get());
+            }
+            delete.send(Delete::new(*lane));
+        }
+    }
+}
+
+fn sparsify_lanes(
+    mut commands: Commands,
+    mut sparsify: EventReader<SparsifyLanes>,
+    lanes: Query<LaneProperties, With<LaneMarker>>,
+    anchors: Query<(&Anchor, &Parent)>,
+    levels: Query<(), With<LevelElevation>>,
+    dependents: Query<&Dependents>,
+    mut delete: EventWriter<Delete>,
+    // Anchors between merged lanes can only be deleted after the lanes
+    // that depend on them are gone.
+    mut pending_anchors: Local<Vec<Entity>>,
+) {
+    pending_anchors.retain(|anchor| match dependents.get(*anchor) {
+        Ok(deps) if deps.is_empty() => {
+            delete.send(Delete::new(*anchor));
+            false
+        }
+        Ok(_) => true,
+        Err(_) => false,
+    });
+
+    for SparsifyLanes {
+        lanes: requested,
+        tolerance,
+    } in sparsify.read()
+    {
+        let requested: HashSet<Entity> = requested
+            .iter()
+            .copied()
+            .filter(|lane| lanes.contains(*lane))
+            .collect();
+        let mut lane_starting_at: HashMap<Entity, Entity> = HashMap::new();
+        let mut lane_ending_at: HashMap<Entity, Entity> = HashMap::new();
+        for lane in &requested {
+            if let Ok((edge, ..)) = lanes.get(*lane) {
+                lane_starting_at.insert(edge.start(), *lane);
+                lane_ending_at.insert(edge.end(), *lane);
+            }
+        }
+
+        // Whether two consecutive lanes could be one lane, ignoring docks
+        let same_properties = |a: Entity, b: Entity| -> bool {
+            let (Ok((_, fa, ra, ga, _, ea, ca)), Ok((_, fb, rb, gb, _, eb, cb))) =
+                (lanes.get(a), lanes.get(b))
+            else {
+                return false;
+            };
+            // Curved lanes are never merged since one curve cannot replace them
+            let straight = |c: Option<&LaneCurve>| c.map_or(true, |c| c.is_straight());
+            straight(ca)
+                && straight(cb)
+                && segment_motion(fa, false) == segment_motion(fb, false)
+                && segment_reverse(ra, false) == segment_reverse(rb, false)
+                && ga == gb
+                && ea.copied().unwrap_or_default() == eb.copied().unwrap_or_default()
+        };
+
+        // The next lane of a chain if the anchor between them can be removed
+        let next_in_chain = |lane: Entity| -> Option<Entity> {
+            let (edge, ..) = lanes.get(lane).ok()?;
+            let anchor = edge.end();
+            let next = *lane_starting_at.get(&anchor)?;
+            let deps = dependents.get(anchor).ok()?;
+            let only_chain = deps.len() == 2 && deps.contains(&lane) && deps.contains(&next);
+            (next != lane && only_chain && same_properties(lane, next)).then_some(next)
+        };
+
+        let mut consumed = HashSet::new();
+        let mut ordered: Vec<Entity> = requested.iter().copied().collect();
+        ordered.sort();
+        for first in ordered {
+            if consumed.contains(&first) {
+                continue;
+            }
+            let Ok((first_edge, forward, reverse, graphs, lane_parent, evacuation, _)) =
+                lanes.get(first)
+            else {
+                continue;
+            };
+            // Only start chains from lanes that are not in the middle of one
+            let is_interior = lane_ending_at
+                .get(&first_edge.start())
+                .is_some_and(|previous| next_in_chain(*previous) == Some(first));
+            if is_interior {
+                continue;
+            }
+
+            let Some((level, start, _)) = lane_endpoints(first_edge, &anchors, &levels) else {
+                continue;
+            };
+
+            let mut chain = vec![first];
+            let mut interior_points = Vec::new();
+            while let Some(next) = next_in_chain(*chain.last().unwrap()) {
+                if consumed.contains(&next) || chain.contains(&next) {
+                    break;
+                }
+                let Ok((next_edge, ..)) = lanes.get(next) else {
+                    break;
+                };
+                let Some((next_level, mid, end)) = lane_endpoints(next_edge, &anchors, &levels)
+                else {
+                    break;
+                };
+                if next_level != level {
+                    break;
+                }
+                // Every anchor that gets removed must stay close to the
+                // merged lane.
+                let direction = (end - start).normalize_or_zero();
+                let off_line = |p: Vec2| (p - start).perp_dot(direction).abs() > *tolerance;
+                if off_line(mid) || interior_points.iter().copied().any(off_line) {
+                    break;
+                }
+                interior_points.push(mid);
+                chain.push(next);
+            }
+
+            if chain.len() < 2 {
+                continue;
+            }
+
+            let last = *chain.last().unwrap();
+            let Ok((last_edge, last_forward, ..)) = lanes.get(last) else {
+                continue;
+            };
+            let mut merged_forward = forward.clone();
+            merged_forward.dock = last_forward.dock.clone();
+            commands
+                .spawn(Lane {
+                    anchors: Edge::new(first_edge.start(), last_edge.end()),
+                    forward: merged_forward,
+                    reverse: reverse.clone(),
+                    graphs: graphs.clone(),
+                    evacuation: evacuation.copied().unwrap_or_default(),
+                    curve: LaneCurve::Straight,
+                    marker: LaneMarker,
+                })
+                .set_parent(lane_parent.get());
+
+            for lane in &chain {
+                consumed.insert(*lane);
+                delete.send(Delete::new(*lane));
+                if *lane != last {
+                    if let Ok((edge, ..)) = lanes.get(*lane) {
+                        pending_anchors.push(edge.end());
+                    }
+                }
+            }
+        }
+    }
+}
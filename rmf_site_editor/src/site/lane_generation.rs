@@ -0,0 +1,235 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, AppState};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// How high above the floor proposed lanes are drawn
+const LANE_PROPOSAL_HEIGHT: f32 = 0.12;
+
+/// Proposed lanes are connected to existing anchors that are closer than
+/// this instead of getting new anchors.
+const PROPOSAL_SNAP_DISTANCE: f32 = 0.5 * LANE_WIDTH;
+
+/// Send this event to propose lanes along the centerlines of the corridors
+/// between the walls of a level. The proposals are stored in
+/// [`LaneProposals`] until they are accepted or discarded.
+#[derive(Event, Clone, Debug)]
+pub struct ProposeLanes {
+    pub level: Entity,
+    pub settings: CenterlineSettings,
+    /// Only use the free space that is covered by the floors of the level
+    pub within_floors: bool,
+}
+
+/// Send this event to turn the accepted [`LaneProposals`] into lanes. The
+/// proposals are cleared afterwards.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct AcceptLaneProposals;
+
+/// Lanes that were proposed for a level by [`ProposeLanes`].
+#[derive(Resource, Default, Debug, Clone)]
+pub struct LaneProposals {
+    pub level: Option<Entity>,
+    /// Positions of the vertices of the proposals in the frame of the level
+    pub vertices: Vec<Vec2>,
+    pub lanes: Vec<LaneProposal>,
+    /// The proposal that should stand out, such as while it is hovered in a
+    /// list of proposals
+    pub highlighted: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LaneProposal {
+    pub ends: [usize; 2],
+    pub accepted: bool,
+}
+
+impl LaneProposals {
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lanes.is_empty()
+    }
+
+    pub fn accepted_count(&self) -> usize {
+        self.lanes.iter().filter(|l| l.accepted).count()
+    }
+
+    pub fn length(&self, proposal: &LaneProposal) -> f32 {
+        let [a, b] = proposal.ends;
+        self.vertices[a].distance(self.vertices[b])
+    }
+}
+
+#[derive(Default)]
+pub struct LaneGenerationPlugin;
+
+impl Plugin for LaneGenerationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LaneProposals>()
+            .add_event::<ProposeLanes>()
+            .add_event::<AcceptLaneProposals>()
+            .add_systems(
+                Update,
+                (propose_lanes, accept_lane_proposals, draw_lane_proposals)
+                    .chain()
+                    .run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+fn propose_lanes(
+    mut propose: EventReader<ProposeLanes>,
+    mut proposals: ResMut<LaneProposals>,
+    walls: Query<&Edge<Entity>, With<WallMarker>>,
+    floors: Query<&Path<Entity>, With<FloorMarker>>,
+    anchors: Query<(&Anchor, &Parent)>,
+    levels: Query<(), With<LevelElevation>>,
+) {
+    let Some(request) = propose.read().last() else {
+        return;
+    };
+
+    let level_walls: Vec<[Vec2; 2]> = walls
+        .iter()
+        .filter_map(|edge| lane_endpoints(edge, &anchors, &levels))
+        .filter(|(level, _, _)| *level == request.level)
+        .map(|(_, p0, p1)| [p0, p1])
+        .collect();
+
+    let regions: Vec<Vec<[f32; 2]>> = if request.within_floors {
+        floors
+            .iter()
+            .filter(|path| {
+                path.first()
+                    .and_then(|a| anchors.get(*a).ok())
+                    .is_some_and(|(_, parent)| parent.get() == request.level)
+            })
+            .map(|path| {
+                path.iter()
+                    .filter_map(|a| anchors.get(*a).ok())
+                    .map(|(anchor, _)| anchor.translation_for_category(Category::Floor))
+                    .collect()
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let graph = corridor_centerlines(&level_walls, &regions, &request.settings);
+    info!(
+        "Proposed {} lanes from {} walls",
+        graph.edges.len(),
+        level_walls.len()
+    );
+    *proposals = LaneProposals {
+        level: Some(request.level),
+        vertices: graph.vertices,
+        lanes: graph
+            .edges
+            .into_iter()
+            .map(|ends| LaneProposal {
+                ends,
+                accepted: true,
+            })
+            .collect(),
+        highlighted: None,
+    };
+}
+
+fn accept_lane_proposals(
+    mut accept: EventReader<AcceptLaneProposals>,
+    mut proposals: ResMut<LaneProposals>,
+    anchors: Query<(Entity, &Anchor, &Parent)>,
+    mut spawner: Spawner,
+) {
+    if accept.read().last().is_none() {
+        return;
+    }
+    let Some(level) = proposals.level else {
+        return;
+    };
+    let existing: Vec<(Entity, Vec2)> = anchors
+        .iter()
+        .filter(|(_, _, parent)| parent.get() == level)
+        .map(|(e, anchor, _)| {
+            (
+                e,
+                Vec2::from(anchor.translation_for_category(Category::Lane)),
+            )
+        })
+        .collect();
+
+    let mut anchor_of_vertex: HashMap<usize, Entity> = HashMap::new();
+    let mut created = 0;
+    for proposal in proposals.lanes.iter().filter(|l| l.accepted) {
+        let [a, b] = proposal.ends.map(|v| {
+            *anchor_of_vertex.entry(v).or_insert_with(|| {
+                let p = proposals.vertices[v];
+                existing
+                    .iter()
+                    .filter(|(_, q)| p.distance(*q) < PROPOSAL_SNAP_DISTANCE)
+                    .min_by(|(_, q0), (_, q1)| p.distance(*q0).total_cmp(&p.distance(*q1)))
+                    .map(|(e, _)| *e)
+                    .unwrap_or_else(|| spawner.add_vertex(level, p))
+            })
+        });
+        if a == b {
+            continue;
+        }
+        spawner.add_lane(a, b);
+        created += 1;
+    }
+    info!("Created {created} lanes from the proposals");
+    proposals.clear();
+}
+
+fn draw_lane_proposals(
+    proposals: Res<LaneProposals>,
+    current_level: Res<CurrentLevel>,
+    transforms: Query<&GlobalTransform>,
+    mut gizmos: Gizmos,
+) {
+    let Some(level) = proposals.level else {
+        return;
+    };
+    if current_level.0 != Some(level) {
+        return;
+    }
+    let level_tf = transforms.get(level).copied().unwrap_or_default();
+    let position =
+        |v: usize| level_tf.transform_point(proposals.vertices[v].extend(LANE_PROPOSAL_HEIGHT));
+
+    for (i, proposal) in proposals.lanes.iter().enumerate() {
+        let color = if proposals.highlighted == Some(i) {
+            Color::YELLOW
+        } else if proposal.accepted {
+            Color::GREEN
+        } else {
+            Color::GRAY
+        };
+        let [a, b] = proposal.ends.map(position);
+        gizmos.line(a, b, color);
+        for p in [a, b] {
+            gizmos.circle(p, Vec3::Z, 0.1, color);
+        }
+    }
+}
@@ -0,0 +1,330 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, AppState};
+use bevy::prelude::*;
+
+/// How far, in meters, the anchor between two lanes may be from the line
+/// between their outer anchors for [`JoinLanes`] to merge them.
+pub const DEFAULT_JOIN_TOLERANCE: f32 = 0.05;
+
+/// Send this event to split a lane into two lanes at the point of the lane
+/// that is closest to `at`. A new anchor is placed there and both new lanes
+/// inherit the properties of the original lane.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SplitLane {
+    pub lane: Entity,
+    pub at: Vec2,
+}
+
+/// Send this event to merge the two lanes that meet at `anchor` into one
+/// lane. This only happens if nothing else depends on the anchor, the lanes
+/// have the same properties, and the anchor is within `tolerance` meters of
+/// the merged lane. The anchor is deleted afterwards.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct JoinLanes {
+    pub anchor: Entity,
+    pub tolerance: f32,
+}
+
+impl JoinLanes {
+    pub fn new(anchor: Entity) -> Self {
+        Self {
+            anchor,
+            tolerance: DEFAULT_JOIN_TOLERANCE,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct LaneSplitPlugin;
+
+impl Plugin for LaneSplitPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SplitLane>()
+            .add_event::<JoinLanes>()
+            .add_systems(
+                Update,
+                (split_lanes, join_lanes).run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+/// Split `lane`, which goes from `p0` to `p1`, into two lanes that meet at
+/// `anchor`. The lane is split at the point of its curve that is closest to
+/// `at`, and that point is returned along with the two new lanes. Both lanes
+/// follow the curve of the original lane and inherit its properties, except
+/// that docks stay at the ends of the original lane.
+pub(crate) fn split_lane_at(
+    lane: &Lane<Entity>,
+    p0: Vec2,
+    p1: Vec2,
+    at: Vec2,
+    anchor: Entity,
+) -> (Vec2, [Lane<Entity>; 2]) {
+    let curve = lane.curve;
+    // Keep the new anchor away from the ends so neither lane is empty
+    let s = curve.closest_t(p0, p1, at).clamp(0.05, 0.95);
+    let (first_curve, p, second_curve) = curve.split(p0, p1, s);
+    let piece = |anchors: Edge<Entity>, curve: LaneCurve, first: bool| Lane {
+        anchors,
+        forward: segment_motion(&lane.forward, !first),
+        reverse: segment_reverse(&lane.reverse, first),
+        graphs: lane.graphs.clone(),
+        evacuation: lane.evacuation,
+        curve,
+        marker: LaneMarker,
+    };
+    (
+        p,
+        [
+            piece(Edge::new(lane.anchors.start(), anchor), first_curve, true),
+            piece(Edge::new(anchor, lane.anchors.end()), second_curve, false),
+        ],
+    )
+}
+
+/// Gather the properties of a lane from a [`LaneProperties`] query.
+pub(crate) fn lane_from_properties(
+    (edge, forward, reverse, graphs, _, evacuation, curve): LaneProperties,
+) -> Lane<Entity> {
+    Lane {
+        anchors: *edge,
+        forward: forward.clone(),
+        reverse: reverse.clone(),
+        graphs: graphs.clone(),
+        evacuation: evacuation.copied().unwrap_or_default(),
+        curve: curve.copied().unwrap_or_default(),
+        marker: LaneMarker,
+    }
+}
+
+fn split_lanes(
+    mut commands: Commands,
+    mut requests: EventReader<SplitLane>,
+    lanes: Query<(LaneProperties, Has<HumanLaneMarker>), With<LaneMarker>>,
+    anchors: Query<(&Anchor, &Parent)>,
+    levels: Query<(), With<LevelElevation>>,
+    mut delete: EventWriter<Delete>,
+) {
+    for SplitLane { lane, at } in requests.read() {
+        let Ok((properties, human)) = lanes.get(*lane) else {
+            continue;
+        };
+        let lane_parent = properties.4.get();
+        let original = lane_from_properties(properties);
+        let Some((level, p0, p1)) = lane_endpoints(&original.anchors, &anchors, &levels) else {
+            warn!("Unable to split a lane whose anchors are on different levels");
+            continue;
+        };
+        if p0.distance_squared(p1) < f32::EPSILON {
+            continue;
+        }
+        let anchor = commands.spawn_empty().id();
+        let (p, pieces) = split_lane_at(&original, p0, p1, *at, anchor);
+        commands
+            .entity(anchor)
+            .insert(AnchorBundle::new(p.to_array().into()))
+            .set_parent(level);
+
+        for piece in pieces {
+            let mut new_lane = commands.spawn(piece);
+            new_lane.set_parent(lane_parent);
+            if human {
+                new_lane.insert(HumanLaneMarker);
+            }
+        }
+        delete.send(Delete::new(*lane));
+    }
+}
+
+fn join_lanes(
+    mut commands: Commands,
+    mut requests: EventReader<JoinLanes>,
+    lanes: Query<(LaneProperties, Has<HumanLaneMarker>), With<LaneMarker>>,
+    anchors: Query<(&Anchor, &Parent)>,
+    levels: Query<(), With<LevelElevation>>,
+    dependents: Query<&Dependents>,
+    mut delete: EventWriter<Delete>,
+    // The anchor between the lanes can only be deleted after the lanes that
+    // depend on it are gone.
+    mut pending_anchors: Local<Vec<Entity>>,
+) {
+    pending_anchors.retain(|anchor| match dependents.get(*anchor) {
+        Ok(deps) if deps.is_empty() => {
+            delete.send(Delete::new(*anchor));
+            false
+        }
+        Ok(_) => true,
+        Err(_) => false,
+    });
+
+    for JoinLanes { anchor, tolerance } in requests.read() {
+        let Ok(deps) = dependents.get(*anchor) else {
+            continue;
+        };
+        let joined: Vec<Entity> = deps.iter().copied().collect();
+        let [a, b] = joined[..] else {
+            warn!("Only an anchor that is shared by exactly two lanes can be joined");
+            continue;
+        };
+        let (Ok((props_a, human_a)), Ok((props_b, human_b))) = (lanes.get(a), lanes.get(b)) else {
+            warn!("Only an anchor that is shared by exactly two lanes can be joined");
+            continue;
+        };
+
+        // Put the lanes in the order that they are traveled
+        let (first, second) = if props_a.0.end() == *anchor && props_b.0.start() == *anchor {
+            ((props_a, a), (props_b, b))
+        } else if props_b.0.end() == *anchor && props_a.0.start() == *anchor {
+            ((props_b, b), (props_a, a))
+        } else {
+            warn!("Unable to join lanes that travel in opposite directions");
+            continue;
+        };
+        let ((first_edge, forward, reverse, graphs, lane_parent, evacuation, first_curve), first) =
+            first;
+        let (
+            (
+                second_edge,
+                second_forward,
+                second_reverse,
+                second_graphs,
+                _,
+                second_evacuation,
+                second_curve,
+            ),
+            second,
+        ) = second;
+
+        let straight = |c: Option<&LaneCurve>| c.map_or(true, |c| c.is_straight());
+        if !straight(first_curve) || !straight(second_curve) {
+            warn!("Unable to join curved lanes");
+            continue;
+        }
+
+        let same_properties = segment_motion(forward, false)
+            == segment_motion(second_forward, false)
+            && segment_reverse(reverse, false) == segment_reverse(second_reverse, false)
+            && graphs == second_graphs
+            && evacuation.copied().unwrap_or_default()
+                == second_evacuation.copied().unwrap_or_default()
+            && human_a == human_b;
+        if !same_properties {
+            warn!("Unable to join lanes that have different properties");
+            continue;
+        }
+
+        let (Some((level, start, mid)), Some((second_level, _, end))) = (
+            lane_endpoints(first_edge, &anchors, &levels),
+            lane_endpoints(second_edge, &anchors, &levels),
+        ) else {
+            continue;
+        };
+        let direction = (end - start).normalize_or_zero();
+        if level != second_level || (mid - start).perp_dot(direction).abs() > *tolerance {
+            warn!("Unable to join lanes that are not in a straight line");
+            continue;
+        }
+
+        let mut merged_forward = forward.clone();
+        merged_forward.dock = second_forward.dock.clone();
+        let mut lane = commands.spawn(Lane {
+            anchors: Edge::new(first_edge.start(), second_edge.end()),
+            forward: merged_forward,
+            reverse: reverse.clone(),
+            graphs: graphs.clone(),
+            evacuation: evacuation.copied().unwrap_or_default(),
+            curve: LaneCurve::Straight,
+            marker: LaneMarker,
+        });
+        lane.set_parent(lane_parent.get());
+        if human_a {
+            lane.insert(HumanLaneMarker);
+        }
+
+        delete.send(Delete::new(first));
+        delete.send(Delete::new(second));
+        pending_anchors.push(*anchor);
+    }
+}
+
+#[test]
+fn test_split_lane_at_keeps_docks_at_the_ends() {
+    let [start, end, anchor] = [0, 1, 2].map(Entity::from_raw);
+    let dock = |name: &str| Motion {
+        dock: Some(Dock {
+            name: name.to_owned(),
+            duration: None,
+        }),
+        ..Default::default()
+    };
+    let lane = Lane {
+        anchors: Edge::new(start, end),
+        forward: dock("forward"),
+        reverse: ReverseLane::Different(dock("reverse")),
+        graphs: AssociatedGraphs::All,
+        evacuation: Default::default(),
+        curve: LaneCurve::Straight,
+        marker: LaneMarker,
+    };
+
+    let (p, [first, second]) = split_lane_at(
+        &lane,
+        Vec2::ZERO,
+        Vec2::new(10.0, 0.0),
+        Vec2::new(3.0, 1.0),
+        anchor,
+    );
+    assert!(p.distance(Vec2::new(3.0, 0.0)) < 1e-4);
+    assert_eq!(first.anchors, Edge::new(start, anchor));
+    assert_eq!(second.anchors, Edge::new(anchor, end));
+    assert!(first.forward.dock.is_none());
+    assert_eq!(second.forward, lane.forward);
+    assert_eq!(first.reverse, lane.reverse);
+    assert_eq!(second.reverse, ReverseLane::Different(Motion::default()));
+}
+
+#[test]
+fn test_split_lane_at_follows_the_curve() {
+    let [start, end, anchor] = [0, 1, 2].map(Entity::from_raw);
+    let (p0, p1) = (Vec2::ZERO, Vec2::new(10.0, 0.0));
+    let curve = LaneCurve::Bezier {
+        start_control: [0.0, 5.0],
+        end_control: [0.0, 5.0],
+    };
+    let lane = Lane {
+        anchors: Edge::new(start, end),
+        forward: Default::default(),
+        reverse: Default::default(),
+        graphs: AssociatedGraphs::All,
+        evacuation: Default::default(),
+        curve,
+        marker: LaneMarker,
+    };
+
+    let at = Vec2::new(4.0, 10.0);
+    let s = curve.closest_t(p0, p1, at);
+    let (p, [first, second]) = split_lane_at(&lane, p0, p1, at, anchor);
+    assert!(p.distance(curve.point(p0, p1, s)) < 1e-4);
+    for t in [0.25, 0.5, 0.75] {
+        let q = first.curve.point(p0, p, t);
+        assert!(q.distance(curve.point(p0, p1, t * s)) < 1e-4);
+        let q = second.curve.point(p, p1, t);
+        assert!(q.distance(curve.point(p0, p1, s + t * (1.0 - s))) < 1e-4);
+    }
+}
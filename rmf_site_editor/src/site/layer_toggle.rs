@@ -0,0 +1,159 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::Selectable,
+    settings::EditorSettings,
+    site::{HiddenByGroup, LockedByGroup, *},
+    CurrentWorkspace,
+};
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+/// Marks an element that is hidden because its type is hidden on its level.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct HiddenByLayer;
+
+/// Marks a selectable mesh that cannot be selected because the type of its
+/// element is locked or hidden on its level.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct LockedByLayer;
+
+/// Keep the visibility and selectability of site elements in sync with the
+/// layer toggles of the editor settings.
+#[derive(Default)]
+pub struct LayerTogglePlugin;
+
+impl Plugin for LayerTogglePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_layer_flags);
+    }
+}
+
+/// The level that an element belongs to. Elements that are drawn between
+/// anchors, such as lanes and locations, belong to the level of their first
+/// anchor.
+pub fn level_of_element(
+    e: Entity,
+    parents: &Query<&Parent>,
+    levels: &Query<(), With<LevelElevation>>,
+    anchors: &Query<(
+        Option<&Point<Entity>>,
+        Option<&Edge<Entity>>,
+        Option<&Path<Entity>>,
+    )>,
+) -> Option<Entity> {
+    let e = match anchors.get(e) {
+        Ok((Some(point), _, _)) => point.0,
+        Ok((_, Some(edge), _)) => edge.start(),
+        Ok((_, _, Some(path))) => path.0.first().copied().unwrap_or(e),
+        _ => e,
+    };
+    std::iter::once(e)
+        .chain(AncestorIter::new(parents, e))
+        .find(|a| levels.contains(*a))
+}
+
+fn update_layer_flags(
+    mut commands: Commands,
+    settings: Res<EditorSettings>,
+    current_workspace: Res<CurrentWorkspace>,
+    new_elements: Query<(), Or<(Added<Category>, Added<Selectable>, Changed<Parent>)>>,
+    elements: Query<(Entity, &Category)>,
+    names: Query<&NameInSite>,
+    parents: Query<&Parent>,
+    levels: Query<(), With<LevelElevation>>,
+    anchors: Query<(
+        Option<&Point<Entity>>,
+        Option<&Edge<Entity>>,
+        Option<&Path<Entity>>,
+    )>,
+    mut visibilities: Query<&mut Visibility>,
+    hidden_by_layer: Query<Entity, With<HiddenByLayer>>,
+    hidden_by_group: Query<(), With<HiddenByGroup>>,
+    mut selectables: Query<(
+        Entity,
+        &mut Selectable,
+        Has<LockedByLayer>,
+        Has<LockedByGroup>,
+    )>,
+) {
+    if !settings.is_changed() && !current_workspace.is_changed() && new_elements.is_empty() {
+        return;
+    }
+
+    let mut hidden = HashSet::new();
+    let mut locked = HashSet::new();
+    if let Some(site) = current_workspace.root {
+        for (e, category) in &elements {
+            if matches!(category, Category::Site | Category::Level) {
+                continue;
+            }
+            let Some(level) = level_of_element(e, &parents, &levels, &anchors) else {
+                continue;
+            };
+            if !AncestorIter::new(&parents, level).any(|p| p == site) {
+                continue;
+            }
+            let Ok(level_name) = names.get(level) else {
+                continue;
+            };
+            let toggle = settings.layers.get(&level_name.0, *category);
+            if toggle.hidden {
+                hidden.insert(e);
+            }
+            if toggle.hidden || toggle.locked {
+                locked.insert(e);
+            }
+        }
+    }
+
+    for e in &hidden_by_layer {
+        if hidden.contains(&e) {
+            continue;
+        }
+        if !hidden_by_group.contains(e) {
+            if let Ok(mut visibility) = visibilities.get_mut(e) {
+                *visibility = Visibility::Inherited;
+            }
+        }
+        commands.entity(e).remove::<HiddenByLayer>();
+    }
+    for e in &hidden {
+        if hidden_by_layer.contains(*e) {
+            continue;
+        }
+        let Ok(mut visibility) = visibilities.get_mut(*e) else {
+            continue;
+        };
+        *visibility = Visibility::Hidden;
+        commands.entity(*e).insert(HiddenByLayer);
+    }
+
+    for (e, mut selectable, locked_by_layer, locked_by_group) in &mut selectables {
+        let lock = locked.contains(&selectable.element);
+        if lock && !locked_by_layer {
+            selectable.is_selectable = false;
+            commands.entity(e).insert(LockedByLayer);
+        } else if !lock && locked_by_layer {
+            if !locked_by_group {
+                selectable.is_selectable = true;
+            }
+            commands.entity(e).remove::<LockedByLayer>();
+        }
+    }
+}
@@ -16,8 +16,12 @@
 */
 
 use crate::site::*;
-use crate::CurrentWorkspace;
-use bevy::prelude::*;
+use crate::{CurrentWorkspace, RecencyRanking};
+use bevy::{
+    ecs::{event::Events, system::CommandQueue},
+    prelude::*,
+};
+use std::collections::{HashMap, HashSet};
 
 pub fn update_level_visibility(
     mut levels: Query<(Entity, &mut Visibility), With<LevelElevation>>,
@@ -66,3 +70,271 @@ pub fn assign_orphan_elements_to_level<T: Component>(
         commands.entity(current_level).add_child(orphan);
     }
 }
+
+/// Decides whether the flattened offsets of the levels of a site get
+/// computed automatically from their bounding boxes so they never overlap.
+/// The offsets are only laid out again when the user changes these settings
+/// or when a level is added or its bounding box changes, so loading and
+/// saving a site keeps the offsets that it was saved with.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct FlattenedOffsetSettings {
+    pub automatic: bool,
+    /// Space between the bounding boxes of neighboring levels, in meters
+    pub margin: f32,
+}
+
+impl Default for FlattenedOffsetSettings {
+    fn default() -> Self {
+        Self {
+            automatic: true,
+            margin: 5.0,
+        }
+    }
+}
+
+impl FlattenedOffsetSettings {
+    /// Levels that were saved with their own flattened offsets should not
+    /// be laid out automatically.
+    pub fn for_site(site: &rmf_site_format::Site) -> Self {
+        Self {
+            automatic: site
+                .levels
+                .values()
+                .all(|level| level.flattened_offset.is_default()),
+            ..Default::default()
+        }
+    }
+}
+
+/// The bounding box of the anchors of a level, used to notice when a level
+/// changes size and its site needs to be laid out again.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct FlattenedLevelBounds(pub Option<[Vec2; 2]>);
+
+fn level_bounds(children: Option<&Children>, anchors: &Query<&Anchor>) -> Option<[Vec2; 2]> {
+    let mut bounds: Option<[Vec2; 2]> = None;
+    for anchor in children.into_iter().flatten() {
+        let Ok(anchor) = anchors.get(*anchor) else {
+            continue;
+        };
+        let p = Vec2::from(anchor.translation_for_category(Category::General));
+        bounds = Some(match bounds {
+            Some([min, max]) => [min.min(p), max.max(p)],
+            None => [p, p],
+        });
+    }
+    bounds
+}
+
+pub fn update_flattened_offsets(
+    mut commands: Commands,
+    sites: Query<(Ref<FlattenedOffsetSettings>, &Children), With<NameOfSite>>,
+    levels: Query<(
+        Ref<LevelElevation>,
+        Option<&Children>,
+        Option<&FlattenedOffset>,
+        Option<&FlattenedLevelBounds>,
+    )>,
+    anchors: Query<&Anchor>,
+    changed_anchors: Query<&Parent, Changed<Anchor>>,
+) {
+    let touched: HashSet<Entity> = changed_anchors.iter().map(|p| p.get()).collect();
+
+    for (settings, site_children) in &sites {
+        // A site that was just loaded keeps the offsets from its file
+        let loaded = settings.is_added();
+        let requested = settings.is_changed() && !loaded;
+        let mut relayout = requested;
+        let mut site_levels = Vec::new();
+        for e in site_children {
+            let Ok((elevation, children, _, cached)) = levels.get(*e) else {
+                continue;
+            };
+            let bounds = match cached {
+                Some(cached) if !touched.contains(e) => cached.0,
+                _ => level_bounds(children, &anchors),
+            };
+            if cached.map(|c| c.0) != Some(bounds) {
+                commands.entity(*e).insert(FlattenedLevelBounds(bounds));
+                // Levels without cached bounds are new unless the whole site
+                // was just loaded.
+                relayout |= cached.is_some() || !loaded;
+            }
+            relayout |= elevation.is_changed() && !elevation.is_added();
+            site_levels.push((*e, elevation.0, bounds));
+        }
+
+        if !settings.automatic || !relayout {
+            continue;
+        }
+
+        for (e, offset) in compute_flattened_offsets(site_levels, settings.margin) {
+            let current = levels.get(e).ok().and_then(|(_, _, offset, _)| offset);
+            if current != Some(&offset) {
+                commands.entity(e).insert(offset);
+            }
+        }
+    }
+}
+
+/// Send this event to add a new level to the current site. The new level
+/// becomes the current level.
+#[derive(Event, Clone, Debug)]
+pub struct AddLevel {
+    pub name: String,
+    pub elevation: f32,
+}
+
+/// Send this event to make a copy of a level with all of its anchors, walls,
+/// doors, floors, drawings, lights, and cameras. Model instances belong to
+/// scenarios, so they are not copied. The copy becomes the current level.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DuplicateLevel {
+    pub level: Entity,
+}
+
+pub fn add_levels(
+    mut commands: Commands,
+    mut add_levels: EventReader<AddLevel>,
+    mut current_level: ResMut<CurrentLevel>,
+) {
+    for AddLevel { name, elevation } in add_levels.read() {
+        let new_level = commands
+            .spawn((
+                SpatialBundle::default(),
+                LevelProperties {
+                    elevation: LevelElevation(*elevation),
+                    name: NameInSite(name.clone()),
+                    ..Default::default()
+                },
+                Category::Level,
+                RecencyRanking::<DrawingMarker>::default(),
+                RecencyRanking::<FloorMarker>::default(),
+            ))
+            .id();
+        current_level.0 = Some(new_level);
+    }
+}
+
+pub fn duplicate_level(world: &mut World) {
+    let requests: Vec<_> = world
+        .resource_mut::<Events<DuplicateLevel>>()
+        .drain()
+        .collect();
+    for DuplicateLevel { level } in requests {
+        let Some(site) = world.get::<Parent>(level).map(|p| p.get()) else {
+            error!("Unable to duplicate level {level:?} because it does not belong to a site");
+            continue;
+        };
+        // Generating the site makes sure that every element of the level has
+        // a SiteID that the copy can use to refer to the other elements.
+        let site_data = match generate_site(world, site) {
+            Ok(site_data) => site_data,
+            Err(err) => {
+                error!("Unable to duplicate level: {err}");
+                continue;
+            }
+        };
+        let Some(level_id) = world.get::<SiteID>(level).map(|id| id.0) else {
+            error!("Unable to duplicate level {level:?} because it was not saved into the site");
+            continue;
+        };
+        let Some(mut level_data) = site_data.levels.get(&level_id).cloned() else {
+            error!("Unable to duplicate level {level:?} because its data could not be found");
+            continue;
+        };
+        level_data.properties.name.0 = format!("{} copy", level_data.properties.name.0);
+
+        // The level may refer to elements of the site, such as texture groups
+        let mut id_to_entity = HashMap::new();
+        if let Some(children) = world.get::<Children>(site) {
+            for child in children.iter() {
+                if let Some(id) = world.get::<SiteID>(*child) {
+                    id_to_entity.insert(id.0, *child);
+                }
+            }
+        }
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, world);
+        let new_level = match spawn_level_entities(
+            &mut commands,
+            site,
+            level_id,
+            &level_data,
+            &mut id_to_entity,
+        ) {
+            Ok(new_level) => new_level,
+            Err(broken) => {
+                error!("Unable to duplicate level because of a broken reference: {broken}");
+                continue;
+            }
+        };
+        queue.apply(world);
+
+        // The copies need to receive new IDs the next time the site is saved
+        let mut stack = vec![new_level];
+        while let Some(e) = stack.pop() {
+            world.entity_mut(e).remove::<SiteID>();
+            if let Some(children) = world.get::<Children>(e) {
+                stack.extend(children.iter().copied());
+            }
+        }
+        world.resource_mut::<CurrentLevel>().0 = Some(new_level);
+    }
+}
+
+#[test]
+fn test_flattened_offsets_are_kept_on_load_and_laid_out_on_resize() {
+    let mut world = World::new();
+    let site = world
+        .spawn((
+            NameOfSite("site".to_owned()),
+            FlattenedOffsetSettings::default(),
+        ))
+        .id();
+    let ground = world
+        .spawn((LevelElevation(0.0), FlattenedOffset::default()))
+        .set_parent(site)
+        .id();
+    let upper = world
+        .spawn((LevelElevation(5.0), FlattenedOffset::default()))
+        .set_parent(site)
+        .id();
+    let [middle, edge] = [5.0, 10.0].map(|x| {
+        world
+            .spawn(Anchor::Translate2D([x, 0.0]))
+            .set_parent(ground)
+            .id()
+    });
+    for x in [0.0, 10.0] {
+        world.spawn(Anchor::Translate2D([x, 0.0])).set_parent(upper);
+    }
+    world
+        .spawn(Anchor::Translate2D([0.0, 0.0]))
+        .set_parent(ground);
+
+    let mut system = IntoSystem::into_system(update_flattened_offsets);
+    system.initialize(&mut world);
+    let mut run = |world: &mut World| {
+        system.run((), world);
+        system.apply_deferred(world);
+    };
+
+    // Loading a site keeps the offsets that it was saved with
+    run(&mut world);
+    assert!(world.get::<FlattenedOffset>(upper).unwrap().is_default());
+
+    // Moving an anchor without resizing the level does not lay it out
+    *world.get_mut::<Anchor>(middle).unwrap() = Anchor::Translate2D([6.0, 0.0]);
+    run(&mut world);
+    assert!(world.get::<FlattenedOffset>(upper).unwrap().is_default());
+
+    // Resizing a level lays out the levels of its site
+    *world.get_mut::<Anchor>(edge).unwrap() = Anchor::Translate2D([20.0, 0.0]);
+    run(&mut world);
+    assert_eq!(
+        *world.get::<FlattenedOffset>(upper).unwrap(),
+        FlattenedOffset([25.0, 0.0])
+    );
+}
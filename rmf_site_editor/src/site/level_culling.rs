@@ -0,0 +1,149 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, AppState};
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use std::collections::HashMap;
+
+/// Levels other than the one being edited are hidden, but their walls and
+/// floors would still keep their meshes in memory. When culling is enabled,
+/// levels that stay hidden for longer than `delay` seconds release those
+/// meshes, and they get rebuilt as soon as the level is shown again.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct LevelCulling {
+    pub enabled: bool,
+    /// How long a level needs to stay hidden before its meshes are released,
+    /// so that flipping quickly between levels does not rebuild them each time
+    pub delay: f32,
+}
+
+impl Default for LevelCulling {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            delay: 2.0,
+        }
+    }
+}
+
+/// Marks a level whose wall and floor meshes have been released.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct CulledLevel;
+
+#[derive(Default)]
+pub struct LevelCullingPlugin;
+
+impl Plugin for LevelCullingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelCulling>().add_systems(
+            PostUpdate,
+            (cull_hidden_levels, restore_shown_levels)
+                .after(SiteUpdateSet::BetweenVisibilityAndTransformFlush)
+                .run_if(AppState::in_displaying_mode()),
+        );
+    }
+}
+
+fn cull_hidden_levels(
+    mut commands: Commands,
+    culling: Res<LevelCulling>,
+    time: Res<Time>,
+    mut hidden_since: Local<HashMap<Entity, f32>>,
+    levels: Query<
+        (Entity, &Visibility, Option<&Children>),
+        (With<LevelElevation>, Without<CulledLevel>),
+    >,
+    walls: Query<(), With<WallMarker>>,
+    floors: Query<&FloorSegments>,
+    mut meshes: Query<&mut Handle<Mesh>>,
+) {
+    if !culling.enabled {
+        hidden_since.clear();
+        return;
+    }
+
+    let now = time.elapsed_seconds();
+    hidden_since.retain(|level, _| levels.contains(*level));
+    for (level, visibility, children) in &levels {
+        if *visibility != Visibility::Hidden {
+            hidden_since.remove(&level);
+            continue;
+        }
+        let since = *hidden_since.entry(level).or_insert(now);
+        if now - since < culling.delay {
+            continue;
+        }
+        hidden_since.remove(&level);
+
+        for child in children.into_iter().flatten() {
+            let mesh_entity = if walls.contains(*child) {
+                Some(*child)
+            } else {
+                floors.get(*child).ok().map(|segments| segments.mesh)
+            };
+            if let Some(mut mesh) = mesh_entity.and_then(|e| meshes.get_mut(e).ok()) {
+                *mesh = Handle::default();
+            }
+        }
+        commands.entity(level).insert(CulledLevel);
+    }
+}
+
+fn restore_shown_levels(
+    mut commands: Commands,
+    culling: Res<LevelCulling>,
+    levels: Query<(Entity, &Visibility, Option<&Children>), With<CulledLevel>>,
+    mut walls: Query<&mut Edge<Entity>, With<WallMarker>>,
+    mut floors: Query<&mut Path<Entity>, With<FloorMarker>>,
+) {
+    for (level, visibility, children) in &levels {
+        if culling.enabled && *visibility == Visibility::Hidden {
+            continue;
+        }
+
+        // Triggering change detection makes the usual wall and floor systems
+        // rebuild the meshes.
+        for child in children.into_iter().flatten() {
+            if let Ok(mut edge) = walls.get_mut(*child) {
+                edge.set_changed();
+            } else if let Ok(mut path) = floors.get_mut(*child) {
+                path.set_changed();
+            }
+        }
+        commands.entity(level).remove::<CulledLevel>();
+    }
+}
+
+/// Rebuild the walls and floors of every culled level right away. Exporters
+/// read meshes out of the world, so this needs to run before they do.
+pub fn restore_culled_levels(world: &mut World) {
+    let culled: Vec<Entity> = world
+        .query_filtered::<Entity, With<CulledLevel>>()
+        .iter(world)
+        .collect();
+    if culled.is_empty() {
+        return;
+    }
+
+    for level in culled {
+        world.entity_mut(level).remove::<CulledLevel>();
+    }
+    // Systems that run for the first time see every component as changed,
+    // so these rebuild the meshes of all walls and floors.
+    world.run_system_once(update_walls);
+    world.run_system_once(update_floors);
+}
@@ -16,7 +16,7 @@
 */
 
 use crate::{
-    interaction::Selectable, shapes::*, site::*, CurrentWorkspace, Issue, ValidateWorkspace,
+    interaction::Selectable, shapes::*, site::*, CurrentWorkspace, Issue, ValidationRequests,
 };
 use bevy::{
     prelude::*,
@@ -624,16 +624,16 @@ pub const DUPLICATED_LIFT_NAME_ISSUE_UUID: Uuid =
 // generate an issue if that is the case
 pub fn check_for_duplicated_lift_names(
     mut commands: Commands,
-    mut validate_events: EventReader<ValidateWorkspace>,
+    mut validate_events: ValidationRequests,
     parents: Query<&Parent>,
     lift_names: Query<(Entity, &NameInSite), With<LiftCabin<Entity>>>,
 ) {
     const ISSUE_HINT: &str = "Lifts use their names as identifiers with RMF and each lift should \
                               have a unique name, rename the affected lifts";
-    for root in validate_events.read() {
+    for root in validate_events.roots() {
         let mut names: HashMap<String, BTreeSet<Entity>> = HashMap::new();
         for (e, name) in &lift_names {
-            if AncestorIter::new(&parents, e).any(|p| p == **root) {
+            if AncestorIter::new(&parents, e).any(|p| p == root) {
                 let entities_with_name = names.entry(name.0.clone()).or_default();
                 entities_with_name.insert(e);
             }
@@ -649,7 +649,7 @@ pub fn check_for_duplicated_lift_names(
                     hint: ISSUE_HINT.to_string(),
                 };
                 let id = commands.spawn(issue).id();
-                commands.entity(**root).add_child(id);
+                commands.entity(root).add_child(id);
             }
         }
     }
@@ -18,7 +18,7 @@
 use crate::{recency::RecencyRanking, site::*, WorkspaceMarker};
 use bevy::{ecs::system::SystemParam, prelude::*};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
     path::PathBuf,
 };
 use thiserror::Error as ThisError;
@@ -51,9 +51,9 @@ impl LoadSite {
 
 #[derive(ThisError, Debug)]
 #[error("The site has a broken internal reference: {broken}")]
-struct LoadSiteError {
-    site: Entity,
-    broken: u32,
+pub(crate) struct LoadSiteError {
+    pub(crate) site: Entity,
+    pub(crate) broken: u32,
     // TODO(@mxgrey): reintroduce Backtrack when it's supported on stable
     // backtrace: Backtrace,
 }
@@ -74,7 +74,187 @@ impl<T> LoadResult<T> for Result<T, u32> {
     }
 }
 
-fn generate_site_entities(
+/// Spawn the entities of one level and all of its children. Every spawned
+/// element is added to `id_to_entity` so that later elements can refer to it.
+fn generate_level_entities(
+    commands: &mut Commands,
+    site_id: Entity,
+    level_id: u32,
+    level_data: &rmf_site_format::Level,
+    id_to_entity: &mut HashMap<u32, Entity>,
+    consider_id: &mut impl FnMut(u32),
+) -> Result<Entity, LoadSiteError> {
+    let level_entity = commands.spawn(SiteID(level_id)).set_parent(site_id).id();
+
+    for (anchor_id, anchor) in &level_data.anchors {
+        let anchor_entity = commands
+            .spawn(AnchorBundle::new(anchor.clone()))
+            .insert(SiteID(*anchor_id))
+            .set_parent(level_entity)
+            .id();
+        id_to_entity.insert(*anchor_id, anchor_entity);
+        consider_id(*anchor_id);
+    }
+
+    for (door_id, door) in &level_data.doors {
+        let door_entity = commands
+            .spawn(door.convert(&*id_to_entity).for_site(site_id)?)
+            .insert(SiteID(*door_id))
+            .set_parent(level_entity)
+            .id();
+        id_to_entity.insert(*door_id, door_entity);
+        consider_id(*door_id);
+    }
+
+    for (drawing_id, drawing) in &level_data.drawings {
+        let drawing_entity = commands
+            .spawn(DrawingBundle::new(drawing.properties.clone()))
+            .insert(SiteID(*drawing_id))
+            .set_parent(level_entity)
+            .id();
+        if let Some(visibility) = drawing.visibility {
+            commands.entity(drawing_entity).insert(visibility);
+        }
+
+        for (anchor_id, anchor) in &drawing.anchors {
+            let anchor_entity = commands
+                .spawn(AnchorBundle::new(anchor.clone()))
+                .insert(SiteID(*anchor_id))
+                .set_parent(drawing_entity)
+                .id();
+            id_to_entity.insert(*anchor_id, anchor_entity);
+            consider_id(*anchor_id);
+        }
+
+        for (fiducial_id, fiducial) in &drawing.fiducials {
+            let fiducial_entity = commands
+                .spawn(fiducial.convert(&*id_to_entity).for_site(site_id)?)
+                .insert(SiteID(*fiducial_id))
+                .set_parent(drawing_entity)
+                .id();
+            id_to_entity.insert(*fiducial_id, fiducial_entity);
+            consider_id(*fiducial_id);
+        }
+
+        for (measurement_id, measurement) in &drawing.measurements {
+            let measurement_entity = commands
+                .spawn(measurement.convert(&*id_to_entity).for_site(site_id)?)
+                .insert(SiteID(*measurement_id))
+                .set_parent(drawing_entity)
+                .id();
+            id_to_entity.insert(*measurement_id, measurement_entity);
+            consider_id(*measurement_id);
+        }
+
+        consider_id(*drawing_id);
+    }
+
+    for (floor_id, floor) in &level_data.floors {
+        commands
+            .spawn(floor.convert(&*id_to_entity).for_site(site_id)?)
+            .insert(SiteID(*floor_id))
+            .set_parent(level_entity);
+        consider_id(*floor_id);
+    }
+
+    for (wall_id, wall) in &level_data.walls {
+        commands
+            .spawn(wall.convert(&*id_to_entity).for_site(site_id)?)
+            .insert(SiteID(*wall_id))
+            .set_parent(level_entity);
+        consider_id(*wall_id);
+    }
+
+    for (zone_id, zone) in &level_data.zones {
+        commands
+            .spawn(zone.convert(&*id_to_entity).for_site(site_id)?)
+            .insert(SiteID(*zone_id))
+            .set_parent(level_entity);
+        consider_id(*zone_id);
+    }
+
+    commands
+        .entity(level_entity)
+        .insert(SpatialBundle::HIDDEN_IDENTITY)
+        .insert(level_data.properties.clone())
+        .insert(level_data.paper_space.clone())
+        .insert(level_data.flattened_offset)
+        // Extension entities are spawned by their LevelExtensionPlugin
+        .insert(level_data.extensions.clone())
+        .insert(Category::Level)
+        .with_children(|level| {
+            // These don't need a return value so can be wrapped in a with_children
+            for (light_id, light) in &level_data.lights {
+                level.spawn(light.clone()).insert(SiteID(*light_id));
+                consider_id(*light_id);
+            }
+
+            for (physical_camera_id, physical_camera) in &level_data.physical_cameras {
+                level
+                    .spawn(physical_camera.clone())
+                    .insert(SiteID(*physical_camera_id));
+                consider_id(*physical_camera_id);
+            }
+
+            for (custom_entity_id, custom_entity) in &level_data.custom_entities {
+                level
+                    .spawn(custom_entity.clone())
+                    .insert(SiteID(*custom_entity_id));
+                consider_id(*custom_entity_id);
+            }
+
+            for extension_id in level_data.extensions.site_ids() {
+                consider_id(extension_id);
+            }
+
+            for (camera_pose_id, camera_pose) in &level_data.user_camera_poses {
+                level
+                    .spawn(camera_pose.clone())
+                    .insert(SiteID(*camera_pose_id));
+                consider_id(*camera_pose_id);
+            }
+        });
+
+    // TODO(MXG): Log when a RecencyRanking fails to load correctly.
+    commands
+        .entity(level_entity)
+        .insert(
+            RecencyRanking::<FloorMarker>::from_u32(&level_data.rankings.floors, &*id_to_entity)
+                .unwrap_or(RecencyRanking::new()),
+        )
+        .insert(
+            RecencyRanking::<DrawingMarker>::from_u32(
+                &level_data.rankings.drawings,
+                &*id_to_entity,
+            )
+            .unwrap_or(RecencyRanking::new()),
+        );
+    Ok(level_entity)
+}
+
+/// Spawn a copy of a level into an open site. Elements of the site that the
+/// level refers to, such as texture groups, need to be in `id_to_entity`
+/// already. If a reference is broken, the ID that could not be found is
+/// returned.
+pub(crate) fn spawn_level_entities(
+    commands: &mut Commands,
+    site_id: Entity,
+    level_id: u32,
+    level_data: &rmf_site_format::Level,
+    id_to_entity: &mut HashMap<u32, Entity>,
+) -> Result<Entity, u32> {
+    generate_level_entities(
+        commands,
+        site_id,
+        level_id,
+        level_data,
+        id_to_entity,
+        &mut |_| {},
+    )
+    .map_err(|err| err.broken)
+}
+
+pub(crate) fn generate_site_entities(
     commands: &mut Commands,
     model_loader: &mut ModelLoader,
     site_data: &rmf_site_format::Site,
@@ -91,6 +271,7 @@ fn generate_site_entities(
         .spawn(SpatialBundle::HIDDEN_IDENTITY)
         .insert(Category::Site)
         .insert(WorkspaceMarker)
+        .insert(FlattenedOffsetSettings::for_site(site_data))
         .id();
 
     for (anchor_id, anchor) in &site_data.anchors {
@@ -124,125 +305,14 @@ fn generate_site_entities(
     }
 
     for (level_id, level_data) in &site_data.levels {
-        let level_entity = commands.spawn(SiteID(*level_id)).set_parent(site_id).id();
-
-        for (anchor_id, anchor) in &level_data.anchors {
-            let anchor_entity = commands
-                .spawn(AnchorBundle::new(anchor.clone()))
-                .insert(SiteID(*anchor_id))
-                .set_parent(level_entity)
-                .id();
-            id_to_entity.insert(*anchor_id, anchor_entity);
-            consider_id(*anchor_id);
-        }
-
-        for (door_id, door) in &level_data.doors {
-            let door_entity = commands
-                .spawn(door.convert(&id_to_entity).for_site(site_id)?)
-                .insert(SiteID(*door_id))
-                .set_parent(level_entity)
-                .id();
-            id_to_entity.insert(*door_id, door_entity);
-            consider_id(*door_id);
-        }
-
-        for (drawing_id, drawing) in &level_data.drawings {
-            let drawing_entity = commands
-                .spawn(DrawingBundle::new(drawing.properties.clone()))
-                .insert(SiteID(*drawing_id))
-                .set_parent(level_entity)
-                .id();
-
-            for (anchor_id, anchor) in &drawing.anchors {
-                let anchor_entity = commands
-                    .spawn(AnchorBundle::new(anchor.clone()))
-                    .insert(SiteID(*anchor_id))
-                    .set_parent(drawing_entity)
-                    .id();
-                id_to_entity.insert(*anchor_id, anchor_entity);
-                consider_id(*anchor_id);
-            }
-
-            for (fiducial_id, fiducial) in &drawing.fiducials {
-                let fiducial_entity = commands
-                    .spawn(fiducial.convert(&id_to_entity).for_site(site_id)?)
-                    .insert(SiteID(*fiducial_id))
-                    .set_parent(drawing_entity)
-                    .id();
-                id_to_entity.insert(*fiducial_id, fiducial_entity);
-                consider_id(*fiducial_id);
-            }
-
-            for (measurement_id, measurement) in &drawing.measurements {
-                let measurement_entity = commands
-                    .spawn(measurement.convert(&id_to_entity).for_site(site_id)?)
-                    .insert(SiteID(*measurement_id))
-                    .set_parent(drawing_entity)
-                    .id();
-                id_to_entity.insert(*measurement_id, measurement_entity);
-                consider_id(*measurement_id);
-            }
-
-            consider_id(*drawing_id);
-        }
-
-        for (floor_id, floor) in &level_data.floors {
-            commands
-                .spawn(floor.convert(&id_to_entity).for_site(site_id)?)
-                .insert(SiteID(*floor_id))
-                .set_parent(level_entity);
-            consider_id(*floor_id);
-        }
-
-        for (wall_id, wall) in &level_data.walls {
-            commands
-                .spawn(wall.convert(&id_to_entity).for_site(site_id)?)
-                .insert(SiteID(*wall_id))
-                .set_parent(level_entity);
-            consider_id(*wall_id);
-        }
-
-        commands
-            .entity(level_entity)
-            .insert(SpatialBundle::HIDDEN_IDENTITY)
-            .insert(level_data.properties.clone())
-            .insert(Category::Level)
-            .with_children(|level| {
-                // These don't need a return value so can be wrapped in a with_children
-                for (light_id, light) in &level_data.lights {
-                    level.spawn(light.clone()).insert(SiteID(*light_id));
-                    consider_id(*light_id);
-                }
-
-                for (physical_camera_id, physical_camera) in &level_data.physical_cameras {
-                    level
-                        .spawn(physical_camera.clone())
-                        .insert(SiteID(*physical_camera_id));
-                    consider_id(*physical_camera_id);
-                }
-
-                for (camera_pose_id, camera_pose) in &level_data.user_camera_poses {
-                    level
-                        .spawn(camera_pose.clone())
-                        .insert(SiteID(*camera_pose_id));
-                    consider_id(*camera_pose_id);
-                }
-            });
-
-        // TODO(MXG): Log when a RecencyRanking fails to load correctly.
-        commands
-            .entity(level_entity)
-            .insert(
-                RecencyRanking::<FloorMarker>::from_u32(&level_data.rankings.floors, &id_to_entity)
-                    .unwrap_or(RecencyRanking::new()),
-            )
-            .insert(
-                RecencyRanking::<DrawingMarker>::from_u32(
-                    &level_data.rankings.drawings,
-                    &id_to_entity,
-                )
-                .unwrap_or(RecencyRanking::new()),
-            );
+        let level_entity = generate_level_entities(
+            commands,
+            site_id,
+            *level_id,
+            level_data,
+            &mut id_to_entity,
+            &mut consider_id,
+        )?;
         id_to_entity.insert(*level_id, level_entity);
         consider_id(*level_id);
     }
@@ -317,6 +387,16 @@ fn generate_site_entities(
         consider_id(*lane_id);
     }
 
+    for (lane_id, lane_data) in &site_data.navigation.guided.human_lanes {
+        let lane = commands
+            .spawn(lane_data.convert(&id_to_entity).for_site(site_id)?)
+            .insert((SiteID(*lane_id), HumanLaneMarker))
+            .set_parent(site_id)
+            .id();
+        id_to_entity.insert(*lane_id, lane);
+        consider_id(*lane_id);
+    }
+
     for (location_id, location_data) in &site_data.navigation.guided.locations {
         let location = commands
             .spawn(location_data.convert(&id_to_entity).for_site(site_id)?)
@@ -333,8 +413,14 @@ fn generate_site_entities(
             .convert(&id_to_entity)
             .for_site(site_id)?,
     );
+    commands.entity(site_id).insert(
+        site_data
+            .crowd_sim
+            .convert(&id_to_entity)
+            .for_site(site_id)?,
+    );
 
-    let mut model_description_dependents = HashMap::<Entity, HashSet<Entity>>::new();
+    let mut model_description_dependents = BTreeMap::<Entity, BTreeSet<Entity>>::new();
     let mut model_description_to_source = HashMap::<Entity, AssetSource>::new();
     for (model_description_id, model_description) in &site_data.model_descriptions {
         let model_description_entity = commands
@@ -345,7 +431,7 @@ fn generate_site_entities(
             .id();
         id_to_entity.insert(*model_description_id, model_description_entity);
         consider_id(*model_description_id);
-        model_description_dependents.insert(model_description_entity, HashSet::new());
+        model_description_dependents.insert(model_description_entity, BTreeSet::new());
         model_description_to_source
             .insert(model_description_entity, model_description.source.0.clone());
     }
@@ -469,8 +555,24 @@ fn generate_site_entities(
     commands
         .entity(site_id)
         .insert(nav_graph_rankings)
+        .insert(site_data.custom_entity_schemas.clone())
         .insert(NextSiteID(highest_id + 1));
 
+    for (id, tags) in &site_data.tags {
+        // Tags of elements that do not exist are dropped
+        if let Some(e) = id_to_entity.get(id) {
+            commands.entity(*e).insert(tags.clone());
+        }
+    }
+
+    // Group members that refer to missing elements are dropped instead of
+    // failing the whole load
+    let mut entity_groups = site_data.entity_groups.clone();
+    entity_groups.retain_members(|m| id_to_entity.contains_key(m));
+    commands
+        .entity(site_id)
+        .insert(entity_groups.convert(&id_to_entity).for_site(site_id)?);
+
     // Make the lift cabin anchors that are used by doors subordinate
     for (lift_id, lift_data) in &site_data.lifts {
         for (_, door) in &lift_data.cabin_doors {
@@ -495,6 +597,7 @@ pub fn load_site(
     mut model_loader: ModelLoader,
     mut load_sites: EventReader<LoadSite>,
     mut change_current_site: EventWriter<ChangeCurrentSite>,
+    mut map_loaded: EventWriter<MapLoaded>,
 ) {
     for cmd in load_sites.read() {
         let site = match generate_site_entities(&mut commands, &mut model_loader, &cmd.site) {
@@ -512,6 +615,7 @@ pub fn load_site(
         if let Some(path) = &cmd.default_file {
             commands.entity(site).insert(DefaultFile(path.clone()));
         }
+        map_loaded.send(MapLoaded { site });
 
         if cmd.focus {
             change_current_site.send(ChangeCurrentSite {
@@ -537,6 +641,39 @@ pub enum ImportNavGraphError {
     MissingCabinAnchorGroup(String),
 }
 
+pub fn load_reference_site(
+    mut commands: Commands,
+    mut model_loader: ModelLoader,
+    mut load_references: EventReader<LoadReferenceSite>,
+) {
+    for cmd in load_references.read() {
+        let site = match generate_site_entities(&mut commands, &mut model_loader, &cmd.site) {
+            Ok(site) => site,
+            Err(err) => {
+                commands.entity(err.site).despawn_recursive();
+                error!(
+                    "Failed to load the reference site entities because the file \
+                    had an internal inconsistency:\n{err:#?}",
+                );
+                continue;
+            }
+        };
+
+        commands
+            .entity(site)
+            .remove::<(NameOfSite, WorkspaceMarker, FlattenedOffsetSettings)>()
+            .insert((
+                ReferenceSite {
+                    host: cmd.host,
+                    name: cmd.site.properties.name.0.clone(),
+                    source: cmd.source.clone(),
+                    visible: true,
+                },
+                Pose::default(),
+            ));
+    }
+}
+
 #[derive(Event)]
 pub struct ImportNavGraphs {
     pub into_site: Entity,
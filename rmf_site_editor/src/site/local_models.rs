@@ -0,0 +1,120 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{settings::EditorSettings, site::AssetSource};
+use bevy::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// A model that was found in one of the local model folders.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalModel {
+    pub name: String,
+    /// The folder that contains the `model.sdf` of the model
+    pub folder: PathBuf,
+}
+
+impl LocalModel {
+    pub fn source(&self) -> AssetSource {
+        AssetSource::Local(self.folder.join("model.sdf").to_string_lossy().into_owned())
+    }
+
+    /// The thumbnail of the model, if it has one. Models use the same layout
+    /// as Fuel, where thumbnails are kept in a `thumbnails` subfolder.
+    pub fn thumbnail(&self) -> Option<AssetSource> {
+        let path = self.folder.join("thumbnails").join("1.png");
+        path.exists()
+            .then(|| AssetSource::Local(path.to_string_lossy().into_owned()))
+    }
+}
+
+/// The models that were found in the model folders of the editor settings.
+#[derive(Resource, Default, Debug)]
+pub struct LocalModels {
+    pub models: Vec<LocalModel>,
+}
+
+#[derive(Default)]
+pub struct LocalModelsPlugin {}
+
+impl Plugin for LocalModelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LocalModels>()
+            .add_systems(Update, scan_local_model_folders);
+    }
+}
+
+fn scan_local_model_folders(
+    settings: Option<Res<EditorSettings>>,
+    mut local_models: ResMut<LocalModels>,
+    mut scanned: Local<Option<Vec<PathBuf>>>,
+) {
+    let Some(settings) = settings else {
+        return;
+    };
+    if scanned.as_ref() == Some(&settings.model_folders) {
+        return;
+    }
+
+    let mut models = Vec::new();
+    for folder in &settings.model_folders {
+        find_models(folder, &mut models);
+    }
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+    info!(
+        "Found {} models in {} local model folders",
+        models.len(),
+        settings.model_folders.len(),
+    );
+    local_models.models = models;
+    *scanned = Some(settings.model_folders.clone());
+}
+
+/// Find the models of a folder. The folder may be a model itself or a
+/// collection of models.
+fn find_models(folder: &Path, models: &mut Vec<LocalModel>) {
+    let as_model = |folder: &Path| -> Option<LocalModel> {
+        if !folder.join("model.sdf").exists() {
+            return None;
+        }
+        let name = folder.file_name()?.to_string_lossy().into_owned();
+        Some(LocalModel {
+            name,
+            folder: folder.to_owned(),
+        })
+    };
+
+    if let Some(model) = as_model(folder) {
+        models.push(model);
+        return;
+    }
+
+    let entries = match std::fs::read_dir(folder) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn!("Unable to read model folder {}: {err}", folder.display());
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Some(model) = as_model(&path) {
+                models.push(model);
+            }
+        }
+    }
+}
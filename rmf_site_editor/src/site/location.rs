@@ -27,6 +27,10 @@ pub struct LocationTagMeshes {
     charger: Option<Entity>,
     parking_spot: Option<Entity>,
     holding_point: Option<Entity>,
+    assembly_point: Option<Entity>,
+    dispenser: Option<Entity>,
+    ingestor: Option<Entity>,
+    spawn_point: Option<Entity>,
 }
 
 fn location_halo_tf(tag: &LocationTag) -> Transform {
@@ -35,10 +39,14 @@ fn location_halo_tf(tag: &LocationTag) -> Transform {
         LocationTag::ParkingSpot => 1,
         LocationTag::HoldingPoint => 2,
         LocationTag::Workcell(_) => 3,
+        LocationTag::AssemblyPoint => 4,
+        LocationTag::Dispenser(_) => 5,
+        LocationTag::Ingestor(_) => 6,
+        LocationTag::SpawnPoint(_) => 7,
     };
     Transform {
         translation: Vec3::new(0., 0., 0.01),
-        rotation: Quat::from_rotation_z((position as f32 / 6.0 * 360.0).to_radians()),
+        rotation: Quat::from_rotation_z((position as f32 / 8.0 * 360.0).to_radians()),
         ..default()
     }
 }
@@ -120,6 +128,22 @@ pub fn add_location_visuals(
                     tag_meshes.holding_point = Some(id);
                     assets.holding_point_material.clone()
                 }
+                LocationTag::AssemblyPoint => {
+                    tag_meshes.assembly_point = Some(id);
+                    assets.assembly_point_material.clone()
+                }
+                LocationTag::Dispenser(_) => {
+                    tag_meshes.dispenser = Some(id);
+                    assets.dispenser_material.clone()
+                }
+                LocationTag::Ingestor(_) => {
+                    tag_meshes.ingestor = Some(id);
+                    assets.ingestor_material.clone()
+                }
+                LocationTag::SpawnPoint(_) => {
+                    tag_meshes.spawn_point = Some(id);
+                    assets.spawn_point_material.clone()
+                }
                 // Workcells are not visualized
                 LocationTag::Workcell(_) => continue,
             };
@@ -240,6 +264,30 @@ pub fn update_location_for_changed_location_tags(
                 tag_meshes.holding_point = None;
             }
         }
+        if let Some(id) = tag_meshes.assembly_point {
+            if !tags.iter().any(|t| t.is_assembly_point()) {
+                commands.entity(id).despawn_recursive();
+                tag_meshes.assembly_point = None;
+            }
+        }
+        if let Some(id) = tag_meshes.dispenser {
+            if !tags.iter().any(|t| t.dispenser().is_some()) {
+                commands.entity(id).despawn_recursive();
+                tag_meshes.dispenser = None;
+            }
+        }
+        if let Some(id) = tag_meshes.ingestor {
+            if !tags.iter().any(|t| t.ingestor().is_some()) {
+                commands.entity(id).despawn_recursive();
+                tag_meshes.ingestor = None;
+            }
+        }
+        if let Some(id) = tag_meshes.spawn_point {
+            if !tags.iter().any(|t| t.spawn_point().is_some()) {
+                commands.entity(id).despawn_recursive();
+                tag_meshes.spawn_point = None;
+            }
+        }
         // Spawn the new tags
         for tag in tags.iter() {
             let (id, material) = match tag {
@@ -270,6 +318,42 @@ pub fn update_location_for_changed_location_tags(
                         continue;
                     }
                 }
+                LocationTag::AssemblyPoint => {
+                    if tag_meshes.assembly_point.is_none() {
+                        let id = commands.spawn_empty().id();
+                        tag_meshes.assembly_point = Some(id);
+                        (id, assets.assembly_point_material.clone())
+                    } else {
+                        continue;
+                    }
+                }
+                LocationTag::Dispenser(_) => {
+                    if tag_meshes.dispenser.is_none() {
+                        let id = commands.spawn_empty().id();
+                        tag_meshes.dispenser = Some(id);
+                        (id, assets.dispenser_material.clone())
+                    } else {
+                        continue;
+                    }
+                }
+                LocationTag::Ingestor(_) => {
+                    if tag_meshes.ingestor.is_none() {
+                        let id = commands.spawn_empty().id();
+                        tag_meshes.ingestor = Some(id);
+                        (id, assets.ingestor_material.clone())
+                    } else {
+                        continue;
+                    }
+                }
+                LocationTag::SpawnPoint(_) => {
+                    if tag_meshes.spawn_point.is_none() {
+                        let id = commands.spawn_empty().id();
+                        tag_meshes.spawn_point = Some(id);
+                        (id, assets.spawn_point_material.clone())
+                    } else {
+                        continue;
+                    }
+                }
                 // Workcells are not visualized
                 LocationTag::Workcell(_) => continue,
             };
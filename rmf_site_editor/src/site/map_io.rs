@@ -0,0 +1,258 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+//! Functions for loading, spawning, extracting, and saving maps without any
+//! of the editor's user interface. These are meant for other Bevy-based tools,
+//! such as simulation bringup tools, that need to process site files the same
+//! way that the editor does.
+//!
+//! The world that these functions are used with needs to have [`SitePlugin`]
+//! added so that the spawned elements get their components and assets.
+
+use crate::{
+    site::*,
+    workspace::{FileCompression, WorkspaceData},
+};
+use bevy::{ecs::system::SystemState, prelude::*};
+use rmf_site_format::{legacy::building_map::BuildingMap, LevelTexts, Site};
+use std::path::{Path, PathBuf};
+use thiserror::Error as ThisError;
+
+#[derive(ThisError, Debug)]
+pub enum MapIoError {
+    #[error("unable to access the file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("the file type of {0:?} is not recognized")]
+    UnrecognizedFile(std::path::PathBuf),
+    #[error("unable to parse the map: {0}")]
+    Parse(String),
+    #[error("the map has a broken internal reference: {0}")]
+    BrokenReference(u32),
+    #[error("unable to generate the map from the world: {0}")]
+    Generation(#[from] SiteGenerationError),
+    #[error("unable to write the map: {0}")]
+    Write(String),
+}
+
+/// Read a map from a file. Sites (`.site.ron` and `.site.json`) and legacy
+/// building maps (`.building.yaml`) are supported, and any of them may be
+/// compressed with gzip or zstd by adding a `.gz` or `.zst` suffix. Legacy building maps and the
+/// buildings of traffic-editor projects (`.project.yaml`) are converted into
+/// sites.
+pub fn load_map(path: &Path) -> Result<Site, MapIoError> {
+    let data = std::fs::read(path)?;
+    let Some(data) = WorkspaceData::new(&path.to_path_buf(), data) else {
+        return Err(MapIoError::UnrecognizedFile(path.to_path_buf()));
+    };
+
+    match data {
+        WorkspaceData::LegacyBuilding(data)
+        | WorkspaceData::LegacyProject { building: data, .. } => BuildingMap::from_bytes(&data)
+            .map_err(|err| MapIoError::Parse(err.to_string()))?
+            .to_site()
+            .map_err(|err| MapIoError::Parse(err.to_string())),
+        WorkspaceData::RonSite(data) => {
+            Site::from_bytes_ron(&data).map_err(|err| MapIoError::Parse(err.to_string()))
+        }
+        WorkspaceData::JsonSite(data) => {
+            Site::from_bytes_json(&data).map_err(|err| MapIoError::Parse(err.to_string()))
+        }
+        WorkspaceData::LoadSite(load) => Ok(load.site),
+    }
+}
+
+/// Spawn the entities of a site into the world and return the root entity of
+/// the site. This is what happens when the editor receives a [`LoadSite`]
+/// event, except the site does not become the current workspace. Model assets
+/// keep loading over the next updates of the app.
+pub fn spawn_into_world(world: &mut World, site: &Site) -> Result<Entity, MapIoError> {
+    let mut state: SystemState<(Commands, ModelLoader)> = SystemState::new(world);
+    let (mut commands, mut model_loader) = state.get_mut(world);
+    let result = generate_site_entities(&mut commands, &mut model_loader, site);
+    if let Err(err) = &result {
+        commands.entity(err.site).despawn_recursive();
+    }
+    state.apply(world);
+
+    let site = result.map_err(|err| MapIoError::BrokenReference(err.broken))?;
+    world.send_event(MapLoaded { site });
+    Ok(site)
+}
+
+/// Collect the entities of a site that was spawned into the world back into
+/// a site description. Every element that does not have a site ID yet will
+/// be given one. Relative asset paths are kept as they are.
+pub fn extract_map_from_world(world: &mut World, site: Entity) -> Result<Site, MapIoError> {
+    generate_site(world, site).map_err(MapIoError::from)
+}
+
+/// Write a site into a file. The format is chosen by the extension of the
+/// path: `.json` is written as json and anything else as ron. Paths that end
+/// with `.gz` are compressed with gzip and paths that end with `.zst` with
+/// zstd.
+///
+/// In a web browser there is no file system to write into, so the file is
+/// offered as a download with the file name of the path instead.
+pub fn save_map(site: &Site, path: &Path) -> Result<(), MapIoError> {
+    let data = serialize_map(site, path)?;
+    write_map(path, data)
+}
+
+/// Same as [`save_map`], except that the levels in `reuse` are written with
+/// the text that they had in an earlier save instead of being serialized
+/// again. Returns the text of every level so that it can be reused by the
+/// next save.
+pub fn save_map_reusing_levels(
+    site: &mut Site,
+    path: &Path,
+    reuse: &LevelTexts,
+) -> Result<LevelTexts, MapIoError> {
+    let (data, texts) = serialize_map_reusing_levels(site, path, reuse)?;
+    write_map(path, data)?;
+    Ok(texts)
+}
+
+fn write_map(path: &Path, data: Vec<u8>) -> Result<(), MapIoError> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::fs::write(path, data)?;
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "map.site.ron".to_owned());
+        crate::web::download_file(&name, &data).map_err(MapIoError::Write)?;
+    }
+    Ok(())
+}
+
+/// Serialize a site the same way that [`save_map`] would write it into a
+/// file at `path`.
+pub fn serialize_map(site: &Site, path: &Path) -> Result<Vec<u8>, MapIoError> {
+    let path_str = path.to_string_lossy();
+    let (inner, compression) = FileCompression::split(&path_str);
+    let json = inner.ends_with(".json");
+
+    let mut data = Vec::new();
+    write_site(site, &mut data, json)?;
+    match compression {
+        Some(compression) => Ok(compression.compress(&data)?),
+        None => Ok(data),
+    }
+}
+
+/// Serialize a site the same way that [`save_map_reusing_levels`] would
+/// write it into a file at `path`.
+pub fn serialize_map_reusing_levels(
+    site: &mut Site,
+    path: &Path,
+    reuse: &LevelTexts,
+) -> Result<(Vec<u8>, LevelTexts), MapIoError> {
+    let path_str = path.to_string_lossy();
+    let (inner, compression) = FileCompression::split(&path_str);
+    let (text, texts) = if inner.ends_with(".json") {
+        site.to_string_json_reusing_levels(reuse)
+            .map_err(|err| MapIoError::Write(err.to_string()))?
+    } else {
+        site.to_string_ron_reusing_levels(reuse)
+            .map_err(|err| MapIoError::Write(err.to_string()))?
+    };
+
+    match compression {
+        Some(compression) => Ok((compression.compress(text.as_bytes())?, texts)),
+        None => Ok((text.into_bytes(), texts)),
+    }
+}
+
+/// The path that a backup of `path` is kept in. The most recent backup is
+/// `.bak`, followed by `.bak1`, `.bak2`, and so on.
+pub fn backup_path(path: &Path, index: usize) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    if index == 0 {
+        backup.push(".bak");
+    } else {
+        backup.push(format!(".bak{index}"));
+    }
+    backup.into()
+}
+
+/// The backups of `path` that currently exist, from the most recent to the
+/// oldest.
+pub fn existing_backups(path: &Path) -> Vec<PathBuf> {
+    (0..)
+        .map(|index| backup_path(path, index))
+        .take_while(|backup| backup.is_file())
+        .collect()
+}
+
+/// Keep a copy of the file at `path` before it gets overwritten. Existing
+/// backups are shifted back by one, and the oldest is dropped so that at
+/// most `depth` backups remain. Nothing happens if the file does not exist
+/// yet or `depth` is zero.
+pub fn rotate_backups(path: &Path, depth: usize) -> Result<(), MapIoError> {
+    if depth == 0 || !path.is_file() {
+        return Ok(());
+    }
+
+    let oldest = backup_path(path, depth - 1);
+    if oldest.exists() {
+        std::fs::remove_file(&oldest)?;
+    }
+    for index in (1..depth).rev() {
+        let from = backup_path(path, index - 1);
+        if from.exists() {
+            std::fs::rename(&from, backup_path(path, index))?;
+        }
+    }
+    // Copy instead of renaming so the original stays in place if writing the
+    // new file fails.
+    std::fs::copy(path, backup_path(path, 0))?;
+    Ok(())
+}
+
+fn write_site<W: std::io::Write>(site: &Site, writer: W, json: bool) -> Result<(), MapIoError> {
+    if json {
+        site.to_writer_json(writer)
+            .map_err(|err| MapIoError::Write(err.to_string()))
+    } else {
+        site.to_writer_ron(writer)
+            .map_err(|err| MapIoError::Write(err.to_string()))
+    }
+}
+
+#[test]
+fn test_map_round_trips_through_a_saved_file() {
+    let folder = std::env::temp_dir().join(format!("rmf_site_map_io_{}", std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    let legacy = folder.join("office.building.yaml");
+    std::fs::write(&legacy, crate::demo_world::demo_office()).unwrap();
+
+    let site = load_map(&legacy).unwrap();
+    let saved = folder.join("office.site.ron");
+    save_map(&site, &saved).unwrap();
+    let reloaded = load_map(&saved).unwrap();
+    let saved_again = folder.join("office_again.site.ron");
+    save_map(&reloaded, &saved_again).unwrap();
+
+    let first = std::fs::read(&saved).unwrap();
+    let second = std::fs::read(&saved_again).unwrap();
+    std::fs::remove_dir_all(&folder).ok();
+    assert_eq!(first, second);
+    assert_eq!(reloaded.levels.len(), site.levels.len());
+}
@@ -0,0 +1,110 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+use crate::{
+    settings::EditorSettings,
+    site::{LibraryMaterial, MaterialLibrary},
+};
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// The manifest of materials that ships with the editor.
+const BUILTIN_MATERIALS: &[u8] = include_bytes!("../../../assets/materials/library.yaml");
+
+/// A material of one of the material libraries along with the folder of the
+/// manifest that it came from, which its relative texture paths refer to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AvailableMaterial {
+    pub material: LibraryMaterial,
+    pub manifest_folder: Option<PathBuf>,
+}
+
+/// The materials of the built-in manifest and of every manifest listed in
+/// the editor settings. Materials of later manifests replace materials of
+/// earlier manifests that have the same name.
+#[derive(Resource, Default, Debug)]
+pub struct MaterialLibraries {
+    pub materials: Vec<AvailableMaterial>,
+}
+
+impl MaterialLibraries {
+    pub fn get(&self, name: &str) -> Option<&AvailableMaterial> {
+        self.materials.iter().find(|m| m.material.name == name)
+    }
+
+    fn add(&mut self, library: MaterialLibrary, manifest_folder: Option<PathBuf>) {
+        for material in library.materials {
+            self.materials.retain(|m| m.material.name != material.name);
+            self.materials.push(AvailableMaterial {
+                material,
+                manifest_folder: manifest_folder.clone(),
+            });
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MaterialLibraryPlugin {}
+
+impl Plugin for MaterialLibraryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MaterialLibraries>()
+            .add_systems(Update, load_material_libraries);
+    }
+}
+
+fn load_material_libraries(
+    settings: Option<Res<EditorSettings>>,
+    mut libraries: ResMut<MaterialLibraries>,
+    mut loaded: Local<Option<Vec<PathBuf>>>,
+) {
+    let manifests = settings
+        .map(|s| s.material_libraries.clone())
+        .unwrap_or_default();
+    if loaded.as_ref() == Some(&manifests) {
+        return;
+    }
+
+    let mut new_libraries = MaterialLibraries::default();
+    match MaterialLibrary::from_bytes_yaml(BUILTIN_MATERIALS) {
+        Ok(library) => new_libraries.add(library, None),
+        Err(err) => error!("Unable to parse the built-in material library: {err}"),
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    for manifest in &manifests {
+        let library = std::fs::read(manifest)
+            .map_err(|err| err.to_string())
+            .and_then(|data| {
+                MaterialLibrary::from_bytes_yaml(&data).map_err(|err| err.to_string())
+            });
+        match library {
+            Ok(library) => new_libraries.add(library, manifest.parent().map(Into::into)),
+            Err(err) => warn!(
+                "Unable to load material library {}: {err}",
+                manifest.display()
+            ),
+        }
+    }
+
+    info!(
+        "Loaded {} materials from {} material libraries",
+        new_libraries.materials.len(),
+        manifests.len() + 1,
+    );
+    *libraries = new_libraries;
+    *loaded = Some(manifests);
+}
@@ -0,0 +1,266 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{Hovered, Selected},
+    site::*,
+    AppState,
+};
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
+        view::RenderLayers,
+    },
+};
+use rmf_site_format::{WallAlpha, WallMarker};
+use std::collections::{HashMap, HashSet};
+
+/// Walls whose geometry is drawn by a merged mesh are moved onto this render
+/// layer, which no camera renders. Their own meshes stay in the scene so that
+/// raycasts can still pick out each individual wall.
+pub const MERGED_WALL_SOURCE_LAYER: u8 = 31;
+
+/// Walls of the same level that look the same get drawn by one merged mesh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WallBatchKey {
+    pub level: Entity,
+    pub texture: Option<Entity>,
+    alpha: u32,
+}
+
+/// Marks a mesh that draws every wall of one [`WallBatchKey`].
+#[derive(Component, Debug, Clone, Copy)]
+pub struct MergedWallMesh(pub WallBatchKey);
+
+/// Spawning one mesh and material for every segment of wall becomes slow for
+/// sites with thousands of walls, so the walls of each level are merged into
+/// one mesh per texture. A merged mesh is only rebuilt when one of its walls
+/// changes. Walls that are hovered, selected, or still being drawn are
+/// rendered on their own so their outlines and previews keep working.
+#[derive(Resource, Default, Debug)]
+pub struct MergedWalls {
+    /// The merged mesh entity of each batch
+    meshes: HashMap<WallBatchKey, Entity>,
+    /// The batch that each wall is currently drawn by
+    members: HashMap<Entity, WallBatchKey>,
+    /// Batches whose merged mesh needs to be rebuilt
+    dirty: HashSet<WallBatchKey>,
+    /// Walls that may need to switch between individual and merged rendering
+    restyle: HashSet<Entity>,
+}
+
+#[derive(Default)]
+pub struct MergedWallsPlugin;
+
+impl Plugin for MergedWallsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MergedWalls>().add_systems(
+            PostUpdate,
+            (
+                assign_wall_batches,
+                rebuild_merged_walls,
+                update_wall_render_layers,
+            )
+                .chain()
+                .after(SiteUpdateSet::BetweenVisibilityAndTransformFlush)
+                .run_if(AppState::in_displaying_mode()),
+        );
+    }
+}
+
+fn assign_wall_batches(
+    mut merged: ResMut<MergedWalls>,
+    walls: Query<
+        (&Parent, &Affiliation<Entity>, &WallAlpha, &Visibility),
+        (With<WallMarker>, With<Handle<Mesh>>, Without<Pending>),
+    >,
+    changed_walls: Query<
+        Entity,
+        (
+            With<WallMarker>,
+            Or<(
+                Changed<Handle<Mesh>>,
+                Changed<Parent>,
+                Changed<Affiliation<Entity>>,
+                Changed<WallAlpha>,
+                Changed<Visibility>,
+            )>,
+        ),
+    >,
+    mut removed_walls: RemovedComponents<WallMarker>,
+    mut removed_pending: RemovedComponents<Pending>,
+) {
+    for e in removed_walls.read() {
+        if let Some(key) = merged.members.remove(&e) {
+            merged.dirty.insert(key);
+        }
+    }
+
+    let changed: HashSet<Entity> = changed_walls.iter().chain(removed_pending.read()).collect();
+    for e in changed {
+        let key = walls
+            .get(e)
+            .ok()
+            .filter(|(_, _, _, visibility)| **visibility != Visibility::Hidden)
+            .map(|(parent, texture, alpha, _)| WallBatchKey {
+                level: parent.get(),
+                texture: texture.0,
+                alpha: alpha.0.to_bits(),
+            });
+
+        let previous = match key {
+            Some(key) => merged.members.insert(e, key),
+            None => merged.members.remove(&e),
+        };
+        merged.dirty.extend(previous.into_iter().chain(key));
+        merged.restyle.insert(e);
+    }
+}
+
+fn rebuild_merged_walls(
+    mut commands: Commands,
+    mut merged: ResMut<MergedWalls>,
+    walls: Query<(&Handle<Mesh>, &Handle<StandardMaterial>), With<WallMarker>>,
+    mut merged_meshes: Query<
+        (&mut Handle<Mesh>, &mut Handle<StandardMaterial>),
+        (With<MergedWallMesh>, Without<WallMarker>),
+    >,
+    levels: Query<(), With<LevelElevation>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    if merged.dirty.is_empty() {
+        return;
+    }
+
+    let dirty: Vec<WallBatchKey> = merged.dirty.drain().collect();
+    for key in dirty {
+        let members: Vec<Entity> = merged
+            .members
+            .iter()
+            .filter(|(_, k)| **k == key)
+            .map(|(e, _)| *e)
+            .collect();
+        let material = members
+            .iter()
+            .find_map(|e| walls.get(*e).ok())
+            .map(|(_, material)| material.clone());
+        let mesh = merge_meshes(
+            members
+                .iter()
+                .filter_map(|e| walls.get(*e).ok())
+                .filter_map(|(mesh, _)| meshes.get(mesh)),
+        );
+
+        let (Some(mesh), Some(material), true) = (mesh, material, levels.contains(key.level))
+        else {
+            if let Some(e) = merged.meshes.remove(&key) {
+                if let Some(e_mut) = commands.get_entity(e) {
+                    e_mut.despawn_recursive();
+                }
+            }
+            continue;
+        };
+
+        let mesh = meshes.add(mesh);
+        if let Some(existing) = merged.meshes.get(&key) {
+            if let Ok((mut current_mesh, mut current_material)) = merged_meshes.get_mut(*existing) {
+                *current_mesh = mesh;
+                *current_material = material;
+                continue;
+            }
+        }
+
+        let e = commands
+            .spawn(PbrBundle {
+                mesh,
+                material,
+                ..default()
+            })
+            .insert(MergedWallMesh(key))
+            .set_parent(key.level)
+            .id();
+        merged.meshes.insert(key, e);
+    }
+}
+
+fn update_wall_render_layers(
+    mut commands: Commands,
+    mut merged: ResMut<MergedWalls>,
+    walls: Query<(Option<&Hovered>, Option<&Selected>), With<WallMarker>>,
+    cued_walls: Query<Entity, (With<WallMarker>, Or<(Changed<Hovered>, Changed<Selected>)>)>,
+) {
+    let restyle: Vec<Entity> = merged.restyle.drain().chain(&cued_walls).collect();
+    for e in restyle {
+        let Ok((hovered, selected)) = walls.get(e) else {
+            continue;
+        };
+        let cued = hovered.is_some_and(|h| h.cue()) || selected.is_some_and(|s| s.cue());
+        let layers = if merged.members.contains_key(&e) && !cued {
+            RenderLayers::layer(MERGED_WALL_SOURCE_LAYER)
+        } else {
+            RenderLayers::default()
+        };
+        commands.entity(e).insert(layers);
+    }
+}
+
+/// Combine triangle meshes into one. Returns None if there is nothing to
+/// combine.
+fn merge_meshes<'a>(sources: impl Iterator<Item = &'a Mesh>) -> Option<Mesh> {
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+    let mut uvs: Vec<[f32; 2]> = Vec::new();
+    let mut indices: Vec<u32> = Vec::new();
+    for source in sources {
+        let (Some(VertexAttributeValues::Float32x3(p)), Some(VertexAttributeValues::Float32x3(n))) = (
+            source.attribute(Mesh::ATTRIBUTE_POSITION),
+            source.attribute(Mesh::ATTRIBUTE_NORMAL),
+        ) else {
+            continue;
+        };
+        if p.len() != n.len() {
+            continue;
+        }
+
+        let offset = positions.len() as u32;
+        match source.indices() {
+            Some(Indices::U32(i)) => indices.extend(i.iter().map(|i| i + offset)),
+            Some(Indices::U16(i)) => indices.extend(i.iter().map(|i| *i as u32 + offset)),
+            None => indices.extend((0..p.len() as u32).map(|i| i + offset)),
+        }
+        match source.attribute(Mesh::ATTRIBUTE_UV_0) {
+            Some(VertexAttributeValues::Float32x2(uv)) if uv.len() == p.len() => {
+                uvs.extend_from_slice(uv)
+            }
+            _ => uvs.extend(std::iter::repeat([0.0, 0.0]).take(p.len())),
+        }
+        positions.extend_from_slice(p);
+        normals.extend_from_slice(n);
+    }
+
+    if indices.is_empty() {
+        return None;
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    Some(mesh)
+}
@@ -21,12 +21,33 @@ pub use anchor::*;
 pub mod assets;
 pub use assets::*;
 
+pub mod camera_bookmark;
+pub use camera_bookmark::*;
+
 pub mod change_plugin;
 pub use change_plugin::*;
 
+pub mod clipboard;
+pub use clipboard::*;
+
+pub mod connectivity;
+pub use connectivity::*;
+
+pub mod cross_section;
+pub use cross_section::*;
+
+pub mod crowd_preview;
+pub use crowd_preview::*;
+
+pub mod custom_entity;
+pub use custom_entity::*;
+
 pub mod deletion;
 pub use deletion::*;
 
+pub mod digital_twin_exporter;
+pub use digital_twin_exporter::*;
+
 pub mod display_color;
 pub use display_color::*;
 
@@ -36,9 +57,21 @@ pub use drawing_editor::{alignment, *};
 pub mod door;
 pub use door::*;
 
+pub mod doorway;
+pub use doorway::*;
+
 pub mod drawing;
 pub use drawing::*;
 
+pub mod entity_group;
+pub use entity_group::*;
+
+pub mod evacuation;
+pub use evacuation::*;
+
+pub mod extension;
+pub use extension::*;
+
 pub mod fiducial;
 pub use fiducial::*;
 
@@ -57,9 +90,30 @@ pub use group::*;
 pub mod lane;
 pub use lane::*;
 
+pub mod lane_arrows;
+pub use lane_arrows::*;
+
+pub mod lane_curve;
+pub use lane_curve::*;
+
+pub mod lane_density;
+pub use lane_density::*;
+
+pub mod lane_generation;
+pub use lane_generation::*;
+
+pub mod lane_split;
+pub use lane_split::*;
+
+pub mod layer_toggle;
+pub use layer_toggle::*;
+
 pub mod level;
 pub use level::*;
 
+pub mod level_culling;
+pub use level_culling::*;
+
 pub mod lift;
 pub use lift::*;
 
@@ -69,30 +123,57 @@ pub use light::*;
 pub mod load;
 pub use load::*;
 
+pub mod local_models;
+pub use local_models::*;
+
 pub mod location;
 pub use location::*;
 
+pub mod map_io;
+pub use map_io::*;
+
+pub mod material_library;
+pub use material_library::*;
+
 pub mod measurement;
 pub use measurement::*;
 
+pub mod merged_walls;
+pub use merged_walls::*;
+
 pub mod model;
 pub use model::*;
 
 pub mod nav_graph;
 pub use nav_graph::*;
 
+pub mod orphans;
+pub use orphans::*;
+
 pub mod path;
 pub use path::*;
 
+pub mod path_preview;
+pub use path_preview::*;
+
 pub mod physical_camera;
 pub use physical_camera::*;
 
+pub mod point_alignment;
+pub use point_alignment::*;
+
 pub mod pose;
 pub use pose::*;
 
 pub mod primitive_shape;
 pub use primitive_shape::*;
 
+pub mod reference;
+pub use reference::*;
+
+pub mod robot_spawn;
+pub use robot_spawn::*;
+
 pub mod recall_plugin;
 pub use recall_plugin::RecallPlugin;
 
@@ -102,27 +183,62 @@ pub use save::*;
 pub mod scenario;
 pub use scenario::*;
 
+#[cfg(feature = "scripting")]
+pub mod script;
+#[cfg(feature = "scripting")]
+pub use script::*;
+
 pub mod sdf_exporter;
 pub use sdf_exporter::*;
 
 pub mod site;
 pub use site::*;
 
+pub mod site_events;
+pub use site_events::*;
+
 pub mod site_visualizer;
 pub use site_visualizer::*;
 
+pub mod spawner;
+pub use spawner::*;
+
+pub mod survey;
+pub use survey::*;
+
+pub mod template;
+pub use template::*;
+
 pub mod texture;
 pub use texture::*;
 
+pub mod thumbnail;
+pub use thumbnail::*;
+
+pub mod traffic_preview;
+pub use traffic_preview::*;
+
 pub mod util;
 pub use util::*;
 
+pub mod vertex_cleanup;
+pub use vertex_cleanup::*;
+
 pub mod view_menu;
 pub use view_menu::*;
 
+pub mod walkthrough;
+pub use walkthrough::*;
+
 pub mod wall;
 pub use wall::*;
 
+pub mod yaml_source;
+pub use yaml_source::*;
+
+pub mod zone;
+pub use zone::*;
+
 use crate::recency::{RecencyRank, RecencyRankingPlugin};
 use crate::{AppState, RegisterIssueType};
 pub use rmf_site_format::{DirectionalLight, PointLight, SpotLight, Style, *};
@@ -194,6 +310,7 @@ impl Plugin for SitePlugin {
         .init_resource::<SiteAssets>()
         .init_resource::<CurrentLevel>()
         .init_resource::<CurrentScenario>()
+        .init_resource::<LevelChangeTracker>()
         .init_resource::<PhysicalLightToggle>()
         .add_event::<LoadSite>()
         .add_event::<ImportNavGraphs>()
@@ -204,10 +321,20 @@ impl Plugin for SitePlugin {
         .add_event::<UpdateInstanceEvent>()
         .add_event::<SaveSite>()
         .add_event::<SaveNavGraphs>()
+        .add_event::<ExportPerturbedNavGraphs>()
+        .add_event::<ExportPlan>()
+        .add_event::<ExportOccupancyGrid>()
+        .add_event::<ExportEvacuation>()
+        .add_event::<ExportGeoJson>()
+        .add_event::<ExportSelection>()
         .add_event::<ExportLights>()
         .add_event::<ConsiderAssociatedGraph>()
         .add_event::<ConsiderLocationTag>()
         .add_event::<MergeGroups>()
+        .add_event::<AddLevel>()
+        .add_event::<DuplicateLevel>()
+        .add_event::<SetHumanLanes>()
+        .add_event::<ReverseLanes>()
         .add_plugins((
             ChangePlugin::<AssociatedGraphs<Entity>>::default(),
             RecallPlugin::<RecallAssociatedGraphs<Entity>>::default(),
@@ -260,17 +387,75 @@ impl Plugin for SitePlugin {
             ChangePlugin::<ModelProperty<AssetSource>>::default(),
             ChangePlugin::<ModelProperty<Scale>>::default(),
             ChangePlugin::<ModelProperty<IsStatic>>::default(),
+            ChangePlugin::<ModelProperty<ZOffset>>::default(),
             RecallPlugin::<RecallInstance>::default(),
+            ReferenceSitePlugin,
+            ClipboardPlugin,
+            TemplatePlugin,
+            ChangePlugin::<PaperSpace>::default(),
+            ChangePlugin::<ExportSettings>::default(),
+            ChangePlugin::<FlattenedOffset>::default(),
+            ChangePlugin::<CrowdSim<Entity>>::default(),
+            ChangePlugin::<LiftLevelElevations<Entity>>::default(),
+            SiteEventsPlugin,
+            LaneDensityPlugin,
+        ))
+        .add_plugins((
+            LaneArrowsPlugin,
+            ZonePlugin,
+            RobotSpawnPlugin,
+            MergedWallsPlugin,
+            SurveyPlugin,
+            WalkthroughPlugin,
+            CameraBookmarkPlugin,
+            LevelCullingPlugin,
+            DoorwayPlugin,
+            #[cfg(feature = "scripting")]
+            SiteScriptPlugin,
+            ThumbnailPlugin::default(),
+            MaterialLibraryPlugin::default(),
+            ChangePlugin::<WallHeight>::default(),
+            ChangePlugin::<WallAlpha>::default(),
+            ChangePlugin::<DrawingSourceInfo>::default(),
+        ))
+        .add_plugins((
+            CrowdPreviewPlugin,
+            ChangePlugin::<EntityGroups<Entity>>::default(),
+            EntityGroupPlugin,
+            YamlSourcePlugin,
+            TrafficPreviewPlugin,
+            ChangePlugin::<Rectification>::default(),
+            PathPreviewPlugin,
+            ConnectivityPlugin,
+            CrossSectionPlugin,
+            ChangePlugin::<DoorTiming>::default(),
+            ChangePlugin::<LiftTiming>::default(),
+            EvacuationPlugin,
+            ChangePlugin::<GeographicReferences>::default(),
+            CustomEntityPlugin,
+            LocalModelsPlugin::default(),
+        ))
+        .add_plugins((
+            LayerTogglePlugin,
+            LaneSplitPlugin,
+            LaneCurvePlugin,
+            LaneGenerationPlugin,
+            VertexCleanupPlugin,
+            PointAlignmentPlugin,
+            GeographicVerticesPlugin,
+            ChangePlugin::<EntityTags>::default(),
         ))
         .add_issue_type(&DUPLICATED_DOOR_NAME_ISSUE_UUID, "Duplicate door name")
         .add_issue_type(&DUPLICATED_LIFT_NAME_ISSUE_UUID, "Duplicate lift name")
-        .add_issue_type(
+        .add_level_issue_type(
             &FIDUCIAL_WITHOUT_AFFILIATION_ISSUE_UUID,
             "Fiducial without affiliation",
         )
         .add_issue_type(&DUPLICATED_DOCK_NAME_ISSUE_UUID, "Duplicated dock name")
-        .add_issue_type(&UNCONNECTED_ANCHORS_ISSUE_UUID, "Unconnected anchors")
+        .add_level_issue_type(&UNCONNECTED_ANCHORS_ISSUE_UUID, "Unconnected anchors")
+        .add_level_issue_type(&LANE_CROSSES_DOOR_ISSUE_UUID, "Lane crossing door")
         .add_systems(Update, (load_site, import_nav_graph))
+        .add_systems(Update, track_level_changes.before(save_site))
         .add_systems(
             PreUpdate,
             (
@@ -281,6 +466,7 @@ impl Plugin for SitePlugin {
                 check_for_duplicated_door_names,
                 check_for_duplicated_lift_names,
                 check_for_duplicated_dock_names,
+                check_for_lanes_crossing_doors,
                 check_for_fiducials_without_affiliation,
                 check_for_close_unconnected_anchors,
                 check_for_orphan_model_instances,
@@ -299,7 +485,20 @@ impl Plugin for SitePlugin {
         )
         .add_systems(
             Update,
-            (save_site, save_nav_graphs, change_site.before(load_site))
+            (
+                save_site,
+                save_nav_graphs,
+                export_perturbed_nav_graphs,
+                export_plan,
+                export_occupancy_grid,
+                export_evacuation,
+                export_geojson,
+                export_selection,
+                change_site.before(load_site),
+                add_levels,
+                duplicate_level,
+                update_flattened_offsets,
+            )
                 .run_if(AppState::in_displaying_mode()),
         )
         .add_systems(
@@ -374,6 +573,8 @@ impl Plugin for SitePlugin {
                 add_unused_fiducial_tracker,
                 update_fiducial_usage_tracker,
                 update_visibility_for_lanes.after(remove_association_for_deleted_graphs),
+                update_human_lane_visuals.after(update_visibility_for_lanes),
+                (handle_set_human_lanes, handle_reverse_lanes),
                 update_visibility_for_locations.after(remove_association_for_deleted_graphs),
                 update_changed_location,
                 update_location_for_moved_anchors,
@@ -399,12 +600,15 @@ impl Plugin for SitePlugin {
                 update_model_instances::<AssetSource>,
                 update_model_instances::<Scale>,
                 update_model_instances::<IsStatic>,
+                update_model_instances::<ZOffset>,
                 update_affiliations,
                 update_members_of_groups.after(update_affiliations),
                 update_model_scales,
                 handle_new_primitive_shapes,
                 add_drawing_visuals,
                 handle_loaded_drawing,
+                update_drawing_rectification.after(handle_loaded_drawing),
+                check_drawing_source_info.after(handle_loaded_drawing),
                 update_drawing_rank,
                 add_physical_camera_visuals,
             )
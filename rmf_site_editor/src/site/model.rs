@@ -16,10 +16,10 @@
 */
 
 use crate::{
-    interaction::{DragPlaneBundle, Preview, MODEL_PREVIEW_LAYER},
+    interaction::{DragPlaneBundle, ModelGizmoRoot, Preview, MODEL_PREVIEW_LAYER},
     site::SiteAssets,
     site_asset_io::MODEL_ENVIRONMENT_VARIABLE,
-    Issue, ValidateWorkspace,
+    Issue, ValidationRequests,
 };
 use bevy::{
     ecs::system::{EntityCommands, SystemParam},
@@ -293,6 +293,11 @@ struct SpawnModelLabel(Entity);
 #[derive(Component, Deref, DerefMut)]
 pub struct ModelLoadingState(Promise<ModelLoadingResult>);
 
+/// Component added to models while their asset is being fetched, pointing to
+/// the box that stands in for the model until it is ready.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ModelLoadingPlaceholder(pub Entity);
+
 /// Component added to models that failed loading and containing the reason loading failed.
 #[derive(Component, Deref, DerefMut)]
 pub struct ModelFailedLoading(ModelLoadingError);
@@ -302,6 +307,7 @@ pub struct ModelFailedLoading(ModelLoadingError);
 fn handle_model_loading_errors(
     In(result): In<ModelLoadingResult>,
     model_scenes: Query<&ModelScene>,
+    placeholders: Query<&ModelLoadingPlaceholder>,
     mut commands: Commands,
 ) -> ModelLoadingResult {
     let parent = match result {
@@ -324,14 +330,56 @@ fn handle_model_loading_errors(
         }
     };
 
+    if let Ok(placeholder) = placeholders.get(parent) {
+        if let Some(entity_mut) = commands.get_entity(placeholder.0) {
+            entity_mut.despawn_recursive();
+        }
+    }
     if let Some(mut entity_mut) = commands.get_entity(parent) {
         // The parent entity might not exist any longer after the loading failed,
         // so we check for its existence before removing from it.
-        entity_mut.remove::<ModelLoadingState>();
+        entity_mut.remove::<(ModelLoadingState, ModelLoadingPlaceholder)>();
     }
     result
 }
 
+/// Show a translucent box in place of a model whose asset may need to be
+/// downloaded, so users can see where it is while they wait.
+fn spawn_loading_placeholder(
+    In(request): In<ModelLoadingRequest>,
+    mut commands: Commands,
+    site_assets: Res<SiteAssets>,
+    previews: Query<(), Or<(With<Pending>, With<Preview>)>>,
+    render_layers: Query<&RenderLayers>,
+    placeholders: Query<(), With<ModelLoadingPlaceholder>>,
+) -> ModelLoadingRequest {
+    let remote = matches!(
+        request.source,
+        AssetSource::Remote(_) | AssetSource::Search(_)
+    );
+    if !remote || previews.contains(request.parent) || placeholders.contains(request.parent) {
+        return request;
+    }
+    if commands.get_entity(request.parent).is_none() {
+        return request;
+    }
+    let mut placeholder = commands.spawn(PbrBundle {
+        mesh: site_assets.box_mesh.clone(),
+        material: site_assets.translucent_white.clone(),
+        transform: Transform::from_xyz(0.0, 0.0, 0.25).with_scale(Vec3::splat(0.5)),
+        ..default()
+    });
+    if let Ok(layers) = render_layers.get(request.parent) {
+        placeholder.insert(*layers);
+    }
+    let placeholder = placeholder.id();
+    commands
+        .entity(request.parent)
+        .insert(ModelLoadingPlaceholder(placeholder))
+        .add_child(placeholder);
+    request
+}
+
 fn instance_spawn_request_into_model_load_request(
     In(request): In<InstanceSpawningRequest>,
     descriptions: Query<&ModelProperty<AssetSource>>,
@@ -512,6 +560,7 @@ impl ModelLoadingServices {
                     .chain(builder)
                     .then(skip_if_unchanged)
                     .branch_for_err(|res| res.connect(scope.terminate))
+                    .then(spawn_loading_placeholder.into_blocking_callback())
                     .then(model_loading_service)
                     .connect_on_err(scope.terminate)
                     .then(load_model_dependencies)
@@ -664,6 +713,7 @@ pub fn make_models_selectable(
     In(req): In<ModelLoadingRequest>,
     mut commands: Commands,
     pending_or_previews: Query<(), Or<(With<Pending>, With<Preview>)>>,
+    gizmo_roots: Query<(), With<ModelGizmoRoot>>,
     scene_roots: Query<&RenderLayers, With<ModelMarker>>,
     all_children: Query<&Children>,
     mesh_handles: Query<&Handle<Mesh>>,
@@ -704,7 +754,9 @@ pub fn make_models_selectable(
 
         if let Ok(children) = all_children.get(e) {
             for child in children {
-                queue.push(*child);
+                if !gizmo_roots.contains(*child) {
+                    queue.push(*child);
+                }
             }
         }
     }
@@ -800,14 +852,14 @@ pub const ORPHAN_MODEL_INSTANCE_ISSUE_UUID: Uuid =
 
 pub fn check_for_orphan_model_instances(
     mut commands: Commands,
-    mut validate_events: EventReader<ValidateWorkspace>,
+    mut validate_events: ValidationRequests,
     mut orphan_instances: Query<
         (Entity, &NameInSite, &Affiliation<Entity>),
         (With<ModelMarker>, Without<Group>, Without<Parent>),
     >,
     model_descriptions: Query<&NameInSite, (With<ModelMarker>, With<Group>)>,
 ) {
-    for root in validate_events.read() {
+    for root in validate_events.roots() {
         for (instance_entity, instance_name, affiliation) in orphan_instances.iter_mut() {
             let brief = match affiliation
                 .0
@@ -835,7 +887,7 @@ pub fn check_for_orphan_model_instances(
                     .to_string(),
             };
             let issue_id = commands.spawn(issue).id();
-            commands.entity(**root).add_child(issue_id);
+            commands.entity(root).add_child(issue_id);
         }
     }
 }
@@ -0,0 +1,123 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::site::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use std::collections::HashSet;
+
+/// Elements of a site that nothing refers to anymore. These tend to pile up
+/// over months of editing a map.
+#[derive(Debug, Clone, Default)]
+pub struct Orphans {
+    /// Anchors that are not used by any lane, wall, measurement, fiducial,
+    /// or any other element of the site
+    pub anchors: Vec<Entity>,
+    /// Drawings that are not placed on any level
+    pub drawings: Vec<Entity>,
+    /// Textures that are not used by any wall or floor
+    pub textures: Vec<Entity>,
+}
+
+impl Orphans {
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty() && self.drawings.is_empty() && self.textures.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.anchors.len() + self.drawings.len() + self.textures.len()
+    }
+}
+
+#[derive(SystemParam)]
+pub struct FindOrphans<'w, 's> {
+    anchors: Query<'w, 's, (Entity, Option<&'static Dependents>), (With<Anchor>, Without<Pending>)>,
+    drawings: Query<'w, 's, Entity, (With<DrawingMarker>, Without<Pending>)>,
+    textures: Query<
+        'w,
+        's,
+        (Entity, Option<&'static Members>),
+        (With<Texture>, With<Group>, Without<Pending>),
+    >,
+    levels: Query<'w, 's, (), With<LevelElevation>>,
+    cabin_anchor_groups: Query<'w, 's, (), With<CabinAnchorGroup>>,
+    crowd_sims: Query<'w, 's, &'static CrowdSim<Entity>>,
+    parents: Query<'w, 's, &'static Parent>,
+}
+
+impl<'w, 's> FindOrphans<'w, 's> {
+    pub fn find(&self, site: Entity) -> Orphans {
+        let in_site = |e: Entity| AncestorIter::new(&self.parents, e).any(|a| a == site);
+
+        // Goals of the crowd simulation refer to anchors without being their
+        // dependents.
+        let goals: HashSet<Entity> = self
+            .crowd_sims
+            .get(site)
+            .map(|crowd_sim| {
+                crowd_sim
+                    .goal_sets
+                    .iter()
+                    .flat_map(|g| g.anchors.iter().copied())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut anchors: Vec<Entity> = self
+            .anchors
+            .iter()
+            .filter(|(e, deps)| {
+                deps.map_or(true, |d| d.is_empty())
+                    && !goals.contains(e)
+                    // Lift cabins always keep their own anchors
+                    && !self
+                        .parents
+                        .get(*e)
+                        .is_ok_and(|p| self.cabin_anchor_groups.contains(p.get()))
+                    && in_site(*e)
+            })
+            .map(|(e, _)| e)
+            .collect();
+        anchors.sort();
+
+        let mut drawings: Vec<Entity> = self
+            .drawings
+            .iter()
+            .filter(|e| {
+                in_site(*e)
+                    && !self
+                        .parents
+                        .get(*e)
+                        .is_ok_and(|p| self.levels.contains(p.get()))
+            })
+            .collect();
+        drawings.sort();
+
+        let mut textures: Vec<Entity> = self
+            .textures
+            .iter()
+            .filter(|(e, members)| members.map_or(true, |m| m.is_empty()) && in_site(*e))
+            .map(|(e, _)| e)
+            .collect();
+        textures.sort();
+
+        Orphans {
+            anchors,
+            drawings,
+            textures,
+        }
+    }
+}
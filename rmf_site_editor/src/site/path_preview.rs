@@ -0,0 +1,283 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, AppState};
+use bevy::prelude::*;
+
+/// How high above the floor the planned path is drawn
+const PATH_PREVIEW_HEIGHT: f32 = 0.1;
+
+/// The outcome of planning a path between two waypoints.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlannedPath {
+    Found {
+        /// Every waypoint of the path, including the start and the goal
+        waypoints: Vec<Entity>,
+        /// Total length of the path, in meters
+        length: f32,
+        /// Estimated time to drive the path, in seconds. This includes the
+        /// time spent waiting for doors to open.
+        duration: f32,
+        /// Doors that the path passes through, in order
+        doors: Vec<Entity>,
+    },
+    /// The goal cannot be reached from the start by following the lanes
+    Unreachable,
+}
+
+/// The start and goal of the path that is being previewed. The path is
+/// planned over the robot lanes of the current level, following the
+/// direction of each lane.
+#[derive(Resource)]
+pub struct PathPreview {
+    pub start: Option<Entity>,
+    pub goal: Option<Entity>,
+    /// Only plan over the lanes of this nav graph. When this is None, every
+    /// lane of the level is used.
+    pub nav_graph: Option<Entity>,
+    /// How fast the robot drives where lanes do not limit it, in meters per
+    /// second
+    pub robot_speed: f32,
+    /// The latest planned path. This is kept up to date as the lanes change.
+    pub path: Option<PlannedPath>,
+}
+
+impl Default for PathPreview {
+    fn default() -> Self {
+        Self {
+            start: None,
+            goal: None,
+            nav_graph: None,
+            robot_speed: 0.7,
+            path: None,
+        }
+    }
+}
+
+/// Plan and highlight the shortest path between two waypoints so users can
+/// check that their lanes connect the way they intended.
+#[derive(Default)]
+pub struct PathPreviewPlugin;
+
+impl Plugin for PathPreviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PathPreview>().add_systems(
+            Update,
+            (update_path_preview, draw_path_preview)
+                .chain()
+                .run_if(AppState::in_displaying_mode()),
+        );
+    }
+}
+
+fn update_path_preview(
+    mut preview: ResMut<PathPreview>,
+    current_level: Res<CurrentLevel>,
+    lanes: TrafficLanes,
+    anchors: Query<(&Anchor, &Parent)>,
+    changed_lanes: Query<
+        (),
+        (
+            With<LaneMarker>,
+            Or<(
+                Changed<Edge<Entity>>,
+                Changed<Motion>,
+                Changed<ReverseLane>,
+                Changed<AssociatedGraphs<Entity>>,
+            )>,
+        ),
+    >,
+    changed_anchors: Query<(), Changed<Anchor>>,
+    mut removed_lanes: RemovedComponents<LaneMarker>,
+    doors: Query<(Entity, &Edge<Entity>, &DoorTiming, &Parent), With<DoorMarker>>,
+    changed_doors: Query<
+        (),
+        (
+            With<DoorMarker>,
+            Or<(Changed<Edge<Entity>>, Changed<DoorTiming>)>,
+        ),
+    >,
+) {
+    let lanes_removed = removed_lanes.read().count() > 0;
+    if !preview.is_changed()
+        && !current_level.is_changed()
+        && changed_lanes.is_empty()
+        && changed_anchors.is_empty()
+        && changed_doors.is_empty()
+        && !lanes_removed
+    {
+        return;
+    }
+
+    let path = match (preview.start, preview.goal, current_level.0) {
+        (Some(start), Some(goal), Some(level)) => Some(plan_path(
+            level, start, goal, &preview, &lanes, &anchors, &doors,
+        )),
+        _ => None,
+    };
+    // Writing the result should not make the path get planned again
+    if preview.path != path {
+        preview.bypass_change_detection().path = path;
+    }
+}
+
+fn plan_path(
+    level: Entity,
+    start: Entity,
+    goal: Entity,
+    preview: &PathPreview,
+    lanes: &TrafficLanes,
+    anchors: &Query<(&Anchor, &Parent)>,
+    doors: &Query<(Entity, &Edge<Entity>, &DoorTiming, &Parent), With<DoorMarker>>,
+) -> PlannedPath {
+    let traffic = LevelTrafficGraph::new(level, preview.nav_graph, lanes, anchors);
+    let (Some(from), Some(to)) = (traffic.nodes.get(&start), traffic.nodes.get(&goal)) else {
+        return PlannedPath::Unreachable;
+    };
+    let Some(route) = traffic.graph.shortest_route(*from, *to) else {
+        return PlannedPath::Unreachable;
+    };
+
+    let length = traffic.graph.route_length(*from, &route);
+    let duration = traffic
+        .graph
+        .route_duration(*from, &route, preview.robot_speed);
+    let waypoints: Vec<Entity> = std::iter::once(start)
+        .chain(route.iter().map(|i| traffic.waypoints[*i]))
+        .collect();
+
+    // Robots have to wait for each door along the way to open
+    let position = |anchor: Entity| -> Option<Vec2> {
+        let p = anchors
+            .get(anchor)
+            .ok()?
+            .0
+            .translation_for_category(Category::General);
+        Some(Vec2::from(p))
+    };
+    let mut passed_doors = Vec::new();
+    let mut door_delay = 0.0;
+    for pair in waypoints.windows(2) {
+        let (Some(p0), Some(p1)) = (position(pair[0]), position(pair[1])) else {
+            continue;
+        };
+        let segment = SectionLine::new(p0, p1);
+        let mut crossed: Vec<(f32, Entity, f32)> = doors
+            .iter()
+            .filter(|(_, _, _, parent)| parent.get() == level)
+            .filter_map(|(door, edge, timing, _)| {
+                let d = segment.crossing(position(edge.start())?, position(edge.end())?)?;
+                Some((d, door, timing.passage_delay()))
+            })
+            .collect();
+        crossed.sort_by(|a, b| a.0.total_cmp(&b.0));
+        for (_, door, delay) in crossed {
+            passed_doors.push(door);
+            door_delay += delay;
+        }
+    }
+
+    PlannedPath::Found {
+        waypoints,
+        length,
+        duration: duration + door_delay,
+        doors: passed_doors,
+    }
+}
+
+fn draw_path_preview(
+    preview: Res<PathPreview>,
+    transforms: Query<&GlobalTransform>,
+    mut gizmos: Gizmos,
+) {
+    let position = |e: Entity| -> Option<Vec3> {
+        let p = transforms.get(e).ok()?.translation();
+        Some(p + Vec3::Z * PATH_PREVIEW_HEIGHT)
+    };
+
+    for (anchor, color) in [(preview.start, Color::GREEN), (preview.goal, Color::RED)] {
+        if let Some(p) = anchor.and_then(position) {
+            gizmos.circle(p, Vec3::Z, 0.3, color);
+        }
+    }
+
+    if let Some(PlannedPath::Found { waypoints, .. }) = &preview.path {
+        gizmos.linestrip(waypoints.iter().filter_map(|e| position(*e)), Color::CYAN);
+    }
+}
+
+#[test]
+fn test_plan_path_follows_lane_directions() {
+    use bevy::ecs::system::SystemState;
+
+    let mut world = World::new();
+    let site = world.spawn_empty().id();
+    let level = world.spawn_empty().set_parent(site).id();
+    let anchors: Vec<Entity> = [[0.0, 0.0], [3.0, 0.0], [3.0, 4.0]]
+        .into_iter()
+        .map(|p| world.spawn(Anchor::Translate2D(p)).set_parent(level).id())
+        .collect();
+    for pair in anchors.windows(2) {
+        world
+            .spawn((
+                LaneMarker,
+                Edge::new(pair[0], pair[1]),
+                Motion::default(),
+                ReverseLane::Disable,
+                AssociatedGraphs::<Entity>::All,
+            ))
+            .set_parent(site);
+    }
+
+    let mut state: SystemState<(
+        TrafficLanes,
+        Query<(&Anchor, &Parent)>,
+        Query<(Entity, &Edge<Entity>, &DoorTiming, &Parent), With<DoorMarker>>,
+    )> = SystemState::new(&mut world);
+    let (lanes, anchor_query, doors) = state.get(&world);
+    let preview = PathPreview::default();
+
+    let path = plan_path(
+        level,
+        anchors[0],
+        anchors[2],
+        &preview,
+        &lanes,
+        &anchor_query,
+        &doors,
+    );
+    let PlannedPath::Found {
+        waypoints, length, ..
+    } = path.clone()
+    else {
+        panic!("Expected a path to be found, got {path:?}");
+    };
+    assert_eq!(waypoints, anchors);
+    assert!((length - 7.0).abs() < 1e-5);
+
+    // The lanes are one-way, so there is no way back
+    let path = plan_path(
+        level,
+        anchors[2],
+        anchors[0],
+        &preview,
+        &lanes,
+        &anchor_query,
+        &doors,
+    );
+    assert_eq!(path, PlannedPath::Unreachable);
+}
@@ -0,0 +1,316 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, AppState};
+use bevy::{ecs::system::SystemParam, math::DVec2, prelude::*};
+use rmf_site_format::alignment::{fit_point_pairs, Alignment};
+
+/// The coordinate frame that the anchors of a point alignment belong to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlignmentFrame {
+    Level(Entity),
+    Drawing(Entity),
+}
+
+impl AlignmentFrame {
+    pub fn entity(&self) -> Entity {
+        match self {
+            Self::Level(e) | Self::Drawing(e) => *e,
+        }
+    }
+}
+
+/// Pairs of anchors picked by the user to align one level or drawing with
+/// another. The first anchor of each pair belongs to the side that gets
+/// moved and the second anchor is where it should end up.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PointAlignment {
+    pub pairs: Vec<[Option<Entity>; 2]>,
+    /// Fit a scale as well as a rotation and translation. Levels get
+    /// stretched around their origin while drawings have their pixels per
+    /// meter changed.
+    pub fit_scale: bool,
+}
+
+impl PointAlignment {
+    pub fn complete_pairs(&self) -> Vec<[Entity; 2]> {
+        self.pairs
+            .iter()
+            .filter_map(|[a, b]| Some([(*a)?, (*b)?]))
+            .collect()
+    }
+}
+
+/// The best fit for the pairs of a [`PointAlignment`].
+#[derive(Debug, Clone, Copy)]
+pub struct PointAlignmentFit {
+    /// The level or drawing that gets moved
+    pub frame: AlignmentFrame,
+    /// Maps the local coordinates of the moved frame to where they belong
+    pub alignment: Alignment,
+    /// How far apart, in meters, the pairs remain after the alignment
+    pub rms_error: f64,
+}
+
+/// Send this event to move the level or drawing of the current
+/// [`PointAlignment`] so that its pairs line up as well as possible.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ApplyPointAlignment;
+
+#[derive(Default)]
+pub struct PointAlignmentPlugin;
+
+impl Plugin for PointAlignmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PointAlignment>()
+            .add_event::<ApplyPointAlignment>()
+            .add_systems(
+                Update,
+                (apply_point_alignment, draw_point_alignment_pairs)
+                    .run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+#[derive(SystemParam)]
+pub struct AlignmentPoints<'w, 's> {
+    anchors: Query<'w, 's, (&'static Anchor, &'static Parent)>,
+    levels: Query<'w, 's, (), With<LevelElevation>>,
+    drawings: Query<
+        'w,
+        's,
+        (&'static Pose, &'static PixelsPerMeter, &'static Parent),
+        With<DrawingMarker>,
+    >,
+}
+
+impl<'w, 's> AlignmentPoints<'w, 's> {
+    pub fn frame_of(&self, anchor: Entity) -> Option<AlignmentFrame> {
+        let (_, parent) = self.anchors.get(anchor).ok()?;
+        let parent = parent.get();
+        if self.levels.contains(parent) {
+            Some(AlignmentFrame::Level(parent))
+        } else if self.drawings.contains(parent) {
+            Some(AlignmentFrame::Drawing(parent))
+        } else {
+            None
+        }
+    }
+
+    /// The position of an anchor in the coordinates of its own level or
+    /// drawing. Drawings are measured in pixels.
+    pub fn local_position(&self, anchor: Entity) -> Option<DVec2> {
+        let (anchor, _) = self.anchors.get(anchor).ok()?;
+        Some(Vec2::from(anchor.translation_for_category(Category::General)).as_dvec2())
+    }
+
+    /// The position of an anchor in meters on the level that it belongs to.
+    pub fn level_position(&self, anchor: Entity) -> Option<DVec2> {
+        let p = self.local_position(anchor)?;
+        match self.frame_of(anchor)? {
+            AlignmentFrame::Level(_) => Some(p),
+            AlignmentFrame::Drawing(drawing) => {
+                let (pose, ppm, _) = self.drawings.get(drawing).ok()?;
+                Some(drawing_alignment(pose, ppm).to_affine().transform_point2(p))
+            }
+        }
+    }
+
+    /// Check if `frame` gets moved when `moving` is moved. Drawings move
+    /// along with their level.
+    fn moves_with(&self, frame: AlignmentFrame, moving: AlignmentFrame) -> bool {
+        if frame == moving {
+            return true;
+        }
+        match (frame, moving) {
+            (AlignmentFrame::Drawing(drawing), AlignmentFrame::Level(level)) => self
+                .drawings
+                .get(drawing)
+                .is_ok_and(|(_, _, parent)| parent.get() == level),
+            _ => false,
+        }
+    }
+
+    pub fn fit(&self, alignment: &PointAlignment) -> Result<PointAlignmentFit, String> {
+        let pairs = alignment.complete_pairs();
+        if pairs.len() < 2 {
+            return Err("Pick at least two pairs of points".to_owned());
+        }
+
+        let mut frame = None;
+        let mut from = Vec::new();
+        let mut to = Vec::new();
+        for [moving, reference] in pairs {
+            let (Some(moving_frame), Some(reference_frame)) =
+                (self.frame_of(moving), self.frame_of(reference))
+            else {
+                return Err("Only points of levels and drawings can be aligned".to_owned());
+            };
+            if *frame.get_or_insert(moving_frame) != moving_frame {
+                return Err(
+                    "Every point to move must belong to the same level or drawing".to_owned(),
+                );
+            }
+            if self.moves_with(reference_frame, moving_frame) {
+                return Err(
+                    "Points cannot be aligned to points that move along with them".to_owned(),
+                );
+            }
+            let (Some(p), Some(q)) = (self.local_position(moving), self.level_position(reference))
+            else {
+                return Err("Unable to find the position of a point".to_owned());
+            };
+            from.push(p);
+            to.push(q);
+        }
+        let Some(frame) = frame else {
+            return Err("Pick at least two pairs of points".to_owned());
+        };
+
+        let scale = if alignment.fit_scale {
+            None
+        } else {
+            match frame {
+                AlignmentFrame::Level(_) => Some(1.0),
+                AlignmentFrame::Drawing(drawing) => {
+                    let (_, ppm, _) = self.drawings.get(drawing).map_err(|e| e.to_string())?;
+                    Some(1.0 / ppm.0 as f64)
+                }
+            }
+        };
+        let Some(fit) = fit_point_pairs(&from, &to, scale) else {
+            return Err("The points to move are all in the same place".to_owned());
+        };
+        Ok(PointAlignmentFit {
+            frame,
+            alignment: fit,
+            rms_error: fit.rms_error(&from, &to),
+        })
+    }
+}
+
+/// The alignment that maps the pixels of a drawing onto its level.
+fn drawing_alignment(pose: &Pose, ppm: &PixelsPerMeter) -> Alignment {
+    Alignment {
+        translation: Vec2::from_slice(&pose.trans).as_dvec2(),
+        rotation: pose.rot.yaw().radians() as f64,
+        scale: 1.0 / ppm.0 as f64,
+    }
+}
+
+fn apply_point_alignment(
+    mut events: EventReader<ApplyPointAlignment>,
+    alignment: Res<PointAlignment>,
+    mut params: ParamSet<(
+        AlignmentPoints,
+        Query<&mut Anchor>,
+        Query<(&mut Pose, Option<&mut PixelsPerMeter>)>,
+    )>,
+    children: Query<&Children>,
+) {
+    if events.is_empty() {
+        return;
+    }
+    events.clear();
+
+    let fit = match params.p0().fit(&alignment) {
+        Ok(fit) => fit,
+        Err(err) => {
+            warn!("Unable to align points: {err}");
+            return;
+        }
+    };
+    let fitted = fit.alignment;
+
+    // TODO: When we implement an undo buffer, remember to make an
+    // undo operation for this set of changes.
+    match fit.frame {
+        AlignmentFrame::Drawing(drawing) => {
+            let mut drawings = params.p2();
+            let Ok((mut pose, Some(mut ppm))) = drawings.get_mut(drawing) else {
+                return;
+            };
+            pose.trans[0] = fitted.translation.x as f32;
+            pose.trans[1] = fitted.translation.y as f32;
+            pose.rot =
+                Rotation::Yaw(Angle::Rad(fitted.rotation as f32).match_variant(pose.rot.yaw()));
+            ppm.0 = 1.0 / fitted.scale as f32;
+        }
+        AlignmentFrame::Level(level) => {
+            // Everything on the level moves together, including its
+            // drawings and models.
+            let tf = fitted.to_affine();
+            let move_point = |p: &mut [f32]| {
+                let moved = tf.transform_point2(DVec2::new(p[0] as f64, p[1] as f64));
+                p[0] = moved.x as f32;
+                p[1] = moved.y as f32;
+            };
+            let Ok(level_children) = children.get(level) else {
+                return;
+            };
+
+            let mut anchors = params.p1();
+            for child in level_children {
+                let Ok(mut anchor) = anchors.get_mut(*child) else {
+                    continue;
+                };
+                match &mut *anchor {
+                    Anchor::Translate2D(p) => move_point(p),
+                    Anchor::Pose3D(pose) => {
+                        move_point(&mut pose.trans);
+                        pose.rot.apply_yaw(Angle::Rad(fitted.rotation as f32));
+                    }
+                    // Categorized anchors belong to lift cabins
+                    Anchor::CategorizedTranslate2D(_) => {}
+                }
+            }
+
+            let mut poses = params.p2();
+            for child in level_children {
+                let Ok((mut pose, ppm)) = poses.get_mut(*child) else {
+                    continue;
+                };
+                move_point(&mut pose.trans);
+                pose.rot.apply_yaw(Angle::Rad(fitted.rotation as f32));
+                if let Some(mut ppm) = ppm {
+                    ppm.0 /= fitted.scale as f32;
+                }
+            }
+        }
+    }
+    info!(
+        "Aligned {} pairs of points with a remaining error of {:.3} m",
+        alignment.complete_pairs().len(),
+        fit.rms_error,
+    );
+}
+
+fn draw_point_alignment_pairs(
+    alignment: Res<PointAlignment>,
+    transforms: Query<&GlobalTransform, With<Anchor>>,
+    mut gizmos: Gizmos,
+) {
+    for [moving, reference] in alignment.complete_pairs() {
+        let (Ok(a), Ok(b)) = (transforms.get(moving), transforms.get(reference)) else {
+            continue;
+        };
+        let (a, b) = (a.translation(), b.translation());
+        gizmos.line(a, b, Color::ORANGE);
+        gizmos.circle(a, Vec3::Z, 0.15, Color::ORANGE);
+        gizmos.circle(b, Vec3::Z, 0.15, Color::GREEN);
+    }
+}
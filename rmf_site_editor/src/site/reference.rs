@@ -0,0 +1,164 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{interaction::Selectable, site::*, CurrentWorkspace};
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Placed on the root entity of a site that was loaded as a read-only
+/// reference underlay for another site, in the style of a CAD xref. The
+/// reference is only displayed while its host site is the current workspace,
+/// and none of its elements can be selected or saved into the host.
+#[derive(Component, Clone, Debug)]
+pub struct ReferenceSite {
+    /// The site that this reference is being displayed for
+    pub host: Entity,
+    /// The name of the reference. Reference roots do not get a
+    /// [`NameOfSite`](crate::site::NameOfSite) so that systems which work on
+    /// every open site leave them alone.
+    pub name: String,
+    /// The file that the reference was loaded from, if any
+    pub source: Option<PathBuf>,
+    /// Whether the user wants the reference displayed while its host is open
+    pub visible: bool,
+}
+
+/// Send this event to load a site as a reference underlay of another site.
+#[derive(Event, Clone)]
+pub struct LoadReferenceSite {
+    /// The site that the reference should be displayed for
+    pub host: Entity,
+    /// The site data of the reference
+    pub site: rmf_site_format::Site,
+    /// Where the reference was loaded from
+    pub source: Option<PathBuf>,
+}
+
+pub struct ReferenceSitePlugin;
+
+impl Plugin for ReferenceSitePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<LoadReferenceSite>()
+            .add_systems(Update, load_reference_site)
+            .add_systems(
+                PostUpdate,
+                (
+                    update_reference_site_visibility.after(update_level_visibility),
+                    disable_selection_in_reference_sites,
+                    unload_orphaned_reference_sites,
+                )
+                    .run_if(AppState::in_displaying_mode())
+                    .in_set(SiteUpdateSet::BetweenVisibilityAndTransform),
+            );
+    }
+}
+
+fn update_reference_site_visibility(
+    current_workspace: Res<CurrentWorkspace>,
+    current_level: Res<CurrentLevel>,
+    references: Query<(Entity, &ReferenceSite, Option<&Children>)>,
+    levels: Query<(&NameInSite, &LevelElevation)>,
+    mut visibility: Query<&mut Visibility>,
+) {
+    let host_level = current_level.0.and_then(|l| levels.get(l).ok());
+    let mut set_visibility = |e: Entity, value: Visibility| {
+        if let Ok(mut v) = visibility.get_mut(e) {
+            // Avoid triggering change detection every frame
+            if *v != value {
+                *v = value;
+            }
+        }
+    };
+
+    for (e, reference, children) in &references {
+        let shown = reference.visible && current_workspace.root == Some(reference.host);
+        set_visibility(
+            e,
+            if shown {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            },
+        );
+
+        let Some(children) = children else {
+            continue;
+        };
+
+        // Display whichever level of the reference best matches the level
+        // currently being edited in the host: first by name, then by the
+        // closest elevation.
+        let mut chosen: Option<(Entity, bool, f32)> = None;
+        if let Some((host_name, host_elevation)) = host_level {
+            for child in children {
+                let Ok((name, elevation)) = levels.get(*child) else {
+                    continue;
+                };
+                let same_name = name.0 == host_name.0;
+                let gap = (elevation.0 - host_elevation.0).abs();
+                let better = match chosen {
+                    None => true,
+                    Some((_, chosen_same_name, chosen_gap)) => {
+                        (same_name && !chosen_same_name)
+                            || (same_name == chosen_same_name && gap < chosen_gap)
+                    }
+                };
+                if better {
+                    chosen = Some((*child, same_name, gap));
+                }
+            }
+        }
+
+        for child in children {
+            if levels.contains(*child) {
+                set_visibility(
+                    *child,
+                    if chosen.is_some_and(|(c, _, _)| c == *child) {
+                        Visibility::Inherited
+                    } else {
+                        Visibility::Hidden
+                    },
+                );
+            }
+        }
+    }
+}
+
+fn disable_selection_in_reference_sites(
+    mut selectables: Query<(Entity, &mut Selectable), Added<Selectable>>,
+    references: Query<(), With<ReferenceSite>>,
+    parents: Query<&Parent>,
+) {
+    for (e, mut selectable) in &mut selectables {
+        if AncestorIter::new(&parents, e).any(|p| references.contains(p)) {
+            selectable.is_selectable = false;
+        }
+    }
+}
+
+fn unload_orphaned_reference_sites(
+    mut commands: Commands,
+    references: Query<(Entity, &ReferenceSite)>,
+    sites: Query<(), With<NameOfSite>>,
+) {
+    for (e, reference) in &references {
+        if !sites.contains(reference.host) {
+            // The host site was closed, so its references go with it
+            commands.entity(e).despawn_recursive();
+        }
+    }
+}
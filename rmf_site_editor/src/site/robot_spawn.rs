@@ -0,0 +1,134 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, CurrentWorkspace};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use std::collections::BTreeMap;
+
+/// The fleet and robot model that get assigned to the next robot spawn point
+/// that the user places.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct SpawnPointDraft {
+    pub fleet: String,
+    pub robot_type: String,
+}
+
+/// Spawn this to create a location that marks where a robot starts out. The
+/// fleet and robot model are filled in from [`SpawnPointDraft`].
+#[derive(Bundle)]
+pub struct NewSpawnPoint {
+    location: Location<Entity>,
+    marker: NewSpawnPointMarker,
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct NewSpawnPointMarker;
+
+impl From<Point<Entity>> for NewSpawnPoint {
+    fn from(anchor: Point<Entity>) -> Self {
+        Self {
+            location: Location::from(anchor),
+            marker: NewSpawnPointMarker,
+        }
+    }
+}
+
+pub struct RobotSpawnPlugin;
+
+impl Plugin for RobotSpawnPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpawnPointDraft>()
+            .add_systems(Update, fill_new_spawn_points);
+    }
+}
+
+fn fill_new_spawn_points(
+    mut commands: Commands,
+    mut new_spawn_points: Query<
+        (Entity, &mut LocationTags, &mut NameInSite),
+        With<NewSpawnPointMarker>,
+    >,
+    spawn_points: RobotSpawnPoints,
+    draft: Res<SpawnPointDraft>,
+) {
+    if new_spawn_points.is_empty() {
+        return;
+    }
+
+    let robot_type = if draft.robot_type.is_empty() {
+        "robot"
+    } else {
+        draft.robot_type.as_str()
+    };
+    let mut taken: Vec<String> = spawn_points
+        .by_fleet()
+        .into_values()
+        .flatten()
+        .map(|(_, spawn)| spawn.robot_name)
+        .collect();
+    for (e, mut tags, mut name) in &mut new_spawn_points {
+        let robot_name = (1..)
+            .map(|i| format!("{robot_type}_{i}"))
+            .find(|n| !taken.contains(n))
+            .unwrap();
+        taken.push(robot_name.clone());
+        name.0 = robot_name.clone();
+        tags.push(LocationTag::SpawnPoint(RobotSpawnPoint {
+            fleet: draft.fleet.clone(),
+            robot_name,
+            robot_type: draft.robot_type.clone(),
+        }));
+        commands.entity(e).remove::<NewSpawnPointMarker>();
+    }
+}
+
+#[derive(SystemParam)]
+pub struct RobotSpawnPoints<'w, 's> {
+    locations: Query<
+        'w,
+        's,
+        (Entity, &'static LocationTags, &'static Parent),
+        (Without<Pending>, Without<NewSpawnPointMarker>),
+    >,
+    current_workspace: Res<'w, CurrentWorkspace>,
+}
+
+impl<'w, 's> RobotSpawnPoints<'w, 's> {
+    /// Every robot spawn point of the current site, grouped by fleet name
+    /// and sorted by robot name.
+    pub fn by_fleet(&self) -> BTreeMap<String, Vec<(Entity, RobotSpawnPoint)>> {
+        let mut fleets: BTreeMap<String, Vec<(Entity, RobotSpawnPoint)>> = BTreeMap::new();
+        let Some(site) = self.current_workspace.root else {
+            return fleets;
+        };
+        for (e, tags, parent) in &self.locations {
+            if parent.get() != site {
+                continue;
+            }
+            for spawn in tags.iter().filter_map(|t| t.spawn_point()) {
+                fleets
+                    .entry(spawn.fleet.clone())
+                    .or_default()
+                    .push((e, spawn.clone()));
+            }
+        }
+        for spawns in fleets.values_mut() {
+            spawns.sort_by(|(_, a), (_, b)| a.robot_name.cmp(&b.robot_name));
+        }
+        fleets
+    }
+}
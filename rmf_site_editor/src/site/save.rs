@@ -16,16 +16,27 @@
 */
 
 use bevy::{
-    ecs::{event::Events, system::SystemState},
+    ecs::{
+        event::Events,
+        system::{SystemParam, SystemState},
+    },
     prelude::*,
+    render::primitives::Aabb,
 };
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     path::PathBuf,
 };
 use thiserror::Error as ThisError;
 
-use crate::{interaction::Preview, recency::RecencyRanking, site::*, ExportFormat};
+use crate::{
+    interaction::{MultiSelection, Preview, Selection},
+    recency::RecencyRanking,
+    settings::{BackupSettings, EditorSettings},
+    site::*,
+    workspace::FileCompression,
+    CurrentWorkspace, ExportFormat,
+};
 use rmf_site_format::*;
 
 #[derive(Event)]
@@ -41,6 +52,54 @@ pub struct SaveNavGraphs {
     pub to_file: PathBuf,
 }
 
+/// Export nav graphs for seeded random variants of a site into a folder, to
+/// test how robust fleet planners are against noise in the map.
+#[derive(Event)]
+pub struct ExportPerturbedNavGraphs {
+    pub site: Entity,
+    pub options: PerturbationOptions,
+    pub to_folder: PathBuf,
+}
+
+/// Export a printable plan of a level. The file extension decides whether it
+/// is written as an SVG image or a PDF document.
+#[derive(Event)]
+pub struct ExportPlan {
+    pub level: Entity,
+    pub to_file: PathBuf,
+}
+
+/// Export a nav2 occupancy grid for each level of a site into a folder. Each
+/// level gets a PGM image and a map.yaml file that describes it.
+#[derive(Event)]
+pub struct ExportOccupancyGrid {
+    pub site: Entity,
+    pub options: OccupancyGridOptions,
+    pub to_folder: PathBuf,
+}
+
+/// Export the evacuation nav graph of a site and an evacuation plan of each of
+/// its levels into a folder.
+#[derive(Event)]
+pub struct ExportEvacuation {
+    pub site: Entity,
+    pub to_folder: PathBuf,
+}
+
+/// Export the levels, walls, and lanes of a site as GeoJSON features in WGS84
+/// coordinates.
+#[derive(Event)]
+pub struct ExportGeoJson {
+    pub site: Entity,
+    pub to_file: PathBuf,
+}
+
+/// Save only the selected elements of the current site, along with the
+/// anchors that they are attached to, as a site file of their own. This is
+/// meant for sharing a small part of a site, e.g. to attach to a bug report.
+#[derive(Event)]
+pub struct ExportSelection(pub PathBuf);
+
 // TODO(MXG): Change all these errors to use u32 SiteIDs instead of entities
 #[derive(ThisError, Debug, Clone)]
 pub enum SiteGenerationError {
@@ -104,13 +163,17 @@ fn assign_site_ids(world: &mut World, site: Entity) -> Result<(), SiteGeneration
             (
                 Or<(
                     With<Anchor>,
+                    With<CustomEntityMarker>,
                     With<DoorType>,
+                    With<LevelExtensionMarker>,
                     With<DrawingMarker>,
                     With<FloorMarker>,
                     With<LightKind>,
                     With<ModelMarker>,
                     With<PhysicalCameraProperties>,
+                    With<UserCameraPoseMarker>,
                     With<WallMarker>,
+                    With<ZoneMarker>,
                 )>,
                 Without<Pending>,
             ),
@@ -311,9 +374,265 @@ fn collect_site_anchors(world: &mut World, site: Entity) -> BTreeMap<u32, Anchor
     site_anchors
 }
 
+/// The levels of a site as they were written by its last save. Saving the
+/// site to the same file again reuses the levels that did not change since
+/// then, both as they were collected from the world and as they were written
+/// into the file, which keeps saving fast for sites with many large levels.
+#[derive(Component, Default)]
+pub struct LevelSaveCache {
+    path: PathBuf,
+    levels: HashMap<Entity, CachedLevel>,
+    /// Levels whose content changed since the last save
+    changed: HashSet<Entity>,
+}
+
+struct CachedLevel {
+    id: u32,
+    level: Level,
+    /// How the level was written into the file
+    text: String,
+}
+
+impl LevelSaveCache {
+    /// The cached levels that can be saved into `path` as they are.
+    fn clean_levels(&self, world: &World, path: &PathBuf) -> HashMap<Entity, &CachedLevel> {
+        if self.path != *path {
+            return HashMap::new();
+        }
+        self.levels
+            .iter()
+            .filter(|(level, cached)| {
+                !self.changed.contains(*level)
+                    && world
+                        .get::<SiteID>(**level)
+                        .is_some_and(|id| id.0 == cached.id)
+            })
+            .map(|(level, cached)| (*level, cached))
+            .collect()
+    }
+}
+
+/// Any change to a component that is saved as part of a level.
+type ChangedLevelContent = Or<(
+    Or<(
+        Changed<Anchor>,
+        Changed<SiteID>,
+        Changed<Edge<Entity>>,
+        Changed<Original<Edge<Entity>>>,
+        Changed<NameInSite>,
+        Changed<DoorType>,
+        Changed<DoorTiming>,
+        Changed<EmergencyExit>,
+        Changed<AssetSource>,
+        Changed<Pose>,
+        Changed<PixelsPerMeter>,
+        Changed<PreferredSemiTransparency>,
+        Changed<Rectification>,
+        Changed<DrawingSourceInfo>,
+        Changed<LayerVisibility>,
+    )>,
+    Or<(
+        Changed<Point<Entity>>,
+        Changed<Original<Point<Entity>>>,
+        Changed<Affiliation<Entity>>,
+        Changed<Path<Entity>>,
+        Changed<Original<Path<Entity>>>,
+        Changed<LightKind>,
+        Changed<Distance>,
+        Changed<PhysicalCameraProperties>,
+        Changed<WallHeight>,
+        Changed<WallAlpha>,
+        Changed<LevelElevation>,
+        Changed<GlobalFloorVisibility>,
+        Changed<GlobalDrawingVisibility>,
+        Changed<PaperSpace>,
+        Changed<FlattenedOffset>,
+    )>,
+    Or<(
+        Changed<RecencyRanking<FloorMarker>>,
+        Changed<RecencyRanking<DrawingMarker>>,
+        Changed<CustomEntityKind>,
+        Changed<CustomFields>,
+        Changed<ZoneKind>,
+        Changed<Pending>,
+        Changed<Parent>,
+        Changed<Children>,
+    )>,
+)>;
+
+/// Removals of components that change how a level is saved.
+#[derive(SystemParam)]
+struct RemovedLevelContent<'w, 's> {
+    pending: RemovedComponents<'w, 's, Pending>,
+    original_edges: RemovedComponents<'w, 's, Original<Edge<Entity>>>,
+    original_points: RemovedComponents<'w, 's, Original<Point<Entity>>>,
+    original_paths: RemovedComponents<'w, 's, Original<Path<Entity>>>,
+    door_timings: RemovedComponents<'w, 's, DoorTiming>,
+    emergency_exits: RemovedComponents<'w, 's, EmergencyExit>,
+    rectifications: RemovedComponents<'w, 's, Rectification>,
+    drawing_sources: RemovedComponents<'w, 's, DrawingSourceInfo>,
+    layer_visibilities: RemovedComponents<'w, 's, LayerVisibility>,
+    paper_spaces: RemovedComponents<'w, 's, PaperSpace>,
+    flattened_offsets: RemovedComponents<'w, 's, FlattenedOffset>,
+    floor_rankings: RemovedComponents<'w, 's, RecencyRanking<FloorMarker>>,
+    drawing_rankings: RemovedComponents<'w, 's, RecencyRanking<DrawingMarker>>,
+}
+
+impl<'w, 's> RemovedLevelContent<'w, 's> {
+    fn read(&mut self) -> Vec<Entity> {
+        let mut removed: Vec<Entity> = self.pending.read().collect();
+        removed.extend(self.original_edges.read());
+        removed.extend(self.original_points.read());
+        removed.extend(self.original_paths.read());
+        removed.extend(self.door_timings.read());
+        removed.extend(self.emergency_exits.read());
+        removed.extend(self.rectifications.read());
+        removed.extend(self.drawing_sources.read());
+        removed.extend(self.layer_visibilities.read());
+        removed.extend(self.paper_spaces.read());
+        removed.extend(self.flattened_offsets.read());
+        removed.extend(self.floor_rankings.read());
+        removed.extend(self.drawing_rankings.read());
+        removed
+    }
+}
+
+#[derive(Resource)]
+pub struct LevelChangeTracker {
+    state: SystemState<(
+        Query<'static, 'static, Entity, ChangedLevelContent>,
+        RemovedLevelContent<'static, 'static>,
+        Query<'static, 'static, &'static Parent>,
+        Query<'static, 'static, (), With<LevelElevation>>,
+        Query<'static, 'static, &'static mut LevelSaveCache>,
+    )>,
+}
+
+impl FromWorld for LevelChangeTracker {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            state: SystemState::new(world),
+        }
+    }
+}
+
+/// Mark the levels whose content changed in the save caches of their sites.
+/// This only looks at the elements that changed, so it can run every frame,
+/// and it runs once more right before each save so that no change is missed.
+pub fn track_level_changes(world: &mut World) {
+    world.resource_scope::<LevelChangeTracker, _>(|world, mut tracker| {
+        let (changed, mut removed, parents, levels, mut caches) = tracker.state.get_mut(world);
+        let mut changed_levels = HashSet::new();
+        for e in changed.iter().chain(removed.read()) {
+            let level = std::iter::once(e)
+                .chain(AncestorIter::new(&parents, e))
+                .find(|e| levels.contains(*e));
+            if let Some(level) = level {
+                changed_levels.insert(level);
+            }
+        }
+
+        for level in changed_levels {
+            let Some(site) = AncestorIter::new(&parents, level).find(|e| caches.contains(*e))
+            else {
+                continue;
+            };
+            if let Ok(mut cache) = caches.get_mut(site) {
+                cache.changed.insert(level);
+            }
+        }
+    });
+}
+
+/// Remember the levels of a site that was just saved into `path`.
+fn update_level_save_cache(
+    world: &mut World,
+    site_entity: Entity,
+    site: &Site,
+    path: &PathBuf,
+    mut texts: LevelTexts,
+) {
+    let Some(children) = world.get::<Children>(site_entity) else {
+        return;
+    };
+    let mut levels = HashMap::new();
+    for child in children.iter() {
+        let Some(id) = world
+            .get::<SiteID>(*child)
+            .filter(|_| world.get::<LevelElevation>(*child).is_some())
+        else {
+            continue;
+        };
+        let (Some(level), Some(text)) = (site.levels.get(&id.0), texts.remove(&id.0)) else {
+            continue;
+        };
+        levels.insert(
+            *child,
+            CachedLevel {
+                id: id.0,
+                level: level.clone(),
+                text,
+            },
+        );
+    }
+    world.entity_mut(site_entity).insert(LevelSaveCache {
+        path: path.clone(),
+        levels,
+        changed: HashSet::new(),
+    });
+}
+
+/// Generate the levels that are about to be reused once more and compare them
+/// with how they were cached. A level that differs means that a component
+/// which is saved as part of a level is missing from [`ChangedLevelContent`]
+/// or [`RemovedLevelContent`]. Such levels are reported and left out so that
+/// they get saved again.
+#[cfg(debug_assertions)]
+fn verify_cached_levels(
+    world: &mut World,
+    site: Entity,
+    cached: Vec<(Entity, u32, Level, String)>,
+) -> Vec<(Entity, u32, Level, String)> {
+    if cached.is_empty() {
+        return cached;
+    }
+    assemble_edited_drawing(world);
+    let generated = generate_levels(world, site, &HashMap::new());
+    disassemble_edited_drawing(world);
+    let generated = match generated {
+        Ok(generated) => generated,
+        Err(err) => {
+            error!("Unable to verify the cached levels, none of them will be reused: {err}");
+            return Vec::new();
+        }
+    };
+    // Extensions are generated again for every save, so they do not matter
+    let without_extensions = |level: &Level| {
+        let mut level = level.clone();
+        level.extensions = Default::default();
+        serde_json::to_value(level).ok()
+    };
+    cached
+        .into_iter()
+        .filter(|(_, id, level, _)| {
+            let matches = generated
+                .get(id)
+                .is_some_and(|current| without_extensions(current) == without_extensions(level));
+            if !matches {
+                error!(
+                    "Level #{id} changed since it was last saved but the change was not \
+                    tracked, so the level will be saved again. Please report this as a bug."
+                );
+            }
+            matches
+        })
+        .collect()
+}
+
 fn generate_levels(
     world: &mut World,
     site: Entity,
+    reuse: &HashMap<Entity, Level>,
 ) -> Result<BTreeMap<u32, Level>, SiteGenerationError> {
     let mut state: SystemState<(
         Query<&Children, With<NameOfSite>>,
@@ -325,6 +644,8 @@ fn generate_levels(
                 Option<&Original<Edge<Entity>>>,
                 &NameInSite,
                 &DoorType,
+                Option<&DoorTiming>,
+                Option<&EmergencyExit>,
                 &SiteID,
             ),
             Without<Pending>,
@@ -336,6 +657,9 @@ fn generate_levels(
                 &Pose,
                 &PixelsPerMeter,
                 &PreferredSemiTransparency,
+                Option<&Rectification>,
+                Option<&DrawingSourceInfo>,
+                Option<&LayerVisibility>,
                 &SiteID,
                 &Children,
             ),
@@ -376,6 +700,8 @@ fn generate_levels(
                 &Edge<Entity>,
                 Option<&Original<Edge<Entity>>>,
                 &Affiliation<Entity>,
+                &WallHeight,
+                &WallAlpha,
                 &SiteID,
             ),
             (With<WallMarker>, Without<Pending>),
@@ -390,11 +716,33 @@ fn generate_levels(
                 &Children,
                 Option<&RecencyRanking<FloorMarker>>,
                 Option<&RecencyRanking<DrawingMarker>>,
+                Option<&PaperSpace>,
+                Option<&FlattenedOffset>,
             ),
             Without<Pending>,
         >,
         Query<&SiteID>,
         Query<(&Pose, &NameInSite, &SiteID), With<UserCameraPoseMarker>>,
+        Query<
+            (
+                &NameInSite,
+                &CustomEntityKind,
+                &Pose,
+                &CustomFields,
+                &SiteID,
+            ),
+            (With<CustomEntityMarker>, Without<Pending>),
+        >,
+        Query<
+            (
+                &Path<Entity>,
+                Option<&Original<Path<Entity>>>,
+                &NameInSite,
+                &ZoneKind,
+                &SiteID,
+            ),
+            (With<ZoneMarker>, Without<Pending>),
+        >,
     )> = SystemState::new(world);
 
     let (
@@ -412,6 +760,8 @@ fn generate_levels(
         q_levels,
         q_site_ids,
         q_user_camera_poses,
+        q_custom_entities,
+        q_zones,
     ) = state.get(world);
 
     let get_anchor_id = |entity| {
@@ -456,8 +806,14 @@ fn generate_levels(
                 level_children,
                 floor_ranking,
                 drawing_ranking,
+                paper_space,
+                flattened_offset,
             )) = q_levels.get(*c)
             {
+                if let Some(cached) = reuse.get(c) {
+                    levels.insert(level_id.0, cached.clone());
+                    continue;
+                }
                 let mut level = Level::new(
                     LevelProperties {
                         name: name.clone(),
@@ -474,11 +830,15 @@ fn generate_levels(
                             .unwrap_or(Vec::new()),
                     },
                 );
+                level.paper_space = paper_space.cloned().unwrap_or_default();
+                level.flattened_offset = flattened_offset.copied().unwrap_or_default();
                 for c in level_children.iter() {
                     if let Ok((anchor, id)) = q_anchors.get(*c) {
                         level.anchors.insert(id.0, anchor.clone());
                     }
-                    if let Ok((edge, o_edge, name, kind, id)) = q_doors.get(*c) {
+                    if let Ok((edge, o_edge, name, kind, timing, emergency_exit, id)) =
+                        q_doors.get(*c)
+                    {
                         let edge = o_edge.map(|x| &x.0).unwrap_or(edge);
                         let anchors = get_anchor_id_edge(edge)?;
                         level.doors.insert(
@@ -487,6 +847,8 @@ fn generate_levels(
                                 anchors,
                                 name: name.clone(),
                                 kind: kind.clone(),
+                                timing: timing.copied().unwrap_or_default(),
+                                emergency_exit: emergency_exit.copied().unwrap_or_default(),
                                 marker: DoorMarker,
                             },
                         );
@@ -497,6 +859,9 @@ fn generate_levels(
                         pose,
                         pixels_per_meter,
                         preferred_alpha,
+                        rectification,
+                        source_info,
+                        visibility,
                         id,
                         children,
                     )) = q_drawings.get(*c)
@@ -547,10 +912,13 @@ fn generate_levels(
                                     pose: pose.clone(),
                                     pixels_per_meter: pixels_per_meter.clone(),
                                     preferred_semi_transparency: preferred_alpha.clone(),
+                                    rectification: rectification.copied().unwrap_or_default(),
+                                    source_info: source_info.cloned().unwrap_or_default(),
                                 },
                                 anchors,
                                 fiducials,
                                 measurements,
+                                visibility: visibility.copied(),
                             },
                         );
                     }
@@ -582,6 +950,18 @@ fn generate_levels(
                             },
                         );
                     }
+                    if let Ok((name, kind, pose, fields, id)) = q_custom_entities.get(*c) {
+                        level.custom_entities.insert(
+                            id.0,
+                            CustomEntity {
+                                name: name.clone(),
+                                kind: kind.clone(),
+                                pose: pose.clone(),
+                                fields: fields.clone(),
+                                marker: CustomEntityMarker,
+                            },
+                        );
+                    }
                     if let Ok((name, pose, properties, id)) = q_physical_cameras.get(*c) {
                         level.physical_cameras.insert(
                             id.0,
@@ -593,7 +973,7 @@ fn generate_levels(
                             },
                         );
                     }
-                    if let Ok((edge, o_edge, texture, id)) = q_walls.get(*c) {
+                    if let Ok((edge, o_edge, texture, height, alpha, id)) = q_walls.get(*c) {
                         let edge = o_edge.map(|x| &x.0).unwrap_or(edge);
                         let anchors = get_anchor_id_edge(edge)?;
                         let texture = if let Affiliation(Some(e)) = texture {
@@ -607,6 +987,8 @@ fn generate_levels(
                             Wall {
                                 anchors,
                                 texture,
+                                height: *height,
+                                alpha: *alpha,
                                 marker: WallMarker,
                             },
                         );
@@ -621,6 +1003,18 @@ fn generate_levels(
                             },
                         );
                     }
+                    if let Ok((path, o_path, name, kind, id)) = q_zones.get(*c) {
+                        let path = o_path.map(|x| &x.0).unwrap_or(path);
+                        level.zones.insert(
+                            id.0,
+                            Zone {
+                                anchors: get_anchor_id_path(&path)?,
+                                name: name.clone(),
+                                kind: *kind,
+                                marker: ZoneMarker,
+                            },
+                        );
+                    }
                 }
                 levels.insert(level_id.0, level);
             }
@@ -629,6 +1023,23 @@ fn generate_levels(
     return Ok(levels);
 }
 
+fn generate_extensions(world: &mut World, site: Entity, levels: &mut BTreeMap<u32, Level>) {
+    let mut state: SystemState<(Query<&Children>, Query<&SiteID, With<LevelElevation>>)> =
+        SystemState::new(world);
+    let (children, level_ids) = state.get(world);
+    let site_levels: Vec<(Entity, u32)> = children
+        .get(site)
+        .iter()
+        .flat_map(|c| c.iter())
+        .filter_map(|child| level_ids.get(*child).ok().map(|id| (*child, id.0)))
+        .collect();
+    for (level_entity, level_id) in site_levels {
+        if let Some(level) = levels.get_mut(&level_id) {
+            level.extensions = generate_level_extensions(world, level_entity);
+        }
+    }
+}
+
 type QueryLift<'w, 's> = Query<
     'w,
     's,
@@ -640,6 +1051,8 @@ type QueryLift<'w, 's> = Query<
         &'static LiftCabin<Entity>,
         &'static IsStatic,
         &'static InitialLevel<Entity>,
+        Option<&'static LiftLevelElevations<Entity>>,
+        Option<&'static LiftTiming>,
         &'static SiteID,
         &'static Parent,
     ),
@@ -721,7 +1134,20 @@ fn generate_lifts(
         Ok(())
     };
 
-    for (lift_entity, name, edge, o_edge, cabin, is_static, initial_level, id, parent) in &q_lifts {
+    for (
+        lift_entity,
+        name,
+        edge,
+        o_edge,
+        cabin,
+        is_static,
+        initial_level,
+        level_elevations,
+        timing,
+        id,
+        parent,
+    ) in &q_lifts
+    {
         if parent.get() != site {
             continue;
         }
@@ -804,6 +1230,17 @@ fn generate_lifts(
                             .0
                             .map_or(Ok(None), |level| get_level_id(level).map(|id| Some(id)))?,
                     ),
+                    // Heights of levels that have since been deleted are dropped
+                    level_elevations: LiftLevelElevations(
+                        level_elevations
+                            .into_iter()
+                            .flat_map(|e| e.iter())
+                            .filter_map(|(level, elevation)| {
+                                get_level_id(*level).ok().map(|id| (id, *elevation))
+                            })
+                            .collect(),
+                    ),
+                    timing: timing.copied().unwrap_or_default(),
                 },
                 cabin_anchors,
             },
@@ -957,10 +1394,11 @@ fn generate_nav_graphs(
     return Ok(nav_graphs);
 }
 
+/// Generate the robot lanes and the human lanes of a site, in that order.
 fn generate_lanes(
     world: &mut World,
     site: Entity,
-) -> Result<BTreeMap<u32, Lane<u32>>, SiteGenerationError> {
+) -> Result<(BTreeMap<u32, Lane<u32>>, BTreeMap<u32, Lane<u32>>), SiteGenerationError> {
     let mut state: SystemState<(
         Query<
             (
@@ -969,6 +1407,9 @@ fn generate_lanes(
                 &Motion,
                 &ReverseLane,
                 &AssociatedGraphs<Entity>,
+                Has<HumanLaneMarker>,
+                Option<&EvacuationRoute>,
+                Option<&LaneCurve>,
                 &SiteID,
                 &Parent,
             ),
@@ -994,7 +1435,10 @@ fn generate_lanes(
     };
 
     let mut lanes = BTreeMap::new();
-    for (edge, o_edge, forward, reverse, graphs, lane_id, parent) in &q_lanes {
+    let mut human_lanes = BTreeMap::new();
+    for (edge, o_edge, forward, reverse, graphs, is_human, evacuation, curve, lane_id, parent) in
+        &q_lanes
+    {
         if parent.get() != site {
             continue;
         }
@@ -1005,19 +1449,23 @@ fn generate_lanes(
             .to_u32(&q_nav_graphs)
             .map_err(|e| SiteGenerationError::BrokenNavGraphReference(e))?;
 
-        lanes.insert(
-            lane_id.0,
-            Lane {
-                anchors: edge.clone(),
-                forward: forward.clone(),
-                reverse: reverse.clone(),
-                graphs,
-                marker: LaneMarker,
-            },
-        );
+        let lane = Lane {
+            anchors: edge.clone(),
+            forward: forward.clone(),
+            reverse: reverse.clone(),
+            graphs,
+            evacuation: evacuation.copied().unwrap_or_default(),
+            curve: curve.copied().unwrap_or_default(),
+            marker: LaneMarker,
+        };
+        if is_human {
+            human_lanes.insert(lane_id.0, lane);
+        } else {
+            lanes.insert(lane_id.0, lane);
+        }
     }
 
-    Ok(lanes)
+    Ok((lanes, human_lanes))
 }
 
 fn generate_locations(
@@ -1111,13 +1559,17 @@ fn generate_site_properties(
             &FilteredIssues<Entity>,
             &FilteredIssueKinds,
             &GeographicComponent,
+            Option<&GeographicReferences>,
+            Option<&ExportSettings>,
         )>,
         Query<&SiteID>,
     )> = SystemState::new(world);
 
     let (q_properties, q_ids) = state.get(world);
 
-    let Ok((name, issues, issue_kinds, geographic_offset)) = q_properties.get(site) else {
+    let Ok((name, issues, issue_kinds, geographic_offset, geographic_references, export_settings)) =
+        q_properties.get(site)
+    else {
         return Err(SiteGenerationError::InvalidSiteEntity(site));
     };
 
@@ -1139,11 +1591,15 @@ fn generate_site_properties(
     Ok(SiteProperties {
         name: name.clone(),
         geographic_offset: geographic_offset.clone(),
+        geographic_references: geographic_references.cloned().unwrap_or_default(),
         filtered_issues: FilteredIssues(converted_issues),
         filtered_issue_kinds: issue_kinds.clone(),
+        export_settings: export_settings.cloned().unwrap_or_default(),
     })
 }
 
+/// Make the relative asset paths of a site relative to `new_path`. Returns the
+/// asset sources that were changed, as they were before the migration.
 fn migrate_relative_paths(
     site: Entity,
     new_path: &PathBuf,
@@ -1153,7 +1609,7 @@ fn migrate_relative_paths(
     // mut default_files: Query<&mut DefaultFile>,
     // mut commands: Commands,
     // parents: Query<&Parent>,
-) {
+) -> Vec<(Entity, AssetSource)> {
     let old_path = if let Some(mut default_file) = world.get_mut::<DefaultFile>(site) {
         let old_path = default_file.0.clone();
         default_file.0 = new_path.clone();
@@ -1163,14 +1619,19 @@ fn migrate_relative_paths(
         // If there was not already a default file then there is no way to
         // migrate relative paths because they had no reference path to actually
         // be relative to.
-        return;
+        return Vec::new();
     };
+    if old_path == *new_path {
+        // Leave the assets untouched so that their levels stay unchanged
+        return Vec::new();
+    }
 
     let mut state: SystemState<(Query<(Entity, &mut AssetSource)>, Query<&Parent>)> =
         SystemState::new(world);
 
     let (mut assets, parents) = state.get_mut(world);
 
+    let mut migrated = Vec::new();
     for (mut e, mut source) in &mut assets {
         let asset_entity = e;
         if !source.is_local_relative() {
@@ -1179,7 +1640,10 @@ fn migrate_relative_paths(
 
         loop {
             if e == site {
-                if source.migrate_relative_path(&old_path, new_path).is_err() {
+                let original = source.clone();
+                if source.migrate_relative_path(&old_path, new_path).is_ok() {
+                    migrated.push((asset_entity, original));
+                } else {
                     error!(
                         "Failed to migrate relative path for {asset_entity:?}: {:?}",
                         *source,
@@ -1195,6 +1659,30 @@ fn migrate_relative_paths(
             }
         }
     }
+    migrated
+}
+
+/// Undo [`migrate_relative_paths`] when the site could not be saved into its
+/// new path, so the relative asset paths keep pointing at the right files.
+fn revert_relative_paths(
+    site: Entity,
+    old_default_path: Option<DefaultFile>,
+    migrated: Vec<(Entity, AssetSource)>,
+    world: &mut World,
+) {
+    match old_default_path {
+        Some(old_default_path) => {
+            world.entity_mut(site).insert(old_default_path);
+        }
+        None => {
+            world.entity_mut(site).remove::<DefaultFile>();
+        }
+    }
+    for (e, source) in migrated {
+        if let Some(mut entity) = world.get_entity_mut(e) {
+            entity.insert(source);
+        }
+    }
 }
 
 fn generate_model_descriptions(
@@ -1208,6 +1696,7 @@ fn generate_model_descriptions(
                 &NameInSite,
                 &ModelProperty<AssetSource>,
                 &ModelProperty<IsStatic>,
+                Option<&ModelProperty<ZOffset>>,
                 &ModelProperty<Scale>,
             ),
             (With<ModelMarker>, With<Group>, Without<Pending>),
@@ -1219,11 +1708,14 @@ fn generate_model_descriptions(
     let mut res = BTreeMap::<u32, ModelDescriptionBundle>::new();
     if let Ok(children) = children.get(site) {
         for child in children.iter() {
-            if let Ok((site_id, name, source, is_static, scale)) = model_descriptions.get(*child) {
+            if let Ok((site_id, name, source, is_static, z_offset, scale)) =
+                model_descriptions.get(*child)
+            {
                 let desc_bundle = ModelDescriptionBundle {
                     name: name.clone(),
                     source: source.clone(),
                     is_static: is_static.clone(),
+                    z_offset: z_offset.cloned().unwrap_or_default(),
                     scale: scale.clone(),
                     ..Default::default()
                 };
@@ -1412,21 +1904,90 @@ fn generate_tasks(
     Ok(res)
 }
 
+fn generate_crowd_sim(site: Entity, world: &mut World) -> CrowdSim<u32> {
+    let mut state: SystemState<(Query<&CrowdSim<Entity>>, Query<&SiteID, With<Anchor>>)> =
+        SystemState::new(world);
+    let (crowd_sims, anchors) = state.get(world);
+    let Ok(crowd_sim) = crowd_sims.get(site) else {
+        return CrowdSim::default();
+    };
+
+    // Goals whose anchors have been deleted are dropped from their sets
+    let id_map: HashMap<Entity, u32> = crowd_sim
+        .goal_sets
+        .iter()
+        .flat_map(|g| g.anchors.iter())
+        .filter_map(|a| anchors.get(*a).ok().map(|id| (*a, id.0)))
+        .collect();
+    let mut crowd_sim = crowd_sim.clone();
+    for goal_set in &mut crowd_sim.goal_sets {
+        goal_set.anchors.retain(|a| id_map.contains_key(a));
+    }
+    // All references were filtered above, so the conversion cannot fail
+    crowd_sim.convert(&id_map).unwrap_or_default()
+}
+
+fn generate_entity_groups(site: Entity, world: &mut World) -> EntityGroups<u32> {
+    let mut state: SystemState<(Query<&EntityGroups<Entity>>, Query<&SiteID>)> =
+        SystemState::new(world);
+    let (entity_groups, site_ids) = state.get(world);
+    let Ok(entity_groups) = entity_groups.get(site) else {
+        return EntityGroups::default();
+    };
+
+    // Members that were deleted or are never saved are dropped from their
+    // groups
+    let mut entity_groups = entity_groups.clone();
+    entity_groups.retain_members(|m| site_ids.contains(*m));
+    let id_map: HashMap<Entity, u32> = entity_groups
+        .iter()
+        .flat_map(|g| g.all_members())
+        .filter_map(|m| site_ids.get(m).ok().map(|id| (m, id.0)))
+        .collect();
+    entity_groups.convert(&id_map).unwrap_or_default()
+}
+
+fn generate_tags(site: Entity, world: &mut World) -> BTreeMap<u32, EntityTags> {
+    let mut state: SystemState<(
+        Query<(Entity, &EntityTags, &SiteID), Without<Pending>>,
+        Query<&Parent>,
+    )> = SystemState::new(world);
+    let (tagged, parents) = state.get(world);
+    tagged
+        .iter()
+        .filter(|(e, tags, _)| {
+            !tags.is_empty() && AncestorIter::new(&parents, *e).any(|p| p == site)
+        })
+        .map(|(_, tags, id)| (id.0, tags.clone()))
+        .collect()
+}
+
 pub fn generate_site(
     world: &mut World,
     site: Entity,
+) -> Result<rmf_site_format::Site, SiteGenerationError> {
+    generate_site_reusing_levels(world, site, &HashMap::new())
+}
+
+/// Same as [`generate_site`], except that the levels in `reuse` are taken as
+/// they are instead of being collected from the world.
+fn generate_site_reusing_levels(
+    world: &mut World,
+    site: Entity,
+    reuse: &HashMap<Entity, Level>,
 ) -> Result<rmf_site_format::Site, SiteGenerationError> {
     assemble_edited_drawing(world);
 
     assign_site_ids(world, site)?;
     let anchors = collect_site_anchors(world, site);
-    let levels = generate_levels(world, site)?;
+    let mut levels = generate_levels(world, site, reuse)?;
+    generate_extensions(world, site, &mut levels);
     let lifts = generate_lifts(world, site)?;
     let fiducials = generate_fiducials(world, site)?;
     let fiducial_groups = generate_fiducial_groups(world, site)?;
     let textures = generate_texture_groups(world, site)?;
     let nav_graphs = generate_nav_graphs(world, site)?;
-    let lanes = generate_lanes(world, site)?;
+    let (lanes, human_lanes) = generate_lanes(world, site)?;
     let locations = generate_locations(world, site)?;
     let graph_ranking = generate_graph_rankings(world, site)?;
     let properties = generate_site_properties(world, site)?;
@@ -1435,6 +1996,13 @@ pub fn generate_site(
     let model_instances = generate_model_instances(site, world)?;
     let scenarios = generate_scenarios(site, world)?;
     let tasks = generate_tasks(site, world)?;
+    let crowd_sim = generate_crowd_sim(site, world);
+    let entity_groups = generate_entity_groups(site, world);
+    let tags = generate_tags(site, world);
+    let custom_entity_schemas = world
+        .get::<CustomEntitySchemas>(site)
+        .cloned()
+        .unwrap_or_default();
 
     disassemble_edited_drawing(world);
     return Ok(Site {
@@ -1451,6 +2019,7 @@ pub fn generate_site(
                 graphs: nav_graphs,
                 ranking: graph_ranking,
                 lanes,
+                human_lanes,
                 locations,
             },
         },
@@ -1461,6 +2030,10 @@ pub fn generate_site(
         model_instances,
         scenarios,
         tasks,
+        crowd_sim,
+        entity_groups,
+        custom_entity_schemas,
+        tags,
     });
 }
 
@@ -1472,63 +2045,110 @@ pub fn save_site(world: &mut World) {
             Some(s) => s,
             None => {
                 error!("Unable to save file: Invalid path [{new_path:?}]");
+                if matches!(save_event.format, ExportFormat::Default) {
+                    world.send_event(MapSaveFailed {
+                        site: save_event.site,
+                    });
+                }
                 continue;
             }
         };
         match save_event.format {
             ExportFormat::Default => {
+                // Compression is decided by a .gz or .zst suffix on top of the
+                // usual extension, e.g. office.site.ron.gz
+                let (path_str, compression) = FileCompression::split(path_str);
                 if path_str.ends_with(".building.yaml") {
                     warn!("Detected old file format, converting to new format");
                     new_path = path_str.replace(".building.yaml", ".site.ron").into();
-                } else if path_str.ends_with(".site.json") {
-                    // Noop
-                } else if !path_str.ends_with(".site.ron") {
-                    info!("Appending .site.ron to {}", new_path.display());
-                    new_path = new_path.with_extension("site.ron");
+                } else if path_str.ends_with(".site.json") || path_str.ends_with(".site.ron") {
+                    new_path = path_str.into();
+                } else {
+                    info!("Appending .site.ron to {}", path_str);
+                    new_path = PathBuf::from(path_str).with_extension("site.ron");
+                }
+                if let Some(compression) = compression {
+                    let mut compressed_path = new_path.into_os_string();
+                    compressed_path.push(compression.suffix());
+                    new_path = compressed_path.into();
                 }
                 info!("Saving to {}", new_path.display());
-                let f = match std::fs::File::create(new_path.clone()) {
-                    Ok(f) => f,
-                    Err(err) => {
-                        error!("Unable to save file: {err}");
-                        continue;
-                    }
-                };
 
                 let old_default_path = world.get::<DefaultFile>(save_event.site).cloned();
-                migrate_relative_paths(save_event.site, &new_path, world);
-
-                let site = match generate_site(world, save_event.site) {
+                let migrated = migrate_relative_paths(save_event.site, &new_path, world);
+
+                track_level_changes(world);
+                let cached: Vec<(Entity, u32, Level, String)> = world
+                    .get::<LevelSaveCache>(save_event.site)
+                    .map(|cache| {
+                        cache
+                            .clean_levels(world, &new_path)
+                            .into_iter()
+                            .map(|(e, c)| (e, c.id, c.level.clone(), c.text.clone()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                #[cfg(debug_assertions)]
+                let cached = verify_cached_levels(world, save_event.site, cached);
+                if !cached.is_empty() {
+                    debug!("Reusing {} unchanged levels", cached.len());
+                }
+                let reuse: HashMap<Entity, Level> = cached
+                    .iter()
+                    .map(|(e, _, level, _)| (*e, level.clone()))
+                    .collect();
+                let mut site = match generate_site_reusing_levels(world, save_event.site, &reuse) {
                     Ok(site) => site,
                     Err(err) => {
+                        revert_relative_paths(save_event.site, old_default_path, migrated, world);
                         error!("Unable to compile site: {err}");
+                        world.send_event(MapSaveFailed {
+                            site: save_event.site,
+                        });
                         continue;
                     }
                 };
+                // Level extensions are generated again for every save, so the
+                // text of a level can only be reused if they did not change.
+                let reuse_texts: LevelTexts = cached
+                    .into_iter()
+                    .filter(|(_, id, level, _)| {
+                        site.levels
+                            .get(id)
+                            .is_some_and(|l| l.extensions == level.extensions)
+                    })
+                    .map(|(_, id, _, text)| (id, text))
+                    .collect();
+
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let depth = world
+                        .get_resource::<EditorSettings>()
+                        .map(|settings| settings.backups.depth)
+                        .unwrap_or_else(|| BackupSettings::default().depth);
+                    if let Err(err) = rotate_backups(&new_path, depth) {
+                        warn!("Unable to back up {}: {err}", new_path.display());
+                    }
+                }
 
-                if new_path.extension().is_some_and(|e| e == "json") {
-                    match site.to_writer_json(f) {
-                        Ok(()) => {
-                            info!("Save successful");
-                        }
-                        Err(err) => {
-                            if let Some(old_default_path) = old_default_path {
-                                world.entity_mut(save_event.site).insert(old_default_path);
-                            }
-                            error!("Save failed: {err}");
-                        }
+                match save_map_reusing_levels(&mut site, &new_path, &reuse_texts) {
+                    Ok(texts) => {
+                        info!("Save successful");
+                        // Absorb the changes made while generating the site,
+                        // such as newly assigned SiteIDs.
+                        track_level_changes(world);
+                        update_level_save_cache(world, save_event.site, &site, &new_path, texts);
+                        world.send_event(MapSaved {
+                            site: save_event.site,
+                            path: new_path.clone(),
+                        });
                     }
-                } else {
-                    match site.to_writer_ron(f) {
-                        Ok(()) => {
-                            info!("Save successful");
-                        }
-                        Err(err) => {
-                            if let Some(old_default_path) = old_default_path {
-                                world.entity_mut(save_event.site).insert(old_default_path);
-                            }
-                            error!("Save failed: {err}");
-                        }
+                    Err(err) => {
+                        revert_relative_paths(save_event.site, old_default_path, migrated, world);
+                        error!("Save failed: {err}");
+                        world.send_event(MapSaveFailed {
+                            site: save_event.site,
+                        });
                     }
                 }
             }
@@ -1576,6 +2196,7 @@ pub fn save_site(world: &mut World) {
                     error!("Unable to create folder {}: {e}", meshes_dir.display());
                     continue;
                 }
+                restore_culled_levels(world);
                 if let Err(e) = collect_site_meshes(world, save_event.site, &meshes_dir) {
                     error!("Unable to collect site meshes: {e}");
                     continue;
@@ -1583,13 +2204,32 @@ pub fn save_site(world: &mut World) {
 
                 migrate_relative_paths(save_event.site, &sdf_path, world);
                 let graphs = legacy::nav_graph::NavGraph::from_site(&site);
-                let sdf = match site.to_sdf() {
+                let mut sdf = match site.to_sdf() {
                     Ok(sdf) => sdf,
                     Err(err) => {
                         error!("Unable to convert site to sdf: {err}");
                         continue;
                     }
                 };
+                let mut crowd_sim_files = Vec::new();
+                if site.crowd_sim.enable {
+                    let crowd_sim_dir = new_path.join("crowd_sim");
+                    match write_menge_files(&site, &crowd_sim_dir) {
+                        Ok(files) => {
+                            let resource_path = crowd_sim_dir
+                                .canonicalize()
+                                .unwrap_or(crowd_sim_dir.clone());
+                            sdf.world[0]
+                                .plugin
+                                .push(site.menge_sdf_plugin(&resource_path.to_string_lossy()));
+                            crowd_sim_files = files
+                                .into_iter()
+                                .map(|f| format!("crowd_sim/{f}"))
+                                .collect();
+                        }
+                        Err(err) => error!("Unable to export crowd simulation: {err}"),
+                    }
+                }
                 let config = yaserde::ser::Config {
                     perform_indent: true,
                     write_document_declaration: true,
@@ -1605,6 +2245,7 @@ pub fn save_site(world: &mut World) {
                     error!("Unable to create folder {}: {e}", navgraph_dir.display());
                     continue;
                 }
+                let mut graph_files = Vec::new();
                 for (name, graph) in &graphs {
                     let mut graph_file = navgraph_dir.clone();
                     graph_file.push(name.to_owned() + ".yaml");
@@ -1621,8 +2262,112 @@ pub fn save_site(world: &mut World) {
                     };
                     if let Err(err) = serde_yaml::to_writer(f, &graph) {
                         error!("Failed to save nav graph: {err}");
+                        continue;
+                    }
+                    graph_files.push(format!("nav_graphs/{name}.yaml"));
+                }
+
+                let export_settings = &site.properties.export_settings;
+                let mut manifest = ExportManifest::new(site.properties.name.0.clone());
+                manifest.entries.push(ExportManifestEntry {
+                    exporter: "sdf".to_owned(),
+                    options: export_settings.sdf.clone(),
+                    files: vec![
+                        format!("{}.world", site.properties.name.0),
+                        "meshes".to_owned(),
+                    ],
+                });
+                manifest.entries.push(ExportManifestEntry {
+                    exporter: "nav_graph".to_owned(),
+                    options: export_settings.nav_graph.clone(),
+                    files: graph_files,
+                });
+                if !crowd_sim_files.is_empty() {
+                    manifest.entries.push(ExportManifestEntry {
+                        exporter: "crowd_sim".to_owned(),
+                        options: ExportOptions::default(),
+                        files: crowd_sim_files,
+                    });
+                }
+                write_export_manifest(&manifest, &new_path.join("manifest.json"));
+            }
+            ExportFormat::CrowdSim => {
+                let site = match generate_site(world, save_event.site) {
+                    Ok(site) => site,
+                    Err(err) => {
+                        error!("Unable to compile site: {err}");
+                        continue;
+                    }
+                };
+
+                info!("Exporting crowd simulation to {}", new_path.display());
+                match write_menge_files(&site, &new_path) {
+                    Ok(files) => {
+                        let mut manifest = ExportManifest::new(site.properties.name.0.clone());
+                        manifest.entries.push(ExportManifestEntry {
+                            exporter: "crowd_sim".to_owned(),
+                            options: ExportOptions::default(),
+                            files,
+                        });
+                        write_export_manifest(&manifest, &new_path.join("manifest.json"));
+                        info!("Crowd simulation export successful");
+                    }
+                    Err(err) => error!("Unable to export crowd simulation: {err}"),
+                }
+            }
+            ExportFormat::DigitalTwin => {
+                // Generating the site assigns a SiteID to every element
+                let site = match generate_site(world, save_event.site) {
+                    Ok(site) => site,
+                    Err(err) => {
+                        error!("Unable to compile site: {err}");
+                        continue;
+                    }
+                };
+
+                info!("Exporting digital twin to {}", new_path.display());
+                if let Err(e) = std::fs::create_dir_all(&new_path) {
+                    error!("Unable to create folder {}: {e}", new_path.display());
+                    continue;
+                }
+                if !new_path.is_dir() {
+                    error!("A digital twin can only be exported to a folder");
+                    continue;
+                }
+
+                let options = &site.properties.export_settings.digital_twin;
+                restore_culled_levels(world);
+                if let Err(e) =
+                    collect_digital_twin_meshes(world, save_event.site, &new_path, options)
+                {
+                    error!("Unable to collect site meshes: {e}");
+                    continue;
+                }
+
+                let twin = site.to_digital_twin_manifest(options);
+                let twin_file = format!("{}.twin.json", site.properties.name.0);
+                let f = match std::fs::File::create(new_path.join(&twin_file)) {
+                    Ok(f) => f,
+                    Err(err) => {
+                        error!("Unable to save digital twin manifest: {err}");
+                        continue;
                     }
+                };
+                if let Err(err) = twin.to_writer_json(f) {
+                    error!("Failed to save digital twin manifest: {err}");
+                    continue;
                 }
+
+                let mut manifest = ExportManifest::new(site.properties.name.0.clone());
+                let mut files: Vec<String> = twin.levels.iter().map(|l| l.file.clone()).collect();
+                files.push(twin_file);
+                manifest.entries.push(ExportManifestEntry {
+                    exporter: "digital_twin".to_owned(),
+                    options: options.clone(),
+                    files,
+                });
+                write_export_manifest(&manifest, &new_path.join("manifest.json"));
+                info!("Digital twin export successful");
             }
         }
     }
@@ -1644,9 +2389,11 @@ pub fn save_nav_graphs(world: &mut World) {
             }
         };
 
+        let mut graph_files = Vec::new();
         for (name, nav_graph) in legacy::nav_graph::NavGraph::from_site(&site) {
+            let file_name = name + ".nav.yaml";
             let mut graph_file = path.clone();
-            graph_file.set_file_name(name + ".nav.yaml");
+            graph_file.set_file_name(&file_name);
             info!(
                 "Saving legacy nav graph to {}",
                 graph_file.to_str().unwrap_or("<failed to render??>")
@@ -1660,9 +2407,21 @@ pub fn save_nav_graphs(world: &mut World) {
             };
             if let Err(err) = serde_yaml::to_writer(f, &nav_graph) {
                 error!("Failed to save nav graph: {err}");
+                continue;
             }
+            graph_files.push(file_name);
         }
 
+        let mut manifest = ExportManifest::new(site.properties.name.0.clone());
+        manifest.entries.push(ExportManifestEntry {
+            exporter: "nav_graph".to_owned(),
+            options: site.properties.export_settings.nav_graph.clone(),
+            files: graph_files,
+        });
+        let mut manifest_file = path.clone();
+        manifest_file.set_file_name(format!("{}.manifest.json", site.properties.name.0));
+        write_export_manifest(&manifest, &manifest_file);
+
         // Clear the elements that are not related to nav graphs
         for (_, level) in &mut site.levels {
             level.doors.clear();
@@ -1694,3 +2453,575 @@ pub fn save_nav_graphs(world: &mut World) {
         }
     }
 }
+
+pub fn export_perturbed_nav_graphs(world: &mut World) {
+    let export_events: Vec<_> = world
+        .resource_mut::<Events<ExportPerturbedNavGraphs>>()
+        .drain()
+        .collect();
+    for export_event in export_events {
+        let site = match generate_site(world, export_event.site) {
+            Ok(site) => site,
+            Err(err) => {
+                error!("Unable to compile site: {err}");
+                continue;
+            }
+        };
+
+        let folder = export_event.to_folder;
+        let options = export_event.options;
+        let mut manifest = ExportManifest::new(site.properties.name.0.clone());
+        for (i, variant) in site.perturbed_variants(&options).iter().enumerate() {
+            let variant_name = format!("variant_{i}");
+            let variant_dir = folder.join(&variant_name);
+            if let Err(err) = std::fs::create_dir_all(&variant_dir) {
+                error!("Unable to create folder {}: {err}", variant_dir.display());
+                continue;
+            }
+
+            let mut graph_files = Vec::new();
+            for (name, nav_graph) in legacy::nav_graph::NavGraph::from_site(variant) {
+                let file_name = name + ".nav.yaml";
+                let f = match std::fs::File::create(variant_dir.join(&file_name)) {
+                    Ok(f) => f,
+                    Err(err) => {
+                        error!("Unable to save nav graph: {err}");
+                        continue;
+                    }
+                };
+                if let Err(err) = serde_yaml::to_writer(f, &nav_graph) {
+                    error!("Failed to save nav graph: {err}");
+                    continue;
+                }
+                graph_files.push(format!("{variant_name}/{file_name}"));
+            }
+
+            manifest.entries.push(ExportManifestEntry {
+                exporter: "nav_graph".to_owned(),
+                options: site.properties.export_settings.nav_graph.clone(),
+                files: graph_files,
+            });
+        }
+        write_export_manifest(&manifest, &folder.join("manifest.json"));
+
+        // Record the options so the same variants can be generated again
+        let options_file = folder.join("perturbation.json");
+        match std::fs::File::create(&options_file) {
+            Ok(f) => {
+                if let Err(err) = serde_json::to_writer_pretty(f, &options) {
+                    error!("Failed to save perturbation options: {err}");
+                }
+            }
+            Err(err) => {
+                error!("Unable to save file {}: {err}", options_file.display());
+            }
+        }
+        info!(
+            "Exported {} perturbed variants to {}",
+            options.variants,
+            folder.display()
+        );
+    }
+}
+
+pub fn export_occupancy_grid(world: &mut World) {
+    let export_events: Vec<_> = world
+        .resource_mut::<Events<ExportOccupancyGrid>>()
+        .drain()
+        .collect();
+    for export_event in export_events {
+        let site = match generate_site(world, export_event.site) {
+            Ok(site) => site,
+            Err(err) => {
+                error!("Unable to compile site: {err}");
+                continue;
+            }
+        };
+
+        let folder = export_event.to_folder;
+        if let Err(err) = std::fs::create_dir_all(&folder) {
+            error!("Unable to create folder {}: {err}", folder.display());
+            continue;
+        }
+
+        let footprints = static_model_footprints(world);
+        let mut manifest = ExportManifest::new(site.properties.name.0.clone());
+        let mut files = Vec::new();
+        for (level_id, level) in &site.levels {
+            let level_footprints = level_entity_of(world, *level_id)
+                .and_then(|e| footprints.get(&e))
+                .map(Vec::as_slice)
+                .unwrap_or(&[]);
+            let Some(grid) =
+                site.to_occupancy_grid(*level_id, &export_event.options, level_footprints)
+            else {
+                continue;
+            };
+
+            let image_file = occupancy_grid_file(&level.properties.name.0);
+            let yaml_file = image_file.replace(".pgm", ".yaml");
+            if let Err(err) = std::fs::write(folder.join(&image_file), grid.to_pgm()) {
+                error!("Unable to save occupancy grid {image_file}: {err}");
+                continue;
+            }
+            let yaml = match grid.to_map_yaml(image_file.clone()) {
+                Ok(yaml) => yaml,
+                Err(err) => {
+                    error!("Failed to serialize map metadata: {err}");
+                    continue;
+                }
+            };
+            if let Err(err) = std::fs::write(folder.join(&yaml_file), yaml) {
+                error!("Unable to save map metadata {yaml_file}: {err}");
+                continue;
+            }
+            files.push(image_file);
+            files.push(yaml_file);
+        }
+
+        manifest.entries.push(ExportManifestEntry {
+            exporter: "occupancy_grid".to_owned(),
+            options: ExportOptions::default(),
+            files,
+        });
+        write_export_manifest(&manifest, &folder.join("manifest.json"));
+        info!("Exported occupancy grids to {}", folder.display());
+    }
+}
+
+pub fn export_evacuation(world: &mut World) {
+    let export_events: Vec<_> = world
+        .resource_mut::<Events<ExportEvacuation>>()
+        .drain()
+        .collect();
+    for export_event in export_events {
+        let site = match generate_site(world, export_event.site) {
+            Ok(site) => site,
+            Err(err) => {
+                error!("Unable to compile site: {err}");
+                continue;
+            }
+        };
+
+        let folder = export_event.to_folder;
+        if let Err(err) = std::fs::create_dir_all(&folder) {
+            error!("Unable to create folder {}: {err}", folder.display());
+            continue;
+        }
+
+        let mut graph_files = Vec::new();
+        let evacuation = site.evacuation_site();
+        for (name, nav_graph) in legacy::nav_graph::NavGraph::from_site(&evacuation) {
+            let file_name = name + ".nav.yaml";
+            let f = match std::fs::File::create(folder.join(&file_name)) {
+                Ok(f) => f,
+                Err(err) => {
+                    error!("Unable to save evacuation nav graph: {err}");
+                    continue;
+                }
+            };
+            if let Err(err) = serde_yaml::to_writer(f, &nav_graph) {
+                error!("Failed to save evacuation nav graph: {err}");
+                continue;
+            }
+            graph_files.push(file_name);
+        }
+
+        let mut plan_files = Vec::new();
+        for (level_id, level) in &site.levels {
+            let level_name = &level.properties.name.0;
+            if site.emergency_exits(*level_id).is_empty() {
+                warn!("Level {level_name} does not have any emergency exits");
+            }
+            let plan = match site.to_evacuation_plan(*level_id) {
+                Ok(plan) => plan,
+                Err(err) => {
+                    error!("Unable to lay out evacuation plan of {level_name}: {err}");
+                    continue;
+                }
+            };
+            let name: String = level_name
+                .chars()
+                .map(|c| if c.is_alphanumeric() { c } else { '_' })
+                .collect();
+            let file_name = format!("evacuation_{name}.svg");
+            if let Err(err) = std::fs::write(folder.join(&file_name), plan.to_svg()) {
+                error!("Unable to save evacuation plan {file_name}: {err}");
+                continue;
+            }
+            plan_files.push(file_name);
+        }
+
+        let mut manifest = ExportManifest::new(site.properties.name.0.clone());
+        manifest.entries.push(ExportManifestEntry {
+            exporter: "evacuation_nav_graph".to_owned(),
+            options: site.properties.export_settings.nav_graph.clone(),
+            files: graph_files,
+        });
+        manifest.entries.push(ExportManifestEntry {
+            exporter: "evacuation_plan".to_owned(),
+            options: ExportOptions::default(),
+            files: plan_files,
+        });
+        write_export_manifest(&manifest, &folder.join("manifest.json"));
+        info!("Exported evacuation layer to {}", folder.display());
+    }
+}
+
+pub fn export_geojson(world: &mut World) {
+    let export_events: Vec<_> = world
+        .resource_mut::<Events<ExportGeoJson>>()
+        .drain()
+        .collect();
+    for export_event in export_events {
+        let site = match generate_site(world, export_event.site) {
+            Ok(site) => site,
+            Err(err) => {
+                error!("Unable to compile site: {err}");
+                continue;
+            }
+        };
+
+        let Some(projection) = SiteProjection::new(
+            site.properties.geographic_offset.0.as_ref(),
+            &site.properties.geographic_references,
+        ) else {
+            error!(
+                "Unable to export GeoJSON: the site needs a geographic offset or \
+                geographic reference points"
+            );
+            continue;
+        };
+
+        let geojson = match site.to_geojson(|p| projection.lon_lat(p)) {
+            Ok(geojson) => geojson,
+            Err(err) => {
+                error!("Unable to export GeoJSON: {err}");
+                continue;
+            }
+        };
+
+        let path = export_event.to_file;
+        let f = match std::fs::File::create(&path) {
+            Ok(f) => f,
+            Err(err) => {
+                error!("Unable to create file {}: {err}", path.display());
+                continue;
+            }
+        };
+        if let Err(err) = serde_json::to_writer_pretty(f, &geojson) {
+            error!("Failed to save GeoJSON: {err}");
+            continue;
+        }
+        info!("Exported GeoJSON to {}", path.display());
+    }
+}
+
+pub fn export_selection(world: &mut World) {
+    let export_events: Vec<_> = world
+        .resource_mut::<Events<ExportSelection>>()
+        .drain()
+        .collect();
+    for ExportSelection(path) in export_events {
+        let Some(site_entity) = world.resource::<CurrentWorkspace>().root else {
+            error!("Unable to export the selection: no site is open");
+            continue;
+        };
+        let mut selected: BTreeSet<Entity> = world.resource::<MultiSelection>().0.clone();
+        selected.extend(world.resource::<Selection>().0);
+
+        // Generating the site makes sure that every selected element has a
+        // site ID.
+        let site = match generate_site(world, site_entity) {
+            Ok(site) => site,
+            Err(err) => {
+                error!("Unable to compile site: {err}");
+                continue;
+            }
+        };
+        let keep: BTreeSet<u32> = selected
+            .iter()
+            .filter_map(|e| world.get::<SiteID>(*e))
+            .map(|id| id.0)
+            .collect();
+        if keep.is_empty() {
+            warn!("Nothing that can be exported is selected");
+            continue;
+        }
+
+        let subset = site.subset(&keep);
+        match save_map(&subset, &path) {
+            Ok(()) => info!(
+                "Exported {} selected elements to {}",
+                keep.len(),
+                path.display()
+            ),
+            Err(err) => error!("Unable to export the selection: {err}"),
+        }
+    }
+}
+
+fn level_entity_of(world: &mut World, level_id: u32) -> Option<Entity> {
+    let mut q_levels = world.query_filtered::<(Entity, &SiteID), With<LevelElevation>>();
+    q_levels
+        .iter(world)
+        .find(|(_, id)| id.0 == level_id)
+        .map(|(e, _)| e)
+}
+
+/// Find the footprints of the visual meshes of every static model, grouped by
+/// the level that the model is on. Each footprint is the bottom face of the
+/// bounding box of a mesh, projected onto the floor.
+fn static_model_footprints(world: &mut World) -> HashMap<Entity, Vec<Vec<Vec2>>> {
+    let mut state: SystemState<(
+        Query<(Entity, &IsStatic, &Parent), With<ModelMarker>>,
+        Query<&Children>,
+        Query<(), With<VisualMeshMarker>>,
+        Query<(&Aabb, &GlobalTransform)>,
+    )> = SystemState::new(world);
+    let (q_models, q_children, q_visuals, q_bounds) = state.get(world);
+
+    let mut footprints: HashMap<Entity, Vec<Vec<Vec2>>> = HashMap::new();
+    for (model, is_static, parent) in &q_models {
+        if !is_static.0 {
+            continue;
+        }
+        for model_child in DescendantIter::new(&q_children, model) {
+            if !q_visuals.contains(model_child) {
+                continue;
+            }
+            for entity in DescendantIter::new(&q_children, model_child) {
+                let Ok((aabb, tf)) = q_bounds.get(entity) else {
+                    continue;
+                };
+                let (min, max) = (aabb.min(), aabb.max());
+                let footprint = [
+                    Vec3::new(min.x, min.y, min.z),
+                    Vec3::new(max.x, min.y, min.z),
+                    Vec3::new(max.x, max.y, min.z),
+                    Vec3::new(min.x, max.y, min.z),
+                ]
+                .into_iter()
+                .map(|p| tf.transform_point(p).truncate())
+                .collect();
+                footprints.entry(parent.get()).or_default().push(footprint);
+            }
+        }
+    }
+    footprints
+}
+
+/// Write the Menge files of the crowd simulation of a site into `folder`.
+/// Returns the names of the files that were written.
+fn write_menge_files(site: &Site, folder: &PathBuf) -> Result<Vec<String>, String> {
+    std::fs::create_dir_all(folder)
+        .map_err(|e| format!("Unable to create folder {}: {e}", folder.display()))?;
+    let menge = site.to_menge();
+    let site_name = &site.properties.name.0;
+    let mut files = vec![
+        (menge_scene_file(site_name), menge.scene),
+        (menge_behavior_file(site_name), menge.behavior),
+    ];
+    files.extend(menge.navmeshes);
+    let mut names = Vec::new();
+    for (name, contents) in files {
+        let path = folder.join(&name);
+        std::fs::write(&path, contents)
+            .map_err(|e| format!("Unable to save {}: {e}", path.display()))?;
+        names.push(name);
+    }
+    Ok(names)
+}
+
+fn write_export_manifest(manifest: &ExportManifest, path: &PathBuf) {
+    let f = match std::fs::File::create(path) {
+        Ok(f) => f,
+        Err(err) => {
+            error!("Unable to save export manifest {}: {err}", path.display());
+            return;
+        }
+    };
+    if let Err(err) = manifest.to_writer_json(f) {
+        error!("Failed to save export manifest: {err}");
+    }
+}
+
+pub fn export_plan(world: &mut World) {
+    let export_events: Vec<_> = world.resource_mut::<Events<ExportPlan>>().drain().collect();
+    for export_event in export_events {
+        let Some(site_entity) = world.get::<Parent>(export_event.level).map(|p| p.get()) else {
+            error!("Unable to export plan: the level does not belong to a site");
+            continue;
+        };
+
+        // Generating the site makes sure that the level has a SiteID
+        let site = match generate_site(world, site_entity) {
+            Ok(site) => site,
+            Err(err) => {
+                error!("Unable to compile site: {err}");
+                continue;
+            }
+        };
+        let Some(level_id) = world.get::<SiteID>(export_event.level).map(|id| id.0) else {
+            error!("Unable to export plan: the level was not saved into the site");
+            continue;
+        };
+
+        let plan = match site.to_plan(level_id) {
+            Ok(plan) => plan,
+            Err(err) => {
+                error!("Unable to lay out plan: {err}");
+                continue;
+            }
+        };
+
+        let path = export_event.to_file;
+        let data = if path.extension().is_some_and(|e| e == "pdf") {
+            plan.to_pdf()
+        } else {
+            plan.to_svg().into_bytes()
+        };
+        info!("Exporting plan to {}", path.display());
+        match std::fs::write(&path, data) {
+            Ok(()) => {
+                info!("Export successful");
+            }
+            Err(err) => {
+                error!("Export failed: {err}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_failed_save_reverts_relative_path_migration() {
+    let mut world = World::new();
+    let old_path = PathBuf::from("/maps/office/office.site.ron");
+    let site = world.spawn(DefaultFile(old_path.clone())).id();
+    let relative = AssetSource::Local("meshes/chair.obj".to_owned());
+    let model = world.spawn(relative.clone()).set_parent(site).id();
+
+    let old_default_path = world.get::<DefaultFile>(site).cloned();
+    let migrated = migrate_relative_paths(
+        site,
+        &PathBuf::from("/backups/office/office.site.ron"),
+        &mut world,
+    );
+    assert_eq!(migrated.len(), 1);
+    assert_ne!(world.get::<AssetSource>(model), Some(&relative));
+
+    revert_relative_paths(site, old_default_path, migrated, &mut world);
+    assert_eq!(world.get::<AssetSource>(model), Some(&relative));
+    assert_eq!(world.get::<DefaultFile>(site).unwrap().0, old_path);
+}
+
+#[cfg(test)]
+fn cache_saved_levels(world: &mut World, site: Entity, levels: &[(Entity, u32, Level)]) {
+    // Saving absorbs every change that happened before it
+    track_level_changes(world);
+    world.entity_mut(site).insert(LevelSaveCache {
+        path: PathBuf::from("/maps/office.site.ron"),
+        levels: levels
+            .iter()
+            .map(|(e, id, level)| {
+                let cached = CachedLevel {
+                    id: *id,
+                    level: level.clone(),
+                    text: String::new(),
+                };
+                (*e, cached)
+            })
+            .collect(),
+        changed: HashSet::new(),
+    });
+}
+
+#[cfg(test)]
+fn is_level_reused(world: &mut World, site: Entity, level: Entity) -> bool {
+    track_level_changes(world);
+    world
+        .get::<LevelSaveCache>(site)
+        .unwrap()
+        .clean_levels(world, &PathBuf::from("/maps/office.site.ron"))
+        .contains_key(&level)
+}
+
+#[test]
+fn test_editing_level_content_saves_the_level_again() {
+    let mut world = World::new();
+    world.init_resource::<LevelChangeTracker>();
+    let site = world.spawn(NameOfSite("office".to_owned())).id();
+    let level = world
+        .spawn((LevelElevation(0.0), SiteID(1)))
+        .set_parent(site)
+        .id();
+    let wall = world
+        .spawn((WallHeight::default(), SiteID(2)))
+        .set_parent(level)
+        .id();
+    let door = world
+        .spawn((DoorTiming::default(), SiteID(3)))
+        .set_parent(level)
+        .id();
+    let light = world.spawn(SiteID(4)).set_parent(level).id();
+    let saved = [(level, 1, Level::default())];
+
+    cache_saved_levels(&mut world, site, &saved);
+    assert!(is_level_reused(&mut world, site, level));
+
+    world.get_mut::<WallHeight>(wall).unwrap().0 = 3.5;
+    assert!(!is_level_reused(&mut world, site, level));
+
+    cache_saved_levels(&mut world, site, &saved);
+    world.get_mut::<DoorTiming>(door).unwrap().open_duration = Some(4.0);
+    assert!(!is_level_reused(&mut world, site, level));
+
+    cache_saved_levels(&mut world, site, &saved);
+    world.entity_mut(light).despawn_recursive();
+    assert!(!is_level_reused(&mut world, site, level));
+
+    cache_saved_levels(&mut world, site, &saved);
+    assert!(is_level_reused(&mut world, site, level));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_untracked_level_changes_are_not_reused() {
+    let mut world = World::new();
+    let site = world.spawn(NameOfSite("office".to_owned())).id();
+    let level = world
+        .spawn((LevelProperties::default(), SiteID(1)))
+        .set_parent(site)
+        .id();
+    let left = world
+        .spawn((Anchor::Translate2D([0.0, 0.0]), SiteID(2)))
+        .set_parent(level)
+        .id();
+    let right = world
+        .spawn((Anchor::Translate2D([5.0, 0.0]), SiteID(3)))
+        .set_parent(level)
+        .id();
+    let wall = world
+        .spawn((
+            Wall {
+                anchors: Edge::new(left, right),
+                texture: Affiliation(None),
+                height: WallHeight::default(),
+                alpha: WallAlpha::default(),
+                marker: WallMarker,
+            },
+            SiteID(4),
+        ))
+        .set_parent(level)
+        .id();
+
+    let saved = generate_levels(&mut world, site, &HashMap::new()).unwrap();
+    let cached = vec![(level, 1, saved[&1].clone(), String::new())];
+    assert_eq!(
+        verify_cached_levels(&mut world, site, cached.clone()).len(),
+        1
+    );
+
+    world.get_mut::<WallHeight>(wall).unwrap().0 = 3.5;
+    assert!(verify_cached_levels(&mut world, site, cached).is_empty());
+}
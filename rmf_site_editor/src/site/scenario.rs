@@ -23,7 +23,7 @@ use crate::{
         ScenarioBundle, ScenarioMarker,
     },
     widgets::view_model_instances::count_scenarios,
-    CurrentWorkspace, Issue, ValidateWorkspace,
+    CurrentWorkspace, Issue, ValidationRequests,
 };
 use bevy::{prelude::*, utils::Uuid};
 use std::collections::HashMap;
@@ -515,7 +515,7 @@ pub fn handle_remove_scenarios(
 ) {
     for request in remove_scenario_requests.read() {
         // Any child scenarios are considered dependents to be deleted
-        let mut subtree_dependents = std::collections::HashSet::<Entity>::new();
+        let mut subtree_dependents = std::collections::BTreeSet::<Entity>::new();
         let mut queue = vec![request.0];
         while let Some(scenario_entity) = queue.pop() {
             if let Ok(children) = children.get(scenario_entity) {
@@ -562,7 +562,7 @@ pub const HIDDEN_MODEL_INSTANCE_ISSUE_UUID: Uuid =
 
 pub fn check_for_hidden_model_instances(
     mut commands: Commands,
-    mut validate_events: EventReader<ValidateWorkspace>,
+    mut validate_events: ValidationRequests,
     children: Query<&Children>,
     instances: Query<
         (Entity, &NameInSite, &Affiliation<Entity>),
@@ -571,7 +571,7 @@ pub fn check_for_hidden_model_instances(
     scenarios: Query<(Entity, &NameInSite, &Affiliation<Entity>), With<ScenarioMarker>>,
     instance_modifiers: Query<(&mut InstanceModifier, &Affiliation<Entity>)>,
 ) {
-    for root in validate_events.read() {
+    for root in validate_events.roots() {
         for (instance_entity, instance_name, _) in instances.iter() {
             if count_scenarios(&scenarios, instance_entity, &children, &instance_modifiers) > 0 {
                 continue;
@@ -590,7 +590,7 @@ pub fn check_for_hidden_model_instances(
                     .to_string(),
             };
             let issue_id = commands.spawn(issue).id();
-            commands.entity(**root).add_child(issue_id);
+            commands.entity(root).add_child(issue_id);
         }
     }
 }
@@ -0,0 +1,150 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, AppState, CurrentWorkspace};
+use bevy::{
+    ecs::{event::Events, system::SystemState},
+    prelude::*,
+};
+use std::collections::HashMap;
+
+/// Send this event to run a script that edits the current site. See
+/// [`rmf_site_format::scripting`] for what scripts can do.
+#[derive(Event, Debug, Clone)]
+pub struct RunSiteScript {
+    pub script: String,
+}
+
+/// The result of the last script that was run on the current site.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SiteScriptResult {
+    pub output: Vec<String>,
+    pub error: Option<String>,
+    /// How many elements the script changed
+    pub changed: usize,
+}
+
+#[derive(Default)]
+pub struct SiteScriptPlugin;
+
+impl Plugin for SiteScriptPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RunSiteScript>()
+            .init_resource::<SiteScriptResult>()
+            .add_systems(
+                Update,
+                run_site_scripts.run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+fn run_site_scripts(world: &mut World) {
+    let requests: Vec<_> = world
+        .resource_mut::<Events<RunSiteScript>>()
+        .drain()
+        .collect();
+    for request in requests {
+        let Some(root) = world.resource::<CurrentWorkspace>().root else {
+            continue;
+        };
+        let result = run_site_script(world, root, &request.script);
+        if let Some(err) = &result.error {
+            error!("{err}");
+        } else {
+            info!("The script changed {} elements", result.changed);
+        }
+        *world.resource_mut::<SiteScriptResult>() = result;
+    }
+}
+
+fn run_site_script(world: &mut World, root: Entity, script: &str) -> SiteScriptResult {
+    let site = match generate_site(world, root) {
+        Ok(site) => site,
+        Err(err) => {
+            return SiteScriptResult {
+                error: Some(format!("Unable to run the script: {err}")),
+                ..Default::default()
+            }
+        }
+    };
+    let outcome = match site.run_script(script) {
+        Ok(outcome) => outcome,
+        Err(err) => {
+            return SiteScriptResult {
+                error: Some(err.to_string()),
+                ..Default::default()
+            }
+        }
+    };
+    apply_script_changes(world, root, &outcome);
+    SiteScriptResult {
+        output: outcome.output,
+        error: None,
+        changed: outcome.changes.count(),
+    }
+}
+
+/// Scripts edit a copy of the site, so copy the properties that the script
+/// assigned back onto the entities of the site.
+fn apply_script_changes(world: &mut World, root: Entity, outcome: &ScriptOutcome) {
+    let mut state: SystemState<(Query<(Entity, &SiteID)>, Query<&Parent>)> =
+        SystemState::new(world);
+    let (elements, parents) = state.get(world);
+    let id_to_entity: HashMap<u32, Entity> = elements
+        .iter()
+        .filter(|(e, _)| AncestorIter::new(&parents, *e).any(|p| p == root))
+        .map(|(e, id)| (id.0, e))
+        .collect();
+
+    let site = &outcome.site;
+    let changes = &outcome.changes;
+    for id in &changes.anchors {
+        let (Some(e), Some(anchor)) = (
+            id_to_entity.get(id),
+            site.levels.values().find_map(|l| l.anchors.get(id)),
+        ) else {
+            continue;
+        };
+        world.entity_mut(*e).insert(anchor.clone());
+    }
+    for id in &changes.lanes {
+        let (Some(e), Some(lane)) = (id_to_entity.get(id), site.navigation.guided.lanes.get(id))
+        else {
+            continue;
+        };
+        world
+            .entity_mut(*e)
+            .insert((lane.forward.clone(), lane.reverse.clone()));
+    }
+    for id in &changes.walls {
+        let (Some(e), Some(wall)) = (
+            id_to_entity.get(id),
+            site.levels.values().find_map(|l| l.walls.get(id)),
+        ) else {
+            continue;
+        };
+        world.entity_mut(*e).insert(wall.height);
+    }
+    for id in &changes.model_instances {
+        let (Some(e), Some(instance)) = (id_to_entity.get(id), site.model_instances.get(id)) else {
+            continue;
+        };
+        world
+            .entity_mut(*e)
+            .insert((instance.bundle.pose.clone(), instance.bundle.name.clone()));
+    }
+}
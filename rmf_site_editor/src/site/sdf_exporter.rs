@@ -15,6 +15,7 @@ use crate::{
 };
 use rmf_site_format::{
     IsStatic, LevelElevation, LiftCabin, ModelMarker, NameInSite, NameOfSite, SiteID, WallMarker,
+    ZOffset,
 };
 
 /// Manages a simple state machine where we:
@@ -102,7 +103,7 @@ pub fn collect_site_meshes(world: &mut World, site: Entity, folder: &Path) -> Re
         Query<Entity, With<WallMarker>>,
         Query<&FloorSegments>,
         Query<(Option<&NameInSite>, &DoorSegments)>,
-        Query<(Entity, &IsStatic, &NameInSite), With<ModelMarker>>,
+        Query<(Entity, &IsStatic, Option<&ZOffset>, &NameInSite), With<ModelMarker>>,
         Query<(), With<CollisionMeshMarker>>,
         Query<(), With<VisualMeshMarker>>,
         Query<(&Handle<Mesh>, &Handle<StandardMaterial>)>,
@@ -214,7 +215,10 @@ pub fn collect_site_meshes(world: &mut World, site: Entity, folder: &Path) -> Re
                         material: Some(material),
                         transform: Some(level_tf.clone()),
                     });
-                } else if let Ok((model, is_static, name)) = q_models.get(*child) {
+                } else if let Ok((model, is_static, z_offset, name)) = q_models.get(*child) {
+                    // Static models are baked into the level mesh, so their
+                    // z offset has to be applied to the mesh itself.
+                    let z_offset = z_offset.map(|z| z.0).unwrap_or(0.0);
                     let mut model_collisions = vec![];
                     let mut model_visuals = vec![];
                     // TODO(luca) don't do full descendant iter here or we might add twice?
@@ -230,7 +234,7 @@ pub fn collect_site_meshes(world: &mut World, site: Entity, folder: &Path) -> Re
                                     continue;
                                 };
                                 let mut tf = tf.compute_transform();
-                                tf.translation.z = tf.translation.z + **elevation;
+                                tf.translation.z = tf.translation.z + **elevation + z_offset;
                                 // Non static meshes have their translation in the SDF element, not in the
                                 // gltf node
                                 model_collisions.push(MeshData {
@@ -249,7 +253,7 @@ pub fn collect_site_meshes(world: &mut World, site: Entity, folder: &Path) -> Re
                                     continue;
                                 };
                                 let mut tf = tf.compute_transform();
-                                tf.translation.z = tf.translation.z + **elevation;
+                                tf.translation.z = tf.translation.z + **elevation + z_offset;
                                 model_visuals.push(MeshData {
                                     mesh,
                                     material: Some(material),
@@ -0,0 +1,172 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+//! Events that describe how the sites in the editor are changing, so that
+//! observers such as analytics, collaboration, or ROS bridge plugins can
+//! react without patching the editor's own systems.
+//!
+//! The events are generated in the [`Last`] schedule from change detection,
+//! so they cover every editing system, including systems added by other
+//! plugins. Read them with an [`EventReader`] from a system in [`Last`] that
+//! runs after [`SiteEventSet`], or from any system in the next frame.
+
+use crate::{interaction::Preview, site::*};
+use bevy::prelude::*;
+use std::{collections::HashSet, path::PathBuf};
+
+/// A new element was added to a site. Elements that are still being drawn by
+/// the user are only reported once they are finished.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct EntityAdded {
+    pub entity: Entity,
+}
+
+/// A component of a site element was changed.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct EntityModified {
+    pub entity: Entity,
+    /// The type name of the component that changed
+    pub component: &'static str,
+}
+
+/// A site element was removed.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct EntityRemoved {
+    pub entity: Entity,
+}
+
+/// A site finished loading into the editor.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct MapLoaded {
+    pub site: Entity,
+}
+
+/// A site was saved to a file.
+#[derive(Event, Debug, Clone)]
+pub struct MapSaved {
+    pub site: Entity,
+    pub path: PathBuf,
+}
+
+/// Saving a site to a file failed.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct MapSaveFailed {
+    pub site: Entity,
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SiteEventSet;
+
+pub struct SiteEventsPlugin;
+
+impl Plugin for SiteEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<EntityAdded>()
+            .add_event::<EntityModified>()
+            .add_event::<EntityRemoved>()
+            .add_event::<MapLoaded>()
+            .add_event::<MapSaved>()
+            .add_event::<MapSaveFailed>()
+            .add_systems(
+                Last,
+                (
+                    report_added_entities,
+                    report_removed_entities,
+                    report_modified::<Anchor>,
+                    report_modified::<Edge<Entity>>,
+                    report_modified::<Path<Entity>>,
+                    report_modified::<Point<Entity>>,
+                    report_modified::<Pose>,
+                    report_modified::<NameInSite>,
+                    report_modified::<Motion>,
+                    report_modified::<ReverseLane>,
+                    report_modified::<DoorType>,
+                    report_modified::<LocationTags>,
+                    report_modified::<AssociatedGraphs<Entity>>,
+                    report_modified::<Affiliation<Entity>>,
+                    report_modified::<LevelElevation>,
+                    report_modified::<Distance>,
+                )
+                    .in_set(SiteEventSet),
+            );
+    }
+}
+
+/// Report changes to a component of site elements with [`EntityModified`].
+/// Add this system to the [`Last`] schedule in [`SiteEventSet`] to observe
+/// components that are not reported by default.
+pub fn report_modified<T: Component>(
+    changed: Query<
+        (Entity, Ref<T>),
+        (
+            Changed<T>,
+            Or<(With<Category>, With<Anchor>)>,
+            Without<Pending>,
+            Without<Preview>,
+        ),
+    >,
+    mut modified: EventWriter<EntityModified>,
+) {
+    for (entity, value) in &changed {
+        if value.is_added() {
+            // This will be reported by EntityAdded instead
+            continue;
+        }
+        modified.send(EntityModified {
+            entity,
+            component: std::any::type_name::<T>(),
+        });
+    }
+}
+
+fn report_added_entities(
+    added: Query<
+        Entity,
+        (
+            Or<(Added<Category>, Added<Anchor>)>,
+            Without<Pending>,
+            Without<Preview>,
+        ),
+    >,
+    elements: Query<(), (Or<(With<Category>, With<Anchor>)>, Without<Preview>)>,
+    pending: Query<(), With<Pending>>,
+    mut finished: RemovedComponents<Pending>,
+    mut events: EventWriter<EntityAdded>,
+) {
+    let mut reported = HashSet::new();
+    let finished = finished
+        .read()
+        .filter(|e| elements.contains(*e) && !pending.contains(*e));
+    for entity in added.iter().chain(finished) {
+        if reported.insert(entity) {
+            events.send(EntityAdded { entity });
+        }
+    }
+}
+
+fn report_removed_entities(
+    mut removed_category: RemovedComponents<Category>,
+    mut removed_anchor: RemovedComponents<Anchor>,
+    mut events: EventWriter<EntityRemoved>,
+) {
+    let mut reported = HashSet::new();
+    for entity in removed_category.read().chain(removed_anchor.read()) {
+        if reported.insert(entity) {
+            events.send(EntityRemoved { entity });
+        }
+    }
+}
@@ -16,6 +16,7 @@
 */
 
 use bevy::prelude::*;
+use std::collections::HashMap;
 
 use crate::interaction::{InteractionState, SetCategoryVisibility};
 use crate::site::{CurrentLevel, LaneMarker, LevelElevation, NameOfSite};
@@ -24,13 +25,50 @@ use crate::{AppState, CurrentWorkspace};
 #[derive(Default)]
 pub struct SiteVisualizerPlugin;
 
+/// Settings for how the site visualizer stacks the levels of a site at their
+/// elevations.
+#[derive(Resource, Clone, Debug)]
+pub struct StackedLevelView {
+    /// Multiplies the elevation of every level to make the space between
+    /// levels easier to inspect.
+    pub vertical_exaggeration: f32,
+    /// Opacity of each level from 0.0 to 1.0. Levels without an entry are
+    /// fully opaque.
+    pub opacity: HashMap<Entity, f32>,
+}
+
+impl Default for StackedLevelView {
+    fn default() -> Self {
+        Self {
+            vertical_exaggeration: 1.0,
+            opacity: HashMap::new(),
+        }
+    }
+}
+
+impl StackedLevelView {
+    pub fn opacity(&self, level: Entity) -> f32 {
+        self.opacity.get(&level).copied().unwrap_or(1.0)
+    }
+}
+
+/// Remembers the original material of a mesh whose level is faded out in the
+/// site visualizer.
+#[derive(Component)]
+pub struct FadedLevelMaterial {
+    original: Handle<StandardMaterial>,
+}
+
 fn show_all_levels(
     workspace: Res<CurrentWorkspace>,
     open_sites: Query<Entity, With<NameOfSite>>,
     children: Query<&Children>,
     mut levels: Query<(&mut Visibility, &mut Transform, &LevelElevation)>,
     mut lanes_visibility: EventWriter<SetCategoryVisibility<LaneMarker>>,
+    mut stacked_view: ResMut<StackedLevelView>,
 ) {
+    // Make sure the opacity of each level gets applied again
+    stacked_view.set_changed();
     if let Some(children) = workspace
         .to_site(&open_sites)
         .and_then(|s| children.get(s).ok())
@@ -38,7 +76,7 @@ fn show_all_levels(
         for child in children.iter() {
             if let Ok((mut vis, mut tf, elevation)) = levels.get_mut(*child) {
                 *vis = Visibility::Inherited;
-                tf.translation.z = elevation.0;
+                tf.translation.z = elevation.0 * stacked_view.vertical_exaggeration;
             }
         }
         lanes_visibility.send(false.into());
@@ -72,10 +110,73 @@ fn hide_all_non_current_levels(
 }
 
 fn update_level_elevation(
-    mut changed_levels: Query<(&mut Transform, &LevelElevation), Changed<LevelElevation>>,
+    mut levels: Query<(&mut Transform, Ref<LevelElevation>)>,
+    stacked_view: Res<StackedLevelView>,
+) {
+    for (mut tf, elevation) in &mut levels {
+        if elevation.is_changed() || stacked_view.is_changed() {
+            tf.translation.z = elevation.0 * stacked_view.vertical_exaggeration;
+        }
+    }
+}
+
+fn update_level_opacity(
+    mut commands: Commands,
+    stacked_view: Res<StackedLevelView>,
+    levels: Query<Entity, With<LevelElevation>>,
+    children: Query<&Children>,
+    mut meshes: Query<(&mut Handle<StandardMaterial>, Option<&FadedLevelMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !stacked_view.is_changed() {
+        return;
+    }
+
+    for level in &levels {
+        let opacity = stacked_view.opacity(level);
+        for e in DescendantIter::new(&children, level) {
+            let Ok((mut handle, faded)) = meshes.get_mut(e) else {
+                continue;
+            };
+
+            if opacity >= 1.0 {
+                if let Some(faded) = faded {
+                    *handle = faded.original.clone();
+                    commands.entity(e).remove::<FadedLevelMaterial>();
+                }
+                continue;
+            }
+
+            // Materials are often shared between meshes, so faded meshes get
+            // a copy of their original material.
+            let original = faded
+                .map(|f| f.original.clone())
+                .unwrap_or_else(|| handle.clone());
+            let Some(mut material) = materials.get(&original).cloned() else {
+                continue;
+            };
+            let alpha = material.base_color.a() * opacity;
+            material.base_color.set_a(alpha);
+            material.alpha_mode = AlphaMode::Blend;
+            if faded.is_some() {
+                if let Some(faded_material) = materials.get_mut(&*handle) {
+                    *faded_material = material;
+                }
+            } else {
+                *handle = materials.add(material);
+                commands.entity(e).insert(FadedLevelMaterial { original });
+            }
+        }
+    }
+}
+
+fn restore_level_materials(
+    mut commands: Commands,
+    mut faded: Query<(Entity, &mut Handle<StandardMaterial>, &FadedLevelMaterial)>,
 ) {
-    for (mut tf, elevation) in &mut changed_levels {
-        tf.translation.z = elevation.0;
+    for (e, mut handle, faded) in &mut faded {
+        *handle = faded.original.clone();
+        commands.entity(e).remove::<FadedLevelMaterial>();
     }
 }
 
@@ -91,17 +192,23 @@ fn enable_interaction(mut interaction_state: ResMut<NextState<InteractionState>>
 
 impl Plugin for SiteVisualizerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            OnEnter(AppState::SiteVisualizer),
-            (show_all_levels, disable_interaction),
-        )
-        .add_systems(
-            OnExit(AppState::SiteVisualizer),
-            (hide_all_non_current_levels, enable_interaction),
-        )
-        .add_systems(
-            Update,
-            update_level_elevation.run_if(in_state(AppState::SiteVisualizer)),
-        );
+        app.init_resource::<StackedLevelView>()
+            .add_systems(
+                OnEnter(AppState::SiteVisualizer),
+                (show_all_levels, disable_interaction),
+            )
+            .add_systems(
+                OnExit(AppState::SiteVisualizer),
+                (
+                    hide_all_non_current_levels,
+                    restore_level_materials,
+                    enable_interaction,
+                ),
+            )
+            .add_systems(
+                Update,
+                (update_level_elevation, update_level_opacity)
+                    .run_if(in_state(AppState::SiteVisualizer)),
+            );
     }
 }
@@ -0,0 +1,231 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+//! A stable API for plugins that want to generate site content, such as
+//! procedural warehouse or hotel layouts.
+//!
+//! Use [`Spawner`] as a system parameter:
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use librmf_site_editor::{site::Spawner, SiteEditor};
+//!
+//! fn add_corridor(mut spawner: Spawner, keyboard: Res<Input<KeyCode>>) {
+//!     if !keyboard.just_pressed(KeyCode::G) {
+//!         return;
+//!     }
+//!     let Some(level) = spawner.current_level() else {
+//!         return;
+//!     };
+//!     let a = spawner.add_vertex(level, Vec2::new(0.0, 0.0));
+//!     let b = spawner.add_vertex(level, Vec2::new(10.0, 0.0));
+//!     spawner.add_lane(a, b);
+//! }
+//!
+//! fn main() {
+//!     let mut app = App::new();
+//!     app.add_plugins(SiteEditor::default())
+//!         .add_systems(Update, add_corridor);
+//!
+//!     app.run();
+//! }
+//! ```
+//!
+//! Elements are given a [`SiteID`](crate::site::SiteID) automatically the
+//! next time the site is saved, so plugins only need to keep track of the
+//! [`Entity`] of each element that they create.
+
+use crate::{
+    site::{
+        unique_door_name, Anchor, AnchorBundle, AssociatedGraphs, Category, ChangeDependent,
+        CurrentLevel, Door, DoorMarker, DoorType, Edge, Lane, LaneMarker, ModelInstance,
+        ModelLoader, Motion, NameInSite, ReverseLane, TextureNeedsAssignment, Wall, WallMarker,
+    },
+    CurrentWorkspace,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+
+/// A system parameter for adding vertices, lanes, walls, doors, and models
+/// into a site. Each function returns the entity that was created, and the
+/// entity can be customized further with [`Spawner::commands`].
+#[derive(SystemParam)]
+pub struct Spawner<'w, 's> {
+    commands: Commands<'w, 's>,
+    model_loader: ModelLoader<'w, 's>,
+    current_level: Res<'w, CurrentLevel>,
+    current_workspace: Res<'w, CurrentWorkspace>,
+    parents: Query<'w, 's, &'static Parent>,
+    doors: Query<'w, 's, (Entity, &'static NameInSite), With<DoorMarker>>,
+    /// Doors whose spawn commands have not been applied yet
+    pending_doors: Local<'s, Vec<(Entity, String)>>,
+    /// Parents of vertices whose spawn commands have not been applied yet
+    pending_parents: Local<'s, HashMap<Entity, Entity>>,
+}
+
+impl<'w, 's> Spawner<'w, 's> {
+    pub fn commands(&mut self) -> &mut Commands<'w, 's> {
+        &mut self.commands
+    }
+
+    /// The level that is currently being displayed in the editor
+    pub fn current_level(&self) -> Option<Entity> {
+        self.current_level.0
+    }
+
+    /// The site that is currently open in the editor
+    pub fn current_site(&self) -> Option<Entity> {
+        self.current_workspace.root
+    }
+
+    /// Add a vertex to a level. `position` is in the coordinates of the level.
+    pub fn add_vertex(&mut self, level: Entity, position: Vec2) -> Entity {
+        let e = self
+            .commands
+            .spawn(AnchorBundle::new(Anchor::Translate2D([
+                position.x, position.y,
+            ])))
+            .set_parent(level)
+            .id();
+        self.pending_parents.insert(e, level);
+        e
+    }
+
+    /// Add a bidirectional lane to every navigation graph. Use
+    /// [`Spawner::add_lane_with`] to choose its properties.
+    pub fn add_lane(&mut self, start: Entity, end: Entity) -> Entity {
+        self.add_lane_with(Lane {
+            anchors: Edge::new(start, end),
+            forward: Motion::default(),
+            reverse: ReverseLane::Same,
+            graphs: AssociatedGraphs::All,
+            evacuation: Default::default(),
+            curve: Default::default(),
+            marker: LaneMarker,
+        })
+    }
+
+    pub fn add_lane_with(&mut self, lane: Lane<Entity>) -> Entity {
+        let anchors = lane.anchors;
+        let site = self.site_of(anchors.start());
+        let mut e = self.commands.spawn(lane);
+        if let Some(site) = site {
+            e.set_parent(site);
+        }
+        let e = e.id();
+        self.add_dependent(anchors, e);
+        e
+    }
+
+    /// Add a wall. It will be given the texture of the most recently edited
+    /// wall.
+    pub fn add_wall(&mut self, start: Entity, end: Entity) -> Entity {
+        let level = self.parent_of(start);
+        let mut e = self.commands.spawn((
+            Wall {
+                anchors: Edge::new(start, end),
+                texture: Default::default(),
+                height: Default::default(),
+                alpha: Default::default(),
+                marker: WallMarker,
+            },
+            TextureNeedsAssignment,
+        ));
+        if let Some(level) = level {
+            e.set_parent(level);
+        }
+        let e = e.id();
+        self.add_dependent(Edge::new(start, end), e);
+        e
+    }
+
+    /// Add a door. RMF requires door names to be unique, so a suffix will be
+    /// added to `name` if another door already uses it.
+    pub fn add_door(
+        &mut self,
+        start: Entity,
+        end: Entity,
+        name: impl Into<String>,
+        kind: DoorType,
+    ) -> Entity {
+        let name = self.unique_door_name(name.into());
+        let level = self.parent_of(start);
+        let mut e = self.commands.spawn(Door {
+            anchors: Edge::new(start, end),
+            name: NameInSite(name.clone()),
+            kind,
+            timing: Default::default(),
+            emergency_exit: Default::default(),
+            marker: DoorMarker,
+        });
+        if let Some(level) = level {
+            e.set_parent(level);
+        }
+        let e = e.id();
+        self.pending_doors.push((e, name));
+        self.add_dependent(Edge::new(start, end), e);
+        e
+    }
+
+    /// Add a model instance to a level. The description of the instance must
+    /// belong to the same site as the level.
+    pub fn add_model(&mut self, level: Entity, instance: ModelInstance<Entity>) -> Entity {
+        self.model_loader
+            .spawn_model_instance(level, instance)
+            .insert(Category::Model)
+            .id()
+    }
+
+    fn add_dependent(&mut self, anchors: Edge<Entity>, dependent: Entity) {
+        for anchor in anchors.array() {
+            self.commands.add(ChangeDependent::add(anchor, dependent));
+        }
+    }
+
+    fn unique_door_name(&mut self, name: String) -> String {
+        // Doors that have finished spawning will show up in the query
+        let doors = &self.doors;
+        self.pending_doors.retain(|(e, _)| !doors.contains(*e));
+
+        unique_door_name(&name, |candidate| {
+            self.pending_doors.iter().any(|(_, n)| n == candidate)
+                || self.doors.iter().any(|(_, n)| n.0 == candidate)
+        })
+    }
+
+    /// Find the parent of an element, including vertices that were added by
+    /// this spawner and whose parent has not been applied yet.
+    fn parent_of(&mut self, e: Entity) -> Option<Entity> {
+        let parents = &self.parents;
+        self.pending_parents
+            .retain(|vertex, _| !parents.contains(*vertex));
+        self.parents
+            .get(e)
+            .ok()
+            .map(|p| p.get())
+            .or_else(|| self.pending_parents.get(&e).copied())
+    }
+
+    /// Find the site that an anchor belongs to. Anchors can belong to a level,
+    /// which belongs to a site, or they can belong to the site directly.
+    fn site_of(&mut self, anchor: Entity) -> Option<Entity> {
+        let parent = self.parent_of(anchor)?;
+        match self.parent_of(parent) {
+            Some(grandparent) => Some(grandparent),
+            None => Some(parent),
+        }
+    }
+}
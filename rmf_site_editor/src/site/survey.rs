@@ -0,0 +1,206 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{interaction::IntersectGroundPlaneParams, settings::EditorSettings, site::*, AppState};
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::{Input as UserInput, *},
+    window::PrimaryWindow,
+};
+use bevy_egui::EguiContexts;
+use std::collections::HashSet;
+
+/// Width of the doors that get dropped while surveying. Their anchors are
+/// expected to be moved onto the real door frame during cleanup.
+pub const QUICK_ADD_DOOR_WIDTH: f32 = 1.0;
+
+/// While survey mode is active, single key presses drop doors, chargers, and
+/// parking spots at the cursor so a site can be roughed out while walking
+/// through the facility. Precise geometry can be fixed later.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct SurveyMode {
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickAddKind {
+    Door,
+    Charger,
+    ParkingSpot,
+}
+
+impl QuickAddKind {
+    /// Prefix of the names that are generated for this kind of element.
+    pub fn name_prefix(&self) -> &'static str {
+        match self {
+            Self::Door => "door",
+            Self::Charger => "charger",
+            Self::ParkingSpot => "parking",
+        }
+    }
+}
+
+/// Send this event to drop a new element at the cursor on the current level.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct QuickAddAtCursor(pub QuickAddKind);
+
+pub struct SurveyPlugin;
+
+impl Plugin for SurveyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SurveyMode>()
+            .add_event::<QuickAddAtCursor>()
+            .add_systems(
+                Update,
+                (handle_survey_keys, quick_add_at_cursor)
+                    .chain()
+                    .run_if(in_state(AppState::SiteEditor)),
+            );
+    }
+}
+
+fn handle_survey_keys(
+    keyboard_input: Res<UserInput<KeyCode>>,
+    settings: Res<EditorSettings>,
+    mut survey_mode: ResMut<SurveyMode>,
+    walkthrough: Res<WalkthroughMode>,
+    mut egui_context: EguiContexts,
+    primary_windows: Query<Entity, With<PrimaryWindow>>,
+    mut quick_add: EventWriter<QuickAddAtCursor>,
+) {
+    let Some(egui_context) = primary_windows
+        .get_single()
+        .ok()
+        .and_then(|w| egui_context.try_ctx_for_window_mut(w))
+    else {
+        return;
+    };
+    if egui_context.wants_keyboard_input() || walkthrough.active {
+        return;
+    }
+
+    let keys = &settings.keybindings;
+    if keys.toggle_survey_mode.just_pressed(&keyboard_input) {
+        survey_mode.active = !survey_mode.active;
+        if survey_mode.active {
+            info!(
+                "Survey mode on: press [{}] for a door, [{}] for a charger, or [{}] \
+                for a parking spot at the cursor",
+                keys.quick_add_door, keys.quick_add_charger, keys.quick_add_parking_spot,
+            );
+        } else {
+            info!("Survey mode off");
+        }
+    }
+
+    if !survey_mode.active || egui_context.is_pointer_over_area() {
+        return;
+    }
+
+    if keys.quick_add_door.just_pressed(&keyboard_input) {
+        quick_add.send(QuickAddAtCursor(QuickAddKind::Door));
+    }
+    if keys.quick_add_charger.just_pressed(&keyboard_input) {
+        quick_add.send(QuickAddAtCursor(QuickAddKind::Charger));
+    }
+    if keys.quick_add_parking_spot.just_pressed(&keyboard_input) {
+        quick_add.send(QuickAddAtCursor(QuickAddKind::ParkingSpot));
+    }
+}
+
+#[derive(SystemParam)]
+struct QuickAddParams<'w, 's> {
+    commands: Commands<'w, 's>,
+    current_level: Res<'w, CurrentLevel>,
+    intersect_ground_params: IntersectGroundPlaneParams<'w, 's>,
+    names: Query<'w, 's, &'static NameInSite, Or<(With<DoorMarker>, With<LocationTags>)>>,
+}
+
+impl<'w, 's> QuickAddParams<'w, 's> {
+    fn spawn_anchor(&mut self, p: Vec2) -> Entity {
+        self.commands
+            .spawn(AnchorBundle::new(Anchor::Translate2D([p.x, p.y])))
+            .id()
+    }
+
+    fn quick_add(&mut self, kind: QuickAddKind, taken: &mut HashSet<String>) {
+        if self.current_level.0.is_none() {
+            warn!(
+                "Unable to add a {} because there is no current level",
+                kind.name_prefix()
+            );
+            return;
+        }
+        let Some(tf) = self.intersect_ground_params.ground_plane_intersection() else {
+            return;
+        };
+        let p = tf.translation.truncate();
+        let name = next_name(kind.name_prefix(), taken);
+
+        // Orphaned anchors, doors, and locations will be assigned to the
+        // current level and site automatically.
+        match kind {
+            QuickAddKind::Door => {
+                let offset = Vec2::new(QUICK_ADD_DOOR_WIDTH / 2.0, 0.0);
+                let a0 = self.spawn_anchor(p - offset);
+                let a1 = self.spawn_anchor(p + offset);
+                let mut door = Door::from(Edge::new(a0, a1));
+                door.name = NameInSite(name.clone());
+                let e = self.commands.spawn(door).id();
+                self.commands.add(ChangeDependent::add(a0, e));
+                self.commands.add(ChangeDependent::add(a1, e));
+            }
+            QuickAddKind::Charger | QuickAddKind::ParkingSpot => {
+                let anchor = self.spawn_anchor(p);
+                let tag = match kind {
+                    QuickAddKind::Charger => LocationTag::Charger,
+                    _ => LocationTag::ParkingSpot,
+                };
+                let mut location = Location::from(Point(anchor));
+                location.name = NameInSite(name.clone());
+                location.tags = LocationTags(vec![tag]);
+                let e = self.commands.spawn(location).id();
+                self.commands.add(ChangeDependent::add(anchor, e));
+            }
+        }
+        info!("Added {name} at ({:.2}, {:.2})", p.x, p.y);
+    }
+}
+
+/// The first name of the form `{prefix}_{n}` that is not taken yet. The new
+/// name gets added to the taken names.
+fn next_name(prefix: &str, taken: &mut HashSet<String>) -> String {
+    let mut n = 1;
+    loop {
+        let name = format!("{prefix}_{n}");
+        if taken.insert(name.clone()) {
+            return name;
+        }
+        n += 1;
+    }
+}
+
+fn quick_add_at_cursor(mut requests: EventReader<QuickAddAtCursor>, mut params: QuickAddParams) {
+    if requests.is_empty() {
+        return;
+    }
+
+    let mut taken: HashSet<String> = params.names.iter().map(|n| n.0.clone()).collect();
+    for QuickAddAtCursor(kind) in requests.read() {
+        params.quick_add(*kind, &mut taken);
+    }
+}
@@ -0,0 +1,330 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::site::{clipboard::CopyParams, *};
+use bevy::prelude::*;
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// Send this event to save the current selection into the [`TemplateLibrary`].
+#[derive(Event, Clone, Debug)]
+pub struct SaveSelectionAsTemplate {
+    pub name: String,
+}
+
+/// Send this event to remove a template from the [`TemplateLibrary`] and
+/// delete its file.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DeleteTemplate {
+    pub index: usize,
+}
+
+/// The templates that are available to stamp into sites. Each template is
+/// saved as its own `.template.ron` file inside of the library directory.
+///
+/// A template gets stamped by loading it into the [`Clipboard`] with
+/// [`clipboard_from_template`] and then pasting it as many times as needed.
+#[derive(Resource, Clone, Debug)]
+pub struct TemplateLibrary {
+    pub directory: Option<PathBuf>,
+    pub templates: Vec<(PathBuf, SiteTemplate)>,
+}
+
+impl Default for TemplateLibrary {
+    fn default() -> Self {
+        let directory = dirs::data_dir().map(|mut p| {
+            p.push("open-robotics");
+            p.push("rmf_site_editor");
+            p.push("templates");
+            p
+        });
+        let mut library = Self {
+            directory,
+            templates: Vec::new(),
+        };
+        library.reload();
+        library
+    }
+}
+
+impl TemplateLibrary {
+    /// Read every template inside of the library directory again.
+    pub fn reload(&mut self) {
+        self.templates.clear();
+        let Some(directory) = &self.directory else {
+            return;
+        };
+        let Ok(entries) = std::fs::read_dir(directory) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.to_string_lossy().ends_with(".template.ron") {
+                continue;
+            }
+            let template = match std::fs::read(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|data| SiteTemplate::from_bytes_ron(&data).map_err(|err| err.to_string()))
+            {
+                Ok(template) => template,
+                Err(err) => {
+                    warn!("Unable to load template {}: {err}", path.display());
+                    continue;
+                }
+            };
+            self.templates.push((path, template));
+        }
+        self.templates.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name));
+    }
+}
+
+pub struct TemplatePlugin;
+
+impl Plugin for TemplatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TemplateLibrary>()
+            .add_event::<SaveSelectionAsTemplate>()
+            .add_event::<DeleteTemplate>()
+            .add_systems(
+                Update,
+                (save_selection_as_template, delete_template)
+                    .chain()
+                    .run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+/// Convert copied elements into a template whose anchors are relative to
+/// the center of the copied elements. Models are not included because their
+/// descriptions belong to a specific site.
+pub fn template_from_clipboard(name: String, clipboard: &Clipboard) -> SiteTemplate {
+    let center = clipboard.center();
+    let mut template = SiteTemplate {
+        name,
+        ..Default::default()
+    };
+    // Anchors get the first IDs, and everything else follows
+    let anchor_id = |i: usize| i as u32;
+    let mut next_id = clipboard.anchors.len() as u32;
+    for (i, p) in clipboard.anchors.iter().enumerate() {
+        let p = *p - center;
+        template
+            .level
+            .anchors
+            .insert(anchor_id(i), Anchor::Translate2D([p.x, p.y]));
+    }
+    for wall in &clipboard.walls {
+        let [a0, a1] = wall.anchors.map(anchor_id);
+        let (_, height, alpha) = &wall.properties;
+        template.level.walls.insert(
+            next_id,
+            Wall {
+                anchors: Edge::new(a0, a1),
+                texture: Affiliation(None),
+                height: *height,
+                alpha: *alpha,
+                marker: WallMarker,
+            },
+        );
+        next_id += 1;
+    }
+    for door in &clipboard.doors {
+        let [a0, a1] = door.anchors.map(anchor_id);
+        let (name, kind, timing) = door.properties.clone();
+        template.level.doors.insert(
+            next_id,
+            Door {
+                anchors: Edge::new(a0, a1),
+                name,
+                kind,
+                timing,
+                emergency_exit: Default::default(),
+                marker: DoorMarker,
+            },
+        );
+        next_id += 1;
+    }
+    for lane in &clipboard.lanes {
+        let [a0, a1] = lane.anchors.map(anchor_id);
+        let (forward, reverse, _, curve) = lane.properties.clone();
+        template.lanes.insert(
+            next_id,
+            Lane {
+                anchors: Edge::new(a0, a1),
+                forward,
+                reverse,
+                graphs: AssociatedGraphs::All,
+                evacuation: Default::default(),
+                curve,
+                marker: LaneMarker,
+            },
+        );
+        next_id += 1;
+    }
+    template
+}
+
+/// Convert a template into clipboard contents so it can be pasted. Anchor
+/// IDs of the template are remapped into indices of [`Clipboard::anchors`].
+pub fn clipboard_from_template(template: &SiteTemplate) -> Clipboard {
+    let mut clipboard = Clipboard::default();
+    let mut indices = BTreeMap::new();
+    for (id, anchor) in &template.level.anchors {
+        indices.insert(*id, clipboard.anchors.len());
+        clipboard.anchors.push(Vec2::from(
+            anchor.translation_for_category(Category::General),
+        ));
+    }
+    let remap = |edge: &Edge<u32>| -> Option<[usize; 2]> {
+        let [a0, a1] = edge.array();
+        Some([*indices.get(&a0)?, *indices.get(&a1)?])
+    };
+
+    for wall in template.level.walls.values() {
+        let Some(anchors) = remap(&wall.anchors) else {
+            warn!(
+                "Template [{}] has a wall with a broken anchor",
+                template.name
+            );
+            continue;
+        };
+        clipboard.walls.push(ClipboardEdge {
+            anchors,
+            properties: (Affiliation(None), wall.height, wall.alpha),
+        });
+    }
+    for door in template.level.doors.values() {
+        let Some(anchors) = remap(&door.anchors) else {
+            warn!(
+                "Template [{}] has a door with a broken anchor",
+                template.name
+            );
+            continue;
+        };
+        clipboard.doors.push(ClipboardEdge {
+            anchors,
+            properties: (door.name.clone(), door.kind.clone(), door.timing),
+        });
+    }
+    for lane in template.lanes.values() {
+        let Some(anchors) = remap(&lane.anchors) else {
+            warn!(
+                "Template [{}] has a lane with a broken anchor",
+                template.name
+            );
+            continue;
+        };
+        clipboard.lanes.push(ClipboardEdge {
+            anchors,
+            properties: (
+                lane.forward.clone(),
+                lane.reverse.clone(),
+                AssociatedGraphs::All,
+                lane.curve,
+            ),
+        });
+    }
+    clipboard
+}
+
+fn save_selection_as_template(
+    mut requests: EventReader<SaveSelectionAsTemplate>,
+    copy: CopyParams,
+    mut library: ResMut<TemplateLibrary>,
+) {
+    for request in requests.read() {
+        let Some(clipboard) = copy.copy_selection() else {
+            continue;
+        };
+        let mut template = template_from_clipboard(request.name.clone(), &clipboard);
+        if template.is_empty() {
+            warn!("The selection does not contain anything that can be saved as a template");
+            continue;
+        }
+
+        let Some(directory) = library.directory.clone() else {
+            error!("Unable to save template because there is no data directory");
+            continue;
+        };
+        if let Err(err) = std::fs::create_dir_all(&directory) {
+            error!(
+                "Unable to create template folder {}: {err}",
+                directory.display()
+            );
+            continue;
+        }
+
+        // Never overwrite an existing template, give the new one a suffix
+        // instead.
+        let template_path = |name: &str| {
+            let file_name: String = name
+                .chars()
+                .map(|c| if c.is_alphanumeric() { c } else { '_' })
+                .collect();
+            directory.join(format!("{file_name}.template.ron"))
+        };
+        let mut suffix = 1;
+        while template_path(&template.name).exists()
+            || library
+                .templates
+                .iter()
+                .any(|(_, t)| t.name == template.name)
+        {
+            template.name = format!("{} ({suffix})", request.name);
+            suffix += 1;
+        }
+        if template.name != request.name {
+            warn!(
+                "A template named [{}] already exists, so the new template \
+                will be named [{}]",
+                request.name, template.name,
+            );
+        }
+        let path = template_path(&template.name);
+        let f = match std::fs::File::create(&path) {
+            Ok(f) => f,
+            Err(err) => {
+                error!("Unable to save template: {err}");
+                continue;
+            }
+        };
+        if let Err(err) = template.to_writer_ron(f) {
+            error!("Failed to save template: {err}");
+            continue;
+        }
+        info!("Saved template [{}] to {}", template.name, path.display());
+        library.reload();
+    }
+}
+
+fn delete_template(
+    mut requests: EventReader<DeleteTemplate>,
+    mut library: ResMut<TemplateLibrary>,
+) {
+    let mut changed = false;
+    for request in requests.read() {
+        let Some((path, _)) = library.templates.get(request.index) else {
+            continue;
+        };
+        if let Err(err) = std::fs::remove_file(path) {
+            error!("Unable to delete template {}: {err}", path.display());
+        }
+        changed = true;
+    }
+    if changed {
+        library.reload();
+    }
+}
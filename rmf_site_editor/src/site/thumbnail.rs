@@ -0,0 +1,486 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+use crate::{
+    site::{Anchor, CurrentLevel, LevelElevation, ModelLoadingState, NameInSite, NameOfSite},
+    Autoload, WorkspaceLoader,
+};
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{RenderTarget, ScalingMode},
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        render_asset::RenderAssets,
+        render_resource::{
+            BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d, ImageCopyBuffer,
+            ImageDataLayout, MapMode, TextureDescriptor, TextureDimension, TextureFormat,
+            TextureUsages, COPY_BYTES_PER_ROW_ALIGNMENT,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        Render, RenderApp, RenderSet,
+    },
+};
+use crossbeam_channel::{Receiver, Sender};
+use std::{collections::VecDeque, path::PathBuf};
+
+/// Send this event to render a top-down orthographic image of a level and
+/// write it to a PNG file. The level is framed by the extents of its anchors.
+#[derive(Event, Debug, Clone)]
+pub struct RenderThumbnail {
+    pub level: Entity,
+    pub path: PathBuf,
+    pub resolution: UVec2,
+}
+
+/// Sent after a thumbnail was written, or failed to be written.
+#[derive(Event, Debug, Clone)]
+pub struct ThumbnailFinished {
+    pub level: Entity,
+    pub path: PathBuf,
+    pub result: Result<(), String>,
+}
+
+/// How many frames the thumbnail camera renders before its image gets copied.
+/// This gives meshes and materials of the level a chance to reach the GPU.
+const THUMBNAIL_WARMUP_FRAMES: u32 = 3;
+
+#[derive(Default)]
+pub struct ThumbnailPlugin {}
+
+impl Plugin for ThumbnailPlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        app.add_event::<RenderThumbnail>()
+            .add_event::<ThumbnailFinished>()
+            .init_resource::<ThumbnailQueue>()
+            .init_resource::<ThumbnailCaptures>()
+            .insert_resource(ThumbnailResults(receiver))
+            .add_plugins(ExtractResourcePlugin::<ThumbnailCaptures>::default())
+            .add_systems(
+                Update,
+                (
+                    queue_thumbnails,
+                    start_next_thumbnail,
+                    advance_thumbnail_capture,
+                    finish_thumbnails,
+                )
+                    .chain(),
+            );
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(ThumbnailSender(sender))
+                .add_systems(Render, copy_thumbnails.in_set(RenderSet::Cleanup));
+        }
+    }
+}
+
+/// Requests that are waiting for the current capture to finish. Levels are
+/// only visible while they are the current level, so thumbnails are rendered
+/// one at a time.
+#[derive(Resource, Default)]
+struct ThumbnailQueue {
+    pending: VecDeque<RenderThumbnail>,
+    active: Option<ActiveThumbnail>,
+    /// The level that was current before the queue started, which gets
+    /// restored once the queue is empty.
+    restore_level: Option<Option<Entity>>,
+}
+
+struct ActiveThumbnail {
+    request: RenderThumbnail,
+    camera: Entity,
+    image: Handle<Image>,
+    frames: u32,
+    copy_requested: bool,
+}
+
+/// Images that should be copied back from the GPU during this frame.
+#[derive(Resource, ExtractResource, Clone, Default)]
+struct ThumbnailCaptures(Vec<(Handle<Image>, PathBuf)>);
+
+#[derive(Resource)]
+struct ThumbnailSender(Sender<(PathBuf, Result<(), String>)>);
+
+#[derive(Resource)]
+struct ThumbnailResults(Receiver<(PathBuf, Result<(), String>)>);
+
+fn queue_thumbnails(mut requests: EventReader<RenderThumbnail>, mut queue: ResMut<ThumbnailQueue>) {
+    queue.pending.extend(requests.read().cloned());
+}
+
+fn start_next_thumbnail(
+    mut commands: Commands,
+    mut queue: ResMut<ThumbnailQueue>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut images: ResMut<Assets<Image>>,
+    mut finished: EventWriter<ThumbnailFinished>,
+    levels: Query<(&LevelElevation, Option<&Children>)>,
+    anchors: Query<&GlobalTransform, With<Anchor>>,
+) {
+    if queue.active.is_some() {
+        return;
+    }
+
+    let Some(request) = queue.pending.pop_front() else {
+        if let Some(level) = queue.restore_level.take() {
+            current_level.0 = level;
+        }
+        return;
+    };
+
+    let Ok((elevation, children)) = levels.get(request.level) else {
+        finished.send(ThumbnailFinished {
+            level: request.level,
+            path: request.path,
+            result: Err("The requested entity is not a level".to_owned()),
+        });
+        return;
+    };
+
+    let mut min = Vec2::splat(f32::INFINITY);
+    let mut max = Vec2::splat(f32::NEG_INFINITY);
+    for child in children.into_iter().flatten() {
+        if let Ok(tf) = anchors.get(*child) {
+            let p = tf.translation().truncate();
+            min = min.min(p);
+            max = max.max(p);
+        }
+    }
+    if min.x > max.x {
+        // The level has no anchors, so just show the area around its origin.
+        min = Vec2::splat(-10.0);
+        max = Vec2::splat(10.0);
+    }
+    let margin = 1.0;
+    let center = (min + max) / 2.0;
+    let extent = (max - min) + Vec2::splat(2.0 * margin);
+
+    let resolution = request.resolution.max(UVec2::ONE);
+    let size = Extent3d {
+        width: resolution.x,
+        height: resolution.y,
+        depth_or_array_layers: 1,
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("thumbnail"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..default()
+    };
+    image.resize(size);
+    let image = images.add(image);
+
+    // Look straight down from above the level with +Y pointing up in the image.
+    let height = **elevation + 100.0;
+    let camera = commands
+        .spawn(Camera3dBundle {
+            transform: Transform::from_xyz(center.x, center.y, height)
+                .looking_at(Vec3::new(center.x, center.y, **elevation), Vec3::Y),
+            camera: Camera {
+                target: RenderTarget::Image(image.clone()),
+                order: -1,
+                ..default()
+            },
+            projection: Projection::Orthographic(OrthographicProjection {
+                scaling_mode: ScalingMode::AutoMin {
+                    min_width: extent.x,
+                    min_height: extent.y,
+                },
+                far: 1000.0,
+                ..default()
+            }),
+            ..default()
+        })
+        .id();
+
+    if queue.restore_level.is_none() {
+        queue.restore_level = Some(current_level.0);
+    }
+    if current_level.0 != Some(request.level) {
+        current_level.0 = Some(request.level);
+    }
+
+    queue.active = Some(ActiveThumbnail {
+        request,
+        camera,
+        image,
+        frames: 0,
+        copy_requested: false,
+    });
+}
+
+fn advance_thumbnail_capture(
+    mut queue: ResMut<ThumbnailQueue>,
+    mut captures: ResMut<ThumbnailCaptures>,
+) {
+    // A capture only needs to be extracted into the render world once.
+    captures.0.clear();
+    let Some(active) = &mut queue.active else {
+        return;
+    };
+    if active.copy_requested {
+        return;
+    }
+
+    active.frames += 1;
+    if active.frames > THUMBNAIL_WARMUP_FRAMES {
+        captures
+            .0
+            .push((active.image.clone(), active.request.path.clone()));
+        active.copy_requested = true;
+    }
+}
+
+fn finish_thumbnails(
+    mut commands: Commands,
+    mut queue: ResMut<ThumbnailQueue>,
+    mut images: ResMut<Assets<Image>>,
+    results: Res<ThumbnailResults>,
+    mut finished: EventWriter<ThumbnailFinished>,
+) {
+    while let Ok((path, result)) = results.0.try_recv() {
+        if queue
+            .active
+            .as_ref()
+            .map_or(true, |a| a.request.path != path)
+        {
+            continue;
+        }
+        let Some(active) = queue.active.take() else {
+            continue;
+        };
+        match &result {
+            Ok(()) => info!("Saved thumbnail to {path:?}"),
+            Err(err) => error!("Unable to save thumbnail to {path:?}: {err}"),
+        }
+        commands.entity(active.camera).despawn_recursive();
+        images.remove(&active.image);
+        finished.send(ThumbnailFinished {
+            level: active.request.level,
+            path,
+            result,
+        });
+    }
+}
+
+/// Copy the images of thumbnail cameras into buffers that the CPU can read,
+/// then encode them as PNG files. This runs after the frame was rendered, so
+/// the copy sees the finished image.
+fn copy_thumbnails(
+    captures: Option<Res<ThumbnailCaptures>>,
+    gpu_images: Res<RenderAssets<Image>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    sender: Res<ThumbnailSender>,
+) {
+    let Some(captures) = captures else {
+        return;
+    };
+    for (handle, path) in &captures.0 {
+        let result = match gpu_images.get(handle) {
+            Some(gpu_image) => {
+                let texture = &gpu_image.texture;
+                let width = gpu_image.size.x as u32;
+                let height = gpu_image.size.y as u32;
+                copy_texture(&render_device, &render_queue, texture, width, height)
+                    .and_then(|data| write_png(data, width, height, path))
+            }
+            None => Err("The thumbnail image was never sent to the GPU".to_owned()),
+        };
+        sender.0.send((path.clone(), result)).ok();
+    }
+}
+
+fn copy_texture(
+    render_device: &RenderDevice,
+    render_queue: &RenderQueue,
+    texture: &bevy::render::render_resource::Texture,
+    width: u32,
+    height: u32,
+) -> Result<Vec<u8>, String> {
+    let unpadded_row = width as usize * 4;
+    let align = COPY_BYTES_PER_ROW_ALIGNMENT as usize;
+    let padded_row = (unpadded_row + align - 1) / align * align;
+
+    let buffer = render_device.create_buffer(&BufferDescriptor {
+        label: Some("thumbnail_readback"),
+        size: (padded_row * height as usize) as u64,
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("thumbnail_readback"),
+    });
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        ImageCopyBuffer {
+            buffer: &buffer,
+            layout: ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row as u32),
+                rows_per_image: None,
+            },
+        },
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+    render_queue.submit([encoder.finish()]);
+
+    let slice = buffer.slice(..);
+    let (tx, rx) = crossbeam_channel::bounded(1);
+    slice.map_async(MapMode::Read, move |result| {
+        tx.send(result).ok();
+    });
+    render_device
+        .wgpu_device()
+        .poll(bevy::render::render_resource::Maintain::Wait);
+    rx.recv()
+        .map_err(|err| err.to_string())?
+        .map_err(|err| err.to_string())?;
+
+    let mapped = slice.get_mapped_range();
+    let mut data = Vec::with_capacity(unpadded_row * height as usize);
+    for row in mapped.chunks(padded_row) {
+        data.extend_from_slice(&row[..unpadded_row]);
+    }
+    drop(mapped);
+    buffer.unmap();
+    Ok(data)
+}
+
+fn write_png(data: Vec<u8>, width: u32, height: u32, path: &PathBuf) -> Result<(), String> {
+    let image = Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    let image = image.try_into_dynamic().map_err(|err| err.to_string())?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    image.to_rgba8().save(path).map_err(|err| err.to_string())
+}
+
+/// The file name of the thumbnail of a level.
+pub fn thumbnail_file_name(level_name: &str) -> String {
+    let name: String = level_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{name}.png")
+}
+
+/// Manages a simple state machine for rendering thumbnails from the command
+/// line where we:
+///   * Load the site and wait for its models.
+///   * Request a thumbnail for every level.
+///   * Exit once every thumbnail is finished.
+#[derive(Debug, Resource)]
+pub struct HeadlessThumbnailState {
+    iterations: u32,
+    requested: Option<usize>,
+    finished: usize,
+    folder: PathBuf,
+    resolution: UVec2,
+}
+
+impl HeadlessThumbnailState {
+    pub fn new(folder: impl Into<PathBuf>, resolution: UVec2) -> Self {
+        Self {
+            iterations: 0,
+            requested: None,
+            finished: 0,
+            folder: folder.into(),
+            resolution,
+        }
+    }
+}
+
+pub fn headless_thumbnails(
+    mut state: ResMut<HeadlessThumbnailState>,
+    mut exit: EventWriter<bevy::app::AppExit>,
+    mut render: EventWriter<RenderThumbnail>,
+    mut finished: EventReader<ThumbnailFinished>,
+    missing_models: Query<(), With<ModelLoadingState>>,
+    sites: Query<&Children, With<NameOfSite>>,
+    levels: Query<&NameInSite, With<LevelElevation>>,
+    autoload: Option<ResMut<Autoload>>,
+    mut workspace_loader: WorkspaceLoader,
+) {
+    if let Some(mut autoload) = autoload {
+        if let Some(filename) = autoload.filename.take() {
+            workspace_loader.load_from_path(filename);
+        }
+    } else {
+        error!("Cannot render thumbnails since no site file was specified for loading");
+        exit.send(bevy::app::AppExit);
+        return;
+    }
+
+    state.iterations += 1;
+    if let Some(requested) = state.requested {
+        state.finished += finished.read().count();
+        if state.finished >= requested {
+            exit.send(bevy::app::AppExit);
+        }
+        return;
+    }
+
+    if state.iterations < 5 || !missing_models.is_empty() {
+        return;
+    }
+    let Ok(children) = sites.get_single() else {
+        warn!(
+            "No site is loaded so we cannot render thumbnails into [{:?}]",
+            state.folder,
+        );
+        exit.send(bevy::app::AppExit);
+        return;
+    };
+
+    let mut requested = 0;
+    for child in children {
+        let Ok(name) = levels.get(*child) else {
+            continue;
+        };
+        render.send(RenderThumbnail {
+            level: *child,
+            path: state.folder.join(thumbnail_file_name(name)),
+            resolution: state.resolution,
+        });
+        requested += 1;
+    }
+    state.requested = Some(requested);
+}
@@ -0,0 +1,396 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{shapes::make_cylinder, site::*, AppState};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+/// The longest step that the preview takes at once.
+const MAX_PREVIEW_STEP: f32 = 0.1;
+const ROBOT_RADIUS: f32 = 0.3;
+const ROBOT_HEIGHT: f32 = 0.4;
+
+/// Send this event to start or stop previewing robot traffic on the nav
+/// graphs of the current level.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ControlTrafficPreview {
+    Start,
+    Stop,
+}
+
+/// The traffic preview that is currently running, if any.
+#[derive(Resource)]
+pub struct TrafficPreviewState {
+    pub preview: Option<TrafficPreview>,
+    /// The level that the preview is running on
+    pub level: Option<Entity>,
+    /// The anchors that robots start from when the preview is started
+    pub spawn_points: Vec<Entity>,
+    /// The anchor of each waypoint of the running preview
+    pub waypoints: Vec<Entity>,
+    pub paused: bool,
+    /// How many times faster than real time the preview runs
+    pub speed: f32,
+    /// How fast robots drive where lanes do not limit them, in meters per
+    /// second
+    pub robot_speed: f32,
+}
+
+impl Default for TrafficPreviewState {
+    fn default() -> Self {
+        Self {
+            preview: None,
+            level: None,
+            spawn_points: Vec::new(),
+            waypoints: Vec::new(),
+            paused: false,
+            speed: 1.0,
+            robot_speed: 0.7,
+        }
+    }
+}
+
+impl TrafficPreviewState {
+    pub fn is_running(&self) -> bool {
+        self.preview.is_some()
+    }
+}
+
+/// Marks the visual of a robot of the traffic preview. The value is the index
+/// of the robot inside of the preview.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct TrafficPreviewRobot(pub usize);
+
+#[derive(Resource)]
+pub struct TrafficPreviewAssets {
+    pub robot_mesh: Handle<Mesh>,
+    pub driving_material: Handle<StandardMaterial>,
+    pub waiting_material: Handle<StandardMaterial>,
+    pub deadlocked_material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for TrafficPreviewAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let robot_mesh = meshes.add(Mesh::from(make_cylinder(ROBOT_HEIGHT, ROBOT_RADIUS)));
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let driving_material = materials.add(old_default_material(Color::rgb(0.1, 0.4, 0.9)));
+        let waiting_material = materials.add(old_default_material(Color::rgb(0.95, 0.8, 0.1)));
+        let deadlocked_material = materials.add(old_default_material(Color::rgb(0.9, 0.1, 0.1)));
+        Self {
+            robot_mesh,
+            driving_material,
+            waiting_material,
+            deadlocked_material,
+        }
+    }
+}
+
+/// Animate placeholder robots along the nav graphs of a level to give quick
+/// feedback about the design of the graphs, such as corridors where robots
+/// get stuck facing each other.
+#[derive(Default)]
+pub struct TrafficPreviewPlugin;
+
+impl Plugin for TrafficPreviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ControlTrafficPreview>()
+            .init_resource::<TrafficPreviewState>()
+            .init_resource::<TrafficPreviewAssets>()
+            .add_systems(
+                Update,
+                (
+                    control_traffic_preview,
+                    update_traffic_preview,
+                    draw_traffic_conflicts,
+                )
+                    .chain()
+                    .run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+pub(crate) type TrafficLanes<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Edge<Entity>,
+        &'static Motion,
+        &'static ReverseLane,
+        &'static AssociatedGraphs<Entity>,
+    ),
+    (With<LaneMarker>, Without<HumanLaneMarker>),
+>;
+
+/// The robot lanes of one level gathered into a [`TrafficGraph`].
+pub(crate) struct LevelTrafficGraph {
+    pub(crate) graph: TrafficGraph,
+    /// The anchor of each waypoint of the graph
+    pub(crate) waypoints: Vec<Entity>,
+    /// The waypoint of each anchor of the graph
+    pub(crate) nodes: HashMap<Entity, usize>,
+}
+
+impl LevelTrafficGraph {
+    /// Gather the lanes of a level. When a nav graph is given, only the lanes
+    /// that belong to it are included. Lanes are children of the site, so a
+    /// lane is on the level that both of its anchors belong to.
+    pub(crate) fn new(
+        level: Entity,
+        nav_graph: Option<Entity>,
+        lanes: &TrafficLanes,
+        anchors: &Query<(&Anchor, &Parent)>,
+    ) -> Self {
+        let mut waypoints = Vec::new();
+        let mut points = Vec::new();
+        let mut nodes: HashMap<Entity, usize> = HashMap::new();
+        let mut traffic_lanes = Vec::new();
+        for (edge, forward, reverse, associated) in lanes {
+            let on_level = |anchor: Entity| {
+                anchors
+                    .get(anchor)
+                    .is_ok_and(|(_, parent)| parent.get() == level)
+            };
+            if !on_level(edge.start()) || !on_level(edge.end()) {
+                continue;
+            }
+            if nav_graph.is_some_and(|g| !associated.includes(g)) {
+                continue;
+            }
+            let mut node = |anchor: Entity| -> Option<usize> {
+                if let Some(index) = nodes.get(&anchor) {
+                    return Some(*index);
+                }
+                let p = anchors
+                    .get(anchor)
+                    .ok()?
+                    .0
+                    .translation_for_category(Category::Lane);
+                points.push(Vec2::from(p));
+                waypoints.push(anchor);
+                nodes.insert(anchor, points.len() - 1);
+                Some(points.len() - 1)
+            };
+            let (Some(start), Some(end)) = (node(edge.start()), node(edge.end())) else {
+                continue;
+            };
+            traffic_lanes.push(TrafficLane {
+                start,
+                end,
+                speed_limit: forward.speed_limit,
+            });
+            let reverse_limit = match reverse {
+                ReverseLane::Same => Some(forward.speed_limit),
+                ReverseLane::Different(motion) => Some(motion.speed_limit),
+                ReverseLane::Disable => None,
+            };
+            if let Some(speed_limit) = reverse_limit {
+                traffic_lanes.push(TrafficLane {
+                    start: end,
+                    end: start,
+                    speed_limit,
+                });
+            }
+        }
+
+        Self {
+            graph: TrafficGraph::new(points, traffic_lanes),
+            waypoints,
+            nodes,
+        }
+    }
+}
+
+fn control_traffic_preview(
+    mut commands: Commands,
+    mut control: EventReader<ControlTrafficPreview>,
+    mut state: ResMut<TrafficPreviewState>,
+    current_level: Res<CurrentLevel>,
+    lanes: TrafficLanes,
+    anchors: Query<(&Anchor, &Parent)>,
+    robots: Query<Entity, With<TrafficPreviewRobot>>,
+    assets: Res<TrafficPreviewAssets>,
+) {
+    let Some(control) = control.read().last() else {
+        return;
+    };
+
+    for robot in &robots {
+        commands.entity(robot).despawn_recursive();
+    }
+    state.preview = None;
+    state.level = None;
+    state.waypoints.clear();
+
+    if *control == ControlTrafficPreview::Stop {
+        return;
+    }
+    let Some(level) = current_level.0 else {
+        return;
+    };
+
+    let LevelTrafficGraph {
+        graph,
+        waypoints,
+        nodes,
+    } = LevelTrafficGraph::new(level, None, &lanes, &anchors);
+    let spawn: Vec<usize> = state
+        .spawn_points
+        .iter()
+        .filter_map(|anchor| nodes.get(anchor).copied())
+        .collect();
+    let preview = TrafficPreview::new(graph, &spawn, state.robot_speed, level.to_bits());
+    for (i, robot) in preview.robots.iter().enumerate() {
+        commands
+            .spawn((
+                PbrBundle {
+                    mesh: assets.robot_mesh.clone(),
+                    material: assets.driving_material.clone(),
+                    transform: robot_transform(robot),
+                    ..default()
+                },
+                TrafficPreviewRobot(i),
+            ))
+            .set_parent(level);
+    }
+    state.preview = Some(preview);
+    state.level = Some(level);
+    state.waypoints = waypoints;
+}
+
+fn update_traffic_preview(
+    mut state: ResMut<TrafficPreviewState>,
+    mut control: EventWriter<ControlTrafficPreview>,
+    current_level: Res<CurrentLevel>,
+    time: Res<Time>,
+    assets: Res<TrafficPreviewAssets>,
+    mut robots: Query<(
+        &TrafficPreviewRobot,
+        &mut Transform,
+        &mut Handle<StandardMaterial>,
+    )>,
+) {
+    if !state.is_running() {
+        return;
+    }
+    if state.level != current_level.0 {
+        // The preview only covers one level, so stop it when the user moves
+        // to a different one.
+        control.send(ControlTrafficPreview::Stop);
+        return;
+    }
+
+    let state = &mut *state;
+    let Some(preview) = &mut state.preview else {
+        return;
+    };
+    if !state.paused {
+        let mut remaining = time.delta_seconds() * state.speed;
+        while remaining > 0.0 {
+            let dt = remaining.min(MAX_PREVIEW_STEP);
+            preview.step(dt);
+            remaining -= dt;
+        }
+    }
+
+    let deadlocked = preview.deadlocked();
+    for (index, mut tf, mut material) in &mut robots {
+        let Some(robot) = preview.robots.get(index.0) else {
+            continue;
+        };
+        *tf = robot_transform(robot);
+        let expected = if deadlocked.contains(&index.0) {
+            &assets.deadlocked_material
+        } else if robot.waiting_for.is_some() {
+            &assets.waiting_material
+        } else {
+            &assets.driving_material
+        };
+        if *material != *expected {
+            *material = expected.clone();
+        }
+    }
+}
+
+/// Highlight the lanes where robots had to wait for robots coming the other
+/// way, since those are likely to deadlock in a real deployment.
+fn draw_traffic_conflicts(
+    state: Res<TrafficPreviewState>,
+    levels: Query<&GlobalTransform>,
+    mut gizmos: Gizmos,
+) {
+    let (Some(preview), Some(level)) = (&state.preview, state.level) else {
+        return;
+    };
+    let Ok(level_tf) = levels.get(level) else {
+        return;
+    };
+    for (a, b) in &preview.head_on {
+        let (Some(p0), Some(p1)) = (preview.graph.points.get(*a), preview.graph.points.get(*b))
+        else {
+            continue;
+        };
+        gizmos.line(
+            level_tf.transform_point(p0.extend(0.05)),
+            level_tf.transform_point(p1.extend(0.05)),
+            Color::RED,
+        );
+    }
+}
+
+fn robot_transform(robot: &PreviewRobot) -> Transform {
+    Transform::from_translation(robot.position.extend(ROBOT_HEIGHT / 2.0))
+}
+
+#[test]
+fn test_level_traffic_graph_finds_lanes_between_level_anchors() {
+    use bevy::ecs::system::SystemState;
+
+    let mut world = World::new();
+    let site = world.spawn_empty().id();
+    let level = world.spawn_empty().set_parent(site).id();
+    let start = world
+        .spawn(Anchor::Translate2D([0.0, 0.0]))
+        .set_parent(level)
+        .id();
+    let end = world
+        .spawn(Anchor::Translate2D([5.0, 0.0]))
+        .set_parent(level)
+        .id();
+    // Lanes are saved as children of the site, not of their level.
+    world
+        .spawn((
+            LaneMarker,
+            Edge::new(start, end),
+            Motion::default(),
+            ReverseLane::Same,
+            AssociatedGraphs::<Entity>::All,
+        ))
+        .set_parent(site);
+
+    let mut state: SystemState<(TrafficLanes, Query<(&Anchor, &Parent)>)> =
+        SystemState::new(&mut world);
+    let (lanes, anchors) = state.get(&world);
+    let traffic = LevelTrafficGraph::new(level, None, &lanes, &anchors);
+    assert_eq!(traffic.waypoints, vec![start, end]);
+    assert_eq!(traffic.graph.lanes.len(), 2);
+
+    let other_level = world.spawn_empty().set_parent(site).id();
+    let (lanes, anchors) = state.get(&world);
+    let traffic = LevelTrafficGraph::new(other_level, None, &lanes, &anchors);
+    assert!(traffic.graph.lanes.is_empty());
+}
@@ -18,7 +18,7 @@
 use crate::site::*;
 use crate::CurrentWorkspace;
 use bevy::prelude::*;
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::path::PathBuf;
 
 pub fn line_stroke_transform(p_start: &Vec3, p_end: &Vec3, width: f32) -> Transform {
@@ -81,11 +81,13 @@ impl EdgeLabels {
     }
 }
 
+/// Ordered so that anything which walks over the dependents of an element,
+/// such as deletion or saving, always visits them in the same order.
 #[derive(Component, Debug, Default, Clone, Deref, DerefMut)]
-pub struct Dependents(pub HashSet<Entity>);
+pub struct Dependents(pub BTreeSet<Entity>);
 
 impl Dependents {
     pub fn single(dependent: Entity) -> Self {
-        Dependents(HashSet::from_iter([dependent]))
+        Dependents(BTreeSet::from_iter([dependent]))
     }
 }
@@ -0,0 +1,491 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, AppState};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use std::collections::{HashMap, HashSet};
+
+/// The default distance, in meters, within which two vertices are treated as
+/// the same vertex.
+pub const DEFAULT_MERGE_EPSILON: f32 = 0.01;
+
+/// Send this event to clean up the vertices of a site. Vertices on the same
+/// level that are within `epsilon` meters of each other get merged into one,
+/// lanes and walls that become identical or zero-length are removed, and
+/// vertices that nothing refers to anymore are deleted. A summary of what
+/// happened is stored in [`LastVertexCleanup`].
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CleanupVertices {
+    pub site: Entity,
+    pub epsilon: f32,
+}
+
+/// The changes made by a [`CleanupVertices`] request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VertexCleanupReport {
+    /// How many vertices were merged into another vertex
+    pub merged_vertices: usize,
+    pub duplicate_lanes: usize,
+    pub duplicate_walls: usize,
+    /// Lanes, walls, doors, and measurements whose two ends became the same
+    /// vertex
+    pub degenerate_edges: usize,
+    /// Vertices that were deleted because nothing refers to them
+    pub orphaned_vertices: usize,
+}
+
+impl VertexCleanupReport {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "Nothing needed to be cleaned up".to_owned();
+        }
+        format!(
+            "Merged {} vertices, removed {} duplicate lanes, {} duplicate walls, \
+            {} zero-length elements, and {} unused vertices",
+            self.merged_vertices,
+            self.duplicate_lanes,
+            self.duplicate_walls,
+            self.degenerate_edges,
+            self.orphaned_vertices,
+        )
+    }
+}
+
+/// The report of the most recent [`CleanupVertices`] request, if there was one.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct LastVertexCleanup(pub Option<VertexCleanupReport>);
+
+#[derive(Default)]
+pub struct VertexCleanupPlugin;
+
+impl Plugin for VertexCleanupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CleanupVertices>()
+            .init_resource::<LastVertexCleanup>()
+            .add_systems(
+                Update,
+                cleanup_vertices.run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+/// Find the vertices of a site that are close enough to each other to be
+/// merged.
+#[derive(SystemParam)]
+pub struct FindDuplicateAnchors<'w, 's> {
+    anchors: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Anchor,
+            &'static Parent,
+            Option<&'static Dependents>,
+        ),
+        Without<Pending>,
+    >,
+    levels: Query<'w, 's, &'static Parent, With<LevelElevation>>,
+    crowd_sims: Query<'w, 's, &'static CrowdSim<Entity>>,
+}
+
+impl<'w, 's> FindDuplicateAnchors<'w, 's> {
+    /// Each group that is returned has at least two vertices. The first
+    /// vertex of a group is the one that the others get merged into, which is
+    /// the vertex that the most elements already refer to. Every other vertex
+    /// of the group is within `epsilon` of the first one.
+    pub fn find(&self, site: Entity, epsilon: f32) -> Vec<Vec<Entity>> {
+        let epsilon = epsilon.max(f32::EPSILON);
+        // Goals of the crowd simulation refer to anchors without being their
+        // dependents, so those anchors are left alone.
+        let goals: HashSet<Entity> = self
+            .crowd_sims
+            .get(site)
+            .map(|crowd_sim| {
+                crowd_sim
+                    .goal_sets
+                    .iter()
+                    .flat_map(|g| g.anchors.iter().copied())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Only vertices that belong directly to a level are considered.
+        // Drawings and lift cabins manage their own anchors.
+        let mut candidates: Vec<(Entity, Entity, Vec2, usize)> = Vec::new();
+        for (e, anchor, parent, deps) in &self.anchors {
+            let Anchor::Translate2D(p) = anchor else {
+                continue;
+            };
+            if goals.contains(&e) {
+                continue;
+            }
+            if !self.levels.get(parent.get()).is_ok_and(|p| p.get() == site) {
+                continue;
+            }
+            let deps = deps.map_or(0, |d| d.len());
+            candidates.push((e, parent.get(), Vec2::from(*p), deps));
+        }
+        candidates.sort_by_key(|(e, ..)| *e);
+        // The sort is stable, so ties go to the oldest vertex
+        let mut by_priority: Vec<usize> = (0..candidates.len()).collect();
+        by_priority.sort_by_key(|i| std::cmp::Reverse(candidates[*i].3));
+
+        let cell_of = |p: Vec2| (p / epsilon).floor().as_ivec2();
+        let mut grid: HashMap<(Entity, IVec2), Vec<usize>> = HashMap::new();
+        for (i, (_, level, p, _)) in candidates.iter().enumerate() {
+            grid.entry((*level, cell_of(*p))).or_default().push(i);
+        }
+
+        // Vertices are only grouped with a vertex that gets kept, so a chain
+        // of vertices that are each close to the next one is not collapsed
+        // into one vertex that is far away from the ends of the chain.
+        let mut grouped = vec![false; candidates.len()];
+        let mut groups = Vec::new();
+        for keep in by_priority {
+            if grouped[keep] {
+                continue;
+            }
+            grouped[keep] = true;
+            let (_, level, p, _) = candidates[keep];
+            let cell = cell_of(p);
+            let mut group = vec![keep];
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    let Some(others) = grid.get(&(level, cell + IVec2::new(dx, dy))) else {
+                        continue;
+                    };
+                    for j in others.iter().copied() {
+                        if !grouped[j] && p.distance(candidates[j].2) <= epsilon {
+                            grouped[j] = true;
+                            group.push(j);
+                        }
+                    }
+                }
+            }
+            if group.len() > 1 {
+                group[1..].sort_by_key(|i| (std::cmp::Reverse(candidates[*i].3), *i));
+                groups.push(group.into_iter().map(|i| candidates[i].0).collect());
+            }
+        }
+        groups
+    }
+}
+
+fn cleanup_vertices(
+    mut requests: EventReader<CleanupVertices>,
+    mut params: ParamSet<(FindDuplicateAnchors, FindOrphans, Query<&mut Dependents>)>,
+    mut edges: Query<&mut Edge<Entity>>,
+    mut paths: Query<&mut Path<Entity>>,
+    mut points: Query<&mut Point<Entity>>,
+    lanes: Query<
+        (
+            &Motion,
+            &ReverseLane,
+            &AssociatedGraphs<Entity>,
+            Option<&LaneCurve>,
+            Has<HumanLaneMarker>,
+        ),
+        With<LaneMarker>,
+    >,
+    walls: Query<(), With<WallMarker>>,
+    mut last_cleanup: ResMut<LastVertexCleanup>,
+    mut delete: EventWriter<Delete>,
+) {
+    for CleanupVertices { site, epsilon } in requests.read() {
+        let mut summary = VertexCleanupReport::default();
+        let groups = params.p0().find(*site, *epsilon);
+        let already_unused = params.p1().find(*site).anchors;
+        let mut dependents = params.p2();
+
+        // Decide which vertex each duplicate gets merged into. A vertex that
+        // is used by something other than a lane, wall, floor, or any other
+        // element that refers to it by an edge, path, or point cannot be
+        // merged safely.
+        let mut merge_into: HashMap<Entity, Entity> = HashMap::new();
+        for group in groups {
+            let keep = group[0];
+            for duplicate in group.into_iter().skip(1) {
+                let mergeable = dependents.get(duplicate).map_or(true, |deps| {
+                    deps.iter()
+                        .all(|d| edges.contains(*d) || paths.contains(*d) || points.contains(*d))
+                });
+                if mergeable {
+                    merge_into.insert(duplicate, keep);
+                }
+            }
+        }
+
+        let mut changed_elements: HashSet<Entity> = HashSet::new();
+        for (duplicate, keep) in &merge_into {
+            let Ok(mut deps) = dependents.get_mut(*duplicate) else {
+                continue;
+            };
+            let moved = std::mem::take(&mut deps.0);
+            for element in &moved {
+                if let Ok(mut edge) = edges.get_mut(*element) {
+                    for anchor in edge.array_mut() {
+                        if *anchor == *duplicate {
+                            *anchor = *keep;
+                        }
+                    }
+                }
+                if let Ok(mut path) = paths.get_mut(*element) {
+                    for anchor in path.0.iter_mut() {
+                        if *anchor == *duplicate {
+                            *anchor = *keep;
+                        }
+                    }
+                    // Merged corners should not appear twice in a row
+                    path.0.dedup();
+                    if path.0.len() > 1 && path.0.first() == path.0.last() {
+                        path.0.pop();
+                    }
+                }
+                if let Ok(mut point) = points.get_mut(*element) {
+                    if point.0 == *duplicate {
+                        point.0 = *keep;
+                    }
+                }
+            }
+            if let Ok(mut keep_deps) = dependents.get_mut(*keep) {
+                keep_deps.extend(moved.iter().copied());
+            }
+            changed_elements.extend(moved);
+            delete.send(Delete::new(*duplicate));
+        }
+        summary.merged_vertices = merge_into.len();
+
+        // Look for lanes and walls that merging made redundant
+        let mut removed: HashSet<Entity> = HashSet::new();
+        let mut changed_elements: Vec<Entity> = changed_elements.into_iter().collect();
+        changed_elements.sort();
+        let neighbors = |anchor: Entity| -> Vec<Entity> {
+            dependents
+                .get(anchor)
+                .map(|deps| deps.iter().copied().collect())
+                .unwrap_or_default()
+        };
+        for element in &changed_elements {
+            let Ok(edge) = edges.get(*element) else {
+                continue;
+            };
+            if edge.left() == edge.right() {
+                removed.insert(*element);
+                summary.degenerate_edges += 1;
+                continue;
+            }
+            // Compare against every element that touches the same vertex,
+            // including elements that were not changed by the merge. Of a
+            // stack of identical elements, only the last one visited stays.
+            let is_duplicate = |same: &dyn Fn(Entity, &Edge<Entity>) -> bool| {
+                neighbors(edge.left()).into_iter().any(|other| {
+                    other != *element
+                        && !removed.contains(&other)
+                        && edges.get(other).is_ok_and(|e| same(other, e))
+                })
+            };
+            if let Ok(lane) = lanes.get(*element) {
+                let same_lane = |other: Entity, other_edge: &Edge<Entity>| {
+                    other_edge.array() == edge.array() && lanes.get(other).ok() == Some(lane)
+                };
+                if is_duplicate(&same_lane) {
+                    removed.insert(*element);
+                    summary.duplicate_lanes += 1;
+                }
+            } else if walls.contains(*element) {
+                let same_wall = |other: Entity, other_edge: &Edge<Entity>| {
+                    walls.contains(other)
+                        && (other_edge.array() == edge.array() || other_edge.is_reverse_of(edge))
+                };
+                if is_duplicate(&same_wall) {
+                    removed.insert(*element);
+                    summary.duplicate_walls += 1;
+                }
+            }
+        }
+
+        // Detach the removed elements from their vertices right away so the
+        // vertices can be recognized as unused in this same pass.
+        for element in &removed {
+            if let Ok(edge) = edges.get(*element) {
+                for anchor in edge.array() {
+                    if let Ok(mut deps) = dependents.get_mut(anchor) {
+                        deps.remove(element);
+                    }
+                }
+            }
+            delete.send(Delete::new(*element));
+        }
+
+        let mut unused: HashSet<Entity> = already_unused.into_iter().collect();
+        for element in &removed {
+            if let Ok(edge) = edges.get(*element) {
+                unused.extend(edge.array().into_iter().filter(|a| {
+                    !merge_into.contains_key(a)
+                        && dependents.get(*a).is_ok_and(|deps| deps.is_empty())
+                }));
+            }
+        }
+        for anchor in merge_into.keys() {
+            unused.remove(anchor);
+        }
+        summary.orphaned_vertices = unused.len();
+        for anchor in unused {
+            delete.send(Delete::new(anchor));
+        }
+
+        info!("Vertex cleanup: {}", summary.summary());
+        last_cleanup.0 = Some(summary);
+    }
+}
+
+#[cfg(test)]
+fn spawn_vertex(world: &mut World, level: Entity, p: [f32; 2]) -> Entity {
+    world.spawn(Anchor::Translate2D(p)).set_parent(level).id()
+}
+
+#[cfg(test)]
+fn set_dependents(world: &mut World, anchor: Entity, dependents: &[Entity]) {
+    world
+        .entity_mut(anchor)
+        .insert(Dependents(std::collections::BTreeSet::from_iter(
+            dependents.iter().copied(),
+        )));
+}
+
+#[test]
+fn test_vertices_are_grouped_with_the_vertex_that_is_kept() {
+    let mut world = World::new();
+    let site = world.spawn_empty().id();
+    let level = world.spawn(LevelElevation(0.0)).set_parent(site).id();
+    let other_level = world.spawn(LevelElevation(5.0)).set_parent(site).id();
+    let elements: Vec<Entity> = (0..3).map(|_| world.spawn_empty().id()).collect();
+
+    // Each vertex of the chain is close to the next one, but the ends of the
+    // chain are too far apart from each other.
+    let start = spawn_vertex(&mut world, level, [0.0, 0.0]);
+    let middle = spawn_vertex(&mut world, level, [0.008, 0.0]);
+    let end = spawn_vertex(&mut world, level, [0.016, 0.0]);
+    set_dependents(&mut world, start, &elements[..1]);
+    set_dependents(&mut world, middle, &elements[1..]);
+    let above = spawn_vertex(&mut world, other_level, [0.0, 0.0]);
+    let far = spawn_vertex(&mut world, level, [3.0, 0.0]);
+    let near_far = spawn_vertex(&mut world, level, [3.0, 0.005]);
+
+    let mut state: bevy::ecs::system::SystemState<FindDuplicateAnchors> =
+        bevy::ecs::system::SystemState::new(&mut world);
+    let groups = state.get(&world).find(site, 0.01);
+    // The middle vertex is used the most so it is kept, and it is close to
+    // both ends of the chain. Ties go to the oldest vertex.
+    assert_eq!(groups, vec![vec![middle, start, end], vec![far, near_far]]);
+    assert!(groups.iter().all(|group| !group.contains(&above)));
+
+    // When an end of the chain is kept, the far end is left alone
+    set_dependents(&mut world, start, &elements);
+    let groups = state.get(&world).find(site, 0.01);
+    assert_eq!(groups, vec![vec![start, middle], vec![far, near_far]]);
+}
+
+#[test]
+fn test_cleanup_removes_duplicate_and_degenerate_elements() {
+    let mut world = World::new();
+    world.init_resource::<Events<CleanupVertices>>();
+    world.init_resource::<Events<Delete>>();
+    world.init_resource::<LastVertexCleanup>();
+    let site = world.spawn_empty().id();
+    let level = world.spawn(LevelElevation(0.0)).set_parent(site).id();
+
+    let a = spawn_vertex(&mut world, level, [0.0, 0.0]);
+    let b = spawn_vertex(&mut world, level, [5.0, 0.0]);
+    let duplicate_of_a = spawn_vertex(&mut world, level, [0.005, 0.0]);
+    let unused = spawn_vertex(&mut world, level, [9.0, 9.0]);
+
+    let lane = |world: &mut World, left: Entity, right: Entity| {
+        world
+            .spawn((
+                Edge::new(left, right),
+                Motion::default(),
+                ReverseLane::default(),
+                AssociatedGraphs::<Entity>::default(),
+                LaneMarker,
+            ))
+            .id()
+    };
+    let wall = |world: &mut World, left: Entity, right: Entity| {
+        world.spawn((Edge::new(left, right), WallMarker)).id()
+    };
+    let kept_lane = lane(&mut world, a, b);
+    let duplicate_lane = lane(&mut world, duplicate_of_a, b);
+    let kept_wall = wall(&mut world, a, b);
+    let reversed_wall = wall(&mut world, b, duplicate_of_a);
+    let zero_length_wall = wall(&mut world, a, duplicate_of_a);
+    set_dependents(&mut world, a, &[kept_lane, kept_wall, zero_length_wall]);
+    set_dependents(
+        &mut world,
+        b,
+        &[kept_lane, duplicate_lane, kept_wall, reversed_wall],
+    );
+    set_dependents(
+        &mut world,
+        duplicate_of_a,
+        &[duplicate_lane, reversed_wall, zero_length_wall],
+    );
+
+    world.send_event(CleanupVertices {
+        site,
+        epsilon: DEFAULT_MERGE_EPSILON,
+    });
+    let mut system = IntoSystem::into_system(cleanup_vertices);
+    system.initialize(&mut world);
+    system.run((), &mut world);
+
+    assert_eq!(
+        world.resource::<LastVertexCleanup>().0,
+        Some(VertexCleanupReport {
+            merged_vertices: 1,
+            duplicate_lanes: 1,
+            duplicate_walls: 1,
+            degenerate_edges: 1,
+            orphaned_vertices: 1,
+        }),
+    );
+    let deleted: HashSet<Entity> = world
+        .resource_mut::<Events<Delete>>()
+        .drain()
+        .map(|delete| delete.element)
+        .collect();
+    assert_eq!(
+        deleted,
+        HashSet::from_iter([
+            duplicate_of_a,
+            duplicate_lane,
+            reversed_wall,
+            zero_length_wall,
+            unused,
+        ]),
+    );
+    assert_eq!(world.get::<Edge<Entity>>(duplicate_lane).unwrap().left(), a);
+    assert!(world
+        .get::<Dependents>(a)
+        .unwrap()
+        .iter()
+        .all(|e| [kept_lane, kept_wall].contains(e)));
+}
@@ -17,8 +17,9 @@
 
 use crate::interaction::{CategoryVisibility, SetCategoryVisibility};
 use crate::site::{
-    CollisionMeshMarker, DoorMarker, FiducialMarker, FloorMarker, LaneMarker, LiftCabin,
-    LiftCabinDoorMarker, LocationTags, MeasurementMarker, VisualMeshMarker, WallMarker,
+    CollisionMeshMarker, DoorMarker, FiducialMarker, FloorMarker, LaneArrowDisplay, LaneMarker,
+    LiftCabin, LiftCabinDoorMarker, LocationTags, MeasurementMarker, VisualMeshMarker, WallMarker,
+    ZoneMarker,
 };
 use crate::widgets::menu_bar::{MenuEvent, MenuItem, ViewMenu};
 use bevy::ecs::system::SystemParam;
@@ -37,6 +38,7 @@ struct VisibilityEvents<'w> {
     walls: EventWriter<'w, SetCategoryVisibility<WallMarker>>,
     visuals: EventWriter<'w, SetCategoryVisibility<VisualMeshMarker>>,
     collisions: EventWriter<'w, SetCategoryVisibility<CollisionMeshMarker>>,
+    zones: EventWriter<'w, SetCategoryVisibility<ZoneMarker>>,
 }
 
 #[derive(Default)]
@@ -47,6 +49,8 @@ pub struct ViewMenuItems {
     doors: Entity,
     floors: Entity,
     lanes: Entity,
+    lane_arrows: Entity,
+    lane_chevrons: Entity,
     lifts: Entity,
     locations: Entity,
     fiducials: Entity,
@@ -54,6 +58,7 @@ pub struct ViewMenuItems {
     collisions: Entity,
     visuals: Entity,
     walls: Entity,
+    zones: Entity,
 }
 
 impl FromWorld for ViewMenuItems {
@@ -83,6 +88,23 @@ impl FromWorld for ViewMenuItems {
             ))
             .set_parent(view_header)
             .id();
+        let arrow_display = world
+            .get_resource_or_insert_with(LaneArrowDisplay::default)
+            .clone();
+        let lane_arrows = world
+            .spawn(MenuItem::CheckBox(
+                "Lane arrows".to_string(),
+                arrow_display.show,
+            ))
+            .set_parent(view_header)
+            .id();
+        let lane_chevrons = world
+            .spawn(MenuItem::CheckBox(
+                "One-way lane chevrons".to_string(),
+                arrow_display.one_way_chevrons,
+            ))
+            .set_parent(view_header)
+            .id();
         let default_visibility = world.resource::<CategoryVisibility<LiftCabin<Entity>>>();
         let lifts = world
             .spawn(MenuItem::CheckBox(
@@ -139,11 +161,21 @@ impl FromWorld for ViewMenuItems {
             ))
             .set_parent(view_header)
             .id();
+        let default_visibility = world.resource::<CategoryVisibility<ZoneMarker>>();
+        let zones = world
+            .spawn(MenuItem::CheckBox(
+                "Zones".to_string(),
+                default_visibility.0,
+            ))
+            .set_parent(view_header)
+            .id();
 
         ViewMenuItems {
             doors,
             floors,
             lanes,
+            lane_arrows,
+            lane_chevrons,
             lifts,
             locations,
             fiducials,
@@ -151,6 +183,7 @@ impl FromWorld for ViewMenuItems {
             collisions,
             visuals,
             walls,
+            zones,
         }
     }
 }
@@ -160,6 +193,7 @@ fn handle_view_menu_events(
     view_menu: Res<ViewMenuItems>,
     mut menu_items: Query<&mut MenuItem>,
     mut events: VisibilityEvents,
+    mut lane_arrows: ResMut<LaneArrowDisplay>,
 ) {
     let mut toggle = |entity| {
         let mut menu = menu_items.get_mut(entity).unwrap();
@@ -174,6 +208,10 @@ fn handle_view_menu_events(
             events.floors.send(toggle(event.source()).into());
         } else if event.clicked() && event.source() == view_menu.lanes {
             events.lanes.send(toggle(event.source()).into());
+        } else if event.clicked() && event.source() == view_menu.lane_arrows {
+            lane_arrows.show = toggle(event.source());
+        } else if event.clicked() && event.source() == view_menu.lane_chevrons {
+            lane_arrows.one_way_chevrons = toggle(event.source());
         } else if event.clicked() && event.source() == view_menu.lifts {
             let value = toggle(event.source());
             events.lift_cabins.send(value.into());
@@ -190,6 +228,8 @@ fn handle_view_menu_events(
             events.visuals.send(toggle(event.source()).into());
         } else if event.clicked() && event.source() == view_menu.walls {
             events.walls.send(toggle(event.source()).into());
+        } else if event.clicked() && event.source() == view_menu.zones {
+            events.zones.send(toggle(event.source()).into());
         }
     }
 }
@@ -0,0 +1,336 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{CameraControls, ChangeProjectionMode, PickingBlockers, ProjectionMode},
+    settings::EditorSettings,
+    site::*,
+    AppState,
+};
+use bevy::{
+    ecs::system::SystemParam,
+    input::mouse::MouseMotion,
+    prelude::{Input as UserInput, *},
+};
+use rmf_site_format::{Edge, WallMarker};
+
+/// Height of the eyes of the person walking through the site
+pub const WALKTHROUGH_EYE_HEIGHT: f32 = 1.6;
+/// Walking speed in meters per second. Holding shift runs instead.
+pub const WALKTHROUGH_SPEED: f32 = 1.4;
+pub const WALKTHROUGH_RUN_FACTOR: f32 = 3.0;
+/// How close the eyes can get to the center line of a wall
+pub const WALKTHROUGH_BODY_RADIUS: f32 = 0.25 + DEFAULT_WALL_THICKNESS / 2.0;
+/// Radians of mouse-look per pixel of mouse motion
+pub const WALKTHROUGH_LOOK_SENSITIVITY: f32 = 0.003;
+pub const WALKTHROUGH_MAX_PITCH: f32 = 85.0;
+
+/// While the walkthrough is active, the perspective camera moves at human
+/// height through the current level. WASD walks, dragging with the right mouse
+/// button looks around, and walls block the way so door widths and signage
+/// placement can be reviewed the way a person would see them.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct WalkthroughMode {
+    pub active: bool,
+    /// Heading of the walker, counterclockwise from the X axis
+    yaw: f32,
+    pitch: f32,
+    /// The view of the perspective camera from before the walkthrough began
+    previous_view: Option<Transform>,
+}
+
+pub struct WalkthroughPlugin;
+
+impl Plugin for WalkthroughPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WalkthroughMode>().add_systems(
+            Update,
+            (handle_walkthrough_keys, walk_through_site)
+                .chain()
+                .run_if(in_state(AppState::SiteEditor)),
+        );
+    }
+}
+
+#[derive(SystemParam)]
+struct WalkthroughCamera<'w, 's> {
+    controls: Res<'w, CameraControls>,
+    transforms: Query<'w, 's, &'static mut Transform, With<Projection>>,
+    change_mode: EventWriter<'w, ChangeProjectionMode>,
+}
+
+impl<'w, 's> WalkthroughCamera<'w, 's> {
+    fn begin(&mut self, walkthrough: &mut WalkthroughMode) {
+        self.change_mode
+            .send(ChangeProjectionMode::to_perspective().for_tool());
+        let persp = self.controls.perspective_camera_entities[0];
+        let (Ok(tf), Ok(previous)) = (
+            self.transforms.get(self.controls.active_camera()),
+            self.transforms.get(persp),
+        ) else {
+            return;
+        };
+        let (tf, previous) = (*tf, *previous);
+        walkthrough.previous_view = Some(previous);
+
+        // Start where the camera was looking, or right below the camera if it
+        // was not looking at the ground.
+        let forward = tf.forward();
+        let below = Vec2::new(tf.translation.x, tf.translation.y);
+        let start = if forward.z < -1e-3 {
+            let t = -tf.translation.z / forward.z;
+            let ahead = tf.translation + t * forward;
+            let ahead = Vec2::new(ahead.x, ahead.y);
+            // Back away a little so the walker does not begin inside of
+            // whatever they were looking at.
+            ahead - (ahead - below).clamp_length_max(2.0)
+        } else {
+            below
+        };
+        walkthrough.yaw = if forward.x.abs() + forward.y.abs() > 1e-3 {
+            forward.y.atan2(forward.x)
+        } else {
+            let up = tf.up();
+            up.y.atan2(up.x)
+        };
+        walkthrough.pitch = 0.0;
+        if let Ok(mut persp_tf) = self.transforms.get_mut(persp) {
+            *persp_tf = walkthrough.eye_transform(start);
+        }
+    }
+
+    fn end(&mut self, walkthrough: &mut WalkthroughMode, settings: &EditorSettings) {
+        if let Some(previous) = walkthrough.previous_view.take() {
+            if let Ok(mut tf) = self
+                .transforms
+                .get_mut(self.controls.perspective_camera_entities[0])
+            {
+                *tf = previous;
+            }
+        }
+        if settings.camera.orthographic {
+            self.change_mode
+                .send(ChangeProjectionMode::to_orthographic().for_tool());
+        }
+    }
+}
+
+impl WalkthroughMode {
+    fn eye_transform(&self, p: Vec2) -> Transform {
+        let look = Vec3::new(
+            self.pitch.cos() * self.yaw.cos(),
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+        );
+        Transform::from_xyz(p.x, p.y, WALKTHROUGH_EYE_HEIGHT).looking_to(look, Vec3::Z)
+    }
+}
+
+fn handle_walkthrough_keys(
+    keyboard_input: Res<UserInput<KeyCode>>,
+    settings: Res<EditorSettings>,
+    picking_blockers: Res<PickingBlockers>,
+    mut walkthrough: ResMut<WalkthroughMode>,
+    mut survey_mode: ResMut<SurveyMode>,
+    mut camera: WalkthroughCamera,
+) {
+    if picking_blockers.ui {
+        return;
+    }
+
+    let toggle = settings
+        .keybindings
+        .toggle_walkthrough
+        .just_pressed(&keyboard_input);
+    let escape = walkthrough.active && keyboard_input.just_pressed(KeyCode::Escape);
+    if !toggle && !escape {
+        return;
+    }
+
+    if walkthrough.active {
+        walkthrough.active = false;
+        camera.end(&mut walkthrough, &settings);
+        info!("Walkthrough off");
+    } else {
+        walkthrough.active = true;
+        // The quick-add keys of survey mode would get in the way of walking
+        survey_mode.active = false;
+        camera.begin(&mut walkthrough);
+        info!(
+            "Walkthrough on: use WASD to walk, hold shift to run, drag with the \
+            right mouse button to look around, and press Escape to leave"
+        );
+    }
+}
+
+#[derive(SystemParam)]
+struct WalkthroughInput<'w, 's> {
+    keyboard_input: Res<'w, UserInput<KeyCode>>,
+    mouse_buttons: Res<'w, UserInput<MouseButton>>,
+    mouse_motion: EventReader<'w, 's, MouseMotion>,
+    picking_blockers: Res<'w, PickingBlockers>,
+    time: Res<'w, Time>,
+}
+
+fn walk_through_site(
+    mut input: WalkthroughInput,
+    mut walkthrough: ResMut<WalkthroughMode>,
+    mut camera: WalkthroughCamera,
+    current_level: Res<CurrentLevel>,
+    walls: Query<(Entity, &Edge<Entity>, &Parent), With<WallMarker>>,
+    anchors: AnchorParams,
+) {
+    let look: Vec2 = input.mouse_motion.read().map(|m| m.delta).sum();
+    if !walkthrough.active {
+        return;
+    }
+    if camera.controls.mode() != ProjectionMode::Perspective {
+        // Wait for the perspective camera to take over
+        return;
+    }
+
+    if input.mouse_buttons.pressed(MouseButton::Right) && !input.picking_blockers.ui {
+        walkthrough.yaw -= look.x * WALKTHROUGH_LOOK_SENSITIVITY;
+        let max_pitch = WALKTHROUGH_MAX_PITCH.to_radians();
+        walkthrough.pitch = (walkthrough.pitch - look.y * WALKTHROUGH_LOOK_SENSITIVITY)
+            .clamp(-max_pitch, max_pitch);
+    }
+
+    let mut step = Vec2::ZERO;
+    if !input.picking_blockers.ui {
+        let keys = &input.keyboard_input;
+        let forward = Vec2::new(walkthrough.yaw.cos(), walkthrough.yaw.sin());
+        let right = Vec2::new(forward.y, -forward.x);
+        if keys.pressed(KeyCode::W) {
+            step += forward;
+        }
+        if keys.pressed(KeyCode::S) {
+            step -= forward;
+        }
+        if keys.pressed(KeyCode::D) {
+            step += right;
+        }
+        if keys.pressed(KeyCode::A) {
+            step -= right;
+        }
+        let running = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        let speed = if running {
+            WALKTHROUGH_SPEED * WALKTHROUGH_RUN_FACTOR
+        } else {
+            WALKTHROUGH_SPEED
+        };
+        // Long frames are clamped so a hitch cannot carry the walker through
+        // a wall.
+        let dt = input.time.delta_seconds().min(0.1);
+        step = step.normalize_or_zero() * speed * dt;
+    }
+
+    let Ok(tf) = camera
+        .transforms
+        .get(camera.controls.perspective_camera_entities[0])
+    else {
+        return;
+    };
+    let from = Vec2::new(tf.translation.x, tf.translation.y);
+
+    let mut to = from + step;
+    if step != Vec2::ZERO {
+        let segments: Vec<(Vec2, Vec2)> = walls
+            .iter()
+            .filter(|(_, _, parent)| current_level.0 == Some(parent.get()))
+            .filter_map(|(e, edge, _)| {
+                let p0 = anchors
+                    .point_in_parent_frame_of(edge.start(), Category::Wall, e)
+                    .ok()?;
+                let p1 = anchors
+                    .point_in_parent_frame_of(edge.end(), Category::Wall, e)
+                    .ok()?;
+                Some((p0.truncate(), p1.truncate()))
+            })
+            .collect();
+        to = collide_with_walls(from, to, &segments, WALKTHROUGH_BODY_RADIUS);
+    }
+
+    let eye = walkthrough.eye_transform(to);
+    if let Ok(mut tf) = camera
+        .transforms
+        .get_mut(camera.controls.perspective_camera_entities[0])
+    {
+        if *tf != eye {
+            *tf = eye;
+        }
+    }
+}
+
+/// Move from one point towards another while keeping a distance of at least
+/// `radius` from every wall. Walls push the walker out along their normal, so
+/// walking into a wall at an angle slides along it.
+fn collide_with_walls(from: Vec2, to: Vec2, walls: &[(Vec2, Vec2)], radius: f32) -> Vec2 {
+    let mut p = to;
+    for _ in 0..3 {
+        for (a, b) in walls {
+            let q = closest_point_on_segment(p, *a, *b);
+            let d = p - q;
+            let dist = d.length();
+            if dist >= radius {
+                continue;
+            }
+            let n = if dist > 1e-5 {
+                d / dist
+            } else {
+                let side = (b - a).perp().normalize_or_zero();
+                if side.dot(from - q) < 0.0 {
+                    -side
+                } else {
+                    side
+                }
+            };
+            p = q + n * radius;
+        }
+    }
+
+    // Never end up on the other side of a wall
+    if walls
+        .iter()
+        .any(|(a, b)| segments_intersect(from, p, *a, *b))
+    {
+        return from;
+    }
+    p
+}
+
+fn closest_point_on_segment(p: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    let ab = b - a;
+    let length_squared = ab.length_squared();
+    if length_squared < 1e-8 {
+        return a;
+    }
+    let t = ((p - a).dot(ab) / length_squared).clamp(0.0, 1.0);
+    a + t * ab
+}
+
+fn segments_intersect(p0: Vec2, p1: Vec2, q0: Vec2, q1: Vec2) -> bool {
+    let d = p1 - p0;
+    let e = q1 - q0;
+    let denom = d.perp_dot(e);
+    if denom.abs() < 1e-8 {
+        return false;
+    }
+    let t = (q0 - p0).perp_dot(e) / denom;
+    let u = (q0 - p0).perp_dot(d) / denom;
+    (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)
+}
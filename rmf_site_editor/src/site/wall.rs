@@ -17,7 +17,7 @@
 
 use crate::{interaction::Selectable, shapes::*, site::*};
 use bevy::prelude::*;
-use rmf_site_format::{Edge, WallMarker, DEFAULT_LEVEL_HEIGHT};
+use rmf_site_format::{Edge, WallAlpha, WallHeight, WallMarker};
 
 pub const DEFAULT_WALL_THICKNESS: f32 = 0.1;
 
@@ -25,6 +25,7 @@ fn make_wall(
     entity: Entity,
     wall: &Edge<Entity>,
     texture: &Texture,
+    height: &WallHeight,
     anchors: &AnchorParams,
 ) -> Mesh {
     // TODO(luca) map texture rotation to UV coordinates
@@ -47,7 +48,7 @@ fn make_wall(
         p_start,
         p_end,
         DEFAULT_WALL_THICKNESS,
-        DEFAULT_LEVEL_HEIGHT,
+        height.0,
         texture.height,
         texture.width,
     ))
@@ -55,26 +56,42 @@ fn make_wall(
     .unwrap()
 }
 
+/// The alpha of a wall combines the alpha of its texture with the alpha of
+/// the wall itself.
+fn wall_color_and_alpha_mode(texture: &Texture, alpha: &WallAlpha) -> (Color, AlphaMode) {
+    let alpha = texture.alpha.unwrap_or(1.0) * alpha.0;
+    if alpha < 1.0 {
+        (*Color::default().set_a(alpha), AlphaMode::Blend)
+    } else {
+        (Color::default(), AlphaMode::Opaque)
+    }
+}
+
 pub fn add_wall_visual(
     mut commands: Commands,
-    walls: Query<(Entity, &Edge<Entity>, &Affiliation<Entity>), Added<WallMarker>>,
+    walls: Query<
+        (
+            Entity,
+            &Edge<Entity>,
+            &Affiliation<Entity>,
+            &WallHeight,
+            &WallAlpha,
+        ),
+        Added<WallMarker>,
+    >,
     anchors: AnchorParams,
     textures: Query<(Option<&Handle<Image>>, &Texture)>,
     mut dependents: Query<&mut Dependents, With<Anchor>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
-    for (e, edge, texture_source) in &walls {
+    for (e, edge, texture_source, height, alpha) in &walls {
         let (base_color_texture, texture) = from_texture_source(texture_source, &textures);
-        let (base_color, alpha_mode) = if let Some(alpha) = texture.alpha.filter(|a| a < &1.0) {
-            (*Color::default().set_a(alpha), AlphaMode::Blend)
-        } else {
-            (Color::default(), AlphaMode::Opaque)
-        };
+        let (base_color, alpha_mode) = wall_color_and_alpha_mode(&texture, alpha);
         commands
             .entity(e)
             .insert(PbrBundle {
-                mesh: meshes.add(make_wall(e, edge, &texture, &anchors)),
+                mesh: meshes.add(make_wall(e, edge, &texture, height, &anchors)),
                 material: materials.add(StandardMaterial {
                     base_color_texture,
                     base_color,
@@ -103,6 +120,7 @@ pub fn update_walls_for_moved_anchors(
             Entity,
             &Edge<Entity>,
             &Affiliation<Entity>,
+            &WallHeight,
             &mut Handle<Mesh>,
         ),
         With<WallMarker>,
@@ -120,9 +138,11 @@ pub fn update_walls_for_moved_anchors(
 ) {
     for dependents in &changed_anchors {
         for dependent in dependents.iter() {
-            if let Some((e, edge, texture_source, mut mesh)) = walls.get_mut(*dependent).ok() {
+            if let Some((e, edge, texture_source, height, mut mesh)) =
+                walls.get_mut(*dependent).ok()
+            {
                 let (_, texture) = from_texture_source(texture_source, &textures);
-                *mesh = meshes.add(make_wall(e, edge, &texture, &anchors));
+                *mesh = meshes.add(make_wall(e, edge, &texture, height, &anchors));
             }
         }
     }
@@ -133,6 +153,8 @@ pub fn update_walls(
         (
             &Edge<Entity>,
             &Affiliation<Entity>,
+            &WallHeight,
+            &WallAlpha,
             &mut Handle<Mesh>,
             &Handle<StandardMaterial>,
         ),
@@ -142,7 +164,12 @@ pub fn update_walls(
         Entity,
         (
             With<WallMarker>,
-            Or<(Changed<Affiliation<Entity>>, Changed<Edge<Entity>>)>,
+            Or<(
+                Changed<Affiliation<Entity>>,
+                Changed<Edge<Entity>>,
+                Changed<WallHeight>,
+                Changed<WallAlpha>,
+            )>,
         ),
     >,
     changed_texture_sources: Query<
@@ -159,17 +186,13 @@ pub fn update_walls(
             .iter()
             .flat_map(|members| members.iter().cloned()),
     ) {
-        let Ok((edge, texture_source, mut mesh, material)) = walls.get_mut(e) else {
+        let Ok((edge, texture_source, height, alpha, mut mesh, material)) = walls.get_mut(e) else {
             continue;
         };
         let (base_color_texture, texture) = from_texture_source(texture_source, &textures);
-        *mesh = meshes.add(make_wall(e, edge, &texture, &anchors));
+        *mesh = meshes.add(make_wall(e, edge, &texture, height, &anchors));
         if let Some(material) = materials.get_mut(material) {
-            let (base_color, alpha_mode) = if let Some(alpha) = texture.alpha.filter(|a| a < &1.0) {
-                (*Color::default().set_a(alpha), AlphaMode::Blend)
-            } else {
-                (Color::default(), AlphaMode::Opaque)
-            };
+            let (base_color, alpha_mode) = wall_color_and_alpha_mode(&texture, alpha);
             material.base_color_texture = base_color_texture;
             material.base_color = base_color;
             material.alpha_mode = alpha_mode;
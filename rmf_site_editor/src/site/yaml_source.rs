@@ -0,0 +1,492 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{site::*, CurrentWorkspace};
+use bevy::{
+    ecs::system::{Command, SystemState},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// What the YAML source view is showing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SourceTarget {
+    /// A single element of the site, such as a lane or a door
+    Element(Entity),
+    /// A level with every element on it
+    Level(Entity),
+}
+
+/// A single element of a site, as it appears in the site file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceElement {
+    Anchor(Anchor),
+    Lane(Lane<u32>),
+    Wall(Wall<u32>),
+    Door(Door<u32>),
+    Floor(Floor<u32>),
+    Light(Light),
+    Location(Location<u32>),
+    Model(ModelInstance<u32>),
+}
+
+/// A level and the elements on it, as they appear in the site file. Lanes,
+/// locations, and models are stored elsewhere in the file, so they are
+/// gathered here from the anchors and parents that tie them to the level.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LevelSource {
+    pub properties: LevelProperties,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub anchors: BTreeMap<u32, Anchor>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub doors: BTreeMap<u32, Door<u32>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub floors: BTreeMap<u32, Floor<u32>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub walls: BTreeMap<u32, Wall<u32>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lights: BTreeMap<u32, Light>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lanes: BTreeMap<u32, Lane<u32>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub locations: BTreeMap<u32, Location<u32>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub models: BTreeMap<u32, ModelInstance<u32>>,
+}
+
+impl LevelSource {
+    fn new(site: &rmf_site_format::Site, level_id: u32, level: &Level) -> Self {
+        let guided = &site.navigation.guided;
+        let on_level = |anchor: u32| level.anchors.contains_key(&anchor);
+        Self {
+            properties: level.properties.clone(),
+            anchors: level.anchors.clone(),
+            doors: level.doors.clone(),
+            floors: level.floors.clone(),
+            walls: level.walls.clone(),
+            lights: level.lights.clone(),
+            lanes: guided
+                .lanes
+                .iter()
+                .chain(guided.human_lanes.iter())
+                .filter(|(_, lane)| lane.anchors.array().into_iter().any(on_level))
+                .map(|(id, lane)| (*id, lane.clone()))
+                .collect(),
+            locations: guided
+                .locations
+                .iter()
+                .filter(|(_, location)| on_level(location.anchor.0))
+                .map(|(id, location)| (*id, location.clone()))
+                .collect(),
+            models: site
+                .model_instances
+                .iter()
+                .filter(|(_, model)| model.parent == level_id)
+                .map(|(id, model)| (*id, model.bundle.clone()))
+                .collect(),
+        }
+    }
+
+    fn elements(self) -> impl Iterator<Item = (u32, SourceElement)> {
+        let anchors = self
+            .anchors
+            .into_iter()
+            .map(|(id, a)| (id, SourceElement::Anchor(a)));
+        let doors = self
+            .doors
+            .into_iter()
+            .map(|(id, d)| (id, SourceElement::Door(d)));
+        let floors = self
+            .floors
+            .into_iter()
+            .map(|(id, f)| (id, SourceElement::Floor(f)));
+        let walls = self
+            .walls
+            .into_iter()
+            .map(|(id, w)| (id, SourceElement::Wall(w)));
+        let lights = self
+            .lights
+            .into_iter()
+            .map(|(id, l)| (id, SourceElement::Light(l)));
+        let lanes = self
+            .lanes
+            .into_iter()
+            .map(|(id, l)| (id, SourceElement::Lane(l)));
+        let locations = self
+            .locations
+            .into_iter()
+            .map(|(id, l)| (id, SourceElement::Location(l)));
+        let models = self
+            .models
+            .into_iter()
+            .map(|(id, m)| (id, SourceElement::Model(m)));
+        anchors
+            .chain(doors)
+            .chain(floors)
+            .chain(walls)
+            .chain(lights)
+            .chain(lanes)
+            .chain(locations)
+            .chain(models)
+    }
+}
+
+/// The text of the YAML source view.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct YamlSource {
+    pub target: Option<SourceTarget>,
+    pub text: String,
+    /// The text as it was last loaded from the site, to tell whether there
+    /// are edits that have not been applied yet
+    pub loaded: String,
+    pub error: Option<String>,
+}
+
+impl YamlSource {
+    pub fn is_modified(&self) -> bool {
+        self.text != self.loaded
+    }
+}
+
+/// Send this event to load the YAML of a target into the [`YamlSource`].
+#[derive(Event, Clone, Copy, Debug)]
+pub struct RefreshYamlSource(pub SourceTarget);
+
+/// Send this event to apply the edited text of the [`YamlSource`] back onto
+/// the elements of the site. Elements can be edited but not added or
+/// removed this way.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ApplyYamlSource;
+
+#[derive(Default)]
+pub struct YamlSourcePlugin;
+
+impl Plugin for YamlSourcePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<YamlSource>()
+            .add_event::<RefreshYamlSource>()
+            .add_event::<ApplyYamlSource>()
+            .add_systems(
+                Update,
+                (apply_yaml_source, refresh_yaml_source)
+                    .chain()
+                    .run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+fn refresh_yaml_source(world: &mut World) {
+    let Some(RefreshYamlSource(target)) = world
+        .resource_mut::<Events<RefreshYamlSource>>()
+        .drain()
+        .last()
+    else {
+        return;
+    };
+
+    let result = generate_source(world, target);
+    let mut source = world.resource_mut::<YamlSource>();
+    source.target = Some(target);
+    match result {
+        Ok(text) => {
+            source.text = text.clone();
+            source.loaded = text;
+            source.error = None;
+        }
+        Err(err) => {
+            source.text.clear();
+            source.loaded.clear();
+            source.error = Some(err);
+        }
+    }
+}
+
+fn apply_yaml_source(world: &mut World) {
+    if world
+        .resource_mut::<Events<ApplyYamlSource>>()
+        .drain()
+        .last()
+        .is_none()
+    {
+        return;
+    }
+    let source = world.resource::<YamlSource>();
+    let Some(target) = source.target else {
+        return;
+    };
+    let (text, loaded) = (source.text.clone(), source.loaded.clone());
+
+    match apply_source(world, target, &text, &loaded) {
+        Ok(()) => {
+            // Reload the source so that it shows the values as they are saved
+            world.send_event(RefreshYamlSource(target));
+        }
+        Err(err) => {
+            world.resource_mut::<YamlSource>().error = Some(err);
+        }
+    }
+}
+
+fn current_site(world: &World) -> Result<Entity, String> {
+    world
+        .get_resource::<CurrentWorkspace>()
+        .and_then(|w| w.root)
+        .ok_or_else(|| "No site is open".to_owned())
+}
+
+fn generate_source(world: &mut World, target: SourceTarget) -> Result<String, String> {
+    let site = current_site(world)?;
+    // Generating the site assigns IDs to every element, which the source
+    // uses to refer to other elements.
+    let data = generate_site(world, site).map_err(|err| err.to_string())?;
+    let (SourceTarget::Element(entity) | SourceTarget::Level(entity)) = target;
+    let id = world
+        .get::<SiteID>(entity)
+        .ok_or_else(|| "This is not saved as part of the site".to_owned())?
+        .0;
+
+    match target {
+        SourceTarget::Element(_) => {
+            let element = find_element(&data, id)
+                .ok_or_else(|| "The source of this kind of element cannot be shown".to_owned())?;
+            serde_yaml::to_string(&element).map_err(|err| err.to_string())
+        }
+        SourceTarget::Level(_) => {
+            let level = data
+                .levels
+                .get(&id)
+                .ok_or_else(|| "The level could not be found".to_owned())?;
+            serde_yaml::to_string(&LevelSource::new(&data, id, level))
+                .map_err(|err| err.to_string())
+        }
+    }
+}
+
+fn find_element(site: &rmf_site_format::Site, id: u32) -> Option<SourceElement> {
+    if let Some(anchor) = site.anchors.get(&id) {
+        return Some(SourceElement::Anchor(anchor.clone()));
+    }
+    for level in site.levels.values() {
+        if let Some(anchor) = level.anchors.get(&id) {
+            return Some(SourceElement::Anchor(anchor.clone()));
+        }
+        if let Some(door) = level.doors.get(&id) {
+            return Some(SourceElement::Door(door.clone()));
+        }
+        if let Some(floor) = level.floors.get(&id) {
+            return Some(SourceElement::Floor(floor.clone()));
+        }
+        if let Some(wall) = level.walls.get(&id) {
+            return Some(SourceElement::Wall(wall.clone()));
+        }
+        if let Some(light) = level.lights.get(&id) {
+            return Some(SourceElement::Light(light.clone()));
+        }
+    }
+    let guided = &site.navigation.guided;
+    if let Some(lane) = guided
+        .lanes
+        .get(&id)
+        .or_else(|| guided.human_lanes.get(&id))
+    {
+        return Some(SourceElement::Lane(lane.clone()));
+    }
+    if let Some(location) = guided.locations.get(&id) {
+        return Some(SourceElement::Location(location.clone()));
+    }
+    site.model_instances
+        .get(&id)
+        .map(|model| SourceElement::Model(model.bundle.clone()))
+}
+
+fn apply_source(
+    world: &mut World,
+    target: SourceTarget,
+    text: &str,
+    loaded: &str,
+) -> Result<(), String> {
+    let site = current_site(world)?;
+    let ids = site_entities(world, site);
+    match target {
+        SourceTarget::Element(entity) => {
+            let element: SourceElement =
+                serde_yaml::from_str(text).map_err(|err| err.to_string())?;
+            let original: SourceElement =
+                serde_yaml::from_str(loaded).map_err(|err| err.to_string())?;
+            if std::mem::discriminant(&element) != std::mem::discriminant(&original) {
+                return Err("The kind of an element cannot be changed".to_owned());
+            }
+            let element = convert_element(element, &ids)?;
+            insert_element(world, entity, element)
+        }
+        SourceTarget::Level(level) => {
+            let source: LevelSource = serde_yaml::from_str(text).map_err(|err| err.to_string())?;
+            // Check and convert every element before changing anything so
+            // that a mistake does not leave the level half applied.
+            let properties = source.properties.clone();
+            let mut elements = Vec::new();
+            for (id, element) in source.elements() {
+                let entity = *ids
+                    .get(&id)
+                    .ok_or_else(|| format!("There is no element #{id} to apply changes to"))?;
+                if !is_kind_of(world, entity, &element) {
+                    return Err(format!("Element #{id} is a different kind of element"));
+                }
+                elements.push((entity, convert_element(element, &ids)?));
+            }
+            if world.get_entity(level).is_none() {
+                return Err("The level no longer exists".to_owned());
+            }
+            for (entity, element) in elements {
+                insert_element(world, entity, element)?;
+            }
+            world.entity_mut(level).insert(properties);
+            Ok(())
+        }
+    }
+}
+
+/// Map the IDs of the site file to the entities of the site.
+fn site_entities(world: &mut World, site: Entity) -> HashMap<u32, Entity> {
+    let mut state: SystemState<(Query<&Children>, Query<&SiteID>)> = SystemState::new(world);
+    let (children, site_ids) = state.get(world);
+    DescendantIter::new(&children, site)
+        .filter_map(|e| site_ids.get(e).ok().map(|id| (id.0, e)))
+        .collect()
+}
+
+fn is_kind_of(world: &World, entity: Entity, element: &SourceElement) -> bool {
+    let Some(e) = world.get_entity(entity) else {
+        return false;
+    };
+    match element {
+        SourceElement::Anchor(_) => e.contains::<Anchor>(),
+        SourceElement::Lane(_) => e.contains::<LaneMarker>(),
+        SourceElement::Wall(_) => e.contains::<WallMarker>(),
+        SourceElement::Door(_) => e.contains::<DoorMarker>(),
+        SourceElement::Floor(_) => e.contains::<FloorMarker>(),
+        SourceElement::Light(_) => e.contains::<LightKind>(),
+        SourceElement::Location(_) => e.contains::<LocationTags>(),
+        SourceElement::Model(_) => e.contains::<ModelMarker>(),
+    }
+}
+
+/// A [`SourceElement`] whose references to other elements have been
+/// converted into entities.
+enum ConvertedElement {
+    Anchor(Anchor),
+    Lane(Lane<Entity>),
+    Wall(Wall<Entity>),
+    Door(Door<Entity>),
+    Floor(Floor<Entity>),
+    Light(Light),
+    Location(Location<Entity>),
+    Model(ModelInstance<Entity>),
+}
+
+fn convert_element(
+    element: SourceElement,
+    ids: &HashMap<u32, Entity>,
+) -> Result<ConvertedElement, String> {
+    let missing = |id: u32| format!("There is no element #{id}");
+    let converted = match element {
+        SourceElement::Anchor(anchor) => ConvertedElement::Anchor(anchor),
+        SourceElement::Lane(lane) => ConvertedElement::Lane(lane.convert(ids).map_err(missing)?),
+        SourceElement::Wall(wall) => ConvertedElement::Wall(wall.convert(ids).map_err(missing)?),
+        SourceElement::Door(door) => ConvertedElement::Door(door.convert(ids).map_err(missing)?),
+        SourceElement::Floor(floor) => {
+            ConvertedElement::Floor(floor.convert(ids).map_err(missing)?)
+        }
+        SourceElement::Light(light) => ConvertedElement::Light(light),
+        SourceElement::Location(location) => {
+            ConvertedElement::Location(location.convert(ids).map_err(missing)?)
+        }
+        SourceElement::Model(model) => {
+            ConvertedElement::Model(model.convert(ids).map_err(missing)?)
+        }
+    };
+    Ok(converted)
+}
+
+/// The anchors that an element is drawn between.
+fn anchors_of(world: &World, entity: Entity) -> Vec<Entity> {
+    let Some(e) = world.get_entity(entity) else {
+        return Vec::new();
+    };
+    if let Some(edge) = e.get::<Edge<Entity>>() {
+        edge.array().to_vec()
+    } else if let Some(path) = e.get::<Path<Entity>>() {
+        path.0.clone()
+    } else if let Some(point) = e.get::<Point<Entity>>() {
+        vec![point.0]
+    } else {
+        Vec::new()
+    }
+}
+
+fn insert_element(
+    world: &mut World,
+    entity: Entity,
+    element: ConvertedElement,
+) -> Result<(), String> {
+    let old_anchors = anchors_of(world, entity);
+    let mut e = world
+        .get_entity_mut(entity)
+        .ok_or_else(|| "The element no longer exists".to_owned())?;
+    match element {
+        ConvertedElement::Anchor(anchor) => {
+            e.insert(anchor);
+        }
+        ConvertedElement::Lane(lane) => {
+            e.insert(lane);
+        }
+        ConvertedElement::Wall(wall) => {
+            e.insert(wall);
+        }
+        ConvertedElement::Door(door) => {
+            e.insert(door);
+        }
+        ConvertedElement::Floor(floor) => {
+            e.insert(floor);
+        }
+        ConvertedElement::Light(light) => {
+            e.insert(light);
+        }
+        ConvertedElement::Location(location) => {
+            e.insert(location);
+        }
+        ConvertedElement::Model(model) => {
+            e.insert(model);
+        }
+    }
+
+    // Keep the anchors aware of which elements depend on them
+    let new_anchors = anchors_of(world, entity);
+    for anchor in &old_anchors {
+        if !new_anchors.contains(anchor) {
+            ChangeDependent::remove(*anchor, entity).apply(world);
+        }
+    }
+    for anchor in &new_anchors {
+        if !old_anchors.contains(anchor) {
+            ChangeDependent::add(*anchor, entity).apply(world);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,195 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{interaction::Selectable, shapes::*, site::*, AppState};
+use bevy::{prelude::*, render::mesh::PrimitiveTopology};
+use geo::{
+    geometry::{LineString, Polygon},
+    CoordsIter, TriangulateSpade,
+};
+use rmf_site_format::{Path, ZoneKind, ZoneMarker};
+
+/// Zones are drawn just beneath the lanes so that lanes stay visible on top
+/// of them.
+pub const ZONE_LAYER_HEIGHT: f32 = LANE_LAYER_START - 0.0001;
+pub const ZONE_ALPHA: f32 = 0.35;
+
+#[derive(Debug, Clone, Copy, Component)]
+pub struct ZoneSegments {
+    pub mesh: Entity,
+}
+
+/// Add support for zones, which are named areas of a level that are drawn as
+/// translucent fills in the color of their kind.
+#[derive(Default)]
+pub struct ZonePlugin;
+
+impl Plugin for ZonePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ChangePlugin::<ZoneKind>::default())
+            .add_systems(
+                PostUpdate,
+                assign_orphan_elements_to_level::<ZoneMarker>
+                    .run_if(AppState::in_displaying_mode())
+                    .in_set(SiteUpdateSet::AssignOrphans),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    add_zone_visuals,
+                    update_zones.after(add_zone_visuals),
+                    update_zones_for_moved_anchors.after(add_zone_visuals),
+                )
+                    .run_if(AppState::in_displaying_mode())
+                    .in_set(SiteUpdateSet::BetweenVisibilityAndTransform),
+            );
+    }
+}
+
+fn make_zone_mesh(entity: Entity, path: &Path<Entity>, anchors: &AnchorParams) -> Mesh {
+    let mut positions = Vec::new();
+    for anchor in path.iter() {
+        match anchors.point_in_parent_frame_of(*anchor, Category::Zone, entity) {
+            Ok(p) => positions.push(p.to_array()),
+            Err(_) => {
+                error!("Failed to find anchor {anchor:?} used by a zone");
+                return Mesh::new(PrimitiveTopology::TriangleList);
+            }
+        }
+    }
+    if positions.len() < 3 {
+        return Mesh::new(PrimitiveTopology::TriangleList);
+    }
+
+    let polygon = Polygon::new(
+        LineString::from(positions.iter().map(|p| [p[0], p[1]]).collect::<Vec<_>>()),
+        vec![],
+    );
+    let outline_buffer = make_closed_path_outline(positions);
+    let Ok(triangles) = polygon.constrained_triangulation(Default::default()) else {
+        warn!("Failed to triangulate zone {entity:?}");
+        return outline_buffer.into();
+    };
+    let vertices: Vec<[f32; 3]> = triangles
+        .iter()
+        .flat_map(|triangle| triangle.coords_iter().map(|v| [v.x, v.y, 0.]))
+        .collect();
+    let indices = (0..vertices.len() as u32).collect();
+    let normals = vertices.iter().map(|_| [0., 0., 1.]).collect();
+    MeshBuffer::new(vertices, normals, indices)
+        .merge_with(outline_buffer)
+        .into()
+}
+
+fn zone_color(kind: &ZoneKind) -> Color {
+    let [r, g, b] = kind.color();
+    Color::rgba(r, g, b, ZONE_ALPHA)
+}
+
+pub fn add_zone_visuals(
+    mut commands: Commands,
+    zones: Query<(Entity, &Path<Entity>, &ZoneKind), Added<ZoneMarker>>,
+    anchors: AnchorParams,
+    mut dependents: Query<&mut Dependents, With<Anchor>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (e, path, kind) in &zones {
+        let mesh = commands
+            .spawn(PbrBundle {
+                mesh: meshes.add(make_zone_mesh(e, path, &anchors)),
+                material: materials.add(StandardMaterial {
+                    base_color: zone_color(kind),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    double_sided: true,
+                    cull_mode: None,
+                    ..default()
+                }),
+                ..default()
+            })
+            .insert(Selectable::new(e))
+            .id();
+
+        commands
+            .entity(e)
+            .insert(SpatialBundle {
+                transform: Transform::from_xyz(0.0, 0.0, ZONE_LAYER_HEIGHT),
+                ..default()
+            })
+            .insert(ZoneSegments { mesh })
+            .insert(Category::Zone)
+            .insert(PathBehavior::for_floor())
+            .add_child(mesh);
+
+        for anchor in path.iter() {
+            if let Ok(mut deps) = dependents.get_mut(*anchor) {
+                deps.insert(e);
+            }
+        }
+    }
+}
+
+pub fn update_zones(
+    zones: Query<
+        (Entity, &ZoneSegments, &Path<Entity>, &ZoneKind),
+        Or<(Changed<Path<Entity>>, Changed<ZoneKind>)>,
+    >,
+    anchors: AnchorParams,
+    mut mesh_handles: Query<&mut Handle<Mesh>>,
+    material_handles: Query<&Handle<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (e, segments, path, kind) in &zones {
+        if let Ok(mut mesh) = mesh_handles.get_mut(segments.mesh) {
+            *mesh = meshes.add(make_zone_mesh(e, path, &anchors));
+        }
+        if let Some(material) = material_handles
+            .get(segments.mesh)
+            .ok()
+            .and_then(|handle| materials.get_mut(handle))
+        {
+            material.base_color = zone_color(kind);
+        }
+    }
+}
+
+pub fn update_zones_for_moved_anchors(
+    zones: Query<(&ZoneSegments, &Path<Entity>), With<ZoneMarker>>,
+    changed_anchors: Query<
+        &Dependents,
+        (
+            With<Anchor>,
+            Or<(Changed<Anchor>, Changed<GlobalTransform>)>,
+        ),
+    >,
+    anchors: AnchorParams,
+    mut mesh_handles: Query<&mut Handle<Mesh>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for dependents in &changed_anchors {
+        for dependent in dependents.iter() {
+            let Ok((segments, path)) = zones.get(*dependent) else {
+                continue;
+            };
+            if let Ok(mut mesh) = mesh_handles.get_mut(segments.mesh) {
+                *mesh = meshes.add(make_zone_mesh(*dependent, path, &anchors));
+            }
+        }
+    }
+}
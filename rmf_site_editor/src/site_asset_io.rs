@@ -47,8 +47,42 @@ fn load_from_file<'a>(path: PathBuf) -> Result<Box<Reader<'a>>, AssetReaderError
     }
 }
 
+/// Whether the name of a remote asset is a full URL rather than the name of a
+/// Fuel asset.
+fn is_url(name: &str) -> bool {
+    name.starts_with("http://") || name.starts_with("https://")
+}
+
+/// Where a remote asset gets cached on disk. Fuel assets are keyed by their
+/// name, which includes the version of the model when one was requested. Assets
+/// hosted at a URL are keyed by the host and path of the URL.
+pub fn remote_cache_file(name: &str) -> PathBuf {
+    let mut path = cache_path();
+    match name
+        .strip_prefix("http://")
+        .or_else(|| name.strip_prefix("https://"))
+    {
+        Some(url) => {
+            path.push("remote");
+            // Drop any query so the cache file keeps the extension of the asset
+            let url = url.split(['?', '#']).next().unwrap_or(url);
+            for component in url.split('/').filter(|c| !c.is_empty() && *c != "..") {
+                path.push(component.replace(':', "_"));
+            }
+        }
+        None => path.push(PathBuf::from(name)),
+    }
+    path
+}
+
 fn generate_remote_asset_url(name: &str) -> Result<String, AssetReaderError> {
+    if is_url(name) {
+        return Ok(name.to_owned());
+    }
     // Expected format: OrgName/ModelName/FileName.ext
+    // A specific version of a model can be requested with
+    // OrgName/ModelName@Version/FileName.ext, otherwise the latest version is
+    // used.
     // We may need to be a bit magical here because some assets
     // are found in Fuel and others are not.
     let binding = name.to_owned();
@@ -81,9 +115,10 @@ fn generate_remote_asset_url(name: &str) -> Result<String, AssetReaderError> {
         )));
     }
     let filename = binding.split_at(1).1;
+    let (model_name, version) = model_name.split_once('@').unwrap_or((model_name, "tip"));
     let uri = format!(
-        "{0}/{1}/models/{2}/tip/files/{3}",
-        FUEL_BASE_URI, org_name, model_name, filename
+        "{0}/{1}/models/{2}/{3}/files/{4}",
+        FUEL_BASE_URI, org_name, model_name, version, filename
     );
     return Ok(uri);
 }
@@ -108,10 +143,19 @@ async fn fetch_asset<'a>(
             )));
         }
     }
-    let bytes = ehttp::fetch_async(req)
+    let response = ehttp::fetch_async(req)
         .await
-        .map_err(|e| AssetReaderError::Io(io::Error::new(io::ErrorKind::Other, e.to_string())))?
-        .bytes;
+        .map_err(|e| AssetReaderError::Io(io::Error::new(io::ErrorKind::Other, e.to_string())))?;
+    if !response.ok {
+        return Err(AssetReaderError::Io(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "Failed to fetch asset from {} [status {}]: {}",
+                remote_url, response.status, response.status_text,
+            ),
+        )));
+    }
+    let bytes = response.bytes;
 
     match serde_json::from_slice::<FuelErrorMsg>(&bytes) {
         Ok(error) => {
@@ -149,13 +193,28 @@ fn get_path_from_env() -> Result<PathBuf, env::VarError> {
 }
 
 fn save_to_cache(name: &str, bytes: &[u8]) {
-    let mut asset_path = cache_path();
-    asset_path.push(PathBuf::from(name));
-    fs::create_dir_all(asset_path.parent().unwrap()).unwrap();
-    if bytes.len() > 0 {
-        if let Err(err) = std::fs::write(asset_path, bytes) {
-            error!("Unable to write to file {:?}", err);
-        };
+    if bytes.is_empty() {
+        return;
+    }
+    let asset_path = remote_cache_file(name);
+    if let Some(parent) = asset_path.parent() {
+        if let Err(err) = fs::create_dir_all(parent) {
+            error!("Unable to create cache folder {parent:?}: {err}");
+            return;
+        }
+    }
+    // Write into a temporary file first so that an interrupted download never
+    // leaves a truncated asset in the cache for the next session to load.
+    let mut partial_path = asset_path.clone().into_os_string();
+    partial_path.push(".part");
+    let partial_path = PathBuf::from(partial_path);
+    if let Err(err) = fs::write(&partial_path, bytes) {
+        error!("Unable to write to file {:?}", err);
+        return;
+    }
+    if let Err(err) = fs::rename(&partial_path, &asset_path) {
+        error!("Unable to move {partial_path:?} into the cache: {err}");
+        let _ = fs::remove_file(&partial_path);
     }
 }
 
@@ -245,8 +304,7 @@ impl Plugin for SiteAssetIoPlugin {
                     // Try local cache
                     #[cfg(not(target_arch = "wasm32"))]
                     {
-                        let mut asset_path = cache_path();
-                        asset_path.push(PathBuf::from(&asset_name));
+                        let asset_path = remote_cache_file(&asset_name);
                         if asset_path.exists() {
                             return Box::pin(async move { load_from_file(asset_path) });
                         }
@@ -276,8 +334,7 @@ impl Plugin for SiteAssetIoPlugin {
                     // Try local cache first
                     #[cfg(not(target_arch = "wasm32"))]
                     {
-                        let mut asset_path = cache_path();
-                        asset_path.push(path);
+                        let asset_path = remote_cache_file(&asset_name);
                         if asset_path.exists() {
                             return Box::pin(async move { load_from_file(asset_path) });
                         }
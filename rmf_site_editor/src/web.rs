@@ -0,0 +1,56 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+//! Helpers for running the editor in a web browser, where there is no file
+//! system to save into.
+
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url, UrlSearchParams};
+
+fn js_error(err: JsValue) -> String {
+    format!("{err:?}")
+}
+
+/// Offer `data` to the user as a download named `file_name`.
+pub fn download_file(file_name: &str, data: &[u8]) -> Result<(), String> {
+    let window = web_sys::window().ok_or("there is no browser window")?;
+    let document = window
+        .document()
+        .ok_or("the browser window has no document")?;
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(data));
+    let mut options = BlobPropertyBag::new();
+    options.type_("application/octet-stream");
+    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options).map_err(js_error)?;
+    let url = Url::create_object_url_with_blob(&blob).map_err(js_error)?;
+
+    let anchor: HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(js_error)?
+        .dyn_into()
+        .map_err(|_| "unable to create a download link".to_owned())?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+    Url::revoke_object_url(&url).map_err(js_error)
+}
+
+/// The value of the `?map=URL` query parameter of the page, if there is one.
+pub fn map_url_from_query() -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    UrlSearchParams::new_with_str(&search).ok()?.get("map")
+}
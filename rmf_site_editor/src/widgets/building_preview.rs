@@ -16,12 +16,12 @@
 */
 
 use crate::{
-    site::{AlignSiteDrawings, FinishEditDrawing},
+    site::{AlignSiteDrawings, FinishEditDrawing, StackedLevelView},
     widgets::prelude::*,
     AppState, CurrentWorkspace, Icons,
 };
 use bevy::prelude::*;
-use bevy_egui::egui::{Button, Ui};
+use bevy_egui::egui::{Button, DragValue, Ui};
 
 #[derive(Default)]
 pub struct BuildingPreviewPlugin {}
@@ -40,6 +40,7 @@ pub struct BuildingPreview<'w> {
     current_workspace: Res<'w, CurrentWorkspace>,
     align_site: EventWriter<'w, AlignSiteDrawings>,
     finish_edit_drawing: EventWriter<'w, FinishEditDrawing>,
+    stacked_view: ResMut<'w, StackedLevelView>,
 }
 
 impl<'w> WidgetSystem<Tile> for BuildingPreview<'w> {
@@ -52,6 +53,22 @@ impl<'w> WidgetSystem<Tile> for BuildingPreview<'w> {
         }
 
         if *params.app_state == AppState::SiteVisualizer {
+            ui.horizontal(|ui| {
+                let mut exaggeration = params.stacked_view.vertical_exaggeration;
+                ui.label("Vertical exaggeration").on_hover_text(
+                    "Multiply the elevation of every level to inspect the space between them",
+                );
+                ui.add(
+                    DragValue::new(&mut exaggeration)
+                        .clamp_range(0.1..=10.0)
+                        .speed(0.05)
+                        .suffix("x"),
+                );
+                if exaggeration != params.stacked_view.vertical_exaggeration {
+                    params.stacked_view.vertical_exaggeration = exaggeration;
+                }
+            });
+
             if ui
                 .add(Button::image_and_text(
                     params.icons.alignment.egui(),
@@ -0,0 +1,268 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{
+        CleanupVertices, Delete, FindDuplicateAnchors, FindOrphans, LastVertexCleanup, NameInSite,
+        DEFAULT_MERGE_EPSILON,
+    },
+    widgets::{
+        menu_bar::{MenuEvent, MenuItem, ToolMenu},
+        prelude::*,
+        SelectorWidget,
+    },
+    AppState, CurrentWorkspace,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_egui::egui::{self, Button, CollapsingHeader, DragValue, ScrollArea, Ui};
+use std::collections::HashSet;
+
+/// Add a [`Cleanup`] widget to your application.
+#[derive(Default)]
+pub struct CleanupPlugin {}
+
+impl Plugin for CleanupPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CleanupMenu>()
+            .init_resource::<CleanupDisplay>()
+            .add_systems(Update, handle_cleanup_panel_visibility);
+
+        let panel = PanelWidget::new(cleanup_panel, &mut app.world);
+        let widget = Widget::new::<Cleanup>(&mut app.world);
+        app.world.spawn((panel, widget));
+    }
+}
+
+fn cleanup_panel(In(input): In<PanelWidgetInput>, world: &mut World) {
+    if world.resource::<CleanupDisplay>().show {
+        egui::SidePanel::left("cleanup")
+            .resizable(true)
+            .min_width(320.0)
+            .show(&input.context, |ui| {
+                if let Err(err) = world.try_show(input.id, ui) {
+                    error!("Unable to display cleanup panel: {err:?}");
+                }
+            });
+    }
+}
+
+/// A widget that lists the vertices, drawings, and textures of the current
+/// site that nothing refers to, and lets the user delete any of them. It also
+/// offers to merge vertices that sit on top of each other.
+///
+/// Use [`CleanupPlugin`] to add this to your application.
+#[derive(SystemParam)]
+pub struct Cleanup<'w, 's> {
+    orphans: FindOrphans<'w, 's>,
+    duplicates: FindDuplicateAnchors<'w, 's>,
+    cleanup_vertices: EventWriter<'w, CleanupVertices>,
+    last_cleanup: Res<'w, LastVertexCleanup>,
+    names: Query<'w, 's, &'static NameInSite>,
+    display: ResMut<'w, CleanupDisplay>,
+    current_workspace: Res<'w, CurrentWorkspace>,
+    delete: EventWriter<'w, Delete>,
+    selector: SelectorWidget<'w, 's>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem for Cleanup<'w, 's> {
+    fn show(_: (), ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) -> () {
+        let mut params = state.get_mut(world);
+        params.show_widget(ui);
+    }
+}
+
+impl<'w, 's> Cleanup<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let Some(root) = self.current_workspace.root else {
+            return;
+        };
+        if *self.app_state.get() != AppState::SiteEditor {
+            ui.label("Cleanup is only available while editing a site");
+            if ui.add(Button::new("Close")).clicked() {
+                self.display.show = false;
+            }
+            return;
+        }
+
+        let orphans = self.orphans.find(root);
+        // Forget about anything that has been deleted or is in use again
+        self.display.marked.retain(|e| {
+            orphans.anchors.contains(e)
+                || orphans.drawings.contains(e)
+                || orphans.textures.contains(e)
+        });
+
+        ScrollArea::vertical()
+            .max_height(600.0)
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                if orphans.is_empty() {
+                    ui.label("Nothing to clean up");
+                }
+                self.show_orphans(ui, "Unused vertices", &orphans.anchors);
+                self.show_orphans(ui, "Drawings without a level", &orphans.drawings);
+                self.show_orphans(ui, "Unused textures", &orphans.textures);
+            });
+        ui.add_space(10.0);
+        self.show_duplicates(ui, root);
+        ui.add_space(10.0);
+
+        let marked = self.display.marked.len();
+        if ui
+            .add_enabled(marked > 0, Button::new(format!("Delete {marked} marked")))
+            .clicked()
+        {
+            for e in self.display.marked.drain() {
+                if orphans.drawings.contains(&e) {
+                    // Drawings own their own anchors and fiducials
+                    self.delete.send(Delete::new(e).and_dependents());
+                } else {
+                    self.delete.send(Delete::new(e));
+                }
+            }
+        }
+        if ui.add(Button::new("Close")).clicked() {
+            self.display.show = false;
+        }
+    }
+
+    fn show_duplicates(&mut self, ui: &mut Ui, root: Entity) {
+        CollapsingHeader::new("Duplicate vertices")
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Merge within")
+                        .on_hover_text("Vertices on the same level closer than this become one");
+                    ui.add(
+                        DragValue::new(&mut self.display.merge_epsilon)
+                            .clamp_range(0.001..=1.0)
+                            .speed(0.001)
+                            .suffix(" m"),
+                    );
+                });
+                let groups = self.duplicates.find(root, self.display.merge_epsilon);
+                let duplicates: usize = groups.iter().map(|g| g.len() - 1).sum();
+                ui.label(format!(
+                    "{duplicates} vertices can be merged into {} others",
+                    groups.len()
+                ));
+                if ui
+                    .button("Merge duplicates and clean up")
+                    .on_hover_text(
+                        "Merge duplicate vertices, remove lanes and walls that become \
+                        identical, and delete every unused vertex",
+                    )
+                    .clicked()
+                {
+                    self.cleanup_vertices.send(CleanupVertices {
+                        site: root,
+                        epsilon: self.display.merge_epsilon,
+                    });
+                    self.display.marked.clear();
+                }
+                if let Some(report) = &self.last_cleanup.0 {
+                    ui.label(format!("Last cleanup: {}", report.summary()));
+                }
+            });
+    }
+
+    fn show_orphans(&mut self, ui: &mut Ui, label: &str, orphans: &[Entity]) {
+        if orphans.is_empty() {
+            return;
+        }
+
+        CollapsingHeader::new(format!("{label} ({})", orphans.len()))
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Mark all").clicked() {
+                        self.display.marked.extend(orphans.iter().copied());
+                    }
+                    if ui.button("Unmark all").clicked() {
+                        for e in orphans {
+                            self.display.marked.remove(e);
+                        }
+                    }
+                });
+                for e in orphans {
+                    ui.horizontal(|ui| {
+                        let mut marked = self.display.marked.contains(e);
+                        if ui.checkbox(&mut marked, "").changed() {
+                            if marked {
+                                self.display.marked.insert(*e);
+                            } else {
+                                self.display.marked.remove(e);
+                            }
+                        }
+                        self.selector.show_widget(*e, ui);
+                        if let Ok(name) = self.names.get(*e) {
+                            ui.label(&name.0);
+                        }
+                    });
+                }
+            });
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct CleanupDisplay {
+    pub show: bool,
+    /// Orphaned elements that the user has marked for deletion
+    pub marked: HashSet<Entity>,
+    /// Vertices closer than this many meters are treated as duplicates
+    pub merge_epsilon: f32,
+}
+
+impl Default for CleanupDisplay {
+    fn default() -> Self {
+        Self {
+            show: false,
+            marked: HashSet::new(),
+            merge_epsilon: DEFAULT_MERGE_EPSILON,
+        }
+    }
+}
+
+fn handle_cleanup_panel_visibility(
+    mut menu_events: EventReader<MenuEvent>,
+    cleanup_menu: Res<CleanupMenu>,
+    mut display: ResMut<CleanupDisplay>,
+) {
+    for event in menu_events.read() {
+        if event.clicked() && event.source() == cleanup_menu.cleanup_tool {
+            display.show = true;
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct CleanupMenu {
+    cleanup_tool: Entity,
+}
+
+impl FromWorld for CleanupMenu {
+    fn from_world(world: &mut World) -> Self {
+        let tool_header = world.resource::<ToolMenu>().get();
+        let cleanup_tool = world
+            .spawn(MenuItem::Text("Cleanup Tool".into()))
+            .set_parent(tool_header)
+            .id();
+
+        CleanupMenu { cleanup_tool }
+    }
+}
@@ -0,0 +1,226 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{
+        connectivity_color, AnalyzeConnectivity, ConnectivityOverlay, CurrentLevel,
+        GraphConnectivity, NameInSite,
+    },
+    widgets::{
+        menu_bar::{MenuEvent, MenuItem, ToolMenu},
+        prelude::*,
+        SelectorWidget,
+    },
+    AppState,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_egui::egui::{self, Button, CollapsingHeader, Color32, RichText, ScrollArea, Ui};
+
+/// Add a [`ConnectivityReport`] widget to your application.
+#[derive(Default)]
+pub struct ConnectivityReportPlugin {}
+
+impl Plugin for ConnectivityReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConnectivityReportMenu>()
+            .init_resource::<ConnectivityReportDisplay>()
+            .add_systems(Update, handle_connectivity_report_visibility);
+
+        let panel = PanelWidget::new(connectivity_report_panel, &mut app.world);
+        let widget = Widget::new::<ConnectivityReport>(&mut app.world);
+        app.world.spawn((panel, widget));
+    }
+}
+
+fn connectivity_report_panel(In(input): In<PanelWidgetInput>, world: &mut World) {
+    if world.resource::<ConnectivityReportDisplay>().show {
+        egui::SidePanel::left("connectivity_report")
+            .resizable(true)
+            .min_width(320.0)
+            .show(&input.context, |ui| {
+                if let Err(err) = world.try_show(input.id, ui) {
+                    error!("Unable to display connectivity report: {err:?}");
+                }
+            });
+    }
+}
+
+/// A widget that reports how well the lanes of each nav graph on the current
+/// level are connected, so users can find out why a robot fails to plan
+/// between two places.
+///
+/// Use [`ConnectivityReportPlugin`] to add this to your application.
+#[derive(SystemParam)]
+pub struct ConnectivityReport<'w, 's> {
+    analyze: AnalyzeConnectivity<'w, 's>,
+    names: Query<'w, 's, &'static NameInSite>,
+    current_level: Res<'w, CurrentLevel>,
+    overlay: ResMut<'w, ConnectivityOverlay>,
+    display: ResMut<'w, ConnectivityReportDisplay>,
+    selector: SelectorWidget<'w, 's>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem for ConnectivityReport<'w, 's> {
+    fn show(_: (), ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) -> () {
+        let mut params = state.get_mut(world);
+        params.show_widget(ui);
+    }
+}
+
+impl<'w, 's> ConnectivityReport<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let level = self.current_level.0.filter(|_| {
+            matches!(
+                self.app_state.get(),
+                AppState::SiteEditor | AppState::SiteVisualizer
+            )
+        });
+        let Some(level) = level else {
+            ui.label("Connectivity can only be analyzed for a level of a site");
+            if ui.add(Button::new("Close")).clicked() {
+                self.display.show = false;
+                self.overlay.enabled = false;
+            }
+            return;
+        };
+
+        let mut enabled = self.overlay.enabled;
+        if ui
+            .checkbox(&mut enabled, "Color graphs by connectivity")
+            .changed()
+        {
+            self.overlay.enabled = enabled;
+        }
+
+        let mut reports = vec![self.analyze.analyze(level, None)];
+        reports.extend(self.analyze.analyze_all(level));
+        ScrollArea::vertical()
+            .max_height(600.0)
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                for report in &reports {
+                    self.show_report(ui, report);
+                }
+            });
+        ui.add_space(10.0);
+
+        if ui.add(Button::new("Close")).clicked() {
+            self.display.show = false;
+            self.overlay.enabled = false;
+        }
+    }
+
+    fn show_report(&mut self, ui: &mut Ui, report: &GraphConnectivity) {
+        let name = match report.nav_graph {
+            Some(g) => self
+                .names
+                .get(g)
+                .map(|n| n.0.clone())
+                .unwrap_or_else(|_| format!("{g:?}")),
+            None => "All lanes".to_owned(),
+        };
+        let status = if report.components.is_empty() {
+            "no lanes".to_owned()
+        } else if report.is_connected() {
+            "connected".to_owned()
+        } else {
+            format!(
+                "{} regions, {} isolated locations",
+                report.components.len(),
+                report.isolated_locations.len(),
+            )
+        };
+
+        CollapsingHeader::new(format!("{name}: {status}"))
+            .id_source(("connectivity_report", report.nav_graph))
+            .default_open(!report.is_connected())
+            .show(ui, |ui| {
+                let colored = self.overlay.enabled && self.overlay.nav_graph == report.nav_graph;
+                if ui
+                    .add_enabled(!colored, Button::new("Color this graph"))
+                    .clicked()
+                {
+                    self.overlay.nav_graph = report.nav_graph;
+                    self.overlay.enabled = true;
+                }
+
+                for (i, region) in report.unreachable_regions().iter().enumerate() {
+                    let c = connectivity_color(i + 1).as_rgba_u8();
+                    let color = Color32::from_rgb(c[0], c[1], c[2]);
+                    CollapsingHeader::new(
+                        RichText::new(format!("Unreachable region of {} waypoints", region.len()))
+                            .color(color),
+                    )
+                    .id_source(("connectivity_region", report.nav_graph, i))
+                    .show(ui, |ui| {
+                        ui.horizontal_wrapped(|ui| {
+                            for e in region {
+                                self.selector.show_widget(*e, ui);
+                            }
+                        });
+                    });
+                }
+
+                if !report.isolated_locations.is_empty() {
+                    ui.label("Locations that are not on any lane");
+                    for e in &report.isolated_locations {
+                        ui.horizontal(|ui| {
+                            self.selector.show_widget(*e, ui);
+                            if let Ok(name) = self.names.get(*e) {
+                                ui.label(&name.0);
+                            }
+                        });
+                    }
+                }
+            });
+    }
+}
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ConnectivityReportDisplay {
+    pub show: bool,
+}
+
+fn handle_connectivity_report_visibility(
+    mut menu_events: EventReader<MenuEvent>,
+    menu: Res<ConnectivityReportMenu>,
+    mut display: ResMut<ConnectivityReportDisplay>,
+) {
+    for event in menu_events.read() {
+        if event.clicked() && event.source() == menu.connectivity_tool {
+            display.show = true;
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct ConnectivityReportMenu {
+    connectivity_tool: Entity,
+}
+
+impl FromWorld for ConnectivityReportMenu {
+    fn from_world(world: &mut World) -> Self {
+        let tool_header = world.resource::<ToolMenu>().get();
+        let connectivity_tool = world
+            .spawn(MenuItem::Text("Connectivity".into()))
+            .set_parent(tool_header)
+            .id();
+
+        ConnectivityReportMenu { connectivity_tool }
+    }
+}
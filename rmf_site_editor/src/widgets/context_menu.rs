@@ -0,0 +1,170 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{
+        ActionTarget, EntityActions, Hovering, IntersectGroundPlaneParams, PickingBlockers,
+        RunEntityAction, ToolMode,
+    },
+    AppState,
+};
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::{egui, EguiContexts};
+
+/// How far the cursor may move while the right mouse button is held down for
+/// the press to still count as a click instead of a camera drag, in logical
+/// pixels.
+const CONTEXT_CLICK_TOLERANCE: f32 = 4.0;
+
+/// Right-clicking an entity in the scene opens a menu of the
+/// [`EntityActions`] that apply to it.
+#[derive(Default)]
+pub struct ContextMenuPlugin {}
+
+impl Plugin for ContextMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ContextMenu>().add_systems(
+            Update,
+            (
+                detect_context_menu_clicks,
+                collect_context_menu_actions,
+                show_context_menu,
+            )
+                .chain()
+                .run_if(in_state(AppState::SiteEditor)),
+        );
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OpenContextMenu {
+    pub target: ActionTarget,
+    /// Where the menu is drawn, in logical pixels
+    pub screen_position: Vec2,
+    /// Labels of the actions that apply to the target
+    pub actions: Vec<&'static str>,
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct ContextMenu {
+    pub open: Option<OpenContextMenu>,
+    /// Where the right mouse button was pressed down
+    pressed_at: Option<Vec2>,
+    /// A menu that was requested but whose actions are not known yet
+    pending: Option<(ActionTarget, Vec2)>,
+}
+
+fn detect_context_menu_clicks(
+    mouse: Res<Input<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    picking_blockers: Res<PickingBlockers>,
+    hovering: Res<Hovering>,
+    tool_mode: Res<ToolMode>,
+    intersect_ground_params: IntersectGroundPlaneParams,
+    mut menu: ResMut<ContextMenu>,
+) {
+    let Some(cursor) = windows.get_single().ok().and_then(|w| w.cursor_position()) else {
+        return;
+    };
+
+    if mouse.just_pressed(MouseButton::Right) {
+        menu.pressed_at = (!picking_blockers.ui).then_some(cursor);
+    }
+
+    if !mouse.just_released(MouseButton::Right) {
+        return;
+    }
+
+    let Some(pressed_at) = menu.pressed_at.take() else {
+        return;
+    };
+    if pressed_at.distance(cursor) > CONTEXT_CLICK_TOLERANCE {
+        // The right button was used to move the camera
+        return;
+    }
+
+    menu.open = None;
+    // Other tools use the right button for their own purposes
+    if *tool_mode != ToolMode::Select {
+        return;
+    }
+    let Some(entity) = hovering.0 else {
+        return;
+    };
+    let mut target = ActionTarget::new(entity);
+    if let Some(tf) = intersect_ground_params.ground_plane_intersection() {
+        target = target.at(tf.translation);
+    }
+    menu.pending = Some((target, cursor));
+}
+
+fn collect_context_menu_actions(world: &mut World) {
+    let Some((target, screen_position)) = world.resource_mut::<ContextMenu>().pending.take() else {
+        return;
+    };
+    let actions = world
+        .resource::<EntityActions>()
+        .available_for(world, target.entity)
+        .into_iter()
+        .map(|action| action.label)
+        .collect();
+    world.resource_mut::<ContextMenu>().open = Some(OpenContextMenu {
+        target,
+        screen_position,
+        actions,
+    });
+}
+
+fn show_context_menu(
+    mut egui_context: EguiContexts,
+    mut menu: ResMut<ContextMenu>,
+    mut run_action: EventWriter<RunEntityAction>,
+) {
+    let Some(open) = &menu.open else {
+        return;
+    };
+
+    let ctx = egui_context.ctx_mut();
+    let mut chosen = None;
+    let response = egui::Area::new("entity_context_menu")
+        .order(egui::Order::Foreground)
+        .fixed_pos(egui::pos2(open.screen_position.x, open.screen_position.y))
+        .show(ctx, |ui| {
+            egui::Frame::menu(ui.style()).show(ui, |ui| {
+                ui.set_min_width(140.0);
+                if open.actions.is_empty() {
+                    ui.label("No actions available");
+                }
+                for label in &open.actions {
+                    if ui.button(*label).clicked() {
+                        chosen = Some(*label);
+                    }
+                }
+            });
+        })
+        .response;
+
+    if let Some(label) = chosen {
+        run_action.send(RunEntityAction {
+            label,
+            target: open.target,
+        });
+        menu.open = None;
+    } else if response.clicked_elsewhere() || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        menu.open = None;
+    }
+}
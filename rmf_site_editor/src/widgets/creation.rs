@@ -16,7 +16,9 @@
 */
 
 use crate::{
-    interaction::{AnchorSelection, ObjectPlacement},
+    interaction::{
+        AnchorSelection, ObjectPlacement, SetToolMode, ToolMode, ToolModeExt, ToolModeInfo,
+    },
     site::{
         Affiliation, AssetSource, Category, DefaultFile, DrawingBundle, DrawingProperties, Group,
         IsStatic, Members, ModelDescriptionBundle, ModelInstance, ModelMarker, ModelProperty,
@@ -24,7 +26,7 @@ use crate::{
     },
     widgets::{
         AssetGalleryStatus, HeaderTilePlugin, Icons, InspectAssetSourceComponent,
-        InspectScaleComponent, Tile, WidgetSystem,
+        InspectScaleComponent, Tile, ToolbarPlugin, WidgetSystem,
     },
     AppState, CurrentWorkspace,
 };
@@ -33,21 +35,19 @@ use bevy::ecs::system::{SystemParam, SystemState};
 use bevy::prelude::*;
 use bevy_egui::egui::{self, Button, ComboBox, Ui};
 
-/// This plugin creates a standard set of site object creation buttons
+/// This plugin creates the toolbar along with a standard set of site object
+/// creation buttons
 #[derive(Default)]
 pub struct StandardCreationPlugin {}
 
 impl Plugin for StandardCreationPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
-            LaneCreationPlugin::default(),
+            ToolbarPlugin::default(),
             LocationCreationPlugin::default(),
-            WallCreationPlugin::default(),
-            DoorCreationPlugin::default(),
             LiftCreationPlugin::default(),
-            FloorCreationPlugin::default(),
+            ZoneCreationPlugin::default(),
             FiducialCreationPlugin::default(),
-            MeasurementCreationPlugin::default(),
             DrawingCreationPlugin::default(),
             ModelCreationPlugin::default(),
             BrowseFuelTogglePlugin::default(),
@@ -55,33 +55,6 @@ impl Plugin for StandardCreationPlugin {
     }
 }
 
-/// Add a widget for lane creation
-#[derive(Default)]
-pub struct LaneCreationPlugin {}
-
-impl Plugin for LaneCreationPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_plugins(HeaderTilePlugin::<LaneCreation>::new());
-    }
-}
-
-#[derive(SystemParam)]
-struct LaneCreation<'w, 's> {
-    app_state: Res<'w, State<AppState>>,
-    anchor_selection: AnchorSelection<'w, 's>,
-}
-
-impl<'w, 's> WidgetSystem<Tile> for LaneCreation<'w, 's> {
-    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
-        let mut params = state.get_mut(world);
-        if let AppState::SiteEditor = params.app_state.get() {
-            if button_clicked(ui, "↔", "Lane") {
-                params.anchor_selection.create_lanes();
-            }
-        }
-    }
-}
-
 /// Add widget for location creation
 #[derive(Default)]
 pub struct LocationCreationPlugin {}
@@ -109,60 +82,6 @@ impl<'w, 's> WidgetSystem<Tile> for LocationCreation<'w, 's> {
     }
 }
 
-/// Add widget for wall creation
-#[derive(Default)]
-pub struct WallCreationPlugin {}
-
-impl Plugin for WallCreationPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_plugins(HeaderTilePlugin::<WallCreation>::new());
-    }
-}
-
-#[derive(SystemParam)]
-pub struct WallCreation<'w, 's> {
-    app_state: Res<'w, State<AppState>>,
-    anchor_selection: AnchorSelection<'w, 's>,
-}
-
-impl<'w, 's> WidgetSystem<Tile> for WallCreation<'w, 's> {
-    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
-        let mut params = state.get_mut(world);
-        if let AppState::SiteEditor = params.app_state.get() {
-            if button_clicked(ui, "■", "Wall") {
-                params.anchor_selection.create_walls();
-            }
-        }
-    }
-}
-
-/// Add widget for door creation
-#[derive(Default)]
-pub struct DoorCreationPlugin {}
-
-impl Plugin for DoorCreationPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_plugins(HeaderTilePlugin::<DoorCreation>::new());
-    }
-}
-
-#[derive(SystemParam)]
-pub struct DoorCreation<'w, 's> {
-    app_state: Res<'w, State<AppState>>,
-    anchor_selection: AnchorSelection<'w, 's>,
-}
-
-impl<'w, 's> WidgetSystem<Tile> for DoorCreation<'w, 's> {
-    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
-        let mut params = state.get_mut(world);
-        if let AppState::SiteEditor = params.app_state.get() {
-            if button_clicked(ui, "🚪", "Door") {
-                params.anchor_selection.create_door();
-            }
-        }
-    }
-}
-
 /// Add widget for lift creation
 #[derive(Default)]
 pub struct LiftCreationPlugin {}
@@ -190,28 +109,28 @@ impl<'w, 's> WidgetSystem<Tile> for LiftCreation<'w, 's> {
     }
 }
 
-/// Add widget for floor creation
+/// Add a widget for zone creation
 #[derive(Default)]
-pub struct FloorCreationPlugin {}
+pub struct ZoneCreationPlugin {}
 
-impl Plugin for FloorCreationPlugin {
+impl Plugin for ZoneCreationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(HeaderTilePlugin::<FloorCreation>::new());
+        app.add_plugins(HeaderTilePlugin::<ZoneCreation>::new());
     }
 }
 
 #[derive(SystemParam)]
-pub struct FloorCreation<'w, 's> {
+pub struct ZoneCreation<'w, 's> {
     app_state: Res<'w, State<AppState>>,
     anchor_selection: AnchorSelection<'w, 's>,
 }
 
-impl<'w, 's> WidgetSystem<Tile> for FloorCreation<'w, 's> {
+impl<'w, 's> WidgetSystem<Tile> for ZoneCreation<'w, 's> {
     fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
         let mut params = state.get_mut(world);
         if let AppState::SiteEditor = params.app_state.get() {
-            if button_clicked(ui, "✏", "Floor") {
-                params.anchor_selection.create_floor();
+            if button_clicked(ui, "⛔", "Zone") {
+                params.anchor_selection.create_zone();
             }
         }
     }
@@ -254,33 +173,6 @@ impl<'w, 's> WidgetSystem<Tile> for FiducialCreation<'w, 's> {
     }
 }
 
-/// Add widget for measurement creation
-#[derive(Default)]
-pub struct MeasurementCreationPlugin {}
-
-impl Plugin for MeasurementCreationPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_plugins(HeaderTilePlugin::<MeasurementCreation>::new());
-    }
-}
-
-#[derive(SystemParam)]
-pub struct MeasurementCreation<'w, 's> {
-    app_state: Res<'w, State<AppState>>,
-    anchor_selection: AnchorSelection<'w, 's>,
-}
-
-impl<'w, 's> WidgetSystem<Tile> for MeasurementCreation<'w, 's> {
-    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
-        let mut params = state.get_mut(world);
-        if let AppState::SiteDrawingEditor = params.app_state.get() {
-            if button_clicked(ui, "📏", "Measurement") {
-                params.anchor_selection.create_measurements();
-            }
-        }
-    }
-}
-
 #[derive(Default)]
 pub struct DrawingCreationPlugin {}
 
@@ -374,7 +266,17 @@ pub struct ModelCreationPlugin {}
 impl Plugin for ModelCreationPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PendingModelDescription>()
-            .add_plugins(HeaderTilePlugin::<ModelCreation>::new());
+            .add_plugins(HeaderTilePlugin::<ModelCreation>::new())
+            .add_tool_mode(
+                ToolModeInfo {
+                    mode: ToolMode::AddModel,
+                    icon: "⊕",
+                    label: "Model: place an instance of the chosen model description",
+                    app_states: &[AppState::SiteEditor],
+                    shortcut: Some(|keys| &keys.add_model_tool),
+                },
+                start_model_tool,
+            );
     }
 }
 
@@ -408,8 +310,7 @@ pub struct ModelCreation<'w, 's> {
     icons: Res<'w, Icons>,
     children: Query<'w, 's, &'static Children>,
     descriptions: Query<'w, 's, &'static NameInSite, (With<ModelMarker>, With<Group>)>,
-    object_placement: ObjectPlacement<'w, 's>,
-    next_instance_name: GetNextInstanceName<'w, 's>,
+    set_tool_mode: EventWriter<'w, SetToolMode>,
     commands: Commands<'w, 's>,
 }
 
@@ -427,7 +328,7 @@ impl<'w, 's> WidgetSystem<Tile> for ModelCreation<'w, 's> {
                     // we reset the style before drawing the inner widgets.
                     ui.reset_style();
 
-                    let mut add_instance = None;
+                    let mut add_instance = false;
                     ui.vertical(|ui| {
                         egui::Resize::default()
                             .default_width(300.0)
@@ -444,9 +345,7 @@ impl<'w, 's> WidgetSystem<Tile> for ModelCreation<'w, 's> {
                                         .on_hover_text("Add Instance")
                                         .clicked()
                                     {
-                                        add_instance = params.pending.selected.map(|description| {
-                                            params.next_instance_name.get_for(description)
-                                        });
+                                        add_instance = true;
                                     }
 
                                     let selected_description_text =
@@ -482,11 +381,7 @@ impl<'w, 's> WidgetSystem<Tile> for ModelCreation<'w, 's> {
                                     if let Some(selected_new_description) = selected_new_description
                                     {
                                         params.pending.selected = Some(selected_new_description);
-                                        add_instance = Some(
-                                            params
-                                                .next_instance_name
-                                                .get_for(selected_new_description),
-                                        );
+                                        add_instance = true;
                                     }
                                 })
                                 .response
@@ -551,23 +446,15 @@ impl<'w, 's> WidgetSystem<Tile> for ModelCreation<'w, 's> {
                                         .id();
 
                                     params.pending.selected = Some(description_entity);
-                                    add_instance = Some(format!("{}_0", params.pending.name));
+                                    add_instance = true;
                                 }
 
                                 ui.add_space(10.0);
                             });
                     });
 
-                    if let Some(new_instance_name) = add_instance {
-                        if let Some(description) = params.pending.selected {
-                            let instance = ModelInstance {
-                                name: NameInSite(new_instance_name),
-                                description: Affiliation(Some(description)),
-                                ..Default::default()
-                            };
-                            params.object_placement.place_object_2d(instance);
-                        }
-
+                    if add_instance {
+                        params.set_tool_mode.send(SetToolMode(ToolMode::AddModel));
                         ui.close_menu();
                     }
                 })
@@ -579,6 +466,32 @@ impl<'w, 's> WidgetSystem<Tile> for ModelCreation<'w, 's> {
     }
 }
 
+/// Start placing an instance of the model description that was last chosen
+/// in the model menu.
+fn start_model_tool(
+    pending: Res<PendingModelDescription>,
+    next_instance_name: GetNextInstanceName,
+    mut object_placement: ObjectPlacement,
+    asset_gallery: Option<ResMut<AssetGalleryStatus>>,
+    mut tool_mode: ResMut<ToolMode>,
+) {
+    let Some(description) = pending.selected else {
+        warn!("Choose or create a model description before placing models");
+        if let Some(mut asset_gallery) = asset_gallery {
+            asset_gallery.show = true;
+        }
+        *tool_mode = ToolMode::Select;
+        return;
+    };
+
+    let instance = ModelInstance {
+        name: NameInSite(next_instance_name.get_for(description)),
+        description: Affiliation(Some(description)),
+        ..Default::default()
+    };
+    object_placement.place_object_2d(instance);
+}
+
 #[derive(SystemParam)]
 pub struct GetNextInstanceName<'w, 's> {
     names: Query<'w, 's, &'static NameInSite>,
@@ -646,11 +559,11 @@ impl<'w> WidgetSystem<Tile> for BrowseFuelToggle<'w> {
             .as_ref()
             .is_some_and(|gallery| gallery.show);
         let tooltip = if !enabled {
-            "Fuel asset browser is not available"
+            "Asset gallery is not available"
         } else if toggled_on {
-            "Close fuel asset browser"
+            "Close asset gallery"
         } else {
-            "Open fuel asset browser"
+            "Open asset gallery"
         };
 
         if ui
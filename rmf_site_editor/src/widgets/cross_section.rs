@@ -0,0 +1,307 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::Selection,
+    site::{Anchor, Category, CrossSection, CutCrossSection, SectionLine, SiteSection},
+    widgets::{
+        menu_bar::{MenuEvent, MenuItem, ToolMenu},
+        prelude::*,
+    },
+    AppState, CurrentWorkspace,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_egui::egui::{
+    self, Align2, Button, Color32, DragValue, FontId, Grid, Pos2, Rect, Rounding, Sense, Stroke,
+    Ui, Vec2 as EguiVec2,
+};
+use rmf_site_format::DEFAULT_LEVEL_HEIGHT;
+
+/// How thick floors are drawn in a section, in meters
+const SECTION_FLOOR_THICKNESS: f32 = 0.15;
+/// How thick walls and doors are drawn in a section, in meters
+const SECTION_WALL_THICKNESS: f32 = 0.2;
+/// Space around the section drawing, in points
+const SECTION_MARGIN: f32 = 24.0;
+
+/// Add a [`CrossSectionView`] widget to your application.
+#[derive(Default)]
+pub struct CrossSectionViewPlugin {}
+
+impl Plugin for CrossSectionViewPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CrossSectionMenu>()
+            .init_resource::<CrossSectionDisplay>()
+            .add_systems(Update, handle_cross_section_visibility);
+
+        let panel = PanelWidget::new(cross_section_panel, &mut app.world);
+        let widget = Widget::new::<CrossSectionView>(&mut app.world);
+        app.world.spawn((panel, widget));
+    }
+}
+
+fn cross_section_panel(In(input): In<PanelWidgetInput>, world: &mut World) {
+    if world.resource::<CrossSectionDisplay>().show {
+        egui::SidePanel::left("cross_section")
+            .resizable(true)
+            .min_width(480.0)
+            .show(&input.context, |ui| {
+                if let Err(err) = world.try_show(input.id, ui) {
+                    error!("Unable to display cross section: {err:?}");
+                }
+            });
+    }
+}
+
+/// A widget that shows a vertical section through every level of the site
+/// along a line, to check that lift shafts and atriums line up.
+///
+/// Use [`CrossSectionViewPlugin`] to add this to your application.
+#[derive(SystemParam)]
+pub struct CrossSectionView<'w, 's> {
+    cut: CutCrossSection<'w, 's>,
+    cross_section: ResMut<'w, CrossSection>,
+    display: ResMut<'w, CrossSectionDisplay>,
+    selection: Res<'w, Selection>,
+    anchors: Query<'w, 's, &'static Anchor>,
+    current_workspace: Res<'w, CurrentWorkspace>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem for CrossSectionView<'w, 's> {
+    fn show(_: (), ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) -> () {
+        let mut params = state.get_mut(world);
+        params.show_widget(ui);
+    }
+}
+
+impl<'w, 's> CrossSectionView<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let site = self.current_workspace.root.filter(|_| {
+            matches!(
+                self.app_state.get(),
+                AppState::SiteEditor | AppState::SiteVisualizer
+            )
+        });
+        let Some(site) = site else {
+            ui.label("Cross sections are only available for sites");
+            if ui.add(Button::new("Close")).clicked() {
+                self.close();
+            }
+            return;
+        };
+
+        let selected = self
+            .selection
+            .0
+            .and_then(|e| self.anchors.get(e).ok())
+            .map(|a| Vec2::from(a.translation_for_category(Category::General)));
+        let mut line = self
+            .cross_section
+            .line
+            .unwrap_or(SectionLine::new(Vec2::ZERO, Vec2::new(10.0, 0.0)));
+        Grid::new("cross_section_line")
+            .num_columns(3)
+            .show(ui, |ui| {
+                for (label, p) in [("Start", &mut line.start), ("End", &mut line.end)] {
+                    ui.label(label);
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            DragValue::new(&mut p.x)
+                                .speed(0.05)
+                                .prefix("x ")
+                                .suffix(" m"),
+                        );
+                        ui.add(
+                            DragValue::new(&mut p.y)
+                                .speed(0.05)
+                                .prefix("y ")
+                                .suffix(" m"),
+                        );
+                    });
+                    if ui
+                        .add_enabled(selected.is_some(), Button::new("Use selected vertex"))
+                        .clicked()
+                    {
+                        if let Some(selected) = selected {
+                            *p = selected;
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+        if self.cross_section.line != Some(line) {
+            self.cross_section.line = Some(line);
+        }
+
+        let section = self.cut.cut(site, &line);
+        ui.separator();
+        show_section(ui, &line, &section);
+        ui.separator();
+
+        if ui.add(Button::new("Close")).clicked() {
+            self.close();
+        }
+    }
+
+    fn close(&mut self) {
+        self.display.show = false;
+        self.cross_section.line = None;
+    }
+}
+
+fn show_section(ui: &mut Ui, line: &SectionLine, section: &SiteSection) {
+    let Some((bottom, top)) =
+        section
+            .levels
+            .iter()
+            .map(|l| l.elevation)
+            .fold(None, |range: Option<(f32, f32)>, e| match range {
+                Some((lo, hi)) => Some((lo.min(e), hi.max(e))),
+                None => Some((e, e)),
+            })
+    else {
+        ui.label("This site has no levels");
+        return;
+    };
+    let top = top + DEFAULT_LEVEL_HEIGHT;
+    let length = line.length().max(0.1);
+
+    let width = ui.available_width();
+    let height = (width * (top - bottom) / length).clamp(120.0, 480.0);
+    let (response, painter) = ui.allocate_painter(EguiVec2::new(width, height), Sense::hover());
+    let area = response.rect.shrink(SECTION_MARGIN);
+    // Use the same scale in both directions so the section is not distorted
+    let scale = (area.width() / length).min(area.height() / (top - bottom).max(0.1));
+    let to_screen = |distance: f32, elevation: f32| -> Pos2 {
+        Pos2::new(
+            area.left() + distance * scale,
+            area.bottom() - (elevation - bottom) * scale,
+        )
+    };
+    let bar = |distance: f32, elevation: f32, thickness: f32| -> Rect {
+        let half = (thickness * scale / 2.0).max(1.0);
+        Rect::from_two_pos(
+            to_screen(distance, elevation) - EguiVec2::new(half, 0.0),
+            to_screen(distance, elevation + DEFAULT_LEVEL_HEIGHT) + EguiVec2::new(half, 0.0),
+        )
+    };
+
+    let visuals = ui.visuals();
+    let text_color = visuals.text_color();
+    painter.rect_filled(response.rect, Rounding::ZERO, visuals.extreme_bg_color);
+
+    let shaft_color = Color32::from_rgba_unmultiplied(80, 140, 255, 60);
+    for lift in &section.lifts {
+        for [a, b] in &lift.intervals {
+            let rect = Rect::from_two_pos(to_screen(*a, bottom), to_screen(*b, top));
+            painter.rect(
+                rect,
+                Rounding::ZERO,
+                shaft_color,
+                Stroke::new(1.0, Color32::from_rgb(80, 140, 255)),
+            );
+            painter.text(
+                rect.center_top(),
+                Align2::CENTER_BOTTOM,
+                &lift.name,
+                FontId::proportional(12.0),
+                text_color,
+            );
+        }
+    }
+
+    for level in &section.levels {
+        let e = level.elevation;
+        painter.line_segment(
+            [to_screen(0.0, e), to_screen(length, e)],
+            Stroke::new(1.0, Color32::from_gray(90)),
+        );
+        painter.text(
+            to_screen(0.0, e) + EguiVec2::new(-4.0, 0.0),
+            Align2::RIGHT_BOTTOM,
+            format!("{} ({e:.2} m)", level.name),
+            FontId::proportional(12.0),
+            text_color,
+        );
+        for [a, b] in &level.floors {
+            let rect =
+                Rect::from_two_pos(to_screen(*a, e), to_screen(*b, e - SECTION_FLOOR_THICKNESS));
+            painter.rect_filled(rect, Rounding::ZERO, Color32::from_gray(160));
+        }
+        for (d, _) in &level.walls {
+            painter.rect_filled(
+                bar(*d, e, SECTION_WALL_THICKNESS),
+                Rounding::ZERO,
+                Color32::from_gray(220),
+            );
+        }
+        for (d, _) in &level.doors {
+            painter.rect_filled(
+                bar(*d, e, SECTION_WALL_THICKNESS),
+                Rounding::ZERO,
+                Color32::from_rgb(200, 140, 60),
+            );
+        }
+    }
+
+    ui.horizontal(|ui| {
+        for (label, color) in [
+            ("Floor", Color32::from_gray(160)),
+            ("Wall", Color32::from_gray(220)),
+            ("Door", Color32::from_rgb(200, 140, 60)),
+            ("Lift shaft", Color32::from_rgb(80, 140, 255)),
+        ] {
+            ui.colored_label(color, "■");
+            ui.label(label);
+        }
+    });
+}
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CrossSectionDisplay {
+    pub show: bool,
+}
+
+fn handle_cross_section_visibility(
+    mut menu_events: EventReader<MenuEvent>,
+    menu: Res<CrossSectionMenu>,
+    mut display: ResMut<CrossSectionDisplay>,
+) {
+    for event in menu_events.read() {
+        if event.clicked() && event.source() == menu.cross_section_tool {
+            display.show = true;
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct CrossSectionMenu {
+    cross_section_tool: Entity,
+}
+
+impl FromWorld for CrossSectionMenu {
+    fn from_world(world: &mut World) -> Self {
+        let tool_header = world.resource::<ToolMenu>().get();
+        let cross_section_tool = world
+            .spawn(MenuItem::Text("Cross Section".into()))
+            .set_parent(tool_header)
+            .id();
+
+        CrossSectionMenu { cross_section_tool }
+    }
+}
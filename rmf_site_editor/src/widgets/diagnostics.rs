@@ -16,13 +16,16 @@
 */
 
 use crate::{
-    site::{Change, FilteredIssueKinds, FilteredIssues, IssueKey},
+    site::{
+        Change, FilteredIssueKinds, FilteredIssues, InsertDoorway, IssueKey, LaneMarker,
+        LANE_CROSSES_DOOR_ISSUE_UUID,
+    },
     widgets::{
         menu_bar::{MenuEvent, MenuItem, ToolMenu},
         prelude::*,
-        SelectorWidget,
+        IssueBadgesDisplay, SelectorWidget,
     },
-    CurrentWorkspace, Icons, Issue, IssueDictionary, ValidateWorkspace,
+    AutoValidation, CurrentWorkspace, Icons, Issue, IssueDictionary, ValidateWorkspace,
 };
 use bevy::{ecs::system::SystemParam, prelude::*};
 use bevy_egui::egui::{self, Button, Checkbox, Grid, ImageButton, ScrollArea, Ui};
@@ -70,9 +73,13 @@ pub struct Diagnostics<'w, 's> {
     display_diagnostics: ResMut<'w, DiagnosticsDisplay>,
     current_workspace: ResMut<'w, CurrentWorkspace>,
     validate_workspace: EventWriter<'w, ValidateWorkspace>,
+    auto_validation: ResMut<'w, AutoValidation>,
+    badges: ResMut<'w, IssueBadgesDisplay>,
     change_filtered_issues: EventWriter<'w, Change<FilteredIssues<Entity>>>,
     change_filtered_issue_kinds: EventWriter<'w, Change<FilteredIssueKinds>>,
     selector: SelectorWidget<'w, 's>,
+    lanes: Query<'w, 's, (), With<LaneMarker>>,
+    insert_doorway: EventWriter<'w, InsertDoorway>,
 }
 
 impl<'w, 's> WidgetSystem for Diagnostics<'w, 's> {
@@ -192,8 +199,15 @@ impl<'w, 's> Diagnostics<'w, 's> {
                         self.selector.show_widget(*e, ui);
                     }
                 });
+                if sel.kind == LANE_CROSSES_DOOR_ISSUE_UUID {
+                    self.show_doorway_fix(sel, ui);
+                }
             }
 
+            ui.checkbox(&mut self.auto_validation.enabled, "Validate automatically")
+                .on_hover_text("Validate the site in the background whenever it changes");
+            ui.checkbox(&mut self.badges.show, "Show badges in viewport")
+                .on_hover_text("Mark entities that have active issues with a warning badge");
             if ui.add(Button::new("Validate")).clicked() {
                 self.validate_workspace.send(ValidateWorkspace(root));
             }
@@ -211,6 +225,27 @@ impl<'w, 's> Diagnostics<'w, 's> {
         }
         *self.display_diagnostics = state;
     }
+
+    fn show_doorway_fix(&mut self, issue: &IssueKey<Entity>, ui: &mut Ui) {
+        let (lanes, doors): (Vec<Entity>, Vec<Entity>) = issue
+            .entities
+            .iter()
+            .copied()
+            .partition(|e| self.lanes.contains(*e));
+        let (Some(lane), Some(door)) = (lanes.first(), doors.first()) else {
+            return;
+        };
+        if ui
+            .button("Insert doorway waypoint")
+            .on_hover_text("Split the lane at a new waypoint in the middle of the door")
+            .clicked()
+        {
+            self.insert_doorway.send(InsertDoorway {
+                lane: *lane,
+                door: *door,
+            });
+        }
+    }
 }
 
 #[derive(Resource, Debug, Clone, Default)]
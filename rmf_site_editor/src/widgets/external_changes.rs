@@ -0,0 +1,441 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{generate_site, load_map, DefaultFile, LoadSite, MapLoaded, MapSaved, NameOfSite},
+    AppState,
+};
+use bevy::{ecs::system::SystemState, prelude::*};
+use bevy_egui::{
+    egui::{self, Grid, RichText},
+    EguiContexts,
+};
+use rmf_site_format::Site;
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// How often the files of open sites are checked for changes
+const WATCH_PERIOD_SECS: f32 = 1.0;
+
+/// Watch the files of the open sites for changes that are made outside of the
+/// editor, such as a teammate pulling new changes or a script rewriting the
+/// file, and ask whether to reload the site or keep the version that is open.
+#[derive(Default)]
+pub struct ExternalChangesPlugin {}
+
+impl Plugin for ExternalChangesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ExternalChangeWatch>().add_systems(
+            Update,
+            (
+                watch_site_files,
+                show_external_change_prompt,
+                finish_reloading_sites,
+            )
+                .chain()
+                .run_if(AppState::in_displaying_mode()),
+        );
+    }
+}
+
+/// What the editor knows about the file of a site the last time it was read
+/// or written by the editor.
+#[derive(Component, Clone, Debug)]
+pub struct WatchedFile {
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+    /// The contents of the file as it was last read or written
+    base: SiteFingerprint,
+}
+
+/// Hashes of the serialized parts of a site, so that the versions of a site
+/// can be compared without keeping copies of them around.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct SiteFingerprint {
+    /// Hash of each level, keyed by the name of the level
+    levels: BTreeMap<String, u64>,
+    /// Hash of everything else in the site, e.g. lifts and navigation
+    other: u64,
+}
+
+impl SiteFingerprint {
+    fn new(site: &Site) -> Self {
+        let levels = site
+            .levels
+            .values()
+            .map(|level| (level.properties.name.0.clone(), hash_of(level)))
+            .collect();
+        let mut other = site.clone();
+        other.levels.clear();
+        Self {
+            levels,
+            other: hash_of(&other),
+        }
+    }
+}
+
+fn hash_of<T: serde::Serialize>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_vec(value)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+/// How one part of a site differs between the version that the editor last
+/// read or wrote, the version on disk, and the version open in the editor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExternalDifference {
+    Unchanged,
+    ChangedOnDisk,
+    ChangedInEditor,
+    /// Both versions were changed in the same way
+    ChangedIdentically,
+    ChangedInBoth,
+}
+
+impl ExternalDifference {
+    fn new<T: PartialEq>(base: Option<T>, disk: Option<T>, editor: Option<T>) -> Self {
+        match (base == disk, base == editor) {
+            (true, true) => Self::Unchanged,
+            (false, true) => Self::ChangedOnDisk,
+            (true, false) => Self::ChangedInEditor,
+            (false, false) if disk == editor => Self::ChangedIdentically,
+            (false, false) => Self::ChangedInBoth,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Unchanged => "Unchanged",
+            Self::ChangedOnDisk => "Changed on disk",
+            Self::ChangedInEditor => "Changed in editor",
+            Self::ChangedIdentically => "Same changes in both",
+            Self::ChangedInBoth => "Conflicting changes",
+        }
+    }
+}
+
+/// A site whose file was changed outside of the editor and is waiting for
+/// the user to decide what to do.
+pub struct ExternalChange {
+    pub site: Entity,
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+    /// The site as it is now on disk
+    pub disk: Site,
+    /// How each level differs, with the rest of the site listed last
+    pub differences: Vec<(String, ExternalDifference)>,
+    disk_fingerprint: SiteFingerprint,
+}
+
+#[derive(Resource)]
+pub struct ExternalChangeWatch {
+    timer: Timer,
+    pub pending: Option<ExternalChange>,
+    /// Sites that are being replaced by their reloaded version. They are
+    /// closed once the new version finishes loading.
+    reloading: Vec<Entity>,
+}
+
+impl Default for ExternalChangeWatch {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(WATCH_PERIOD_SECS, TimerMode::Repeating),
+            pending: None,
+            reloading: Vec::new(),
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).ok()?.modified().ok()
+}
+
+fn watch_site_files(
+    world: &mut World,
+    state: &mut SystemState<(
+        Res<Time>,
+        ResMut<ExternalChangeWatch>,
+        EventReader<MapSaved>,
+        Query<(Entity, &DefaultFile, Option<&WatchedFile>), With<NameOfSite>>,
+    )>,
+) {
+    let (time, mut watch, mut saved, sites) = state.get_mut(world);
+    let check_files = watch.timer.tick(time.delta()).just_finished() && watch.pending.is_none();
+    let saved: Vec<_> = saved
+        .read()
+        .map(|MapSaved { site, path }| (*site, path.clone()))
+        .collect();
+    if !check_files && saved.is_empty() {
+        return;
+    }
+
+    let mut to_check = Vec::new();
+    let mut to_watch = Vec::new();
+    for (site, file, watched) in &sites {
+        match watched {
+            Some(watched) if watched.path == file.0 => {
+                if check_files && modified_time(&file.0) != watched.modified {
+                    to_check.push((site, watched.clone()));
+                }
+            }
+            // The site started being watched or was saved somewhere else
+            _ => to_watch.push((site, file.0.clone())),
+        }
+    }
+
+    // Whatever the editor writes itself becomes the new base
+    for (site, path) in saved {
+        to_check.retain(|(s, _)| *s != site);
+        to_watch.push((site, path));
+    }
+    for (site, path) in to_watch {
+        let base = match load_map(&path) {
+            Ok(disk) => SiteFingerprint::new(&disk),
+            Err(err) => {
+                warn!("Unable to watch {} for changes: {err}", path.display());
+                SiteFingerprint::default()
+            }
+        };
+        if let Some(mut site) = world.get_entity_mut(site) {
+            site.insert(WatchedFile {
+                modified: modified_time(&path),
+                path,
+                base,
+            });
+        }
+    }
+
+    for (site, watched) in to_check {
+        let modified = modified_time(&watched.path);
+        let disk = match load_map(&watched.path) {
+            Ok(disk) => disk,
+            Err(err) => {
+                // The file may still be in the middle of being written
+                debug!("Unable to read {}: {err}", watched.path.display());
+                continue;
+            }
+        };
+        let disk_fingerprint = SiteFingerprint::new(&disk);
+        if disk_fingerprint == watched.base {
+            // Only the modification time changed
+            if let Some(mut watched) = world.get_mut::<WatchedFile>(site) {
+                watched.modified = modified;
+            }
+            continue;
+        }
+
+        let editor = match generate_site(world, site) {
+            Ok(editor) => SiteFingerprint::new(&editor),
+            Err(err) => {
+                error!("Unable to compare the site with its file: {err}");
+                continue;
+            }
+        };
+
+        let base = &watched.base;
+        let names: BTreeSet<&String> = base
+            .levels
+            .keys()
+            .chain(disk_fingerprint.levels.keys())
+            .chain(editor.levels.keys())
+            .collect();
+        let mut differences: Vec<_> = names
+            .into_iter()
+            .map(|name| {
+                let difference = ExternalDifference::new(
+                    base.levels.get(name),
+                    disk_fingerprint.levels.get(name),
+                    editor.levels.get(name),
+                );
+                (name.clone(), difference)
+            })
+            .collect();
+        differences.push((
+            "Rest of the site".to_owned(),
+            ExternalDifference::new(
+                Some(base.other),
+                Some(disk_fingerprint.other),
+                Some(editor.other),
+            ),
+        ));
+
+        let conflicts = differences.iter().any(|(_, difference)| {
+            !matches!(
+                difference,
+                ExternalDifference::Unchanged | ExternalDifference::ChangedIdentically
+            )
+        });
+        if !conflicts {
+            // The editor already has the same version as the file
+            if let Some(mut watched) = world.get_mut::<WatchedFile>(site) {
+                watched.modified = modified;
+                watched.base = disk_fingerprint;
+            }
+            continue;
+        }
+
+        info!(
+            "{} was changed outside of the editor",
+            watched.path.display()
+        );
+        world.resource_mut::<ExternalChangeWatch>().pending = Some(ExternalChange {
+            site,
+            path: watched.path,
+            modified,
+            disk,
+            differences,
+            disk_fingerprint,
+        });
+        return;
+    }
+}
+
+fn show_external_change_prompt(
+    mut watch: ResMut<ExternalChangeWatch>,
+    mut watched_files: Query<&mut WatchedFile>,
+    site_names: Query<&NameOfSite>,
+    mut load_site: EventWriter<LoadSite>,
+    mut egui_context: EguiContexts,
+) {
+    let Some(change) = &watch.pending else {
+        return;
+    };
+    let Ok(name) = site_names.get(change.site) else {
+        // The site was closed while the prompt was open
+        watch.pending = None;
+        return;
+    };
+
+    let mut reload = false;
+    let mut keep = false;
+    egui::Window::new("File Changed on Disk")
+        .collapsible(false)
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.label(format!(
+                "The file of {} was changed outside of the editor:",
+                name.0
+            ));
+            ui.label(RichText::new(change.path.display().to_string()).monospace());
+            ui.add_space(5.0);
+            Grid::new("external_change_differences")
+                .striped(true)
+                .show(ui, |ui| {
+                    for (part, difference) in &change.differences {
+                        ui.label(part);
+                        let label = match difference {
+                            ExternalDifference::ChangedInBoth => {
+                                RichText::new(difference.label()).color(ui.visuals().warn_fg_color)
+                            }
+                            _ => RichText::new(difference.label()),
+                        };
+                        ui.label(label);
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                reload = ui
+                    .button("Reload from Disk")
+                    .on_hover_text("Changes made in the editor since the last save will be lost")
+                    .clicked();
+                keep = ui
+                    .button("Keep Editor Version")
+                    .on_hover_text("Saving the site will overwrite the changes on disk")
+                    .clicked();
+            });
+        });
+
+    if !reload && !keep {
+        return;
+    }
+    let Some(change) = watch.pending.take() else {
+        return;
+    };
+    if let Ok(mut watched) = watched_files.get_mut(change.site) {
+        watched.modified = change.modified;
+        watched.base = change.disk_fingerprint;
+    }
+    if reload {
+        info!("Reloading {}", change.path.display());
+        load_site.send(LoadSite {
+            site: change.disk,
+            focus: true,
+            default_file: Some(change.path),
+        });
+        watch.reloading.push(change.site);
+    }
+}
+
+fn finish_reloading_sites(
+    mut commands: Commands,
+    mut watch: ResMut<ExternalChangeWatch>,
+    mut loaded: EventReader<MapLoaded>,
+) {
+    if loaded.read().count() == 0 {
+        return;
+    }
+    for old in watch.reloading.drain(..) {
+        commands.entity(old).despawn_recursive();
+    }
+}
+
+#[test]
+fn test_external_difference_compares_each_version_with_the_base() {
+    use ExternalDifference::*;
+    assert_eq!(
+        ExternalDifference::new(Some(1), Some(1), Some(1)),
+        Unchanged
+    );
+    assert_eq!(
+        ExternalDifference::new(Some(1), Some(2), Some(1)),
+        ChangedOnDisk
+    );
+    assert_eq!(
+        ExternalDifference::new(Some(1), Some(1), Some(2)),
+        ChangedInEditor
+    );
+    assert_eq!(
+        ExternalDifference::new(Some(1), Some(2), Some(2)),
+        ChangedIdentically
+    );
+    assert_eq!(
+        ExternalDifference::new(Some(1), Some(2), Some(3)),
+        ChangedInBoth
+    );
+
+    // Levels that only exist in some of the versions
+    assert_eq!(ExternalDifference::new(None, Some(1), None), ChangedOnDisk);
+    assert_eq!(
+        ExternalDifference::new(Some(1), Some(1), None),
+        ChangedInEditor
+    );
+    assert_eq!(
+        ExternalDifference::new(None, Some(1), Some(1)),
+        ChangedIdentically
+    );
+    assert_eq!(
+        ExternalDifference::new(Some(1), None, None),
+        ChangedIdentically
+    );
+}
@@ -0,0 +1,192 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::Selection,
+    site::{
+        AssetSource, Change, CurrentLevel, DrawingBundle, DrawingMarker, DrawingProperties,
+        NameInSite,
+    },
+    workspace::FileCompression,
+    AppState, CurrentWorkspace, UnsavedChanges, WorkspaceLoader, WorkspaceLoadingServices,
+    WorkspaceSaver,
+};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::path::{Path, PathBuf};
+
+/// Open files that are dropped onto the window. Site and building files are
+/// loaded as workspaces, while images become the drawing of the current
+/// level.
+#[derive(Default)]
+pub struct FileDropPlugin {}
+
+impl Plugin for FileDropPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingFileDrop>()
+            .add_systems(Update, (handle_dropped_files, confirm_dropped_file).chain());
+    }
+}
+
+/// A dropped workspace file that is waiting for the user to decide what to do
+/// with the unsaved changes of the current workspace.
+#[derive(Resource, Default)]
+pub struct PendingFileDrop {
+    pub path: Option<PathBuf>,
+}
+
+const IMAGE_EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+
+fn is_workspace_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|f| f.to_str()) else {
+        return false;
+    };
+    let (name, _) = FileCompression::split(name);
+    name.ends_with(".building.yaml")
+        || name.ends_with(".project.yaml")
+        || name.ends_with("site.ron")
+        || name.ends_with("site.json")
+}
+
+fn is_image_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
+
+fn handle_dropped_files(
+    mut commands: Commands,
+    mut drops: EventReader<FileDragAndDrop>,
+    mut pending: ResMut<PendingFileDrop>,
+    mut workspace_loader: WorkspaceLoader,
+    app_state: Res<State<AppState>>,
+    current_workspace: Res<CurrentWorkspace>,
+    unsaved: Res<UnsavedChanges>,
+    current_level: Res<CurrentLevel>,
+    selection: Res<Selection>,
+    drawings: Query<(Entity, &Parent), With<DrawingMarker>>,
+    mut change_source: EventWriter<Change<AssetSource>>,
+) {
+    for event in drops.read() {
+        let FileDragAndDrop::DroppedFile { path_buf, .. } = event else {
+            continue;
+        };
+
+        if is_workspace_file(path_buf) {
+            let unsaved_changes = *app_state.get() != AppState::MainMenu
+                && current_workspace
+                    .root
+                    .is_some_and(|root| unsaved.has_unsaved_changes(root));
+            if unsaved_changes {
+                pending.path = Some(path_buf.clone());
+            } else {
+                workspace_loader.load_from_path(path_buf.clone());
+            }
+        } else if is_image_file(path_buf) {
+            if *app_state.get() != AppState::SiteEditor {
+                warn!("Images can only be dropped onto a level while editing a site");
+                continue;
+            }
+            let Some(level) = **current_level else {
+                warn!("There is no level to drop the image {path_buf:?} onto");
+                continue;
+            };
+            let source = AssetSource::Local(path_buf.to_string_lossy().into_owned());
+            // Prefer replacing the selected drawing if it is on this level
+            let on_level = |e: Entity| drawings.get(e).is_ok_and(|(_, p)| p.get() == level);
+            let drawing = selection
+                .0
+                .filter(|e| on_level(*e))
+                .or_else(|| drawings.iter().map(|(e, _)| e).find(|e| on_level(*e)));
+            match drawing {
+                Some(drawing) => {
+                    info!("Replacing the drawing of the current level with {path_buf:?}");
+                    change_source.send(Change::new(source, drawing));
+                }
+                None => {
+                    let name = path_buf
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    commands
+                        .spawn(DrawingBundle::new(DrawingProperties {
+                            name: NameInSite(name),
+                            source,
+                            ..default()
+                        }))
+                        .set_parent(level);
+                }
+            }
+        } else {
+            warn!("Unable to open the dropped file {path_buf:?}, its type is not supported");
+        }
+    }
+}
+
+fn confirm_dropped_file(
+    mut egui_context: EguiContexts,
+    mut pending: ResMut<PendingFileDrop>,
+    mut workspace_loader: WorkspaceLoader,
+    mut workspace_saver: WorkspaceSaver,
+    workspace_loading: Res<WorkspaceLoadingServices>,
+) {
+    let Some(path) = &pending.path else {
+        return;
+    };
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut save = false;
+    let mut open = false;
+    let mut cancel = false;
+    egui::Window::new("Unsaved Changes")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0., 0.))
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.label("The current site has changes that have not been saved.");
+            ui.label(format!("Save them before opening {name}?"));
+            ui.add_space(10.);
+            ui.horizontal(|ui| {
+                save = ui.button("Save and open").clicked();
+                open = ui.button("Open without saving").clicked();
+                cancel = ui.button("Cancel").clicked();
+            });
+        });
+
+    if save {
+        if let Some(path) = pending.path.take() {
+            // The save may need to ask for a file first, so the dropped file
+            // is only opened once the current site has been sent for saving.
+            // Otherwise the newly opened site would be the one that gets
+            // saved.
+            workspace_saver
+                .save_to_default_file_impulse()
+                .map_block(move |_| path.clone())
+                .then(workspace_loading.load_workspace_from_path.clone())
+                .detach();
+        }
+    } else if open {
+        if let Some(path) = pending.path.take() {
+            workspace_loader.load_from_path(path);
+        }
+    } else if cancel {
+        pending.path = None;
+    }
+}
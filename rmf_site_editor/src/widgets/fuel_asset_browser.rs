@@ -18,31 +18,45 @@
 use crate::{
     interaction::{ModelPreviewCamera, ObjectPlacement},
     site::{
-        Affiliation, AssetSource, Category, FuelClient, ModelDescriptionBundle, ModelInstance,
-        ModelLoader, ModelProperty, NameInSite, SetFuelApiKey, UpdateFuelCache,
+        Affiliation, AssetSource, Category, FuelClient, LocalModel, LocalModels,
+        ModelDescriptionBundle, ModelInstance, ModelLoader, ModelProperty, NameInSite,
+        SetFuelApiKey, UpdateFuelCache,
     },
     widgets::{prelude::*, PendingModelDescription},
     AppState, CurrentWorkspace,
 };
-use bevy::{ecs::system::SystemParam, prelude::*};
-use bevy_egui::egui::{self, Button, ComboBox, ImageSource, RichText, ScrollArea, Ui, Window};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+use bevy_egui::{
+    egui::{
+        self, Button, ComboBox, ImageSource, RichText, ScrollArea, Sense, TextureId, Ui, Window,
+    },
+    EguiUserTextures,
+};
 use gz_fuel::FuelModel;
 
+/// Size of the thumbnails shown next to each model in the gallery
+const THUMBNAIL_SIZE: f32 = 48.0;
+
 /// Add a [`FuelAssetBrowser`] widget to your application.
 pub struct FuelAssetBrowserPlugin;
 
 impl Plugin for FuelAssetBrowserPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<AssetGalleryStatus>();
+        app.init_resource::<AssetGalleryStatus>()
+            .init_resource::<ModelThumbnails>();
         let panel = PanelWidget::new(fuel_asset_browser_panel, &mut app.world);
         let widget = Widget::new::<FuelAssetBrowser>(&mut app.world);
         app.world.spawn((panel, widget));
     }
 }
 
-/// Filters applied to models in the fuel list
+/// Filters applied to models in the gallery
 #[derive(Default)]
 pub struct ShowAssetFilters {
+    /// Only show models whose name or owner contains this text
+    pub search: String,
+    pub hide_fuel: bool,
+    pub hide_local: bool,
     pub owner: Option<String>,
     pub recall_owner: Option<String>,
     pub tag: Option<String>,
@@ -55,7 +69,7 @@ pub struct ShowAssetFilters {
 #[derive(Resource, Default)]
 pub struct AssetGalleryStatus {
     pub show: bool,
-    pub selected: Option<FuelModel>,
+    pub selected: Option<GalleryModel>,
     pub cached_owners: Option<Vec<String>>,
     pub cached_tags: Option<Vec<String>>,
     pub filters: ShowAssetFilters,
@@ -64,7 +78,63 @@ pub struct AssetGalleryStatus {
     pub show_api_window: bool,
 }
 
-/// A widget for browsing models that can be downloaded from fuel.
+/// A model that can be picked from the gallery, either from Fuel or from one
+/// of the local model folders.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GalleryModel {
+    /// Name of the model description that gets created for the model
+    pub name: String,
+    pub source: AssetSource,
+    pub thumbnail: Option<AssetSource>,
+}
+
+impl From<&FuelModel> for GalleryModel {
+    fn from(model: &FuelModel) -> Self {
+        let path = model.owner.clone() + "/" + &model.name;
+        Self {
+            name: path.clone(),
+            source: AssetSource::Remote(path.clone() + "/model.sdf"),
+            thumbnail: Some(AssetSource::Remote(path + "/thumbnails/1.png")),
+        }
+    }
+}
+
+impl From<&LocalModel> for GalleryModel {
+    fn from(model: &LocalModel) -> Self {
+        Self {
+            name: model.name.clone(),
+            source: model.source(),
+            thumbnail: model.thumbnail(),
+        }
+    }
+}
+
+/// Thumbnails of gallery models that have been requested so far. Thumbnails
+/// are only loaded once their model is scrolled into view.
+#[derive(Resource, Default)]
+pub struct ModelThumbnails {
+    thumbnails: HashMap<String, (Handle<Image>, TextureId)>,
+}
+
+impl ModelThumbnails {
+    fn texture_for(
+        &mut self,
+        source: &AssetSource,
+        asset_server: &AssetServer,
+        user_textures: &mut EguiUserTextures,
+    ) -> Option<TextureId> {
+        let path = String::try_from(source).ok()?;
+        let (_, id) = self.thumbnails.entry(path.clone()).or_insert_with(|| {
+            let handle: Handle<Image> = asset_server.load(path);
+            let id = user_textures.add_image(handle.clone());
+            (handle, id)
+        });
+        Some(*id)
+    }
+}
+
+/// A widget for browsing models that can be downloaded from fuel or found in
+/// the local model folders of the editor settings.
 ///
 /// This is part of the [`StandardUiPlugin`][1]. If you are not using the
 /// `StandardUiPlugin` then it is recommended that you use the
@@ -84,6 +154,10 @@ pub struct FuelAssetBrowser<'w, 's> {
     model_loader: ModelLoader<'w, 's>,
     pending_model_description: Option<ResMut<'w, PendingModelDescription>>,
     object_placement: ObjectPlacement<'w, 's>,
+    local_models: Res<'w, LocalModels>,
+    thumbnails: ResMut<'w, ModelThumbnails>,
+    user_textures: ResMut<'w, EguiUserTextures>,
+    asset_server: Res<'w, AssetServer>,
 }
 
 fn fuel_asset_browser_panel(In(input): In<PanelWidgetInput>, world: &mut World) {
@@ -116,114 +190,132 @@ impl<'w, 's> FuelAssetBrowser<'w, 's> {
         let gallery_status = &mut self.asset_gallery_status;
         ui.label(RichText::new("Asset Gallery").size(18.0));
         ui.add_space(10.0);
-        match &fuel_client.models {
-            Some(models) => {
-                // Note, unwraps here are safe because the client will return None only if models
-                // are not populated which will not happen in this match branch
-                let owner_filter = gallery_status.filters.owner.clone();
-                let mut owner_filter_enabled = owner_filter.is_some();
-                ui.label(RichText::new("Filters").size(14.0));
-                ui.add_space(5.0);
-                ui.horizontal(|ui| {
-                    ui.checkbox(&mut owner_filter_enabled, "Owners");
-                    gallery_status.filters.owner = match owner_filter_enabled {
-                        true => {
-                            let owners = gallery_status
-                                .cached_owners
+
+        ui.horizontal(|ui| {
+            ui.label("Search");
+            ui.text_edit_singleline(&mut gallery_status.filters.search);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Sources");
+            let mut show_fuel = !gallery_status.filters.hide_fuel;
+            ui.checkbox(&mut show_fuel, "Fuel");
+            gallery_status.filters.hide_fuel = !show_fuel;
+            let mut show_local = !gallery_status.filters.hide_local;
+            ui.checkbox(&mut show_local, "Local folders")
+                .on_hover_text("Model folders are configured in the editor settings");
+            gallery_status.filters.hide_local = !show_local;
+        });
+        ui.add_space(5.0);
+
+        let mut gallery_models: Vec<GalleryModel> = Vec::new();
+        if let (Some(models), false) = (&fuel_client.models, gallery_status.filters.hide_fuel) {
+            // Note, unwraps here are safe because the client will return None only if models
+            // are not populated which will not happen in this match branch
+            let owner_filter = gallery_status.filters.owner.clone();
+            let mut owner_filter_enabled = owner_filter.is_some();
+            ui.label(RichText::new("Fuel Filters").size(14.0));
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut owner_filter_enabled, "Owners");
+                gallery_status.filters.owner = match owner_filter_enabled {
+                    true => {
+                        let owners = gallery_status
+                            .cached_owners
+                            .clone()
+                            .or_else(|| fuel_client.get_owners())
+                            .unwrap();
+                        let mut selected = match &owner_filter {
+                            Some(s) => s.clone(),
+                            None => gallery_status
+                                .filters
+                                .recall_owner
                                 .clone()
-                                .or_else(|| fuel_client.get_owners())
-                                .unwrap();
-                            let mut selected = match &owner_filter {
-                                Some(s) => s.clone(),
-                                None => gallery_status
-                                    .filters
-                                    .recall_owner
-                                    .clone()
-                                    .unwrap_or(owners[0].clone()),
-                            };
-                            ComboBox::from_id_source("Asset Owner Filter")
-                                .selected_text(selected.clone())
-                                .show_ui(ui, |ui| {
-                                    for owner in owners.into_iter() {
-                                        ui.selectable_value(&mut selected, owner.clone(), owner);
-                                    }
-                                    ui.end_row();
-                                });
-                            gallery_status.filters.recall_owner = Some(selected.clone());
-                            Some(selected)
-                        }
-                        false => None,
-                    };
-                });
+                                .unwrap_or(owners[0].clone()),
+                        };
+                        ComboBox::from_id_source("Asset Owner Filter")
+                            .selected_text(selected.clone())
+                            .show_ui(ui, |ui| {
+                                for owner in owners.into_iter() {
+                                    ui.selectable_value(&mut selected, owner.clone(), owner);
+                                }
+                                ui.end_row();
+                            });
+                        gallery_status.filters.recall_owner = Some(selected.clone());
+                        Some(selected)
+                    }
+                    false => None,
+                };
+            });
 
-                let tag_filter = gallery_status.filters.tag.clone();
-                let mut tag_filter_enabled = tag_filter.is_some();
-                ui.horizontal(|ui| {
-                    ui.checkbox(&mut tag_filter_enabled, "Tags");
-                    gallery_status.filters.tag = match tag_filter_enabled {
-                        true => {
-                            let tags = gallery_status
-                                .cached_tags
+            let tag_filter = gallery_status.filters.tag.clone();
+            let mut tag_filter_enabled = tag_filter.is_some();
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut tag_filter_enabled, "Tags");
+                gallery_status.filters.tag = match tag_filter_enabled {
+                    true => {
+                        let tags = gallery_status
+                            .cached_tags
+                            .clone()
+                            .or_else(|| fuel_client.get_tags())
+                            .unwrap();
+                        let mut selected = match &tag_filter {
+                            Some(s) => s.clone(),
+                            None => gallery_status
+                                .filters
+                                .recall_tag
                                 .clone()
-                                .or_else(|| fuel_client.get_tags())
-                                .unwrap();
-                            let mut selected = match &tag_filter {
-                                Some(s) => s.clone(),
-                                None => gallery_status
-                                    .filters
-                                    .recall_tag
-                                    .clone()
-                                    .unwrap_or(tags[0].clone()),
-                            };
-                            ComboBox::from_id_source("Asset Tag Filter")
-                                .selected_text(selected.clone())
-                                .show_ui(ui, |ui| {
-                                    for tag in tags.into_iter() {
-                                        ui.selectable_value(&mut selected, tag.clone(), tag);
-                                    }
-                                    ui.end_row();
-                                });
-                            gallery_status.filters.recall_tag = Some(selected.clone());
-                            Some(selected)
-                        }
-                        false => None,
-                    };
-                });
+                                .unwrap_or(tags[0].clone()),
+                        };
+                        ComboBox::from_id_source("Asset Tag Filter")
+                            .selected_text(selected.clone())
+                            .show_ui(ui, |ui| {
+                                for tag in tags.into_iter() {
+                                    ui.selectable_value(&mut selected, tag.clone(), tag);
+                                }
+                                ui.end_row();
+                            });
+                        gallery_status.filters.recall_tag = Some(selected.clone());
+                        Some(selected)
+                    }
+                    false => None,
+                };
+            });
 
-                let private_filter = gallery_status.filters.private.clone();
-                let mut private_filter_enabled = private_filter.is_some();
-                ui.horizontal(|ui| {
-                    ui.checkbox(&mut private_filter_enabled, "Private");
-                    gallery_status.filters.private = match private_filter_enabled {
-                        true => {
-                            let mut selected = match &private_filter {
-                                Some(s) => s.clone(),
-                                None => gallery_status.filters.recall_private.unwrap_or(false),
-                            };
-                            ComboBox::from_id_source("Asset Private Filter")
-                                .selected_text(selected.to_string())
-                                .show_ui(ui, |ui| {
-                                    for private in [true, false].into_iter() {
-                                        ui.selectable_value(
-                                            &mut selected,
-                                            private,
-                                            private.to_string(),
-                                        );
-                                    }
-                                    ui.end_row();
-                                });
-                            gallery_status.filters.recall_private = Some(selected);
-                            Some(selected)
-                        }
-                        false => None,
-                    };
-                });
+            let private_filter = gallery_status.filters.private.clone();
+            let mut private_filter_enabled = private_filter.is_some();
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut private_filter_enabled, "Private");
+                gallery_status.filters.private = match private_filter_enabled {
+                    true => {
+                        let mut selected = match &private_filter {
+                            Some(s) => s.clone(),
+                            None => gallery_status.filters.recall_private.unwrap_or(false),
+                        };
+                        ComboBox::from_id_source("Asset Private Filter")
+                            .selected_text(selected.to_string())
+                            .show_ui(ui, |ui| {
+                                for private in [true, false].into_iter() {
+                                    ui.selectable_value(
+                                        &mut selected,
+                                        private,
+                                        private.to_string(),
+                                    );
+                                }
+                                ui.end_row();
+                            });
+                        gallery_status.filters.recall_private = Some(selected);
+                        Some(selected)
+                    }
+                    false => None,
+                };
+            });
 
-                ui.add_space(10.0);
+            ui.add_space(10.0);
 
-                // TODO(luca) should we cache the models by filters result to avoid calling at every
-                // frame?
-                let models = models
+            // TODO(luca) should we cache the models by filters result to avoid calling at every
+            // frame?
+            gallery_models.extend(
+                models
                     .iter()
                     .filter(|m| {
                         owner_filter.is_none()
@@ -238,82 +330,91 @@ impl<'w, 's> FuelAssetBrowser<'w, 's> {
                     .filter(|m| {
                         tag_filter.is_none()
                             | tag_filter.as_ref().is_some_and(|tag| m.tags.contains(&tag))
-                    });
-
-                ui.label(RichText::new("Models").size(14.0));
-                ui.add_space(5.0);
-                // Show models
-                let mut new_selected = None;
-                ScrollArea::vertical()
-                    .max_height(300.0)
-                    .auto_shrink([false, false])
-                    .show(ui, |ui| {
-                        for model in models {
-                            let sel = gallery_status.selected.as_ref().is_some_and(|s| s == model);
-                            if ui.selectable_label(sel, &model.name).clicked() {
-                                new_selected = Some(model);
+                    })
+                    .map(GalleryModel::from),
+            );
+        }
+
+        if !gallery_status.filters.hide_local {
+            gallery_models.extend(self.local_models.models.iter().map(GalleryModel::from));
+        }
+
+        let search = gallery_status.filters.search.to_lowercase();
+        if !search.is_empty() {
+            gallery_models.retain(|m| m.name.to_lowercase().contains(&search));
+        }
+
+        ui.label(RichText::new("Models").size(14.0));
+        ui.add_space(5.0);
+        let mut new_selected = None;
+        let mut place = None;
+        if gallery_models.is_empty() {
+            ui.label("No models found");
+        } else {
+            let thumbnails = &mut self.thumbnails;
+            let user_textures = &mut self.user_textures;
+            let asset_server = &self.asset_server;
+            // Only the rows that are in view get drawn, so thumbnails are
+            // loaded as the user scrolls through the models.
+            ScrollArea::vertical()
+                .max_height(300.0)
+                .auto_shrink([false, false])
+                .show_rows(ui, THUMBNAIL_SIZE, gallery_models.len(), |ui, rows| {
+                    for model in &gallery_models[rows] {
+                        let sel = gallery_status.selected.as_ref() == Some(model);
+                        ui.horizontal(|ui| {
+                            let thumbnail = model.thumbnail.as_ref().and_then(|source| {
+                                thumbnails.texture_for(source, asset_server, user_textures)
+                            });
+                            match thumbnail {
+                                Some(id) => {
+                                    ui.image(ImageSource::Texture(
+                                        (id, [THUMBNAIL_SIZE, THUMBNAIL_SIZE].into()).into(),
+                                    ));
+                                }
+                                None => {
+                                    ui.allocate_exact_size(
+                                        [THUMBNAIL_SIZE, THUMBNAIL_SIZE].into(),
+                                        Sense::hover(),
+                                    );
+                                }
+                            }
+                            let response = ui
+                                .selectable_label(sel, &model.name)
+                                .on_hover_text("Double click to place the model");
+                            if response.clicked() {
+                                new_selected = Some(model.clone());
                             }
-                        }
-                    });
-                ui.add_space(10.0);
-
-                ui.image(ImageSource::Texture(
-                    (self.model_preview_camera.egui_handle, [320.0, 240.0].into()).into(),
-                ));
-                ui.add_space(10.0);
-
-                if gallery_status.selected.as_ref() != new_selected {
-                    if let Some(selected) = new_selected {
-                        // Set the model preview source to what is selected
-                        let model_entity = self.model_preview_camera.model_entity;
-                        let source = AssetSource::Remote(
-                            selected.owner.clone() + "/" + &selected.name + "/model.sdf",
-                        );
-                        self.model_loader.update_asset_source(model_entity, source);
-                        gallery_status.selected = Some(selected.clone());
+                            if response.double_clicked() {
+                                place = Some(model.clone());
+                            }
+                        });
                     }
-                }
+                });
+        }
+        ui.add_space(10.0);
 
-                if let Some(selected) = &gallery_status.selected {
-                    if ui.button("Load as Description").clicked() {
-                        if let Some(site_entity) = self.current_workspace.root {
-                            let description_name = selected.owner.clone() + "/" + &selected.name;
-                            let model_description: ModelDescriptionBundle =
-                                ModelDescriptionBundle {
-                                    name: NameInSite(description_name.clone()),
-                                    source: ModelProperty(AssetSource::Remote(
-                                        selected.owner.clone()
-                                            + "/"
-                                            + &selected.name
-                                            + "/model.sdf",
-                                    )),
-                                    ..Default::default()
-                                };
-                            let description = self
-                                .commands
-                                .spawn(model_description)
-                                .insert(Category::ModelDescription)
-                                .set_parent(site_entity)
-                                .id();
-
-                            if let Some(pending) = &mut self.pending_model_description {
-                                pending.selected = Some(description);
-                            }
+        ui.image(ImageSource::Texture(
+            (self.model_preview_camera.egui_handle, [320.0, 240.0].into()).into(),
+        ));
+        ui.add_space(10.0);
 
-                            let instance = ModelInstance {
-                                name: NameInSite(format!("{description_name}_0")),
-                                description: Affiliation(Some(description)),
-                                ..Default::default()
-                            };
-                            self.object_placement.place_object_2d(instance);
-                        }
-                    }
-                }
+        if let Some(selected) = new_selected {
+            if gallery_status.selected.as_ref() != Some(&selected) {
+                // Set the model preview source to what is selected
+                let model_entity = self.model_preview_camera.model_entity;
+                self.model_loader
+                    .update_asset_source(model_entity, selected.source.clone());
+                gallery_status.selected = Some(selected);
             }
-            None => {
-                ui.label("No models found");
+        }
+
+        if let Some(selected) = &gallery_status.selected {
+            if ui.button("Load as Description").clicked() {
+                place = Some(selected.clone());
             }
         }
+
         ui.add_space(10.0);
         if gallery_status.show_api_window {
             Window::new("API Key").show(ui.ctx(), |ui| {
@@ -344,5 +445,39 @@ impl<'w, 's> FuelAssetBrowser<'w, 's> {
         if ui.add(Button::new("Close")).clicked() {
             gallery_status.show = false;
         }
+
+        if let Some(model) = place {
+            self.place_model(&model);
+        }
+    }
+
+    /// Add the model as a description of the current site and start placing
+    /// an instance of it into the current level.
+    fn place_model(&mut self, model: &GalleryModel) {
+        let Some(site_entity) = self.current_workspace.root else {
+            return;
+        };
+        let model_description = ModelDescriptionBundle {
+            name: NameInSite(model.name.clone()),
+            source: ModelProperty(model.source.clone()),
+            ..Default::default()
+        };
+        let description = self
+            .commands
+            .spawn(model_description)
+            .insert(Category::ModelDescription)
+            .set_parent(site_entity)
+            .id();
+
+        if let Some(pending) = &mut self.pending_model_description {
+            pending.selected = Some(description);
+        }
+
+        let instance = ModelInstance {
+            name: NameInSite(format!("{}_0", model.name)),
+            description: Affiliation(Some(description)),
+            ..Default::default()
+        };
+        self.object_placement.place_object_2d(instance);
     }
 }
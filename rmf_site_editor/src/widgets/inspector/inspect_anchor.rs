@@ -17,15 +17,17 @@
 
 use crate::{
     interaction::{Hover, MoveTo},
+    settings::EditorSettings,
     site::{Anchor, Category, Dependents, Subordinate},
     widgets::{
         inspector::{Inspect, InspectPoseComponent},
+        length_field,
         prelude::*,
         Icons, SelectorWidget,
     },
 };
 use bevy::{ecs::system::SystemParam, prelude::*};
-use bevy_egui::egui::{DragValue, ImageButton, Ui};
+use bevy_egui::egui::{ImageButton, Ui};
 use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(SystemParam)]
@@ -42,6 +44,7 @@ pub struct InspectAnchor<'w, 's> {
     icons: Res<'w, Icons>,
     hover: EventWriter<'w, Hover>,
     move_to: EventWriter<'w, MoveTo>,
+    settings: Res<'w, EditorSettings>,
 }
 
 impl<'w, 's> ShareableWidget for InspectAnchor<'w, 's> {}
@@ -121,6 +124,7 @@ fn impl_inspect_anchor(
 
     let mut params = state.get_mut(world);
 
+    let units = params.settings.units;
     if let Ok((anchor, tf, subordinate)) = params.anchors.get(id) {
         if let Some(subordinate) = subordinate.map(|s| s.0) {
             panel.orthogonal(ui, |ui| {
@@ -141,13 +145,13 @@ fn impl_inspect_anchor(
                         ui.label("x");
                     }
                     let mut x = tf.translation.x;
-                    ui.add(DragValue::new(&mut x).speed(0.01));
+                    ui.add(length_field(&mut x, units).speed(0.01));
 
                     if !is_dependency {
                         ui.label("y");
                     }
                     let mut y = tf.translation.y;
-                    ui.add(DragValue::new(&mut y).speed(0.01));
+                    ui.add(length_field(&mut y, units).speed(0.01));
 
                     if x != tf.translation.x || y != tf.translation.y {
                         {}
@@ -162,7 +166,9 @@ fn impl_inspect_anchor(
                 }
                 Anchor::Pose3D(pose) => {
                     panel.align(ui, |ui| {
-                        if let Some(new_pose) = InspectPoseComponent::new(pose).show(ui) {
+                        if let Some(new_pose) =
+                            InspectPoseComponent::new(pose).length_unit(units).show(ui)
+                        {
                             // TODO(luca) Using moveto doesn't allow switching between variants of
                             // Pose3D
                             params.move_to.send(MoveTo {
@@ -15,7 +15,8 @@
  *
 */
 
-use bevy_egui::egui::{DragValue, Ui};
+use crate::widgets::inspector::number_field;
+use bevy_egui::egui::Ui;
 use rmf_site_format::Angle;
 use std::ops::RangeInclusive;
 
@@ -41,7 +42,7 @@ impl<'a> InspectAngle<'a> {
         ui.horizontal(|ui| match self.angle {
             Angle::Deg(deg) => {
                 ui.add(
-                    DragValue::new(deg)
+                    number_field(deg)
                         .min_decimals(0)
                         .max_decimals(1)
                         .speed(1.0)
@@ -56,7 +57,7 @@ impl<'a> InspectAngle<'a> {
             }
             Angle::Rad(rad) => {
                 ui.add(
-                    DragValue::new(rad)
+                    number_field(rad)
                         .min_decimals(2)
                         .max_decimals(4)
                         .speed(std::f32::consts::PI / 180.0)
@@ -0,0 +1,81 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{Change, CustomEntityKind, CustomFieldValue, CustomFields},
+    widgets::{prelude::*, Inspect},
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{DragValue, Grid, Ui};
+
+#[derive(SystemParam)]
+pub struct InspectCustomFields<'w, 's> {
+    custom_entities: Query<'w, 's, (&'static CustomEntityKind, &'static CustomFields)>,
+    change_fields: EventWriter<'w, Change<CustomFields>>,
+}
+
+impl<'w, 's> WidgetSystem<Inspect> for InspectCustomFields<'w, 's> {
+    fn show(
+        Inspect { selection, .. }: Inspect,
+        ui: &mut Ui,
+        state: &mut SystemState<Self>,
+        world: &mut World,
+    ) {
+        let mut params = state.get_mut(world);
+        params.show_widget(selection, ui);
+    }
+}
+
+impl<'w, 's> InspectCustomFields<'w, 's> {
+    pub fn show_widget(&mut self, id: Entity, ui: &mut Ui) {
+        let Ok((kind, fields)) = self.custom_entities.get(id) else {
+            return;
+        };
+
+        ui.label(format!("Kind: {}", kind.0));
+        if fields.is_empty() {
+            ui.add_space(10.0);
+            return;
+        }
+
+        let mut new_fields = fields.clone();
+        Grid::new("inspect_custom_fields")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (name, value) in new_fields.0.iter_mut() {
+                    ui.label(name);
+                    match value {
+                        CustomFieldValue::Boolean(b) => {
+                            ui.checkbox(b, "");
+                        }
+                        CustomFieldValue::Number(n) => {
+                            ui.add(DragValue::new(n).speed(0.1));
+                        }
+                        CustomFieldValue::Text(s) => {
+                            ui.text_edit_singleline(s);
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if new_fields != *fields {
+            self.change_fields.send(Change::new(new_fields, id));
+        }
+        ui.add_space(10.0);
+    }
+}
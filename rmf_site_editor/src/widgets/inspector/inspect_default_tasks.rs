@@ -15,9 +15,10 @@
  *
 */
 
+use crate::widgets::inspector::number_field;
 use crate::TaskKinds;
 use bevy::prelude::*;
-use bevy_egui::egui::{ComboBox, DragValue, Ui};
+use bevy_egui::egui::{ComboBox, Ui};
 use rmf_site_format::{GoToPlace, Task, WaitFor};
 
 #[derive(Default)]
@@ -117,7 +118,7 @@ impl<'a> InspectWaitFor<'a> {
         };
         ui.horizontal(|ui| {
             ui.add(
-                DragValue::new(&mut new_wait_for.duration)
+                number_field(&mut new_wait_for.duration)
                     .clamp_range(0_f32..=std::f32::INFINITY)
                     .speed(0.01),
             );
@@ -19,12 +19,13 @@ use crate::{
     site::Change,
     widgets::{
         inspector::{InspectAngle, InspectSide},
+        number_field,
         prelude::*,
         Inspect,
     },
 };
 use bevy::prelude::*;
-use bevy_egui::egui::{ComboBox, DragValue, Ui};
+use bevy_egui::egui::{ComboBox, Ui};
 use rmf_site_format::{DoorType, RecallDoorType, Swing};
 
 #[derive(SystemParam)]
@@ -85,7 +86,7 @@ impl<'a> InspectDoorType<'a> {
             ui.horizontal(|ui| {
                 ui.label("Left : Right");
                 ui.add(
-                    DragValue::new(ratio)
+                    number_field(ratio)
                         .speed(0.01)
                         .clamp_range(0.01..=std::f32::INFINITY),
                 )
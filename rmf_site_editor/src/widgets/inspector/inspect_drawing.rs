@@ -16,22 +16,48 @@
 */
 
 use crate::{
-    site::{AlignSiteDrawings, BeginEditDrawing, Change, PixelsPerMeter},
-    widgets::{prelude::*, Inspect, InspectValue},
+    site::{
+        AlignSiteDrawings, Anchor, Angle, BeginEditDrawing, Category, Change, DrawingSourceInfo,
+        DrawingSourceMismatch, PixelsPerMeter, Pose, Rectification, RectificationPoints, SiteID,
+    },
+    widgets::{inspector::InspectOptionF32, prelude::*, Inspect, InspectValue},
     AppState, CurrentWorkspace, Icons,
 };
 use bevy::prelude::*;
-use bevy_egui::egui::Button;
+use bevy_egui::egui::{Button, CollapsingHeader, Color32, ComboBox, DragValue, Grid, Ui};
+
+/// How far a drawing moves for each click of a nudge button, in meters
+const NUDGE_DISTANCE: f32 = 0.05;
+/// How far a drawing turns for each click of a nudge button, in degrees
+const NUDGE_ANGLE: f32 = 0.5;
 
 #[derive(SystemParam)]
 pub struct InspectDrawing<'w, 's> {
     pixels_per_meter: Query<'w, 's, &'static PixelsPerMeter>,
     change_pixels_per_meter: EventWriter<'w, Change<PixelsPerMeter>>,
+    poses: Query<'w, 's, &'static Pose>,
+    change_pose: EventWriter<'w, Change<Pose>>,
     current_workspace: Res<'w, CurrentWorkspace>,
     align_site: EventWriter<'w, AlignSiteDrawings>,
     app_state: Res<'w, State<AppState>>,
     icons: Res<'w, Icons>,
     begin_edit_drawing: EventWriter<'w, BeginEditDrawing>,
+    rectifications: Query<'w, 's, &'static Rectification>,
+    change_rectification: EventWriter<'w, Change<Rectification>>,
+    children: Query<'w, 's, &'static Children>,
+    anchors: Query<'w, 's, (&'static Anchor, Option<&'static SiteID>)>,
+    source_info: Query<
+        'w,
+        's,
+        (
+            &'static DrawingSourceInfo,
+            Option<&'static DrawingSourceMismatch>,
+        ),
+    >,
+    change_source_info: EventWriter<'w, Change<DrawingSourceInfo>>,
+    /// Corners that are being entered for the drawing, which only get
+    /// applied once the user is done with all four of them.
+    rectification_draft: Local<'s, Option<(Entity, RectificationPoints)>>,
 }
 
 impl<'w, 's> WidgetSystem<Inspect> for InspectDrawing<'w, 's> {
@@ -42,7 +68,7 @@ impl<'w, 's> WidgetSystem<Inspect> for InspectDrawing<'w, 's> {
         world: &mut World,
     ) {
         let mut params = state.get_mut(world);
-        let Ok(ppm) = params.pixels_per_meter.get(selection) else {
+        let Ok(ppm) = params.pixels_per_meter.get(selection).copied() else {
             return;
         };
 
@@ -87,5 +113,194 @@ impl<'w, 's> WidgetSystem<Inspect> for InspectDrawing<'w, 's> {
                 .change_pixels_per_meter
                 .send(Change::new(PixelsPerMeter(new_ppm), selection));
         }
+        params.show_source_info(selection, &ppm, ui);
+
+        if let Ok(pose) = params.poses.get(selection) {
+            if let Some(new_pose) = show_nudge(ui, pose) {
+                params.change_pose.send(Change::new(new_pose, selection));
+            }
+        }
+
+        if *params.app_state.get() == AppState::SiteDrawingEditor {
+            params.show_rectification(selection, ui);
+        }
     }
 }
+
+impl<'w, 's> InspectDrawing<'w, 's> {
+    fn show_source_info(&mut self, drawing: Entity, ppm: &PixelsPerMeter, ui: &mut Ui) {
+        let Ok((info, mismatch)) = self.source_info.get(drawing) else {
+            return;
+        };
+        let (info, mismatch) = (info.clone(), mismatch.cloned());
+
+        if let Some(new_dpi) = InspectOptionF32::new("DPI", info.dpi, 300.0)
+            .clamp_range(1.0..=std::f32::INFINITY)
+            .tooltip("Resolution that the drawing was scanned or rendered at")
+            .show(ui)
+        {
+            let new_info = DrawingSourceInfo {
+                dpi: new_dpi,
+                ..info.clone()
+            };
+            self.change_source_info.send(Change::new(new_info, drawing));
+        }
+        if let Some(scale) = info.plan_scale(ppm) {
+            ui.label(format!("Plan scale 1:{scale:.0}"));
+        }
+
+        let Some(mismatch) = mismatch else {
+            return;
+        };
+        ui.colored_label(
+            Color32::YELLOW,
+            "The image has changed since this drawing was calibrated",
+        );
+        ui.horizontal(|ui| {
+            if *self.app_state.get() == AppState::SiteEditor
+                && ui
+                    .button("Recalibrate")
+                    .on_hover_text("Edit the drawing to correct its measurements")
+                    .clicked()
+            {
+                self.begin_edit_drawing.send(BeginEditDrawing(drawing));
+            }
+            if ui
+                .button("Keep Calibration")
+                .on_hover_text("Accept the new image without changing the calibration")
+                .clicked()
+            {
+                self.change_source_info
+                    .send(Change::new(mismatch.0.clone(), drawing));
+            }
+        });
+    }
+
+    fn show_rectification(&mut self, drawing: Entity, ui: &mut Ui) {
+        let Ok(current) = self.rectifications.get(drawing) else {
+            return;
+        };
+        if self
+            .rectification_draft
+            .as_ref()
+            .is_some_and(|(e, _)| *e != drawing)
+        {
+            *self.rectification_draft = None;
+        }
+        let (_, draft) = self.rectification_draft.get_or_insert_with(|| {
+            (
+                drawing,
+                current.0.unwrap_or(RectificationPoints {
+                    pixels: [[0.0; 2]; 4],
+                    meters: [[0.0; 2]; 4],
+                }),
+            )
+        });
+
+        // Anchors of a drawing are placed in its pixel frame, where y points
+        // up while the v coordinate of the image points down.
+        let anchors: Vec<(String, [f32; 2])> = self
+            .children
+            .get(drawing)
+            .into_iter()
+            .flat_map(|children| children.iter())
+            .filter_map(|child| {
+                let (anchor, site_id) = self.anchors.get(*child).ok()?;
+                let [x, y] = anchor.translation_for_category(Category::Drawing);
+                let label = match site_id {
+                    Some(id) => format!("#{}", id.0),
+                    None => format!("{child:?}"),
+                };
+                Some((label, [x, -y]))
+            })
+            .collect();
+
+        CollapsingHeader::new("Rectify")
+            .default_open(current.0.is_some())
+            .show(ui, |ui| {
+                ui.label("Match four corners of the image with their real coordinates");
+                Grid::new("inspect_drawing_rectification")
+                    .num_columns(4)
+                    .show(ui, |ui| {
+                        ui.label("");
+                        ui.label("Pixel (u, v)");
+                        ui.label("Real (x, y)");
+                        ui.label("");
+                        ui.end_row();
+                        for i in 0..4 {
+                            ui.label(format!("{}", i + 1));
+                            ui.horizontal(|ui| {
+                                for value in &mut draft.pixels[i] {
+                                    ui.add(DragValue::new(value).speed(1.0).suffix(" px"));
+                                }
+                            });
+                            ui.horizontal(|ui| {
+                                for value in &mut draft.meters[i] {
+                                    ui.add(DragValue::new(value).speed(0.01).suffix(" m"));
+                                }
+                            });
+                            ComboBox::from_id_source(("rectification_anchor", i))
+                                .selected_text("From anchor")
+                                .show_ui(ui, |ui| {
+                                    for (label, pixel) in &anchors {
+                                        if ui.selectable_label(false, label).clicked() {
+                                            draft.pixels[i] = *pixel;
+                                        }
+                                    }
+                                });
+                            ui.end_row();
+                        }
+                    });
+
+                let valid = draft.homography().is_some();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(valid, Button::new("Apply"))
+                        .on_disabled_hover_text("Three or more of the corners are in a line")
+                        .clicked()
+                    {
+                        self.change_rectification
+                            .send(Change::new(Rectification(Some(*draft)), drawing));
+                    }
+                    if ui
+                        .add_enabled(current.0.is_some(), Button::new("Clear"))
+                        .on_hover_text("Show the original image without any warping")
+                        .clicked()
+                    {
+                        self.change_rectification
+                            .send(Change::new(Rectification(None), drawing));
+                    }
+                });
+            });
+    }
+}
+
+/// Buttons that shift or turn the drawing by a small amount relative to the
+/// site, which helps to line up a scan with vertices that are already placed.
+fn show_nudge(ui: &mut Ui, pose: &Pose) -> Option<Pose> {
+    let mut new_pose = *pose;
+    ui.horizontal(|ui| {
+        ui.label("Nudge");
+        for (text, tooltip, [dx, dy]) in [
+            ("←", "Move left", [-1.0, 0.0]),
+            ("→", "Move right", [1.0, 0.0]),
+            ("↓", "Move down", [0.0, -1.0]),
+            ("↑", "Move up", [0.0, 1.0]),
+        ] {
+            if ui.button(text).on_hover_text(tooltip).clicked() {
+                new_pose.trans[0] += dx * NUDGE_DISTANCE;
+                new_pose.trans[1] += dy * NUDGE_DISTANCE;
+            }
+        }
+        for (text, tooltip, sign) in [
+            ("↺", "Turn counter-clockwise", 1.0),
+            ("↻", "Turn clockwise", -1.0),
+        ] {
+            if ui.button(text).on_hover_text(tooltip).clicked() {
+                new_pose.rot.apply_yaw(Angle::Deg(sign * NUDGE_ANGLE));
+            }
+        }
+    });
+
+    (new_pose != *pose).then_some(new_pose)
+}
@@ -40,6 +40,7 @@ pub struct InspectEdge<'w, 's> {
         ),
     >,
     anchor_selection: AnchorSelection<'w, 's>,
+    transforms: Query<'w, 's, &'static GlobalTransform>,
 }
 
 impl<'w, 's> ShareableWidget for InspectEdge<'w, 's> {}
@@ -107,6 +108,15 @@ impl<'w, 's> WidgetSystem<Inspect> for InspectEdge<'w, 's> {
                 world,
             );
         });
+
+        let params = state.get_mut(world);
+        if let (Ok(left), Ok(right)) = (
+            params.transforms.get(edge.left()),
+            params.transforms.get(edge.right()),
+        ) {
+            let length = left.translation().distance(right.translation());
+            ui.label(format!("Length: {length:.3} m"));
+        }
         ui.add_space(10.0);
     }
 }
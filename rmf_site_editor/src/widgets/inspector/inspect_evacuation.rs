@@ -0,0 +1,70 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{Change, EmergencyExit, EvacuationRoute},
+    widgets::{prelude::*, Inspect},
+};
+use bevy::prelude::*;
+use bevy_egui::egui::Ui;
+
+#[derive(SystemParam)]
+pub struct InspectEvacuation<'w, 's> {
+    emergency_exits: Query<'w, 's, &'static EmergencyExit>,
+    change_emergency_exit: EventWriter<'w, Change<EmergencyExit>>,
+    evacuation_routes: Query<'w, 's, &'static EvacuationRoute>,
+    change_evacuation_route: EventWriter<'w, Change<EvacuationRoute>>,
+}
+
+impl<'w, 's> WidgetSystem<Inspect> for InspectEvacuation<'w, 's> {
+    fn show(
+        Inspect { selection, .. }: Inspect,
+        ui: &mut Ui,
+        state: &mut SystemState<Self>,
+        world: &mut World,
+    ) {
+        let mut params = state.get_mut(world);
+        params.show_widget(selection, ui);
+    }
+}
+
+impl<'w, 's> InspectEvacuation<'w, 's> {
+    pub fn show_widget(&mut self, id: Entity, ui: &mut Ui) {
+        if let Ok(exit) = self.emergency_exits.get(id) {
+            let mut is_exit = exit.0;
+            ui.checkbox(&mut is_exit, "Emergency exit")
+                .on_hover_text("Emergency exits are highlighted on evacuation plans");
+            if is_exit != exit.0 {
+                self.change_emergency_exit
+                    .send(Change::new(EmergencyExit(is_exit), id));
+            }
+            ui.add_space(10.0);
+        } else if let Ok(route) = self.evacuation_routes.get(id) {
+            let mut is_route = route.0;
+            ui.checkbox(&mut is_route, "Evacuation route")
+                .on_hover_text(
+                    "Evacuation routes are exported as their own nav graph and do not \
+                change how robots use the lane",
+                );
+            if is_route != route.0 {
+                self.change_evacuation_route
+                    .send(Change::new(EvacuationRoute(is_route), id));
+            }
+            ui.add_space(10.0);
+        }
+    }
+}
@@ -22,11 +22,17 @@ use crate::{
     CurrentWorkspace,
 };
 use bevy::prelude::*;
-use bevy_egui::egui::DragValue;
 
 #[derive(SystemParam)]
 pub struct InspectGeography<'w, 's> {
-    geographical: Query<'w, 's, &'static GeographicComponent>,
+    geography: Query<
+        'w,
+        's,
+        (
+            &'static GeographicComponent,
+            Option<&'static GeographicReferences>,
+        ),
+    >,
     tfs: Query<'w, 's, &'static Transform, Or<(With<Anchor>, With<Pose>)>>,
     move_to: EventWriter<'w, MoveTo>,
     current_workspace: Res<'w, CurrentWorkspace>,
@@ -43,40 +49,35 @@ impl<'w, 's> WidgetSystem<Inspect> for InspectGeography<'w, 's> {
         let Some(workspace) = param.current_workspace.root else {
             return;
         };
-        let Ok(geo) = param.geographical.get(workspace) else {
+        let Ok(tf) = param.tfs.get(selection) else {
             return;
         };
-        let Ok(tf) = param.tfs.get(selection) else {
+        // The reference points of the site take priority over its offset
+        let Some(projection) = site_projection(workspace, &param.geography) else {
+            return;
+        };
+        let Some([mut lon, mut lat]) = projection.lon_lat([tf.translation.x, tf.translation.y])
+        else {
+            warn!("Unable to obtain latitude and longitude");
             return;
         };
 
-        if let Some(offset) = geo.0 {
-            let (mut lat, mut lon) = match world_to_latlon(tf.translation, offset.anchor) {
-                Ok(values) => values,
-                Err(err) => {
-                    warn!("Unable to obtain latitude and longitude: {err:?}");
-                    return;
-                }
-            };
-
-            let old_lat = lat;
-            let old_lon = lon;
+        let old_lat = lat;
+        let old_lon = lon;
 
-            ui.label("Latitude");
-            ui.add(DragValue::new(&mut lat).speed(0.0));
-            ui.label("Longitude");
-            ui.add(DragValue::new(&mut lon).speed(0.0));
+        ui.label("Latitude");
+        ui.add(number_field(&mut lat).speed(0.0).max_decimals(7));
+        ui.label("Longitude");
+        ui.add(number_field(&mut lon).speed(0.0).max_decimals(7));
 
-            if old_lat != lat || old_lon != lon {
-                param.move_to.send(MoveTo {
-                    entity: selection,
-                    transform: Transform::from_translation(latlon_to_world(
-                        lat as f32,
-                        lon as f32,
-                        offset.anchor,
-                    )),
-                });
-            }
+        if old_lat != lat || old_lon != lon {
+            let Some([x, y]) = projection.site_position(lat, lon) else {
+                return;
+            };
+            param.move_to.send(MoveTo {
+                entity: selection,
+                transform: Transform::from_xyz(x, y, tf.translation.z),
+            });
         }
     }
 }
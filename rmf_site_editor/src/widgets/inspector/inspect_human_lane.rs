@@ -0,0 +1,63 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::SetHumanLanes,
+    widgets::{prelude::*, Inspect},
+};
+use bevy::prelude::*;
+use bevy_egui::egui::Ui;
+use rmf_site_format::{HumanLaneMarker, LaneMarker};
+
+#[derive(SystemParam)]
+pub struct InspectHumanLane<'w, 's> {
+    lanes: Query<'w, 's, Has<HumanLaneMarker>, With<LaneMarker>>,
+    set_human_lanes: EventWriter<'w, SetHumanLanes>,
+}
+
+impl<'w, 's> WidgetSystem<Inspect> for InspectHumanLane<'w, 's> {
+    fn show(
+        Inspect { selection, .. }: Inspect,
+        ui: &mut Ui,
+        state: &mut SystemState<Self>,
+        world: &mut World,
+    ) {
+        let mut params = state.get_mut(world);
+        params.show_widget(selection, ui);
+    }
+}
+
+impl<'w, 's> InspectHumanLane<'w, 's> {
+    pub fn show_widget(&mut self, id: Entity, ui: &mut Ui) {
+        let Ok(is_human) = self.lanes.get(id) else {
+            return;
+        };
+
+        let mut human = is_human;
+        ui.checkbox(&mut human, "Human lane").on_hover_text(
+            "Human lanes are walked by agents of the crowd simulation and are \
+            not part of any robot navigation graph",
+        );
+        if human != is_human {
+            self.set_human_lanes.send(SetHumanLanes {
+                lanes: vec![id],
+                human,
+            });
+        }
+        ui.add_space(10.0);
+    }
+}
@@ -0,0 +1,135 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    settings::EditorSettings,
+    site::{Anchor, Angle, Category, Change, Edge, LaneCurve},
+    widgets::{inspector::InspectAngle, length_field, prelude::*, Inspect},
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{ComboBox, Grid, Ui};
+
+#[derive(SystemParam)]
+pub struct InspectLaneCurve<'w, 's> {
+    curves: Query<'w, 's, (&'static LaneCurve, &'static Edge<Entity>)>,
+    anchors: Query<'w, 's, &'static Anchor>,
+    change_curve: EventWriter<'w, Change<LaneCurve>>,
+    settings: Res<'w, EditorSettings>,
+}
+
+impl<'w, 's> WidgetSystem<Inspect> for InspectLaneCurve<'w, 's> {
+    fn show(
+        Inspect { selection, .. }: Inspect,
+        ui: &mut Ui,
+        state: &mut SystemState<Self>,
+        world: &mut World,
+    ) {
+        let mut params = state.get_mut(world);
+        params.show_widget(selection, ui);
+    }
+}
+
+impl<'w, 's> InspectLaneCurve<'w, 's> {
+    pub fn show_widget(&mut self, id: Entity, ui: &mut Ui) {
+        let Ok((curve, edge)) = self.curves.get(id) else {
+            return;
+        };
+
+        let mut new_curve = *curve;
+        ui.horizontal(|ui| {
+            ui.label("Shape");
+            ComboBox::from_id_source("lane_curve")
+                .selected_text(curve.label())
+                .show_ui(ui, |ui| {
+                    for choice in self.choices(curve, edge) {
+                        let label = choice.label();
+                        if ui.selectable_label(curve.label() == label, label).clicked() {
+                            new_curve = choice;
+                        }
+                    }
+                });
+        });
+
+        match &mut new_curve {
+            LaneCurve::Straight => {}
+            LaneCurve::Bezier {
+                start_control,
+                end_control,
+            } => {
+                Grid::new("lane_curve_bezier").show(ui, |ui| {
+                    for (label, control) in [
+                        ("Start control", start_control),
+                        ("End control", end_control),
+                    ] {
+                        ui.label(label).on_hover_text(
+                            "Offset of the control point from the anchor at this end of the lane",
+                        );
+                        for value in control.iter_mut() {
+                            ui.add(length_field(value, self.settings.units).speed(0.01));
+                        }
+                        ui.end_row();
+                    }
+                });
+            }
+            LaneCurve::Arc { sweep } => {
+                ui.horizontal(|ui| {
+                    ui.label("Sweep").on_hover_text(
+                        "How far the direction of travel turns along the lane. \
+                        Positive values turn left.",
+                    );
+                    InspectAngle::new(sweep)
+                        .range_degrees(-300.0..=300.0)
+                        .show(ui);
+                });
+            }
+        }
+
+        if new_curve != *curve {
+            self.change_curve.send(Change::new(new_curve, id));
+        }
+        ui.add_space(10.0);
+    }
+
+    /// The curves that can be chosen for a lane. Switching to a new kind of
+    /// curve starts from a gentle bend so the change is visible right away.
+    fn choices(&self, current: &LaneCurve, edge: &Edge<Entity>) -> [LaneCurve; 3] {
+        let point = |e: Entity| {
+            self.anchors
+                .get(e)
+                .map(|a| Vec2::from(a.translation_for_category(Category::Lane)))
+                .unwrap_or_default()
+        };
+        let chord = point(edge.end()) - point(edge.start());
+        let bezier = match current {
+            LaneCurve::Bezier { .. } => *current,
+            _ => {
+                let offset = chord.perp() / 3.0;
+                LaneCurve::Bezier {
+                    start_control: (chord / 3.0 + offset).to_array(),
+                    end_control: (-chord / 3.0 + offset).to_array(),
+                }
+            }
+        };
+        let arc = match current {
+            LaneCurve::Arc { .. } => *current,
+            _ => LaneCurve::Arc {
+                sweep: Angle::Deg(90.0),
+            },
+        };
+        [LaneCurve::Straight, bezier, arc]
+    }
+}
@@ -21,11 +21,11 @@ use crate::{
         BeginEditDrawing, Change, DrawingMarker, FloorMarker, LayerVisibility,
         PreferredSemiTransparency, VisibilityCycle,
     },
-    widgets::{inspector::Inspect, prelude::*, Icons, MoveLayer, SelectorWidget},
+    widgets::{inspector::Inspect, number_field, prelude::*, Icons, MoveLayer, SelectorWidget},
     ChangeRank,
 };
 use bevy::prelude::*;
-use bevy_egui::egui::{DragValue, ImageButton, Ui};
+use bevy_egui::egui::{ImageButton, Ui};
 
 #[derive(SystemParam)]
 pub struct InspectLayer<'w, 's> {
@@ -180,7 +180,7 @@ impl<'w, 's> InspectLayer<'w, 's> {
             if let Some(LayerVisibility::Alpha(mut alpha)) = vis {
                 if ui
                     .add(
-                        DragValue::new(&mut alpha)
+                        number_field(&mut alpha)
                             .clamp_range(0_f32..=1_f32)
                             .speed(0.01),
                     )
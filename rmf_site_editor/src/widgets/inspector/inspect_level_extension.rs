@@ -0,0 +1,59 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{Change, LevelExtension},
+    widgets::{prelude::*, Inspect},
+};
+use bevy::prelude::*;
+use bevy_egui::egui::Ui;
+
+/// Inspector for entities whose kind was added by a
+/// [`LevelExtensionPlugin`](crate::site::LevelExtensionPlugin). This is added
+/// automatically by the plugin.
+#[derive(SystemParam)]
+pub struct InspectLevelExtension<'w, 's, T: LevelExtension> {
+    values: Query<'w, 's, &'static T>,
+    change: EventWriter<'w, Change<T>>,
+}
+
+impl<'w, 's, T: LevelExtension> WidgetSystem<Inspect> for InspectLevelExtension<'w, 's, T> {
+    fn show(
+        Inspect { selection, .. }: Inspect,
+        ui: &mut Ui,
+        state: &mut SystemState<Self>,
+        world: &mut World,
+    ) {
+        let mut params = state.get_mut(world);
+        params.show_widget(selection, ui);
+    }
+}
+
+impl<'w, 's, T: LevelExtension> InspectLevelExtension<'w, 's, T> {
+    pub fn show_widget(&mut self, id: Entity, ui: &mut Ui) {
+        let Ok(value) = self.values.get(id) else {
+            return;
+        };
+
+        ui.label(T::LABEL);
+        let mut new_value = value.clone();
+        if new_value.inspect(ui) && new_value != *value {
+            self.change.send(Change::new(new_value, id));
+        }
+        ui.add_space(10.0);
+    }
+}
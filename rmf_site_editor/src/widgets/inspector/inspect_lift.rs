@@ -16,16 +16,17 @@
 */
 
 use crate::{
+    settings::EditorSettings,
     site::{
         CabinDoorId, Change, CurrentLevel, LevelElevation, NameInSite, ToggleLiftDoorAvailability,
     },
     widgets::{
-        inspector::InspectOptionF32, prelude::*, Inspect, InspectionPlugin, LevelDisplay,
-        SelectorWidget,
+        inspector::InspectOptionF32, length_field, prelude::*, Inspect, InspectionPlugin,
+        LevelDisplay, SelectorWidget,
     },
 };
 use bevy::{ecs::system::SystemParam, prelude::*};
-use bevy_egui::egui::{CollapsingHeader, DragValue, Ui};
+use bevy_egui::egui::{CollapsingHeader, Ui};
 use rmf_site_format::lift::*;
 
 #[derive(Default)]
@@ -48,6 +49,9 @@ pub struct InspectLiftCabin<'w, 's> {
     selector: SelectorWidget<'w, 's>,
     toggle_door_levels: EventWriter<'w, ToggleLiftDoorAvailability>,
     current_level: Res<'w, CurrentLevel>,
+    level_elevations: Query<'w, 's, &'static LiftLevelElevations<Entity>>,
+    change_level_elevations: EventWriter<'w, Change<LiftLevelElevations<Entity>>>,
+    settings: Res<'w, EditorSettings>,
 }
 
 impl<'w, 's> WidgetSystem<Inspect> for InspectLiftCabin<'w, 's> {
@@ -67,14 +71,14 @@ impl<'w, 's> InspectLiftCabin<'w, 's> {
         let Ok((cabin, recall)) = self.cabins.get(id) else {
             return;
         };
+        let units = self.settings.units;
         let mut new_cabin = cabin.clone();
         match &mut new_cabin {
             LiftCabin::Rect(params) => {
                 ui.horizontal(|ui| {
                     ui.label("width");
                     ui.add(
-                        DragValue::new(&mut params.width)
-                            .suffix("m")
+                        length_field(&mut params.width, units)
                             .clamp_range(0.01..=std::f32::INFINITY)
                             .fixed_decimals(2)
                             .speed(0.01),
@@ -84,8 +88,7 @@ impl<'w, 's> InspectLiftCabin<'w, 's> {
                 ui.horizontal(|ui| {
                     ui.label("depth");
                     ui.add(
-                        DragValue::new(&mut params.depth)
-                            .suffix("m")
+                        length_field(&mut params.depth, units)
                             .clamp_range(0.01..=std::f32::INFINITY)
                             .fixed_decimals(2)
                             .speed(0.01),
@@ -100,7 +103,7 @@ impl<'w, 's> InspectLiftCabin<'w, 's> {
                         .unwrap_or(DEFAULT_CABIN_WALL_THICKNESS),
                 )
                 .clamp_range(0.001..=std::f32::INFINITY)
-                .suffix("m")
+                .length_unit(units)
                 .min_decimals(2)
                 .max_decimals(4)
                 .speed(0.001)
@@ -115,7 +118,7 @@ impl<'w, 's> InspectLiftCabin<'w, 's> {
                     recall.gap.unwrap_or(DEFAULT_CABIN_GAP),
                 )
                 .clamp_range(0.001..=std::f32::INFINITY)
-                .suffix("m")
+                .length_unit(units)
                 .min_decimals(2)
                 .max_decimals(4)
                 .speed(0.001)
@@ -126,7 +129,7 @@ impl<'w, 's> InspectLiftCabin<'w, 's> {
 
                 if let Some(new_shift) =
                     InspectOptionF32::new("Shift", params.shift, recall.shift.unwrap_or(0.0))
-                        .suffix("m")
+                        .length_unit(units)
                         .min_decimals(2)
                         .max_decimals(4)
                         .speed(0.001)
@@ -146,8 +149,7 @@ impl<'w, 's> InspectLiftCabin<'w, 's> {
                                 ui.horizontal(|ui| {
                                     ui.label("width");
                                     ui.add(
-                                        DragValue::new(&mut placement.width)
-                                            .suffix("m")
+                                        length_field(&mut placement.width, units)
                                             .clamp_range(0.001..=cabin_width - 0.001)
                                             .min_decimals(2)
                                             .max_decimals(4)
@@ -157,7 +159,7 @@ impl<'w, 's> InspectLiftCabin<'w, 's> {
 
                                 if let Some(new_shift) =
                                     InspectOptionF32::new("Shifted", placement.shifted, 0.0)
-                                        .suffix("m")
+                                        .length_unit(units)
                                         .min_decimals(2)
                                         .max_decimals(4)
                                         .speed(0.005)
@@ -171,7 +173,7 @@ impl<'w, 's> InspectLiftCabin<'w, 's> {
                                     placement.custom_gap,
                                     cabin_gap,
                                 )
-                                .suffix("m")
+                                .length_unit(units)
                                 .clamp_range(0.0..=std::f32::INFINITY)
                                 .min_decimals(2)
                                 .max_decimals(4)
@@ -186,7 +188,7 @@ impl<'w, 's> InspectLiftCabin<'w, 's> {
                                     placement.thickness,
                                     DEFAULT_CABIN_DOOR_THICKNESS,
                                 )
-                                .suffix("m")
+                                .length_unit(units)
                                 .clamp_range(0.001..=std::f32::INFINITY)
                                 .min_decimals(2)
                                 .max_decimals(4)
@@ -255,6 +257,39 @@ impl<'w, 's> InspectLiftCabin<'w, 's> {
 
             self.change_lift_cabin.send(Change::new(new_cabin, id));
         }
+
+        self.show_level_elevations(id, ui);
         ui.add_space(10.0);
     }
+
+    fn show_level_elevations(&mut self, id: Entity, ui: &mut Ui) {
+        let old = self.level_elevations.get(id).cloned().unwrap_or_default();
+        let mut new = old.clone();
+        let units = self.settings.units;
+        CollapsingHeader::new("Floor Heights")
+            .default_open(false)
+            .show(ui, |ui| {
+                for level in &self.display_level.order {
+                    let Ok((name, elevation)) = self.levels.get(*level) else {
+                        continue;
+                    };
+                    ui.horizontal(|ui| {
+                        let mut known = new.contains_key(level);
+                        ui.checkbox(&mut known, &name.0)
+                            .on_hover_text("The lift data gives a floor height for this level");
+                        if known {
+                            let height = new.entry(*level).or_insert(elevation.0);
+                            ui.add(length_field(height, units).speed(0.01));
+                        } else {
+                            new.remove(level);
+                        }
+                    });
+                }
+            });
+
+        if new != old {
+            self.change_level_elevations
+                .send(Change::new(new, id).or_insert());
+        }
+    }
 }
@@ -17,12 +17,12 @@
 
 use crate::{
     site::{Change, LightKind, RecallLightKind},
-    widgets::{prelude::*, Inspect},
+    widgets::{number_field, prelude::*, Inspect},
 };
 use bevy::prelude::*;
 use bevy_egui::egui::{
     color_picker::{color_edit_button_rgba, Alpha},
-    ComboBox, DragValue, Rgba, Ui,
+    ComboBox, Rgba, Ui,
 };
 
 #[derive(SystemParam)]
@@ -86,21 +86,19 @@ impl<'a> InspectLightKind<'a> {
                 ui.horizontal(|ui| {
                     ui.label("Intensity");
                     ui.add(
-                        DragValue::new(&mut point.intensity)
+                        number_field(&mut point.intensity)
                             .clamp_range(0_f32..=std::f32::INFINITY)
                             .speed(10),
                     );
                 });
                 ui.horizontal(|ui| {
                     ui.label("Range");
-                    ui.add(
-                        DragValue::new(&mut point.range).clamp_range(0_f32..=std::f32::INFINITY),
-                    );
+                    ui.add(number_field(&mut point.range).clamp_range(0_f32..=std::f32::INFINITY));
                 });
                 ui.horizontal(|ui| {
                     ui.label("Radius");
                     ui.add(
-                        DragValue::new(&mut point.radius)
+                        number_field(&mut point.radius)
                             .clamp_range(0_f32..=std::f32::INFINITY)
                             .speed(0.1),
                     );
@@ -115,19 +113,19 @@ impl<'a> InspectLightKind<'a> {
                 ui.horizontal(|ui| {
                     ui.label("Intensity");
                     ui.add(
-                        DragValue::new(&mut spot.intensity)
+                        number_field(&mut spot.intensity)
                             .clamp_range(0_f32..=std::f32::INFINITY)
                             .speed(10),
                     );
                 });
                 ui.horizontal(|ui| {
                     ui.label("Range");
-                    ui.add(DragValue::new(&mut spot.range).clamp_range(0_f32..=std::f32::INFINITY));
+                    ui.add(number_field(&mut spot.range).clamp_range(0_f32..=std::f32::INFINITY));
                 });
                 ui.horizontal(|ui| {
                     ui.label("Radius");
                     ui.add(
-                        DragValue::new(&mut spot.radius)
+                        number_field(&mut spot.radius)
                             .clamp_range(0_f32..=std::f32::INFINITY)
                             .speed(0.1),
                     );
@@ -142,7 +140,7 @@ impl<'a> InspectLightKind<'a> {
                 ui.horizontal(|ui| {
                     ui.label("Illuminance");
                     ui.add(
-                        DragValue::new(&mut dir.illuminance)
+                        number_field(&mut dir.illuminance)
                             .clamp_range(0_f32..=std::f32::INFINITY)
                             .speed(1000),
                     );
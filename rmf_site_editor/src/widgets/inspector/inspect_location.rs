@@ -16,11 +16,14 @@
 */
 
 use crate::{
-    site::{Change, ConsiderLocationTag, LocationTag, LocationTags, RecallLocationTags},
+    site::{
+        Change, ConsiderLocationTag, DeliveryWorkcell, LocationTag, LocationTags,
+        RecallLocationTags, RobotSpawnPoint,
+    },
     widgets::{prelude::*, Icons, Inspect},
 };
 use bevy::prelude::*;
-use bevy_egui::egui::{ComboBox, ImageButton, RichText, Ui};
+use bevy_egui::egui::{ComboBox, Grid, ImageButton, RichText, TextEdit, Ui};
 use smallvec::SmallVec;
 
 #[derive(SystemParam)]
@@ -51,6 +54,7 @@ impl<'w, 's> InspectLocation<'w, 's> {
 
         ui.label(RichText::new("Location Tags").size(18.0));
         let mut deleted_tag = None;
+        let mut edited_tag = None;
         for (i, tag) in tags.0.iter().enumerate() {
             ui.horizontal(|ui| {
                 if ui.add(ImageButton::new(self.icons.trash.egui())).clicked() {
@@ -58,6 +62,24 @@ impl<'w, 's> InspectLocation<'w, 's> {
                 }
                 ui.label(tag.label());
             });
+            match tag {
+                LocationTag::Dispenser(cell) => {
+                    if let Some(cell) = show_delivery_workcell(ui, i, cell, "TeleportDispenser") {
+                        edited_tag = Some((i, LocationTag::Dispenser(cell)));
+                    }
+                }
+                LocationTag::Ingestor(cell) => {
+                    if let Some(cell) = show_delivery_workcell(ui, i, cell, "TeleportIngestor") {
+                        edited_tag = Some((i, LocationTag::Ingestor(cell)));
+                    }
+                }
+                LocationTag::SpawnPoint(spawn) => {
+                    if let Some(spawn) = show_robot_spawn_point(ui, i, spawn) {
+                        edited_tag = Some((i, LocationTag::SpawnPoint(spawn)));
+                    }
+                }
+                _ => {}
+            }
             ui.add_space(5.0);
             ui.separator();
             ui.add_space(5.0);
@@ -69,7 +91,7 @@ impl<'w, 's> InspectLocation<'w, 's> {
                     .horizontal(|ui| {
                         let add = ui.button("Confirm").clicked();
                         let mut consider = recall.assume_tag(tags);
-                        let mut variants: SmallVec<[LocationTag; 5]> = SmallVec::new();
+                        let mut variants: SmallVec<[LocationTag; 9]> = SmallVec::new();
                         if tags.iter().find(|t| t.is_charger()).is_none() {
                             variants.push(LocationTag::Charger);
                         }
@@ -79,6 +101,18 @@ impl<'w, 's> InspectLocation<'w, 's> {
                         if tags.iter().find(|t| t.is_holding_point()).is_none() {
                             variants.push(LocationTag::HoldingPoint);
                         }
+                        if tags.iter().find(|t| t.is_assembly_point()).is_none() {
+                            variants.push(LocationTag::AssemblyPoint);
+                        }
+                        if tags.iter().find_map(|t| t.dispenser()).is_none() {
+                            variants.push(LocationTag::Dispenser(DeliveryWorkcell::default()));
+                        }
+                        if tags.iter().find_map(|t| t.ingestor()).is_none() {
+                            variants.push(LocationTag::Ingestor(DeliveryWorkcell::default()));
+                        }
+                        if tags.iter().find_map(|t| t.spawn_point()).is_none() {
+                            variants.push(LocationTag::SpawnPoint(RobotSpawnPoint::default()));
+                        }
                         variants.push(recall.assume_workcell());
 
                         ComboBox::from_id_source("Add Location Tag")
@@ -113,8 +147,11 @@ impl<'w, 's> InspectLocation<'w, 's> {
             .body_returned
             .flatten();
 
-        if deleted_tag.is_some() || added_tag.is_some() {
+        if deleted_tag.is_some() || added_tag.is_some() || edited_tag.is_some() {
             let mut new_tags = tags.clone();
+            if let Some((i, tag)) = edited_tag {
+                new_tags[i] = tag;
+            }
             if let Some(i) = deleted_tag {
                 new_tags.remove(i);
             }
@@ -127,3 +164,52 @@ impl<'w, 's> InspectLocation<'w, 's> {
         }
     }
 }
+
+/// Show the name and type of a dispenser or ingestor. Returns the edited
+/// workcell if anything changed.
+fn show_delivery_workcell(
+    ui: &mut Ui,
+    index: usize,
+    cell: &DeliveryWorkcell,
+    type_hint: &str,
+) -> Option<DeliveryWorkcell> {
+    let mut new_cell = cell.clone();
+    Grid::new(("delivery_workcell", index))
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Name");
+            ui.add(TextEdit::singleline(&mut new_cell.name).hint_text("Used by delivery tasks"));
+            ui.end_row();
+
+            ui.label("Type");
+            ui.add(TextEdit::singleline(&mut new_cell.workcell_type).hint_text(type_hint));
+            ui.end_row();
+        });
+    (new_cell != *cell).then_some(new_cell)
+}
+
+/// Show the fleet, name, and model of a robot spawn point. Returns the edited
+/// spawn point if anything changed.
+fn show_robot_spawn_point(
+    ui: &mut Ui,
+    index: usize,
+    spawn: &RobotSpawnPoint,
+) -> Option<RobotSpawnPoint> {
+    let mut new_spawn = spawn.clone();
+    Grid::new(("robot_spawn_point", index))
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Fleet");
+            ui.text_edit_singleline(&mut new_spawn.fleet);
+            ui.end_row();
+
+            ui.label("Robot Name");
+            ui.text_edit_singleline(&mut new_spawn.robot_name);
+            ui.end_row();
+
+            ui.label("Robot Type");
+            ui.add(TextEdit::singleline(&mut new_spawn.robot_type).hint_text("e.g. TinyRobot"));
+            ui.end_row();
+        });
+    (new_spawn != *spawn).then_some(new_spawn)
+}
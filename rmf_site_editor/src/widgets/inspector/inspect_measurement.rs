@@ -16,15 +16,22 @@
 */
 
 use crate::{
-    site::{Change, Distance},
+    settings::EditorSettings,
+    site::{Anchor, Category, Change, Distance, DrawingMarker, Edge, PixelsPerMeter},
     widgets::{prelude::*, Inspect, InspectOptionF32},
 };
 use bevy::prelude::*;
+use bevy_egui::egui::Button;
 
 #[derive(SystemParam)]
 pub struct InspectMeasurement<'w, 's> {
     distances: Query<'w, 's, &'static Distance>,
     change_distance: EventWriter<'w, Change<Distance>>,
+    edges: Query<'w, 's, (&'static Edge<Entity>, &'static Parent)>,
+    anchors: Query<'w, 's, &'static Anchor>,
+    drawings: Query<'w, 's, &'static PixelsPerMeter, With<DrawingMarker>>,
+    change_pixels_per_meter: EventWriter<'w, Change<PixelsPerMeter>>,
+    settings: Res<'w, EditorSettings>,
 }
 
 impl<'w, 's> WidgetSystem<Inspect> for InspectMeasurement<'w, 's> {
@@ -44,13 +51,57 @@ impl<'w, 's> WidgetSystem<Inspect> for InspectMeasurement<'w, 's> {
             .min_decimals(2)
             .max_decimals(2)
             .speed(0.01)
-            .suffix(" m")
+            .length_unit(params.settings.units)
             .show(ui)
         {
             params
                 .change_distance
                 .send(Change::new(Distance(new_distance), selection));
         }
+
+        params.show_calibration(selection, distance.0, ui);
         ui.add_space(10.0);
     }
 }
+
+impl<'w, 's> InspectMeasurement<'w, 's> {
+    /// Let the user set the scale of the drawing from this measurement
+    fn show_calibration(&mut self, selection: Entity, distance: Option<f32>, ui: &mut Ui) {
+        let Ok((edge, parent)) = self.edges.get(selection) else {
+            return;
+        };
+        let drawing = parent.get();
+        let Ok(ppm) = self.drawings.get(drawing) else {
+            return;
+        };
+        let Ok([anchor0, anchor1]) = self.anchors.get_many(edge.array()) else {
+            return;
+        };
+        let p0 = Vec2::from_array(anchor0.translation_for_category(Category::Measurement));
+        let p1 = Vec2::from_array(anchor1.translation_for_category(Category::Measurement));
+        let in_pixels = (p1 - p0).length();
+
+        ui.label(format!(
+            "{:.1} px, which is {:.2} m at the current scale",
+            in_pixels,
+            in_pixels / ppm.0,
+        ));
+
+        let calibrated = distance
+            .filter(|d| *d > 0.0 && in_pixels > 0.0)
+            .map(|d| in_pixels / d);
+        if ui
+            .add_enabled(calibrated.is_some(), Button::new("Calibrate Scale"))
+            .on_hover_text(
+                "Set the pixels per meter of the drawing so that this \
+                measurement matches its distance",
+            )
+            .clicked()
+        {
+            if let Some(calibrated) = calibrated {
+                self.change_pixels_per_meter
+                    .send(Change::new(PixelsPerMeter(calibrated), drawing));
+            }
+        }
+    }
+}
@@ -25,10 +25,10 @@ use super::{
 };
 use crate::{
     site::{Change, Group, ModelMarker, ModelProperty, Pose, Robot},
-    widgets::{prelude::*, Inspect},
+    widgets::{number_field, prelude::*, Inspect},
 };
 use bevy::{ecs::system::SystemParam, prelude::*};
-use bevy_egui::egui::{DragValue, Grid, Ui};
+use bevy_egui::egui::{Grid, Ui};
 use rmf_site_format::Recall;
 use serde::{Deserialize, Serialize};
 use serde_json::Map;
@@ -254,7 +254,7 @@ impl<'w, 's> WidgetSystem<Inspect> for InspectCircleCollision<'w, 's> {
                     ui.label("Collision Radius");
                     if ui
                         .add(
-                            DragValue::new(&mut new_circle_collision.radius)
+                            number_field(&mut new_circle_collision.radius)
                                 .clamp_range(0_f32..=std::f32::INFINITY)
                                 .speed(0.01),
                         )
@@ -279,12 +279,12 @@ impl<'w, 's> WidgetSystem<Inspect> for InspectCircleCollision<'w, 's> {
 
                     ui.label("");
                     ui.add(
-                        DragValue::new(&mut new_circle_collision.offset[0])
+                        number_field(&mut new_circle_collision.offset[0])
                             .clamp_range(std::f32::NEG_INFINITY..=std::f32::INFINITY)
                             .speed(0.01),
                     );
                     ui.add(
-                        DragValue::new(&mut new_circle_collision.offset[1])
+                        number_field(&mut new_circle_collision.offset[1])
                             .clamp_range(std::f32::NEG_INFINITY..=std::f32::INFINITY)
                             .speed(0.01),
                     );
@@ -25,10 +25,10 @@ use super::{
 };
 use crate::{
     site::{Change, Group, ModelMarker, ModelProperty, Robot},
-    widgets::{prelude::*, Inspect},
+    widgets::{number_field, prelude::*, Inspect},
 };
 use bevy::{ecs::system::SystemParam, prelude::*};
-use bevy_egui::egui::{DragValue, Grid, Ui};
+use bevy_egui::egui::{Grid, Ui};
 use rmf_site_format::Recall;
 use serde::{Deserialize, Serialize};
 use serde_json::Map;
@@ -266,12 +266,12 @@ impl<'w, 's> WidgetSystem<Inspect> for InspectDifferentialDrive<'w, 's> {
 
                     ui.label("");
                     ui.add(
-                        DragValue::new(&mut new_differential_drive.rotation_center_offset[0])
+                        number_field(&mut new_differential_drive.rotation_center_offset[0])
                             .clamp_range(std::f32::NEG_INFINITY..=std::f32::INFINITY)
                             .speed(0.01),
                     );
                     ui.add(
-                        DragValue::new(&mut new_differential_drive.rotation_center_offset[1])
+                        number_field(&mut new_differential_drive.rotation_center_offset[1])
                             .clamp_range(std::f32::NEG_INFINITY..=std::f32::INFINITY)
                             .speed(0.01),
                     );
@@ -283,7 +283,7 @@ impl<'w, 's> WidgetSystem<Inspect> for InspectDifferentialDrive<'w, 's> {
 
                     ui.label("Max Velocity");
                     ui.add(
-                        DragValue::new(&mut new_differential_drive.translational_speed)
+                        number_field(&mut new_differential_drive.translational_speed)
                             .clamp_range(0_f32..=std::f32::INFINITY)
                             .speed(0.01),
                     );
@@ -292,7 +292,7 @@ impl<'w, 's> WidgetSystem<Inspect> for InspectDifferentialDrive<'w, 's> {
 
                     ui.label("Max Angular");
                     ui.add(
-                        DragValue::new(&mut new_differential_drive.rotational_speed)
+                        number_field(&mut new_differential_drive.rotational_speed)
                             .clamp_range(0_f32..=std::f32::INFINITY)
                             .speed(0.01),
                     );
@@ -17,11 +17,15 @@
 
 use super::{get_selected_description_entity, ModelPropertyQuery};
 use crate::{
+    settings::EditorSettings,
     site::{
-        AssetSource, Change, DefaultFile, Group, ModelLoader, ModelMarker, ModelProperty,
-        RecallAssetSource, Scale,
+        AssetSource, Change, DefaultFile, Group, IsStatic, ModelLoader, ModelMarker, ModelProperty,
+        RecallAssetSource, Scale, ZOffset,
+    },
+    widgets::{
+        length_field, prelude::*, Inspect, InspectAssetSourceComponent, InspectIsStatic,
+        InspectScaleComponent,
     },
-    widgets::{prelude::*, Inspect, InspectAssetSourceComponent, InspectScaleComponent},
     CurrentWorkspace,
 };
 use bevy::{ecs::system::SystemParam, prelude::*};
@@ -61,6 +65,84 @@ impl<'w, 's> WidgetSystem<Inspect> for InspectModelScale<'w, 's> {
     }
 }
 
+#[derive(SystemParam)]
+pub struct InspectModelIsStatic<'w, 's> {
+    model_instances: ModelPropertyQuery<'w, 's, IsStatic>,
+    model_descriptions:
+        Query<'w, 's, &'static ModelProperty<IsStatic>, (With<ModelMarker>, With<Group>)>,
+    change_is_static: EventWriter<'w, Change<ModelProperty<IsStatic>>>,
+}
+
+impl<'w, 's> WidgetSystem<Inspect> for InspectModelIsStatic<'w, 's> {
+    fn show(
+        Inspect { selection, .. }: Inspect,
+        ui: &mut Ui,
+        state: &mut SystemState<Self>,
+        world: &mut World,
+    ) {
+        let mut params = state.get_mut(world);
+        let Some(description_entity) = get_selected_description_entity(
+            selection,
+            &params.model_instances,
+            &params.model_descriptions,
+        ) else {
+            return;
+        };
+
+        let Ok(ModelProperty(is_static)) = params.model_descriptions.get(description_entity) else {
+            return;
+        };
+        if let Some(new_is_static) = InspectIsStatic::new(is_static).show(ui) {
+            params.change_is_static.send(Change::new(
+                ModelProperty(new_is_static),
+                description_entity,
+            ));
+        }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct InspectModelZOffset<'w, 's> {
+    model_instances: ModelPropertyQuery<'w, 's, ZOffset>,
+    model_descriptions:
+        Query<'w, 's, &'static ModelProperty<ZOffset>, (With<ModelMarker>, With<Group>)>,
+    change_z_offset: EventWriter<'w, Change<ModelProperty<ZOffset>>>,
+    settings: Res<'w, EditorSettings>,
+}
+
+impl<'w, 's> WidgetSystem<Inspect> for InspectModelZOffset<'w, 's> {
+    fn show(
+        Inspect { selection, .. }: Inspect,
+        ui: &mut Ui,
+        state: &mut SystemState<Self>,
+        world: &mut World,
+    ) {
+        let mut params = state.get_mut(world);
+        let Some(description_entity) = get_selected_description_entity(
+            selection,
+            &params.model_instances,
+            &params.model_descriptions,
+        ) else {
+            return;
+        };
+
+        let Ok(ModelProperty(z_offset)) = params.model_descriptions.get(description_entity) else {
+            return;
+        };
+        let mut new_z_offset = *z_offset;
+        ui.horizontal(|ui| {
+            ui.label("Z Offset")
+                .on_hover_text("Height that the model is raised by when spawned in simulation");
+            ui.add(length_field(&mut new_z_offset.0, params.settings.units).speed(0.01));
+        });
+        if new_z_offset != *z_offset {
+            params
+                .change_z_offset
+                .send(Change::new(ModelProperty(new_z_offset), description_entity));
+        }
+    }
+}
+
 #[derive(SystemParam)]
 pub struct InspectModelAssetSource<'w, 's> {
     model_instances: ModelPropertyQuery<'w, 's, AssetSource>,
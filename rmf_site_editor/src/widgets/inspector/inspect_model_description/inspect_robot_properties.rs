@@ -23,7 +23,7 @@ use crate::{
         SiteUpdateSet,
     },
     widgets::{prelude::*, Inspect},
-    AppState, Issue, ModelPropertyData, ValidateWorkspace,
+    AppState, Issue, ModelPropertyData, ValidationRequests,
 };
 use bevy::{
     ecs::system::SystemParam,
@@ -537,11 +537,11 @@ pub const MISSING_ROBOT_PROPERTY_KIND_ISSUE_UUID: Uuid =
 
 pub fn check_for_missing_robot_property_kinds(
     mut commands: Commands,
-    mut validate_events: EventReader<ValidateWorkspace>,
+    mut validate_events: ValidationRequests,
     robot_property_widgets: Res<RobotPropertyWidgetRegistry>,
     robots: Query<(Entity, &NameInSite, &ModelProperty<Robot>), (With<ModelMarker>, With<Group>)>,
 ) {
-    for root in validate_events.read() {
+    for root in validate_events.roots() {
         for (entity, description_name, robot) in robots.iter() {
             for (property, value) in robot.0.properties.iter() {
                 let Some(widget_registration) = robot_property_widgets.get(property) else {
@@ -584,7 +584,7 @@ pub fn check_for_missing_robot_property_kinds(
                     ),
                 };
                 let issue_id = commands.spawn(issue).id();
-                commands.entity(**root).add_child(issue_id);
+                commands.entity(root).add_child(issue_id);
             }
         }
     }
@@ -30,7 +30,7 @@ use std::{collections::HashMap, fmt::Debug};
 use crate::{
     site::{
         update_model_instances, Affiliation, AssetSource, Change, Group, IsStatic, ModelLoader,
-        ModelMarker, ModelProperty, NameInSite, Scale,
+        ModelMarker, ModelProperty, NameInSite, Scale, ZOffset,
     },
     widgets::{prelude::*, Inspect},
     MainInspector,
@@ -133,8 +133,20 @@ impl FromWorld for ModelPropertyData {
                 .unwrap(),
             (
                 "Is Static".to_string(),
-                get_insert_model_property_fn::<IsStatic>(),
-                get_remove_model_property_fn::<IsStatic>(),
+                get_insert_model_property_fn::<ModelProperty<IsStatic>>(),
+                get_remove_model_property_fn::<ModelProperty<IsStatic>>(),
+            ),
+        );
+        world.init_component::<ModelProperty<ZOffset>>();
+        required.insert(
+            world
+                .components()
+                .component_id::<ModelProperty<ZOffset>>()
+                .unwrap(),
+            (
+                "Z Offset".to_string(),
+                get_insert_model_property_fn::<ModelProperty<ZOffset>>(),
+                get_remove_model_property_fn::<ModelProperty<ZOffset>>(),
             ),
         );
         let optional = HashMap::new();
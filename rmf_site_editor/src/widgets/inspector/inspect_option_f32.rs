@@ -15,7 +15,11 @@
  *
 */
 
-use bevy_egui::egui::{DragValue, Ui};
+use crate::{
+    settings::LengthUnit,
+    widgets::inspector::{in_length_unit, number_field},
+};
+use bevy_egui::egui::Ui;
 use std::ops::RangeInclusive;
 
 pub struct InspectOptionF32<'a> {
@@ -28,6 +32,7 @@ pub struct InspectOptionF32<'a> {
     speed: f64,
     suffix: &'a str,
     tooltip: Option<&'a str>,
+    unit: Option<LengthUnit>,
 }
 
 impl<'a> InspectOptionF32<'a> {
@@ -42,6 +47,7 @@ impl<'a> InspectOptionF32<'a> {
             speed: 1.0,
             suffix: Default::default(),
             tooltip: Default::default(),
+            unit: None,
         }
     }
 
@@ -75,20 +81,29 @@ impl<'a> InspectOptionF32<'a> {
         self
     }
 
+    /// The value is a length in meters that should be shown in `unit`. This
+    /// takes the place of a suffix.
+    pub fn length_unit(mut self, unit: LengthUnit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
     pub fn show(self, ui: &mut Ui) -> Option<Option<f32>> {
         ui.horizontal(|ui| {
             let mut has_value = self.current_value.is_some();
             let mut assumed_value = self.current_value.unwrap_or(self.assumed_value);
             ui.checkbox(&mut has_value, self.title);
             if has_value {
-                let response = ui.add(
-                    DragValue::new(&mut assumed_value)
-                        .clamp_range(self.range)
-                        .min_decimals(self.min_decimals)
-                        .max_decimals_opt(self.max_decimals)
-                        .speed(self.speed)
-                        .suffix(self.suffix),
-                );
+                let mut field = number_field(&mut assumed_value)
+                    .clamp_range(self.range)
+                    .min_decimals(self.min_decimals)
+                    .max_decimals_opt(self.max_decimals)
+                    .speed(self.speed)
+                    .suffix(self.suffix);
+                if let Some(unit) = self.unit {
+                    field = in_length_unit(field, unit);
+                }
+                let response = ui.add(field);
 
                 if let Some(tooltip) = self.tooltip {
                     response.on_hover_text(tooltip);
@@ -16,14 +16,15 @@
 */
 
 use crate::{
+    settings::{EditorSettings, LengthUnit},
     site::{
         scenario::*, Affiliation, Change, CurrentScenario, InstanceModifier, UpdateInstance,
         UpdateInstanceEvent,
     },
-    widgets::{inspector::InspectAngle, prelude::*, Inspect},
+    widgets::{inspector::InspectAngle, length_field, number_field, prelude::*, Inspect},
 };
 use bevy::{math::Quat, prelude::*};
-use bevy_egui::egui::{ComboBox, DragValue, Grid, Ui};
+use bevy_egui::egui::{ComboBox, Grid, Ui};
 use rmf_site_format::{Pose, Rotation};
 
 #[derive(SystemParam)]
@@ -35,6 +36,7 @@ pub struct InspectPose<'w, 's> {
     instance_modifiers:
         Query<'w, 's, (&'static mut InstanceModifier, &'static Affiliation<Entity>)>,
     update_instance: EventWriter<'w, UpdateInstanceEvent>,
+    settings: Res<'w, EditorSettings>,
 }
 
 impl<'w, 's> WidgetSystem<Inspect> for InspectPose<'w, 's> {
@@ -48,7 +50,10 @@ impl<'w, 's> WidgetSystem<Inspect> for InspectPose<'w, 's> {
         let Ok(pose) = params.poses.get(selection) else {
             return;
         };
-        if let Some(new_pose) = InspectPoseComponent::new(pose).show(ui) {
+        if let Some(new_pose) = InspectPoseComponent::new(pose)
+            .length_unit(params.settings.units)
+            .show(ui)
+        {
             params.change_pose.send(Change::new(new_pose, selection));
         }
 
@@ -90,6 +95,7 @@ impl<'w, 's> WidgetSystem<Inspect> for InspectPose<'w, 's> {
 pub struct InspectPoseComponent<'a> {
     pub pose: &'a Pose,
     pub for_rotation: &'a bool,
+    pub unit: LengthUnit,
 }
 
 impl<'a> InspectPoseComponent<'a> {
@@ -97,6 +103,7 @@ impl<'a> InspectPoseComponent<'a> {
         Self {
             pose,
             for_rotation: &false,
+            unit: LengthUnit::Meters,
         }
     }
 
@@ -105,6 +112,12 @@ impl<'a> InspectPoseComponent<'a> {
         self
     }
 
+    /// Show the translation in `unit` instead of meters
+    pub fn length_unit(mut self, unit: LengthUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
     pub fn show(self, ui: &mut Ui) -> Option<Pose> {
         let mut new_pose = self.pose.clone();
         if !self.for_rotation {
@@ -114,9 +127,9 @@ impl<'a> InspectPoseComponent<'a> {
                 ui.label("z");
                 ui.end_row();
 
-                ui.add(DragValue::new(&mut new_pose.trans[0]).speed(0.01));
-                ui.add(DragValue::new(&mut new_pose.trans[1]).speed(0.01));
-                ui.add(DragValue::new(&mut new_pose.trans[2]).speed(0.01));
+                for value in &mut new_pose.trans {
+                    ui.add(length_field(value, self.unit).speed(0.01));
+                }
                 ui.end_row();
             });
             ui.add_space(5.0);
@@ -161,10 +174,10 @@ impl<'a> InspectPoseComponent<'a> {
                     ui.label("w");
                     ui.end_row();
 
-                    ui.add(DragValue::new(x).speed(0.01).clamp_range(-1.0..=1.0));
-                    ui.add(DragValue::new(y).speed(0.01).clamp_range(-1.0..=1.0));
-                    ui.add(DragValue::new(z).speed(0.01).clamp_range(-1.0..=1.0));
-                    ui.add(DragValue::new(w).speed(0.01).clamp_range(-1.0..=1.0));
+                    ui.add(number_field(x).speed(0.01).clamp_range(-1.0..=1.0));
+                    ui.add(number_field(y).speed(0.01).clamp_range(-1.0..=1.0));
+                    ui.add(number_field(z).speed(0.01).clamp_range(-1.0..=1.0));
+                    ui.add(number_field(w).speed(0.01).clamp_range(-1.0..=1.0));
                     ui.end_row();
                 });
 
@@ -17,10 +17,10 @@
 
 use crate::{
     site::Change,
-    widgets::{prelude::*, Inspect},
+    widgets::{number_field, prelude::*, Inspect},
 };
 use bevy::prelude::*;
-use bevy_egui::egui::{DragValue, Grid, Ui};
+use bevy_egui::egui::{Grid, Ui};
 use rmf_site_format::{Affiliation, Scale};
 
 #[derive(SystemParam)]
@@ -68,17 +68,17 @@ impl<'a> InspectScaleComponent<'a> {
 
             ui.label("");
             ui.add(
-                DragValue::new(&mut new_scale.0[0])
+                number_field(&mut new_scale.0[0])
                     .clamp_range(0_f32..=std::f32::INFINITY)
                     .speed(0.01),
             );
             ui.add(
-                DragValue::new(&mut new_scale.0[1])
+                number_field(&mut new_scale.0[1])
                     .clamp_range(0_f32..=std::f32::INFINITY)
                     .speed(0.01),
             );
             ui.add(
-                DragValue::new(&mut new_scale.0[2])
+                number_field(&mut new_scale.0[2])
                     .clamp_range(0_f32..=std::f32::INFINITY)
                     .speed(0.01),
             );
@@ -0,0 +1,93 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{Category, Change, EntityTags},
+    widgets::{prelude::*, Inspect},
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{Button, Key, Ui};
+
+#[derive(SystemParam)]
+pub struct InspectTags<'w, 's> {
+    elements: Query<'w, 's, Option<&'static EntityTags>, With<Category>>,
+    change_tags: EventWriter<'w, Change<EntityTags>>,
+    /// Text of the tag that is being typed in
+    new_tag: Local<'s, String>,
+}
+
+impl<'w, 's> WidgetSystem<Inspect> for InspectTags<'w, 's> {
+    fn show(
+        Inspect { selection, .. }: Inspect,
+        ui: &mut Ui,
+        state: &mut SystemState<Self>,
+        world: &mut World,
+    ) {
+        let mut params = state.get_mut(world);
+        params.show_widget(selection, ui);
+    }
+}
+
+impl<'w, 's> InspectTags<'w, 's> {
+    pub fn show_widget(&mut self, id: Entity, ui: &mut Ui) {
+        let Ok(tags) = self.elements.get(id) else {
+            return;
+        };
+        let old = tags.cloned().unwrap_or_default();
+        let mut new = old.clone();
+
+        ui.label("Tags");
+        if !new.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                let mut remove = None;
+                for tag in new.iter() {
+                    if ui
+                        .add(Button::new(format!("{tag} ✖")).small())
+                        .on_hover_text("Remove this tag")
+                        .clicked()
+                    {
+                        remove = Some(tag.clone());
+                    }
+                }
+                if let Some(tag) = remove {
+                    new.0.remove(&tag);
+                }
+            });
+        }
+        ui.horizontal(|ui| {
+            let response = ui.text_edit_singleline(&mut *self.new_tag);
+            let entered = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            let tag = EntityTags::parse_tag(&self.new_tag);
+            if ui
+                .add_enabled(tag.is_some(), Button::new("Add"))
+                .on_hover_text("Tags can be searched for and used to select many elements")
+                .clicked()
+                || entered
+            {
+                if let Some(tag) = tag {
+                    new.0.insert(tag);
+                }
+                self.new_tag.clear();
+            }
+        });
+
+        if new != old {
+            self.change_tags.send(Change::new(new, id).or_insert());
+        }
+        ui.add_space(10.0);
+    }
+}
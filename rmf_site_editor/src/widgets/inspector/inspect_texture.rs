@@ -17,7 +17,7 @@
 
 use crate::{
     inspector::{InspectAssetSourceComponent, InspectValue, SearchResult},
-    site::{Category, Change, DefaultFile},
+    site::{Category, Change, DefaultFile, MaterialLibraries},
     widgets::{prelude::*, Inspect, InspectionPlugin},
     Icons, WorkspaceMarker,
 };
@@ -50,6 +50,7 @@ pub struct InspectTextureAffiliation<'w, 's> {
     parents: Query<'w, 's, &'static Parent>,
     sites: Query<'w, 's, &'static Children, With<WorkspaceMarker>>,
     icons: Res<'w, Icons>,
+    material_libraries: Res<'w, MaterialLibraries>,
     search_for_texture: ResMut<'w, SearchForTexture>,
     commands: Commands<'w, 's>,
     change_affiliation: EventWriter<'w, Change<Affiliation<Entity>>>,
@@ -254,6 +255,53 @@ impl<'w, 's> InspectTextureAffiliation<'w, 's> {
             }
         });
 
+        // Materials of the library are used through the texture group of
+        // the site that has their name, which is made if it does not exist.
+        let mut chosen_material = None;
+        if !self.material_libraries.materials.is_empty() {
+            ui.horizontal(|ui| {
+                ui.label("Library");
+                ComboBox::from_id_source("texture_material_library")
+                    .selected_text("Choose a material...")
+                    .show_ui(ui, |ui| {
+                        for available in &self.material_libraries.materials {
+                            let material = &available.material;
+                            if ui
+                                .selectable_label(false, &material.name)
+                                .on_hover_text(format!(
+                                    "{} ({} m per tile)",
+                                    material.texture, material.scale
+                                ))
+                                .clicked()
+                            {
+                                chosen_material = Some(available);
+                            }
+                        }
+                    });
+            });
+        }
+
+        if let Some(chosen) = chosen_material {
+            let existing = children.iter().find(|child| {
+                self.texture_groups
+                    .get(**child)
+                    .is_ok_and(|(name, _)| name.0 == chosen.material.name)
+            });
+            let group = match existing {
+                Some(existing) => *existing,
+                None => self
+                    .commands
+                    .spawn(
+                        chosen
+                            .material
+                            .to_texture_group(chosen.manifest_folder.as_deref()),
+                    )
+                    .set_parent(site)
+                    .id(),
+            };
+            new_affiliation = Affiliation(Some(group));
+        }
+
         if new_affiliation != *affiliation {
             self.change_affiliation
                 .send(Change::new(new_affiliation, id));
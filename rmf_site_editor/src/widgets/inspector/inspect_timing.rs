@@ -0,0 +1,113 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{Change, DoorTiming, LiftTiming},
+    widgets::{inspector::InspectOptionF32, prelude::*, Inspect},
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{CollapsingHeader, Ui};
+
+#[derive(SystemParam)]
+pub struct InspectTiming<'w, 's> {
+    door_timings: Query<'w, 's, &'static DoorTiming>,
+    change_door_timing: EventWriter<'w, Change<DoorTiming>>,
+    lift_timings: Query<'w, 's, &'static LiftTiming>,
+    change_lift_timing: EventWriter<'w, Change<LiftTiming>>,
+}
+
+impl<'w, 's> WidgetSystem<Inspect> for InspectTiming<'w, 's> {
+    fn show(
+        Inspect { selection, .. }: Inspect,
+        ui: &mut Ui,
+        state: &mut SystemState<Self>,
+        world: &mut World,
+    ) {
+        let mut params = state.get_mut(world);
+        if let Ok(timing) = params.door_timings.get(selection) {
+            CollapsingHeader::new("Timing")
+                .default_open(!timing.is_default())
+                .show(ui, |ui| {
+                    if let Some(new_timing) = show_door_timing(ui, timing) {
+                        params
+                            .change_door_timing
+                            .send(Change::new(new_timing, selection));
+                    }
+                });
+        } else if let Ok(timing) = params.lift_timings.get(selection) {
+            CollapsingHeader::new("Timing")
+                .default_open(!timing.is_default())
+                .show(ui, |ui| {
+                    let mut new_timing = *timing;
+                    if let Some(speed) = InspectOptionF32::new("Travel speed", timing.speed, 2.0)
+                        .clamp_range(0.01..=20.0)
+                        .speed(0.05)
+                        .suffix(" m/s")
+                        .tooltip("Top speed of the cabin")
+                        .show(ui)
+                    {
+                        new_timing.speed = speed;
+                    }
+                    ui.label("Doors");
+                    if let Some(doors) = show_door_timing(ui, &timing.doors) {
+                        new_timing.doors = doors;
+                    }
+                    if new_timing != *timing {
+                        params
+                            .change_lift_timing
+                            .send(Change::new(new_timing, selection));
+                    }
+                });
+        }
+    }
+}
+
+fn show_door_timing(ui: &mut Ui, timing: &DoorTiming) -> Option<DoorTiming> {
+    let mut new_timing = *timing;
+    for (title, value, assumed, tooltip) in [
+        (
+            "Open duration",
+            &mut new_timing.open_duration,
+            3.0,
+            "How long the door takes to open",
+        ),
+        (
+            "Close duration",
+            &mut new_timing.close_duration,
+            3.0,
+            "How long the door takes to close",
+        ),
+        (
+            "Dwell time",
+            &mut new_timing.dwell,
+            5.0,
+            "How long the door stays open before it starts to close",
+        ),
+    ] {
+        if let Some(new_value) = InspectOptionF32::new(title, *value, assumed)
+            .clamp_range(0.0..=600.0)
+            .speed(0.1)
+            .suffix(" s")
+            .tooltip(tooltip)
+            .show(ui)
+        {
+            *value = new_value;
+        }
+    }
+
+    (new_timing != *timing).then_some(new_timing)
+}
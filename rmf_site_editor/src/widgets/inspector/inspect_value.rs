@@ -15,8 +15,12 @@
  *
 */
 
+use crate::{
+    settings::LengthUnit,
+    widgets::inspector::{in_length_unit, number_field},
+};
 use bevy_egui::egui::emath::Numeric;
-use bevy_egui::egui::{DragValue, Ui};
+use bevy_egui::egui::Ui;
 use std::ops::RangeInclusive;
 
 pub struct InspectValue<'a, T> {
@@ -28,6 +32,7 @@ pub struct InspectValue<'a, T> {
     speed: f64,
     suffix: &'a str,
     tooltip: Option<&'a str>,
+    unit: Option<LengthUnit>,
 }
 
 impl<'a, T: Numeric> InspectValue<'a, T> {
@@ -41,6 +46,7 @@ impl<'a, T: Numeric> InspectValue<'a, T> {
             speed: 1.0,
             suffix: Default::default(),
             tooltip: Default::default(),
+            unit: None,
         }
     }
 
@@ -78,18 +84,27 @@ impl<'a, T: Numeric> InspectValue<'a, T> {
         self
     }
 
+    /// The value is a length in meters that should be shown in `unit`. This
+    /// takes the place of a suffix.
+    pub fn length_unit(mut self, unit: LengthUnit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
     pub fn show(self, ui: &mut Ui) -> Option<T> {
         ui.horizontal(|ui| {
             let mut new_value = self.current_value;
             ui.label(self.title);
-            let response = ui.add(
-                DragValue::new(&mut new_value)
-                    .clamp_range(self.range)
-                    .min_decimals(self.min_decimals)
-                    .max_decimals_opt(self.max_decimals)
-                    .speed(self.speed)
-                    .suffix(self.suffix),
-            );
+            let mut field = number_field(&mut new_value)
+                .clamp_range(self.range)
+                .min_decimals(self.min_decimals)
+                .max_decimals_opt(self.max_decimals)
+                .speed(self.speed)
+                .suffix(self.suffix);
+            if let Some(unit) = self.unit {
+                field = in_length_unit(field, unit);
+            }
+            let response = ui.add(field);
 
             if let Some(tooltip) = self.tooltip {
                 response.on_hover_text(tooltip);
@@ -0,0 +1,72 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    settings::EditorSettings,
+    site::{Change, WallAlpha, WallHeight, WallMarker},
+    widgets::{prelude::*, Inspect, InspectValue},
+};
+use bevy::prelude::*;
+
+#[derive(SystemParam)]
+pub struct InspectWall<'w, 's> {
+    walls: Query<'w, 's, (&'static WallHeight, &'static WallAlpha), With<WallMarker>>,
+    change_height: EventWriter<'w, Change<WallHeight>>,
+    change_alpha: EventWriter<'w, Change<WallAlpha>>,
+    settings: Res<'w, EditorSettings>,
+}
+
+impl<'w, 's> WidgetSystem<Inspect> for InspectWall<'w, 's> {
+    fn show(
+        Inspect { selection, .. }: Inspect,
+        ui: &mut Ui,
+        state: &mut SystemState<Self>,
+        world: &mut World,
+    ) {
+        let mut params = state.get_mut(world);
+        let Ok((height, alpha)) = params.walls.get(selection) else {
+            return;
+        };
+
+        if let Some(new_height) = InspectValue::<f32>::new("Height", height.0)
+            .clamp_range(0.01..=f32::INFINITY)
+            .speed(0.01)
+            .length_unit(params.settings.units)
+            .tooltip("Height of the wall above the floor of its level")
+            .show(ui)
+        {
+            params
+                .change_height
+                .send(Change::new(WallHeight(new_height), selection));
+        }
+
+        if let Some(new_alpha) = InspectValue::<f32>::new("Alpha", alpha.0)
+            .clamp_range(0.0..=1.0)
+            .speed(0.01)
+            .tooltip(
+                "Transparency of this wall (0 = transparent, 1 = opaque). \
+                This is combined with the alpha of the wall's texture.",
+            )
+            .show(ui)
+        {
+            params
+                .change_alpha
+                .send(Change::new(WallAlpha(new_alpha), selection));
+        }
+        ui.add_space(10.0);
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{Change, ZoneKind, ZoneMarker},
+    widgets::{prelude::*, Inspect, InspectValue},
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{ComboBox, Ui};
+
+#[derive(SystemParam)]
+pub struct InspectZone<'w, 's> {
+    zones: Query<'w, 's, &'static ZoneKind, With<ZoneMarker>>,
+    change_kind: EventWriter<'w, Change<ZoneKind>>,
+}
+
+impl<'w, 's> WidgetSystem<Inspect> for InspectZone<'w, 's> {
+    fn show(
+        Inspect { selection, .. }: Inspect,
+        ui: &mut Ui,
+        state: &mut SystemState<Self>,
+        world: &mut World,
+    ) {
+        let mut params = state.get_mut(world);
+        let Ok(kind) = params.zones.get(selection) else {
+            return;
+        };
+
+        let mut new_kind = *kind;
+        ui.horizontal(|ui| {
+            ui.label("Zone Kind:");
+            ComboBox::from_id_source("Zone Kind")
+                .selected_text(new_kind.label())
+                .show_ui(ui, |ui| {
+                    for variant in ZoneKind::ALL {
+                        if ui
+                            .selectable_label(variant.is_same_kind(&new_kind), variant.label())
+                            .clicked()
+                            && !variant.is_same_kind(&new_kind)
+                        {
+                            new_kind = variant;
+                        }
+                    }
+                });
+        });
+
+        if let ZoneKind::SpeedLimited { max_speed } = &mut new_kind {
+            if let Some(new_speed) = InspectValue::<f32>::new("Max Speed", *max_speed)
+                .clamp_range(0.01..=f32::INFINITY)
+                .speed(0.01)
+                .suffix(" m/s")
+                .tooltip("Fastest that robots may drive while inside of this zone")
+                .show(ui)
+            {
+                *max_speed = new_speed;
+            }
+        }
+
+        if new_kind != *kind {
+            params.change_kind.send(Change::new(new_kind, selection));
+        }
+        ui.add_space(10.0);
+    }
+}
@@ -27,6 +27,9 @@ pub use inspect_angle::*;
 pub mod inspect_asset_source;
 pub use inspect_asset_source::*;
 
+pub mod inspect_custom_fields;
+pub use inspect_custom_fields::*;
+
 pub mod inspect_default_tasks;
 pub use inspect_default_tasks::*;
 
@@ -39,6 +42,9 @@ pub use inspect_drawing::*;
 pub mod inspect_edge;
 pub use inspect_edge::*;
 
+pub mod inspect_evacuation;
+pub use inspect_evacuation::*;
+
 pub mod inspect_fiducial;
 pub use inspect_fiducial::*;
 
@@ -48,15 +54,24 @@ pub use inspect_geography::*;
 pub mod inspect_group;
 pub use inspect_group::*;
 
+pub mod inspect_human_lane;
+pub use inspect_human_lane::*;
+
 pub mod inspect_is_static;
 pub use inspect_is_static::*;
 
 pub mod inspect_option_string;
 pub use inspect_option_string::*;
 
+pub mod inspect_lane_curve;
+pub use inspect_lane_curve::*;
+
 pub mod inspect_layer;
 pub use inspect_layer::*;
 
+pub mod inspect_level_extension;
+pub use inspect_level_extension::*;
+
 pub mod inspect_lift;
 pub use inspect_lift::*;
 
@@ -102,15 +117,30 @@ pub use inspect_scale::*;
 pub mod inspect_side;
 pub use inspect_side::*;
 
+pub mod inspect_tags;
+pub use inspect_tags::*;
+
 pub mod inspect_task;
 pub use inspect_task::*;
 
+pub mod inspect_timing;
+pub use inspect_timing::*;
+
 pub mod inspect_texture;
 pub use inspect_texture::*;
 
 pub mod inspect_value;
 pub use inspect_value::*;
 
+pub mod inspect_wall;
+pub use inspect_wall::*;
+
+pub mod inspect_zone;
+pub use inspect_zone::*;
+
+pub mod number_field;
+pub use number_field::*;
+
 use crate::{
     interaction::Selection,
     site::{Category, SiteID},
@@ -197,9 +227,13 @@ impl Plugin for StandardInspectorPlugin {
                 // Reached the tuple limit
             ))
             .add_plugins((
+                InspectionPlugin::<InspectHumanLane>::new(),
                 InspectionPlugin::<InspectScale>::new(),
                 InspectionPlugin::<InspectLight>::new(),
                 InspectionPlugin::<InspectDoor>::new(),
+                InspectionPlugin::<InspectTiming>::new(),
+                InspectionPlugin::<InspectEvacuation>::new(),
+                InspectionPlugin::<InspectCustomFields>::new(),
                 InspectionPlugin::<InspectPrimitiveShape>::new(),
                 InspectionPlugin::<InspectMeasurement>::new(),
                 InspectionPlugin::<InspectPhysicalCameraProperties>::new(),
@@ -207,10 +241,17 @@ impl Plugin for StandardInspectorPlugin {
                 InspectionPlugin::<InspectGroup>::new(),
                 InspectModelDescriptionPlugin::default(),
                 InspectLiftPlugin::default(),
+                InspectionPlugin::<InspectWall>::new(),
             ))
             .add_plugins((
                 // Required model properties
                 InspectModelPropertyPlugin::<InspectModelScale, Scale>::new("Scale".to_string()),
+                InspectModelPropertyPlugin::<InspectModelIsStatic, IsStatic>::new(
+                    "Is Static".to_string(),
+                ),
+                InspectModelPropertyPlugin::<InspectModelZOffset, ZOffset>::new(
+                    "Z Offset".to_string(),
+                ),
                 InspectModelPropertyPlugin::<InspectModelAssetSource, AssetSource>::new(
                     "Asset Source".to_string(),
                 ),
@@ -231,6 +272,9 @@ impl Plugin for StandardInspectorPlugin {
                 >::new(),
                 InspectTaskPlugin::default(),
                 InspectDefaultTasksPlugin::default(),
+                InspectionPlugin::<InspectZone>::new(),
+                InspectionPlugin::<InspectLaneCurve>::new(),
+                InspectionPlugin::<InspectTags>::new(),
             ));
     }
 }
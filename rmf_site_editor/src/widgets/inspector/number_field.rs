@@ -0,0 +1,202 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::settings::LengthUnit;
+use bevy_egui::egui::{emath, emath::Numeric, DragValue};
+
+/// Create a [`DragValue`] whose text can be typed with either `.` or `,` as
+/// the decimal separator, and which evaluates simple arithmetic such as
+/// `3.2+0.45` or `12*0.3` when the edit is committed.
+pub fn number_field<Num: Numeric>(value: &mut Num) -> DragValue<'_> {
+    DragValue::new(value).custom_parser(parse_number)
+}
+
+/// Create a [`number_field`] for a length that is stored in meters but shown
+/// and typed in `unit`.
+pub fn length_field<Num: Numeric>(value: &mut Num, unit: LengthUnit) -> DragValue<'_> {
+    in_length_unit(DragValue::new(value), unit)
+}
+
+/// Make a [`DragValue`] of a length in meters show and accept `unit`
+/// instead. Any suffix set before this is replaced.
+pub fn in_length_unit(drag: DragValue<'_>, unit: LengthUnit) -> DragValue<'_> {
+    let scale = unit.per_meter();
+    drag.custom_formatter(move |value, decimals| {
+        emath::format_with_decimals_in_range(value * scale, decimals)
+    })
+    .custom_parser(move |text| parse_number(text).map(|value| value / scale))
+    .suffix(unit.suffix())
+}
+
+/// Parse a number or an arithmetic expression made of `+`, `-`, `*`, `/`,
+/// and parentheses.
+///
+/// Either `.` or `,` can be used as the decimal separator, but not both in
+/// the same text, since it would be unclear whether the other one is meant to
+/// group digits. Text that mixes them is rejected.
+pub fn parse_number(text: &str) -> Option<f64> {
+    if text.contains('.') && text.contains(',') {
+        return None;
+    }
+    let text = text.replace(',', ".");
+    let tokens: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if tokens.is_empty() {
+        return None;
+    }
+
+    let mut parser = ExpressionParser { tokens, cursor: 0 };
+    let value = parser.sum()?;
+    if parser.cursor != parser.tokens.len() || !value.is_finite() {
+        return None;
+    }
+    Some(value)
+}
+
+struct ExpressionParser {
+    tokens: Vec<char>,
+    cursor: usize,
+}
+
+impl ExpressionParser {
+    fn peek(&self) -> Option<char> {
+        self.tokens.get(self.cursor).copied()
+    }
+
+    fn sum(&mut self) -> Option<f64> {
+        let mut value = self.product()?;
+        while let Some(op) = self.peek().filter(|c| matches!(c, '+' | '-')) {
+            self.cursor += 1;
+            let rhs = self.product()?;
+            if op == '+' {
+                value += rhs;
+            } else {
+                value -= rhs;
+            }
+        }
+        Some(value)
+    }
+
+    fn product(&mut self) -> Option<f64> {
+        let mut value = self.factor()?;
+        while let Some(op) = self.peek().filter(|c| matches!(c, '*' | '/')) {
+            self.cursor += 1;
+            let rhs = self.factor()?;
+            if op == '*' {
+                value *= rhs;
+            } else {
+                value /= rhs;
+            }
+        }
+        Some(value)
+    }
+
+    fn factor(&mut self) -> Option<f64> {
+        match self.peek()? {
+            '-' => {
+                self.cursor += 1;
+                Some(-self.factor()?)
+            }
+            '+' => {
+                self.cursor += 1;
+                self.factor()
+            }
+            '(' => {
+                self.cursor += 1;
+                let value = self.sum()?;
+                if self.peek()? != ')' {
+                    return None;
+                }
+                self.cursor += 1;
+                Some(value)
+            }
+            _ => self.literal(),
+        }
+    }
+
+    fn literal(&mut self) -> Option<f64> {
+        let start = self.cursor;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+            self.cursor += 1;
+        }
+        // Allow scientific notation such as 1.5e-3
+        if self.cursor > start && matches!(self.peek(), Some('e' | 'E')) {
+            let exponent = self.cursor;
+            self.cursor += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.cursor += 1;
+            }
+            let digits = self.cursor;
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.cursor += 1;
+            }
+            if self.cursor == digits {
+                self.cursor = exponent;
+            }
+        }
+        if self.cursor == start {
+            return None;
+        }
+        let literal: String = self.tokens[start..self.cursor].iter().collect();
+        literal.parse().ok()
+    }
+}
+
+#[test]
+fn test_parse_number_precedence() {
+    assert_eq!(parse_number("1+2*3"), Some(7.0));
+    assert_eq!(parse_number("(1+2)*3"), Some(9.0));
+    assert_eq!(parse_number("8/4/2"), Some(1.0));
+    assert_eq!(parse_number("10-4-3"), Some(3.0));
+    assert_eq!(parse_number(" 12 * 0.5 "), Some(6.0));
+}
+
+#[test]
+fn test_parse_number_unary_minus() {
+    assert_eq!(parse_number("-2"), Some(-2.0));
+    assert_eq!(parse_number("-(1+2)"), Some(-3.0));
+    assert_eq!(parse_number("3*-2"), Some(-6.0));
+    assert_eq!(parse_number("--2"), Some(2.0));
+    assert_eq!(parse_number("1.5e-3"), Some(0.0015));
+}
+
+#[test]
+fn test_parse_number_division_by_zero() {
+    assert_eq!(parse_number("1/0"), None);
+    assert_eq!(parse_number("0/0"), None);
+    assert_eq!(parse_number("1/(2-2)"), None);
+}
+
+#[test]
+fn test_parse_number_decimal_separators() {
+    assert_eq!(parse_number("3.2+0.45"), Some(3.2 + 0.45));
+    assert_eq!(parse_number("3,2+0,45"), Some(3.2 + 0.45));
+    assert_eq!(parse_number("1,5"), Some(1.5));
+    assert_eq!(parse_number("3,2+0.45"), None);
+    assert_eq!(parse_number("1,000.5"), None);
+}
+
+#[test]
+fn test_parse_number_rejects_garbage() {
+    assert_eq!(parse_number(""), None);
+    assert_eq!(parse_number("   "), None);
+    assert_eq!(parse_number("abc"), None);
+    assert_eq!(parse_number("1+"), None);
+    assert_eq!(parse_number("(1+2"), None);
+    assert_eq!(parse_number("1+2)"), None);
+    assert_eq!(parse_number("1..2"), None);
+    assert_eq!(parse_number("2m"), None);
+}
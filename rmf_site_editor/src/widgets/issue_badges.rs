@@ -0,0 +1,153 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{CameraControls, InteractionState, Select},
+    site::{Edge, FilteredIssueKinds, FilteredIssues, Point},
+    widgets::{UserCameraDisplay, UserCameraDisplaySet},
+    CurrentWorkspace, Issue,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_egui::{egui, EguiContexts};
+use std::collections::BTreeMap;
+
+/// Add warning badges to the viewport that mark each entity with an active
+/// issue. Hovering over a badge lists the issues and clicking it selects the
+/// entity.
+#[derive(Default)]
+pub struct IssueBadgesPlugin {}
+
+impl Plugin for IssueBadgesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<IssueBadgesDisplay>().add_systems(
+            Update,
+            draw_issue_badges
+                .after(UserCameraDisplaySet)
+                .run_if(in_state(InteractionState::Enable)),
+        );
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct IssueBadgesDisplay {
+    pub show: bool,
+}
+
+impl Default for IssueBadgesDisplay {
+    fn default() -> Self {
+        Self { show: true }
+    }
+}
+
+#[derive(SystemParam)]
+struct BadgePlacement<'w, 's> {
+    camera_controls: Res<'w, CameraControls>,
+    cameras: Query<'w, 's, (&'static Camera, &'static GlobalTransform)>,
+    global_tfs: Query<'w, 's, &'static GlobalTransform>,
+    visibility: Query<'w, 's, &'static InheritedVisibility>,
+    edges: Query<'w, 's, &'static Edge<Entity>>,
+    points: Query<'w, 's, &'static Point<Entity>>,
+    user_camera_display: Res<'w, UserCameraDisplay>,
+}
+
+impl<'w, 's> BadgePlacement<'w, 's> {
+    /// Where the badge of an entity belongs in the viewport, if the entity can
+    /// currently be seen.
+    fn screen_position(&self, entity: Entity) -> Option<egui::Pos2> {
+        if !self.visibility.get(entity).ok()?.get() {
+            return None;
+        }
+        let p = if let Ok(edge) = self.edges.get(entity) {
+            let [a, b] = edge.array().map(|e| self.global_tfs.get(e).ok());
+            (a?.translation() + b?.translation()) / 2.0
+        } else if let Ok(point) = self.points.get(entity) {
+            self.global_tfs.get(point.0).ok()?.translation()
+        } else {
+            self.global_tfs.get(entity).ok()?.translation()
+        };
+        let (camera, camera_tf) = self
+            .cameras
+            .get(self.camera_controls.active_camera())
+            .ok()?;
+        let p = camera.world_to_viewport(camera_tf, p)?;
+        if !self.user_camera_display.region.contains(p) {
+            return None;
+        }
+        Some(egui::pos2(p.x, p.y))
+    }
+}
+
+fn draw_issue_badges(
+    display: Res<IssueBadgesDisplay>,
+    current_workspace: Res<CurrentWorkspace>,
+    issues: Query<(&Issue, &Parent)>,
+    filters: Query<(&FilteredIssues<Entity>, &FilteredIssueKinds)>,
+    placement: BadgePlacement,
+    mut egui_context: EguiContexts,
+    mut select: EventWriter<Select>,
+) {
+    if !display.show {
+        return;
+    }
+    let Some(root) = current_workspace.root else {
+        return;
+    };
+    let Ok((filtered_issues, filtered_issue_kinds)) = filters.get(root) else {
+        return;
+    };
+
+    let mut badges: BTreeMap<Entity, Vec<&str>> = BTreeMap::new();
+    for (issue, parent) in &issues {
+        if **parent != root
+            || filtered_issue_kinds.contains(&issue.key.kind)
+            || filtered_issues.contains(&issue.key)
+        {
+            continue;
+        }
+        for e in &issue.key.entities {
+            badges.entry(*e).or_default().push(&issue.brief);
+        }
+    }
+
+    let ctx = egui_context.ctx_mut();
+    for (entity, briefs) in badges {
+        let Some(pos) = placement.screen_position(entity) else {
+            continue;
+        };
+        let text = if briefs.len() > 1 {
+            format!("⚠ {}", briefs.len())
+        } else {
+            "⚠".to_owned()
+        };
+        egui::Area::new(egui::Id::new(("issue_badge", entity)))
+            .order(egui::Order::Background)
+            .pivot(egui::Align2::CENTER_BOTTOM)
+            .fixed_pos(pos)
+            .show(ctx, |ui| {
+                let badge = egui::Label::new(
+                    egui::RichText::new(text)
+                        .strong()
+                        .color(egui::Color32::from_rgb(255, 170, 0))
+                        .background_color(egui::Color32::from_black_alpha(160)),
+                )
+                .sense(egui::Sense::click());
+                if ui.add(badge).on_hover_text(briefs.join("\n")).clicked() {
+                    select.send(Select::new(Some(entity)));
+                }
+            });
+    }
+}
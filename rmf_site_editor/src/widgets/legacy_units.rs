@@ -0,0 +1,103 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::InteractionState,
+    site::LoadSite,
+    workspace::{load_legacy_building, PendingLegacyBuilding},
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use rmf_site_format::legacy::building_map::CoordinateSystem;
+
+/// Add a window that asks the user to confirm whether a legacy building map
+/// uses pixels or meters when the units it declares do not match what its
+/// contents suggest.
+#[derive(Default)]
+pub struct LegacyUnitsPlugin {}
+
+impl Plugin for LegacyUnitsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingLegacyBuilding>()
+            .add_systems(Update, confirm_legacy_units);
+    }
+}
+
+fn confirm_legacy_units(
+    mut egui_context: EguiContexts,
+    mut pending_legacy: ResMut<PendingLegacyBuilding>,
+    mut app_state: ResMut<NextState<AppState>>,
+    mut interaction_state: ResMut<NextState<InteractionState>>,
+    mut load_site: EventWriter<LoadSite>,
+) {
+    let Some(import) = &mut pending_legacy.pending else {
+        return;
+    };
+
+    let mut confirm = false;
+    let mut cancel = false;
+    egui::Window::new("Confirm Units")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0., 0.))
+        .show(egui_context.ctx_mut(), |ui| {
+            match import.guess.declared {
+                Some(declared) => {
+                    ui.label(format!("The building map declares {}.", declared.label()));
+                }
+                None => {
+                    ui.label("The building map does not declare its units.");
+                }
+            }
+            ui.label(import.guess.reason());
+            ui.add_space(10.);
+            ui.label("Interpret the coordinates as:");
+            ui.horizontal(|ui| {
+                for coordinate_system in [
+                    CoordinateSystem::ReferenceImage,
+                    CoordinateSystem::CartesianMeters,
+                ] {
+                    let mut label = coordinate_system.label().to_owned();
+                    if coordinate_system == import.guess.detected {
+                        label += " (detected)";
+                    }
+                    ui.radio_value(&mut import.choice, coordinate_system, label);
+                }
+            });
+            ui.add_space(10.);
+            ui.horizontal(|ui| {
+                confirm = ui.button("Import").clicked();
+                cancel = ui.button("Cancel").clicked();
+            });
+        });
+
+    if confirm {
+        if let Some(import) = pending_legacy.pending.take() {
+            load_legacy_building(
+                &import.data,
+                Some(import.choice),
+                import.default_file,
+                &mut app_state,
+                &mut interaction_state,
+                &mut load_site,
+            );
+        }
+    } else if cancel {
+        pending_legacy.pending = None;
+    }
+}
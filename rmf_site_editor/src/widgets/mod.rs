@@ -68,9 +68,21 @@ use console::*;
 pub mod creation;
 use creation::*;
 
+pub mod context_menu;
+pub use context_menu::*;
+
+pub mod cross_section;
+use cross_section::*;
+
 pub mod canvas_tooltips;
 pub use canvas_tooltips::*;
 
+pub mod cleanup;
+use cleanup::*;
+
+pub mod connectivity_report;
+use connectivity_report::*;
+
 pub mod diagnostics;
 use diagnostics::*;
 
@@ -86,6 +98,21 @@ pub use icons::*;
 pub mod inspector;
 pub use inspector::*;
 
+pub mod issue_badges;
+pub use issue_badges::*;
+
+pub mod legacy_units;
+use legacy_units::*;
+
+pub mod new_map_wizard;
+use new_map_wizard::*;
+
+pub mod external_changes;
+pub use external_changes::*;
+
+pub mod file_drop;
+use file_drop::*;
+
 pub mod menu_bar;
 pub use menu_bar::*;
 
@@ -101,21 +128,56 @@ pub use panel::*;
 pub mod properties_panel;
 pub use properties_panel::*;
 
+pub mod restore_backup;
+pub use restore_backup::*;
+
 pub mod sdf_export_menu;
 pub use sdf_export_menu::*;
 
+#[cfg(feature = "scripting")]
+pub mod script_console;
+#[cfg(feature = "scripting")]
+pub use script_console::*;
+
+pub mod settings_window;
+pub use settings_window::*;
+
 pub mod selector_widget;
 pub use selector_widget::*;
 
+pub mod status_bar;
+pub use status_bar::*;
+
+pub mod toolbar;
+pub use toolbar::*;
+
 pub mod user_camera_display;
 pub use user_camera_display::*;
 
+pub mod view_camera_bookmarks;
+use view_camera_bookmarks::*;
+
+pub mod view_crowd_sim;
+use view_crowd_sim::*;
+
+pub mod view_entity_groups;
+use view_entity_groups::*;
+
 pub mod view_groups;
 use view_groups::*;
 
+pub mod view_lane_density;
+use view_lane_density::*;
+
+pub mod view_lane_generator;
+use view_lane_generator::*;
+
 pub mod view_layers;
 use view_layers::*;
 
+pub mod view_layer_toggles;
+use view_layer_toggles::*;
+
 pub mod view_levels;
 use view_levels::*;
 
@@ -125,21 +187,72 @@ use view_model_instances::*;
 pub mod view_scenarios;
 use view_scenarios::*;
 
+pub mod view_search;
+use view_search::*;
+
 pub mod view_lights;
 use view_lights::*;
 
+pub mod view_map_stats;
+use view_map_stats::*;
+
+pub mod view_multi_selection;
+use view_multi_selection::*;
+
 pub mod view_nav_graphs;
 use view_nav_graphs::*;
 
 pub mod view_occupancy;
 use view_occupancy::*;
 
+pub mod view_custom_entities;
+use view_custom_entities::*;
+
+pub mod view_evacuation;
+use view_evacuation::*;
+
+pub mod view_export_options;
+use view_export_options::*;
+
+pub mod view_geographic_references;
+use view_geographic_references::*;
+
+pub mod view_perturbation;
+use view_perturbation::*;
+
+pub mod view_paper_space;
+use view_paper_space::*;
+
+pub mod view_path_preview;
+use view_path_preview::*;
+
+pub mod view_point_alignment;
+use view_point_alignment::*;
+
+pub mod view_references;
+use view_references::*;
+
+pub mod view_spawn_points;
+use view_spawn_points::*;
+
 pub mod view_tasks;
 use view_tasks::*;
 
+pub mod view_templates;
+use view_templates::*;
+
+pub mod view_traffic_preview;
+use view_traffic_preview::*;
+
 pub mod workspace;
 use workspace::*;
 
+pub mod workspace_tabs;
+pub use workspace_tabs::*;
+
+pub mod yaml_source;
+use yaml_source::*;
+
 pub mod prelude {
     //! This module gives easy access to the traits, structs, and plugins that
     //! we expect downstream users are likely to want easy access to if they are
@@ -172,12 +285,33 @@ impl Plugin for StandardUiPlugin {
                 StandardPropertiesPanelPlugin::default(),
                 FuelAssetBrowserPlugin,
                 DiagnosticsPlugin::default(),
-                ConsoleWidgetPlugin::default(),
+                CleanupPlugin::default(),
+                YamlSourceViewPlugin::default(),
+                ConnectivityReportPlugin::default(),
+                CrossSectionViewPlugin::default(),
                 WorkspaceMenuPlugin::default(),
+                WorkspaceTabsPlugin::default(),
                 UserCameraDisplayPlugin::default(),
+                SettingsWindowPlugin::default(),
                 #[cfg(not(target_arch = "wasm32"))]
                 SdfExportMenuPlugin::default(),
             ))
+            .add_plugins((
+                // The status bar goes below the console
+                StatusBarPlugin::default(),
+                ConsoleWidgetPlugin::default(),
+                LegacyUnitsPlugin::default(),
+                IssueBadgesPlugin::default(),
+                NewMapWizardPlugin::default(),
+                #[cfg(feature = "scripting")]
+                ScriptConsolePlugin::default(),
+                FileDropPlugin::default(),
+                ContextMenuPlugin::default(),
+                #[cfg(not(target_arch = "wasm32"))]
+                RestoreBackupPlugin::default(),
+                #[cfg(not(target_arch = "wasm32"))]
+                ExternalChangesPlugin::default(),
+            ))
             .add_systems(Startup, init_ui_style)
             .add_systems(
                 Update,
@@ -190,6 +324,8 @@ impl Plugin for StandardUiPlugin {
                 (
                     resolve_light_export_file,
                     resolve_nav_graph_import_export_files,
+                    resolve_reference_site_file,
+                    resolve_plan_export_file,
                 )
                     .run_if(AppState::in_displaying_mode()),
             );
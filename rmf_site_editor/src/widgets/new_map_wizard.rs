@@ -0,0 +1,273 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{AssetSource, LoadSite, NewMap, NewMapFloorplan},
+    widgets::{FileMenu, MenuEvent, MenuItem},
+    WorkspaceData, WorkspaceLoader,
+};
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use bevy_egui::{
+    egui::{self, DragValue, Grid, TextEdit},
+    EguiContexts,
+};
+use futures_lite::future;
+use rfd::AsyncFileDialog;
+use std::path::PathBuf;
+
+/// Add a window that walks the user through starting a new site: naming it
+/// and its first level, and optionally choosing a floorplan image and
+/// calibrating its scale.
+#[derive(Default)]
+pub struct NewMapWizardPlugin {}
+
+impl Plugin for NewMapWizardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NewMapWizard>()
+            .init_resource::<NewMapWizardMenu>()
+            .add_systems(
+                Update,
+                (
+                    handle_new_map_wizard_menu,
+                    resolve_floorplan_file,
+                    show_new_map_wizard,
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Resource)]
+pub struct NewMapWizard {
+    pub show: bool,
+    pub name: String,
+    pub first_level: String,
+    pub floorplan: Option<PathBuf>,
+    /// A distance measured on the floorplan, in pixels
+    pub calibration_pixels: f32,
+    /// The real length of the measured distance, in meters
+    pub calibration_meters: f32,
+    choosing_floorplan: Option<Task<Option<PathBuf>>>,
+}
+
+impl Default for NewMapWizard {
+    fn default() -> Self {
+        Self {
+            show: false,
+            name: "new".to_owned(),
+            first_level: "L1".to_owned(),
+            floorplan: None,
+            calibration_pixels: 100.0,
+            calibration_meters: 1.0,
+            choosing_floorplan: None,
+        }
+    }
+}
+
+impl NewMapWizard {
+    /// Open the wizard with its fields reset
+    pub fn open(&mut self) {
+        *self = Self {
+            show: true,
+            ..Default::default()
+        };
+    }
+
+    fn new_map(&self) -> Option<NewMap> {
+        let floorplan = match &self.floorplan {
+            Some(path) => Some(NewMapFloorplan::from_calibration(
+                AssetSource::Local(path.to_string_lossy().into_owned()),
+                self.calibration_pixels,
+                self.calibration_meters,
+            )?),
+            None => None,
+        };
+        Some(NewMap {
+            name: self.name.clone(),
+            first_level: self.first_level.clone(),
+            floorplan,
+        })
+    }
+}
+
+#[derive(Resource)]
+pub struct NewMapWizardMenu {
+    new_map: Entity,
+}
+
+impl FromWorld for NewMapWizardMenu {
+    fn from_world(world: &mut World) -> Self {
+        let file_menu = world.resource::<FileMenu>().get();
+        let new_map = world
+            .spawn(MenuItem::Text("New Map Wizard...".into()))
+            .set_parent(file_menu)
+            .id();
+        Self { new_map }
+    }
+}
+
+fn handle_new_map_wizard_menu(
+    mut menu_events: EventReader<MenuEvent>,
+    menu: Res<NewMapWizardMenu>,
+    mut wizard: ResMut<NewMapWizard>,
+) {
+    for event in menu_events.read() {
+        if event.clicked() && event.source() == menu.new_map {
+            wizard.open();
+        }
+    }
+}
+
+fn resolve_floorplan_file(mut wizard: ResMut<NewMapWizard>) {
+    let Some(task) = &mut wizard.choosing_floorplan else {
+        return;
+    };
+    let Some(result) = future::block_on(future::poll_once(task)) else {
+        return;
+    };
+    wizard.choosing_floorplan = None;
+    if let Some(path) = result {
+        wizard.floorplan = Some(path);
+    }
+}
+
+fn show_new_map_wizard(
+    mut egui_context: EguiContexts,
+    mut wizard: ResMut<NewMapWizard>,
+    mut workspace_loader: WorkspaceLoader,
+) {
+    if !wizard.show {
+        return;
+    }
+
+    let mut create = false;
+    let mut cancel = false;
+    egui::Window::new("New Map")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0., 0.))
+        .show(egui_context.ctx_mut(), |ui| {
+            Grid::new("new_map_wizard").num_columns(2).show(ui, |ui| {
+                ui.label("Site name");
+                ui.add(TextEdit::singleline(&mut wizard.name));
+                ui.end_row();
+
+                ui.label("First level");
+                ui.add(TextEdit::singleline(&mut wizard.first_level));
+                ui.end_row();
+
+                ui.label("Floorplan");
+                ui.horizontal(|ui| {
+                    match &wizard.floorplan {
+                        Some(path) => {
+                            let name = path
+                                .file_name()
+                                .map(|n| n.to_string_lossy().into_owned())
+                                .unwrap_or_default();
+                            ui.label(name).on_hover_text(path.display().to_string());
+                        }
+                        None => {
+                            ui.label("None");
+                        }
+                    }
+                    if cfg!(not(target_arch = "wasm32"))
+                        && ui
+                            .add_enabled(
+                                wizard.choosing_floorplan.is_none(),
+                                egui::Button::new("Browse..."),
+                            )
+                            .clicked()
+                    {
+                        wizard.choosing_floorplan =
+                            Some(AsyncComputeTaskPool::get().spawn(async move {
+                                let file = AsyncFileDialog::new()
+                                    .add_filter("Image", &["png", "jpg", "jpeg"])
+                                    .pick_file()
+                                    .await?;
+                                #[cfg(not(target_arch = "wasm32"))]
+                                let file = file.path().to_path_buf();
+                                #[cfg(target_arch = "wasm32")]
+                                let file = PathBuf::from(file.file_name());
+                                Some(file)
+                            }));
+                    }
+
+                    if wizard.floorplan.is_some() && ui.button("Clear").clicked() {
+                        wizard.floorplan = None;
+                    }
+                });
+                ui.end_row();
+            });
+
+            if wizard.floorplan.is_some() {
+                ui.add_space(10.);
+                ui.label("Scale calibration");
+                ui.label("Measure a distance on the floorplan whose real length you know.");
+                ui.horizontal(|ui| {
+                    ui.add(
+                        DragValue::new(&mut wizard.calibration_pixels)
+                            .clamp_range(0.0..=f32::INFINITY)
+                            .speed(1.0)
+                            .suffix(" px"),
+                    );
+                    ui.label("on the image is");
+                    ui.add(
+                        DragValue::new(&mut wizard.calibration_meters)
+                            .clamp_range(0.0..=f32::INFINITY)
+                            .speed(0.1)
+                            .suffix(" m"),
+                    );
+                });
+                if let Some(map) = wizard.new_map() {
+                    if let Some(floorplan) = map.floorplan {
+                        ui.label(format!(
+                            "{:.2} pixels per meter",
+                            floorplan.pixels_per_meter
+                        ));
+                    }
+                }
+            }
+
+            ui.add_space(10.);
+            let valid = wizard.new_map().is_some()
+                && !wizard.name.is_empty()
+                && !wizard.first_level.is_empty();
+            ui.horizontal(|ui| {
+                create = ui
+                    .add_enabled(valid, egui::Button::new("Create"))
+                    .on_disabled_hover_text("Enter names and a valid scale calibration")
+                    .clicked();
+                cancel = ui.button("Cancel").clicked();
+            });
+        });
+
+    if create {
+        if let Some(map) = wizard.new_map() {
+            workspace_loader.load_from_data(WorkspaceData::LoadSite(LoadSite {
+                site: map.to_site(),
+                focus: true,
+                default_file: None,
+            }));
+        }
+        wizard.show = false;
+    } else if cancel {
+        wizard.show = false;
+    }
+}
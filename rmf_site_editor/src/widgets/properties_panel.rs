@@ -17,9 +17,15 @@
 
 use crate::widgets::{
     show_panel_of_tiles, BuildingPreviewPlugin, PanelSide, PanelWidget, StandardInspectorPlugin,
-    Tile, ViewGroupsPlugin, ViewLayersPlugin, ViewLevelsPlugin, ViewLightsPlugin,
-    ViewModelInstancesPlugin, ViewNavGraphsPlugin, ViewOccupancyPlugin, ViewScenariosPlugin,
-    ViewTasks, Widget, WidgetSystem,
+    Tile, ViewCameraBookmarksPlugin, ViewCrowdSimPlugin, ViewCustomEntitiesPlugin,
+    ViewEntityGroupsPlugin, ViewEvacuationPlugin, ViewExportOptionsPlugin,
+    ViewGeographicReferencesPlugin, ViewGroupsPlugin, ViewLaneDensityPlugin,
+    ViewLaneGeneratorPlugin, ViewLayerTogglesPlugin, ViewLayersPlugin, ViewLevelsPlugin,
+    ViewLightsPlugin, ViewMapStatsPlugin, ViewModelInstancesPlugin, ViewMultiSelectionPlugin,
+    ViewNavGraphsPlugin, ViewOccupancyPlugin, ViewPaperSpacePlugin, ViewPathPreviewPlugin,
+    ViewPerturbationPlugin, ViewPointAlignmentPlugin, ViewReferencesPlugin, ViewScenariosPlugin,
+    ViewSearchPlugin, ViewSpawnPointsPlugin, ViewTasks, ViewTemplatesPlugin,
+    ViewTrafficPreviewPlugin, Widget, WidgetSystem,
 };
 use bevy::prelude::*;
 
@@ -38,12 +44,38 @@ impl Plugin for StandardPropertiesPanelPlugin {
             ViewModelInstancesPlugin::default(),
             ViewNavGraphsPlugin::default(),
             ViewLayersPlugin::default(),
+            ViewReferencesPlugin::default(),
+            ViewMultiSelectionPlugin::default(),
             StandardInspectorPlugin::default(),
             ViewGroupsPlugin::default(),
             PropertiesTilePlugin::<ViewTasks>::new(),
             ViewLightsPlugin::default(),
             ViewOccupancyPlugin::default(),
+            ViewPaperSpacePlugin::default(),
             BuildingPreviewPlugin::default(),
+            // Reached the tuple limit
+        ))
+        .add_plugins((
+            ViewTemplatesPlugin::default(),
+            ViewExportOptionsPlugin::default(),
+            ViewPerturbationPlugin::default(),
+            ViewLaneDensityPlugin::default(),
+            ViewCrowdSimPlugin::default(),
+            ViewEntityGroupsPlugin::default(),
+            ViewTrafficPreviewPlugin::default(),
+            ViewPathPreviewPlugin::default(),
+            ViewEvacuationPlugin::default(),
+            ViewGeographicReferencesPlugin::default(),
+            ViewCustomEntitiesPlugin::default(),
+            ViewCameraBookmarksPlugin::default(),
+            ViewSpawnPointsPlugin::default(),
+            ViewMapStatsPlugin::default(),
+            ViewSearchPlugin::default(),
+        ))
+        .add_plugins((
+            ViewLayerTogglesPlugin::default(),
+            ViewLaneGeneratorPlugin::default(),
+            ViewPointAlignmentPlugin::default(),
         ));
     }
 }
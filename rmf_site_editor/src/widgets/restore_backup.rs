@@ -0,0 +1,215 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    menu_bar::{FileMenu, MenuEvent, MenuItem, TextMenuItem},
+    site::{existing_backups, DefaultFile, LoadSite},
+    workspace::WorkspaceData,
+    AppState, CurrentWorkspace,
+};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Grid},
+    EguiContexts,
+};
+use rmf_site_format::Site;
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// Add a window that lists the backups that were made of the file of the
+/// current site and opens any of them as a new workspace.
+#[derive(Default)]
+pub struct RestoreBackupPlugin {}
+
+impl Plugin for RestoreBackupPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RestoreBackupMenu>()
+            .init_resource::<RestoreBackupWindow>()
+            .add_systems(
+                Update,
+                show_restore_backup_window.run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+/// Keeps track of which entity is associated to the restore backup button.
+#[derive(Resource)]
+pub struct RestoreBackupMenu {
+    restore: Entity,
+}
+
+impl RestoreBackupMenu {
+    pub fn get(&self) -> Entity {
+        self.restore
+    }
+}
+
+impl FromWorld for RestoreBackupMenu {
+    fn from_world(world: &mut World) -> Self {
+        let file_header = world.resource::<FileMenu>().get();
+        let restore = world
+            .spawn(MenuItem::Text(TextMenuItem::new("Restore from Backup...")))
+            .set_parent(file_header)
+            .id();
+
+        RestoreBackupMenu { restore }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct RestoreBackupWindow {
+    pub visible: bool,
+    /// Backups of the file that the window was last filled in for, with the
+    /// time that each of them was written.
+    backups: Vec<(PathBuf, Option<SystemTime>)>,
+    file: Option<PathBuf>,
+}
+
+impl RestoreBackupWindow {
+    fn refresh(&mut self, file: Option<&Path>) {
+        self.file = file.map(Path::to_path_buf);
+        self.backups = file
+            .map(existing_backups)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|backup| {
+                let modified = std::fs::metadata(&backup)
+                    .and_then(|metadata| metadata.modified())
+                    .ok();
+                (backup, modified)
+            })
+            .collect();
+    }
+}
+
+fn show_restore_backup_window(
+    mut menu_events: EventReader<MenuEvent>,
+    restore_menu: Res<RestoreBackupMenu>,
+    mut window: ResMut<RestoreBackupWindow>,
+    current_workspace: Res<CurrentWorkspace>,
+    default_files: Query<&DefaultFile>,
+    mut load_site: EventWriter<LoadSite>,
+    mut egui_context: EguiContexts,
+) {
+    let file = current_workspace
+        .root
+        .and_then(|root| default_files.get(root).ok())
+        .map(|file| file.0.as_path());
+
+    for event in menu_events.read() {
+        if event.clicked() && event.source() == restore_menu.get() {
+            window.visible = true;
+            window.refresh(file);
+        }
+    }
+
+    if !window.visible {
+        return;
+    }
+
+    // The list goes stale when a different workspace gets focused
+    if window.file.as_deref() != file {
+        window.refresh(file);
+    }
+
+    let mut open = true;
+    let mut restore = None;
+    egui::Window::new("Restore from Backup")
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            let Some(file) = &window.file else {
+                ui.label("The current site has not been saved to a file yet");
+                return;
+            };
+            if window.backups.is_empty() {
+                ui.label(format!("There are no backups of {}", file.display()));
+                return;
+            }
+
+            ui.label("The backup opens as a new workspace. Save it to replace the current file.");
+            Grid::new("restore_backups").show(ui, |ui| {
+                for (backup, modified) in &window.backups {
+                    let name = backup
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    ui.label(name);
+                    ui.label(modified.map(format_age).unwrap_or_default());
+                    if ui.button("Restore").clicked() {
+                        restore = Some(backup.clone());
+                    }
+                    ui.end_row();
+                }
+            });
+            if ui.button("Refresh").clicked() {
+                let file = file.clone();
+                window.refresh(Some(&file));
+            }
+        });
+
+    if let (Some(backup), Some(file)) = (restore, window.file.clone()) {
+        match read_backup(&backup, &file) {
+            Ok(site) => {
+                info!("Restoring {}", backup.display());
+                load_site.send(LoadSite {
+                    site,
+                    focus: true,
+                    default_file: Some(file),
+                });
+                open = false;
+            }
+            Err(err) => {
+                error!("Unable to restore {}: {err}", backup.display());
+            }
+        }
+    }
+
+    window.visible = open;
+}
+
+/// Backups keep the contents of the file they were made from, so they are
+/// parsed according to the name of that file.
+fn read_backup(backup: &Path, file: &Path) -> Result<Site, String> {
+    let data = std::fs::read(backup).map_err(|err| err.to_string())?;
+    match WorkspaceData::new(&file.to_path_buf(), data) {
+        Some(WorkspaceData::RonSite(data)) => {
+            Site::from_bytes_ron(&data).map_err(|err| err.to_string())
+        }
+        Some(WorkspaceData::JsonSite(data)) => {
+            Site::from_bytes_json(&data).map_err(|err| err.to_string())
+        }
+        _ => Err(format!("{} is not a site file", file.display())),
+    }
+}
+
+fn format_age(modified: SystemTime) -> String {
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or(Duration::ZERO)
+        .as_secs();
+    if age < 60 {
+        "just now".to_owned()
+    } else if age < 60 * 60 {
+        format!("{} min ago", age / 60)
+    } else if age < 24 * 60 * 60 {
+        format!("{} h ago", age / (60 * 60))
+    } else {
+        format!("{} days ago", age / (24 * 60 * 60))
+    }
+}
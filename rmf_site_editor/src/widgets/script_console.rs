@@ -0,0 +1,138 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{RunSiteScript, SiteScriptResult},
+    widgets::{MenuEvent, MenuItem, ToolMenu},
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Color32, RichText, ScrollArea, TextEdit},
+    EguiContexts,
+};
+
+const EXAMPLE_SCRIPT: &str = "// Shift all vertices on L1 by +0.3m in x\n\
+    for v in vertices(\"L1\") { v.x += 0.3; }\n";
+
+/// Add a window for running scripts that make batch edits to the current
+/// site.
+#[derive(Default)]
+pub struct ScriptConsolePlugin {}
+
+impl Plugin for ScriptConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScriptConsole>()
+            .init_resource::<ScriptConsoleMenu>()
+            .add_systems(
+                Update,
+                (handle_script_console_menu, show_script_console)
+                    .chain()
+                    .run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+#[derive(Resource)]
+pub struct ScriptConsole {
+    pub show: bool,
+    pub script: String,
+}
+
+impl Default for ScriptConsole {
+    fn default() -> Self {
+        Self {
+            show: false,
+            script: EXAMPLE_SCRIPT.to_owned(),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct ScriptConsoleMenu {
+    open: Entity,
+}
+
+impl FromWorld for ScriptConsoleMenu {
+    fn from_world(world: &mut World) -> Self {
+        let tool_menu = world.resource::<ToolMenu>().get();
+        let open = world
+            .spawn(MenuItem::Text("Script Console".into()))
+            .set_parent(tool_menu)
+            .id();
+        Self { open }
+    }
+}
+
+fn handle_script_console_menu(
+    mut menu_events: EventReader<MenuEvent>,
+    menu: Res<ScriptConsoleMenu>,
+    mut console: ResMut<ScriptConsole>,
+) {
+    for event in menu_events.read() {
+        if event.clicked() && event.source() == menu.open {
+            console.show = true;
+        }
+    }
+}
+
+fn show_script_console(
+    mut egui_context: EguiContexts,
+    mut console: ResMut<ScriptConsole>,
+    result: Res<SiteScriptResult>,
+    mut run_script: EventWriter<RunSiteScript>,
+) {
+    if !console.show {
+        return;
+    }
+
+    let mut open = true;
+    egui::Window::new("Script Console")
+        .open(&mut open)
+        .default_width(400.0)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.label("Scripts are written in rhai and edit the current site.")
+                .on_hover_text(
+                    "Available functions: levels(), graphs(), vertices(level), lanes(), \
+                    lanes(level), walls(level), models(), models(level), print(value)",
+                );
+            ui.add(
+                TextEdit::multiline(&mut console.script)
+                    .code_editor()
+                    .desired_rows(10)
+                    .desired_width(f32::INFINITY),
+            );
+            if ui.button("Run").clicked() {
+                run_script.send(RunSiteScript {
+                    script: console.script.clone(),
+                });
+            }
+
+            ui.separator();
+            if let Some(err) = &result.error {
+                ui.label(RichText::new(err).color(Color32::RED));
+            } else {
+                ui.label(format!("Changed {} elements", result.changed));
+            }
+            ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+                for line in &result.output {
+                    ui.monospace(line);
+                }
+            });
+        });
+    console.show = open;
+}
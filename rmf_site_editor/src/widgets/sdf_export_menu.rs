@@ -19,16 +19,27 @@ use crate::menu_bar::{FileMenu, MenuEvent, MenuItem, TextMenuItem};
 use crate::{AppState, WorkspaceSaver};
 use bevy::prelude::*;
 
-/// Keeps track of which entity is associated to the export sdf button.
+/// Keeps track of which entities are associated to the export sdf, digital
+/// twin, and crowd simulation buttons.
 #[derive(Resource)]
 pub struct SdfExportMenu {
     export_sdf: Entity,
+    export_digital_twin: Entity,
+    export_crowd_sim: Entity,
 }
 
 impl SdfExportMenu {
     pub fn get(&self) -> Entity {
         self.export_sdf
     }
+
+    pub fn digital_twin(&self) -> Entity {
+        self.export_digital_twin
+    }
+
+    pub fn crowd_sim(&self) -> Entity {
+        self.export_crowd_sim
+    }
 }
 
 impl FromWorld for SdfExportMenu {
@@ -40,8 +51,20 @@ impl FromWorld for SdfExportMenu {
             ))
             .set_parent(file_header)
             .id();
+        let export_digital_twin = world
+            .spawn(MenuItem::Text(TextMenuItem::new("Export Digital Twin")))
+            .set_parent(file_header)
+            .id();
+        let export_crowd_sim = world
+            .spawn(MenuItem::Text(TextMenuItem::new("Export Crowd Simulation")))
+            .set_parent(file_header)
+            .id();
 
-        SdfExportMenu { export_sdf }
+        SdfExportMenu {
+            export_sdf,
+            export_digital_twin,
+            export_crowd_sim,
+        }
     }
 }
 
@@ -53,6 +76,10 @@ fn handle_export_sdf_menu_events(
     for event in menu_events.read() {
         if event.clicked() && event.source() == sdf_menu.get() {
             workspace_saver.export_sdf_to_dialog();
+        } else if event.clicked() && event.source() == sdf_menu.digital_twin() {
+            workspace_saver.export_digital_twin_to_dialog();
+        } else if event.clicked() && event.source() == sdf_menu.crowd_sim() {
+            workspace_saver.export_crowd_sim_to_dialog();
         }
     }
 }
@@ -0,0 +1,349 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    menu_bar::{FileMenu, MenuEvent, MenuItem, TextMenuItem},
+    settings::{
+        EditorSettings, EditorSettingsFile, KeyBinding, KeyBindings, KeyChord, LengthUnit,
+        SaveEditorSettings,
+    },
+    widgets::in_length_unit,
+    AppState,
+};
+use bevy::prelude::{Input as UserInput, *};
+use bevy_egui::{
+    egui::{self, CollapsingHeader, ComboBox, DragValue, Grid, RichText, TextEdit},
+    EguiContexts,
+};
+
+/// Add a window for editing the [`EditorSettings`]. Changes are applied
+/// immediately and can be written into the settings file.
+#[derive(Default)]
+pub struct SettingsWindowPlugin {}
+
+impl Plugin for SettingsWindowPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsMenu>()
+            .init_resource::<SettingsWindow>()
+            .add_systems(
+                Update,
+                show_settings_window.run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+/// Keeps track of which entity is associated to the settings button.
+#[derive(Resource)]
+pub struct SettingsMenu {
+    settings: Entity,
+}
+
+impl SettingsMenu {
+    pub fn get(&self) -> Entity {
+        self.settings
+    }
+}
+
+impl FromWorld for SettingsMenu {
+    fn from_world(world: &mut World) -> Self {
+        let file_header = world.resource::<FileMenu>().get();
+        let settings = world
+            .spawn(MenuItem::Text(TextMenuItem::new("Settings...")))
+            .set_parent(file_header)
+            .id();
+
+        SettingsMenu { settings }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SettingsWindow {
+    pub visible: bool,
+    /// The key bindings that the text fields were last filled in from
+    synced: Option<KeyBindings>,
+    /// Text being edited for each key binding, with an error message if
+    /// the text cannot be parsed.
+    binding_text: Vec<(String, Option<String>)>,
+    /// The key binding that will be replaced by the next chord that is
+    /// pressed
+    recording: Option<usize>,
+    /// A model folder that is being typed in
+    new_model_folder: String,
+}
+
+impl SettingsWindow {
+    fn sync(&mut self, keybindings: &KeyBindings) {
+        if self.synced.as_ref() == Some(keybindings) {
+            return;
+        }
+        let mut keybindings = keybindings.clone();
+        self.binding_text = keybindings
+            .iter_mut()
+            .map(|(_, binding)| (binding.to_string(), None))
+            .collect();
+        self.synced = Some(keybindings);
+    }
+}
+
+fn show_settings_window(
+    mut menu_events: EventReader<MenuEvent>,
+    settings_menu: Res<SettingsMenu>,
+    mut window: ResMut<SettingsWindow>,
+    mut settings: ResMut<EditorSettings>,
+    settings_file: Res<EditorSettingsFile>,
+    mut save_settings: EventWriter<SaveEditorSettings>,
+    keyboard_input: Res<UserInput<KeyCode>>,
+    mut egui_context: EguiContexts,
+) {
+    for event in menu_events.read() {
+        if event.clicked() && event.source() == settings_menu.get() {
+            window.visible = true;
+        }
+    }
+
+    if !window.visible {
+        return;
+    }
+
+    // Pick up changes that came from the settings file being edited
+    window.sync(&settings.keybindings);
+
+    // Edit a copy so that change detection only triggers for real changes
+    let mut edited = settings.clone();
+    if let Some(index) = window.recording {
+        if let Some(chord) = KeyChord::from_just_pressed(&keyboard_input) {
+            // Escape cancels the recording instead of being bound
+            if chord != KeyChord::new(KeyCode::Escape) {
+                if let Some((_, binding)) = edited.keybindings.iter_mut().nth(index) {
+                    *binding = chord.into();
+                    if let Some(text) = window.binding_text.get_mut(index) {
+                        *text = (binding.to_string(), None);
+                    }
+                }
+            }
+            window.recording = None;
+        }
+    }
+    let mut open = true;
+    let mut reset = false;
+    egui::Window::new("Settings")
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            CollapsingHeader::new("Colors")
+                .default_open(true)
+                .show(ui, |ui| {
+                    Grid::new("settings_colors").show(ui, |ui| {
+                        let colors = &mut edited.colors;
+                        for (label, color) in [
+                            ("Background", &mut colors.background),
+                            ("Hover", &mut colors.hover),
+                            ("Select", &mut colors.select),
+                            ("Hover + Select", &mut colors.hover_select),
+                        ] {
+                            ui.label(label);
+                            ui.color_edit_button_rgb(color);
+                            ui.end_row();
+                        }
+                    });
+                });
+
+            CollapsingHeader::new("Units")
+                .default_open(true)
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Lengths").on_hover_text(
+                            "Sites are always saved in meters, this only changes how lengths are shown",
+                        );
+                        ComboBox::from_id_source("settings_length_unit")
+                            .selected_text(edited.units.label())
+                            .show_ui(ui, |ui| {
+                                for unit in LengthUnit::ALL {
+                                    ui.selectable_value(&mut edited.units, unit, unit.label());
+                                }
+                            });
+                    });
+                });
+
+            CollapsingHeader::new("Snapping")
+                .default_open(true)
+                .show(ui, |ui| {
+                    let units = edited.units;
+                    let snapping = &mut edited.snapping;
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut snapping.grid, "Grid");
+                        ui.add(in_length_unit(
+                            DragValue::new(&mut snapping.grid_spacing)
+                                .clamp_range(0.01..=100.0)
+                                .speed(0.01),
+                            units,
+                        ));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut snapping.angle, "Angle");
+                        ui.add(
+                            DragValue::new(&mut snapping.angle_increment)
+                                .clamp_range(1.0..=180.0)
+                                .suffix("°"),
+                        );
+                    });
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut snapping.vertex, "Vertex");
+                        ui.add(
+                            DragValue::new(&mut snapping.vertex_threshold)
+                                .clamp_range(1.0..=100.0)
+                                .suffix(" px"),
+                        );
+                    });
+                });
+
+            CollapsingHeader::new("Camera")
+                .default_open(true)
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Projection transition");
+                        ui.add(
+                            DragValue::new(&mut edited.camera.transition_duration)
+                                .clamp_range(0.0..=5.0)
+                                .speed(0.05)
+                                .suffix(" s"),
+                        )
+                        .on_hover_text("Set to zero to switch projections instantly");
+                    });
+                });
+
+            CollapsingHeader::new("Backups")
+                .default_open(false)
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Backups to keep").on_hover_text(
+                            "Saving over a site file first moves the old file to .bak, .bak1, ...",
+                        );
+                        ui.add(DragValue::new(&mut edited.backups.depth).clamp_range(0..=20));
+                    });
+                });
+
+            CollapsingHeader::new("Model Folders")
+                .default_open(false)
+                .show(ui, |ui| {
+                    ui.label("Folders that the asset gallery searches for models");
+                    let mut remove = None;
+                    for (i, folder) in edited.model_folders.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui.button("✖").on_hover_text("Remove this folder").clicked() {
+                                remove = Some(i);
+                            }
+                            ui.label(folder.display().to_string());
+                        });
+                    }
+                    if let Some(i) = remove {
+                        edited.model_folders.remove(i);
+                    }
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            TextEdit::singleline(&mut window.new_model_folder)
+                                .hint_text("/path/to/models")
+                                .desired_width(200.0),
+                        );
+                        let folder = window.new_model_folder.trim();
+                        if ui
+                            .add_enabled(!folder.is_empty(), egui::Button::new("Add"))
+                            .clicked()
+                        {
+                            edited.model_folders.push(folder.into());
+                            window.new_model_folder.clear();
+                        }
+                    });
+                });
+
+            CollapsingHeader::new("Key Bindings")
+                .default_open(false)
+                .show(ui, |ui| {
+                    ui.label("Separate alternative keys with commas, e.g. Ctrl+Shift+S, F12");
+                    ui.label("Click ⏺ and press a key to record it, or Escape to cancel");
+                    Grid::new("settings_keybindings").show(ui, |ui| {
+                        let SettingsWindow {
+                            binding_text,
+                            recording,
+                            ..
+                        } = &mut *window;
+                        for (i, ((label, binding), (text, error))) in edited
+                            .keybindings
+                            .iter_mut()
+                            .zip(binding_text.iter_mut())
+                            .enumerate()
+                        {
+                            ui.label(label);
+                            let is_recording = *recording == Some(i);
+                            if ui
+                                .add(egui::Button::new("⏺").selected(is_recording))
+                                .on_hover_text("Record the next key that is pressed")
+                                .clicked()
+                            {
+                                *recording = if is_recording { None } else { Some(i) };
+                            }
+                            let response = ui.add(TextEdit::singleline(text).desired_width(160.0));
+                            if response.changed() {
+                                match KeyBinding::parse(text) {
+                                    Ok(parsed) => {
+                                        *binding = parsed;
+                                        *error = None;
+                                    }
+                                    Err(err) => *error = Some(err),
+                                }
+                            }
+                            if let Some(err) = error {
+                                ui.label(RichText::new(err.as_str()).color(egui::Color32::RED));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(settings_file.path.is_some(), egui::Button::new("Save"))
+                    .on_hover_text(match &settings_file.path {
+                        Some(path) => format!("Write these settings to {}", path.display()),
+                        None => "There is no configuration directory to save into".to_owned(),
+                    })
+                    .clicked()
+                {
+                    save_settings.send(SaveEditorSettings);
+                }
+                if ui.button("Restore Defaults").clicked() {
+                    edited = EditorSettings::default();
+                    reset = true;
+                }
+            });
+        });
+
+    if !open {
+        window.visible = false;
+    }
+
+    if reset {
+        window.synced = None;
+    } else {
+        // The text fields already show any key bindings that were edited
+        window.synced = Some(edited.keybindings.clone());
+    }
+
+    if edited != *settings {
+        *settings = edited;
+    }
+}
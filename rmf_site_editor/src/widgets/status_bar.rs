@@ -0,0 +1,181 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{IntersectGroundPlaneParams, MultiSelection, Selection},
+    settings::EditorSettings,
+    site::{
+        site_projection, Category, CurrentLevel, GeographicComponent, GeographicReferences,
+        MapSaved, NameInSite,
+    },
+    widgets::prelude::*,
+    CurrentWorkspace,
+};
+use bevy::{prelude::*, utils::HashMap};
+use bevy_egui::egui;
+
+/// Add a status bar to the bottom of the window that shows where the cursor
+/// is, how points are being snapped, what is selected, and when the site was
+/// last saved.
+#[derive(Default)]
+pub struct StatusBarPlugin {}
+
+impl Plugin for StatusBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LastSaved>()
+            .add_systems(Update, record_last_saved);
+        let widget = PanelWidget::new(status_bar_widget, &mut app.world);
+        app.world.spawn(widget);
+    }
+}
+
+/// When each site was last saved, in seconds since the app started.
+#[derive(Resource, Default, Debug)]
+pub struct LastSaved(pub HashMap<Entity, f64>);
+
+fn record_last_saved(
+    mut saved: EventReader<MapSaved>,
+    mut last_saved: ResMut<LastSaved>,
+    time: Res<Time>,
+) {
+    for MapSaved { site, .. } in saved.read() {
+        last_saved.0.insert(*site, time.elapsed_seconds_f64());
+    }
+}
+
+#[derive(SystemParam)]
+struct StatusBarParams<'w, 's> {
+    selection: Res<'w, Selection>,
+    multi_selection: Res<'w, MultiSelection>,
+    settings: Res<'w, EditorSettings>,
+    intersect_ground_params: IntersectGroundPlaneParams<'w, 's>,
+    current_level: Res<'w, CurrentLevel>,
+    current_workspace: Res<'w, CurrentWorkspace>,
+    names: Query<'w, 's, &'static NameInSite>,
+    geography: Query<
+        'w,
+        's,
+        (
+            &'static GeographicComponent,
+            Option<&'static GeographicReferences>,
+        ),
+    >,
+    categories: Query<'w, 's, &'static Category>,
+    last_saved: Res<'w, LastSaved>,
+    time: Res<'w, Time>,
+}
+
+fn status_bar_widget(In(input): In<PanelWidgetInput>, params: StatusBarParams) {
+    egui::TopBottomPanel::bottom("status_bar")
+        .resizable(false)
+        .show(&input.context, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(params.cursor_text());
+                ui.separator();
+                ui.label(params.snap_text());
+                ui.separator();
+                ui.label(params.selection_text());
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(params.save_text());
+                });
+            });
+        });
+}
+
+impl<'w, 's> StatusBarParams<'w, 's> {
+    fn cursor_text(&self) -> String {
+        let level = self
+            .current_level
+            .0
+            .and_then(|level| self.names.get(level).ok())
+            .map(|name| name.0.as_str())
+            .unwrap_or("no level");
+        let Some(tf) = self.intersect_ground_params.ground_plane_intersection() else {
+            return format!("{level}: cursor outside of view");
+        };
+        let p = [tf.translation.x, tf.translation.y];
+        let units = self.settings.units;
+        let position = format!("{}, {}", units.format(p[0], 3), units.format(p[1], 3));
+        let lon_lat = self
+            .current_workspace
+            .root
+            .and_then(|site| site_projection(site, &self.geography))
+            .and_then(|projection| projection.lon_lat(p));
+        match lon_lat {
+            Some([lon, lat]) => format!("{level}: ({position}), {lat:.7}°, {lon:.7}°"),
+            None => format!("{level}: ({position})"),
+        }
+    }
+
+    fn snap_text(&self) -> String {
+        let snapping = &self.settings.snapping;
+        let mut snaps = Vec::new();
+        if snapping.grid {
+            snaps.push(format!(
+                "grid {}",
+                self.settings.units.format(snapping.grid_spacing, 2)
+            ));
+        }
+        if snapping.angle {
+            snaps.push(format!("angle {}°", snapping.angle_increment));
+        }
+        if snapping.vertex {
+            snaps.push("vertex".to_owned());
+        }
+        if snaps.is_empty() {
+            "Snap: off".to_owned()
+        } else {
+            format!("Snap: {}", snaps.join(", "))
+        }
+    }
+
+    fn selection_text(&self) -> String {
+        if self.multi_selection.len() > 1 {
+            return format!("{} selected", self.multi_selection.len());
+        }
+        let Some(selected) = self.selection.0 else {
+            return "Nothing selected".to_owned();
+        };
+        let category = self
+            .categories
+            .get(selected)
+            .map(|c| c.label())
+            .unwrap_or("Entity");
+        match self.names.get(selected) {
+            Ok(name) => format!("{category} \"{}\"", name.0),
+            Err(_) => format!("{category} {selected:?}"),
+        }
+    }
+
+    fn save_text(&self) -> String {
+        let saved_at = self
+            .current_workspace
+            .root
+            .and_then(|site| self.last_saved.0.get(&site));
+        let Some(saved_at) = saved_at else {
+            return "Not saved yet".to_owned();
+        };
+        let elapsed = (self.time.elapsed_seconds_f64() - saved_at).max(0.0) as u64;
+        if elapsed < 60 {
+            format!("Saved {elapsed} s ago")
+        } else if elapsed < 3600 {
+            format!("Saved {} min ago", elapsed / 60)
+        } else {
+            format!("Saved {} h ago", elapsed / 3600)
+        }
+    }
+}
@@ -0,0 +1,89 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{SetToolMode, ToolMode, ToolModes},
+    settings::EditorSettings,
+    widgets::{HeaderTilePlugin, Tile, WidgetSystem},
+    AppState,
+};
+use bevy::{
+    ecs::system::{SystemParam, SystemState},
+    prelude::*,
+};
+use bevy_egui::egui::{Button, Ui};
+
+/// Add a toolbar to the header with a button for each [`ToolMode`] that is
+/// available in the current app state. The button of the active tool is
+/// highlighted.
+#[derive(Default)]
+pub struct ToolbarPlugin {}
+
+impl Plugin for ToolbarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(HeaderTilePlugin::<Toolbar>::new());
+    }
+}
+
+#[derive(SystemParam)]
+pub struct Toolbar<'w> {
+    app_state: Res<'w, State<AppState>>,
+    tool_modes: Option<Res<'w, ToolModes>>,
+    current: Option<Res<'w, ToolMode>>,
+    settings: Res<'w, EditorSettings>,
+    set_tool_mode: EventWriter<'w, SetToolMode>,
+}
+
+impl<'w> WidgetSystem<Tile> for Toolbar<'w> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        let (Some(tool_modes), Some(current)) = (&params.tool_modes, &params.current) else {
+            return;
+        };
+
+        let mut clicked = None;
+        for tool in tool_modes.iter() {
+            if !tool.info.app_states.contains(params.app_state.get()) {
+                continue;
+            }
+
+            let hover_text = match tool.info.shortcut {
+                Some(shortcut) => {
+                    format!(
+                        "{} ({})",
+                        tool.info.label,
+                        shortcut(&params.settings.keybindings)
+                    )
+                }
+                None => tool.info.label.to_owned(),
+            };
+            let active = **current == tool.info.mode;
+            if ui
+                .add(Button::new(tool.info.icon).selected(active))
+                .on_hover_text(hover_text)
+                .clicked()
+            {
+                clicked = Some(tool.info.mode);
+            }
+        }
+
+        if let Some(mode) = clicked {
+            params.set_tool_mode.send(SetToolMode(mode));
+        }
+        ui.separator();
+    }
+}
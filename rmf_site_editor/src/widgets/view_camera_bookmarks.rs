@@ -0,0 +1,117 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{AddCameraBookmark, CameraBookmarks, Change, Delete, JumpToCameraBookmark, NameInSite},
+    widgets::prelude::*,
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{Button, CollapsingHeader, Grid, TextEdit, Ui};
+
+/// Add a widget for saving named camera views of the current level and
+/// jumping back to them.
+#[derive(Default)]
+pub struct ViewCameraBookmarksPlugin {}
+
+impl Plugin for ViewCameraBookmarksPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PropertiesTilePlugin::<ViewCameraBookmarks>::new());
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewCameraBookmarks<'w, 's> {
+    bookmarks: CameraBookmarks<'w, 's>,
+    new_name: Local<'s, String>,
+    add: EventWriter<'w, AddCameraBookmark>,
+    jump: EventWriter<'w, JumpToCameraBookmark>,
+    delete: EventWriter<'w, Delete>,
+    change_name: EventWriter<'w, Change<NameInSite>>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewCameraBookmarks<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Camera Bookmarks")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewCameraBookmarks<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let bookmarks = self.bookmarks.of_current_level();
+        if bookmarks.is_empty() {
+            ui.label("No bookmarks on this level");
+        }
+
+        Grid::new("camera_bookmarks").num_columns(4).show(ui, |ui| {
+            for (i, (e, name, _)) in bookmarks.iter().enumerate() {
+                if i < 9 {
+                    ui.label(format!("{}", i + 1))
+                        .on_hover_text(format!("Press {} to jump here", i + 1));
+                } else {
+                    ui.label("");
+                }
+
+                let mut new_name = name.0.clone();
+                if ui
+                    .add(TextEdit::singleline(&mut new_name).desired_width(120.0))
+                    .changed()
+                {
+                    self.change_name.send(Change::new(NameInSite(new_name), *e));
+                }
+
+                if ui.button("Go").clicked() {
+                    self.jump.send(JumpToCameraBookmark(*e));
+                }
+                if ui
+                    .button("❌")
+                    .on_hover_text("Remove this bookmark")
+                    .clicked()
+                {
+                    self.delete.send(Delete::new(*e));
+                }
+                ui.end_row();
+            }
+        });
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut *self.new_name)
+                    .hint_text("Loading dock")
+                    .desired_width(120.0),
+            );
+            let name = self.new_name.trim().to_owned();
+            if ui
+                .add_enabled(!name.is_empty(), Button::new("Add Current View"))
+                .clicked()
+            {
+                self.add.send(AddCameraBookmark { name });
+                self.new_name.clear();
+            }
+        });
+    }
+}
@@ -0,0 +1,481 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::Selection,
+    site::{
+        AgentGroup, AgentProfile, Anchor, Change, ControlCrowdPreview, CrowdModelType,
+        CrowdPreviewState, CrowdSim, CrowdState, GoalSet, SiteID,
+    },
+    widgets::{prelude::*, Icons},
+    AppState, CurrentWorkspace,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{Button, CollapsingHeader, ComboBox, DragValue, Grid, ImageButton, Ui};
+
+/// Add a widget for authoring the crowd simulation of a site: the profiles
+/// and groups of agents, the goal sets that they walk between, and the
+/// obstacles and models of the simulation. The simulation can also be
+/// previewed on the current level.
+#[derive(Default)]
+pub struct ViewCrowdSimPlugin {}
+
+impl Plugin for ViewCrowdSimPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PropertiesTilePlugin::<ViewCrowdSim>::new());
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewCrowdSim<'w, 's> {
+    current_workspace: Res<'w, CurrentWorkspace>,
+    crowd_sims: Query<'w, 's, &'static CrowdSim<Entity>>,
+    anchors: Query<'w, 's, Option<&'static SiteID>, With<Anchor>>,
+    selection: Res<'w, Selection>,
+    icons: Res<'w, Icons>,
+    change_crowd_sim: EventWriter<'w, Change<CrowdSim<Entity>>>,
+    preview: ResMut<'w, CrowdPreviewState>,
+    control_preview: EventWriter<'w, ControlCrowdPreview>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewCrowdSim<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Crowd Simulation")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewCrowdSim<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let Some(site) = self.current_workspace.root else {
+            return;
+        };
+        let old = self.crowd_sims.get(site).cloned().unwrap_or_default();
+        let mut new = old.clone();
+
+        ui.checkbox(&mut new.enable, "Enable");
+        ui.horizontal(|ui| {
+            ui.label("Update time step");
+            ui.add(
+                DragValue::new(&mut new.update_time_step)
+                    .clamp_range(0.001..=f32::INFINITY)
+                    .speed(0.01)
+                    .suffix(" s"),
+            );
+        });
+
+        CollapsingHeader::new("Agent Profiles")
+            .id_source("crowd_sim_agent_profiles")
+            .show(ui, |ui| {
+                self.show_agent_profiles(ui, &mut new.agent_profiles);
+            });
+
+        CollapsingHeader::new("Agent Groups")
+            .id_source("crowd_sim_agent_groups")
+            .show(ui, |ui| {
+                let profiles: Vec<String> =
+                    new.agent_profiles.iter().map(|p| p.name.clone()).collect();
+                let states: Vec<String> = new.states.iter().map(|s| s.name.clone()).collect();
+                self.show_agent_groups(ui, &mut new.agent_groups, &profiles, &states);
+            });
+
+        CollapsingHeader::new("Goal Sets")
+            .id_source("crowd_sim_goal_sets")
+            .show(ui, |ui| {
+                self.show_goal_sets(ui, &mut new.goal_sets);
+            });
+
+        CollapsingHeader::new("States")
+            .id_source("crowd_sim_states")
+            .show(ui, |ui| {
+                self.show_states(ui, &mut new.states);
+            });
+
+        CollapsingHeader::new("Obstacles")
+            .id_source("crowd_sim_obstacles")
+            .show(ui, |ui| {
+                Grid::new("crowd_sim_obstacle_set")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Class");
+                        ui.add(DragValue::new(&mut new.obstacle_set.class));
+                        ui.end_row();
+                        ui.label("File");
+                        ui.text_edit_singleline(&mut new.obstacle_set.file_name);
+                        ui.end_row();
+                        ui.label("Type");
+                        ui.text_edit_singleline(&mut new.obstacle_set.kind);
+                        ui.end_row();
+                    });
+                ui.separator();
+                ui.label("Models");
+                self.show_model_types(ui, &mut new.model_types);
+            });
+
+        CollapsingHeader::new("Preview")
+            .id_source("crowd_sim_preview")
+            .show(ui, |ui| {
+                self.show_preview(ui);
+            });
+
+        if new != old {
+            self.change_crowd_sim
+                .send(Change::new(new, site).or_insert());
+        }
+    }
+
+    fn show_preview(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            if self.preview.is_running() {
+                if ui.button("Stop").clicked() {
+                    self.control_preview.send(ControlCrowdPreview::Stop);
+                }
+                let pause = if self.preview.paused {
+                    "Resume"
+                } else {
+                    "Pause"
+                };
+                if ui.button(pause).clicked() {
+                    self.preview.paused = !self.preview.paused;
+                }
+            } else if ui
+                .button("Start")
+                .on_hover_text("Animate the agents along the human lanes of the current level")
+                .clicked()
+            {
+                self.control_preview.send(ControlCrowdPreview::Start);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Speed");
+            ui.add(
+                DragValue::new(&mut self.preview.speed)
+                    .clamp_range(0.1..=10.0)
+                    .speed(0.1)
+                    .suffix("x"),
+            );
+        });
+
+        let Some(preview) = &self.preview.preview else {
+            return;
+        };
+        ui.label(format!(
+            "{} agents, {} blocked",
+            preview.agents.len(),
+            preview.blocked_agents(),
+        ));
+        if preview.graph.is_empty() {
+            ui.label("There are no human lanes on this level");
+        }
+        for (set_id, goal) in &preview.unreachable {
+            ui.label(format!("Goal {goal} of goal set {set_id} is unreachable"));
+        }
+    }
+
+    fn show_agent_profiles(&self, ui: &mut Ui, profiles: &mut Vec<AgentProfile>) {
+        let mut remove = None;
+        for (i, profile) in profiles.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                if ui
+                    .add(ImageButton::new(self.icons.trash.egui()))
+                    .on_hover_text("Remove this profile")
+                    .clicked()
+                {
+                    remove = Some(i);
+                }
+                ui.text_edit_singleline(&mut profile.name);
+            });
+            Grid::new(("crowd_sim_agent_profile", i))
+                .num_columns(2)
+                .show(ui, |ui| {
+                    let row = |ui: &mut Ui, label: &str, value: &mut f32, suffix: &str| {
+                        ui.label(label);
+                        ui.add(
+                            DragValue::new(value)
+                                .clamp_range(0.0..=f32::INFINITY)
+                                .speed(0.01)
+                                .suffix(suffix),
+                        );
+                        ui.end_row();
+                    };
+                    row(ui, "Radius", &mut profile.radius, " m");
+                    row(ui, "Preferred speed", &mut profile.pref_speed, " m/s");
+                    row(ui, "Max speed", &mut profile.max_speed, " m/s");
+                    row(ui, "Max acceleration", &mut profile.max_accel, " m/s²");
+                    row(ui, "Max angular speed", &mut profile.max_angle_vel, " °/s");
+                    row(ui, "Neighbor distance", &mut profile.neighbor_dist, " m");
+                    row(ui, "ORCA tau", &mut profile.orca_tau, " s");
+                    row(ui, "ORCA obstacle tau", &mut profile.orca_tau_obst, " s");
+
+                    ui.label("Max neighbors");
+                    ui.add(DragValue::new(&mut profile.max_neighbors));
+                    ui.end_row();
+                    ui.label("Class");
+                    ui.add(DragValue::new(&mut profile.class));
+                    ui.end_row();
+                    ui.label("Obstacle set");
+                    ui.add(DragValue::new(&mut profile.obstacle_set));
+                    ui.end_row();
+                });
+            ui.separator();
+        }
+        if let Some(i) = remove {
+            profiles.remove(i);
+        }
+        if ui.button("Add profile").clicked() {
+            profiles.push(AgentProfile::default());
+        }
+    }
+
+    fn show_agent_groups(
+        &self,
+        ui: &mut Ui,
+        groups: &mut Vec<AgentGroup>,
+        profiles: &[String],
+        states: &[String],
+    ) {
+        let mut remove = None;
+        for (i, group) in groups.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                if ui
+                    .add(ImageButton::new(self.icons.trash.egui()))
+                    .on_hover_text("Remove this group")
+                    .clicked()
+                {
+                    remove = Some(i);
+                }
+                ui.label(format!("Group {}", group.group_id));
+            });
+            Grid::new(("crowd_sim_agent_group", i))
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("ID");
+                    ui.add(DragValue::new(&mut group.group_id));
+                    ui.end_row();
+
+                    ui.label("Profile");
+                    select_name(
+                        ui,
+                        ("crowd_sim_group_profile", i),
+                        &mut group.profile_selector,
+                        profiles,
+                    );
+                    ui.end_row();
+
+                    ui.label("Initial state");
+                    select_name(
+                        ui,
+                        ("crowd_sim_group_state", i),
+                        &mut group.state_selector,
+                        states,
+                    );
+                    ui.end_row();
+
+                    ui.label("Agents");
+                    ui.add(DragValue::new(&mut group.agents_number));
+                    ui.end_row();
+
+                    ui.label("Spawn point");
+                    ui.horizontal(|ui| {
+                        for value in group.spawn_point.iter_mut() {
+                            ui.add(DragValue::new(value).speed(0.1).suffix(" m"));
+                        }
+                    });
+                    ui.end_row();
+                });
+            ui.separator();
+        }
+        if let Some(i) = remove {
+            groups.remove(i);
+        }
+        if ui.button("Add group").clicked() {
+            let group_id = groups.iter().map(|g| g.group_id + 1).max().unwrap_or(0);
+            groups.push(AgentGroup {
+                group_id,
+                profile_selector: profiles.first().cloned().unwrap_or_default(),
+                state_selector: states.first().cloned().unwrap_or_default(),
+                agents_number: 1,
+                ..Default::default()
+            });
+        }
+    }
+
+    fn show_goal_sets(&self, ui: &mut Ui, goal_sets: &mut Vec<GoalSet<Entity>>) {
+        let selected_anchor = self.selection.0.filter(|e| self.anchors.contains(*e));
+        let mut remove = None;
+        for (i, goal_set) in goal_sets.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                if ui
+                    .add(ImageButton::new(self.icons.trash.egui()))
+                    .on_hover_text("Remove this goal set")
+                    .clicked()
+                {
+                    remove = Some(i);
+                }
+                ui.label("ID");
+                ui.add(DragValue::new(&mut goal_set.set_id));
+                ui.label("Capacity");
+                ui.add(DragValue::new(&mut goal_set.capacity));
+            });
+
+            let mut remove_anchor = None;
+            for (j, anchor) in goal_set.anchors.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add(ImageButton::new(self.icons.trash.egui()))
+                        .on_hover_text("Remove this goal")
+                        .clicked()
+                    {
+                        remove_anchor = Some(j);
+                    }
+                    match self.anchors.get(*anchor) {
+                        Ok(Some(id)) => ui.label(format!("Anchor #{}", id.0)),
+                        Ok(None) => ui.label(format!("Anchor {anchor:?}")),
+                        Err(_) => ui.label("Deleted anchor"),
+                    };
+                });
+            }
+            if let Some(j) = remove_anchor {
+                goal_set.anchors.remove(j);
+            }
+
+            let can_add = selected_anchor.is_some_and(|a| !goal_set.anchors.contains(&a));
+            if ui
+                .add_enabled(can_add, Button::new("Add selected anchor"))
+                .on_hover_text("Select an anchor to use it as a goal of this set")
+                .clicked()
+            {
+                if let Some(anchor) = selected_anchor {
+                    goal_set.anchors.push(anchor);
+                }
+            }
+            ui.separator();
+        }
+        if let Some(i) = remove {
+            goal_sets.remove(i);
+        }
+        if ui.button("Add goal set").clicked() {
+            let set_id = goal_sets.iter().map(|g| g.set_id + 1).max().unwrap_or(0);
+            goal_sets.push(GoalSet {
+                set_id,
+                capacity: 1,
+                anchors: Vec::new(),
+            });
+        }
+    }
+
+    fn show_states(&self, ui: &mut Ui, states: &mut Vec<CrowdState>) {
+        let mut remove = None;
+        for (i, state) in states.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                if ui
+                    .add(ImageButton::new(self.icons.trash.egui()))
+                    .on_hover_text("Remove this state")
+                    .clicked()
+                {
+                    remove = Some(i);
+                }
+                ui.text_edit_singleline(&mut state.name);
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut state.is_final, "Final");
+                let mut has_goal = state.goal_set.is_some();
+                ui.checkbox(&mut has_goal, "Goal set");
+                if has_goal {
+                    let mut goal = state.goal_set.unwrap_or(0);
+                    ui.add(DragValue::new(&mut goal));
+                    state.goal_set = Some(goal);
+                } else {
+                    state.goal_set = None;
+                }
+            });
+            ui.separator();
+        }
+        if let Some(i) = remove {
+            states.remove(i);
+        }
+        if ui.button("Add state").clicked() {
+            states.push(CrowdState {
+                name: format!("state_{}", states.len()),
+                ..Default::default()
+            });
+        }
+    }
+
+    fn show_model_types(&self, ui: &mut Ui, model_types: &mut Vec<CrowdModelType>) {
+        let mut remove = None;
+        for (i, model_type) in model_types.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                if ui
+                    .add(ImageButton::new(self.icons.trash.egui()))
+                    .on_hover_text("Remove this model")
+                    .clicked()
+                {
+                    remove = Some(i);
+                }
+                ui.text_edit_singleline(&mut model_type.typename);
+            });
+            Grid::new(("crowd_sim_model_type", i))
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Animation");
+                    ui.text_edit_singleline(&mut model_type.animation);
+                    ui.end_row();
+                    ui.label("Animation speed");
+                    ui.add(
+                        DragValue::new(&mut model_type.animation_speed)
+                            .clamp_range(0.0..=f32::INFINITY)
+                            .speed(0.01),
+                    );
+                    ui.end_row();
+                    ui.label("Model");
+                    ui.text_edit_singleline(&mut model_type.model_uri);
+                    ui.end_row();
+                });
+            ui.separator();
+        }
+        if let Some(i) = remove {
+            model_types.remove(i);
+        }
+        if ui.button("Add model").clicked() {
+            model_types.push(CrowdModelType {
+                typename: "human".to_owned(),
+                animation: "walk".to_owned(),
+                animation_speed: 0.2,
+                ..Default::default()
+            });
+        }
+    }
+}
+
+fn select_name(ui: &mut Ui, id: impl std::hash::Hash, selected: &mut String, options: &[String]) {
+    ComboBox::from_id_source(id)
+        .selected_text(selected.as_str())
+        .show_ui(ui, |ui| {
+            for option in options {
+                ui.selectable_value(selected, option.clone(), option);
+            }
+        });
+}
@@ -0,0 +1,192 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::Select,
+    site::{
+        Change, CurrentLevel, CustomEntityKind, CustomEntityMarker, CustomEntitySchemas,
+        NameInSite, Pose,
+    },
+    widgets::{prelude::*, SelectorWidget},
+    AppState, CurrentWorkspace,
+};
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use bevy_egui::egui::{Button, CollapsingHeader, Ui};
+use futures_lite::future;
+
+#[cfg(not(target_arch = "wasm32"))]
+use rfd::AsyncFileDialog;
+
+/// Add a widget for registering custom entity schemas with the current site
+/// and creating entities of the custom kinds.
+#[derive(Default)]
+pub struct ViewCustomEntitiesPlugin {}
+
+impl Plugin for ViewCustomEntitiesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CustomEntitySchemaDisplay>()
+            .add_plugins(PropertiesTilePlugin::<ViewCustomEntities>::new())
+            .add_systems(Update, resolve_custom_entity_schema_file);
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct CustomEntitySchemaDisplay {
+    /// The site that the schemas will be registered with and the task that is
+    /// reading the schema file
+    pub choosing_file: Option<(Entity, Task<Option<Vec<u8>>>)>,
+}
+
+#[derive(SystemParam)]
+pub struct ViewCustomEntities<'w, 's> {
+    current_workspace: Res<'w, CurrentWorkspace>,
+    current_level: Res<'w, CurrentLevel>,
+    schemas: Query<'w, 's, &'static CustomEntitySchemas>,
+    custom_entities: Query<
+        'w,
+        's,
+        (Entity, &'static CustomEntityKind, &'static NameInSite),
+        With<CustomEntityMarker>,
+    >,
+    parents: Query<'w, 's, &'static Parent>,
+    display: ResMut<'w, CustomEntitySchemaDisplay>,
+    selector: SelectorWidget<'w, 's>,
+    commands: Commands<'w, 's>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewCustomEntities<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Custom Entities")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewCustomEntities<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let Some(site) = self.current_workspace.root else {
+            return;
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let choosing = self.display.choosing_file.is_some();
+            if ui
+                .add_enabled(!choosing, Button::new("Load Schemas..."))
+                .on_hover_text("Register the custom entity kinds of a YAML schema file")
+                .clicked()
+            {
+                let future = AsyncComputeTaskPool::get().spawn(async move {
+                    let file = AsyncFileDialog::new()
+                        .add_filter("YAML", &["yaml", "yml"])
+                        .pick_file()
+                        .await?;
+                    Some(file.read().await)
+                });
+                self.display.choosing_file = Some((site, future));
+            }
+        }
+
+        let Some(schemas) = self.schemas.get(site).ok().filter(|s| !s.is_empty()) else {
+            ui.label("No custom entity kinds are registered");
+            return;
+        };
+
+        ui.separator();
+        ui.heading("Create new");
+        let level = self.current_level.0;
+        for (kind, schema) in schemas.iter() {
+            let label = match &schema.icon {
+                Some(icon) => format!("{icon} {kind}"),
+                None => kind.clone(),
+            };
+            if ui
+                .add_enabled(level.is_some(), Button::new(label))
+                .clicked()
+            {
+                let Some(level) = level else {
+                    continue;
+                };
+                let new_entity = self
+                    .commands
+                    .spawn(schema.instantiate(kind, Pose::default()))
+                    .set_parent(level)
+                    .id();
+                self.selector.select.send(Select::new(Some(new_entity)));
+            }
+        }
+
+        ui.separator();
+        let mut any = false;
+        for (e, kind, name) in &self.custom_entities {
+            if !AncestorIter::new(&self.parents, e).any(|p| p == site) {
+                continue;
+            }
+            any = true;
+            ui.horizontal(|ui| {
+                self.selector.show_widget(e, ui);
+                ui.label(format!("{} ({})", name.0, kind.0));
+            });
+        }
+        if !any {
+            ui.label("The site has no custom entities");
+        }
+    }
+}
+
+fn resolve_custom_entity_schema_file(
+    mut display: ResMut<CustomEntitySchemaDisplay>,
+    schemas: Query<&CustomEntitySchemas>,
+    mut change_schemas: EventWriter<Change<CustomEntitySchemas>>,
+) {
+    let Some((site, task)) = &mut display.choosing_file else {
+        return;
+    };
+    let Some(result) = future::block_on(future::poll_once(task)) else {
+        return;
+    };
+    let site = *site;
+    display.choosing_file = None;
+
+    let Some(bytes) = result else {
+        return;
+    };
+    let loaded = match std::str::from_utf8(&bytes)
+        .map_err(|err| err.to_string())
+        .and_then(|s| CustomEntitySchemas::from_str_yaml(s).map_err(|err| err.to_string()))
+    {
+        Ok(loaded) => loaded,
+        Err(err) => {
+            error!("Unable to load custom entity schemas: {err}");
+            return;
+        }
+    };
+    info!("Registered {} custom entity kinds", loaded.0.len());
+    let mut merged = schemas.get(site).cloned().unwrap_or_default();
+    merged.merge(loaded);
+    change_schemas.send(Change::new(merged, site).or_insert());
+}
@@ -0,0 +1,234 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{set_multi_selection, MultiSelection, Select, Selected, Selection},
+    site::{Change, EntityGroup, EntityGroups, NameInSite, SiteID},
+    widgets::{prelude::*, Icons},
+    AppState, CurrentWorkspace,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{Button, CollapsingHeader, ImageButton, Ui};
+use std::collections::BTreeSet;
+
+/// Add a widget that shows the logical groups of the site as a tree. Groups
+/// can be hidden, locked, and selected all at once, and their members can
+/// come from any level.
+#[derive(Default)]
+pub struct ViewEntityGroupsPlugin {}
+
+impl Plugin for ViewEntityGroupsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EntityGroupsDisplay>()
+            .add_plugins(PropertiesTilePlugin::<ViewEntityGroups>::new());
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct EntityGroupsDisplay {
+    pub new_name: String,
+}
+
+#[derive(SystemParam)]
+pub struct ViewEntityGroups<'w, 's> {
+    current_workspace: Res<'w, CurrentWorkspace>,
+    entity_groups: Query<'w, 's, &'static EntityGroups<Entity>>,
+    site_ids: Query<'w, 's, &'static SiteID>,
+    names: Query<'w, 's, &'static NameInSite>,
+    display: ResMut<'w, EntityGroupsDisplay>,
+    selection: ResMut<'w, Selection>,
+    multi_selection: ResMut<'w, MultiSelection>,
+    selected: Query<'w, 's, &'static mut Selected>,
+    select: EventWriter<'w, Select>,
+    change_entity_groups: EventWriter<'w, Change<EntityGroups<Entity>>>,
+    icons: Res<'w, Icons>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewEntityGroups<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Structure")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+enum GroupAction {
+    Select(BTreeSet<Entity>),
+    Delete(Vec<usize>),
+}
+
+impl<'w, 's> ViewEntityGroups<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let Some(site) = self.current_workspace.root else {
+            return;
+        };
+        let old = self.entity_groups.get(site).cloned().unwrap_or_default();
+        let mut new = old.clone();
+
+        let current: BTreeSet<Entity> = if self.multi_selection.is_empty() {
+            self.selection.0.into_iter().collect()
+        } else {
+            self.multi_selection.0.clone()
+        };
+
+        let mut action = None;
+        for (i, group) in new.0.iter_mut().enumerate() {
+            self.show_group(ui, group, vec![i], &current, &mut action);
+        }
+
+        ui.horizontal(|ui| {
+            let add = ui
+                .add_enabled(!self.display.new_name.is_empty(), Button::new("Add group"))
+                .on_hover_text("Create a group out of the current selection");
+            if add.clicked() {
+                let mut group = EntityGroup::new(std::mem::take(&mut self.display.new_name));
+                group.members = current.clone();
+                new.0.push(group);
+            }
+            ui.text_edit_singleline(&mut self.display.new_name);
+        });
+
+        match action {
+            Some(GroupAction::Select(members)) => {
+                set_multi_selection(
+                    members,
+                    None,
+                    &mut self.multi_selection,
+                    &mut self.selection,
+                    &mut self.selected,
+                );
+            }
+            Some(GroupAction::Delete(path)) => {
+                new.remove(&path);
+            }
+            None => {}
+        }
+
+        if new != old {
+            self.change_entity_groups
+                .send(Change::new(new, site).or_insert());
+        }
+    }
+
+    fn show_group(
+        &mut self,
+        ui: &mut Ui,
+        group: &mut EntityGroup<Entity>,
+        path: Vec<usize>,
+        current: &BTreeSet<Entity>,
+        action: &mut Option<GroupAction>,
+    ) {
+        let members = group.all_members();
+        CollapsingHeader::new(format!("{} ({})", group.name, members.len()))
+            .id_source(("entity_group", path.clone()))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut group.name);
+                    let (icon, hint) = if group.hidden {
+                        (self.icons.hide.egui(), "Show the members of this group")
+                    } else {
+                        (self.icons.show.egui(), "Hide the members of this group")
+                    };
+                    if ui.add(ImageButton::new(icon)).on_hover_text(hint).clicked() {
+                        group.hidden = !group.hidden;
+                    }
+                    ui.checkbox(&mut group.locked, "Locked")
+                        .on_hover_text("Prevent the members of this group from being selected");
+                    if ui
+                        .add(ImageButton::new(self.icons.trash.egui()))
+                        .on_hover_text("Delete this group")
+                        .clicked()
+                    {
+                        *action = Some(GroupAction::Delete(path.clone()));
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!group.locked && !members.is_empty(), Button::new("Select"))
+                        .on_hover_text("Select every member of this group and its subgroups")
+                        .clicked()
+                    {
+                        *action = Some(GroupAction::Select(members.clone()));
+                    }
+                    if ui
+                        .add_enabled(!current.is_empty(), Button::new("Add selected"))
+                        .clicked()
+                    {
+                        group.members.extend(current.iter().copied());
+                    }
+                    if ui
+                        .add_enabled(
+                            current.iter().any(|e| group.members.contains(e)),
+                            Button::new("Remove selected"),
+                        )
+                        .clicked()
+                    {
+                        group.members.retain(|e| !current.contains(e));
+                    }
+                    if ui.button("Add subgroup").clicked() {
+                        group
+                            .subgroups
+                            .push(EntityGroup::new("New group".to_owned()));
+                    }
+                });
+
+                let mut remove = None;
+                for member in &group.members {
+                    ui.horizontal(|ui| {
+                        if ui.button(self.member_label(*member)).clicked() {
+                            self.select.send(Select::new(Some(*member)));
+                        }
+                        if ui
+                            .add(ImageButton::new(self.icons.reject.egui()))
+                            .on_hover_text("Remove from this group")
+                            .clicked()
+                        {
+                            remove = Some(*member);
+                        }
+                    });
+                }
+                if let Some(member) = remove {
+                    group.members.remove(&member);
+                }
+
+                for (i, subgroup) in group.subgroups.iter_mut().enumerate() {
+                    let mut subpath = path.clone();
+                    subpath.push(i);
+                    self.show_group(ui, subgroup, subpath, current, action);
+                }
+            });
+    }
+
+    fn member_label(&self, member: Entity) -> String {
+        let id = match self.site_ids.get(member) {
+            Ok(id) => format!("#{}", id.0),
+            Err(_) => "*".to_owned(),
+        };
+        match self.names.get(member) {
+            Ok(name) => format!("{id} {}", name.0),
+            Err(_) => id,
+        }
+    }
+}
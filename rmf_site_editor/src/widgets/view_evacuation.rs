@@ -0,0 +1,120 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{EvacuationLayer, ExportEvacuation},
+    widgets::prelude::*,
+    AppState, CurrentWorkspace,
+};
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use bevy_egui::egui::{Button, CollapsingHeader, Ui};
+use futures_lite::future;
+use std::path::PathBuf;
+
+#[cfg(not(target_arch = "wasm32"))]
+use rfd::AsyncFileDialog;
+
+/// Add a widget for showing the evacuation layer of the current level and
+/// exporting it for safety documentation.
+#[derive(Default)]
+pub struct ViewEvacuationPlugin {}
+
+impl Plugin for ViewEvacuationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EvacuationExportDisplay>()
+            .add_plugins(PropertiesTilePlugin::<ViewEvacuation>::new())
+            .add_systems(Update, resolve_evacuation_export_folder);
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct EvacuationExportDisplay {
+    /// The site that will be exported and the task that is choosing the
+    /// folder to export it into
+    pub choosing_folder: Option<(Entity, Task<Option<PathBuf>>)>,
+}
+
+#[derive(SystemParam)]
+pub struct ViewEvacuation<'w> {
+    layer: ResMut<'w, EvacuationLayer>,
+    display: ResMut<'w, EvacuationExportDisplay>,
+    current_workspace: Res<'w, CurrentWorkspace>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w> WidgetSystem<Tile> for ViewEvacuation<'w> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Evacuation")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w> ViewEvacuation<'w> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.layer.visible, "Show evacuation layer")
+            .on_hover_text(
+                "Highlight the evacuation routes, emergency exits, and assembly points \
+                of the current level",
+            );
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let Some(site) = self.current_workspace.root else {
+                return;
+            };
+            let choosing = self.display.choosing_folder.is_some();
+            if ui
+                .add_enabled(!choosing, Button::new("Export Evacuation..."))
+                .on_hover_text("Export the evacuation nav graph and a plan of each level")
+                .clicked()
+            {
+                let future = AsyncComputeTaskPool::get().spawn(async move {
+                    let folder = AsyncFileDialog::new().pick_folder().await?;
+                    Some(folder.path().to_path_buf())
+                });
+                self.display.choosing_folder = Some((site, future));
+            }
+        }
+    }
+}
+
+fn resolve_evacuation_export_folder(
+    mut display: ResMut<EvacuationExportDisplay>,
+    mut export: EventWriter<ExportEvacuation>,
+) {
+    let Some((site, task)) = &mut display.choosing_folder else {
+        return;
+    };
+    let Some(result) = future::block_on(future::poll_once(task)) else {
+        return;
+    };
+    let site = *site;
+    if let Some(to_folder) = result {
+        export.send(ExportEvacuation { site, to_folder });
+    }
+    display.choosing_folder = None;
+}
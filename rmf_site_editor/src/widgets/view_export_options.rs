@@ -0,0 +1,148 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{AxisConvention, Change, ExportOptions, ExportSettings},
+    widgets::prelude::*,
+    AppState, CurrentWorkspace,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{CollapsingHeader, ComboBox, DragValue, Grid, Ui};
+
+/// Add a widget for choosing the axis convention, scale, and origin that each
+/// exporter applies to the current site.
+#[derive(Default)]
+pub struct ViewExportOptionsPlugin {}
+
+impl Plugin for ViewExportOptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PropertiesTilePlugin::<ViewExportOptions>::new());
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewExportOptions<'w, 's> {
+    current_workspace: Res<'w, CurrentWorkspace>,
+    export_settings: Query<'w, 's, &'static ExportSettings>,
+    change_export_settings: EventWriter<'w, Change<ExportSettings>>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewExportOptions<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Export Options")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewExportOptions<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let Some(site) = self.current_workspace.root else {
+            return;
+        };
+
+        let old = self.export_settings.get(site).cloned().unwrap_or_default();
+        let mut new = old.clone();
+
+        ui.label("SDF");
+        show_export_options(ui, "sdf", &mut new.sdf, true, ExportOptions::default());
+        ui.separator();
+        ui.label("Nav Graphs");
+        show_export_options(
+            ui,
+            "nav_graph",
+            &mut new.nav_graph,
+            false,
+            ExportOptions::default(),
+        );
+        ui.horizontal(|ui| {
+            ui.label("Curve resolution")
+                .on_hover_text("The longest straight piece that curved lanes are broken into");
+            ui.add(
+                DragValue::new(&mut new.curve_resolution)
+                    .clamp_range(0.01..=f32::INFINITY)
+                    .speed(0.01)
+                    .suffix(" m"),
+            );
+        });
+        ui.separator();
+        ui.label("Digital Twin");
+        show_export_options(
+            ui,
+            "digital_twin",
+            &mut new.digital_twin,
+            true,
+            ExportOptions::digital_twin(),
+        );
+
+        if new != old {
+            self.change_export_settings
+                .send(Change::new(new, site).or_insert());
+        }
+    }
+}
+
+fn show_export_options(
+    ui: &mut Ui,
+    id: &str,
+    options: &mut ExportOptions,
+    three_dimensional: bool,
+    preset: ExportOptions,
+) {
+    Grid::new(id).num_columns(2).show(ui, |ui| {
+        if three_dimensional {
+            ui.label("Axes");
+            ComboBox::from_id_source((id, "axis"))
+                .selected_text(options.axis.label())
+                .show_ui(ui, |ui| {
+                    for axis in AxisConvention::ALL {
+                        ui.selectable_value(&mut options.axis, axis, axis.label());
+                    }
+                });
+            ui.end_row();
+        }
+
+        ui.label("Scale").on_hover_text("Export units per meter");
+        ui.add(
+            DragValue::new(&mut options.scale)
+                .clamp_range(0.001..=f32::INFINITY)
+                .speed(0.01),
+        );
+        ui.end_row();
+
+        ui.label("Origin")
+            .on_hover_text("The point of the site that becomes the origin of the export");
+        ui.horizontal(|ui| {
+            let dims = if three_dimensional { 3 } else { 2 };
+            for value in options.origin.iter_mut().take(dims) {
+                ui.add(DragValue::new(value).speed(0.1).suffix(" m"));
+            }
+        });
+        ui.end_row();
+    });
+
+    if *options != preset && ui.button("Reset").clicked() {
+        *options = preset;
+    }
+}
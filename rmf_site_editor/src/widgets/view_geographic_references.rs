@@ -0,0 +1,301 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::Selection,
+    site::{
+        AddGeographicVertex, Anchor, Category, Change, CurrentLevel, ExportGeoJson,
+        GeographicReference, GeographicReferences, ImportGeoJsonVertices,
+    },
+    widgets::{prelude::*, Icons},
+    AppState, CurrentWorkspace,
+};
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use bevy_egui::egui::{Button, CollapsingHeader, DragValue, Grid, ImageButton, Ui};
+use futures_lite::future;
+
+#[cfg(not(target_arch = "wasm32"))]
+use rfd::AsyncFileDialog;
+
+/// Add a widget for editing the geographic reference points of a site,
+/// placing vertices by latitude and longitude, and exporting or importing
+/// GeoJSON.
+#[derive(Default)]
+pub struct ViewGeographicReferencesPlugin {}
+
+impl Plugin for ViewGeographicReferencesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GeoJsonExportDisplay>()
+            .add_plugins(PropertiesTilePlugin::<ViewGeographicReferences>::new())
+            .add_systems(
+                Update,
+                (resolve_geojson_export_file, resolve_geojson_import_file),
+            );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct GeoJsonExportDisplay {
+    pub choosing_file: Option<Task<Option<ExportGeoJson>>>,
+    pub choosing_import_file: Option<Task<Option<ImportGeoJsonVertices>>>,
+    /// Latitude and longitude of the next vertex to add
+    pub vertex_lat_lon: (f64, f64),
+}
+
+#[derive(SystemParam)]
+pub struct ViewGeographicReferences<'w, 's> {
+    current_workspace: Res<'w, CurrentWorkspace>,
+    references: Query<'w, 's, &'static GeographicReferences>,
+    change_references: EventWriter<'w, Change<GeographicReferences>>,
+    add_vertex: EventWriter<'w, AddGeographicVertex>,
+    current_level: Res<'w, CurrentLevel>,
+    selection: Res<'w, Selection>,
+    anchors: Query<'w, 's, &'static Anchor>,
+    display: ResMut<'w, GeoJsonExportDisplay>,
+    icons: Res<'w, Icons>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewGeographicReferences<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Geographic References")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewGeographicReferences<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let Some(site) = self.current_workspace.root else {
+            return;
+        };
+
+        let old = self.references.get(site).cloned().unwrap_or_default();
+        let mut new = old.clone();
+
+        if new.is_empty() {
+            ui.label("The geographic offset of the site is used as its origin");
+        } else {
+            let mut remove = None;
+            Grid::new("geographic_references")
+                .num_columns(4)
+                .show(ui, |ui| {
+                    ui.label("Position");
+                    ui.label("Latitude");
+                    ui.label("Longitude");
+                    ui.end_row();
+                    for (i, reference) in new.0.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            for value in &mut reference.position {
+                                ui.add(DragValue::new(value).speed(0.1).suffix(" m"));
+                            }
+                        });
+                        ui.add(
+                            DragValue::new(&mut reference.latitude)
+                                .clamp_range(-90.0..=90.0)
+                                .speed(1e-6)
+                                .max_decimals(7),
+                        );
+                        ui.add(
+                            DragValue::new(&mut reference.longitude)
+                                .clamp_range(-180.0..=180.0)
+                                .speed(1e-6)
+                                .max_decimals(7),
+                        );
+                        if ui
+                            .add(ImageButton::new(self.icons.trash.egui()))
+                            .on_hover_text("Remove this reference point")
+                            .clicked()
+                        {
+                            remove = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+            if let Some(i) = remove {
+                new.0.remove(i);
+            }
+            if new.0.len() == 1 {
+                ui.label("Add another point to also fit the rotation and scale");
+            }
+        }
+
+        let selected_anchor = self
+            .selection
+            .0
+            .and_then(|e| self.anchors.get(e).ok())
+            .map(|anchor| anchor.translation_for_category(Category::General));
+        if ui
+            .add_enabled(
+                selected_anchor.is_some(),
+                Button::new("Add from selected anchor"),
+            )
+            .on_hover_text("Add a reference point at the position of the selected anchor")
+            .clicked()
+        {
+            if let Some(position) = selected_anchor {
+                let (latitude, longitude) = new
+                    .0
+                    .last()
+                    .map(|r| (r.latitude, r.longitude))
+                    .unwrap_or_default();
+                new.0.push(GeographicReference {
+                    position,
+                    latitude,
+                    longitude,
+                });
+            }
+        }
+
+        if new != old {
+            self.change_references
+                .send(Change::new(new, site).or_insert());
+        }
+
+        ui.separator();
+        let (lat, lon) = &mut self.display.vertex_lat_lon;
+        Grid::new("geographic_vertex")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Latitude");
+                ui.add(
+                    DragValue::new(lat)
+                        .clamp_range(-90.0..=90.0)
+                        .speed(1e-6)
+                        .max_decimals(7),
+                );
+                ui.end_row();
+                ui.label("Longitude");
+                ui.add(
+                    DragValue::new(lon)
+                        .clamp_range(-180.0..=180.0)
+                        .speed(1e-6)
+                        .max_decimals(7),
+                );
+                ui.end_row();
+            });
+        let level = self.current_level.0;
+        if ui
+            .add_enabled(level.is_some(), Button::new("Add vertex"))
+            .on_hover_text("Add a vertex to the current level at this latitude and longitude")
+            .clicked()
+        {
+            if let Some(level) = level {
+                let (latitude, longitude) = self.display.vertex_lat_lon;
+                self.add_vertex.send(AddGeographicVertex {
+                    site,
+                    level,
+                    latitude,
+                    longitude,
+                });
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            ui.separator();
+            let choosing = self.display.choosing_file.is_some();
+            if ui
+                .add_enabled(!choosing, Button::new("Export GeoJSON..."))
+                .on_hover_text("Save the levels, walls, and lanes of the site in WGS84")
+                .clicked()
+            {
+                let future = AsyncComputeTaskPool::get().spawn(async move {
+                    let file = AsyncFileDialog::new()
+                        .add_filter("GeoJSON", &["geojson", "json"])
+                        .set_file_name("site.geojson")
+                        .save_file()
+                        .await?;
+                    Some(ExportGeoJson {
+                        site,
+                        to_file: file.path().to_owned(),
+                    })
+                });
+                self.display.choosing_file = Some(future);
+            }
+
+            let importing = self.display.choosing_import_file.is_some();
+            if ui
+                .add_enabled(
+                    !importing && level.is_some(),
+                    Button::new("Import vertices from GeoJSON..."),
+                )
+                .on_hover_text(
+                    "Add a vertex for every point of a GeoJSON file. Points of \
+                    levels that do not exist are added to the current level.",
+                )
+                .clicked()
+            {
+                if let Some(level) = level {
+                    let future = AsyncComputeTaskPool::get().spawn(async move {
+                        let file = AsyncFileDialog::new()
+                            .add_filter("GeoJSON", &["geojson", "json"])
+                            .pick_file()
+                            .await?;
+                        Some(ImportGeoJsonVertices {
+                            site,
+                            level,
+                            from_file: file.path().to_owned(),
+                        })
+                    });
+                    self.display.choosing_import_file = Some(future);
+                }
+            }
+        }
+    }
+}
+
+fn resolve_geojson_export_file(
+    mut display: ResMut<GeoJsonExportDisplay>,
+    mut export: EventWriter<ExportGeoJson>,
+) {
+    let Some(task) = &mut display.choosing_file else {
+        return;
+    };
+    let Some(result) = future::block_on(future::poll_once(task)) else {
+        return;
+    };
+    if let Some(request) = result {
+        export.send(request);
+    }
+    display.choosing_file = None;
+}
+
+fn resolve_geojson_import_file(
+    mut display: ResMut<GeoJsonExportDisplay>,
+    mut import: EventWriter<ImportGeoJsonVertices>,
+) {
+    let Some(task) = &mut display.choosing_import_file else {
+        return;
+    };
+    let Some(result) = future::block_on(future::poll_once(task)) else {
+        return;
+    };
+    if let Some(request) = result {
+        import.send(request);
+    }
+    display.choosing_import_file = None;
+}
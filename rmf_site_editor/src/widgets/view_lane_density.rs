@@ -0,0 +1,166 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{MultiSelection, Selection},
+    site::{Anchor, CurrentLevel, DensifyLanes, Edge, LaneMarker, SparsifyLanes, LANE_WIDTH},
+    widgets::prelude::*,
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{CollapsingHeader, DragValue, Ui};
+
+/// Anchors that are closer than this to a straight line get removed when
+/// lanes are sparsified.
+const SPARSIFY_TOLERANCE: f32 = 0.1 * LANE_WIDTH;
+
+/// Add a widget for splitting long lanes into chains of shorter lanes and
+/// merging those chains back together.
+#[derive(Default)]
+pub struct ViewLaneDensityPlugin {}
+
+impl Plugin for ViewLaneDensityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LaneDensityDisplay>()
+            .add_plugins(PropertiesTilePlugin::<ViewLaneDensity>::new());
+    }
+}
+
+#[derive(Resource)]
+pub struct LaneDensityDisplay {
+    /// The longest that a lane may be after densifying, in meters
+    pub spacing: f32,
+}
+
+impl Default for LaneDensityDisplay {
+    fn default() -> Self {
+        Self { spacing: 2.0 }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewLaneDensity<'w, 's> {
+    display: ResMut<'w, LaneDensityDisplay>,
+    lanes: Query<'w, 's, (Entity, &'static Edge<Entity>), With<LaneMarker>>,
+    anchors: Query<'w, 's, &'static Parent, With<Anchor>>,
+    selection: Res<'w, Selection>,
+    multi_selection: Res<'w, MultiSelection>,
+    current_level: Res<'w, CurrentLevel>,
+    densify: EventWriter<'w, DensifyLanes>,
+    sparsify: EventWriter<'w, SparsifyLanes>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewLaneDensity<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Lane Density")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewLaneDensity<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let lanes = self.target_lanes();
+        if lanes.selected {
+            ui.label(format!("{} selected lanes", lanes.lanes.len()));
+        } else {
+            ui.label(format!("All {} lanes on this level", lanes.lanes.len()));
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Spacing");
+            ui.add(
+                DragValue::new(&mut self.display.spacing)
+                    .clamp_range(0.1..=f32::INFINITY)
+                    .speed(0.1)
+                    .suffix(" m"),
+            )
+            .on_hover_text("The longest that each lane may be after densifying");
+        });
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("Densify")
+                .on_hover_text("Insert evenly spaced anchors along long lanes")
+                .clicked()
+            {
+                self.densify.send(DensifyLanes {
+                    lanes: lanes.lanes.clone(),
+                    spacing: self.display.spacing,
+                });
+            }
+            if ui
+                .button("Sparsify")
+                .on_hover_text("Merge chains of straight lanes back into single lanes")
+                .clicked()
+            {
+                self.sparsify.send(SparsifyLanes {
+                    lanes: lanes.lanes,
+                    tolerance: SPARSIFY_TOLERANCE,
+                });
+            }
+        });
+    }
+
+    /// The selected lanes, or all lanes of the current level if no lanes are
+    /// selected.
+    fn target_lanes(&self) -> TargetLanes {
+        let selected: Vec<Entity> = self
+            .multi_selection
+            .0
+            .iter()
+            .copied()
+            .chain(self.selection.0)
+            .filter(|e| self.lanes.contains(*e))
+            .collect();
+        if !selected.is_empty() {
+            return TargetLanes {
+                lanes: selected,
+                selected: true,
+            };
+        }
+
+        let lanes = self
+            .lanes
+            .iter()
+            .filter(|(_, edge)| {
+                edge.array().iter().all(|anchor| {
+                    self.anchors
+                        .get(*anchor)
+                        .is_ok_and(|p| Some(p.get()) == self.current_level.0)
+                })
+            })
+            .map(|(e, _)| e)
+            .collect();
+        TargetLanes {
+            lanes,
+            selected: false,
+        }
+    }
+}
+
+struct TargetLanes {
+    lanes: Vec<Entity>,
+    selected: bool,
+}
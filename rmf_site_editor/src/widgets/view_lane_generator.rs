@@ -0,0 +1,201 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{AcceptLaneProposals, CenterlineSettings, CurrentLevel, LaneProposals, ProposeLanes},
+    widgets::prelude::*,
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{Button, CollapsingHeader, DragValue, Grid, ScrollArea, Ui};
+
+/// Add a widget that proposes lanes along the corridors between the walls of
+/// the current level and lets the user accept each of them.
+#[derive(Default)]
+pub struct ViewLaneGeneratorPlugin {}
+
+impl Plugin for ViewLaneGeneratorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LaneGeneratorDisplay>()
+            .add_plugins(PropertiesTilePlugin::<ViewLaneGenerator>::new());
+    }
+}
+
+#[derive(Resource)]
+pub struct LaneGeneratorDisplay {
+    pub settings: CenterlineSettings,
+    pub within_floors: bool,
+}
+
+impl Default for LaneGeneratorDisplay {
+    fn default() -> Self {
+        Self {
+            settings: CenterlineSettings::default(),
+            within_floors: true,
+        }
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewLaneGenerator<'w> {
+    display: ResMut<'w, LaneGeneratorDisplay>,
+    proposals: ResMut<'w, LaneProposals>,
+    current_level: Res<'w, CurrentLevel>,
+    propose: EventWriter<'w, ProposeLanes>,
+    accept: EventWriter<'w, AcceptLaneProposals>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w> WidgetSystem<Tile> for ViewLaneGenerator<'w> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Lane Generator")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w> ViewLaneGenerator<'w> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let Some(level) = self.current_level.0 else {
+            ui.label("No level is selected");
+            return;
+        };
+
+        let settings = &mut self.display.settings;
+        Grid::new("lane_generator_settings")
+            .num_columns(2)
+            .show(ui, |ui| {
+                let meters = |ui: &mut Ui, label: &str, hover: &str, value: &mut f32| {
+                    ui.label(label).on_hover_text(hover);
+                    ui.add(
+                        DragValue::new(value)
+                            .clamp_range(0.01..=f32::INFINITY)
+                            .speed(0.01)
+                            .suffix(" m"),
+                    );
+                    ui.end_row();
+                };
+                meters(
+                    ui,
+                    "Resolution",
+                    "Size of the grid that samples the space between walls",
+                    &mut settings.resolution,
+                );
+                meters(
+                    ui,
+                    "Min clearance",
+                    "Corridors narrower than twice this do not get lanes",
+                    &mut settings.min_clearance,
+                );
+                meters(
+                    ui,
+                    "Max clearance",
+                    "Open areas farther than this from every wall do not get lanes",
+                    &mut settings.max_clearance,
+                );
+                meters(
+                    ui,
+                    "Min branch",
+                    "Dead ends shorter than this are left out",
+                    &mut settings.min_branch_length,
+                );
+                meters(
+                    ui,
+                    "Simplify",
+                    "How far the lanes may stray from the centerlines",
+                    &mut settings.simplify_tolerance,
+                );
+            });
+        ui.checkbox(&mut self.display.within_floors, "Only inside floors")
+            .on_hover_text("Skip the space outside of the floors of the level");
+
+        if ui
+            .button("Propose lanes")
+            .on_hover_text("Find the centerlines of the corridors of this level")
+            .clicked()
+        {
+            self.propose.send(ProposeLanes {
+                level,
+                settings: self.display.settings,
+                within_floors: self.display.within_floors,
+            });
+        }
+
+        if self.proposals.level != Some(level) || self.proposals.is_empty() {
+            return;
+        }
+
+        ui.separator();
+        ui.label(format!(
+            "{} of {} proposed lanes accepted",
+            self.proposals.accepted_count(),
+            self.proposals.lanes.len(),
+        ));
+        ui.horizontal(|ui| {
+            if ui.button("Accept all").clicked() {
+                self.proposals
+                    .lanes
+                    .iter_mut()
+                    .for_each(|l| l.accepted = true);
+            }
+            if ui.button("Reject all").clicked() {
+                self.proposals
+                    .lanes
+                    .iter_mut()
+                    .for_each(|l| l.accepted = false);
+            }
+        });
+
+        let mut highlighted = None;
+        ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+            for i in 0..self.proposals.lanes.len() {
+                let length = self.proposals.length(&self.proposals.lanes[i]);
+                let proposal = &mut self.proposals.lanes[i];
+                let response =
+                    ui.checkbox(&mut proposal.accepted, format!("Lane {i} ({length:.1} m)"));
+                if response.hovered() {
+                    highlighted = Some(i);
+                }
+            }
+        });
+        if self.proposals.highlighted != highlighted {
+            self.proposals.highlighted = highlighted;
+        }
+
+        ui.horizontal(|ui| {
+            let accepted = self.proposals.accepted_count();
+            if ui
+                .add_enabled(
+                    accepted > 0,
+                    Button::new(format!("Create {accepted} lanes")),
+                )
+                .clicked()
+            {
+                self.accept.send(AcceptLaneProposals);
+            }
+            if ui.button("Discard").clicked() {
+                self.proposals.clear();
+            }
+        });
+    }
+}
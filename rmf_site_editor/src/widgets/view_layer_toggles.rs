@@ -0,0 +1,128 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    settings::{EditorSettings, LayerToggle, SaveEditorSettings},
+    site::{Category, CurrentLevel, NameInSite},
+    widgets::prelude::*,
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{CollapsingHeader, Grid, Ui};
+
+/// The types of elements that can be hidden or locked per level.
+const LAYER_CATEGORIES: [Category; 12] = [
+    Category::Anchor,
+    Category::Wall,
+    Category::Floor,
+    Category::Door,
+    Category::Lane,
+    Category::Location,
+    Category::Measurement,
+    Category::Fiducial,
+    Category::Model,
+    Category::Drawing,
+    Category::Light,
+    Category::Zone,
+];
+
+/// Add a widget for hiding and locking every element of a type on the
+/// current level.
+#[derive(Default)]
+pub struct ViewLayerTogglesPlugin {}
+
+impl Plugin for ViewLayerTogglesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PropertiesTilePlugin::<ViewLayerToggles>::new());
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewLayerToggles<'w, 's> {
+    current_level: Res<'w, CurrentLevel>,
+    names: Query<'w, 's, &'static NameInSite>,
+    settings: ResMut<'w, EditorSettings>,
+    save_settings: EventWriter<'w, SaveEditorSettings>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewLayerToggles<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Element Layers")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewLayerToggles<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let Some(level) = self
+            .current_level
+            .0
+            .and_then(|l| self.names.get(l).ok())
+            .map(|name| name.0.clone())
+        else {
+            ui.label("No level is selected");
+            return;
+        };
+
+        let old: Vec<LayerToggle> = LAYER_CATEGORIES
+            .iter()
+            .map(|c| self.settings.layers.get(&level, *c))
+            .collect();
+        let mut new = old.clone();
+
+        Grid::new("layer_toggles").num_columns(4).show(ui, |ui| {
+            for (i, category) in LAYER_CATEGORIES.iter().enumerate() {
+                ui.label(category.label());
+                let mut visible = !new[i].hidden;
+                if ui.checkbox(&mut visible, "Visible").changed() {
+                    new[i].hidden = !visible;
+                }
+                ui.checkbox(&mut new[i].locked, "Locked")
+                    .on_hover_text("Prevent these elements from being selected");
+                if ui
+                    .button("Solo")
+                    .on_hover_text("Hide every other type of element on this level")
+                    .clicked()
+                {
+                    for (j, toggle) in new.iter_mut().enumerate() {
+                        toggle.hidden = i != j;
+                    }
+                }
+                ui.end_row();
+            }
+        });
+
+        if old.iter().any(|t| !t.is_default()) && ui.button("Show and unlock all").clicked() {
+            new.fill(LayerToggle::default());
+        }
+
+        if new != old {
+            for (category, toggle) in LAYER_CATEGORIES.iter().zip(new) {
+                self.settings.layers.set(&level, *category, toggle);
+            }
+            self.save_settings.send(SaveEditorSettings);
+        }
+    }
+}
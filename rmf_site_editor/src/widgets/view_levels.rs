@@ -16,15 +16,19 @@
 */
 
 use crate::{
+    settings::EditorSettings,
     site::{
-        Category, Change, CurrentLevel, Delete, DrawingMarker, FloorMarker, LevelElevation,
-        LevelProperties, NameInSite,
+        derive_level_elevations, AddLevel, Change, CurrentLevel, Delete, DuplicateLevel,
+        ElevationWarning, FlattenedOffset, FlattenedOffsetSettings, LevelElevation,
+        LiftLevelElevations, NameInSite, StackedLevelView,
     },
-    widgets::{prelude::*, Icons},
-    AppState, CurrentWorkspace, RecencyRanking,
+    widgets::{in_length_unit, prelude::*, Icons},
+    AppState, CurrentWorkspace,
 };
 use bevy::{ecs::system::SystemParam, prelude::*};
-use bevy_egui::egui::{CollapsingHeader, DragValue, ImageButton, Ui};
+use bevy_egui::egui::{
+    Button, CollapsingHeader, Color32, DragValue, ImageButton, RichText, Slider, Ui,
+};
 use std::cmp::{Ordering, Reverse};
 
 /// Add a plugin for viewing and editing a list of all levels
@@ -49,8 +53,28 @@ pub struct ViewLevels<'w, 's> {
     change_name: EventWriter<'w, Change<NameInSite>>,
     change_level_elevation: EventWriter<'w, Change<LevelElevation>>,
     delete: EventWriter<'w, Delete>,
-    commands: Commands<'w, 's>,
+    add_level: EventWriter<'w, AddLevel>,
+    duplicate_level: EventWriter<'w, DuplicateLevel>,
+    stacked_view: ResMut<'w, StackedLevelView>,
+    flattened: FlattenedOffsetParams<'w, 's>,
+    lifts: Query<
+        'w,
+        's,
+        (
+            &'static NameInSite,
+            &'static LiftLevelElevations<Entity>,
+            &'static Parent,
+        ),
+    >,
     app_state: Res<'w, State<AppState>>,
+    settings: Res<'w, EditorSettings>,
+}
+
+#[derive(SystemParam)]
+pub struct FlattenedOffsetParams<'w, 's> {
+    settings: Query<'w, 's, &'static mut FlattenedOffsetSettings>,
+    offsets: Query<'w, 's, &'static FlattenedOffset>,
+    change_offset: EventWriter<'w, Change<FlattenedOffset>>,
 }
 
 impl<'w, 's> WidgetSystem<Tile> for ViewLevels<'w, 's> {
@@ -74,35 +98,28 @@ impl<'w, 's> ViewLevels<'w, 's> {
 
         if !editing {
             self.display_levels.removing = false;
+            self.display_levels.confirm_delete = None;
         }
 
         if editing {
             ui.horizontal(|ui| {
                 let make_new_level = ui.button("Add").clicked();
                 let mut show_elevation = self.display_levels.new_elevation;
-                ui.add(DragValue::new(&mut show_elevation).suffix("m"))
-                    .on_hover_text("Elevation for the new level");
+                ui.add(in_length_unit(
+                    DragValue::new(&mut show_elevation),
+                    self.settings.units,
+                ))
+                .on_hover_text("Elevation for the new level");
 
                 let mut show_name = self.display_levels.new_name.clone();
                 ui.text_edit_singleline(&mut show_name)
                     .on_hover_text("Name for the new level");
 
                 if make_new_level {
-                    let new_level = self
-                        .commands
-                        .spawn((
-                            SpatialBundle::default(),
-                            LevelProperties {
-                                elevation: LevelElevation(show_elevation),
-                                name: NameInSite(show_name.clone()),
-                                ..Default::default()
-                            },
-                            Category::Level,
-                            RecencyRanking::<DrawingMarker>::default(),
-                            RecencyRanking::<FloorMarker>::default(),
-                        ))
-                        .id();
-                    self.current_level.0 = Some(new_level);
+                    self.add_level.send(AddLevel {
+                        name: show_name.clone(),
+                        elevation: show_elevation,
+                    });
                 }
 
                 self.display_levels.new_elevation = show_elevation;
@@ -139,6 +156,7 @@ impl<'w, 's> ViewLevels<'w, 's> {
             ui.horizontal(|ui| {
                 if ui.button("Select").clicked() {
                     self.display_levels.removing = false;
+                    self.display_levels.confirm_delete = None;
                 }
                 ui.label("Remove");
             });
@@ -153,7 +171,8 @@ impl<'w, 's> ViewLevels<'w, 's> {
 
         let mut any_dragging = false;
         let mut any_deleted = false;
-        for e in self.display_levels.order.iter().copied() {
+        let order = self.display_levels.order.clone();
+        for (i, e) in order.iter().copied().enumerate() {
             if let Ok((_, name, elevation)) = self.levels.get(e) {
                 let mut shown_elevation = elevation.clone().0;
                 let mut shown_name = name.clone().0;
@@ -164,8 +183,7 @@ impl<'w, 's> ViewLevels<'w, 's> {
                             .on_hover_text("Remove this level")
                             .clicked()
                         {
-                            self.delete.send(Delete::new(e).and_dependents());
-                            any_deleted = true;
+                            self.display_levels.confirm_delete = Some(e);
                         }
                     } else if editing {
                         if ui.radio(Some(e) == **self.current_level, "").clicked() {
@@ -174,7 +192,10 @@ impl<'w, 's> ViewLevels<'w, 's> {
                     }
 
                     let r = ui
-                        .add(DragValue::new(&mut shown_elevation).suffix("m"))
+                        .add(in_length_unit(
+                            DragValue::new(&mut shown_elevation),
+                            self.settings.units,
+                        ))
                         .on_hover_text("Elevation of the level");
                     if r.dragged() || r.has_focus() {
                         any_dragging = true;
@@ -182,8 +203,65 @@ impl<'w, 's> ViewLevels<'w, 's> {
 
                     ui.text_edit_singleline(&mut shown_name)
                         .on_hover_text("Name of the level");
+
+                    if editing && !self.display_levels.removing {
+                        // Levels are ordered by elevation, so moving a level
+                        // swaps its elevation with its neighbor.
+                        let above = i.checked_sub(1).and_then(|i| order.get(i));
+                        let below = order.get(i + 1);
+                        for (neighbor, icon, tooltip) in [
+                            (above, &self.icons.layer_up, "Move this level up"),
+                            (below, &self.icons.layer_down, "Move this level down"),
+                        ] {
+                            let neighbor = neighbor
+                                .and_then(|n| self.levels.get(*n).ok())
+                                .filter(|(_, _, n)| n.0 != elevation.0);
+                            if ui
+                                .add_enabled(neighbor.is_some(), ImageButton::new(icon.egui()))
+                                .on_hover_text(tooltip)
+                                .clicked()
+                            {
+                                if let Some((n, _, n_elevation)) = neighbor {
+                                    shown_elevation = n_elevation.0;
+                                    self.change_level_elevation
+                                        .send(Change::new(elevation.clone(), n));
+                                }
+                            }
+                        }
+
+                        if ui
+                            .button("Duplicate")
+                            .on_hover_text("Make a copy of this level and everything on it")
+                            .clicked()
+                        {
+                            self.duplicate_level.send(DuplicateLevel { level: e });
+                        }
+                    }
+
+                    if !editing {
+                        let mut opacity = self.stacked_view.opacity(e);
+                        ui.add(Slider::new(&mut opacity, 0.0..=1.0).show_value(false))
+                            .on_hover_text("Opacity of the level");
+                        if opacity != self.stacked_view.opacity(e) {
+                            self.stacked_view.opacity.insert(e, opacity);
+                        }
+                    }
                 });
 
+                if self.display_levels.confirm_delete == Some(e) {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Delete {} and everything on it?", name.0));
+                        if ui.button("Delete").clicked() {
+                            self.delete.send(Delete::new(e).and_dependents());
+                            self.display_levels.confirm_delete = None;
+                            any_deleted = true;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.display_levels.confirm_delete = None;
+                        }
+                    });
+                }
+
                 if shown_name != name.0 {
                     self.change_name
                         .send(Change::new(NameInSite(shown_name), e));
@@ -200,6 +278,142 @@ impl<'w, 's> ViewLevels<'w, 's> {
         if any_deleted {
             self.display_levels.removing = false;
         }
+
+        if editing {
+            ui.separator();
+            self.show_flattened_offsets(ui);
+            self.show_derived_elevations(ui);
+        }
+    }
+
+    fn show_derived_elevations(&mut self, ui: &mut Ui) {
+        let site = self.current_workspace.root;
+        let levels: Vec<_> = self
+            .display_levels
+            .order
+            .iter()
+            .filter_map(|e| self.levels.get(*e).ok())
+            .map(|(e, _, elevation)| (e, elevation.0))
+            .collect();
+        let lifts = self
+            .lifts
+            .iter()
+            .filter(|(_, _, parent)| Some(parent.get()) == site)
+            .map(|(name, elevations, _)| (name.0.as_str(), &elevations.0));
+        let derived = derive_level_elevations(levels.iter().copied(), lifts);
+        if derived.elevations.is_empty() && derived.warnings.is_empty() {
+            return;
+        }
+
+        let level_name = |e: &Entity| {
+            self.levels
+                .get(*e)
+                .map(|(_, name, _)| name.0.clone())
+                .unwrap_or_else(|_| "<Unknown>".to_owned())
+        };
+        let changes = derived.changes(levels);
+        CollapsingHeader::new(format!("Elevation warnings ({})", derived.warnings.len()))
+            .default_open(false)
+            .show(ui, |ui| {
+                for warning in &derived.warnings {
+                    let text = match warning {
+                        ElevationWarning::LiftsDisagree { level, heights } => {
+                            let heights: Vec<_> = heights
+                                .iter()
+                                .map(|(lift, h)| format!("{lift}: {h:.2}m"))
+                                .collect();
+                            format!(
+                                "Lifts disagree about the floor height of {} ({})",
+                                level_name(level),
+                                heights.join(", "),
+                            )
+                        }
+                        ElevationWarning::DiffersFromLifts {
+                            level,
+                            elevation,
+                            derived,
+                        } => format!(
+                            "{} has an elevation of {elevation:.2}m but its lifts put it at {derived:.2}m",
+                            level_name(level),
+                        ),
+                        ElevationWarning::SharedElevation { levels, elevation } => {
+                            let names: Vec<_> = levels.iter().map(level_name).collect();
+                            format!(
+                                "{} all have an elevation of {elevation:.2}m",
+                                names.join(", "),
+                            )
+                        }
+                    };
+                    ui.label(RichText::new(text).color(Color32::YELLOW));
+                }
+            });
+
+        if ui
+            .add_enabled(!changes.is_empty(), Button::new("Apply lift floor heights"))
+            .on_hover_text("Set the elevation of each level to the floor height given by its lifts")
+            .clicked()
+        {
+            for (level, elevation) in changes {
+                self.change_level_elevation
+                    .send(Change::new(LevelElevation(elevation), level));
+            }
+        }
+    }
+
+    fn show_flattened_offsets(&mut self, ui: &mut Ui) {
+        let units = self.settings.units;
+        let Some(site) = self.current_workspace.root else {
+            return;
+        };
+        let Ok(mut settings) = self.flattened.settings.get_mut(site) else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            let mut automatic = settings.automatic;
+            ui.checkbox(&mut automatic, "Automatic flattened offsets")
+                .on_hover_text(
+                    "Lay the levels out side by side without overlapping when the site \
+                    is flattened onto the ground",
+                );
+            let mut margin = settings.margin;
+            ui.add(in_length_unit(
+                DragValue::new(&mut margin)
+                    .clamp_range(0.0..=f32::INFINITY)
+                    .speed(0.1),
+                units,
+            ))
+            .on_hover_text("Space between neighboring levels");
+            if automatic != settings.automatic || margin != settings.margin {
+                settings.automatic = automatic;
+                settings.margin = margin;
+            }
+        });
+
+        let automatic = settings.automatic;
+        CollapsingHeader::new("Flattened offsets")
+            .default_open(false)
+            .show(ui, |ui| {
+                for e in self.display_levels.order.iter().rev() {
+                    let Ok((_, name, _)) = self.levels.get(*e) else {
+                        continue;
+                    };
+                    let offset = self.flattened.offsets.get(*e).copied().unwrap_or_default();
+                    let mut new_offset = offset;
+                    ui.horizontal(|ui| {
+                        ui.add_enabled_ui(!automatic, |ui| {
+                            for value in new_offset.0.iter_mut() {
+                                ui.add(in_length_unit(DragValue::new(value).speed(0.1), units));
+                            }
+                        });
+                        ui.label(&name.0);
+                    });
+                    if new_offset != offset {
+                        self.flattened
+                            .change_offset
+                            .send(Change::new(new_offset, *e).or_insert());
+                    }
+                }
+            });
     }
 }
 
@@ -210,6 +424,8 @@ pub struct LevelDisplay {
     pub order: Vec<Entity>,
     pub freeze: bool,
     pub removing: bool,
+    /// A level that the user asked to delete, waiting for confirmation
+    pub confirm_delete: Option<Entity>,
 }
 
 impl Default for LevelDisplay {
@@ -220,6 +436,7 @@ impl Default for LevelDisplay {
             order: Vec::new(),
             freeze: false,
             removing: false,
+            confirm_delete: None,
         }
     }
 }
@@ -0,0 +1,119 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+use crate::{
+    site::{generate_site, ElementStats, MapStats},
+    widgets::prelude::*,
+    AppState, CurrentWorkspace,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{Button, CollapsingHeader, Grid, Ui};
+
+/// Add a widget that summarizes how many elements each level of the current
+/// site has.
+#[derive(Default)]
+pub struct ViewMapStatsPlugin {}
+
+impl Plugin for ViewMapStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MapStatsDisplay>()
+            .add_plugins(PropertiesTilePlugin::<ViewMapStats>::new());
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct MapStatsDisplay {
+    /// The site whose stats were computed most recently, and its stats
+    pub stats: Option<(Entity, MapStats)>,
+}
+
+#[derive(SystemParam)]
+pub struct ViewMapStats<'w> {
+    display: Res<'w, MapStatsDisplay>,
+    current_workspace: Res<'w, CurrentWorkspace>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w> WidgetSystem<Tile> for ViewMapStats<'w> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        let Some(site) = params.current_workspace.root else {
+            return;
+        };
+        let mut refresh = false;
+        CollapsingHeader::new("Map Statistics")
+            .default_open(false)
+            .show(ui, |ui| {
+                refresh = params.show_widget(site, ui);
+            });
+
+        if refresh {
+            // Stats are computed from the saved form of the site, so this
+            // needs access to the whole world.
+            match generate_site(world, site) {
+                Ok(data) => {
+                    world.resource_mut::<MapStatsDisplay>().stats = Some((site, data.map_stats()));
+                }
+                Err(err) => error!("Unable to compute map statistics: {err:?}"),
+            }
+        }
+    }
+}
+
+impl<'w> ViewMapStats<'w> {
+    /// Returns true if the stats should be computed again.
+    pub fn show_widget(&self, site: Entity, ui: &mut Ui) -> bool {
+        let refresh = ui
+            .add(Button::new("Refresh"))
+            .on_hover_text("Count the elements of the current site")
+            .clicked();
+
+        let Some((_, stats)) = self.display.stats.as_ref().filter(|(e, _)| *e == site) else {
+            ui.label("Press refresh to compute the statistics of this site");
+            return refresh;
+        };
+
+        ui.label(format!("Nav graphs: {}", stats.graphs));
+        show_element_stats(ui, "map_stats_total", "Total", &stats.total);
+        for (name, level) in &stats.levels {
+            show_element_stats(ui, ("map_stats_level", name), name, level);
+        }
+        refresh
+    }
+}
+
+fn show_element_stats(ui: &mut Ui, id: impl std::hash::Hash, label: &str, stats: &ElementStats) {
+    ui.separator();
+    ui.label(label);
+    Grid::new(id).num_columns(2).show(ui, |ui| {
+        for (name, value) in [
+            ("Vertices", stats.vertices.to_string()),
+            ("Lanes", stats.lanes.to_string()),
+            ("Lane length", format!("{:.1} m", stats.lane_length)),
+            ("Walls", stats.walls.to_string()),
+            ("Floor area", format!("{:.1} m²", stats.floor_area)),
+            ("Models", stats.models.to_string()),
+            ("Chargers", stats.chargers.to_string()),
+        ] {
+            ui.label(name);
+            ui.label(value);
+            ui.end_row();
+        }
+    });
+}
@@ -0,0 +1,232 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{DeleteMultiSelection, MultiSelection, Select, TransformMultiSelection},
+    settings::EditorSettings,
+    site::{Angle, Category, Change, EntityTags, ExportSelection, LaneMarker, ReverseLanes},
+    widgets::{length_field, prelude::*},
+    AppState,
+};
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use bevy_egui::egui::{Button, CollapsingHeader, DragValue, Grid, Ui};
+use futures_lite::future;
+#[cfg(not(target_arch = "wasm32"))]
+use rfd::AsyncFileDialog;
+use std::path::PathBuf;
+
+/// Add a widget for moving, rotating, and deleting a group of selected
+/// elements all at once.
+#[derive(Default)]
+pub struct ViewMultiSelectionPlugin {}
+
+impl Plugin for ViewMultiSelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MultiSelectionDisplay>()
+            .init_resource::<ExportSelectionDisplay>()
+            .add_plugins(PropertiesTilePlugin::<ViewMultiSelection>::new())
+            .add_systems(
+                Update,
+                resolve_export_selection_file.run_if(AppState::in_displaying_mode()),
+            );
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewMultiSelection<'w, 's> {
+    multi_selection: Res<'w, MultiSelection>,
+    lanes: Query<'w, 's, (), With<LaneMarker>>,
+    tags: Query<'w, 's, Option<&'static EntityTags>, With<Category>>,
+    change_tags: EventWriter<'w, Change<EntityTags>>,
+    reverse_lanes: EventWriter<'w, ReverseLanes>,
+    display: ResMut<'w, MultiSelectionDisplay>,
+    export_display: ResMut<'w, ExportSelectionDisplay>,
+    transform: EventWriter<'w, TransformMultiSelection>,
+    delete: EventWriter<'w, DeleteMultiSelection>,
+    select: EventWriter<'w, Select>,
+    app_state: Res<'w, State<AppState>>,
+    settings: Res<'w, EditorSettings>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewMultiSelection<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        if params.multi_selection.is_empty() {
+            return;
+        }
+        CollapsingHeader::new("Group Selection")
+            .default_open(true)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewMultiSelection<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        ui.label(format!("{} elements selected", self.multi_selection.len()))
+            .on_hover_text(
+                "Ctrl+Click toggles an element in the group. \
+                Shift+Drag selects everything inside of a box.",
+            );
+
+        Grid::new("multi_selection_transform").show(ui, |ui| {
+            ui.label("x");
+            ui.label("y");
+            ui.label("yaw");
+            ui.end_row();
+
+            let units = self.settings.units;
+            ui.add(length_field(&mut self.display.translation.x, units).speed(0.01));
+            ui.add(length_field(&mut self.display.translation.y, units).speed(0.01));
+            ui.add(
+                DragValue::new(&mut self.display.yaw_degrees)
+                    .speed(1.0)
+                    .suffix("°"),
+            );
+            ui.end_row();
+        });
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("Apply")
+                .on_hover_text("Move and rotate the group by these amounts")
+                .clicked()
+            {
+                self.transform.send(TransformMultiSelection {
+                    translation: self.display.translation,
+                    yaw: Angle::Deg(self.display.yaw_degrees),
+                });
+            }
+            if ui.button("Reset").clicked() {
+                *self.display = MultiSelectionDisplay::default();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button("Delete All").clicked() {
+                self.delete.send(DeleteMultiSelection);
+            }
+            if ui.button("Deselect").clicked() {
+                self.select.send(Select::new(None));
+            }
+        });
+
+        let lanes: Vec<Entity> = self
+            .multi_selection
+            .iter()
+            .copied()
+            .filter(|e| self.lanes.contains(*e))
+            .collect();
+        if !lanes.is_empty()
+            && ui
+                .button(format!("Reverse {} Lanes", lanes.len()))
+                .on_hover_text("Flip the direction of every selected lane")
+                .clicked()
+        {
+            self.reverse_lanes.send(ReverseLanes { lanes });
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Tag");
+            ui.text_edit_singleline(&mut self.display.tag);
+        });
+        let tag = EntityTags::parse_tag(&self.display.tag);
+        ui.horizontal(|ui| {
+            let add = ui
+                .add_enabled(tag.is_some(), Button::new("Add to All"))
+                .on_hover_text("Give this tag to every selected element")
+                .clicked();
+            let remove = ui
+                .add_enabled(tag.is_some(), Button::new("Remove from All"))
+                .on_hover_text("Take this tag away from every selected element")
+                .clicked();
+            let Some(tag) = tag.filter(|_| add || remove) else {
+                return;
+            };
+            for e in self.multi_selection.iter() {
+                let Ok(tags) = self.tags.get(*e) else {
+                    continue;
+                };
+                let mut new_tags = tags.cloned().unwrap_or_default();
+                let changed = if add {
+                    new_tags.0.insert(tag.clone())
+                } else {
+                    new_tags.0.remove(&tag)
+                };
+                if changed {
+                    self.change_tags.send(Change::new(new_tags, *e).or_insert());
+                }
+            }
+        });
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let choosing = self.export_display.choosing_file.is_some();
+            if ui
+                .add_enabled(!choosing, Button::new("Export Selection..."))
+                .on_hover_text("Save only the selected elements into a site file of their own")
+                .clicked()
+            {
+                let future = AsyncComputeTaskPool::get().spawn(async move {
+                    let file = AsyncFileDialog::new()
+                        .add_filter("Site", &["site.ron", "site.json"])
+                        .set_file_name("selection.site.ron")
+                        .save_file()
+                        .await?;
+                    Some(file.path().to_owned())
+                });
+                self.export_display.choosing_file = Some(future);
+            }
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ExportSelectionDisplay {
+    pub choosing_file: Option<Task<Option<PathBuf>>>,
+}
+
+fn resolve_export_selection_file(
+    mut display: ResMut<ExportSelectionDisplay>,
+    mut export: EventWriter<ExportSelection>,
+) {
+    let Some(task) = &mut display.choosing_file else {
+        return;
+    };
+    let Some(result) = future::block_on(future::poll_once(task)) else {
+        return;
+    };
+    if let Some(path) = result {
+        export.send(ExportSelection(path));
+    }
+    display.choosing_file = None;
+}
+
+#[derive(Resource, Default)]
+pub struct MultiSelectionDisplay {
+    pub translation: Vec2,
+    pub yaw_degrees: f32,
+    /// The tag that gets added to or removed from every selected element
+    pub tag: String,
+}
@@ -15,20 +15,33 @@
  *
 */
 
-use crate::{occupancy::CalculateGrid, widgets::prelude::*, AppState};
-use bevy::prelude::*;
-use bevy_egui::egui::{CollapsingHeader, DragValue, Ui};
-use std::collections::HashSet;
+use crate::{
+    occupancy::CalculateGrid,
+    site::{ExportOccupancyGrid, OccupancyGridOptions},
+    widgets::prelude::*,
+    AppState, CurrentWorkspace,
+};
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use bevy_egui::egui::{Button, CollapsingHeader, DragValue, Grid, Ui};
+use futures_lite::future;
+use std::{collections::HashSet, path::PathBuf};
+
+#[cfg(not(target_arch = "wasm32"))]
+use rfd::AsyncFileDialog;
 
 /// Add a widget that provides a button for producing an occupancy grid
-/// visualization.
+/// visualization and for exporting nav2 occupancy grids of each level.
 #[derive(Default)]
 pub struct ViewOccupancyPlugin {}
 
 impl Plugin for ViewOccupancyPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.init_resource::<OccupancyDisplay>()
-            .add_plugins(PropertiesTilePlugin::<ViewOccupancy>::new());
+            .add_plugins(PropertiesTilePlugin::<ViewOccupancy>::new())
+            .add_systems(Update, resolve_occupancy_grid_export_folder);
     }
 }
 
@@ -36,6 +49,7 @@ impl Plugin for ViewOccupancyPlugin {
 pub struct ViewOccupancy<'w> {
     calculate_grid: EventWriter<'w, CalculateGrid>,
     display_occupancy: ResMut<'w, OccupancyDisplay>,
+    current_workspace: Res<'w, CurrentWorkspace>,
     app_state: Res<'w, State<AppState>>,
 }
 
@@ -82,16 +96,103 @@ impl<'w> ViewOccupancy<'w> {
                 }
             }
         });
+
+        ui.separator();
+        ui.label("Export");
+        let options = &mut self.display_occupancy.export_options;
+        Grid::new("occupancy_grid_export_options")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Resolution")
+                    .on_hover_text("The size of each cell");
+                ui.add(
+                    DragValue::new(&mut options.resolution)
+                        .clamp_range(0.005..=1.0)
+                        .speed(0.005)
+                        .suffix(" m"),
+                );
+                ui.end_row();
+
+                ui.label("Padding")
+                    .on_hover_text("Free space that is added around each level");
+                ui.add(
+                    DragValue::new(&mut options.padding)
+                        .clamp_range(0.0..=f32::INFINITY)
+                        .speed(0.1)
+                        .suffix(" m"),
+                );
+                ui.end_row();
+
+                ui.label("Wall thickness");
+                ui.add(
+                    DragValue::new(&mut options.wall_thickness)
+                        .clamp_range(0.0..=f32::INFINITY)
+                        .speed(0.01)
+                        .suffix(" m"),
+                );
+                ui.end_row();
+            });
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let Some(site) = self.current_workspace.root else {
+                return;
+            };
+            let choosing = self.display_occupancy.choosing_folder.is_some();
+            if ui
+                .add_enabled(!choosing, Button::new("Export Grids..."))
+                .on_hover_text(
+                    "Export a PGM image and a map.yaml file of the walls and static \
+                    models of each level",
+                )
+                .clicked()
+            {
+                let future = AsyncComputeTaskPool::get().spawn(async move {
+                    let folder = AsyncFileDialog::new().pick_folder().await?;
+                    Some(folder.path().to_path_buf())
+                });
+                self.display_occupancy.choosing_folder = Some((site, future));
+            }
+        }
     }
 }
 
 #[derive(Resource)]
 pub struct OccupancyDisplay {
     pub cell_size: f32,
+    pub export_options: OccupancyGridOptions,
+    /// The site that the grids will be exported from and the task that is
+    /// choosing the folder to export them into
+    pub choosing_folder: Option<(Entity, Task<Option<PathBuf>>)>,
 }
 
 impl Default for OccupancyDisplay {
     fn default() -> Self {
-        Self { cell_size: 0.5 }
+        Self {
+            cell_size: 0.5,
+            export_options: OccupancyGridOptions::default(),
+            choosing_folder: None,
+        }
+    }
+}
+
+fn resolve_occupancy_grid_export_folder(
+    mut display: ResMut<OccupancyDisplay>,
+    mut export: EventWriter<ExportOccupancyGrid>,
+) {
+    let Some((site, task)) = &mut display.choosing_folder else {
+        return;
+    };
+    let Some(result) = future::block_on(future::poll_once(task)) else {
+        return;
+    };
+    let site = *site;
+    if let Some(to_folder) = result {
+        export.send(ExportOccupancyGrid {
+            site,
+            options: display.export_options.clone(),
+            to_folder,
+        });
     }
+    display.choosing_folder = None;
 }
@@ -0,0 +1,245 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{Change, CurrentLevel, ExportPlan, NameInSite, PaperSpace},
+    widgets::prelude::*,
+    AppState,
+};
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use bevy_egui::egui::{CollapsingHeader, ComboBox, DragValue, Grid, TextEdit, Ui};
+use futures_lite::future;
+
+#[cfg(not(target_arch = "wasm32"))]
+use rfd::AsyncFileDialog;
+
+/// Standard page sizes in millimeters, in portrait orientation
+const PAGE_SIZES: [(&str, [f32; 2]); 7] = [
+    ("A4", [210.0, 297.0]),
+    ("A3", [297.0, 420.0]),
+    ("A2", [420.0, 594.0]),
+    ("A1", [594.0, 841.0]),
+    ("A0", [841.0, 1189.0]),
+    ("Letter", [215.9, 279.4]),
+    ("Tabloid", [279.4, 431.8]),
+];
+
+/// Add a widget for editing how the current level is laid out on paper and
+/// for exporting it as a plan image or PDF.
+#[derive(Default)]
+pub struct ViewPaperSpacePlugin {}
+
+impl Plugin for ViewPaperSpacePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PaperSpaceDisplay>()
+            .add_plugins(PropertiesTilePlugin::<ViewPaperSpace>::new());
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewPaperSpace<'w, 's> {
+    current_level: Res<'w, CurrentLevel>,
+    paper_spaces: Query<'w, 's, &'static PaperSpace>,
+    level_names: Query<'w, 's, &'static NameInSite>,
+    change_paper_space: EventWriter<'w, Change<PaperSpace>>,
+    display_paper_space: ResMut<'w, PaperSpaceDisplay>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewPaperSpace<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Plan Export")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewPaperSpace<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let Some(level) = self.current_level.0 else {
+            ui.label("No level selected");
+            return;
+        };
+
+        let old = self.paper_spaces.get(level).cloned().unwrap_or_default();
+        let mut new = old.clone();
+
+        Grid::new("paper_space").num_columns(2).show(ui, |ui| {
+            ui.label("Background");
+            ui.color_edit_button_rgb(&mut new.background_color);
+            ui.end_row();
+
+            ui.label("Page");
+            ui.horizontal(|ui| {
+                let landscape = new.page_size[0] > new.page_size[1];
+                let current = PAGE_SIZES
+                    .iter()
+                    .find(|(_, size)| {
+                        let mut size = *size;
+                        if landscape {
+                            size.reverse();
+                        }
+                        (size[0] - new.page_size[0]).abs() < 0.1
+                            && (size[1] - new.page_size[1]).abs() < 0.1
+                    })
+                    .map(|(name, _)| *name)
+                    .unwrap_or("Custom");
+                ComboBox::from_id_source("paper_space_page_size")
+                    .selected_text(current)
+                    .show_ui(ui, |ui| {
+                        for (name, size) in PAGE_SIZES {
+                            let mut size = size;
+                            if landscape {
+                                size.reverse();
+                            }
+                            if ui.selectable_label(name == current, name).clicked() {
+                                new.page_size = size;
+                            }
+                        }
+                    });
+                if ui
+                    .button(if landscape { "Landscape" } else { "Portrait" })
+                    .on_hover_text("Rotate the page")
+                    .clicked()
+                {
+                    new.page_size.reverse();
+                }
+            });
+            ui.end_row();
+
+            ui.label("Size");
+            ui.horizontal(|ui| {
+                for value in &mut new.page_size {
+                    ui.add(
+                        DragValue::new(value)
+                            .clamp_range(10.0..=f32::INFINITY)
+                            .speed(1.0)
+                            .suffix(" mm"),
+                    );
+                }
+            });
+            ui.end_row();
+
+            ui.label("Margin");
+            ui.add(
+                DragValue::new(&mut new.margin)
+                    .clamp_range(0.0..=f32::INFINITY)
+                    .speed(0.5)
+                    .suffix(" mm"),
+            );
+            ui.end_row();
+        });
+
+        ui.separator();
+        ui.label("Title Block");
+        let level_name = self
+            .level_names
+            .get(level)
+            .map(|n| n.0.clone())
+            .unwrap_or_default();
+        Grid::new("paper_space_title_block")
+            .num_columns(2)
+            .show(ui, |ui| {
+                let title = &mut new.title_block;
+                for (label, value, hint) in [
+                    ("Project", &mut title.project, ""),
+                    ("Level", &mut title.level, level_name.as_str()),
+                    ("Revision", &mut title.revision, ""),
+                    ("Date", &mut title.date, "YYYY-MM-DD"),
+                ] {
+                    ui.label(label);
+                    ui.add(TextEdit::singleline(value).hint_text(hint));
+                    ui.end_row();
+                }
+            });
+
+        if new != old {
+            self.change_paper_space
+                .send(Change::new(new, level).or_insert());
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            ui.separator();
+            if ui
+                .button("Export Plan...")
+                .on_hover_text("Save the current level as an SVG image or a PDF")
+                .clicked()
+            {
+                match &self.display_paper_space.choosing_file {
+                    Some(_) => {
+                        warn!("A file is already being chosen!");
+                    }
+                    None => {
+                        let future = AsyncComputeTaskPool::get().spawn(async move {
+                            let file = match AsyncFileDialog::new()
+                                .add_filter("SVG image", &["svg"])
+                                .add_filter("PDF document", &["pdf"])
+                                .set_file_name(format!("{level_name}.svg"))
+                                .save_file()
+                                .await
+                            {
+                                Some(file) => file,
+                                None => return None,
+                            };
+
+                            Some(ExportPlan {
+                                level,
+                                to_file: file.path().to_owned(),
+                            })
+                        });
+                        self.display_paper_space.choosing_file = Some(future);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct PaperSpaceDisplay {
+    pub choosing_file: Option<Task<Option<ExportPlan>>>,
+}
+
+pub fn resolve_plan_export_file(
+    mut paper_space_display: ResMut<PaperSpaceDisplay>,
+    mut export_plan: EventWriter<ExportPlan>,
+) {
+    if 'resolved: {
+        if let Some(task) = &mut paper_space_display.choosing_file {
+            if let Some(result) = future::block_on(future::poll_once(task)) {
+                if let Some(request) = result {
+                    export_plan.send(request);
+                }
+
+                break 'resolved true;
+            }
+        }
+        false
+    } {
+        paper_space_display.choosing_file = None;
+    }
+}
@@ -0,0 +1,186 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::Selection,
+    site::{Anchor, NameInSite, NavGraphMarker, PathPreview, PlannedPath},
+    widgets::{prelude::*, Icons, SelectorWidget},
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{Button, CollapsingHeader, ComboBox, DragValue, Grid, ImageButton, Ui};
+
+/// Add a widget for planning a path between two waypoints of the current
+/// level.
+#[derive(Default)]
+pub struct ViewPathPreviewPlugin {}
+
+impl Plugin for ViewPathPreviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PropertiesTilePlugin::<ViewPathPreview>::new());
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewPathPreview<'w, 's> {
+    preview: ResMut<'w, PathPreview>,
+    selection: Res<'w, Selection>,
+    anchors: Query<'w, 's, (), With<Anchor>>,
+    nav_graphs: Query<'w, 's, (Entity, &'static NameInSite), With<NavGraphMarker>>,
+    selector: SelectorWidget<'w, 's>,
+    icons: Res<'w, Icons>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewPathPreview<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Path Preview")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewPathPreview<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let selected_anchor = self.selection.0.filter(|e| self.anchors.contains(*e));
+        Grid::new("view_path_preview")
+            .num_columns(3)
+            .show(ui, |ui| {
+                for (label, is_start) in [("Start", true), ("Goal", false)] {
+                    ui.label(label);
+                    let current = if is_start {
+                        self.preview.start
+                    } else {
+                        self.preview.goal
+                    };
+                    match current {
+                        Some(anchor) => self.selector.show_widget(anchor, ui),
+                        None => {
+                            ui.label("none");
+                        }
+                    }
+                    let mut new = current;
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(selected_anchor.is_some(), Button::new("Use selected"))
+                            .on_hover_text("Use the selected waypoint")
+                            .clicked()
+                        {
+                            new = selected_anchor;
+                        }
+                        if current.is_some()
+                            && ui
+                                .add(ImageButton::new(self.icons.trash.egui()))
+                                .on_hover_text("Clear")
+                                .clicked()
+                        {
+                            new = None;
+                        }
+                    });
+                    if new != current {
+                        if is_start {
+                            self.preview.start = new;
+                        } else {
+                            self.preview.goal = new;
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if self.preview.start.is_some()
+            && self.preview.goal.is_some()
+            && ui.button("Swap start and goal").clicked()
+        {
+            let preview = &mut *self.preview;
+            std::mem::swap(&mut preview.start, &mut preview.goal);
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Graph");
+            let selected_text = self
+                .preview
+                .nav_graph
+                .and_then(|g| self.nav_graphs.get(g).ok())
+                .map(|(_, name)| name.0.clone())
+                .unwrap_or_else(|| "All lanes".to_owned());
+            let mut nav_graph = self.preview.nav_graph;
+            ComboBox::from_id_source("path_preview_nav_graph")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut nav_graph, None, "All lanes");
+                    for (e, name) in &self.nav_graphs {
+                        ui.selectable_value(&mut nav_graph, Some(e), &name.0);
+                    }
+                });
+            if nav_graph != self.preview.nav_graph {
+                self.preview.nav_graph = nav_graph;
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Robot speed");
+            let mut speed = self.preview.robot_speed;
+            ui.add(
+                DragValue::new(&mut speed)
+                    .clamp_range(0.05..=5.0)
+                    .speed(0.05)
+                    .suffix(" m/s"),
+            );
+            if speed != self.preview.robot_speed {
+                self.preview.robot_speed = speed;
+            }
+        });
+
+        match &self.preview.path {
+            Some(PlannedPath::Found {
+                waypoints,
+                length,
+                duration,
+                doors,
+            }) => {
+                ui.label(format!(
+                    "{} waypoints, {length:.2} m, about {duration:.1} s",
+                    waypoints.len()
+                ))
+                .on_hover_text(
+                    "The time assumes the robot always drives at full speed, \
+                    limited by the speed limits of the lanes, and waits for \
+                    each door along the way to open",
+                );
+                if !doors.is_empty() {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label("Through doors");
+                        for door in doors {
+                            self.selector.show_widget(*door, ui);
+                        }
+                    });
+                }
+            }
+            Some(PlannedPath::Unreachable) => {
+                ui.label("The goal cannot be reached from the start");
+            }
+            None => {}
+        }
+    }
+}
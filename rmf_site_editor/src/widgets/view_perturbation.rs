@@ -0,0 +1,148 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    keyboard::DebugMode,
+    site::{ExportPerturbedNavGraphs, PerturbationOptions},
+    widgets::prelude::*,
+    AppState, CurrentWorkspace,
+};
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use bevy_egui::egui::{Button, CollapsingHeader, DragValue, Grid, Slider, Ui};
+use futures_lite::future;
+use std::path::PathBuf;
+
+#[cfg(not(target_arch = "wasm32"))]
+use rfd::AsyncFileDialog;
+
+/// Add a developer tool for exporting the nav graphs of seeded random
+/// variants of the current site. The tool is only visible in debug mode.
+#[derive(Default)]
+pub struct ViewPerturbationPlugin {}
+
+impl Plugin for ViewPerturbationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PerturbationDisplay>()
+            .add_plugins(PropertiesTilePlugin::<ViewPerturbation>::new())
+            .add_systems(Update, resolve_perturbation_export_folder);
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct PerturbationDisplay {
+    pub options: PerturbationOptions,
+    /// The site that the variants will be generated from and the task that is
+    /// choosing the folder to export them into
+    pub choosing_folder: Option<(Entity, Task<Option<PathBuf>>)>,
+}
+
+#[derive(SystemParam)]
+pub struct ViewPerturbation<'w> {
+    display: ResMut<'w, PerturbationDisplay>,
+    current_workspace: Res<'w, CurrentWorkspace>,
+    debug_mode: Res<'w, DebugMode>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w> WidgetSystem<Tile> for ViewPerturbation<'w> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor || !params.debug_mode.0 {
+            return;
+        }
+        CollapsingHeader::new("Perturbed Variants")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w> ViewPerturbation<'w> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let options = &mut self.display.options;
+        Grid::new("perturbation_options")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Seed");
+                ui.add(DragValue::new(&mut options.seed));
+                ui.end_row();
+
+                ui.label("Displacement")
+                    .on_hover_text("How far each vertex may move along each axis");
+                ui.add(
+                    DragValue::new(&mut options.max_displacement)
+                        .clamp_range(0.0..=f32::INFINITY)
+                        .speed(0.01)
+                        .suffix(" m"),
+                );
+                ui.end_row();
+
+                ui.label("Closed lanes")
+                    .on_hover_text("The fraction of lanes that get closed in each variant");
+                ui.add(Slider::new(&mut options.lane_closure_ratio, 0.0..=1.0));
+                ui.end_row();
+
+                ui.label("Variants");
+                ui.add(DragValue::new(&mut options.variants).clamp_range(1..=1000));
+                ui.end_row();
+            });
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let Some(site) = self.current_workspace.root else {
+                return;
+            };
+            let choosing = self.display.choosing_folder.is_some();
+            if ui
+                .add_enabled(!choosing, Button::new("Export Variants..."))
+                .on_hover_text("Export the nav graphs of every variant into a folder")
+                .clicked()
+            {
+                let future = AsyncComputeTaskPool::get().spawn(async move {
+                    let folder = AsyncFileDialog::new().pick_folder().await?;
+                    Some(folder.path().to_path_buf())
+                });
+                self.display.choosing_folder = Some((site, future));
+            }
+        }
+    }
+}
+
+fn resolve_perturbation_export_folder(
+    mut display: ResMut<PerturbationDisplay>,
+    mut export: EventWriter<ExportPerturbedNavGraphs>,
+) {
+    let Some((site, task)) = &mut display.choosing_folder else {
+        return;
+    };
+    let Some(result) = future::block_on(future::poll_once(task)) else {
+        return;
+    };
+    let site = *site;
+    if let Some(to_folder) = result {
+        export.send(ExportPerturbedNavGraphs {
+            site,
+            options: display.options.clone(),
+            to_folder,
+        });
+    }
+    display.choosing_folder = None;
+}
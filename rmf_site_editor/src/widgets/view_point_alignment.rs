@@ -0,0 +1,145 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{AlignmentFrame, AlignmentPoints, ApplyPointAlignment, NameInSite, PointAlignment},
+    widgets::{prelude::*, SelectorWidget},
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{Button, CollapsingHeader, Grid, Ui};
+
+/// Add a widget for aligning a level or drawing with another one by picking
+/// pairs of points that should end up on top of each other.
+#[derive(Default)]
+pub struct ViewPointAlignmentPlugin {}
+
+impl Plugin for ViewPointAlignmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PropertiesTilePlugin::<ViewPointAlignment>::new());
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewPointAlignment<'w, 's> {
+    alignment: ResMut<'w, PointAlignment>,
+    points: AlignmentPoints<'w, 's>,
+    names: Query<'w, 's, &'static NameInSite>,
+    selector: SelectorWidget<'w, 's>,
+    apply: EventWriter<'w, ApplyPointAlignment>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewPointAlignment<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Point Alignment")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewPointAlignment<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        ui.label(
+            "Select a vertex of the level or drawing to move, then the vertex \
+            where it should end up.",
+        );
+        let selected = (**self.selector.selection).filter(|e| self.points.frame_of(*e).is_some());
+
+        let mut remove = None;
+        Grid::new("point_alignment_pairs")
+            .num_columns(3)
+            .show(ui, |ui| {
+                ui.label("Move");
+                ui.label("Onto");
+                ui.end_row();
+                for (i, pair) in self.alignment.pairs.iter_mut().enumerate() {
+                    for point in pair.iter_mut() {
+                        ui.horizontal(|ui| {
+                            if let Some(e) = point {
+                                self.selector.show_widget(*e, ui);
+                            }
+                            if ui
+                                .add_enabled(selected.is_some(), Button::new("Use selected"))
+                                .clicked()
+                            {
+                                *point = selected;
+                            }
+                        });
+                    }
+                    if ui.button("❌").on_hover_text("Remove this pair").clicked() {
+                        remove = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(i) = remove {
+            self.alignment.pairs.remove(i);
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Add pair").clicked() {
+                self.alignment.pairs.push([None, None]);
+            }
+            if ui
+                .add_enabled(!self.alignment.pairs.is_empty(), Button::new("Clear"))
+                .clicked()
+            {
+                self.alignment.pairs.clear();
+            }
+        });
+        ui.checkbox(&mut self.alignment.fit_scale, "Fit scale")
+            .on_hover_text("Also stretch or shrink the side that gets moved");
+
+        ui.separator();
+        let fit = self.points.fit(&self.alignment);
+        match &fit {
+            Ok(fit) => {
+                let (kind, e) = match fit.frame {
+                    AlignmentFrame::Level(e) => ("level", e),
+                    AlignmentFrame::Drawing(e) => ("drawing", e),
+                };
+                let name = self
+                    .names
+                    .get(e)
+                    .map(|n| n.0.clone())
+                    .unwrap_or_else(|_| format!("{e:?}"));
+                ui.label(format!("Moves the {kind} {name}"));
+                if matches!(fit.frame, AlignmentFrame::Level(_)) {
+                    ui.label(format!(
+                        "Rotation {:.2}°, scale {:.4}",
+                        fit.alignment.rotation.to_degrees(),
+                        fit.alignment.scale,
+                    ));
+                }
+                ui.label(format!("Remaining error {:.3} m", fit.rms_error));
+            }
+            Err(err) => {
+                ui.label(err);
+            }
+        }
+        if ui.add_enabled(fit.is_ok(), Button::new("Align")).clicked() {
+            self.apply.send(ApplyPointAlignment);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{Change, LoadReferenceSite, Pose, ReferenceSite},
+    widgets::{inspector::InspectPoseComponent, prelude::*, Icons},
+    AppState, CurrentWorkspace,
+};
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use bevy_egui::egui::{CollapsingHeader, ImageButton, Ui};
+use futures_lite::future;
+
+#[cfg(not(target_arch = "wasm32"))]
+use rfd::AsyncFileDialog;
+
+/// Add a widget for loading other sites as read-only reference underlays of
+/// the current site.
+#[derive(Default)]
+pub struct ViewReferencesPlugin {}
+
+impl Plugin for ViewReferencesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReferenceDisplay>()
+            .add_plugins(PropertiesTilePlugin::<ViewReferences>::new());
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewReferences<'w, 's> {
+    references: Query<'w, 's, (Entity, &'static ReferenceSite, &'static Pose)>,
+    icons: Res<'w, Icons>,
+    current_workspace: Res<'w, CurrentWorkspace>,
+    display_references: ResMut<'w, ReferenceDisplay>,
+    change_pose: EventWriter<'w, Change<Pose>>,
+    commands: Commands<'w, 's>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewReferences<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("References")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewReferences<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let Some(host) = self.current_workspace.root else {
+            return;
+        };
+
+        let mut references: Vec<_> = self
+            .references
+            .iter()
+            .filter(|(_, reference, ..)| reference.host == host)
+            .collect();
+        references.sort_by_key(|(e, ..)| *e);
+
+        if references.is_empty() {
+            ui.label("No reference sites loaded");
+        }
+
+        for (e, reference, pose) in references {
+            ui.push_id(e, |ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add(ImageButton::new(self.icons.trash.egui()))
+                        .on_hover_text("Unload this reference")
+                        .clicked()
+                    {
+                        self.commands.entity(e).despawn_recursive();
+                    }
+
+                    let mut is_visible = reference.visible;
+                    if ui
+                        .checkbox(&mut is_visible, "")
+                        .on_hover_text(if is_visible {
+                            "Hide this reference"
+                        } else {
+                            "Show this reference"
+                        })
+                        .changed()
+                    {
+                        self.commands.entity(e).insert(ReferenceSite {
+                            visible: is_visible,
+                            ..reference.clone()
+                        });
+                    }
+
+                    let response = ui.label(&reference.name);
+                    if let Some(source) = &reference.source {
+                        response.on_hover_text(source.display().to_string());
+                    }
+                });
+
+                CollapsingHeader::new("Alignment")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        if let Some(new_pose) = InspectPoseComponent::new(pose).show(ui) {
+                            self.change_pose.send(Change::new(new_pose, e));
+                        }
+                    });
+            });
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            ui.separator();
+            if ui.button("Add Reference...").clicked() {
+                match &self.display_references.choosing_file {
+                    Some(_) => {
+                        warn!("A file is already being chosen!");
+                    }
+                    None => {
+                        let future = AsyncComputeTaskPool::get().spawn(async move {
+                            let file = match AsyncFileDialog::new()
+                                .add_filter("Site", &["ron", "json", "yaml"])
+                                .pick_file()
+                                .await
+                            {
+                                Some(file) => file,
+                                None => return None,
+                            };
+
+                            let source = file.path().to_owned();
+                            match read_reference_site(&source, &file.read().await) {
+                                Ok(site) => Some(LoadReferenceSite {
+                                    host,
+                                    site,
+                                    source: Some(source),
+                                }),
+                                Err(err) => {
+                                    error!("Unable to load reference site:\n{err}");
+                                    None
+                                }
+                            }
+                        });
+                        self.display_references.choosing_file = Some(future);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_reference_site(
+    path: &std::path::Path,
+    data: &[u8],
+) -> Result<rmf_site_format::Site, String> {
+    let file_name = path
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if file_name.ends_with(".building.yaml") {
+        rmf_site_format::legacy::building_map::BuildingMap::from_bytes(data)
+            .map_err(|err| format!("{err:?}"))?
+            .to_site()
+            .map_err(|err| format!("{err:?}"))
+    } else if file_name.ends_with(".json") {
+        rmf_site_format::Site::from_bytes_json(data).map_err(|err| err.to_string())
+    } else {
+        rmf_site_format::Site::from_bytes_ron(data).map_err(|err| err.to_string())
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ReferenceDisplay {
+    pub choosing_file: Option<Task<Option<LoadReferenceSite>>>,
+}
+
+pub fn resolve_reference_site_file(
+    mut reference_display: ResMut<ReferenceDisplay>,
+    mut load_reference: EventWriter<LoadReferenceSite>,
+) {
+    if 'resolved: {
+        if let Some(task) = &mut reference_display.choosing_file {
+            if let Some(result) = future::block_on(future::poll_once(task)) {
+                if let Some(request) = result {
+                    load_reference.send(request);
+                }
+
+                break 'resolved true;
+            }
+        }
+        false
+    } {
+        reference_display.choosing_file = None;
+    }
+}
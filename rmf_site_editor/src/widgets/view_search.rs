@@ -0,0 +1,303 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{set_multi_selection, FocusCamera, MultiSelection, Select, Selected, Selection},
+    site::{
+        level_of_element, Affiliation, Category, CurrentLevel, Edge, EntityTags, LevelElevation,
+        LocationTags, NameInSite, Path, Point, SiteID,
+    },
+    widgets::prelude::*,
+    AppState, CurrentWorkspace,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{Button, CollapsingHeader, ComboBox, ScrollArea, Ui};
+use std::collections::BTreeSet;
+
+const SEARCH_RESULTS_HEIGHT: f32 = 250.0;
+
+/// Add a widget that lists every named element of the current site and lets
+/// the user filter them by name, type, level, tag, and parameter values.
+/// Clicking on a result selects it and moves the camera to it.
+#[derive(Default)]
+pub struct ViewSearchPlugin {}
+
+impl Plugin for ViewSearchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SearchDisplay>()
+            .add_plugins(PropertiesTilePlugin::<ViewSearch>::new());
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SearchDisplay {
+    /// Space-separated terms that every result needs to contain
+    pub query: String,
+    /// Only show elements of this category
+    pub category: Option<Category>,
+    /// Only show elements that have this tag
+    pub tag: Option<String>,
+}
+
+#[derive(SystemParam)]
+pub struct ViewSearch<'w, 's> {
+    current_workspace: Res<'w, CurrentWorkspace>,
+    app_state: Res<'w, State<AppState>>,
+    display: ResMut<'w, SearchDisplay>,
+    elements: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static NameInSite,
+            &'static Category,
+            Option<&'static SiteID>,
+            Option<&'static LocationTags>,
+            Option<&'static Affiliation<Entity>>,
+            Option<&'static EntityTags>,
+        ),
+    >,
+    names: Query<'w, 's, &'static NameInSite>,
+    parents: Query<'w, 's, &'static Parent>,
+    levels: Query<'w, 's, (), With<LevelElevation>>,
+    anchors: Query<
+        'w,
+        's,
+        (
+            Option<&'static Point<Entity>>,
+            Option<&'static Edge<Entity>>,
+            Option<&'static Path<Entity>>,
+        ),
+    >,
+    global_tfs: Query<'w, 's, &'static GlobalTransform>,
+    current_level: ResMut<'w, CurrentLevel>,
+    selection: ResMut<'w, Selection>,
+    multi_selection: ResMut<'w, MultiSelection>,
+    selected: Query<'w, 's, &'static mut Selected>,
+    select: EventWriter<'w, Select>,
+    focus: EventWriter<'w, FocusCamera>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewSearch<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Search")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+struct SearchResult {
+    entity: Entity,
+    label: String,
+    level: Option<Entity>,
+}
+
+impl<'w, 's> ViewSearch<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let Some(site) = self.current_workspace.root else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label("Find");
+            ui.text_edit_singleline(&mut self.display.query)
+                .on_hover_text("Match names, types, levels, tags, and parameter values");
+        });
+
+        let mut categories = BTreeSet::new();
+        let mut all_tags = BTreeSet::new();
+        let mut results = Vec::new();
+        let terms: Vec<String> = self
+            .display
+            .query
+            .split_whitespace()
+            .map(|t| t.to_lowercase())
+            .collect();
+        for (e, name, category, site_id, location_tags, affiliation, tags) in &self.elements {
+            if e == site || !AncestorIter::new(&self.parents, e).any(|p| p == site) {
+                continue;
+            }
+            categories.insert(*category);
+            all_tags.extend(tags.iter().flat_map(|t| t.iter()));
+            if self.display.category.is_some_and(|c| c != *category) {
+                continue;
+            }
+            if let Some(tag) = &self.display.tag {
+                if !tags.is_some_and(|t| t.has(tag)) {
+                    continue;
+                }
+            }
+
+            let level = level_of_element(e, &self.parents, &self.levels, &self.anchors);
+            let level_name = level.and_then(|l| self.names.get(l).ok());
+            let mut haystack = format!("{} {}", name.0, category.label());
+            if let Some(level_name) = level_name {
+                haystack += &format!(" {}", level_name.0);
+            }
+            if let Some(site_id) = site_id {
+                haystack += &format!(" #{}", site_id.0);
+            }
+            for tag in location_tags.iter().flat_map(|t| t.iter()) {
+                haystack += &format!(" {}", tag.label());
+            }
+            for tag in tags.iter().flat_map(|t| t.iter()) {
+                haystack += &format!(" {tag}");
+            }
+            if let Some(desc) = affiliation
+                .and_then(|a| a.0)
+                .and_then(|a| self.names.get(a).ok())
+            {
+                haystack += &format!(" {}", desc.0);
+            }
+            let haystack = haystack.to_lowercase();
+            if !terms.iter().all(|t| haystack.contains(t.as_str())) {
+                continue;
+            }
+
+            let mut label = format!("{} [{}]", name.0, category.label());
+            if let Some(level_name) = level_name {
+                label += &format!(" on {}", level_name.0);
+            }
+            results.push(SearchResult {
+                entity: e,
+                label,
+                level,
+            });
+        }
+        results.sort_by(|a, b| a.label.cmp(&b.label));
+
+        ui.horizontal(|ui| {
+            ui.label("Type");
+            ComboBox::from_id_source("search_category")
+                .selected_text(self.display.category.map_or("All", |c| c.label()))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.display.category, None, "All");
+                    for category in &categories {
+                        ui.selectable_value(
+                            &mut self.display.category,
+                            Some(*category),
+                            category.label(),
+                        );
+                    }
+                });
+        });
+
+        if !all_tags.is_empty() || self.display.tag.is_some() {
+            ui.horizontal(|ui| {
+                ui.label("Tag");
+                ComboBox::from_id_source("search_tag")
+                    .selected_text(self.display.tag.as_deref().unwrap_or("Any"))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.display.tag, None, "Any");
+                        for tag in &all_tags {
+                            ui.selectable_value(
+                                &mut self.display.tag,
+                                Some((*tag).clone()),
+                                tag.as_str(),
+                            );
+                        }
+                    });
+            });
+        }
+
+        let select_all = ui
+            .add_enabled(
+                results.len() > 1,
+                Button::new(format!("Select all {} results", results.len())),
+            )
+            .on_hover_text("Add every result to the group selection");
+        if select_all.clicked() {
+            let group = results.iter().map(|r| r.entity).collect();
+            set_multi_selection(
+                group,
+                None,
+                &mut self.multi_selection,
+                &mut self.selection,
+                &mut self.selected,
+            );
+        }
+
+        let mut clicked = None;
+        ScrollArea::vertical()
+            .max_height(SEARCH_RESULTS_HEIGHT)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                if results.is_empty() {
+                    ui.label("Nothing matches the search");
+                }
+                for result in &results {
+                    let is_selected = self.selection.0 == Some(result.entity)
+                        || self.multi_selection.contains(&result.entity);
+                    if ui
+                        .selectable_label(is_selected, &result.label)
+                        .on_hover_text("Ctrl+click to add or remove it from the selection")
+                        .clicked()
+                    {
+                        clicked =
+                            Some((result.entity, result.level, ui.input(|i| i.modifiers.ctrl)));
+                    }
+                }
+            });
+
+        if let Some((entity, level, ctrl)) = clicked {
+            // The selection service looks at the Ctrl key by itself, so a
+            // Ctrl+click toggles the element within the group selection.
+            self.select.send(Select::new(Some(entity)));
+            if !ctrl {
+                if level.is_some() && level != self.current_level.0 {
+                    self.current_level.0 = level;
+                }
+                if let Some(point) = self.focus_point(entity) {
+                    self.focus.send(FocusCamera(point));
+                }
+            }
+        }
+    }
+
+    fn anchors_of(&self, e: Entity) -> Vec<Entity> {
+        match self.anchors.get(e) {
+            Ok((Some(point), _, _)) => vec![point.0],
+            Ok((_, Some(edge), _)) => edge.array().to_vec(),
+            Ok((_, _, Some(path))) => path.0.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Where the camera should look to see an element. Elements that are
+    /// drawn between anchors are centered on their anchors.
+    fn focus_point(&self, e: Entity) -> Option<Vec3> {
+        let anchors = self.anchors_of(e);
+        if anchors.is_empty() {
+            return self.global_tfs.get(e).ok().map(|tf| tf.translation());
+        }
+        let points: Vec<Vec3> = anchors
+            .iter()
+            .filter_map(|a| self.global_tfs.get(*a).ok())
+            .map(|tf| tf.translation())
+            .collect();
+        if points.is_empty() {
+            return None;
+        }
+        Some(points.iter().sum::<Vec3>() / points.len() as f32)
+    }
+}
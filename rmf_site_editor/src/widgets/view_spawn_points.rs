@@ -0,0 +1,114 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{AnchorScope, AnchorSelection, Select, Selection},
+    site::{NewSpawnPoint, RobotSpawnPoints, SpawnPointDraft},
+    widgets::prelude::*,
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{CollapsingHeader, Grid, TextEdit, Ui};
+
+/// Add a widget for placing robot spawn points and listing the spawn points
+/// of each fleet.
+#[derive(Default)]
+pub struct ViewSpawnPointsPlugin {}
+
+impl Plugin for ViewSpawnPointsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PropertiesTilePlugin::<ViewSpawnPoints>::new());
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewSpawnPoints<'w, 's> {
+    spawn_points: RobotSpawnPoints<'w, 's>,
+    draft: ResMut<'w, SpawnPointDraft>,
+    anchor_selection: AnchorSelection<'w, 's>,
+    selection: Res<'w, Selection>,
+    select: EventWriter<'w, Select>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewSpawnPoints<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Robot Spawn Points")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewSpawnPoints<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        Grid::new("spawn_point_draft")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Fleet");
+                ui.text_edit_singleline(&mut self.draft.fleet);
+                ui.end_row();
+
+                ui.label("Robot Type");
+                ui.add(
+                    TextEdit::singleline(&mut self.draft.robot_type).hint_text("e.g. TinyRobot"),
+                );
+                ui.end_row();
+            });
+        if ui
+            .button("Place Spawn Point")
+            .on_hover_text("Click on a vertex or an empty spot to place a new robot there")
+            .clicked()
+        {
+            self.anchor_selection
+                .create_point::<NewSpawnPoint>(false, AnchorScope::General);
+        }
+
+        ui.separator();
+        let fleets = self.spawn_points.by_fleet();
+        if fleets.is_empty() {
+            ui.label("No robot spawn points");
+        }
+        for (fleet, spawns) in &fleets {
+            let fleet_label = if fleet.is_empty() {
+                "<No fleet>"
+            } else {
+                fleet.as_str()
+            };
+            ui.label(format!("{fleet_label} ({})", spawns.len()));
+            ui.indent(("spawn_point_fleet", fleet), |ui| {
+                for (e, spawn) in spawns {
+                    let selected = self.selection.0 == Some(*e);
+                    if ui
+                        .selectable_label(
+                            selected,
+                            format!("{} [{}]", spawn.robot_name, spawn.robot_type),
+                        )
+                        .clicked()
+                    {
+                        self.select.send(Select::new(Some(*e)));
+                    }
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,132 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::{MultiSelection, Selection},
+    site::{
+        clipboard_from_template, Clipboard, DeleteTemplate, SaveSelectionAsTemplate,
+        TemplateLibrary,
+    },
+    widgets::{prelude::*, Icons},
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{Button, CollapsingHeader, ImageButton, TextEdit, Ui};
+
+/// Add a widget for saving selections as reusable templates and stamping them
+/// into the current level.
+#[derive(Default)]
+pub struct ViewTemplatesPlugin {}
+
+impl Plugin for ViewTemplatesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TemplateDisplay>()
+            .add_plugins(PropertiesTilePlugin::<ViewTemplates>::new());
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewTemplates<'w> {
+    library: Res<'w, TemplateLibrary>,
+    clipboard: ResMut<'w, Clipboard>,
+    selection: Res<'w, Selection>,
+    multi_selection: Res<'w, MultiSelection>,
+    display_templates: ResMut<'w, TemplateDisplay>,
+    save_template: EventWriter<'w, SaveSelectionAsTemplate>,
+    delete_template: EventWriter<'w, DeleteTemplate>,
+    icons: Res<'w, Icons>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w> WidgetSystem<Tile> for ViewTemplates<'w> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Templates")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w> ViewTemplates<'w> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        if self.library.templates.is_empty() {
+            ui.label("No templates saved yet");
+        }
+
+        for (index, (path, template)) in self.library.templates.iter().enumerate() {
+            ui.push_id(index, |ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add(ImageButton::new(self.icons.trash.egui()))
+                        .on_hover_text("Delete this template")
+                        .clicked()
+                    {
+                        self.delete_template.send(DeleteTemplate { index });
+                    }
+                    if ui
+                        .button("Stamp")
+                        .on_hover_text(
+                            "Load this template into the clipboard. \
+                            Press Ctrl+V to stamp it at the cursor.",
+                        )
+                        .clicked()
+                    {
+                        *self.clipboard = clipboard_from_template(template);
+                        info!(
+                            "Template [{}] is ready to be stamped with Ctrl+V",
+                            template.name
+                        );
+                    }
+                    ui.label(&template.name)
+                        .on_hover_text(path.display().to_string());
+                });
+            });
+        }
+
+        ui.separator();
+        let has_selection = self.selection.0.is_some() || !self.multi_selection.is_empty();
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.display_templates.new_name)
+                    .hint_text("Template name")
+                    .desired_width(120.0),
+            );
+            let name = self.display_templates.new_name.trim().to_owned();
+            if ui
+                .add_enabled(
+                    has_selection && !name.is_empty(),
+                    Button::new("Save Selection"),
+                )
+                .on_hover_text("Save the selected anchors, walls, doors, and lanes as a template")
+                .clicked()
+            {
+                self.save_template.send(SaveSelectionAsTemplate { name });
+                self.display_templates.new_name.clear();
+            }
+        });
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct TemplateDisplay {
+    pub new_name: String,
+}
@@ -0,0 +1,164 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::Selection,
+    site::{Anchor, ControlTrafficPreview, TrafficPreviewState},
+    widgets::{prelude::*, Icons, SelectorWidget},
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_egui::egui::{Button, CollapsingHeader, DragValue, ImageButton, Ui};
+
+/// Add a widget for previewing robot traffic along the nav graphs of the
+/// current level.
+#[derive(Default)]
+pub struct ViewTrafficPreviewPlugin {}
+
+impl Plugin for ViewTrafficPreviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PropertiesTilePlugin::<ViewTrafficPreview>::new());
+    }
+}
+
+#[derive(SystemParam)]
+pub struct ViewTrafficPreview<'w, 's> {
+    preview: ResMut<'w, TrafficPreviewState>,
+    control: EventWriter<'w, ControlTrafficPreview>,
+    selection: Res<'w, Selection>,
+    anchors: Query<'w, 's, (), With<Anchor>>,
+    selector: SelectorWidget<'w, 's>,
+    icons: Res<'w, Icons>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for ViewTrafficPreview<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        if *params.app_state.get() != AppState::SiteEditor {
+            return;
+        }
+        CollapsingHeader::new("Traffic Preview")
+            .default_open(false)
+            .show(ui, |ui| {
+                params.show_widget(ui);
+            });
+    }
+}
+
+impl<'w, 's> ViewTrafficPreview<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        ui.label("Robots start at");
+        let mut remove = None;
+        for (i, anchor) in self.preview.spawn_points.iter().enumerate() {
+            ui.horizontal(|ui| {
+                self.selector.show_widget(*anchor, ui);
+                if ui
+                    .add(ImageButton::new(self.icons.trash.egui()))
+                    .on_hover_text("Do not start a robot here")
+                    .clicked()
+                {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove {
+            self.preview.spawn_points.remove(i);
+        }
+
+        let selected_anchor = self
+            .selection
+            .0
+            .filter(|e| self.anchors.contains(*e))
+            .filter(|e| !self.preview.spawn_points.contains(e));
+        if ui
+            .add_enabled(
+                selected_anchor.is_some(),
+                Button::new("Add selected waypoint"),
+            )
+            .clicked()
+        {
+            self.preview.spawn_points.extend(selected_anchor);
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Robot speed");
+            ui.add(
+                DragValue::new(&mut self.preview.robot_speed)
+                    .clamp_range(0.05..=5.0)
+                    .speed(0.05)
+                    .suffix(" m/s"),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.label("Playback");
+            ui.add(
+                DragValue::new(&mut self.preview.speed)
+                    .clamp_range(0.1..=10.0)
+                    .speed(0.1)
+                    .suffix("x"),
+            );
+        });
+
+        ui.horizontal(|ui| {
+            if self.preview.is_running() {
+                if ui.button("Stop").clicked() {
+                    self.control.send(ControlTrafficPreview::Stop);
+                }
+                let pause = if self.preview.paused {
+                    "Resume"
+                } else {
+                    "Pause"
+                };
+                if ui.button(pause).clicked() {
+                    self.preview.paused = !self.preview.paused;
+                }
+            } else if ui
+                .add_enabled(!self.preview.spawn_points.is_empty(), Button::new("Play"))
+                .on_hover_text("Drive robots along the lanes of the current level")
+                .clicked()
+            {
+                self.control.send(ControlTrafficPreview::Start);
+            }
+        });
+
+        let Some(preview) = &self.preview.preview else {
+            return;
+        };
+        if preview.graph.is_empty() {
+            ui.label("There are no lanes on this level");
+        }
+        let waiting = preview
+            .robots
+            .iter()
+            .filter(|r| r.waiting_for.is_some())
+            .count();
+        ui.label(format!(
+            "{} robots, {} waiting, {} deadlocked",
+            preview.robots.len(),
+            waiting,
+            preview.deadlocked().len(),
+        ));
+        if !preview.head_on.is_empty() {
+            ui.label(format!(
+                "{} lanes where robots met head-on",
+                preview.head_on.len()
+            ))
+            .on_hover_text("These lanes are highlighted in red");
+        }
+    }
+}
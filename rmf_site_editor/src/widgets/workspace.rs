@@ -15,7 +15,7 @@
  *
 */
 
-use crate::widgets::{FileMenu, MenuDisabled, MenuEvent, MenuItem, TextMenuItem};
+use crate::widgets::{FileMenu, MenuEvent, MenuItem, TextMenuItem};
 use crate::{AppState, CreateNewWorkspace, WorkspaceLoader, WorkspaceSaver};
 use bevy::prelude::*;
 
@@ -60,12 +60,6 @@ impl FromWorld for WorkspaceMenu {
             .spawn(MenuItem::Text(TextMenuItem::new("Open").shortcut("Ctrl-O")))
             .set_parent(file_menu)
             .id();
-
-        // Saving is not enabled in wasm
-        if cfg!(target_arch = "wasm32") {
-            world.entity_mut(save).insert(MenuDisabled);
-            world.entity_mut(save_as).insert(MenuDisabled);
-        }
         Self {
             new,
             save,
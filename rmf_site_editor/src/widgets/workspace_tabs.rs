@@ -0,0 +1,270 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    site::{ChangeCurrentSite, DefaultFile, MapSaveFailed, MapSaved, NameOfSite, ReferenceSite},
+    widgets::{prelude::*, HeaderTilePlugin},
+    AppState, CurrentWorkspace, UnsavedChanges, WorkspaceSaver,
+};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Button, Ui},
+    EguiContexts,
+};
+use bevy_impulse::{Promise, PromiseState};
+
+/// Add a header tile that shows one tab for each site that is currently open,
+/// allowing users to switch between them or close them.
+#[derive(Default)]
+pub struct WorkspaceTabsPlugin {}
+
+impl Plugin for WorkspaceTabsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingSiteClose>()
+            .add_plugins(HeaderTilePlugin::<WorkspaceTabs>::new())
+            .add_systems(Update, (confirm_site_close, close_saved_site));
+    }
+}
+
+/// A site whose tab was closed while it had unsaved changes, waiting for the
+/// user to decide what to do with them.
+#[derive(Resource, Default)]
+pub struct PendingSiteClose {
+    pub site: Option<Entity>,
+    /// The save that was requested for the site. The site gets closed once
+    /// it has been saved. If the save is cancelled or fails then the user is
+    /// asked again.
+    saving: Option<Promise<()>>,
+}
+
+#[derive(SystemParam)]
+pub struct WorkspaceTabs<'w, 's> {
+    sites: Query<
+        'w,
+        's,
+        (Entity, &'static NameOfSite, Option<&'static DefaultFile>),
+        Without<ReferenceSite>,
+    >,
+    current_workspace: Res<'w, CurrentWorkspace>,
+    change_current_site: EventWriter<'w, ChangeCurrentSite>,
+    commands: Commands<'w, 's>,
+    app_state: Res<'w, State<AppState>>,
+    unsaved: Res<'w, UnsavedChanges>,
+    pending_close: ResMut<'w, PendingSiteClose>,
+}
+
+impl<'w, 's> WidgetSystem<Tile> for WorkspaceTabs<'w, 's> {
+    fn show(_: Tile, ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) {
+        let mut params = state.get_mut(world);
+        match params.app_state.get() {
+            AppState::SiteEditor | AppState::SiteVisualizer => {}
+            _ => return,
+        }
+        params.show_widget(ui);
+    }
+}
+
+impl<'w, 's> WorkspaceTabs<'w, 's> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        let mut sites: Vec<_> = self.sites.iter().collect();
+        if sites.len() < 2 {
+            // Tabs are only useful once there is more than one open site
+            return;
+        }
+        // Entities are never reused while a site is open, so this keeps the
+        // tabs in the order that the sites were opened.
+        sites.sort_by_key(|(e, _, _)| *e);
+
+        let mut close = None;
+        for (e, name, file) in &sites {
+            let is_current = self.current_workspace.root == Some(*e);
+            let response = ui.selectable_label(is_current, &name.0);
+            let response = match file {
+                Some(file) => response.on_hover_text(file.0.display().to_string()),
+                None => response.on_hover_text("This site has not been saved to a file yet"),
+            };
+            if response.clicked() && !is_current {
+                self.change_current_site.send(ChangeCurrentSite {
+                    site: *e,
+                    level: None,
+                    scenario: None,
+                });
+            }
+
+            if ui
+                .add(Button::new("x").small())
+                .on_hover_text("Close this site")
+                .clicked()
+            {
+                close = Some(*e);
+            }
+            ui.separator();
+        }
+
+        let Some(close) = close else {
+            return;
+        };
+        if self.unsaved.has_unsaved_changes(close) {
+            // Only the current site can be saved, so show the site while
+            // asking what to do with its changes.
+            if self.current_workspace.root != Some(close) {
+                self.change_current_site.send(ChangeCurrentSite {
+                    site: close,
+                    level: None,
+                    scenario: None,
+                });
+            }
+            *self.pending_close = PendingSiteClose {
+                site: Some(close),
+                saving: None,
+            };
+        } else {
+            close_site(
+                close,
+                sites.iter().map(|(e, _, _)| *e),
+                &self.current_workspace,
+                &mut self.change_current_site,
+                &mut self.commands,
+            );
+        }
+    }
+}
+
+/// Close a site. If it is the current site then focus moves onto a
+/// neighboring site first so the editor is never left without a workspace.
+fn close_site(
+    site: Entity,
+    open_sites: impl IntoIterator<Item = Entity>,
+    current_workspace: &CurrentWorkspace,
+    change_current_site: &mut EventWriter<ChangeCurrentSite>,
+    commands: &mut Commands,
+) {
+    if current_workspace.root == Some(site) {
+        let mut open_sites: Vec<_> = open_sites.into_iter().collect();
+        open_sites.sort();
+        if let Some(next) = open_sites.into_iter().find(|e| *e != site) {
+            change_current_site.send(ChangeCurrentSite {
+                site: next,
+                level: None,
+                scenario: None,
+            });
+        }
+    }
+    commands.entity(site).despawn_recursive();
+}
+
+fn confirm_site_close(
+    mut egui_context: EguiContexts,
+    mut pending: ResMut<PendingSiteClose>,
+    mut workspace_saver: WorkspaceSaver,
+    sites: Query<(Entity, &NameOfSite), Without<ReferenceSite>>,
+    current_workspace: Res<CurrentWorkspace>,
+    mut change_current_site: EventWriter<ChangeCurrentSite>,
+    mut commands: Commands,
+) {
+    let Some(site) = pending.site else {
+        return;
+    };
+    if let Some(saving) = &mut pending.saving {
+        // Wait while a file is being picked or the site is being saved
+        if matches!(
+            saving.peek(),
+            PromiseState::Pending | PromiseState::Available(_)
+        ) {
+            return;
+        }
+        // The file dialog was cancelled
+        pending.saving = None;
+    }
+    let Ok((_, name)) = sites.get(site) else {
+        pending.site = None;
+        return;
+    };
+    let is_current = current_workspace.root == Some(site);
+
+    let mut save = false;
+    let mut close = false;
+    let mut cancel = false;
+    egui::Window::new("Unsaved Changes")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0., 0.))
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.label(format!("{} has changes that have not been saved.", name.0));
+            ui.label("Save them before closing it?");
+            ui.add_space(10.);
+            ui.horizontal(|ui| {
+                save = ui
+                    .add_enabled(is_current, Button::new("Save and close"))
+                    .clicked();
+                close = ui.button("Close without saving").clicked();
+                cancel = ui.button("Cancel").clicked();
+            });
+        });
+
+    if save {
+        // The site is closed by close_saved_site once it has been saved, so
+        // nothing is lost if the save asks for a file and the user cancels.
+        pending.saving = Some(
+            workspace_saver
+                .save_to_default_file_impulse()
+                .take_response(),
+        );
+    } else if close {
+        pending.site = None;
+        close_site(
+            site,
+            sites.iter().map(|(e, _)| e),
+            &current_workspace,
+            &mut change_current_site,
+            &mut commands,
+        );
+    } else if cancel {
+        pending.site = None;
+    }
+}
+
+fn close_saved_site(
+    mut saved: EventReader<MapSaved>,
+    mut failed: EventReader<MapSaveFailed>,
+    mut pending: ResMut<PendingSiteClose>,
+    sites: Query<Entity, (With<NameOfSite>, Without<ReferenceSite>)>,
+    current_workspace: Res<CurrentWorkspace>,
+    mut change_current_site: EventWriter<ChangeCurrentSite>,
+    mut commands: Commands,
+) {
+    for failed in failed.read() {
+        if pending.site == Some(failed.site) {
+            // Ask again so the user can decide what to do with the changes
+            pending.saving = None;
+        }
+    }
+
+    for saved in saved.read() {
+        if pending.saving.is_none() || pending.site != Some(saved.site) {
+            continue;
+        }
+        *pending = PendingSiteClose::default();
+        close_site(
+            saved.site,
+            sites.iter(),
+            &current_workspace,
+            &mut change_current_site,
+            &mut commands,
+        );
+    }
+}
@@ -0,0 +1,285 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{
+    interaction::Selection,
+    site::{ApplyYamlSource, CurrentLevel, RefreshYamlSource, SourceTarget, YamlSource},
+    widgets::{
+        menu_bar::{MenuEvent, MenuItem, ToolMenu},
+        prelude::*,
+    },
+    AppState,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_egui::egui::{
+    self,
+    text::{LayoutJob, TextFormat},
+    Button, Color32, ScrollArea, TextEdit, TextStyle, Ui,
+};
+
+/// Add a [`YamlSourceView`] widget to your application.
+#[derive(Default)]
+pub struct YamlSourceViewPlugin {}
+
+impl Plugin for YamlSourceViewPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<YamlSourceMenu>()
+            .init_resource::<YamlSourceDisplay>()
+            .add_systems(Update, handle_yaml_source_panel_visibility);
+
+        let panel = PanelWidget::new(yaml_source_panel, &mut app.world);
+        let widget = Widget::new::<YamlSourceView>(&mut app.world);
+        app.world.spawn((panel, widget));
+    }
+}
+
+fn yaml_source_panel(In(input): In<PanelWidgetInput>, world: &mut World) {
+    if world.resource::<YamlSourceDisplay>().show {
+        egui::SidePanel::left("yaml_source")
+            .resizable(true)
+            .min_width(400.0)
+            .show(&input.context, |ui| {
+                if let Err(err) = world.try_show(input.id, ui) {
+                    error!("Unable to display YAML source panel: {err:?}");
+                }
+            });
+    }
+}
+
+/// A widget that shows how the selected element or the current level is
+/// written in the site file. The text can be edited and applied back onto the
+/// site, for users who know the format better than the inspector.
+///
+/// Use [`YamlSourceViewPlugin`] to add this to your application.
+#[derive(SystemParam)]
+pub struct YamlSourceView<'w> {
+    source: ResMut<'w, YamlSource>,
+    display: ResMut<'w, YamlSourceDisplay>,
+    selection: Res<'w, Selection>,
+    current_level: Res<'w, CurrentLevel>,
+    refresh: EventWriter<'w, RefreshYamlSource>,
+    apply: EventWriter<'w, ApplyYamlSource>,
+    app_state: Res<'w, State<AppState>>,
+}
+
+impl<'w> WidgetSystem for YamlSourceView<'w> {
+    fn show(_: (), ui: &mut Ui, state: &mut SystemState<Self>, world: &mut World) -> () {
+        let mut params = state.get_mut(world);
+        params.show_widget(ui);
+    }
+}
+
+impl<'w> YamlSourceView<'w> {
+    pub fn show_widget(&mut self, ui: &mut Ui) {
+        if *self.app_state.get() != AppState::SiteEditor {
+            ui.label("The source view is only available while editing a site");
+            if ui.add(Button::new("Close")).clicked() {
+                self.display.show = false;
+            }
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.display.show_level, false, "Selection");
+            ui.selectable_value(&mut self.display.show_level, true, "Level");
+        });
+
+        let target = if self.display.show_level {
+            self.current_level.0.map(SourceTarget::Level)
+        } else {
+            self.selection.0.map(SourceTarget::Element)
+        };
+
+        // Follow the selection unless there are edits that would be lost
+        if target.is_some() && target != self.display.requested {
+            if self.source.is_modified() {
+                ui.label("Apply or discard your edits to follow the selection");
+            } else if let Some(target) = target {
+                self.refresh.send(RefreshYamlSource(target));
+                self.display.requested = Some(target);
+            }
+        }
+
+        if self.source.target.is_none() {
+            ui.label("Select an element to see its source");
+        }
+        if let Some(err) = &self.source.error {
+            ui.colored_label(Color32::RED, err);
+        }
+        ui.separator();
+
+        ScrollArea::both()
+            .max_height(600.0)
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                let mut layouter = |ui: &Ui, text: &str, wrap_width: f32| {
+                    let mut job = highlight_yaml(ui, text);
+                    job.wrap.max_width = wrap_width;
+                    ui.fonts(|f| f.layout_job(job))
+                };
+                ui.add(
+                    TextEdit::multiline(&mut self.source.text)
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .layouter(&mut layouter),
+                );
+            });
+        ui.add_space(10.0);
+
+        let modified = self.source.is_modified();
+        ui.horizontal(|ui| {
+            if ui.add_enabled(modified, Button::new("Apply")).clicked() {
+                self.apply.send(ApplyYamlSource);
+            }
+            if ui.add_enabled(modified, Button::new("Discard")).clicked() {
+                self.source.text = self.source.loaded.clone();
+                self.source.error = None;
+            }
+            if let Some(target) = self.source.target {
+                if ui
+                    .add_enabled(!modified, Button::new("Reload"))
+                    .on_hover_text("Load the source again from the site")
+                    .clicked()
+                {
+                    self.refresh.send(RefreshYamlSource(target));
+                }
+            }
+            if ui.add(Button::new("Close")).clicked() {
+                self.display.show = false;
+            }
+        });
+    }
+}
+
+/// Color the keys, strings, numbers, and comments of YAML text.
+fn highlight_yaml(ui: &Ui, text: &str) -> LayoutJob {
+    let font_id = TextStyle::Monospace.resolve(ui.style());
+    let plain = ui.visuals().text_color();
+    let format = |color| TextFormat::simple(font_id.clone(), color);
+    let key = format(Color32::from_rgb(86, 156, 214));
+    let string = format(Color32::from_rgb(206, 145, 120));
+    let number = format(Color32::from_rgb(181, 206, 168));
+    let keyword = format(Color32::from_rgb(197, 134, 192));
+    let comment = format(Color32::GRAY);
+    let plain = format(plain);
+
+    let mut job = LayoutJob::default();
+    for line in text.split_inclusive('\n') {
+        let (mut content, newline) = match line.strip_suffix('\n') {
+            Some(content) => (content, "\n"),
+            None => (line, ""),
+        };
+
+        let mut comment_text = "";
+        if let Some(i) = find_comment(content) {
+            comment_text = &content[i..];
+            content = &content[..i];
+        }
+
+        // Indentation and list markers
+        let body_start = content
+            .find(|c: char| !c.is_whitespace() && c != '-')
+            .unwrap_or(content.len());
+        job.append(&content[..body_start], 0.0, plain.clone());
+        let mut body = &content[body_start..];
+
+        if !body.starts_with(['"', '\'']) {
+            if let Some(i) = body
+                .find(": ")
+                .or_else(|| body.strip_suffix(':').map(|k| k.len()))
+            {
+                job.append(&body[..i], 0.0, key.clone());
+                job.append(":", 0.0, plain.clone());
+                body = &body[i + 1..];
+            }
+        }
+
+        let value = body.trim();
+        let value_format = if value.starts_with(['"', '\'']) {
+            &string
+        } else if value.parse::<f64>().is_ok() {
+            &number
+        } else if matches!(value, "true" | "false" | "null" | "~") {
+            &keyword
+        } else {
+            &plain
+        };
+        job.append(body, 0.0, value_format.clone());
+        job.append(comment_text, 0.0, comment.clone());
+        job.append(newline, 0.0, plain.clone());
+    }
+    job
+}
+
+/// Find where a comment starts in a line of YAML, ignoring `#` inside of
+/// quoted strings.
+fn find_comment(line: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && previous.is_whitespace() => return Some(i),
+            None => {}
+        }
+        previous = c;
+    }
+    None
+}
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct YamlSourceDisplay {
+    pub show: bool,
+    /// Show the whole current level instead of the selected element
+    pub show_level: bool,
+    /// The target that was most recently requested from the site
+    pub requested: Option<SourceTarget>,
+}
+
+fn handle_yaml_source_panel_visibility(
+    mut menu_events: EventReader<MenuEvent>,
+    yaml_source_menu: Res<YamlSourceMenu>,
+    mut display: ResMut<YamlSourceDisplay>,
+) {
+    for event in menu_events.read() {
+        if event.clicked() && event.source() == yaml_source_menu.yaml_source {
+            display.show = true;
+            // Load the source again in case the site changed while the panel
+            // was closed
+            display.requested = None;
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct YamlSourceMenu {
+    yaml_source: Entity,
+}
+
+impl FromWorld for YamlSourceMenu {
+    fn from_world(world: &mut World) -> Self {
+        let tool_header = world.resource::<ToolMenu>().get();
+        let yaml_source = world
+            .spawn(MenuItem::Text("YAML Source".into()))
+            .set_parent(tool_header)
+            .id();
+
+        YamlSourceMenu { yaml_source }
+    }
+}
@@ -17,14 +17,24 @@
 
 use bevy::{ecs::system::SystemParam, prelude::*};
 use bevy_impulse::*;
+use flate2::{read::GzDecoder, write::GzEncoder};
 use rfd::AsyncFileDialog;
-use std::path::PathBuf;
+use std::{
+    collections::HashSet,
+    io::{Read, Write},
+    path::PathBuf,
+};
 
-use crate::interaction::InteractionState;
-use crate::site::{DefaultFile, LoadSite, SaveSite};
-use crate::AppState;
-use rmf_site_format::legacy::building_map::BuildingMap;
-use rmf_site_format::{NameOfSite, Site};
+use crate::interaction::{InteractionState, Preview};
+use crate::site::{
+    Affiliation, Anchor, DefaultFile, Edge, LoadSite, NameInSite, Path, Point, Pose, SaveSite,
+};
+use crate::{editor_config::EditorConfig, AppState};
+use rmf_site_format::legacy::{
+    building_map::{BuildingMap, CoordinateSystem, CoordinateSystemGuess},
+    project::Project,
+};
+use rmf_site_format::{NameOfSite, Pending, Site};
 
 /// Used as an event to command that a new workspace should be made the current one
 #[derive(Clone, Copy, Debug, Event)]
@@ -45,16 +55,94 @@ pub struct WorkspaceMarker;
 #[derive(Clone)]
 pub enum WorkspaceData {
     LegacyBuilding(Vec<u8>),
+    /// The building of a traffic-editor project, whose drawings were made
+    /// relative to the project folder, and the site file that it should be
+    /// saved as.
+    LegacyProject {
+        building: Vec<u8>,
+        site_file: PathBuf,
+    },
     RonSite(Vec<u8>),
     JsonSite(Vec<u8>),
     LoadSite(LoadSite),
 }
 
+/// Suffix of workspace files that are compressed with gzip, e.g.
+/// `hospital.site.ron.gz`. Any file type that can be loaded can also be loaded
+/// when it is compressed with gzip or [zstd](ZSTD_SUFFIX).
+pub const GZIP_SUFFIX: &str = ".gz";
+
+/// Suffix of workspace files that are compressed with zstd, e.g.
+/// `hospital.site.ron.zst`. Zstd is much faster than gzip for large sites.
+pub const ZSTD_SUFFIX: &str = ".zst";
+
+/// How a workspace file is compressed, which is decided by the suffix of its
+/// file name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileCompression {
+    Gzip,
+    Zstd,
+}
+
+impl FileCompression {
+    /// Split the compression suffix off of a file name, if it has one.
+    pub fn split(filename: &str) -> (&str, Option<Self>) {
+        if let Some(inner) = filename.strip_suffix(GZIP_SUFFIX) {
+            (inner, Some(Self::Gzip))
+        } else if let Some(inner) = filename.strip_suffix(ZSTD_SUFFIX) {
+            (inner, Some(Self::Zstd))
+        } else {
+            (filename, None)
+        }
+    }
+
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Self::Gzip => GZIP_SUFFIX,
+            Self::Zstd => ZSTD_SUFFIX,
+        }
+    }
+
+    pub fn compress(self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            Self::Zstd => zstd::encode_all(data, 0),
+        }
+    }
+
+    pub fn decompress(self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Gzip => {
+                let mut decompressed = Vec::new();
+                GzDecoder::new(data).read_to_end(&mut decompressed)?;
+                Ok(decompressed)
+            }
+            Self::Zstd => zstd::decode_all(data),
+        }
+    }
+}
+
 impl WorkspaceData {
     pub fn new(path: &PathBuf, data: Vec<u8>) -> Option<Self> {
         let filename = path.file_name().and_then(|f| f.to_str())?;
+        let (filename, data) = match FileCompression::split(filename) {
+            (inner, Some(compression)) => match compression.decompress(&data) {
+                Ok(decompressed) => (inner, decompressed),
+                Err(err) => {
+                    error!("Unable to decompress {:?}: {err}", filename);
+                    return None;
+                }
+            },
+            (filename, None) => (filename, data),
+        };
         if filename.ends_with(".building.yaml") {
             Some(WorkspaceData::LegacyBuilding(data))
+        } else if filename.ends_with(".project.yaml") {
+            Self::legacy_project(path, &data)
         } else if filename.ends_with("site.ron") {
             Some(WorkspaceData::RonSite(data))
         } else if filename.ends_with("site.json") {
@@ -64,6 +152,45 @@ impl WorkspaceData {
             None
         }
     }
+
+    /// Read the building file that a traffic-editor project refers to.
+    fn legacy_project(path: &PathBuf, data: &[u8]) -> Option<Self> {
+        let project = match Project::from_bytes(data) {
+            Ok(project) => project,
+            Err(err) => {
+                error!("Unable to parse project file {path:?}: {err}");
+                return None;
+            }
+        };
+        let project_dir: PathBuf = path.parent().map(Into::into).unwrap_or_default();
+        let building_path = project.building_path(&project_dir);
+        #[cfg(not(target_arch = "wasm32"))]
+        let building = match std::fs::read(&building_path) {
+            Ok(building) => building,
+            Err(err) => {
+                error!("Unable to read building file {building_path:?} of project {path:?}: {err}");
+                return None;
+            }
+        };
+        #[cfg(target_arch = "wasm32")]
+        let building: Vec<u8> = {
+            error!(
+                "Building file {building_path:?} of project {path:?} cannot be read in a browser, \
+                open the building file instead"
+            );
+            return None;
+        };
+        match project.rebase_building(&building) {
+            Ok(building) => Some(WorkspaceData::LegacyProject {
+                building,
+                site_file: project_dir.join(project.site_file_name()),
+            }),
+            Err(err) => {
+                error!("Unable to parse building file {building_path:?}: {err}");
+                None
+            }
+        }
+    }
 }
 
 /// Used as a resource that keeps track of the current workspace
@@ -75,6 +202,17 @@ pub struct CurrentWorkspace {
     pub display: bool,
 }
 
+/// Used as a resource that keeps track of which workspaces were changed since
+/// they were last loaded or saved.
+#[derive(Clone, Debug, Default, Resource, Deref, DerefMut)]
+pub struct UnsavedChanges(pub HashSet<Entity>);
+
+impl UnsavedChanges {
+    pub fn has_unsaved_changes(&self, workspace: Entity) -> bool {
+        self.contains(&workspace)
+    }
+}
+
 pub struct LoadWorkspaceFile(pub Option<PathBuf>, pub WorkspaceData);
 
 #[derive(Clone, Default, Debug)]
@@ -82,6 +220,31 @@ pub enum ExportFormat {
     #[default]
     Default,
     Sdf,
+    /// Y-up glTF levels with a manifest of semantic entities, meant for game
+    /// engine digital twins such as Unity and Unreal.
+    DigitalTwin,
+    /// Menge scene, behavior, and navigation mesh files for the crowd
+    /// simulation of RMF.
+    CrowdSim,
+}
+
+/// A legacy building map whose coordinate units could not be trusted, waiting
+/// for the user to confirm how it should be interpreted.
+#[derive(Clone)]
+pub struct LegacyBuildingImport {
+    pub default_file: Option<PathBuf>,
+    pub data: Vec<u8>,
+    pub guess: CoordinateSystemGuess,
+    /// The interpretation that the user currently has selected
+    pub choice: CoordinateSystem,
+}
+
+/// Used as a resource to hold a legacy building map until the user confirms
+/// its coordinate units. When this resource is missing, e.g. in headless mode,
+/// the detected units are used without asking.
+#[derive(Default, Resource)]
+pub struct PendingLegacyBuilding {
+    pub pending: Option<LegacyBuildingImport>,
 }
 
 /// Used to keep track of visibility when switching workspace
@@ -103,12 +266,17 @@ impl Plugin for WorkspacePlugin {
             .add_event::<CreateNewWorkspace>()
             .init_resource::<CurrentWorkspace>()
             .init_resource::<RecallWorkspace>()
+            .init_resource::<UnsavedChanges>()
             .init_resource::<FileDialogServices>()
             .init_resource::<WorkspaceLoadingServices>()
             .init_resource::<WorkspaceSavingServices>()
             .add_systems(
                 Update,
-                (dispatch_new_workspace_events, sync_workspace_visibility),
+                (
+                    dispatch_new_workspace_events,
+                    sync_workspace_visibility,
+                    track_unsaved_changes,
+                ),
             );
     }
 }
@@ -134,39 +302,96 @@ pub fn dispatch_new_workspace_events(
     }
 }
 
+/// Mark the current workspace as having unsaved changes whenever its content
+/// is edited, and clear the mark when it gets saved.
+pub fn track_unsaved_changes(
+    mut unsaved: ResMut<UnsavedChanges>,
+    current_workspace: Res<CurrentWorkspace>,
+    new_sites: Query<(), Added<NameOfSite>>,
+    open_sites: Query<(), With<NameOfSite>>,
+    changed: Query<
+        (),
+        (
+            Or<(
+                Changed<Anchor>,
+                Changed<Edge<Entity>>,
+                Changed<Point<Entity>>,
+                Changed<Path<Entity>>,
+                Changed<Pose>,
+                Changed<NameInSite>,
+                Changed<Affiliation<Entity>>,
+            )>,
+            Without<Preview>,
+            Without<Pending>,
+        ),
+    >,
+    mut saves: EventReader<SaveSite>,
+) {
+    unsaved.retain(|site| open_sites.contains(*site));
+    // Everything in a site looks changed on the frame that it gets loaded
+    if new_sites.is_empty() && !changed.is_empty() {
+        if let Some(root) = current_workspace.root {
+            unsaved.insert(root);
+        }
+    }
+    for save in saves.read() {
+        unsaved.remove(&save.site);
+    }
+}
+
 /// Service that takes workspace data and loads a site / workcell, as well as transition state.
 pub fn process_load_workspace_files(
     In(BlockingService { request, .. }): BlockingServiceInput<LoadWorkspaceFile>,
     mut app_state: ResMut<NextState<AppState>>,
     mut interaction_state: ResMut<NextState<InteractionState>>,
     mut load_site: EventWriter<LoadSite>,
+    pending_legacy: Option<ResMut<PendingLegacyBuilding>>,
 ) {
     let LoadWorkspaceFile(default_file, data) = request;
+    let (default_file, data) = match data {
+        // Projects are imported like their building, but saved next to the
+        // project file.
+        WorkspaceData::LegacyProject {
+            building,
+            site_file,
+        } => (Some(site_file), WorkspaceData::LegacyBuilding(building)),
+        data => (default_file, data),
+    };
     match data {
         WorkspaceData::LegacyBuilding(data) => {
             info!("Opening legacy building map file");
-            match BuildingMap::from_bytes(&data) {
-                Ok(building) => {
-                    match building.to_site() {
-                        Ok(site) => {
-                            // Switch state
-                            app_state.set(AppState::SiteEditor);
-                            load_site.send(LoadSite {
-                                site,
-                                focus: true,
-                                default_file,
-                            });
-                            interaction_state.set(InteractionState::Enable);
-                        }
-                        Err(err) => {
-                            error!("Failed converting to site {:?}", err);
-                        }
-                    }
-                }
+            let guess = match BuildingMap::guess_coordinate_system(&data) {
+                Ok(guess) => guess,
                 Err(err) => {
                     error!("Failed loading legacy building {:?}", err);
+                    return;
+                }
+            };
+            if guess.needs_confirmation() {
+                if let Some(mut pending_legacy) = pending_legacy {
+                    info!("Waiting for the units of the legacy building map to be confirmed");
+                    pending_legacy.pending = Some(LegacyBuildingImport {
+                        default_file,
+                        choice: guess.detected,
+                        data,
+                        guess,
+                    });
+                    return;
                 }
+                warn!(
+                    "Interpreting legacy building map coordinates as {}: {}",
+                    guess.detected.label(),
+                    guess.reason(),
+                );
             }
+            load_legacy_building(
+                &data,
+                Some(guess.detected),
+                default_file,
+                &mut app_state,
+                &mut interaction_state,
+                &mut load_site,
+            );
         }
         WorkspaceData::RonSite(data) => {
             info!("Opening site file");
@@ -204,6 +429,8 @@ pub fn process_load_workspace_files(
                 }
             }
         }
+        // Projects were turned into legacy buildings above
+        WorkspaceData::LegacyProject { .. } => {}
         WorkspaceData::LoadSite(site) => {
             app_state.set(AppState::SiteEditor);
             load_site.send(site);
@@ -212,6 +439,42 @@ pub fn process_load_workspace_files(
     }
 }
 
+/// Convert a legacy building map into a site and load it. When a coordinate
+/// system is given, it overrides whatever the file itself declares.
+pub fn load_legacy_building(
+    data: &[u8],
+    coordinate_system: Option<CoordinateSystem>,
+    default_file: Option<PathBuf>,
+    app_state: &mut NextState<AppState>,
+    interaction_state: &mut NextState<InteractionState>,
+    load_site: &mut EventWriter<LoadSite>,
+) {
+    let building = match coordinate_system {
+        Some(coordinate_system) => BuildingMap::from_bytes_as(data, coordinate_system),
+        None => BuildingMap::from_bytes(data),
+    };
+    match building {
+        Ok(building) => match building.to_site() {
+            Ok(site) => {
+                // Switch state
+                app_state.set(AppState::SiteEditor);
+                load_site.send(LoadSite {
+                    site,
+                    focus: true,
+                    default_file,
+                });
+                interaction_state.set(InteractionState::Enable);
+            }
+            Err(err) => {
+                error!("Failed converting to site {:?}", err);
+            }
+        },
+        Err(err) => {
+            error!("Failed loading legacy building {:?}", err);
+        }
+    }
+}
+
 /// Filter that can be added to a file dialog request to filter extensions
 #[derive(Clone, Debug)]
 pub struct FileDialogFilter {
@@ -231,14 +494,33 @@ pub struct FileDialogServices {
     pub pick_folder: Service<(), PathBuf>,
 }
 
+/// Attach the folder that file dialogs should start in to a request
+fn with_dialog_directory<T: 'static + Send + Sync>(
+    In(BlockingService { request, .. }): BlockingServiceInput<T>,
+    config: Option<Res<EditorConfig>>,
+) -> (T, Option<PathBuf>) {
+    (request, config.and_then(|c| c.last_open_dir.clone()))
+}
+
+fn new_file_dialog(directory: Option<PathBuf>) -> AsyncFileDialog {
+    let dialog = AsyncFileDialog::new();
+    match directory {
+        Some(directory) => dialog.set_directory(directory),
+        None => dialog,
+    }
+}
+
 impl FromWorld for FileDialogServices {
     fn from_world(world: &mut World) -> Self {
+        let filters_with_directory =
+            world.spawn_service(with_dialog_directory::<Vec<FileDialogFilter>>);
         let pick_file_and_load = world.spawn_workflow(|scope, builder| {
             scope
                 .input
                 .chain(builder)
-                .map_async(|filters: Vec<FileDialogFilter>| async move {
-                    let mut dialog = AsyncFileDialog::new();
+                .then(filters_with_directory)
+                .map_async(|(filters, directory)| async move {
+                    let mut dialog = new_file_dialog(directory);
                     for filter in filters {
                         dialog = dialog.add_filter(filter.name, &filter.extensions);
                     }
@@ -256,21 +538,35 @@ impl FromWorld for FileDialogServices {
                 .connect(scope.terminate)
         });
 
+        let filters_with_directory =
+            world.spawn_service(with_dialog_directory::<Vec<FileDialogFilter>>);
         let pick_file_for_saving = world.spawn_workflow(|scope, builder| {
             scope
                 .input
                 .chain(builder)
-                .map_async(|filters: Vec<FileDialogFilter>| async move {
-                    let mut dialog = AsyncFileDialog::new();
-                    for filter in filters {
-                        dialog = dialog.add_filter(filter.name, &filter.extensions);
-                    }
-                    let file = dialog.save_file().await?;
+                .then(filters_with_directory)
+                .map_async(|(filters, directory)| async move {
                     #[cfg(not(target_arch = "wasm32"))]
-                    let file = file.path().to_path_buf();
+                    {
+                        let mut dialog = new_file_dialog(directory);
+                        for filter in filters {
+                            dialog = dialog.add_filter(filter.name, &filter.extensions);
+                        }
+                        let file = dialog.save_file().await?;
+                        Some(file.path().to_path_buf())
+                    }
                     #[cfg(target_arch = "wasm32")]
-                    let file = PathBuf::from(file.file_name());
-                    Some(file)
+                    {
+                        // Browsers download saved files instead of writing
+                        // them, so there is no file to pick, only a name.
+                        let _ = directory;
+                        let extension = filters
+                            .first()
+                            .and_then(|f| f.extensions.first())
+                            .cloned()
+                            .unwrap_or_default();
+                        Some(PathBuf::from(format!("map.{extension}")))
+                    }
                 })
                 .cancel_on_none()
                 .connect(scope.terminate)
@@ -317,6 +613,8 @@ pub struct WorkspaceLoadingServices {
     pub load_workspace_from_path: Service<PathBuf, ()>,
     /// Loads the workspace from the requested data
     pub load_workspace_from_data: Service<WorkspaceData, ()>,
+    /// Downloads the workspace at the requested URL and loads it
+    pub load_workspace_from_url: Service<String, ()>,
 }
 
 impl FromWorld for WorkspaceLoadingServices {
@@ -329,11 +627,26 @@ impl FromWorld for WorkspaceLoadingServices {
         let loading_filters = vec![
             FileDialogFilter {
                 name: "Legacy building".into(),
-                extensions: vec!["building.yaml".into()],
+                extensions: vec![
+                    "building.yaml".into(),
+                    "building.yaml.gz".into(),
+                    "building.yaml.zst".into(),
+                ],
+            },
+            FileDialogFilter {
+                name: "traffic-editor project".into(),
+                extensions: vec!["project.yaml".into()],
             },
             FileDialogFilter {
                 name: "Site".into(),
-                extensions: vec!["site.ron".into(), "site.json".into()],
+                extensions: vec![
+                    "site.ron".into(),
+                    "site.json".into(),
+                    "site.ron.gz".into(),
+                    "site.json.gz".into(),
+                    "site.ron.zst".into(),
+                    "site.json.zst".into(),
+                ],
             },
         ];
         // Spawn all the services
@@ -352,14 +665,16 @@ impl FromWorld for WorkspaceLoadingServices {
                 .connect(scope.terminate)
         });
 
+        let with_directory = world.spawn_service(with_dialog_directory::<()>);
         let create_empty_workspace_from_dialog = world.spawn_workflow(|scope, builder| {
             scope
                 .input
                 .chain(builder)
-                .map_async(|_| async move {
+                .then(with_directory)
+                .map_async(|((), _directory)| async move {
                     #[cfg(not(target_arch = "wasm32"))]
                     {
-                        if let Some(file) = AsyncFileDialog::new().save_file().await {
+                        if let Some(file) = new_file_dialog(_directory).save_file().await {
                             let file = file.path().to_path_buf();
                             let name = file
                                 .file_stem()
@@ -414,11 +729,45 @@ impl FromWorld for WorkspaceLoadingServices {
                 .connect(scope.terminate)
         });
 
+        let load_workspace_from_url = world.spawn_workflow(|scope, builder| {
+            scope
+                .input
+                .chain(builder)
+                .map_async(|url: String| async move {
+                    let response = match ehttp::fetch_async(ehttp::Request::get(&url)).await {
+                        Ok(response) if response.ok => response,
+                        Ok(response) => {
+                            warn!(
+                                "Unable to download [{url}]: {} {}",
+                                response.status, response.status_text
+                            );
+                            return None;
+                        }
+                        Err(err) => {
+                            warn!("Unable to download [{url}]: {err}");
+                            return None;
+                        }
+                    };
+                    // The file name decides how the data gets parsed
+                    let name = url
+                        .split(['?', '#'])
+                        .next()
+                        .and_then(|u| u.rsplit('/').next())
+                        .unwrap_or_default();
+                    let data = WorkspaceData::new(&PathBuf::from(name), response.bytes)?;
+                    Some(LoadWorkspaceFile(None, data))
+                })
+                .cancel_on_none()
+                .then(process_load_files)
+                .connect(scope.terminate)
+        });
+
         Self {
             load_workspace_from_dialog,
             create_empty_workspace_from_dialog,
             load_workspace_from_path,
             load_workspace_from_data,
+            load_workspace_from_url,
         }
     }
 }
@@ -454,6 +803,13 @@ impl<'w, 's> WorkspaceLoader<'w, 's> {
             .request(data, self.workspace_loading.load_workspace_from_data)
             .detach();
     }
+
+    /// Request to download a workspace and load it
+    pub fn load_from_url(&mut self, url: String) {
+        self.commands
+            .request(url, self.workspace_loading.load_workspace_from_url)
+            .detach();
+    }
 }
 
 /// `SystemParam` used to request for workspace loading operations
@@ -499,6 +855,14 @@ pub struct WorkspaceSavingServices {
     pub export_sdf_to_dialog: Service<(), ()>,
     /// Exports the requested workspace as an SDF in the requested path.
     pub export_sdf_to_path: Service<PathBuf, ()>,
+    /// Opens a dialog to pick a folder and exports the requested workspace as a digital twin.
+    pub export_digital_twin_to_dialog: Service<(), ()>,
+    /// Exports the requested workspace as a digital twin in the requested path.
+    pub export_digital_twin_to_path: Service<PathBuf, ()>,
+    /// Opens a dialog to pick a folder and exports the crowd simulation of the requested workspace.
+    pub export_crowd_sim_to_dialog: Service<(), ()>,
+    /// Exports the crowd simulation of the requested workspace in the requested path.
+    pub export_crowd_sim_to_path: Service<PathBuf, ()>,
 }
 
 impl FromWorld for WorkspaceSavingServices {
@@ -519,7 +883,14 @@ impl FromWorld for WorkspaceSavingServices {
         let pick_folder = world.resource::<FileDialogServices>().pick_folder.clone();
         let saving_filters = vec![FileDialogFilter {
             name: "Site".into(),
-            extensions: vec!["site.ron".into(), "site.json".into()],
+            extensions: vec![
+                "site.ron".into(),
+                "site.json".into(),
+                "site.ron.gz".into(),
+                "site.json.gz".into(),
+                "site.ron.zst".into(),
+                "site.json.zst".into(),
+            ],
         }];
         // Spawn all the services
         let save_workspace_to_dialog = world.spawn_workflow(|scope, builder| {
@@ -570,6 +941,40 @@ impl FromWorld for WorkspaceSavingServices {
                 .then(send_file_save)
                 .connect(scope.terminate)
         });
+        let export_digital_twin_to_dialog = world.spawn_workflow(|scope, builder| {
+            scope
+                .input
+                .chain(builder)
+                .then(pick_folder)
+                .map_block(|path| (path, ExportFormat::DigitalTwin))
+                .then(send_file_save)
+                .connect(scope.terminate)
+        });
+        let export_digital_twin_to_path = world.spawn_workflow(|scope, builder| {
+            scope
+                .input
+                .chain(builder)
+                .map_block(|path| (path, ExportFormat::DigitalTwin))
+                .then(send_file_save)
+                .connect(scope.terminate)
+        });
+        let export_crowd_sim_to_dialog = world.spawn_workflow(|scope, builder| {
+            scope
+                .input
+                .chain(builder)
+                .then(pick_folder)
+                .map_block(|path| (path, ExportFormat::CrowdSim))
+                .then(send_file_save)
+                .connect(scope.terminate)
+        });
+        let export_crowd_sim_to_path = world.spawn_workflow(|scope, builder| {
+            scope
+                .input
+                .chain(builder)
+                .map_block(|path| (path, ExportFormat::CrowdSim))
+                .then(send_file_save)
+                .connect(scope.terminate)
+        });
 
         Self {
             save_workspace_to_dialog,
@@ -577,6 +982,10 @@ impl FromWorld for WorkspaceSavingServices {
             save_workspace_to_default_file,
             export_sdf_to_dialog,
             export_sdf_to_path,
+            export_digital_twin_to_dialog,
+            export_digital_twin_to_path,
+            export_crowd_sim_to_dialog,
+            export_crowd_sim_to_path,
         }
     }
 }
@@ -596,9 +1005,15 @@ impl<'w, 's> WorkspaceSaver<'w, 's> {
     /// Request to save the workspace to the default file (or a dialog if no default file is
     /// available).
     pub fn save_to_default_file(&mut self) {
+        self.save_to_default_file_impulse().detach();
+    }
+
+    /// Save the workspace to the default file and then keep attaching impulses that should only
+    /// run once the save has been requested. Remember to call `.detach()` when finished or else
+    /// the whole chain will be dropped right away.
+    pub fn save_to_default_file_impulse(&mut self) -> Impulse<'w, 's, '_, (), ()> {
         self.commands
             .request((), self.workspace_saving.save_workspace_to_default_file)
-            .detach();
     }
 
     /// Request to save the workspace to the requested path
@@ -621,6 +1036,34 @@ impl<'w, 's> WorkspaceSaver<'w, 's> {
             .request(path, self.workspace_saving.export_sdf_to_path)
             .detach();
     }
+
+    /// Request to export the workspace as a digital twin to a folder selected from a dialog
+    pub fn export_digital_twin_to_dialog(&mut self) {
+        self.commands
+            .request((), self.workspace_saving.export_digital_twin_to_dialog)
+            .detach();
+    }
+
+    /// Request to export the workspace as a digital twin to provided folder
+    pub fn export_digital_twin_to_path(&mut self, path: PathBuf) {
+        self.commands
+            .request(path, self.workspace_saving.export_digital_twin_to_path)
+            .detach();
+    }
+
+    /// Request to export the crowd simulation of the workspace to a folder selected from a dialog
+    pub fn export_crowd_sim_to_dialog(&mut self) {
+        self.commands
+            .request((), self.workspace_saving.export_crowd_sim_to_dialog)
+            .detach();
+    }
+
+    /// Request to export the crowd simulation of the workspace to provided folder
+    pub fn export_crowd_sim_to_path(&mut self, path: PathBuf) {
+        self.commands
+            .request(path, self.workspace_saving.export_crowd_sim_to_path)
+            .detach();
+    }
 }
 
 /// `SystemParam` used to request for workspace loading operations
@@ -659,3 +1102,25 @@ pub fn sync_workspace_visibility(
         recall.0 = current_workspace.root;
     }
 }
+
+#[test]
+fn test_compressed_workspace_files_are_decompressed() {
+    let data = b"(format_version: \"0.1\")".to_vec();
+    for compression in [FileCompression::Gzip, FileCompression::Zstd] {
+        let compressed = compression.compress(&data).unwrap();
+        assert_ne!(compressed, data);
+        assert_eq!(compression.decompress(&compressed).unwrap(), data);
+
+        let filename = format!("office.site.ron{}", compression.suffix());
+        assert_eq!(
+            FileCompression::split(&filename),
+            ("office.site.ron", Some(compression))
+        );
+        let loaded = WorkspaceData::new(&PathBuf::from(filename), compressed);
+        assert!(matches!(loaded, Some(WorkspaceData::RonSite(d)) if d == data));
+    }
+    assert_eq!(
+        FileCompression::split("office.site.ron"),
+        ("office.site.ron", None)
+    );
+}
@@ -63,6 +63,58 @@ impl Alignment {
             self.translation,
         )
     }
+
+    /// The root mean square distance between each transformed `from` point
+    /// and its `to` point.
+    pub fn rms_error(&self, from: &[DVec2], to: &[DVec2]) -> f64 {
+        let n = from.len().min(to.len());
+        if n == 0 {
+            return 0.0;
+        }
+        let tf = self.to_affine();
+        let sum: f64 = from
+            .iter()
+            .zip(to)
+            .map(|(p, q)| tf.transform_point2(*p).distance_squared(*q))
+            .sum();
+        (sum / n as f64).sqrt()
+    }
+}
+
+/// Find the rotation, translation, and scale that best moves each `from`
+/// point onto its `to` point in the least squares sense. When `scale` is
+/// given it is used as is instead of being fitted. At least two pairs of
+/// distinct points are needed.
+pub fn fit_point_pairs(from: &[DVec2], to: &[DVec2], scale: Option<f64>) -> Option<Alignment> {
+    let n = from.len().min(to.len());
+    if n < 2 {
+        return None;
+    }
+    let (from, to) = (&from[..n], &to[..n]);
+    let from_center = from.iter().sum::<DVec2>() / n as f64;
+    let to_center = to.iter().sum::<DVec2>() / n as f64;
+
+    let mut dot = 0.0;
+    let mut cross = 0.0;
+    let mut spread = 0.0;
+    for (p, q) in from.iter().zip(to) {
+        let (a, b) = (*p - from_center, *q - to_center);
+        dot += a.dot(b);
+        cross += a.perp_dot(b);
+        spread += a.length_squared();
+    }
+    if spread < 1e-12 || (dot == 0.0 && cross == 0.0) {
+        return None;
+    }
+
+    let rotation = cross.atan2(dot);
+    let scale = scale.unwrap_or_else(|| dot.hypot(cross) / spread);
+    let translation = to_center - scale * (DMat2::from_angle(rotation) * from_center);
+    Some(Alignment {
+        translation,
+        rotation,
+        scale,
+    })
 }
 
 pub fn align_site<T: RefTrait>(site_variables: &SiteVariables<T>) -> HashMap<T, Alignment> {
@@ -574,3 +626,41 @@ fn calculate_center_adjustment(building: &BuildingMap, u: &mut [f64]) {
         u[y] -= dy;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_pairs_recover_similarity_transform() {
+        let expected = Alignment {
+            translation: DVec2::new(3.0, -1.0),
+            rotation: 0.7,
+            scale: 2.5,
+        };
+        let from = [
+            DVec2::new(0.0, 0.0),
+            DVec2::new(4.0, 1.0),
+            DVec2::new(-2.0, 3.0),
+            DVec2::new(5.0, -6.0),
+        ];
+        let to: Vec<DVec2> = from
+            .iter()
+            .map(|p| expected.to_affine().transform_point2(*p))
+            .collect();
+
+        let fit = fit_point_pairs(&from, &to, None).unwrap();
+        assert!((fit.rotation - expected.rotation).abs() < 1e-9);
+        assert!((fit.scale - expected.scale).abs() < 1e-9);
+        assert!(fit.translation.distance(expected.translation) < 1e-9);
+        assert!(fit.rms_error(&from, &to) < 1e-9);
+
+        // With a fixed scale the fit can only rotate and translate
+        let rigid = fit_point_pairs(&from, &to, Some(1.0)).unwrap();
+        assert_eq!(rigid.scale, 1.0);
+        assert!((rigid.rotation - expected.rotation).abs() < 1e-9);
+        assert!(rigid.rms_error(&from, &to) > 1.0);
+
+        assert!(fit_point_pairs(&from[..1], &to[..1], None).is_none());
+    }
+}
@@ -61,6 +61,8 @@ pub enum Category {
     GeoReference,
     NavigationGraph,
     Visual,
+    CustomEntity,
+    Zone,
 }
 
 impl Category {
@@ -93,6 +95,8 @@ impl Category {
             Self::GeoReference => "Georeference",
             Self::NavigationGraph => "Navigation Graph",
             Self::Visual => "Visual",
+            Self::CustomEntity => "Custom Entity",
+            Self::Zone => "Zone",
         }
     }
 
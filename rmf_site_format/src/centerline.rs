@@ -0,0 +1,649 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+//! Estimate the centerlines of the corridors between walls so that lanes can
+//! be proposed for large facilities without drawing each one by hand.
+//!
+//! The free space around the walls is sampled on a grid. Cells whose nearest
+//! wall points jump from one wall to another lie on the medial axis of the
+//! free space. That ridge gets thinned down to a single cell and traced into
+//! a graph of straight segments.
+
+use crate::point_in_polygon;
+use glam::{IVec2, Vec2};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The largest number of grid cells that will be sampled. Coarser cells are
+/// used for levels that would need more than this.
+const MAX_CELLS: usize = 1_000_000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct CenterlineSettings {
+    /// Size of the grid cells that sample the free space, in meters
+    pub resolution: f32,
+    /// Centerlines closer than this to a wall are skipped, so corridors
+    /// narrower than twice this do not get lanes.
+    pub min_clearance: f32,
+    /// Centerlines farther than this from every wall are skipped, such as the
+    /// middle of large open areas.
+    pub max_clearance: f32,
+    /// Dead ends that are shorter than this get pruned. These mostly come
+    /// from the corners of rooms.
+    pub min_branch_length: f32,
+    /// How far the simplified centerlines may stray from the traced ones
+    pub simplify_tolerance: f32,
+}
+
+impl Default for CenterlineSettings {
+    fn default() -> Self {
+        Self {
+            resolution: 0.2,
+            min_clearance: 0.4,
+            max_clearance: 5.0,
+            min_branch_length: 1.5,
+            simplify_tolerance: 0.25,
+        }
+    }
+}
+
+/// Centerlines as a graph of straight segments. Segments that meet at a
+/// junction share the same vertex.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CenterlineGraph {
+    pub vertices: Vec<Vec2>,
+    pub edges: Vec<[usize; 2]>,
+}
+
+/// Compute the centerlines of the free space between a set of walls. Each
+/// wall is given by its two endpoints. If any regions are given, such as the
+/// floors of a level, only the free space inside of them is used.
+pub fn corridor_centerlines(
+    walls: &[[Vec2; 2]],
+    regions: &[Vec<[f32; 2]>],
+    settings: &CenterlineSettings,
+) -> CenterlineGraph {
+    let Some(grid) = Grid::sample(walls, regions, settings) else {
+        return CenterlineGraph::default();
+    };
+    let mut skeleton = grid.ridge(settings);
+    thin(&mut skeleton, grid.nx, grid.ny);
+    let mut chains = grid.trace(&skeleton);
+    prune(&mut chains, settings.min_branch_length);
+    join_chains(&mut chains);
+
+    let mut graph = CenterlineGraph::default();
+    let mut vertex_of_junction = HashMap::new();
+    let mut junction_vertex = |graph: &mut CenterlineGraph, junction: usize, p: Vec2| {
+        *vertex_of_junction.entry(junction).or_insert_with(|| {
+            graph.vertices.push(p);
+            graph.vertices.len() - 1
+        })
+    };
+    for chain in &chains {
+        let points = simplify(&chain.points, settings.simplify_tolerance);
+        let n = points.len();
+        if n < 2 {
+            continue;
+        }
+        let mut previous = junction_vertex(&mut graph, chain.ends[0], points[0]);
+        for (i, p) in points.iter().enumerate().skip(1) {
+            let next = if i + 1 == n {
+                junction_vertex(&mut graph, chain.ends[1], *p)
+            } else {
+                graph.vertices.push(*p);
+                graph.vertices.len() - 1
+            };
+            if next != previous {
+                graph.edges.push([previous, next]);
+            }
+            previous = next;
+        }
+    }
+    graph.remove_unused_vertices();
+    graph
+}
+
+impl CenterlineGraph {
+    /// Junctions of chains that got simplified away are left without edges.
+    fn remove_unused_vertices(&mut self) {
+        let mut used = vec![false; self.vertices.len()];
+        for v in self.edges.iter().flatten() {
+            used[*v] = true;
+        }
+        let mut new_index = vec![0; self.vertices.len()];
+        let mut vertices = Vec::new();
+        for (i, v) in self.vertices.iter().enumerate() {
+            if used[i] {
+                new_index[i] = vertices.len();
+                vertices.push(*v);
+            }
+        }
+        self.vertices = vertices;
+        for v in self.edges.iter_mut().flatten() {
+            *v = new_index[*v];
+        }
+    }
+}
+
+struct Grid {
+    origin: Vec2,
+    resolution: f32,
+    nx: usize,
+    ny: usize,
+    /// Distance from each cell to its nearest wall
+    distance: Vec<f32>,
+    /// The nearest wall point of each cell
+    nearest: Vec<Vec2>,
+    /// Whether each cell is inside of the regions that were asked for
+    inside: Vec<bool>,
+}
+
+impl Grid {
+    fn sample(
+        walls: &[[Vec2; 2]],
+        regions: &[Vec<[f32; 2]>],
+        settings: &CenterlineSettings,
+    ) -> Option<Self> {
+        if settings.resolution <= 0.0 {
+            return None;
+        }
+        let (min, max) = walls.iter().flatten().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), p| (min.min(*p), max.max(*p)),
+        );
+        let size = max - min;
+        if !size.is_finite() {
+            return None;
+        }
+        let resolution = settings
+            .resolution
+            .max((size.x * size.y / MAX_CELLS as f32).sqrt());
+        let nx = (size.x / resolution).ceil() as usize + 1;
+        let ny = (size.y / resolution).ceil() as usize + 1;
+
+        // Only walls within the max clearance matter, so the walls are sorted
+        // into tiles that are that large and each cell only checks the tiles
+        // around it.
+        let tile_size = settings.max_clearance.max(resolution);
+        let tile_of = |p: Vec2| ((p - min) / tile_size).floor().as_ivec2();
+        let mut tiles: HashMap<IVec2, Vec<usize>> = HashMap::new();
+        for (w, [a, b]) in walls.iter().enumerate() {
+            let (t0, t1) = (tile_of(a.min(*b)), tile_of(a.max(*b)));
+            for tx in t0.x..=t1.x {
+                for ty in t0.y..=t1.y {
+                    tiles.entry(IVec2::new(tx, ty)).or_default().push(w);
+                }
+            }
+        }
+
+        let mut distance = Vec::with_capacity(nx * ny);
+        let mut nearest = Vec::with_capacity(nx * ny);
+        let mut inside = Vec::with_capacity(nx * ny);
+        let mut candidates = Vec::new();
+        for j in 0..ny {
+            for i in 0..nx {
+                let c = min + resolution * Vec2::new(i as f32, j as f32);
+                let tile = tile_of(c);
+                candidates.clear();
+                for dx in -1..=1 {
+                    for dy in -1..=1 {
+                        if let Some(ws) = tiles.get(&(tile + IVec2::new(dx, dy))) {
+                            candidates.extend(ws.iter().copied());
+                        }
+                    }
+                }
+                let (d, q) = candidates
+                    .iter()
+                    .map(|w| {
+                        let [a, b] = walls[*w];
+                        let q = closest_point_on_segment(a, b, c);
+                        (c.distance(q), q)
+                    })
+                    .fold((f32::INFINITY, c), |best, next| {
+                        if next.0 < best.0 {
+                            next
+                        } else {
+                            best
+                        }
+                    });
+                inside.push(
+                    regions.is_empty() || regions.iter().any(|r| point_in_polygon(c.to_array(), r)),
+                );
+                distance.push(d);
+                nearest.push(q);
+            }
+        }
+
+        Some(Self {
+            origin: min,
+            resolution,
+            nx,
+            ny,
+            distance,
+            nearest,
+            inside,
+        })
+    }
+
+    fn point(&self, cell: usize) -> Vec2 {
+        let (i, j) = (cell % self.nx, cell / self.nx);
+        self.origin + self.resolution * Vec2::new(i as f32, j as f32)
+    }
+
+    /// Cells on the medial axis of the free space. Neighboring cells whose
+    /// nearest wall points are far apart sit on either side of the axis.
+    fn ridge(&self, settings: &CenterlineSettings) -> Vec<bool> {
+        let threshold = settings.min_clearance.max(3.0 * self.resolution);
+        let clear = |cell: usize| {
+            let d = self.distance[cell];
+            self.inside[cell] && settings.min_clearance <= d && d <= settings.max_clearance
+        };
+        let mut ridge = vec![false; self.nx * self.ny];
+        for j in 0..self.ny {
+            for i in 0..self.nx {
+                let cell = j * self.nx + i;
+                if !self.distance[cell].is_finite() {
+                    continue;
+                }
+                let neighbors = [
+                    (i + 1 < self.nx).then_some(cell + 1),
+                    (j + 1 < self.ny).then_some(cell + self.nx),
+                ];
+                for other in neighbors.into_iter().flatten() {
+                    if !self.distance[other].is_finite()
+                        || self.nearest[cell].distance(self.nearest[other]) <= threshold
+                    {
+                        continue;
+                    }
+                    // The axis may pass right through a cell that is too
+                    // close to the walls, such as in a tight doorway, so
+                    // either side of the axis is enough.
+                    ridge[cell] |= clear(cell);
+                    ridge[other] |= clear(other);
+                }
+            }
+        }
+        ridge
+    }
+
+    /// Trace the skeleton into chains of points that run between junctions
+    /// and dead ends.
+    fn trace(&self, skeleton: &[bool]) -> Vec<Chain> {
+        let neighbors = |cell: usize| neighbors(cell, self.nx, self.ny).filter(|n| skeleton[*n]);
+        // Thinning removed the corners of staircases, so every cell along a
+        // line has exactly two neighbors.
+        let is_junction = |cell: usize| neighbors(cell).count() != 2;
+
+        // Junction cells that touch each other are treated as one junction
+        let mut junction = vec![usize::MAX; skeleton.len()];
+        let mut junction_count = 0;
+        for cell in 0..skeleton.len() {
+            if !skeleton[cell] || junction[cell] != usize::MAX || !is_junction(cell) {
+                continue;
+            }
+            let mut stack = vec![cell];
+            junction[cell] = junction_count;
+            while let Some(c) = stack.pop() {
+                for n in neighbors(c) {
+                    if junction[n] == usize::MAX && is_junction(n) {
+                        junction[n] = junction_count;
+                        stack.push(n);
+                    }
+                }
+            }
+            junction_count += 1;
+        }
+
+        let mut visited = vec![false; skeleton.len()];
+        let mut chains = Vec::new();
+        for cell in 0..skeleton.len() {
+            if junction[cell] == usize::MAX {
+                continue;
+            }
+            for first in neighbors(cell) {
+                // Neighboring junction cells were merged into one junction
+                if junction[first] != usize::MAX || visited[first] {
+                    continue;
+                }
+                chains.extend(self.walk(skeleton, &junction, &mut visited, cell, first));
+            }
+        }
+
+        // Loops without any junction get one at an arbitrary cell
+        for cell in 0..skeleton.len() {
+            if !skeleton[cell] || visited[cell] || junction[cell] != usize::MAX {
+                continue;
+            }
+            junction[cell] = junction_count;
+            junction_count += 1;
+            if let Some(first) = neighbors(cell).next() {
+                chains.extend(self.walk(skeleton, &junction, &mut visited, cell, first));
+            }
+        }
+
+        chains
+    }
+
+    /// Follow the skeleton from a junction until it reaches a junction.
+    fn walk(
+        &self,
+        skeleton: &[bool],
+        junction: &[usize],
+        visited: &mut [bool],
+        start: usize,
+        first: usize,
+    ) -> Option<Chain> {
+        let mut points = vec![self.point(start)];
+        let (mut previous, mut current) = (start, first);
+        while junction[current] == usize::MAX {
+            if visited[current] {
+                return None;
+            }
+            visited[current] = true;
+            points.push(self.point(current));
+            let rank = |n: usize| -> Option<u8> {
+                if junction[n] != usize::MAX {
+                    // Do not step right back into the starting junction
+                    (points.len() > 2 || junction[n] != junction[start]).then_some(0)
+                } else {
+                    (!visited[n]).then_some(1)
+                }
+            };
+            let (_, next) = neighbors(current, self.nx, self.ny)
+                .filter(|n| skeleton[*n] && *n != previous)
+                .filter_map(|n| rank(n).map(|r| (r, n)))
+                .min()?;
+            (previous, current) = (current, next);
+        }
+        points.push(self.point(current));
+        Some(Chain {
+            ends: [junction[start], junction[current]],
+            points,
+        })
+    }
+}
+
+struct Chain {
+    /// The junctions or dead ends at each end of the chain
+    ends: [usize; 2],
+    points: Vec<Vec2>,
+}
+
+impl Chain {
+    fn length(&self) -> f32 {
+        self.points.windows(2).map(|w| w[0].distance(w[1])).sum()
+    }
+}
+
+fn closest_point_on_segment(a: Vec2, b: Vec2, p: Vec2) -> Vec2 {
+    let ab = b - a;
+    let length_squared = ab.length_squared();
+    if length_squared <= f32::EPSILON {
+        return a;
+    }
+    a + ab * ((p - a).dot(ab) / length_squared).clamp(0.0, 1.0)
+}
+
+/// The 8-connected neighbors of a cell in counter-clockwise order, starting
+/// from the cell above.
+fn neighbors(cell: usize, nx: usize, ny: usize) -> impl Iterator<Item = usize> {
+    let (i, j) = ((cell % nx) as isize, (cell / nx) as isize);
+    [
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+    ]
+    .into_iter()
+    .filter_map(move |(di, dj)| {
+        let (i, j) = (i + di, j + dj);
+        if (0..nx as isize).contains(&i) && (0..ny as isize).contains(&j) {
+            Some(j as usize * nx + i as usize)
+        } else {
+            None
+        }
+    })
+}
+
+/// The eight neighbors of a cell in clockwise order, starting from the cell
+/// above. Cells outside of the grid are empty.
+fn ring(cells: &[bool], nx: usize, ny: usize, i: isize, j: isize) -> [bool; 8] {
+    let at = |i: isize, j: isize| {
+        (0..nx as isize).contains(&i)
+            && (0..ny as isize).contains(&j)
+            && cells[j as usize * nx + i as usize]
+    };
+    [
+        at(i, j + 1),
+        at(i + 1, j + 1),
+        at(i + 1, j),
+        at(i + 1, j - 1),
+        at(i, j - 1),
+        at(i - 1, j - 1),
+        at(i - 1, j),
+        at(i - 1, j + 1),
+    ]
+}
+
+/// How many times the ring of neighbors switches from empty to filled.
+fn transitions(ring: &[bool; 8]) -> usize {
+    (0..8).filter(|k| !ring[*k] && ring[(k + 1) % 8]).count()
+}
+
+/// How many separate groups the filled cells of a ring of neighbors form.
+/// Cells next to each other in the ring touch, and so do the cells to the
+/// sides of each corner.
+fn ring_components(ring: &[bool; 8]) -> usize {
+    let mut group: [usize; 8] = std::array::from_fn(|k| k);
+    fn root(group: &mut [usize; 8], mut k: usize) -> usize {
+        while group[k] != k {
+            k = group[k];
+        }
+        k
+    }
+    for k in 0..8 {
+        let touching = [(k + 1) % 8, if k % 2 == 0 { (k + 2) % 8 } else { k }];
+        for other in touching {
+            if ring[k] && ring[other] {
+                let (a, b) = (root(&mut group, k), root(&mut group, other));
+                group[a] = b;
+            }
+        }
+    }
+    (0..8)
+        .filter(|k| ring[*k] && root(&mut group, *k) == *k)
+        .count()
+}
+
+/// Zhang-Suen thinning, which peels cells off the boundary of each region
+/// until only a skeleton that is one cell wide remains.
+fn thin(cells: &mut [bool], nx: usize, ny: usize) {
+    loop {
+        let mut changed = false;
+        for step in 0..2 {
+            let mut remove = Vec::new();
+            for j in 0..ny as isize {
+                for i in 0..nx as isize {
+                    let cell = j as usize * nx + i as usize;
+                    if !cells[cell] {
+                        continue;
+                    }
+                    let p = ring(cells, nx, ny, i, j);
+                    let count = p.iter().filter(|x| **x).count();
+                    let crossings = transitions(&p);
+                    let (up, right, down, left) = (p[0], p[2], p[4], p[6]);
+                    let keep = if step == 0 {
+                        (up && right && down) || (right && down && left)
+                    } else {
+                        (up && right && left) || (up && down && left)
+                    };
+                    if (2..=6).contains(&count) && crossings == 1 && !keep {
+                        remove.push(cell);
+                    }
+                }
+            }
+            changed |= !remove.is_empty();
+            for cell in remove {
+                cells[cell] = false;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Thinning leaves staircases along diagonals. Cells whose neighbors
+    // stay connected without them are removed so that the cells of a line
+    // only touch the cells before and after them.
+    for j in 0..ny as isize {
+        for i in 0..nx as isize {
+            let cell = j as usize * nx + i as usize;
+            if !cells[cell] {
+                continue;
+            }
+            let p = ring(cells, nx, ny, i, j);
+            if p.iter().filter(|x| **x).count() >= 2 && ring_components(&p) == 1 {
+                cells[cell] = false;
+            }
+        }
+    }
+}
+
+/// Remove short dead ends, which show up wherever a wall has a corner, and
+/// tiny loops.
+fn prune(chains: &mut Vec<Chain>, min_length: f32) {
+    loop {
+        let degree = junction_degrees(chains);
+        let before = chains.len();
+        chains.retain(|chain| {
+            let [a, b] = chain.ends;
+            let dead_end = degree[&a] == 1 || degree[&b] == 1;
+            let short = chain.length() < min_length;
+            !(short && (dead_end || a == b))
+        });
+        if chains.len() == before {
+            return;
+        }
+    }
+}
+
+/// Merge chains that meet at a junction with no other chains, since the
+/// junction was left over from pruning.
+fn join_chains(chains: &mut Vec<Chain>) {
+    loop {
+        let degree = junction_degrees(chains);
+        let Some((&junction, _)) = degree
+            .iter()
+            .find(|(j, d)| **d == 2 && chains.iter().filter(|c| c.ends.contains(*j)).count() == 2)
+        else {
+            return;
+        };
+        let mut found = chains
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.ends.contains(&junction))
+            .map(|(i, _)| i);
+        let (Some(first), Some(second)) = (found.next(), found.next()) else {
+            return;
+        };
+        let mut b = chains.remove(second);
+        let a = &mut chains[first];
+        if a.ends[0] == junction {
+            a.ends.reverse();
+            a.points.reverse();
+        }
+        if b.ends[1] == junction {
+            b.ends.reverse();
+            b.points.reverse();
+        }
+        a.points.extend(b.points.into_iter().skip(1));
+        a.ends[1] = b.ends[1];
+    }
+}
+
+fn junction_degrees(chains: &[Chain]) -> HashMap<usize, usize> {
+    let mut degree = HashMap::new();
+    for chain in chains {
+        for end in chain.ends {
+            *degree.entry(end).or_insert(0) += 1;
+        }
+    }
+    degree
+}
+
+/// Douglas-Peucker simplification, which keeps only the points that are
+/// needed to stay within the tolerance of the original line.
+fn simplify(points: &[Vec2], tolerance: f32) -> Vec<Vec2> {
+    if points.len() <= 2 {
+        return points.to_vec();
+    }
+    let (first, last) = (points[0], points[points.len() - 1]);
+    let (farthest, distance) = points[1..points.len() - 1]
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i + 1, p.distance(closest_point_on_segment(first, last, *p))))
+        .fold(
+            (0, 0.0),
+            |best, next| if next.1 > best.1 { next } else { best },
+        );
+    if distance <= tolerance {
+        return vec![first, last];
+    }
+    let mut simplified = simplify(&points[..=farthest], tolerance);
+    simplified.pop();
+    simplified.extend(simplify(&points[farthest..], tolerance));
+    simplified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corridor_centerline_runs_down_the_middle() {
+        let walls = [
+            [Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)],
+            [Vec2::new(0.0, 2.0), Vec2::new(10.0, 2.0)],
+        ];
+        let graph = corridor_centerlines(&walls, &[], &CenterlineSettings::default());
+        assert!(!graph.edges.is_empty());
+        for v in &graph.vertices {
+            assert!((v.y - 1.0).abs() < 0.25, "{v:?} is off the centerline");
+        }
+        let length: f32 = graph
+            .edges
+            .iter()
+            .map(|[a, b]| graph.vertices[*a].distance(graph.vertices[*b]))
+            .sum();
+        assert!(length > 8.0, "centerline is only {length}m long");
+    }
+
+    #[test]
+    fn narrow_corridors_are_skipped() {
+        let walls = [
+            [Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)],
+            [Vec2::new(0.0, 0.6), Vec2::new(10.0, 0.6)],
+        ];
+        let graph = corridor_centerlines(&walls, &[], &CenterlineSettings::default());
+        assert!(graph.edges.is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{Anchor, Pose};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// An edit of a site that is shared between editors that are working on the
+/// same site at the same time. Elements are identified by their site IDs, so
+/// every editor needs to start from the same file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum SiteEdit {
+    Anchor { element: u32, anchor: Anchor },
+    Name { element: u32, name: String },
+    Pose { element: u32, pose: Pose },
+    Delete { element: u32 },
+}
+
+/// Which property of an element an edit changes. Concurrent edits of
+/// different properties never conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EditedProperty {
+    Anchor,
+    Name,
+    Pose,
+    Existence,
+}
+
+impl SiteEdit {
+    pub fn element(&self) -> u32 {
+        match self {
+            Self::Anchor { element, .. }
+            | Self::Name { element, .. }
+            | Self::Pose { element, .. }
+            | Self::Delete { element } => *element,
+        }
+    }
+
+    pub fn property(&self) -> EditedProperty {
+        match self {
+            Self::Anchor { .. } => EditedProperty::Anchor,
+            Self::Name { .. } => EditedProperty::Name,
+            Self::Pose { .. } => EditedProperty::Pose,
+            Self::Delete { .. } => EditedProperty::Existence,
+        }
+    }
+}
+
+/// An edit along with the information needed to order it against edits from
+/// other editors.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EditMessage {
+    /// Unique name of the editor that made the edit
+    pub author: String,
+    /// Lamport timestamp of the edit
+    pub clock: u64,
+    pub edit: SiteEdit,
+}
+
+impl EditMessage {
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(s: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Merges concurrent edits with a last-writer-wins rule for every property
+/// of every element. Edits are ordered by their Lamport timestamps, and ties
+/// are broken by the name of the author, so every editor that sees the same
+/// set of edits ends up with the same values no matter what order the edits
+/// arrive in.
+#[derive(Debug, Clone, Default)]
+pub struct EditMerger {
+    clock: u64,
+    latest: HashMap<(u32, EditedProperty), (u64, String)>,
+}
+
+impl EditMerger {
+    pub fn clock(&self) -> u64 {
+        self.clock
+    }
+
+    /// Stamp an edit that was made by this editor. Local edits always win
+    /// against every edit that has been seen so far.
+    pub fn stamp(&mut self, author: &str, edit: SiteEdit) -> EditMessage {
+        self.clock += 1;
+        self.latest.insert(
+            (edit.element(), edit.property()),
+            (self.clock, author.to_owned()),
+        );
+        EditMessage {
+            author: author.to_owned(),
+            clock: self.clock,
+            edit,
+        }
+    }
+
+    /// Decide whether an edit from another editor should be applied.
+    pub fn accept(&mut self, message: &EditMessage) -> bool {
+        self.clock = self.clock.max(message.clock);
+        let key = (message.edit.element(), message.edit.property());
+        let stamp = (message.clock, message.author.clone());
+        if let Some(latest) = self.latest.get(&key) {
+            if *latest >= stamp {
+                return false;
+            }
+        }
+        self.latest.insert(key, stamp);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename(author: &str, clock: u64, name: &str) -> EditMessage {
+        EditMessage {
+            author: author.to_owned(),
+            clock,
+            edit: SiteEdit::Name {
+                element: 7,
+                name: name.to_owned(),
+            },
+        }
+    }
+
+    #[test]
+    fn concurrent_edits_converge() {
+        let a = rename("alice", 3, "from_alice");
+        let b = rename("bob", 3, "from_bob");
+
+        let mut first = EditMerger::default();
+        let applied: Vec<_> = [&a, &b].into_iter().filter(|m| first.accept(m)).collect();
+        let mut second = EditMerger::default();
+        let reversed: Vec<_> = [&b, &a].into_iter().filter(|m| second.accept(m)).collect();
+
+        // Both orders end with bob's edit because it wins the tie
+        assert_eq!(applied.last().unwrap().author, "bob");
+        assert_eq!(reversed.last().unwrap().author, "bob");
+        assert_eq!(reversed.len(), 1);
+    }
+
+    #[test]
+    fn local_edits_win_against_older_edits() {
+        let mut merger = EditMerger::default();
+        merger.accept(&rename("bob", 5, "old"));
+        let local = merger.stamp("alice", rename("alice", 0, "new").edit);
+        assert!(local.clock > 5);
+        assert!(!merger.accept(&rename("bob", 5, "stale")));
+        let roundtrip = EditMessage::from_json(&local.to_json().unwrap()).unwrap();
+        assert_eq!(roundtrip.clock, local.clock);
+    }
+}
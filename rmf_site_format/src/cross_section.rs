@@ -0,0 +1,135 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use glam::Vec2;
+
+/// A line in plan view that a site gets cut along to show a vertical
+/// section through all of its levels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SectionLine {
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+impl SectionLine {
+    pub fn new(start: Vec2, end: Vec2) -> Self {
+        Self { start, end }
+    }
+
+    pub fn length(&self) -> f32 {
+        self.start.distance(self.end)
+    }
+
+    pub fn point_at(&self, distance: f32) -> Vec2 {
+        let length = self.length();
+        if length <= f32::EPSILON {
+            return self.start;
+        }
+        self.start + (self.end - self.start) * (distance / length)
+    }
+
+    /// Where the segment from `p0` to `p1` crosses the line, measured as the
+    /// distance from the start of the line. Returns None if they do not
+    /// cross or if they are parallel.
+    pub fn crossing(&self, p0: Vec2, p1: Vec2) -> Option<f32> {
+        let d = self.end - self.start;
+        let e = p1 - p0;
+        let denom = d.perp_dot(e);
+        if denom.abs() <= f32::EPSILON {
+            return None;
+        }
+        let w = p0 - self.start;
+        let t = w.perp_dot(e) / denom;
+        let s = w.perp_dot(d) / denom;
+        ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&s)).then(|| t * self.length())
+    }
+
+    /// The parts of the line that are inside of a polygon, as ranges of
+    /// distance from the start of the line.
+    pub fn intervals_inside(&self, polygon: &[Vec2]) -> Vec<[f32; 2]> {
+        if polygon.len() < 3 {
+            return Vec::new();
+        }
+        let mut cuts: Vec<f32> = vec![0.0, self.length()];
+        for (i, p0) in polygon.iter().enumerate() {
+            let p1 = polygon[(i + 1) % polygon.len()];
+            cuts.extend(self.crossing(*p0, p1));
+        }
+        cuts.sort_by(f32::total_cmp);
+
+        let mut intervals: Vec<[f32; 2]> = Vec::new();
+        for pair in cuts.windows(2) {
+            let [a, b] = [pair[0], pair[1]];
+            if b - a <= f32::EPSILON || !contains(polygon, self.point_at((a + b) / 2.0)) {
+                continue;
+            }
+            match intervals.last_mut() {
+                Some(last) if (last[1] - a).abs() <= f32::EPSILON => last[1] = b,
+                _ => intervals.push([a, b]),
+            }
+        }
+        intervals
+    }
+}
+
+/// Check whether a point is inside of a polygon using the even-odd rule.
+fn contains(polygon: &[Vec2], p: Vec2) -> bool {
+    let mut inside = false;
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        if (a.y > p.y) != (b.y > p.y) {
+            let x = a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if p.x < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_through_concave_floor() {
+        // A U-shaped floor that the line passes through twice
+        let floor = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(3.0, 0.0),
+            Vec2::new(3.0, 3.0),
+            Vec2::new(2.0, 3.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 3.0),
+            Vec2::new(0.0, 3.0),
+        ];
+        let line = SectionLine::new(Vec2::new(-1.0, 2.0), Vec2::new(4.0, 2.0));
+        let intervals = line.intervals_inside(&floor);
+        assert_eq!(intervals.len(), 2);
+        assert!((intervals[0][0] - 1.0).abs() < 1e-5);
+        assert!((intervals[0][1] - 2.0).abs() < 1e-5);
+        assert!((intervals[1][0] - 3.0).abs() < 1e-5);
+        assert!((intervals[1][1] - 4.0).abs() < 1e-5);
+
+        let wall = line.crossing(Vec2::new(0.5, 0.0), Vec2::new(0.5, 5.0));
+        assert!((wall.unwrap() - 1.5).abs() < 1e-5);
+        assert!(line
+            .crossing(Vec2::new(0.5, 3.0), Vec2::new(0.5, 5.0))
+            .is_none());
+    }
+}
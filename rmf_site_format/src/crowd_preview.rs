@@ -0,0 +1,414 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{perturb::SeededRng, *};
+use glam::Vec2;
+use std::collections::{BTreeSet, VecDeque};
+
+/// Agents that move slower than this fraction of their preferred speed are
+/// considered to be held up by other agents.
+const BLOCKED_SPEED_RATIO: f32 = 0.25;
+/// How long an agent must be held up before it is reported as blocked.
+const BLOCKED_TIME: f32 = 2.0;
+/// Strength and range of the repulsion between agents in the social force
+/// model.
+const REPULSION_STRENGTH: f32 = 2.0;
+const REPULSION_RANGE: f32 = 0.3;
+/// How quickly agents adjust their velocity towards their desired velocity.
+const RELAXATION_TIME: f32 = 0.5;
+
+/// The graph that agents of a crowd preview walk along, made from the human
+/// lanes of a level.
+#[derive(Debug, Clone, Default)]
+pub struct WalkGraph {
+    points: Vec<Vec2>,
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl WalkGraph {
+    /// Each lane is given as `(start, end, bidirectional)` indices into
+    /// `points`.
+    pub fn new(points: Vec<Vec2>, lanes: impl IntoIterator<Item = (usize, usize, bool)>) -> Self {
+        let mut neighbors = vec![Vec::new(); points.len()];
+        for (start, end, bidirectional) in lanes {
+            if start >= points.len() || end >= points.len() {
+                continue;
+            }
+            neighbors[start].push(end);
+            if bidirectional {
+                neighbors[end].push(start);
+            }
+        }
+        Self { points, neighbors }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The point of the graph that is closest to `p`.
+    pub fn nearest(&self, p: Vec2) -> Option<usize> {
+        self.points
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                a.distance_squared(p)
+                    .partial_cmp(&b.distance_squared(p))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|(i, _)| i)
+    }
+
+    /// The points along the shortest walk from `from` to `to`, including
+    /// both ends.
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<Vec<Vec2>> {
+        let n = self.points.len();
+        if from >= n || to >= n {
+            return None;
+        }
+        let mut cost = vec![f32::INFINITY; n];
+        let mut previous = vec![None; n];
+        let mut done = vec![false; n];
+        cost[from] = 0.0;
+        loop {
+            let Some(current) =
+                (0..n)
+                    .filter(|i| !done[*i] && cost[*i].is_finite())
+                    .min_by(|a, b| {
+                        cost[*a]
+                            .partial_cmp(&cost[*b])
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    })
+            else {
+                return None;
+            };
+            if current == to {
+                break;
+            }
+            done[current] = true;
+            for next in &self.neighbors[current] {
+                let c = cost[current] + self.points[current].distance(self.points[*next]);
+                if c < cost[*next] {
+                    cost[*next] = c;
+                    previous[*next] = Some(current);
+                }
+            }
+        }
+
+        let mut path = vec![self.points[to]];
+        let mut current = to;
+        while let Some(p) = previous[current] {
+            path.push(self.points[p]);
+            current = p;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// One agent walking around a crowd preview.
+#[derive(Debug, Clone)]
+pub struct PreviewAgent {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub radius: f32,
+    pub pref_speed: f32,
+    pub max_speed: f32,
+    pub max_accel: f32,
+    pub neighbor_dist: f32,
+    /// Index of the goal set that the agent walks between, if its state has
+    /// one
+    pub goal_set: Option<usize>,
+    /// How long the agent has been held up by other agents, in seconds
+    pub blocked_time: f32,
+    route: VecDeque<Vec2>,
+    goal: Option<usize>,
+}
+
+impl PreviewAgent {
+    pub fn is_blocked(&self) -> bool {
+        self.blocked_time > BLOCKED_TIME
+    }
+
+    /// True if the agent has somewhere to go.
+    pub fn is_walking(&self) -> bool {
+        !self.route.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PreviewGoalSet {
+    pub set_id: usize,
+    pub goals: Vec<Vec2>,
+}
+
+/// A quick approximation of a crowd simulation that runs inside the editor.
+/// Agents walk between the goals of their goal set along the shortest route
+/// through a [`WalkGraph`] and avoid each other with a social force model.
+#[derive(Debug, Clone)]
+pub struct CrowdPreview {
+    pub graph: WalkGraph,
+    pub goal_sets: Vec<PreviewGoalSet>,
+    pub agents: Vec<PreviewAgent>,
+    /// Goals that could not be reached from where an agent was standing,
+    /// given as `(set_id, index of the goal within its set)`
+    pub unreachable: BTreeSet<(usize, usize)>,
+    rng: SeededRng,
+}
+
+impl CrowdPreview {
+    /// Set up a preview of a crowd simulation. `anchor_position` gives the
+    /// position of the anchors of the goal sets, or `None` for anchors that
+    /// are not part of the preview, such as anchors on other levels.
+    pub fn new<T: RefTrait>(
+        crowd_sim: &CrowdSim<T>,
+        graph: WalkGraph,
+        anchor_position: impl Fn(T) -> Option<Vec2>,
+        seed: u64,
+    ) -> Self {
+        let goal_sets: Vec<PreviewGoalSet> = crowd_sim
+            .goal_sets
+            .iter()
+            .map(|set| PreviewGoalSet {
+                set_id: set.set_id,
+                goals: set
+                    .anchors
+                    .iter()
+                    .filter_map(|a| anchor_position(*a))
+                    .collect(),
+            })
+            .collect();
+
+        let mut agents = Vec::new();
+        for group in &crowd_sim.agent_groups {
+            let profile = crowd_sim
+                .agent_profiles
+                .iter()
+                .find(|p| p.name == group.profile_selector)
+                .cloned()
+                .unwrap_or_default();
+            let goal_set = crowd_sim
+                .states
+                .iter()
+                .find(|s| s.name == group.state_selector)
+                .and_then(|s| s.goal_set)
+                .and_then(|id| goal_sets.iter().position(|g| g.set_id == id));
+
+            // Place the agents of a group on a ring around their spawn point
+            // so that they do not start on top of each other.
+            let spawn = Vec2::from(group.spawn_point);
+            let count = group.agents_number;
+            let ring = if count > 1 {
+                (count as f32 * 2.0 * profile.radius) / std::f32::consts::TAU
+            } else {
+                0.0
+            };
+            for i in 0..count {
+                let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+                agents.push(PreviewAgent {
+                    position: spawn + ring * Vec2::from_angle(angle),
+                    velocity: Vec2::ZERO,
+                    radius: profile.radius,
+                    pref_speed: profile.pref_speed,
+                    max_speed: profile.max_speed,
+                    max_accel: profile.max_accel,
+                    neighbor_dist: profile.neighbor_dist,
+                    goal_set,
+                    blocked_time: 0.0,
+                    route: VecDeque::new(),
+                    goal: None,
+                });
+            }
+        }
+
+        let mut preview = Self {
+            graph,
+            goal_sets,
+            agents,
+            unreachable: BTreeSet::new(),
+            rng: SeededRng(seed),
+        };
+        for i in 0..preview.agents.len() {
+            preview.choose_goal(i);
+        }
+        preview
+    }
+
+    /// The number of agents that are currently held up by other agents.
+    pub fn blocked_agents(&self) -> usize {
+        self.agents.iter().filter(|a| a.is_blocked()).count()
+    }
+
+    /// Advance the preview by `dt` seconds.
+    pub fn step(&mut self, dt: f32) {
+        if dt <= 0.0 {
+            return;
+        }
+
+        let forces: Vec<Vec2> = self
+            .agents
+            .iter()
+            .enumerate()
+            .map(|(i, agent)| {
+                let desired = agent
+                    .route
+                    .front()
+                    .map(|target| (*target - agent.position).normalize_or_zero() * agent.pref_speed)
+                    .unwrap_or(Vec2::ZERO);
+                let mut force = ((desired - agent.velocity) / RELAXATION_TIME)
+                    .clamp_length_max(agent.max_accel.max(f32::EPSILON));
+
+                for (j, other) in self.agents.iter().enumerate() {
+                    if i == j {
+                        continue;
+                    }
+                    let offset = agent.position - other.position;
+                    let distance = offset.length();
+                    if distance < 1e-4 || distance > agent.neighbor_dist {
+                        continue;
+                    }
+                    let overlap = agent.radius + other.radius - distance;
+                    force +=
+                        REPULSION_STRENGTH * (overlap / REPULSION_RANGE).exp() * offset / distance;
+                }
+                force
+            })
+            .collect();
+
+        for (agent, force) in self.agents.iter_mut().zip(forces) {
+            agent.velocity = (agent.velocity + force * dt).clamp_length_max(agent.max_speed);
+            agent.position += agent.velocity * dt;
+            if agent.is_walking()
+                && agent.velocity.length() < BLOCKED_SPEED_RATIO * agent.pref_speed
+            {
+                agent.blocked_time += dt;
+            } else {
+                agent.blocked_time = 0.0;
+            }
+        }
+
+        // Agents are solid, so push apart any that still overlap.
+        for i in 0..self.agents.len() {
+            for j in (i + 1)..self.agents.len() {
+                let offset = self.agents[i].position - self.agents[j].position;
+                let distance = offset.length();
+                let overlap = self.agents[i].radius + self.agents[j].radius - distance;
+                if overlap > 0.0 && distance > 1e-4 {
+                    let push = 0.5 * overlap * offset / distance;
+                    self.agents[i].position += push;
+                    self.agents[j].position -= push;
+                }
+            }
+        }
+
+        for i in 0..self.agents.len() {
+            let agent = &mut self.agents[i];
+            if let Some(target) = agent.route.front() {
+                if target.distance(agent.position) < agent.radius.max(0.1) {
+                    agent.route.pop_front();
+                    if agent.route.is_empty() {
+                        self.choose_goal(i);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pick a new goal for an agent from its goal set and plan a route to
+    /// it. Goals that cannot be reached are recorded and skipped.
+    fn choose_goal(&mut self, i: usize) {
+        let agent = &self.agents[i];
+        let Some(set) = agent.goal_set.and_then(|s| self.goal_sets.get(s)) else {
+            return;
+        };
+        let count = set.goals.len();
+        if count == 0 {
+            return;
+        }
+        let previous = agent.goal;
+        let first = (self.rng.next_u64() % count as u64) as usize;
+        let start = self.graph.nearest(agent.position);
+        for k in 0..count {
+            let goal = (first + k) % count;
+            if count > 1 && Some(goal) == previous {
+                continue;
+            }
+            let target = set.goals[goal];
+            let route = start
+                .zip(self.graph.nearest(target))
+                .and_then(|(from, to)| self.graph.shortest_path(from, to));
+            let Some(route) = route else {
+                self.unreachable.insert((set.set_id, goal));
+                continue;
+            };
+            let agent = &mut self.agents[i];
+            agent.route = route.into_iter().chain([target]).collect();
+            agent.goal = Some(goal);
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agents_walk_to_reachable_goals() {
+        // Two points joined by a lane, and one point off on its own
+        let graph = WalkGraph::new(
+            vec![
+                Vec2::new(0.0, 0.0),
+                Vec2::new(4.0, 0.0),
+                Vec2::new(0.0, 10.0),
+            ],
+            [(0, 1, true)],
+        );
+        let crowd_sim = CrowdSim::<u32> {
+            agent_profiles: vec![AgentProfile::default()],
+            agent_groups: vec![AgentGroup {
+                profile_selector: "human".to_owned(),
+                state_selector: "walk".to_owned(),
+                agents_number: 1,
+                ..Default::default()
+            }],
+            goal_sets: vec![GoalSet {
+                set_id: 0,
+                capacity: 1,
+                anchors: vec![1, 2],
+            }],
+            states: vec![CrowdState {
+                name: "walk".to_owned(),
+                goal_set: Some(0),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let positions = [Vec2::ZERO, Vec2::new(4.0, 0.0), Vec2::new(0.0, 10.0)];
+        let mut preview =
+            CrowdPreview::new(&crowd_sim, graph, |a| positions.get(a as usize).copied(), 7);
+
+        let mut furthest: f32 = 0.0;
+        for _ in 0..200 {
+            preview.step(0.1);
+            furthest = furthest.max(preview.agents[0].position.x);
+        }
+        assert!(furthest > 3.5);
+        assert!(preview.unreachable.contains(&(0, 1)));
+        assert!(!preview.unreachable.contains(&(0, 0)));
+    }
+}
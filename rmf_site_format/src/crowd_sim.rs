@@ -0,0 +1,210 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+#[cfg(feature = "bevy")]
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Describes the simulated crowd of humans that walk around a site. This
+/// follows the crowd simulation section of legacy building maps, which is
+/// based on the Menge crowd simulator.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+pub struct CrowdSim<T: RefTrait> {
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub enable: bool,
+    /// Seconds between each update of the crowd simulation
+    #[serde(default = "CrowdSim::<T>::default_update_time_step")]
+    pub update_time_step: f32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agent_profiles: Vec<AgentProfile>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agent_groups: Vec<AgentGroup>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub goal_sets: Vec<GoalSet<T>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub states: Vec<CrowdState>,
+    /// Models that get animated to represent the agents
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub model_types: Vec<CrowdModelType>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub obstacle_set: ObstacleSet,
+}
+
+impl<T: RefTrait> Default for CrowdSim<T> {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            update_time_step: Self::default_update_time_step(),
+            agent_profiles: Vec::new(),
+            agent_groups: Vec::new(),
+            goal_sets: Vec::new(),
+            states: Vec::new(),
+            model_types: Vec::new(),
+            obstacle_set: ObstacleSet::default(),
+        }
+    }
+}
+
+impl<T: RefTrait> CrowdSim<T> {
+    pub fn default_update_time_step() -> f32 {
+        0.1
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn convert<U: RefTrait>(&self, id_map: &HashMap<T, U>) -> Result<CrowdSim<U>, T> {
+        Ok(CrowdSim {
+            enable: self.enable,
+            update_time_step: self.update_time_step,
+            agent_profiles: self.agent_profiles.clone(),
+            agent_groups: self.agent_groups.clone(),
+            goal_sets: self
+                .goal_sets
+                .iter()
+                .map(|g| g.convert(id_map))
+                .collect::<Result<_, _>>()?,
+            states: self.states.clone(),
+            model_types: self.model_types.clone(),
+            obstacle_set: self.obstacle_set.clone(),
+        })
+    }
+}
+
+/// How a kind of agent moves, as used by the ORCA model of Menge.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AgentProfile {
+    pub name: String,
+    pub class: usize,
+    pub max_accel: f32,
+    /// Degrees per second
+    pub max_angle_vel: f32,
+    pub max_neighbors: usize,
+    pub max_speed: f32,
+    pub neighbor_dist: f32,
+    pub obstacle_set: usize,
+    pub pref_speed: f32,
+    /// Radius of the agent in meters
+    pub radius: f32,
+    pub orca_tau: f32,
+    pub orca_tau_obst: f32,
+}
+
+impl Default for AgentProfile {
+    fn default() -> Self {
+        Self {
+            name: "human".to_owned(),
+            class: 1,
+            max_accel: 0.5,
+            max_angle_vel: 360.0,
+            max_neighbors: 10,
+            max_speed: 1.5,
+            neighbor_dist: 5.0,
+            obstacle_set: 1,
+            pref_speed: 1.0,
+            radius: 0.25,
+            orca_tau: 1.0,
+            orca_tau_obst: 0.4,
+        }
+    }
+}
+
+/// A group of agents that spawn together.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct AgentGroup {
+    pub group_id: usize,
+    /// Name of the [`AgentProfile`] of the agents
+    pub profile_selector: String,
+    /// Name of the [`CrowdState`] that the agents start in
+    pub state_selector: String,
+    /// How many agents are spawned
+    pub agents_number: usize,
+    /// Names of agents that are controlled from outside of the crowd
+    /// simulation, such as robots
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agents_name: Vec<String>,
+    /// Where the agents are spawned, in meters
+    pub spawn_point: [f32; 2],
+}
+
+/// A set of anchors that agents can choose as goals.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct GoalSet<T: RefTrait> {
+    pub set_id: usize,
+    /// How many agents can be at each goal of the set at once
+    pub capacity: usize,
+    pub anchors: Vec<T>,
+}
+
+impl<T: RefTrait> GoalSet<T> {
+    pub fn convert<U: RefTrait>(&self, id_map: &HashMap<T, U>) -> Result<GoalSet<U>, T> {
+        Ok(GoalSet {
+            set_id: self.set_id,
+            capacity: self.capacity,
+            anchors: self
+                .anchors
+                .iter()
+                .map(|a| id_map.get(a).copied().ok_or(*a))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// A behavior state of the agents.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct CrowdState {
+    pub name: String,
+    /// Agents stop being simulated once they reach a final state
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub is_final: bool,
+    /// The goal set that agents in this state walk towards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal_set: Option<usize>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub navmesh_file_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct CrowdModelType {
+    pub typename: String,
+    pub animation: String,
+    pub animation_speed: f32,
+    pub model_uri: String,
+}
+
+/// The obstacles that agents avoid.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ObstacleSet {
+    pub class: usize,
+    pub file_name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+impl Default for ObstacleSet {
+    fn default() -> Self {
+        Self {
+            class: 1,
+            file_name: String::new(),
+            kind: "nav_mesh".to_owned(),
+        }
+    }
+}
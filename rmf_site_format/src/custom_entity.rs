@@ -0,0 +1,227 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+#[cfg(feature = "bevy")]
+use bevy::prelude::{Bundle, Component, Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Describes a kind of entity that is specific to a site, such as a nurse call
+/// station. Schemas are registered from a YAML file that maps the name of each
+/// kind to its schema, and they are saved into every site that uses them.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CustomEntitySchema {
+    /// The fields that every entity of this kind has, along with their
+    /// default values. The type of each field is the type of its default.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, CustomFieldValue>,
+    /// A short glyph, such as an emoji, shown next to the name of the kind
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub style: CustomEntityStyle,
+}
+
+/// The value of one field of a custom entity.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum CustomFieldValue {
+    Boolean(bool),
+    Number(f64),
+    Text(String),
+}
+
+impl Default for CustomFieldValue {
+    fn default() -> Self {
+        Self::Text(String::new())
+    }
+}
+
+impl CustomFieldValue {
+    /// Check whether two values are of the same type.
+    pub fn same_type(&self, other: &CustomFieldValue) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// How the editor renders entities of a custom kind.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CustomEntityStyle {
+    #[serde(default)]
+    pub shape: CustomEntityShape,
+    /// RGBA color of the shape
+    #[serde(default = "CustomEntityStyle::default_color")]
+    pub color: [f32; 4],
+    /// The largest dimension of the shape, in meters
+    #[serde(default = "CustomEntityStyle::default_size")]
+    pub size: f32,
+}
+
+impl Default for CustomEntityStyle {
+    fn default() -> Self {
+        Self {
+            shape: CustomEntityShape::default(),
+            color: Self::default_color(),
+            size: Self::default_size(),
+        }
+    }
+}
+
+impl CustomEntityStyle {
+    fn default_color() -> [f32; 4] {
+        [0.8, 0.4, 0.9, 1.0]
+    }
+
+    fn default_size() -> f32 {
+        0.3
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomEntityShape {
+    #[default]
+    Sphere,
+    Box,
+    Cylinder,
+}
+
+/// The custom entity schemas that a site makes use of, keyed by the name of
+/// each kind.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
+pub struct CustomEntitySchemas(pub BTreeMap<String, CustomEntitySchema>);
+
+impl CustomEntitySchemas {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn from_str_yaml(s: &str) -> serde_yaml::Result<Self> {
+        serde_yaml::from_str(s)
+    }
+
+    /// Add the schemas of another set, replacing any kinds with the same name.
+    pub fn merge(&mut self, other: CustomEntitySchemas) {
+        self.0.extend(other.0);
+    }
+}
+
+/// An instance of a custom kind of entity placed on a level.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "bevy", derive(Bundle))]
+pub struct CustomEntity {
+    pub name: NameInSite,
+    pub kind: CustomEntityKind,
+    pub pose: Pose,
+    #[serde(default, skip_serializing_if = "CustomFields::is_empty")]
+    pub fields: CustomFields,
+    #[serde(skip)]
+    pub marker: CustomEntityMarker,
+}
+
+/// The name of the schema that a custom entity belongs to.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
+pub struct CustomEntityKind(pub String);
+
+/// The values of the fields of a custom entity.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
+pub struct CustomFields(pub BTreeMap<String, CustomFieldValue>);
+
+impl CustomFields {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+pub struct CustomEntityMarker;
+
+impl CustomEntitySchema {
+    /// Create a new entity of this kind with every field set to its default.
+    pub fn instantiate(&self, kind: &str, pose: Pose) -> CustomEntity {
+        CustomEntity {
+            name: NameInSite(kind.to_owned()),
+            kind: CustomEntityKind(kind.to_owned()),
+            pose,
+            fields: CustomFields(self.fields.clone()),
+            marker: Default::default(),
+        }
+    }
+
+    /// Make a set of field values conform to this schema by adding any missing
+    /// fields and replacing values that have the wrong type with the default.
+    /// Fields that the schema does not know about are kept so that no data is
+    /// lost when a schema changes.
+    pub fn conform(&self, fields: &mut CustomFields) {
+        for (name, default) in &self.fields {
+            match fields.0.get_mut(name) {
+                Some(value) if value.same_type(default) => {}
+                Some(value) => *value = default.clone(),
+                None => {
+                    fields.0.insert(name.clone(), default.clone());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_file_is_parsed() {
+        let schemas = CustomEntitySchemas::from_str_yaml(
+            r#"
+nurse_call_station:
+  icon: "🔔"
+  fields:
+    room: "101"
+    priority: 2
+    wall_mounted: true
+  style:
+    shape: box
+    color: [1.0, 0.2, 0.2, 1.0]
+"#,
+        )
+        .unwrap();
+        let schema = schemas.0.get("nurse_call_station").unwrap();
+        assert_eq!(schema.style.shape, CustomEntityShape::Box);
+        assert_eq!(schema.style.size, 0.3);
+        assert_eq!(
+            schema.fields.get("priority"),
+            Some(&CustomFieldValue::Number(2.0))
+        );
+
+        let entity = schema.instantiate("nurse_call_station", Pose::default());
+        let mut fields = CustomFields(BTreeMap::from_iter([
+            ("room".to_owned(), CustomFieldValue::Boolean(false)),
+            ("notes".to_owned(), CustomFieldValue::Text("old".to_owned())),
+        ]));
+        schema.conform(&mut fields);
+        assert_eq!(fields.0.get("room"), entity.fields.0.get("room"));
+        assert_eq!(fields.0.len(), 4);
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// Describes the semantic entities of a site that was exported for a game
+/// engine digital twin, such as Unity or Unreal. All positions are in the
+/// coordinates of the export options.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DigitalTwinManifest {
+    pub site: String,
+    pub options: ExportOptions,
+    pub levels: Vec<DigitalTwinLevel>,
+    pub doors: Vec<DigitalTwinDoor>,
+    pub chargers: Vec<DigitalTwinLocation>,
+    pub lifts: Vec<DigitalTwinLift>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DigitalTwinLevel {
+    pub name: String,
+    /// Name of the root node of the level inside of its glTF file
+    pub node: String,
+    /// Path of the glTF file of the level, relative to the manifest
+    pub file: String,
+    pub elevation: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DigitalTwinDoor {
+    pub name: String,
+    pub level: String,
+    pub kind: String,
+    pub endpoints: [[f32; 3]; 2],
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DigitalTwinLocation {
+    pub name: String,
+    pub level: String,
+    pub position: [f32; 3],
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DigitalTwinLift {
+    pub name: String,
+    /// Center of the cabin at the lowest level
+    pub position: [f32; 3],
+}
+
+impl DigitalTwinManifest {
+    pub fn to_writer_json<W: io::Write>(&self, writer: W) -> serde_json::Result<()> {
+        serde_json::to_writer_pretty(writer, self)
+    }
+}
+
+/// The name of the root node of a level in a digital twin export.
+pub fn digital_twin_level_node(level_name: &str) -> String {
+    format!("level_{level_name}")
+}
+
+/// The path of the glTF file of a level in a digital twin export.
+pub fn digital_twin_level_file(level_name: &str) -> String {
+    let name: String = level_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("levels/{name}.glb")
+}
+
+impl Site {
+    pub fn to_digital_twin_manifest(&self, options: &ExportOptions) -> DigitalTwinManifest {
+        let mut manifest = DigitalTwinManifest {
+            site: self.properties.name.0.clone(),
+            options: options.clone(),
+            ..Default::default()
+        };
+
+        let point_on_level = |level: &Level, anchor: u32| -> Option<[f32; 3]> {
+            let [x, y] = level
+                .anchors
+                .get(&anchor)
+                .or_else(|| self.anchors.get(&anchor))?
+                .translation_for_category(Category::General);
+            let p = Vec3::new(x, y, level.properties.elevation.0);
+            Some(options.transform_point(p).to_array())
+        };
+
+        for level in self.levels.values() {
+            let level_name = &level.properties.name.0;
+            manifest.levels.push(DigitalTwinLevel {
+                name: level_name.clone(),
+                node: digital_twin_level_node(level_name),
+                file: digital_twin_level_file(level_name),
+                elevation: (level.properties.elevation.0 - options.origin[2]) * options.scale,
+            });
+
+            for door in level.doors.values() {
+                let [a0, a1] = door.anchors.array();
+                let (Some(p0), Some(p1)) = (point_on_level(level, a0), point_on_level(level, a1))
+                else {
+                    continue;
+                };
+                manifest.doors.push(DigitalTwinDoor {
+                    name: door.name.0.clone(),
+                    level: level_name.clone(),
+                    kind: door.kind.label().to_owned(),
+                    endpoints: [p0, p1],
+                });
+            }
+
+            for location in self.navigation.guided.locations.values() {
+                if !location.tags.0.iter().any(|t| *t == LocationTag::Charger) {
+                    continue;
+                }
+                if !level.anchors.contains_key(&location.anchor.0) {
+                    continue;
+                }
+                let Some(position) = point_on_level(level, location.anchor.0) else {
+                    continue;
+                };
+                manifest.chargers.push(DigitalTwinLocation {
+                    name: location.name.0.clone(),
+                    level: level_name.clone(),
+                    position,
+                });
+            }
+        }
+
+        let lowest_elevation = self
+            .levels
+            .values()
+            .map(|l| l.properties.elevation.0)
+            .reduce(f32::min)
+            .unwrap_or(0.0);
+        for lift in self.lifts.values() {
+            let Some(center) = lift.properties.center(self) else {
+                continue;
+            };
+            let p = Vec3::new(center.trans[0], center.trans[1], lowest_elevation);
+            manifest.lifts.push(DigitalTwinLift {
+                name: lift.properties.name.0.clone(),
+                position: options.transform_point(p).to_array(),
+            });
+        }
+
+        manifest
+    }
+}
@@ -33,10 +33,42 @@ pub struct Door<T: RefTrait> {
     pub name: NameInSite,
     /// What kind of door is it.
     pub kind: DoorType,
+    /// How long the door takes to operate.
+    #[serde(default, skip_serializing_if = "DoorTiming::is_default")]
+    pub timing: DoorTiming,
+    /// Whether this door is an emergency exit of the building.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub emergency_exit: EmergencyExit,
     #[serde(skip)]
     pub marker: DoorMarker,
 }
 
+/// How long a door takes to operate, in seconds. Anything that is not set
+/// falls back on the defaults of the door plugin.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+pub struct DoorTiming {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_duration: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub close_duration: Option<f32>,
+    /// How long the door stays open before it starts to close
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dwell: Option<f32>,
+}
+
+impl DoorTiming {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// How long a robot has to wait in front of the door before it can drive
+    /// through. This is zero when no open duration is known.
+    pub fn passage_delay(&self) -> f32 {
+        self.open_duration.unwrap_or(0.0)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "bevy", derive(Component))]
 pub enum DoorType {
@@ -394,6 +426,8 @@ impl Door<Entity> {
             anchors,
             name: self.name.clone(),
             kind: self.kind.clone(),
+            timing: self.timing,
+            emergency_exit: self.emergency_exit,
             marker: Default::default(),
         }
     }
@@ -405,6 +439,8 @@ impl<T: RefTrait> Door<T> {
             anchors: self.anchors.convert(id_map)?,
             name: self.name.clone(),
             kind: self.kind.clone(),
+            timing: self.timing,
+            emergency_exit: self.emergency_exit,
             marker: Default::default(),
         })
     }
@@ -416,6 +452,8 @@ impl<T: RefTrait> From<Edge<T>> for Door<T> {
             anchors: edge,
             name: NameInSite("<Unnamed>".to_string()),
             kind: SingleSlidingDoor::default().into(),
+            timing: Default::default(),
+            emergency_exit: Default::default(),
             marker: Default::default(),
         }
     }
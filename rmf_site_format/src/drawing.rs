@@ -46,6 +46,10 @@ pub struct Drawing {
     pub fiducials: BTreeMap<u32, Fiducial<u32>>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub measurements: BTreeMap<u32, Measurement<u32>>,
+    /// Visibility chosen specifically for this drawing. When this is not set
+    /// the drawing follows the [`GlobalDrawingVisibility`] of its site.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<LayerVisibility>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -60,6 +64,10 @@ pub struct DrawingProperties {
         skip_serializing_if = "PreferredSemiTransparency::is_default_for_drawing"
     )]
     pub preferred_semi_transparency: PreferredSemiTransparency,
+    #[serde(default, skip_serializing_if = "Rectification::is_none")]
+    pub rectification: Rectification,
+    #[serde(default, skip_serializing_if = "DrawingSourceInfo::is_default")]
+    pub source_info: DrawingSourceInfo,
 }
 
 impl Default for DrawingProperties {
@@ -70,6 +78,217 @@ impl Default for DrawingProperties {
             pose: Default::default(),
             pixels_per_meter: Default::default(),
             preferred_semi_transparency: PreferredSemiTransparency::for_drawing(),
+            rectification: Default::default(),
+            source_info: Default::default(),
         }
     }
 }
+
+/// What the image of a drawing looked like when its scale was calibrated.
+/// If the image gets swapped out on disk the calibration no longer applies,
+/// so this is compared against the image every time the drawing is loaded.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+pub struct DrawingSourceInfo {
+    /// Checksum of the bytes of the image file, see [`Self::checksum_of`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Width and height of the image in pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<[u32; 2]>,
+    /// Resolution that the drawing was scanned or rendered at, in dots per
+    /// inch. Together with the [`PixelsPerMeter`] of the drawing this gives
+    /// the physical scale of the printed plan.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dpi: Option<f32>,
+}
+
+impl DrawingSourceInfo {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// True if nothing about the image has been recorded yet.
+    pub fn is_unrecorded(&self) -> bool {
+        self.checksum.is_none() && self.size.is_none()
+    }
+
+    /// A 64-bit FNV-1a hash of the file contents, written in hexadecimal.
+    /// This only needs to notice that a file changed, so it does not need
+    /// to be cryptographically secure.
+    pub fn checksum_of(bytes: &[u8]) -> String {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in bytes {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        format!("{hash:016x}")
+    }
+
+    /// Describe how an image that was just loaded differs from the recorded
+    /// one. Only properties that are known for both of them are compared.
+    pub fn mismatch(&self, observed: &DrawingSourceInfo) -> Option<String> {
+        let mut reasons = Vec::new();
+        if let (Some(recorded), Some(observed)) = (self.size, observed.size) {
+            if recorded != observed {
+                reasons.push(format!(
+                    "the size changed from {}x{} to {}x{} pixels",
+                    recorded[0], recorded[1], observed[0], observed[1],
+                ));
+            }
+        }
+        if let (Some(recorded), Some(observed)) = (&self.checksum, &observed.checksum) {
+            if recorded != observed {
+                reasons.push("the file contents changed".to_owned());
+            }
+        }
+        (!reasons.is_empty()).then(|| reasons.join(" and "))
+    }
+
+    /// The denominator of the physical scale of the plan, e.g. 100.0 for a
+    /// plan drawn at 1:100, if the resolution of the drawing is known.
+    pub fn plan_scale(&self, pixels_per_meter: &PixelsPerMeter) -> Option<f32> {
+        let dpi = self.dpi.filter(|dpi| *dpi > 0.0)?;
+        if pixels_per_meter.0 <= 0.0 {
+            return None;
+        }
+        // Pixels per meter of paper, divided by pixels per meter of the world
+        Some(dpi / 0.0254 / pixels_per_meter.0)
+    }
+}
+
+/// Four corners of a drawing that were matched with their known real world
+/// coordinates. Each pixel coordinate is measured in the image with u going
+/// right and v going down. Each real coordinate is in meters, relative to
+/// the origin of the drawing, with y going up.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct RectificationPoints {
+    pub pixels: [[f32; 2]; 4],
+    pub meters: [[f32; 2]; 4],
+}
+
+impl RectificationPoints {
+    /// Compute the homography that maps image pixels onto real coordinates.
+    /// This returns None if three or more of the points are collinear.
+    pub fn homography(&self) -> Option<Homography> {
+        // Each correspondence gives two rows of the linear system that
+        // solves for the first eight entries of the matrix. The last entry
+        // is fixed to 1.
+        let mut a = [[0.0_f64; 9]; 8];
+        for (i, (p, m)) in self.pixels.iter().zip(self.meters.iter()).enumerate() {
+            let (x, y) = (p[0] as f64, p[1] as f64);
+            let (u, v) = (m[0] as f64, m[1] as f64);
+            a[2 * i] = [x, y, 1.0, 0.0, 0.0, 0.0, -u * x, -u * y, u];
+            a[2 * i + 1] = [0.0, 0.0, 0.0, x, y, 1.0, -v * x, -v * y, v];
+        }
+
+        for col in 0..8 {
+            let pivot = (col..8).max_by(|i, j| a[*i][col].abs().total_cmp(&a[*j][col].abs()))?;
+            if a[pivot][col].abs() < 1e-9 {
+                return None;
+            }
+            a.swap(col, pivot);
+            let pivot_row = a[col];
+            for (r, row) in a.iter_mut().enumerate() {
+                if r == col {
+                    continue;
+                }
+                let factor = row[col] / pivot_row[col];
+                for (x, p) in row.iter_mut().zip(pivot_row.iter()).skip(col) {
+                    *x -= factor * p;
+                }
+            }
+        }
+
+        let mut h = [1.0_f32; 9];
+        for (i, (entry, row)) in h.iter_mut().zip(a.iter()).enumerate() {
+            *entry = (row[8] / row[i]) as f32;
+        }
+        Some(Homography(h))
+    }
+}
+
+/// A projective transform stored in row-major order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Homography(pub [f32; 9]);
+
+impl Homography {
+    pub fn apply(&self, p: [f32; 2]) -> [f32; 2] {
+        let h = &self.0;
+        let w = h[6] * p[0] + h[7] * p[1] + h[8];
+        [
+            (h[0] * p[0] + h[1] * p[1] + h[2]) / w,
+            (h[3] * p[0] + h[4] * p[1] + h[5]) / w,
+        ]
+    }
+}
+
+/// A correction for drawings that were scanned at an angle. The image file
+/// is never modified; the editor warps how the image is displayed instead.
+/// Anchors, fiducials, and measurements of the drawing keep their pixel
+/// coordinates in the original image.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+pub struct Rectification(pub Option<RectificationPoints>);
+
+impl Rectification {
+    pub fn is_none(&self) -> bool {
+        self.0.is_none()
+    }
+
+    pub fn homography(&self) -> Option<Homography> {
+        self.0.as_ref().and_then(|points| points.homography())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drawing_source_mismatch_only_compares_known_properties() {
+        let recorded = DrawingSourceInfo {
+            checksum: Some(DrawingSourceInfo::checksum_of(b"old image")),
+            size: Some([1000, 800]),
+            dpi: Some(300.0),
+        };
+        let same = DrawingSourceInfo {
+            checksum: None,
+            ..recorded.clone()
+        };
+        assert!(recorded.mismatch(&same).is_none());
+
+        let swapped = DrawingSourceInfo {
+            checksum: Some(DrawingSourceInfo::checksum_of(b"new image")),
+            size: Some([1200, 800]),
+            dpi: None,
+        };
+        let reason = recorded.mismatch(&swapped).unwrap();
+        assert!(reason.contains("1000x800"));
+        assert!(reason.contains("contents"));
+
+        // 300 dpi is about 11811 pixels per meter of paper
+        let scale = recorded.plan_scale(&PixelsPerMeter(118.11)).unwrap();
+        assert!((scale - 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn homography_maps_corners() {
+        let points = RectificationPoints {
+            pixels: [[10.0, 20.0], [210.0, 30.0], [200.0, 180.0], [0.0, 170.0]],
+            meters: [[0.0, 0.0], [4.0, 0.0], [4.0, -3.0], [0.0, -3.0]],
+        };
+        let h = points.homography().unwrap();
+        for (p, m) in points.pixels.iter().zip(points.meters.iter()) {
+            let q = h.apply(*p);
+            assert!((q[0] - m[0]).abs() < 1e-3 && (q[1] - m[1]).abs() < 1e-3);
+        }
+
+        let collinear = RectificationPoints {
+            pixels: [[0.0, 0.0], [1.0, 0.0], [2.0, 0.0], [0.0, 1.0]],
+            ..points
+        };
+        assert!(collinear.homography().is_none());
+    }
+}
@@ -0,0 +1,208 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+#[cfg(feature = "bevy")]
+use bevy::prelude::{Component, Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// A logical group of elements, such as "Pharmacy wing". Groups are separate
+/// from the level hierarchy, so their members may come from any level, and a
+/// group may contain further groups.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EntityGroup<T: RefTrait> {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub members: BTreeSet<T>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subgroups: Vec<EntityGroup<T>>,
+    /// Hide every member of this group and its subgroups
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub hidden: bool,
+    /// Prevent every member of this group and its subgroups from being
+    /// selected or moved
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub locked: bool,
+}
+
+impl<T: RefTrait> EntityGroup<T> {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            members: BTreeSet::new(),
+            subgroups: Vec::new(),
+            hidden: false,
+            locked: false,
+        }
+    }
+
+    /// Every member of this group, including the members of its subgroups.
+    pub fn all_members(&self) -> BTreeSet<T> {
+        let mut members = self.members.clone();
+        for subgroup in &self.subgroups {
+            members.extend(subgroup.all_members());
+        }
+        members
+    }
+
+    /// Drop the members that do not satisfy `f`, in this group and all of its
+    /// subgroups.
+    pub fn retain_members(&mut self, f: &impl Fn(&T) -> bool) {
+        self.members.retain(|m| f(m));
+        for subgroup in &mut self.subgroups {
+            subgroup.retain_members(f);
+        }
+    }
+
+    pub fn convert<U: RefTrait>(&self, id_map: &HashMap<T, U>) -> Result<EntityGroup<U>, T> {
+        Ok(EntityGroup {
+            name: self.name.clone(),
+            members: self
+                .members
+                .iter()
+                .map(|m| id_map.get(m).copied().ok_or(*m))
+                .collect::<Result<_, _>>()?,
+            subgroups: self
+                .subgroups
+                .iter()
+                .map(|g| g.convert(id_map))
+                .collect::<Result<_, _>>()?,
+            hidden: self.hidden,
+            locked: self.locked,
+        })
+    }
+
+    fn collect_flags(&self, hidden: bool, locked: bool, flags: &mut EntityGroupFlags<T>) {
+        let hidden = hidden || self.hidden;
+        let locked = locked || self.locked;
+        if hidden {
+            flags.hidden.extend(self.members.iter().copied());
+        }
+        if locked {
+            flags.locked.extend(self.members.iter().copied());
+        }
+        for subgroup in &self.subgroups {
+            subgroup.collect_flags(hidden, locked, flags);
+        }
+    }
+}
+
+/// The elements that are hidden or locked by any group that they belong to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityGroupFlags<T: RefTrait> {
+    pub hidden: BTreeSet<T>,
+    pub locked: BTreeSet<T>,
+}
+
+impl<T: RefTrait> Default for EntityGroupFlags<T> {
+    fn default() -> Self {
+        Self {
+            hidden: BTreeSet::new(),
+            locked: BTreeSet::new(),
+        }
+    }
+}
+
+/// All of the logical groups of a site.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
+pub struct EntityGroups<T: RefTrait>(pub Vec<EntityGroup<T>>);
+
+impl<T: RefTrait> Default for EntityGroups<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T: RefTrait> EntityGroups<T> {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Find a group by the indices that lead to it through the tree of
+    /// groups, starting from the top level.
+    pub fn get_mut(&mut self, path: &[usize]) -> Option<&mut EntityGroup<T>> {
+        let (first, rest) = path.split_first()?;
+        let mut group = self.0.get_mut(*first)?;
+        for i in rest {
+            group = group.subgroups.get_mut(*i)?;
+        }
+        Some(group)
+    }
+
+    /// Remove the group at the end of `path`, along with its subgroups.
+    pub fn remove(&mut self, path: &[usize]) -> Option<EntityGroup<T>> {
+        let (last, parent) = path.split_last()?;
+        let siblings = if parent.is_empty() {
+            &mut self.0
+        } else {
+            &mut self.get_mut(parent)?.subgroups
+        };
+        (*last < siblings.len()).then(|| siblings.remove(*last))
+    }
+
+    /// Which elements are hidden or locked. A group that is hidden or locked
+    /// applies that to all of its subgroups as well.
+    pub fn flags(&self) -> EntityGroupFlags<T> {
+        let mut flags = EntityGroupFlags::default();
+        for group in &self.0 {
+            group.collect_flags(false, false, &mut flags);
+        }
+        flags
+    }
+
+    pub fn retain_members(&mut self, f: impl Fn(&T) -> bool) {
+        for group in &mut self.0 {
+            group.retain_members(&f);
+        }
+    }
+
+    pub fn convert<U: RefTrait>(&self, id_map: &HashMap<T, U>) -> Result<EntityGroups<U>, T> {
+        Ok(EntityGroups(
+            self.0
+                .iter()
+                .map(|g| g.convert(id_map))
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subgroups_inherit_flags() {
+        let mut wing = EntityGroup::<u32>::new("Pharmacy wing".to_owned());
+        wing.members.insert(1);
+        wing.locked = true;
+        let mut storage = EntityGroup::new("Storage".to_owned());
+        storage.members.insert(2);
+        storage.hidden = true;
+        wing.subgroups.push(storage);
+        let mut lobby = EntityGroup::new("Lobby".to_owned());
+        lobby.members.insert(3);
+        let groups = EntityGroups(vec![wing, lobby]);
+
+        let flags = groups.flags();
+        assert_eq!(flags.locked, BTreeSet::from([1, 2]));
+        assert_eq!(flags.hidden, BTreeSet::from([2]));
+        assert_eq!(groups.0[0].all_members(), BTreeSet::from([1, 2]));
+    }
+}
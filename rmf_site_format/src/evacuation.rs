@@ -0,0 +1,158 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+#[cfg(feature = "bevy")]
+use bevy::prelude::{Component, Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The name of the nav graph that holds the evacuation routes of a site when
+/// it gets exported.
+pub const EVACUATION_GRAPH_NAME: &str = "evacuation";
+
+/// Marks a door as an emergency exit of the building.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
+pub struct EmergencyExit(pub bool);
+
+/// Marks a lane as part of an evacuation route. Both robot lanes and human
+/// lanes can be evacuation routes, and being one does not change how the
+/// lane is used by robots.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
+pub struct EvacuationRoute(pub bool);
+
+impl Site {
+    /// Make a copy of this site whose only nav graph is made of its
+    /// evacuation routes. The assembly points are the only locations that
+    /// are kept, so the legacy nav graph exporter can be used on the copy to
+    /// produce an evacuation nav graph.
+    pub fn evacuation_site(&self) -> Site {
+        let mut site = self.clone();
+        let guided = &mut site.navigation.guided;
+        // The evacuation graph replaces every nav graph, so it takes over the
+        // ID of one of them, which cannot collide with any other element.
+        let graph_id = match guided.graphs.keys().next() {
+            Some(id) => *id,
+            None => guided
+                .lanes
+                .keys()
+                .chain(guided.human_lanes.keys())
+                .chain(guided.locations.keys())
+                .max()
+                .map(|id| id + 1)
+                .unwrap_or(0),
+        };
+        guided.graphs = BTreeMap::from_iter([(
+            graph_id,
+            NavGraph {
+                name: NameInSite(EVACUATION_GRAPH_NAME.to_owned()),
+                color: DisplayColor([0.1, 0.8, 0.2, 1.0]),
+                marker: NavGraphMarker,
+            },
+        )]);
+        guided.ranking = vec![graph_id];
+
+        let human_lanes = std::mem::take(&mut guided.human_lanes);
+        guided.lanes = std::mem::take(&mut guided.lanes)
+            .into_iter()
+            .chain(human_lanes)
+            .filter(|(_, lane)| lane.evacuation.0)
+            .map(|(id, mut lane)| {
+                lane.graphs = AssociatedGraphs::All;
+                (id, lane)
+            })
+            .collect();
+        guided.locations.retain(|_, location| {
+            location.graphs = AssociatedGraphs::All;
+            location.tags.0.iter().any(|t| t.is_assembly_point())
+        });
+        site
+    }
+
+    /// Names of the emergency exits of a level.
+    pub fn emergency_exits(&self, level_id: u32) -> Vec<&str> {
+        let Some(level) = self.levels.get(&level_id) else {
+            return Vec::new();
+        };
+        level
+            .doors
+            .values()
+            .filter(|door| door.emergency_exit.0)
+            .map(|door| door.name.0.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evacuation_site_keeps_only_routes() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let mut site = legacy::building_map::BuildingMap::from_bytes(&data)
+            .unwrap()
+            .to_site()
+            .unwrap();
+        let route: Vec<u32> = site
+            .navigation
+            .guided
+            .lanes
+            .keys()
+            .take(3)
+            .copied()
+            .collect();
+        for id in &route {
+            site.navigation.guided.lanes.get_mut(id).unwrap().evacuation = EvacuationRoute(true);
+        }
+        let assembly_anchor = site.navigation.guided.lanes[&route[0]].anchors.start();
+        site.navigation.guided.locations.insert(
+            u32::MAX,
+            Location {
+                anchor: Point(assembly_anchor),
+                tags: LocationTags(vec![LocationTag::AssemblyPoint]),
+                name: NameInSite("assembly".to_owned()),
+                graphs: AssociatedGraphs::Only(Default::default()),
+            },
+        );
+
+        let evacuation = site.evacuation_site();
+        assert_eq!(evacuation.navigation.guided.graphs.len(), 1);
+        assert_eq!(
+            evacuation.navigation.guided.graphs.keys().next(),
+            site.navigation.guided.graphs.keys().next(),
+        );
+        assert_eq!(
+            evacuation
+                .navigation
+                .guided
+                .lanes
+                .keys()
+                .collect::<Vec<_>>(),
+            route.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(evacuation.navigation.guided.locations.len(), 1);
+
+        let graphs = legacy::nav_graph::NavGraph::from_site(&evacuation);
+        assert_eq!(graphs.len(), 1);
+        assert_eq!(graphs[0].0, EVACUATION_GRAPH_NAME);
+    }
+}
@@ -0,0 +1,232 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{Angle, Pose, Rotation};
+#[cfg(feature = "bevy")]
+use bevy::prelude::Component;
+use glam::{EulerRot, Quat, Vec3};
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// Which axis points up in the coordinate system of an export. Sites are
+/// always edited with Z pointing up.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AxisConvention {
+    /// X forward, Y left, Z up. This is what Gazebo and ROS use.
+    #[default]
+    ZUp,
+    /// X right, Y up, Z towards the viewer. Site coordinates `(x, y, z)`
+    /// become `(x, z, -y)`.
+    YUp,
+}
+
+impl AxisConvention {
+    pub const ALL: [AxisConvention; 2] = [AxisConvention::ZUp, AxisConvention::YUp];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ZUp => "Z-up",
+            Self::YUp => "Y-up",
+        }
+    }
+
+    /// The rotation that brings site coordinates into this convention.
+    pub fn rotation(&self) -> Quat {
+        match self {
+            Self::ZUp => Quat::IDENTITY,
+            Self::YUp => Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2),
+        }
+    }
+}
+
+/// How the coordinates of a site get transformed when it is exported. The
+/// origin offset is applied first, then the scale, then the axis convention.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    #[serde(default)]
+    pub axis: AxisConvention,
+    /// How many export units there are per meter, e.g. 100.0 for centimeters.
+    #[serde(default = "ExportOptions::default_scale")]
+    pub scale: f32,
+    /// The point of the site, in meters, that becomes the origin of the
+    /// exported coordinates.
+    #[serde(default)]
+    pub origin: [f32; 3],
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            axis: AxisConvention::default(),
+            scale: Self::default_scale(),
+            origin: [0.0; 3],
+        }
+    }
+}
+
+impl ExportOptions {
+    fn default_scale() -> f32 {
+        1.0
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The preset for game engines such as Unity and Unreal: Y-up with
+    /// meters as units.
+    pub fn digital_twin() -> Self {
+        Self {
+            axis: AxisConvention::YUp,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_digital_twin_default(&self) -> bool {
+        *self == Self::digital_twin()
+    }
+
+    pub fn rotation(&self) -> Quat {
+        self.axis.rotation()
+    }
+
+    /// Transform a point from site coordinates into export coordinates.
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        self.rotation() * (self.scale * (p - Vec3::from(self.origin)))
+    }
+
+    /// Transform a direction, such as gravity, into export coordinates.
+    /// Directions are not affected by the scale or the origin.
+    pub fn transform_direction(&self, v: Vec3) -> Vec3 {
+        self.rotation() * v
+    }
+
+    /// Transform a pose from site coordinates into export coordinates.
+    /// Poses keep their yaw representation as long as the axis convention
+    /// does not change.
+    pub fn transform_pose(&self, pose: &Pose) -> Pose {
+        let trans = self.transform_point(Vec3::from(pose.trans)).to_array();
+        let rot = match self.axis {
+            AxisConvention::ZUp => pose.rot.clone(),
+            _ => {
+                let q = self.rotation() * pose.rot.as_bevy_quat();
+                let (yaw, pitch, roll) = q.to_euler(EulerRot::ZYX);
+                Rotation::EulerExtrinsicXYZ([Angle::Rad(roll), Angle::Rad(pitch), Angle::Rad(yaw)])
+            }
+        };
+        Pose { trans, rot }
+    }
+}
+
+/// Export options for each of the exporters of a site. Downstream consumers
+/// do not agree on conventions, so every exporter can be configured on its
+/// own.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+pub struct ExportSettings {
+    #[serde(default, skip_serializing_if = "ExportOptions::is_default")]
+    pub sdf: ExportOptions,
+    /// Nav graphs are always two dimensional, so only the scale and the
+    /// horizontal components of the origin are used.
+    #[serde(default, skip_serializing_if = "ExportOptions::is_default")]
+    pub nav_graph: ExportOptions,
+    #[serde(
+        default = "ExportOptions::digital_twin",
+        skip_serializing_if = "ExportOptions::is_digital_twin_default"
+    )]
+    pub digital_twin: ExportOptions,
+    /// The longest straight piece, in meters, that curved lanes are broken
+    /// into when they are exported into nav graphs.
+    #[serde(
+        default = "ExportSettings::default_curve_resolution",
+        skip_serializing_if = "ExportSettings::is_default_curve_resolution"
+    )]
+    pub curve_resolution: f32,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            sdf: ExportOptions::default(),
+            nav_graph: ExportOptions::default(),
+            digital_twin: ExportOptions::digital_twin(),
+            curve_resolution: Self::default_curve_resolution(),
+        }
+    }
+}
+
+impl ExportSettings {
+    fn default_curve_resolution() -> f32 {
+        0.5
+    }
+
+    fn is_default_curve_resolution(resolution: &f32) -> bool {
+        *resolution == Self::default_curve_resolution()
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A record of one export of a site, written next to the exported files so
+/// that downstream tools know how the coordinates were transformed.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ExportManifest {
+    /// Name of the site that was exported
+    pub site: String,
+    pub entries: Vec<ExportManifestEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ExportManifestEntry {
+    /// Name of the exporter, such as `sdf` or `nav_graph`
+    pub exporter: String,
+    pub options: ExportOptions,
+    /// Paths of the files that were produced, relative to the manifest
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+impl ExportManifest {
+    pub fn new(site: String) -> Self {
+        Self {
+            site,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn to_writer_json<W: io::Write>(&self, writer: W) -> serde_json::Result<()> {
+        serde_json::to_writer_pretty(writer, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn y_up_export_swaps_axes() {
+        let options = ExportOptions {
+            axis: AxisConvention::YUp,
+            scale: 100.0,
+            origin: [1.0, 0.0, 0.0],
+        };
+        let p = options.transform_point(Vec3::new(2.0, 3.0, 4.0));
+        assert!((p - Vec3::new(100.0, 400.0, -300.0)).length() < 1e-3);
+    }
+}
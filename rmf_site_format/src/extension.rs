@@ -0,0 +1,118 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::{Component, Deref, DerefMut};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Data of entity types that are not part of the site format but were added
+/// by extensions of the site editor, such as wifi access points. The data is
+/// grouped by the key of each entity type and then by the site ID of each
+/// entity. Files keep this data even when they are opened by an editor that
+/// does not know about the extension.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
+pub struct Extensions(pub BTreeMap<String, BTreeMap<u32, serde_json::Value>>);
+
+impl Extensions {
+    pub fn is_empty(&self) -> bool {
+        self.0.values().all(|entities| entities.is_empty())
+    }
+
+    /// Site IDs of every entity in every extension
+    pub fn site_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.0
+            .values()
+            .flat_map(|entities| entities.keys().copied())
+    }
+
+    pub fn insert<T: Serialize>(
+        &mut self,
+        key: &str,
+        id: u32,
+        value: &T,
+    ) -> serde_json::Result<()> {
+        let value = serde_json::to_value(value)?;
+        self.0.entry(key.to_owned()).or_default().insert(id, value);
+        Ok(())
+    }
+
+    /// Remove all entities of an extension and parse them.
+    pub fn take<T: DeserializeOwned>(&mut self, key: &str) -> Vec<(u32, serde_json::Result<T>)> {
+        self.0
+            .remove(key)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(id, value)| (id, serde_json::from_value(value)))
+            .collect()
+    }
+}
+
+/// Keys of extensions need a namespace, such as `my_company/wifi_access_point`,
+/// so that extensions from different crates do not collide.
+pub fn is_namespaced_extension_key(key: &str) -> bool {
+    match key.split_once('/') {
+        Some((namespace, name)) => {
+            let valid = |s: &str| {
+                !s.is_empty()
+                    && s.chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')
+            };
+            valid(namespace) && valid(name)
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct AccessPoint {
+        ssid: String,
+        channel: u32,
+    }
+
+    #[test]
+    fn extensions_survive_round_trip() {
+        let ap = AccessPoint {
+            ssid: "lobby".to_owned(),
+            channel: 6,
+        };
+        let mut extensions = Extensions::default();
+        extensions.insert("acme/access_point", 12, &ap).unwrap();
+        let text = serde_json::to_string(&extensions).unwrap();
+        let mut parsed: Extensions = serde_json::from_str(&text).unwrap();
+        assert_eq!(parsed.site_ids().collect::<Vec<_>>(), vec![12]);
+
+        let taken = parsed.take::<AccessPoint>("acme/access_point");
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].1.as_ref().unwrap(), &ap);
+        assert!(parsed.is_empty());
+    }
+
+    #[test]
+    fn extension_keys_need_namespace() {
+        assert!(is_namespaced_extension_key("acme/access_point"));
+        assert!(!is_namespaced_extension_key("access_point"));
+        assert!(!is_namespaced_extension_key("/access_point"));
+        assert!(!is_namespaced_extension_key("acme/access point"));
+    }
+}
@@ -0,0 +1,254 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum GeoJsonExportError {
+    #[error("Entity [{0}] referenced a non existing anchor")]
+    BrokenAnchorReference(u32),
+    #[error("The position {0:?} could not be converted into geographic coordinates")]
+    ProjectionFailed([f32; 2]),
+}
+
+impl Site {
+    /// Describe the levels, vertices, walls, and lanes of the site as a
+    /// GeoJSON feature collection. The `project` function converts a point in site
+    /// coordinates into a WGS84 `[longitude, latitude]` pair.
+    pub fn to_geojson(
+        &self,
+        project: impl Fn([f32; 2]) -> Option<[f64; 2]>,
+    ) -> Result<Value, GeoJsonExportError> {
+        let position = |id: u32| -> Result<[f64; 2], GeoJsonExportError> {
+            let p = self
+                .get_anchor(id)
+                .ok_or(GeoJsonExportError::BrokenAnchorReference(id))?
+                .translation_for_category(Category::General);
+            project(p).ok_or(GeoJsonExportError::ProjectionFailed(p))
+        };
+        let graph_names = |graphs: &AssociatedGraphs<u32>| -> Vec<&str> {
+            self.navigation
+                .guided
+                .graphs
+                .iter()
+                .filter(|(id, _)| graphs.includes(**id))
+                .map(|(_, graph)| graph.name.0.as_str())
+                .collect()
+        };
+
+        let mut features = Vec::new();
+        for level in self.levels.values() {
+            let level_name = &level.properties.name.0;
+            let elevation = level.properties.elevation.0;
+
+            let mut floors = Vec::new();
+            for floor in level.floors.values() {
+                let mut ring = floor
+                    .anchors
+                    .0
+                    .iter()
+                    .map(|id| position(*id))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if let Some(first) = ring.first().copied() {
+                    // GeoJSON rings must end where they start
+                    ring.push(first);
+                    floors.push(vec![ring]);
+                }
+            }
+            if !floors.is_empty() {
+                features.push(feature(
+                    json!({ "type": "MultiPolygon", "coordinates": floors }),
+                    json!({ "kind": "level", "name": level_name, "elevation": elevation }),
+                ));
+            }
+
+            for wall in level.walls.values() {
+                let [a, b] = wall.anchors.array();
+                features.push(feature(
+                    json!({ "type": "LineString", "coordinates": [position(a)?, position(b)?] }),
+                    json!({ "kind": "wall", "level": level_name }),
+                ));
+            }
+
+            for door in level.doors.values() {
+                let [a, b] = door.anchors.array();
+                features.push(feature(
+                    json!({ "type": "LineString", "coordinates": [position(a)?, position(b)?] }),
+                    json!({
+                        "kind": "door",
+                        "level": level_name,
+                        "name": door.name.0,
+                        "door_type": door.kind.label(),
+                    }),
+                ));
+            }
+
+            let guided = &self.navigation.guided;
+            let lanes = guided
+                .lanes
+                .values()
+                .map(|lane| (lane, false))
+                .chain(guided.human_lanes.values().map(|lane| (lane, true)));
+            for (lane, human) in lanes {
+                let [a, b] = lane.anchors.array();
+                if !level.anchors.contains_key(&a) || !level.anchors.contains_key(&b) {
+                    continue;
+                }
+                features.push(feature(
+                    json!({ "type": "LineString", "coordinates": [position(a)?, position(b)?] }),
+                    json!({
+                        "kind": if human { "human_lane" } else { "lane" },
+                        "level": level_name,
+                        "graphs": graph_names(&lane.graphs),
+                        "bidirectional": !matches!(lane.reverse, ReverseLane::Disable),
+                        "speed_limit": lane.forward.speed_limit,
+                    }),
+                ));
+            }
+
+            for location in guided.locations.values() {
+                if !level.anchors.contains_key(&location.anchor.0) {
+                    continue;
+                }
+                let tags: Vec<_> = location.tags.0.iter().map(|t| t.label()).collect();
+                features.push(feature(
+                    json!({ "type": "Point", "coordinates": position(location.anchor.0)? }),
+                    json!({
+                        "kind": "location",
+                        "level": level_name,
+                        "name": location.name.0,
+                        "tags": tags,
+                    }),
+                ));
+            }
+
+            for id in level.anchors.keys() {
+                features.push(feature(
+                    json!({ "type": "Point", "coordinates": position(*id)? }),
+                    json!({ "kind": "vertex", "level": level_name, "id": id }),
+                ));
+            }
+        }
+
+        Ok(json!({
+            "type": "FeatureCollection",
+            "name": self.properties.name.0,
+            "features": features,
+        }))
+    }
+}
+
+/// A point that was read from a GeoJSON file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoJsonPoint {
+    /// WGS84 `[longitude, latitude]` of the point
+    pub lon_lat: [f64; 2],
+    /// The `level` property of the feature, if it has one
+    pub level: Option<String>,
+    /// The `name` property of the feature, if it has one
+    pub name: Option<String>,
+}
+
+/// Get every point of a GeoJSON feature collection or feature. Points come
+/// from `Point` and `MultiPoint` geometries, while every other kind of
+/// geometry is ignored.
+pub fn geojson_points(value: &Value) -> Vec<GeoJsonPoint> {
+    let features = match value["type"].as_str() {
+        Some("FeatureCollection") => value["features"]
+            .as_array()
+            .map(|f| f.iter().collect())
+            .unwrap_or_default(),
+        Some("Feature") => vec![value],
+        _ => Vec::new(),
+    };
+
+    let lon_lat = |c: &Value| -> Option<[f64; 2]> { Some([c[0].as_f64()?, c[1].as_f64()?]) };
+    let mut points = Vec::new();
+    for feature in features {
+        let property = |key: &str| feature["properties"][key].as_str().map(ToOwned::to_owned);
+        let geometry = &feature["geometry"];
+        let coordinates = match geometry["type"].as_str() {
+            Some("Point") => vec![&geometry["coordinates"]],
+            Some("MultiPoint") => geometry["coordinates"]
+                .as_array()
+                .map(|c| c.iter().collect())
+                .unwrap_or_default(),
+            _ => continue,
+        };
+        for c in coordinates {
+            let Some(lon_lat) = lon_lat(c) else {
+                continue;
+            };
+            points.push(GeoJsonPoint {
+                lon_lat,
+                level: property("level"),
+                name: property("name"),
+            });
+        }
+    }
+    points
+}
+
+fn feature(geometry: Value, properties: Value) -> Value {
+    json!({
+        "type": "Feature",
+        "geometry": geometry,
+        "properties": properties,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geojson_has_walls_and_lanes() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let site = legacy::building_map::BuildingMap::from_bytes(&data)
+            .unwrap()
+            .to_site()
+            .unwrap();
+        let project = |p: [f32; 2]| Some([p[0] as f64 * 1e-5, p[1] as f64 * 1e-5]);
+        let geojson = site.to_geojson(project).unwrap();
+        assert_eq!(geojson["type"], "FeatureCollection");
+
+        let features = geojson["features"].as_array().unwrap();
+        let count = |kind: &str| {
+            features
+                .iter()
+                .filter(|f| f["properties"]["kind"] == kind)
+                .count()
+        };
+        let walls: usize = site.levels.values().map(|l| l.walls.len()).sum();
+        assert_eq!(count("wall"), walls);
+        assert!(count("lane") > 0);
+        let vertices: usize = site.levels.values().map(|l| l.anchors.len()).sum();
+        assert_eq!(count("vertex"), vertices);
+        assert_eq!(geojson_points(&geojson).len(), vertices + count("location"));
+        for f in features
+            .iter()
+            .filter(|f| f["properties"]["kind"] == "level")
+        {
+            for ring in f["geometry"]["coordinates"][0].as_array().unwrap() {
+                let ring = ring.as_array().unwrap();
+                assert_eq!(ring.first(), ring.last());
+            }
+        }
+    }
+}
@@ -36,3 +36,153 @@ impl GeographicOffset {
         }
     }
 }
+
+/// A point of the site whose geographic coordinates are known, such as a
+/// surveyed corner of the building.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct GeographicReference {
+    /// Position of the point in site coordinates, in meters
+    pub position: [f32; 2],
+    /// Latitude of the point in WGS84 degrees
+    pub latitude: f64,
+    /// Longitude of the point in WGS84 degrees
+    pub longitude: f64,
+}
+
+/// Points of the site whose geographic coordinates are known. One point
+/// anchors the site without changing its orientation, while two or more
+/// points also determine the rotation and scale of the site relative to the
+/// projected map.
+#[cfg_attr(feature = "bevy", derive(Component))]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct GeographicReferences(pub Vec<GeographicReference>);
+
+impl GeographicReferences {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// A rotation, uniform scale, and translation in the plane.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Similarity2 {
+    /// The cosine and sine of the rotation, multiplied by the scale
+    pub rotation: [f64; 2],
+    pub translation: [f64; 2],
+}
+
+impl Default for Similarity2 {
+    fn default() -> Self {
+        Self::from_translation([0.0, 0.0])
+    }
+}
+
+impl Similarity2 {
+    pub fn from_translation(translation: [f64; 2]) -> Self {
+        Self {
+            rotation: [1.0, 0.0],
+            translation,
+        }
+    }
+
+    /// Find the similarity that best maps each `from` point onto its `to`
+    /// point in the least squares sense. This needs at least two distinct
+    /// `from` points.
+    pub fn fit(pairs: &[([f64; 2], [f64; 2])]) -> Option<Self> {
+        if pairs.len() < 2 {
+            return None;
+        }
+        let n = pairs.len() as f64;
+        let mean = |f: fn(&([f64; 2], [f64; 2])) -> [f64; 2]| {
+            let sum = pairs
+                .iter()
+                .map(f)
+                .fold([0.0, 0.0], |s, p| [s[0] + p[0], s[1] + p[1]]);
+            [sum[0] / n, sum[1] / n]
+        };
+        let from_mean = mean(|(from, _)| *from);
+        let to_mean = mean(|(_, to)| *to);
+
+        // Treat points as complex numbers and solve for the multiplier s in
+        // (to - to_mean) = s * (from - from_mean)
+        let (mut re, mut im, mut norm) = (0.0, 0.0, 0.0);
+        for (from, to) in pairs {
+            let z = [from[0] - from_mean[0], from[1] - from_mean[1]];
+            let w = [to[0] - to_mean[0], to[1] - to_mean[1]];
+            re += w[0] * z[0] + w[1] * z[1];
+            im += w[1] * z[0] - w[0] * z[1];
+            norm += z[0] * z[0] + z[1] * z[1];
+        }
+        if norm < 1e-12 {
+            return None;
+        }
+        let rotation = [re / norm, im / norm];
+        let rotated = [
+            rotation[0] * from_mean[0] - rotation[1] * from_mean[1],
+            rotation[1] * from_mean[0] + rotation[0] * from_mean[1],
+        ];
+        Some(Self {
+            rotation,
+            translation: [to_mean[0] - rotated[0], to_mean[1] - rotated[1]],
+        })
+    }
+
+    pub fn apply(&self, p: [f64; 2]) -> [f64; 2] {
+        let [c, s] = self.rotation;
+        [
+            c * p[0] - s * p[1] + self.translation[0],
+            s * p[0] + c * p[1] + self.translation[1],
+        ]
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.rotation[0].hypot(self.rotation[1])
+    }
+
+    /// The similarity that undoes this one, if it does not collapse points.
+    pub fn inverse(&self) -> Option<Self> {
+        let [c, s] = self.rotation;
+        let norm = c * c + s * s;
+        if norm < 1e-24 {
+            return None;
+        }
+        let rotation = [c / norm, -s / norm];
+        let [tx, ty] = self.translation;
+        Some(Self {
+            rotation,
+            translation: [
+                -(rotation[0] * tx - rotation[1] * ty),
+                -(rotation[1] * tx + rotation[0] * ty),
+            ],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn similarity_fit_recovers_transform() {
+        let truth = Similarity2 {
+            // A rotation of 30 degrees with a scale of 2
+            rotation: [2.0 * 0.8660254, 2.0 * 0.5],
+            translation: [500_000.0, 4_000_000.0],
+        };
+        let pairs: Vec<_> = [[0.0, 0.0], [10.0, 0.0], [3.0, 7.0]]
+            .into_iter()
+            .map(|p| (p, truth.apply(p)))
+            .collect();
+        let fit = Similarity2::fit(&pairs).unwrap();
+        for (from, to) in pairs {
+            let p = fit.apply(from);
+            assert!((p[0] - to[0]).abs() < 1e-6 && (p[1] - to[1]).abs() < 1e-6);
+        }
+        assert!((fit.scale() - 2.0).abs() < 1e-6);
+        let inverse = fit.inverse().unwrap();
+        let p = inverse.apply(fit.apply([3.0, 7.0]));
+        assert!((p[0] - 3.0).abs() < 1e-6 && (p[1] - 7.0).abs() < 1e-6);
+        assert!(Similarity2::fit(&[([1.0, 1.0], [0.0, 0.0])]).is_none());
+    }
+}
@@ -34,6 +34,12 @@ pub struct Lane<T: RefTrait> {
     pub reverse: ReverseLane,
     /// What graphs this lane is associated with
     pub graphs: AssociatedGraphs<T>,
+    /// Whether this lane is part of an evacuation route
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub evacuation: EvacuationRoute,
+    /// The shape of the lane between its anchors
+    #[serde(default, skip_serializing_if = "LaneCurve::is_straight")]
+    pub curve: LaneCurve,
     /// Marker that tells bevy the entity is a Lane-type
     #[serde(skip)]
     pub marker: LaneMarker,
@@ -43,6 +49,13 @@ pub struct Lane<T: RefTrait> {
 #[cfg_attr(feature = "bevy", derive(Component))]
 pub struct LaneMarker;
 
+/// Marks a lane that humans walk along in a crowd simulation. Human lanes are
+/// saved in their own section of the site and are never exported as part of
+/// a robot navigation graph.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+pub struct HumanLaneMarker;
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "bevy", derive(Component))]
 pub struct Motion {
@@ -200,6 +213,8 @@ impl<T: RefTrait> Lane<T> {
             forward: self.forward.clone(),
             reverse: self.reverse.clone(),
             graphs: self.graphs.convert(id_map)?,
+            evacuation: self.evacuation,
+            curve: self.curve,
             marker: Default::default(),
         })
     }
@@ -212,6 +227,8 @@ impl<T: RefTrait> From<Edge<T>> for Lane<T> {
             forward: Default::default(),
             reverse: Default::default(),
             graphs: Default::default(),
+            evacuation: Default::default(),
+            curve: Default::default(),
             marker: Default::default(),
         }
     }
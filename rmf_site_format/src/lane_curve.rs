@@ -0,0 +1,265 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+#[cfg(feature = "bevy")]
+use bevy::prelude::Component;
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// How many samples are used to estimate the length of a curved lane.
+const LENGTH_SAMPLES: usize = 32;
+
+/// The shape of a lane between its two anchors. Curved lanes are exported
+/// into nav graphs as chains of straight lanes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+#[serde(rename_all = "snake_case")]
+pub enum LaneCurve {
+    #[default]
+    Straight,
+    /// A cubic Bezier curve. Each control point is an offset, in meters, from
+    /// the anchor at its own end of the lane.
+    Bezier {
+        start_control: [f32; 2],
+        end_control: [f32; 2],
+    },
+    /// A circular arc. The sweep is how far the direction of travel turns
+    /// between the start and the end of the lane. Positive sweeps turn left,
+    /// so the lane bulges out to the right of the line between its anchors.
+    Arc { sweep: Angle },
+}
+
+impl LaneCurve {
+    pub fn is_straight(&self) -> bool {
+        match self {
+            Self::Straight => true,
+            Self::Bezier { .. } => false,
+            Self::Arc { sweep } => Self::arc_is_flat(sweep.radians()),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Straight => "Straight",
+            Self::Bezier { .. } => "Bezier",
+            Self::Arc { .. } => "Arc",
+        }
+    }
+
+    fn arc_is_flat(sweep: f32) -> bool {
+        (sweep / 2.0).sin().abs() < 1e-4
+    }
+
+    /// The point of the curve at `t` in the range [0, 1], for a lane that
+    /// goes from `p0` to `p1`.
+    pub fn point(&self, p0: Vec2, p1: Vec2, t: f32) -> Vec2 {
+        match self {
+            Self::Bezier {
+                start_control,
+                end_control,
+            } => {
+                let c0 = p0 + Vec2::from(*start_control);
+                let c1 = p1 + Vec2::from(*end_control);
+                let s = 1.0 - t;
+                s * s * s * p0 + 3.0 * s * s * t * c0 + 3.0 * s * t * t * c1 + t * t * t * p1
+            }
+            Self::Arc { sweep } if !Self::arc_is_flat(sweep.radians()) => {
+                let sweep = sweep.radians();
+                let chord = p1 - p0;
+                let start_dir = Vec2::from_angle(-sweep / 2.0).rotate(chord.normalize_or_zero());
+                // The radius is negative for arcs that turn right, which puts
+                // the center on the right side of the lane.
+                let radius = chord.length() / (2.0 * (sweep / 2.0).sin());
+                let center = p0 + radius * start_dir.perp();
+                center - radius * Vec2::from_angle(sweep * t).rotate(start_dir.perp())
+            }
+            _ => p0.lerp(p1, t),
+        }
+    }
+
+    /// The direction of travel along the curve at `t` in the range [0, 1].
+    pub fn tangent(&self, p0: Vec2, p1: Vec2, t: f32) -> Vec2 {
+        let dir = match self {
+            Self::Bezier {
+                start_control,
+                end_control,
+            } => {
+                let c0 = p0 + Vec2::from(*start_control);
+                let c1 = p1 + Vec2::from(*end_control);
+                let s = 1.0 - t;
+                3.0 * s * s * (c0 - p0) + 6.0 * s * t * (c1 - c0) + 3.0 * t * t * (p1 - c1)
+            }
+            Self::Arc { sweep } if !Self::arc_is_flat(sweep.radians()) => {
+                let sweep = sweep.radians();
+                Vec2::from_angle(sweep * (t - 0.5)).rotate(p1 - p0)
+            }
+            _ => p1 - p0,
+        };
+        dir.try_normalize()
+            .unwrap_or_else(|| (p1 - p0).normalize_or_zero())
+    }
+
+    /// Evenly spaced points of the curve, including both ends.
+    pub fn polyline(&self, p0: Vec2, p1: Vec2, segments: usize) -> Vec<Vec2> {
+        let segments = segments.max(1);
+        (0..=segments)
+            .map(|i| self.point(p0, p1, i as f32 / segments as f32))
+            .collect()
+    }
+
+    /// An estimate of the length of the curve.
+    pub fn length(&self, p0: Vec2, p1: Vec2) -> f32 {
+        if self.is_straight() {
+            return p0.distance(p1);
+        }
+        self.polyline(p0, p1, LENGTH_SAMPLES)
+            .windows(2)
+            .map(|w| w[0].distance(w[1]))
+            .sum()
+    }
+
+    /// The parameter of the point of the curve that is closest to `p`.
+    pub fn closest_t(&self, p0: Vec2, p1: Vec2, p: Vec2) -> f32 {
+        if self.is_straight() {
+            let length_squared = p0.distance_squared(p1);
+            if length_squared < f32::EPSILON {
+                return 0.0;
+            }
+            return ((p - p0).dot(p1 - p0) / length_squared).clamp(0.0, 1.0);
+        }
+        let samples = 4 * LENGTH_SAMPLES;
+        (0..=samples)
+            .map(|i| i as f32 / samples as f32)
+            .min_by(|a, b| {
+                let da = self.point(p0, p1, *a).distance_squared(p);
+                let db = self.point(p0, p1, *b).distance_squared(p);
+                da.total_cmp(&db)
+            })
+            .unwrap_or(0.5)
+    }
+
+    /// Split the curve at `t` into the curves of the two lanes that meet at
+    /// the point of the curve at `t`. Both halves follow exactly the same
+    /// path as the original curve.
+    pub fn split(&self, p0: Vec2, p1: Vec2, t: f32) -> (LaneCurve, Vec2, LaneCurve) {
+        let mid = self.point(p0, p1, t);
+        match self {
+            Self::Straight => (Self::Straight, mid, Self::Straight),
+            Self::Arc { sweep } => {
+                let sweep = sweep.radians();
+                (
+                    Self::Arc {
+                        sweep: Angle::Rad(sweep * t),
+                    },
+                    mid,
+                    Self::Arc {
+                        sweep: Angle::Rad(sweep * (1.0 - t)),
+                    },
+                )
+            }
+            Self::Bezier {
+                start_control,
+                end_control,
+            } => {
+                // De Casteljau's algorithm
+                let c0 = p0 + Vec2::from(*start_control);
+                let c1 = p1 + Vec2::from(*end_control);
+                let q0 = p0.lerp(c0, t);
+                let q1 = c0.lerp(c1, t);
+                let q2 = c1.lerp(p1, t);
+                let r0 = q0.lerp(q1, t);
+                let r1 = q1.lerp(q2, t);
+                (
+                    Self::Bezier {
+                        start_control: (q0 - p0).to_array(),
+                        end_control: (r0 - mid).to_array(),
+                    },
+                    mid,
+                    Self::Bezier {
+                        start_control: (r1 - mid).to_array(),
+                        end_control: (q2 - p1).to_array(),
+                    },
+                )
+            }
+        }
+    }
+
+    /// The points between the ends of a curved lane where waypoints should
+    /// be placed so that no straight piece is longer than `resolution`.
+    /// Straight lanes have no waypoints.
+    pub fn waypoints(&self, p0: Vec2, p1: Vec2, resolution: f32) -> Vec<Vec2> {
+        if self.is_straight() || resolution <= 0.0 {
+            return Vec::new();
+        }
+        let segments = (self.length(p0, p1) / resolution).ceil().max(1.0) as usize;
+        let mut points = self.polyline(p0, p1, segments);
+        points.pop();
+        points.remove(0);
+        points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn half_circle_arc_turns_left() {
+        let curve = LaneCurve::Arc {
+            sweep: Angle::Deg(180.0),
+        };
+        let (p0, p1) = (Vec2::ZERO, Vec2::new(2.0, 0.0));
+        let mid = curve.point(p0, p1, 0.5);
+        assert!((mid - Vec2::new(1.0, -1.0)).length() < 1e-4);
+        assert!((curve.point(p0, p1, 1.0) - p1).length() < 1e-4);
+        assert!((curve.tangent(p0, p1, 0.0) - Vec2::new(0.0, -1.0)).length() < 1e-4);
+        assert!((curve.length(p0, p1) - std::f32::consts::PI).abs() < 0.01);
+    }
+
+    #[test]
+    fn waypoints_respect_the_resolution() {
+        let curve = LaneCurve::Bezier {
+            start_control: [1.0, 1.0],
+            end_control: [-1.0, 1.0],
+        };
+        let (p0, p1) = (Vec2::ZERO, Vec2::new(4.0, 0.0));
+        let waypoints = curve.waypoints(p0, p1, 0.5);
+        assert!(!waypoints.is_empty());
+        let mut chain = vec![p0];
+        chain.extend(waypoints);
+        chain.push(p1);
+        assert!(chain.windows(2).all(|w| w[0].distance(w[1]) <= 0.5 + 1e-3));
+        assert!(LaneCurve::Straight.waypoints(p0, p1, 0.5).is_empty());
+    }
+
+    #[test]
+    fn split_halves_follow_the_original_curve() {
+        let curve = LaneCurve::Bezier {
+            start_control: [1.0, 2.0],
+            end_control: [0.5, 1.0],
+        };
+        let (p0, p1) = (Vec2::ZERO, Vec2::new(3.0, 0.0));
+        let (first, mid, second) = curve.split(p0, p1, 0.3);
+        for s in [0.0, 0.25, 0.5, 1.0] {
+            let a = first.point(p0, mid, s);
+            assert!((a - curve.point(p0, p1, 0.3 * s)).length() < 1e-4);
+            let b = second.point(mid, p1, s);
+            assert!((b - curve.point(p0, p1, 0.3 + 0.7 * s)).length() < 1e-4);
+        }
+    }
+}
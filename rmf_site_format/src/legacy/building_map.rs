@@ -1,29 +1,86 @@
 use super::{
-    floor::FloorParameters, level::Level, lift::Lift, wall::WallProperties, PortingError, Result,
+    crowd_sim::{deserialize_crowd_sim, CrowdSim},
+    floor::FloorParameters,
+    level::Level,
+    lift::Lift,
+    wall::WallProperties,
+    PortingError, Result,
 };
 use crate::{
     alignment::align_legacy_building, legacy::model::Model, AddedInstance, Affiliation, Anchor,
     Angle, AssetSource, AssociatedGraphs, Category, DisplayColor, Dock as SiteDock,
     Drawing as SiteDrawing, DrawingProperties, Fiducial as SiteFiducial, FiducialGroup,
-    FiducialMarker, Guided, InstanceModifier, Lane as SiteLane, LaneMarker, Level as SiteLevel,
-    LevelElevation, LevelProperties as SiteLevelProperties, ModelDescriptionBundle, ModelInstance,
-    Motion, NameInSite, NameOfSite, NavGraph, Navigation, OrientationConstraint, Parented,
-    PixelsPerMeter, Pose, PreferredSemiTransparency, RankingsInLevel, ReverseLane, Robot, Rotation,
-    Scenario, Site, SiteProperties, Tasks, Texture as SiteTexture, TextureGroup, UserCameraPose,
-    DEFAULT_NAV_GRAPH_COLORS,
+    FiducialMarker, FlattenedOffset, Guided, InstanceModifier, Lane as SiteLane, LaneMarker,
+    LayerVisibility, Level as SiteLevel, LevelElevation, LevelProperties as SiteLevelProperties,
+    ModelDescriptionBundle, ModelInstance, Motion, NameInSite, NameOfSite, NavGraph, Navigation,
+    OrientationConstraint, Parented, PixelsPerMeter, Pose, PreferredSemiTransparency,
+    RankingsInLevel, ReverseLane, Robot, Rotation, Scenario, Site, SiteProperties, Tasks,
+    Texture as SiteTexture, TextureGroup, UserCameraPose, DEFAULT_NAV_GRAPH_COLORS,
 };
 use glam::{DAffine2, DMat3, DQuat, DVec2, DVec3, EulerRot};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
 
-#[derive(Deserialize, Serialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum CoordinateSystem {
     ReferenceImage,
     CartesianMeters,
 }
 
+impl CoordinateSystem {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ReferenceImage => "Pixels",
+            Self::CartesianMeters => "Meters",
+        }
+    }
+}
+
+/// Levels that are wider than this many units are assumed to be measured in
+/// pixels when there is nothing better to go on.
+const MAX_EXPECTED_EXTENT_IN_METERS: f64 = 300.0;
+
+/// A guess of which coordinate system a legacy building map uses. Old files
+/// often leave out their coordinate system, and getting it wrong produces a
+/// map that is thousands of meters wide or only a few centimeters wide.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoordinateSystemGuess {
+    /// The coordinate system that the file states, if any
+    pub declared: Option<CoordinateSystem>,
+    /// The coordinate system that the contents of the file suggest
+    pub detected: CoordinateSystem,
+    /// The largest width or height of any level, in the units of the file
+    pub extent: f64,
+    /// Average number of meters per file unit according to the measurements
+    /// of the file, if it has any
+    pub measured_scale: Option<f64>,
+}
+
+impl CoordinateSystemGuess {
+    /// True when the user should confirm the coordinate system because the
+    /// file does not state one or contradicts its own contents.
+    pub fn needs_confirmation(&self) -> bool {
+        self.declared != Some(self.detected)
+    }
+
+    /// Explain to a user why the coordinate system was detected.
+    pub fn reason(&self) -> String {
+        match self.measured_scale {
+            Some(scale) => format!(
+                "The measurements of the file have {scale:.3} meters per unit and the widest \
+                level spans {:.1} units.",
+                self.extent,
+            ),
+            None => format!(
+                "The widest level spans {:.1} units and the file has no measurements.",
+                self.extent,
+            ),
+        }
+    }
+}
+
 impl Default for CoordinateSystem {
     fn default() -> Self {
         CoordinateSystem::ReferenceImage
@@ -38,10 +95,8 @@ pub struct BuildingMap {
     #[serde(default)]
     pub reference_level_name: Option<String>,
     pub levels: BTreeMap<String, Level>,
-    // TODO(MXG): Consider parsing legacy crowdsim data and converting it to
-    // a format that will have future support.
-    // #[serde(default)]
-    // pub crowd_sim: CrowdSim,
+    #[serde(default, deserialize_with = "deserialize_crowd_sim")]
+    pub crowd_sim: CrowdSim,
     #[serde(default)]
     pub lifts: BTreeMap<String, Lift>,
 }
@@ -49,10 +104,79 @@ pub struct BuildingMap {
 impl BuildingMap {
     pub fn from_bytes(data: &[u8]) -> serde_yaml::Result<BuildingMap> {
         let map: BuildingMap = serde_yaml::from_slice(data)?;
+        Ok(Self::from_coordinate_system(map))
+    }
+
+    /// Parse a building map while overriding the coordinate system that the
+    /// file states.
+    pub fn from_bytes_as(
+        data: &[u8],
+        coordinate_system: CoordinateSystem,
+    ) -> serde_yaml::Result<BuildingMap> {
+        let mut map: BuildingMap = serde_yaml::from_slice(data)?;
+        map.coordinate_system = coordinate_system;
+        Ok(Self::from_coordinate_system(map))
+    }
+
+    fn from_coordinate_system(map: BuildingMap) -> BuildingMap {
         match map.coordinate_system {
-            CoordinateSystem::ReferenceImage => Ok(BuildingMap::from_pixel_coordinates(map)),
-            CoordinateSystem::CartesianMeters => Ok(map),
+            CoordinateSystem::ReferenceImage => BuildingMap::from_pixel_coordinates(map),
+            CoordinateSystem::CartesianMeters => map,
+        }
+    }
+
+    /// Guess which coordinate system the file uses from the magnitude of its
+    /// coordinates and from its measurements, which relate a distance in the
+    /// file to a distance in meters.
+    pub fn guess_coordinate_system(data: &[u8]) -> serde_yaml::Result<CoordinateSystemGuess> {
+        let declared = serde_yaml::from_slice::<serde_yaml::Value>(data)?
+            .get("coordinate_system")
+            .cloned()
+            .and_then(|v| serde_yaml::from_value::<CoordinateSystem>(v).ok());
+        let map: BuildingMap = serde_yaml::from_slice(data)?;
+
+        let mut extent: f64 = 0.0;
+        let mut scales = Vec::new();
+        for level in map.levels.values() {
+            let (mut min, mut max) = (DVec2::splat(f64::INFINITY), DVec2::splat(f64::NEG_INFINITY));
+            for v in &level.vertices {
+                min = min.min(v.to_vec());
+                max = max.max(v.to_vec());
+            }
+            if !level.vertices.is_empty() {
+                extent = extent.max((max - min).max_element());
+            }
+
+            for measurement in &level.measurements {
+                let (Some(v0), Some(v1)) = (
+                    level.vertices.get(measurement.0),
+                    level.vertices.get(measurement.1),
+                ) else {
+                    continue;
+                };
+                let length = (v1.to_vec() - v0.to_vec()).length();
+                if length > 0.0 && measurement.2.distance.1 > 0.0 {
+                    scales.push(measurement.2.distance.1 / length);
+                }
+            }
         }
+
+        let measured_scale =
+            (!scales.is_empty()).then(|| scales.iter().sum::<f64>() / scales.len() as f64);
+        let detected = match measured_scale {
+            // Measurements of a metric map agree with the coordinates
+            Some(scale) if (scale - 1.0).abs() < 0.1 => CoordinateSystem::CartesianMeters,
+            Some(_) => CoordinateSystem::ReferenceImage,
+            None if extent > MAX_EXPECTED_EXTENT_IN_METERS => CoordinateSystem::ReferenceImage,
+            None => CoordinateSystem::CartesianMeters,
+        };
+
+        Ok(CoordinateSystemGuess {
+            declared,
+            detected,
+            extent,
+            measured_scale,
+        })
     }
 
     /// Collects all vertices that are used in cartesian features (i.e. floors, walls, doors).
@@ -71,7 +195,7 @@ impl BuildingMap {
             anchors.extend([wall.0, wall.1]);
         }
 
-        for lane in &level.lanes {
+        for lane in level.lanes.iter().chain(&level.human_lanes) {
             anchors.extend([lane.0, lane.1]);
         }
         anchors
@@ -192,6 +316,7 @@ impl BuildingMap {
         let mut levels = BTreeMap::new();
         let mut level_name_to_id = BTreeMap::new();
         let mut lanes = BTreeMap::<u32, SiteLane<u32>>::new();
+        let mut human_lanes = BTreeMap::<u32, SiteLane<u32>>::new();
         let mut locations = BTreeMap::new();
         let mut textures: BTreeMap<u32, SiteTexture> = BTreeMap::new();
         let mut floor_texture_map: HashMap<FloorParameters, u32> = HashMap::new();
@@ -201,7 +326,7 @@ impl BuildingMap {
         let mut building_id_to_nav_graph_id = BTreeMap::new();
 
         let mut fiducial_groups: BTreeMap<u32, FiducialGroup> = BTreeMap::new();
-        let mut cartesian_fiducials: HashMap<u32, Vec<DVec2>> = HashMap::new();
+        let mut cartesian_fiducials: BTreeMap<u32, Vec<DVec2>> = BTreeMap::new();
 
         let mut model_descriptions: BTreeMap<u32, ModelDescriptionBundle> = BTreeMap::new();
         let mut robots: BTreeMap<u32, Robot> = BTreeMap::new();
@@ -213,6 +338,7 @@ impl BuildingMap {
         scenarios.insert(default_scenario_id, Scenario::default());
 
         let mut legacy_robots = Vec::<Model>::new();
+        let mut goal_areas: HashMap<String, Vec<u32>> = HashMap::new();
 
         for (level_name, level) in &self.levels {
             let level_id = site_id.next().unwrap();
@@ -249,6 +375,12 @@ impl BuildingMap {
                 };
 
                 vertex_to_anchor_id.insert(i, anchor_id);
+                if !v.4.human_goal_set_name.is_empty() {
+                    goal_areas
+                        .entry(v.4.human_goal_set_name.1.clone())
+                        .or_default()
+                        .push(anchor_id);
+                }
                 if let Some(location) = v.make_location(anchor_id) {
                     let id = site_id.next().unwrap();
                     if let Some(robot_data) = v.spawn_robot(id.clone()) {
@@ -394,10 +526,13 @@ impl BuildingMap {
                             pose,
                             pixels_per_meter,
                             preferred_semi_transparency: PreferredSemiTransparency::for_drawing(),
+                            rectification: Default::default(),
+                            source_info: Default::default(),
                         },
                         anchors: drawing_anchors,
                         fiducials: drawing_fiducials,
                         measurements,
+                        visibility: None,
                     },
                 );
                 rankings.drawings.push(primary_drawing_id);
@@ -453,10 +588,13 @@ impl BuildingMap {
                             pose,
                             pixels_per_meter: PixelsPerMeter((1.0 / layer.transform.scale) as f32),
                             preferred_semi_transparency: PreferredSemiTransparency::for_drawing(),
+                            rectification: Default::default(),
+                            source_info: Default::default(),
                         },
                         anchors: drawing_anchors,
                         fiducials: drawing_fiducials,
                         measurements: Default::default(),
+                        visibility: (!layer.visible).then_some(LayerVisibility::Hidden),
                     },
                 );
             }
@@ -596,6 +734,7 @@ impl BuildingMap {
                         global_drawing_visibility: Default::default(),
                     },
                     anchors: level_anchors,
+                    custom_entities: Default::default(),
                     doors,
                     drawings,
                     floors,
@@ -604,10 +743,22 @@ impl BuildingMap {
                     walls,
                     rankings,
                     user_camera_poses,
+                    zones: Default::default(),
+                    extensions: Default::default(),
+                    paper_space: Default::default(),
+                    flattened_offset: FlattenedOffset([
+                        level.flattened_x_offset as f32,
+                        level.flattened_y_offset as f32,
+                    ]),
                 },
             );
 
-            for lane in &level.lanes {
+            let all_lanes = level
+                .lanes
+                .iter()
+                .map(|lane| (lane, false))
+                .chain(level.human_lanes.iter().map(|lane| (lane, true)));
+            for (lane, is_human) in all_lanes {
                 let left = *vertex_to_anchor_id
                     .get(&lane.0)
                     .ok_or(PortingError::InvalidVertex(lane.0))?;
@@ -653,19 +804,31 @@ impl BuildingMap {
                     ReverseLane::Same
                 };
 
-                let graph_id = building_id_to_nav_graph_id
-                    .entry(lane.2.graph_idx.1)
-                    .or_insert(site_id.next().unwrap());
+                // Human lanes are not part of any robot nav graph
+                let graphs = if is_human {
+                    AssociatedGraphs::All
+                } else {
+                    let graph_id = building_id_to_nav_graph_id
+                        .entry(lane.2.graph_idx.1)
+                        .or_insert(site_id.next().unwrap());
+                    AssociatedGraphs::Only([*graph_id].into())
+                };
 
                 let site_lane = SiteLane {
                     anchors: [left, right].into(),
                     forward: motion,
                     reverse,
-                    graphs: AssociatedGraphs::Only([*graph_id].into()),
+                    graphs,
+                    evacuation: Default::default(),
+                    curve: Default::default(),
                     marker: LaneMarker,
                 };
 
-                lanes.insert(site_id.next().unwrap(), site_lane);
+                if is_human {
+                    human_lanes.insert(site_id.next().unwrap(), site_lane);
+                } else {
+                    lanes.insert(site_id.next().unwrap(), site_lane);
+                }
             }
         }
 
@@ -759,6 +922,7 @@ impl BuildingMap {
                     graphs: nav_graphs,
                     ranking: Vec::new(),
                     lanes,
+                    human_lanes,
                     locations,
                 },
             },
@@ -768,6 +932,10 @@ impl BuildingMap {
             model_descriptions,
             robots,
             tasks,
+            crowd_sim: self.crowd_sim.to_site(&goal_areas),
+            entity_groups: Default::default(),
+            custom_entity_schemas: Default::default(),
+            tags: Default::default(),
         })
     }
 }
@@ -805,6 +973,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn coordinate_system_is_detected() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let guess = BuildingMap::guess_coordinate_system(&data).unwrap();
+        assert_eq!(guess.declared, None);
+        assert_eq!(guess.detected, CoordinateSystem::ReferenceImage);
+        assert!(guess.needs_confirmation());
+
+        let metric = br#"
+name: metric
+levels:
+  L1:
+    elevation: 0
+    vertices:
+      - [0, 0, 0, ""]
+      - [12, 0, 0, ""]
+      - [12, 8, 0, ""]
+"#;
+        let guess = BuildingMap::guess_coordinate_system(metric).unwrap();
+        assert_eq!(guess.detected, CoordinateSystem::CartesianMeters);
+        assert_eq!(guess.extent, 12.0);
+    }
+
+    #[test]
+    fn dispensers_are_imported_as_location_tags() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let site = BuildingMap::from_bytes(&data).unwrap().to_site().unwrap();
+        let pantry = site
+            .navigation
+            .guided
+            .locations
+            .values()
+            .find(|l| l.name.0 == "pantry")
+            .unwrap();
+        let dispenser = pantry.tags.0.iter().find_map(|t| t.dispenser()).unwrap();
+        assert_eq!(dispenser.name, "coke_dispenser");
+    }
+
+    #[test]
+    fn crowd_sim_conversion() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let site = BuildingMap::from_bytes(&data).unwrap().to_site().unwrap();
+        let crowd_sim = &site.crowd_sim;
+        assert!(!crowd_sim.enable);
+        assert_eq!(crowd_sim.agent_profiles[0].name, "external_agent");
+        assert_eq!(crowd_sim.agent_groups[0].agents_number, 2);
+        assert_eq!(crowd_sim.states[0].goal_set, None);
+        assert_eq!(crowd_sim.obstacle_set.file_name, "L1_navmesh.nav");
+    }
+
     #[test]
     fn site_yaml() {
         let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
@@ -1,7 +1,13 @@
-use serde::{Deserialize, Serialize};
+use crate::{
+    AgentGroup as SiteAgentGroup, AgentProfile as SiteAgentProfile, CrowdModelType,
+    CrowdSim as SiteCrowdSim, CrowdState, GoalSet as SiteGoalSet, ObstacleSet as SiteObstacleSet,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct AgentGroup {
+    #[serde(default)]
     pub agents_name: Vec<String>,
     pub agents_number: usize,
     pub group_id: usize,
@@ -29,13 +35,35 @@ pub struct AgentProfile {
     pub r: f64,
 }
 
-// TODO:y
-#[derive(Deserialize, Serialize, Clone)]
-pub struct GoalSet;
+#[derive(Deserialize, Serialize, Clone, Default)]
+pub struct GoalSet {
+    #[serde(default)]
+    pub capacity: usize,
+    /// Names of the goal areas in this set. Each vertex with a matching
+    /// `human_goal_set_name` belongs to the set.
+    #[serde(default)]
+    pub goal_area: Vec<String>,
+    #[serde(default)]
+    pub set_id: usize,
+}
 
-// TODO:
-#[derive(Deserialize, Serialize, Clone)]
-pub struct ModelType;
+#[derive(Deserialize, Serialize, Clone, Default)]
+pub struct ModelFile {
+    #[serde(default)]
+    pub model_file_path: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+pub struct ModelType {
+    #[serde(default)]
+    pub typename: String,
+    #[serde(default)]
+    pub animation: String,
+    #[serde(default)]
+    pub animation_speed: f64,
+    #[serde(default)]
+    pub ign: ModelFile,
+}
 
 #[derive(Deserialize, Serialize, Clone, Default)]
 pub struct ObstacleSet {
@@ -54,19 +82,135 @@ pub struct State {
     pub navmesh_file_name: String,
 }
 
-// TODO:
-#[derive(Deserialize, Serialize, Clone)]
-pub struct Transition;
-
 #[derive(Deserialize, Serialize, Clone, Default)]
 pub struct CrowdSim {
-    agent_groups: Vec<AgentGroup>,
-    agent_profiles: Vec<AgentProfile>,
-    enable: i8,
-    goal_sets: Vec<GoalSet>,
-    model_types: Vec<ModelType>,
-    obstacle_set: ObstacleSet,
-    states: Vec<State>,
-    transitions: Vec<Transition>,
-    update_time_step: f64,
+    #[serde(default)]
+    pub agent_groups: Vec<AgentGroup>,
+    #[serde(default)]
+    pub agent_profiles: Vec<AgentProfile>,
+    #[serde(default)]
+    pub enable: i8,
+    #[serde(default)]
+    pub goal_sets: Vec<GoalSet>,
+    #[serde(default)]
+    pub model_types: Vec<ModelType>,
+    #[serde(default)]
+    pub obstacle_set: ObstacleSet,
+    #[serde(default)]
+    pub states: Vec<State>,
+    /// The site format cannot describe transitions yet, so these are only
+    /// kept to warn about them being dropped when the building is imported.
+    #[serde(default)]
+    pub transitions: Vec<serde_yaml::Value>,
+    #[serde(default)]
+    pub update_time_step: f64,
+}
+
+/// Crowd simulation data has been edited by many versions of the legacy
+/// traffic editor, so a section that cannot be parsed is skipped instead of
+/// failing to load the whole building.
+pub(super) fn deserialize_crowd_sim<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<CrowdSim, D::Error> {
+    let value = serde_yaml::Value::deserialize(deserializer)?;
+    match serde_yaml::from_value(value) {
+        Ok(crowd_sim) => Ok(crowd_sim),
+        Err(err) => {
+            eprintln!("WARNING: Skipping crowd_sim section that could not be parsed: {err}");
+            Ok(Default::default())
+        }
+    }
+}
+
+impl CrowdSim {
+    /// `goal_areas` maps the name of each goal area to the anchors of the
+    /// vertices that belong to it.
+    pub fn to_site(&self, goal_areas: &HashMap<String, Vec<u32>>) -> SiteCrowdSim<u32> {
+        if !self.transitions.is_empty() {
+            eprintln!(
+                "WARNING: Dropping {} crowd_sim transitions because the site format cannot \
+                describe them yet. Agents that reach a goal will pick a new goal from the same \
+                goal set instead.",
+                self.transitions.len(),
+            );
+        }
+        SiteCrowdSim {
+            enable: self.enable != 0,
+            update_time_step: if self.update_time_step > 0.0 {
+                self.update_time_step as f32
+            } else {
+                SiteCrowdSim::<u32>::default_update_time_step()
+            },
+            agent_profiles: self
+                .agent_profiles
+                .iter()
+                .map(|p| SiteAgentProfile {
+                    name: p.name.clone(),
+                    class: p.class,
+                    max_accel: p.max_accel as f32,
+                    max_angle_vel: p.max_angle_vel as f32,
+                    max_neighbors: p.max_neighbors,
+                    max_speed: p.max_speed as f32,
+                    neighbor_dist: p.neighbor_dist as f32,
+                    obstacle_set: p.obstacle_set,
+                    pref_speed: p.pref_speed as f32,
+                    radius: p.r as f32,
+                    orca_tau: p.orca_tau as f32,
+                    orca_tau_obst: p.orca_tau_obst as f32,
+                })
+                .collect(),
+            agent_groups: self
+                .agent_groups
+                .iter()
+                .map(|g| SiteAgentGroup {
+                    group_id: g.group_id,
+                    profile_selector: g.profile_selector.clone(),
+                    state_selector: g.state_selector.clone(),
+                    agents_number: g.agents_number,
+                    agents_name: g.agents_name.clone(),
+                    spawn_point: [g.x as f32, g.y as f32],
+                })
+                .collect(),
+            goal_sets: self
+                .goal_sets
+                .iter()
+                .map(|g| SiteGoalSet {
+                    set_id: g.set_id,
+                    capacity: g.capacity,
+                    anchors: g
+                        .goal_area
+                        .iter()
+                        .filter_map(|area| goal_areas.get(area))
+                        .flatten()
+                        .copied()
+                        .collect(),
+                })
+                .collect(),
+            states: self
+                .states
+                .iter()
+                .map(|s| CrowdState {
+                    name: s.name.clone(),
+                    is_final: s.final_ != 0,
+                    goal_set: usize::try_from(s.goal_set).ok(),
+                    navmesh_file_name: s.navmesh_file_name.clone(),
+                })
+                .collect(),
+            model_types: self
+                .model_types
+                .iter()
+                .map(|m| CrowdModelType {
+                    typename: m.typename.clone(),
+                    animation: m.animation.clone(),
+                    animation_speed: m.animation_speed as f32,
+                    model_uri: m.ign.model_file_path.clone(),
+                })
+                .collect(),
+            obstacle_set: SiteObstacleSet {
+                class: self.obstacle_set.class,
+                file_name: self.obstacle_set.file_name.clone(),
+                kind: self.obstacle_set.type_.clone(),
+            },
+        }
+    }
 }
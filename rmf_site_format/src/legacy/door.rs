@@ -112,6 +112,8 @@ impl Door {
             anchors: [*left_anchor, *right_anchor].into(),
             name: NameInSite(self.2.name.1.clone()),
             kind,
+            timing: Default::default(),
+            emergency_exit: Default::default(),
             marker: Default::default(),
         })
     }
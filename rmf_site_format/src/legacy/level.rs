@@ -56,6 +56,8 @@ pub struct Level {
     #[serde(default)]
     pub lanes: Vec<Lane>,
     #[serde(default)]
+    pub human_lanes: Vec<Lane>,
+    #[serde(default)]
     pub measurements: Vec<Measurement>,
     #[serde(default)]
     pub models: Vec<Model>,
@@ -80,6 +82,10 @@ pub struct Level {
     pub fiducials: Vec<Fiducial>,
     #[serde(default)]
     pub lights: Vec<Light>,
+    #[serde(default)]
+    pub flattened_x_offset: f64,
+    #[serde(default)]
+    pub flattened_y_offset: f64,
     #[serde(skip)]
     pub alignment: Option<Alignment>,
 }
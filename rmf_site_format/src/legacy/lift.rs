@@ -1,8 +1,8 @@
 use super::{PortingError, Result};
 use crate::{
     Anchor, DoorType, DoubleSlidingDoor, Edge, InitialLevel, IsStatic, LevelVisits,
-    Lift as SiteLift, LiftCabin, LiftCabinDoor, LiftCabinDoorPlacement, LiftProperties, NameInSite,
-    RectFace, RectangularLiftCabin,
+    Lift as SiteLift, LiftCabin, LiftCabinDoor, LiftCabinDoorPlacement, LiftLevelElevations,
+    LiftProperties, NameInSite, RectFace, RectangularLiftCabin,
 };
 use glam::DVec2;
 use serde::{Deserialize, Serialize};
@@ -28,6 +28,9 @@ pub struct Lift {
     pub highest_floor: String,
     pub initial_floor_name: String,
     pub level_doors: BTreeMap<String, Vec<String>>,
+    /// Floor height of each level that the lift travels to, if known
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub level_elevations: BTreeMap<String, f64>,
     pub plugins: bool,
     pub reference_floor_name: String,
     pub width: f64,
@@ -295,6 +298,18 @@ impl Lift {
             cabin_doors
         };
 
+        let level_elevations = LiftLevelElevations(
+            self.level_elevations
+                .iter()
+                .map(|(level_name, elevation)| {
+                    level_name_to_id
+                        .get(level_name)
+                        .map(|level| (*level, *elevation as f32))
+                        .ok_or(PortingError::InvalidLevelName(level_name.clone()))
+                })
+                .collect::<Result<_>>()?,
+        );
+
         let cabin = LiftCabin::Rect(cabin);
         Ok(SiteLift {
             cabin_doors,
@@ -306,6 +321,8 @@ impl Lift {
                 initial_level: InitialLevel(
                     level_name_to_id.get(&self.initial_floor_name).copied(),
                 ),
+                level_elevations,
+                timing: Default::default(),
             },
             cabin_anchors,
         })
@@ -321,6 +338,7 @@ impl Default for Lift {
             highest_floor: "L1".to_string(),
             initial_floor_name: "L1".to_string(),
             level_doors: BTreeMap::new(),
+            level_elevations: BTreeMap::new(),
             plugins: false,
             reference_floor_name: "L1".to_string(),
             width: 1.0,
@@ -28,6 +28,7 @@ pub mod measurement;
 pub mod model;
 pub mod nav_graph;
 pub mod physical_camera;
+pub mod project;
 pub mod rbmf;
 pub mod vertex;
 pub mod wall;
@@ -1,14 +1,43 @@
 use crate::*;
-use glam::Affine2;
+use glam::{Affine2, Vec2};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct NavGraph {
     pub building_name: String,
-    pub levels: HashMap<String, NavLevel>,
-    pub doors: HashMap<String, NavDoor>,
-    pub lifts: HashMap<String, NavLift>,
+    pub levels: BTreeMap<String, NavLevel>,
+    pub doors: BTreeMap<String, NavDoor>,
+    pub lifts: BTreeMap<String, NavLift>,
+}
+
+/// The outlines of the zones of a level. Zones with broken anchor references
+/// are skipped.
+fn zone_polygons(site: &Site, level: &Level) -> Vec<(ZoneKind, Vec<[f32; 2]>)> {
+    let mut zones = Vec::new();
+    for zone in level.zones.values() {
+        let polygon: Option<Vec<[f32; 2]>> = zone
+            .anchors
+            .0
+            .iter()
+            .map(|id| {
+                level
+                    .anchors
+                    .get(id)
+                    .or_else(|| site.anchors.get(id))
+                    .map(|a| a.translation_for_category(Category::General))
+            })
+            .collect();
+        let Some(polygon) = polygon else {
+            eprintln!(
+                "ERROR: Skipping zone {} due to broken anchor reference",
+                zone.name.0
+            );
+            continue;
+        };
+        zones.push((zone.kind, polygon));
+    }
+    zones
 }
 
 // Reference: https://en.wikipedia.org/wiki/Line%E2%80%93line_intersection#Given_two_points_on_each_line_segment
@@ -24,12 +53,42 @@ fn segments_intersect(p1: [f32; 2], p2: [f32; 2], p3: [f32; 2], p4: [f32; 2]) ->
     }
     let t = ((x1 - x3) * (y3 - y4) - (y1 - y3) * (x3 - x4)) / det;
     let u = -((x1 - x2) * (y1 - y3) - (y1 - y2) * (x1 - x3)) / det;
-    if u < 0.0 || t < 0.0 || u > 1.0 || t > 1.0 {
+    // Lanes that end in a doorway touch the door at exactly one of their
+    // endpoints, so allow for some rounding error at the ends.
+    const EPSILON: f32 = 1e-4;
+    if u < -EPSILON || t < -EPSILON || u > 1.0 + EPSILON || t > 1.0 + EPSILON {
         return false;
     }
     true
 }
 
+/// Whether any part of the segment `[p0-p1]` is inside of the polygon. A
+/// segment that only touches the outline of the polygon, such as a lane that
+/// ends at the border of a zone, does not count.
+fn segment_enters_polygon(p0: [f32; 2], p1: [f32; 2], polygon: &[[f32; 2]]) -> bool {
+    let p0 = Vec2::from(p0);
+    let p1 = Vec2::from(p1);
+    if point_in_polygon(((p0 + p1) / 2.0).to_array(), polygon) {
+        return true;
+    }
+    // Otherwise the segment can only be inside of the polygon by crossing
+    // its outline somewhere strictly between their ends.
+    const EPSILON: f32 = 1e-4;
+    let d = p1 - p0;
+    (0..polygon.len()).any(|i| {
+        let q0 = Vec2::from(polygon[i]);
+        let q1 = Vec2::from(polygon[(i + 1) % polygon.len()]);
+        let e = q1 - q0;
+        let det = d.perp_dot(e);
+        if det.abs() < 1e-6 {
+            return false;
+        }
+        let t = (q0 - p0).perp_dot(e) / det;
+        let u = (q0 - p0).perp_dot(d) / det;
+        EPSILON < t && t < 1.0 - EPSILON && EPSILON < u && u < 1.0 - EPSILON
+    })
+}
+
 impl NavGraph {
     pub fn from_site(site: &Site) -> Vec<(String, Self)> {
         let mut graphs = Vec::new();
@@ -60,13 +119,16 @@ impl NavGraph {
                 lanes_with_anchor
             };
 
-            let mut doors = HashMap::new();
-            let mut levels = HashMap::new();
-            let mut lifts = HashMap::new();
+            // Use ordered containers so that exporting the same site twice
+            // produces identical files.
+            let mut doors = BTreeMap::new();
+            let mut levels = BTreeMap::new();
+            let mut lifts = BTreeMap::new();
             for (_, level) in &site.levels {
                 let mut anchor_to_vertex = HashMap::new();
                 let mut vertices = Vec::new();
-                let mut lanes_to_include = HashSet::new();
+                let mut lanes_to_include = BTreeSet::new();
+                let zones = zone_polygons(site, level);
                 // Add vertices for anchors that are in lifts
                 for lift in site.lifts.values() {
                     let lift_name = &lift.properties.name.0;
@@ -133,10 +195,17 @@ impl NavGraph {
                     }
 
                     anchor_to_vertex.insert(*id, vertices.len());
-                    vertices.push(NavVertex::from_anchor(anchor, location_at_anchor.get(id)));
+                    let mut vertex = NavVertex::from_anchor(anchor, location_at_anchor.get(id));
+                    if zones.iter().any(|(kind, polygon)| {
+                        *kind == ZoneKind::ParkingArea
+                            && point_in_polygon([vertex.0, vertex.1], polygon)
+                    }) {
+                        vertex.2.is_parking_spot = true;
+                    }
+                    vertices.push(vertex);
                 }
 
-                let mut level_doors = HashMap::new();
+                let mut level_doors = BTreeMap::new();
                 for (_, door) in &level.doors {
                     let door_name = &door.name.0;
                     let (v0, v1) = match (
@@ -177,30 +246,80 @@ impl NavGraph {
                         }
                     };
 
-                    let mut door_name = None;
-                    let l0 = [vertices[v0].0, vertices[v0].1];
-                    let l1 = [vertices[v1].0, vertices[v1].1];
-                    for (name, door) in &level_doors {
-                        if segments_intersect(l0, l1, door.endpoints[0], door.endpoints[1]) {
-                            door_name = Some(name);
+                    // Curved lanes become a chain of straight lanes that pass
+                    // through extra waypoints along the curve.
+                    let l0 = Vec2::new(vertices[v0].0, vertices[v0].1);
+                    let l1 = Vec2::new(vertices[v1].0, vertices[v1].1);
+                    let resolution = site.properties.export_settings.curve_resolution;
+                    let waypoints = lane.curve.waypoints(l0, l1, resolution);
+                    let points: Vec<[f32; 2]> = std::iter::once(l0)
+                        .chain(waypoints.iter().copied())
+                        .chain(std::iter::once(l1))
+                        .map(|p| p.to_array())
+                        .collect();
+
+                    let mut zone_speed_limit: Option<f32> = None;
+                    let mut excluded = false;
+                    for (kind, polygon) in &zones {
+                        let enters = points
+                            .windows(2)
+                            .any(|pair| segment_enters_polygon(pair[0], pair[1], polygon));
+                        if !enters {
+                            continue;
                         }
+                        if kind.excludes_robots() {
+                            excluded = true;
+                        } else if let ZoneKind::SpeedLimited { max_speed } = kind {
+                            zone_speed_limit =
+                                Some(zone_speed_limit.map_or(*max_speed, |s| s.min(*max_speed)));
+                        }
+                    }
+                    if excluded {
+                        eprintln!("WARNING: Lane {lane_id} passes through a zone that robots may not enter, the lane will be skipped.");
+                        continue;
                     }
 
-                    let props = NavLaneProperties::from_motion(&lane.forward, door_name.cloned());
-                    lanes.push(NavLane(v0, v1, props.clone()));
-                    match &lane.reverse {
-                        ReverseLane::Same => {
-                            lanes.push(NavLane(v1, v0, props));
-                        }
-                        ReverseLane::Different(motion) => {
-                            lanes.push(NavLane(
-                                v1,
-                                v0,
-                                NavLaneProperties::from_motion(motion, door_name.cloned()),
-                            ));
+                    let mut chain = vec![v0];
+                    for p in waypoints {
+                        chain.push(vertices.len());
+                        vertices.push(NavVertex(p.x, p.y, NavVertexProperties::default()));
+                    }
+                    chain.push(v1);
+
+                    let segments = chain.len() - 1;
+                    for (i, pair) in chain.windows(2).enumerate() {
+                        let (a, b) = (pair[0], pair[1]);
+                        let p0 = [vertices[a].0, vertices[a].1];
+                        let p1 = [vertices[b].0, vertices[b].1];
+                        let mut door_name = None;
+                        for (name, door) in &level_doors {
+                            if segments_intersect(p0, p1, door.endpoints[0], door.endpoints[1]) {
+                                door_name = Some(name);
+                            }
                         }
-                        ReverseLane::Disable => {
-                            // Do nothing
+
+                        // Only the piece at the end of a motion keeps its dock
+                        let motion_for = |motion: &Motion, keep_dock: bool| {
+                            let mut motion = motion.clone();
+                            if !keep_dock {
+                                motion.dock = None;
+                            }
+                            NavLaneProperties::from_motion(&motion, door_name.cloned())
+                                .with_zone_speed_limit(zone_speed_limit)
+                        };
+
+                        let props = motion_for(&lane.forward, i + 1 == segments);
+                        lanes.push(NavLane(a, b, props.clone()));
+                        match &lane.reverse {
+                            ReverseLane::Same => {
+                                lanes.push(NavLane(b, a, props));
+                            }
+                            ReverseLane::Different(motion) => {
+                                lanes.push(NavLane(b, a, motion_for(motion, i == 0)));
+                            }
+                            ReverseLane::Disable => {
+                                // Do nothing
+                            }
                         }
                     }
                 }
@@ -212,19 +331,49 @@ impl NavGraph {
                 );
             }
 
-            graphs.push((
-                graph.name.0.clone(),
-                Self {
-                    building_name: site.properties.name.clone().0,
-                    levels,
-                    doors,
-                    lifts,
-                },
-            ))
+            let mut nav_graph = Self {
+                building_name: site.properties.name.clone().0,
+                levels,
+                doors,
+                lifts,
+            };
+            nav_graph.apply_export_options(&site.properties.export_settings.nav_graph);
+            graphs.push((graph.name.0.clone(), nav_graph))
         }
 
         graphs
     }
+
+    /// Scale and offset the coordinates of the graph. Nav graphs are two
+    /// dimensional, so the axis convention and the vertical component of the
+    /// origin are ignored.
+    pub fn apply_export_options(&mut self, options: &ExportOptions) {
+        if options.is_default() {
+            return;
+        }
+        let s = options.scale;
+        let [ox, oy, _] = options.origin;
+        let transform = |x: f32, y: f32| [s * (x - ox), s * (y - oy)];
+        for level in self.levels.values_mut() {
+            for vertex in &mut level.vertices {
+                [vertex.0, vertex.1] = transform(vertex.0, vertex.1);
+                if let Some(radius) = &mut vertex.2.merge_radius {
+                    *radius *= s;
+                }
+            }
+        }
+        for door in self.doors.values_mut() {
+            for p in &mut door.endpoints {
+                *p = transform(p[0], p[1]);
+            }
+        }
+        for lift in self.lifts.values_mut() {
+            let [x, y] = transform(lift.position[0], lift.position[1]);
+            lift.position[0] = x;
+            lift.position[1] = y;
+            lift.dims = lift.dims.map(|d| s * d);
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -271,6 +420,16 @@ impl NavLaneProperties {
             door_name,
         }
     }
+
+    /// A speed limit of zero means that the lane has no speed limit.
+    fn with_zone_speed_limit(mut self, limit: Option<f32>) -> Self {
+        if let Some(limit) = limit {
+            if self.speed_limit <= 0.0 || limit < self.speed_limit {
+                self.speed_limit = limit;
+            }
+        }
+        self
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -295,6 +454,10 @@ pub struct NavVertexProperties {
     #[serde(skip_serializing_if = "is_false")]
     pub is_parking_spot: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub pickup_dispenser: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dropoff_ingestor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub merge_radius: Option<f32>,
     pub name: String,
 }
@@ -320,6 +483,18 @@ impl NavVertexProperties {
             .iter()
             .find(|t| t.is_parking_spot())
             .is_some();
+        props.pickup_dispenser = location
+            .tags
+            .0
+            .iter()
+            .find_map(|t| t.dispenser())
+            .map(|d| d.name.clone());
+        props.dropoff_ingestor = location
+            .tags
+            .0
+            .iter()
+            .find_map(|t| t.ingestor())
+            .map(|i| i.name.clone());
 
         props
     }
@@ -340,3 +515,38 @@ pub struct NavLift {
     pub position: [f32; 3],
     pub dims: [f32; 2],
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lanes_ending_in_doorway_intersect_door() {
+        let door = ([0.0, -1.0], [0.0, 1.0]);
+        let doorway = [(door.0[0] + door.1[0]) / 2.0, (door.0[1] + door.1[1]) / 2.0];
+        assert!(segments_intersect([-3.0, 0.3], doorway, door.0, door.1));
+        assert!(segments_intersect(doorway, [2.0, -0.7], door.0, door.1));
+        assert!(!segments_intersect(
+            [-3.0, 0.3],
+            [-1.0, 0.0],
+            door.0,
+            door.1
+        ));
+    }
+
+    #[test]
+    fn lanes_cutting_a_zone_corner_enter_the_zone() {
+        let zone = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]];
+        // Both ends and the midpoint of this lane are outside of the zone
+        let (p0, p1) = ([-1.0, 0.5], [5.0, -1.0]);
+        assert!(!point_in_polygon(p0, &zone));
+        assert!(!point_in_polygon(p1, &zone));
+        assert!(!point_in_polygon([2.0, -0.25], &zone));
+        assert!(segment_enters_polygon(p0, p1, &zone));
+
+        assert!(segment_enters_polygon([0.5, 0.5], [1.5, 1.5], &zone));
+        assert!(!segment_enters_polygon([-1.0, -1.0], [3.0, -1.0], &zone));
+        // Lanes that end at the border of the zone stay outside of it
+        assert!(!segment_enters_polygon([-1.0, 1.0], [0.0, 1.0], &zone));
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::path::{Path, PathBuf};
+
+/// The `.project.yaml` file of traffic-editor. It refers to the building file
+/// that holds the actual map, and the drawings of the building are relative
+/// to the folder of the building file.
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
+pub struct Project {
+    #[serde(default)]
+    pub name: String,
+    pub building: ProjectBuilding,
+}
+
+#[derive(Deserialize, Serialize, Clone, Default, Debug)]
+pub struct ProjectBuilding {
+    /// Path of the building file, relative to the project file
+    pub filename: String,
+}
+
+impl Project {
+    pub fn from_bytes(data: &[u8]) -> serde_yaml::Result<Project> {
+        serde_yaml::from_slice(data)
+    }
+
+    /// Where the building file of this project can be found when the project
+    /// file is inside of `project_dir`.
+    pub fn building_path(&self, project_dir: &Path) -> PathBuf {
+        project_dir.join(&self.building.filename)
+    }
+
+    /// The name of the site file that an imported project should be saved
+    /// as, inside the folder of the project file.
+    pub fn site_file_name(&self) -> String {
+        let stem = if self.name.is_empty() {
+            Path::new(&self.building.filename)
+                .file_name()
+                .and_then(|f| f.to_str())
+                .and_then(|f| f.split('.').next())
+                .unwrap_or("project")
+                .to_owned()
+        } else {
+            self.name.clone()
+        };
+        format!("{stem}.site.ron")
+    }
+
+    /// Rewrite the data of the building file of this project so that the
+    /// drawings and layers of every level are relative to the folder of the
+    /// project file instead of the folder of the building file. The name of
+    /// the project is used when the building has no name. The result can be
+    /// parsed as a [`BuildingMap`](super::building_map::BuildingMap).
+    pub fn rebase_building(&self, building: &[u8]) -> serde_yaml::Result<Vec<u8>> {
+        let mut map: Value = serde_yaml::from_slice(building)?;
+        let base = Path::new(&self.building.filename)
+            .parent()
+            .unwrap_or(Path::new(""));
+
+        let rebase = |value: Option<&mut Value>| {
+            let Some(value) = value else {
+                return;
+            };
+            let Some(filename) = value.as_str() else {
+                return;
+            };
+            if filename.is_empty() || Path::new(filename).is_absolute() {
+                return;
+            }
+            *value = Value::String(base.join(filename).to_string_lossy().into_owned());
+        };
+
+        if let Some(levels) = map.get_mut("levels").and_then(Value::as_mapping_mut) {
+            for (_, level) in levels.iter_mut() {
+                rebase(level.get_mut("drawing").and_then(|d| d.get_mut("filename")));
+                if let Some(layers) = level.get_mut("layers").and_then(Value::as_mapping_mut) {
+                    for (_, layer) in layers.iter_mut() {
+                        rebase(layer.get_mut("filename"));
+                    }
+                }
+            }
+        }
+
+        let unnamed = map
+            .get("name")
+            .and_then(Value::as_str)
+            .map_or(true, str::is_empty);
+        if unnamed && !self.name.is_empty() {
+            if let Some(map) = map.as_mapping_mut() {
+                map.insert("name".into(), Value::String(self.name.clone()));
+            }
+        }
+
+        Ok(serde_yaml::to_string(&map)?.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legacy::building_map::BuildingMap;
+
+    #[test]
+    fn project_drawings_are_relative_to_the_project() {
+        let project = Project::from_bytes(
+            b"name: office\nbuilding:\n  filename: maps/office.building.yaml\nscenarios: []\n",
+        )
+        .unwrap();
+        assert_eq!(
+            project.building_path(Path::new("/data")),
+            Path::new("/data/maps/office.building.yaml")
+        );
+        assert_eq!(project.site_file_name(), "office.site.ron");
+
+        let building = br#"
+name: ""
+coordinate_system: cartesian_meters
+levels:
+  L1:
+    drawing:
+      filename: scans/L1.png
+    elevation: 0
+"#;
+        let rebased = project.rebase_building(building).unwrap();
+        let map = BuildingMap::from_bytes(&rebased).unwrap();
+        assert_eq!(map.name, "office");
+        assert_eq!(map.levels["L1"].drawing.filename, "maps/scans/L1.png");
+    }
+}
@@ -1,7 +1,7 @@
 use super::rbmf::*;
 use crate::{
-    is_default, legacy::model::Model, AssociatedGraphs, Location, LocationTag, LocationTags,
-    NameInSite,
+    is_default, legacy::model::Model, AssociatedGraphs, DeliveryWorkcell, Location, LocationTag,
+    LocationTags, NameInSite, RobotSpawnPoint,
 };
 use glam::DVec2;
 use serde::{Deserialize, Serialize};
@@ -26,6 +26,8 @@ pub struct VertexProperties {
     pub dock_name: RbmfString,
     #[serde(default, skip_serializing_if = "is_default")]
     pub lift_cabin: RbmfString,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub human_goal_set_name: RbmfString,
 }
 
 #[derive(Deserialize, Serialize, Clone, Default)]
@@ -57,6 +59,26 @@ impl Vertex {
             tags.push(LocationTag::HoldingPoint);
         }
 
+        if !me.pickup_dispenser.is_empty() {
+            tags.push(LocationTag::Dispenser(DeliveryWorkcell::new(
+                me.pickup_dispenser.1.clone(),
+            )));
+        }
+
+        if !me.spawn_robot_name.is_empty() && !me.spawn_robot_type.is_empty() {
+            tags.push(LocationTag::SpawnPoint(RobotSpawnPoint {
+                fleet: String::new(),
+                robot_name: me.spawn_robot_name.1.clone(),
+                robot_type: me.spawn_robot_type.1.clone(),
+            }));
+        }
+
+        if !me.dropoff_ingestor.is_empty() {
+            tags.push(LocationTag::Ingestor(DeliveryWorkcell::new(
+                me.dropoff_ingestor.1.clone(),
+            )));
+        }
+
         let name = if self.3.is_empty() {
             None
         } else {
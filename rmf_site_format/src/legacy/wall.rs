@@ -90,6 +90,8 @@ impl Wall {
         Ok(SiteWall {
             anchors: [*left_anchor, *right_anchor].into(),
             texture: Affiliation(Some(texture_site_id)),
+            height: Default::default(),
+            alpha: Default::default(),
             marker: Default::default(),
         })
     }
@@ -18,6 +18,7 @@
 use crate::*;
 #[cfg(feature = "bevy")]
 use bevy::prelude::{Bundle, Component, Deref, DerefMut};
+use glam::Vec2;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -48,11 +49,174 @@ impl Default for LevelProperties {
 #[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
 pub struct LevelElevation(pub f32);
 
+/// Where a level gets placed, in meters, when all the levels of a site are
+/// laid out side by side on the ground plane, as in the flattened worlds of
+/// legacy building maps.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
+pub struct FlattenedOffset(pub [f32; 2]);
+
+impl FlattenedOffset {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Lay levels out side by side along the x axis, in order of elevation, so
+/// that their bounding boxes do not overlap. Each level is described by its
+/// elevation and the `[min, max]` corners of its bounding box, which is
+/// `None` for a level without any anchors. The lowest level always gets an
+/// offset of zero and each level after it is placed `margin` meters after
+/// the previous one.
+pub fn compute_flattened_offsets<K>(
+    levels: impl IntoIterator<Item = (K, f32, Option<[Vec2; 2]>)>,
+    margin: f32,
+) -> Vec<(K, FlattenedOffset)> {
+    let mut levels: Vec<_> = levels.into_iter().collect();
+    levels.sort_by(|(_, a, _), (_, b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut right_edge: Option<f32> = None;
+    levels
+        .into_iter()
+        .map(|(key, _, bounds)| {
+            let x = match (bounds, right_edge) {
+                (Some([min, max]), Some(edge)) => {
+                    let x = edge + margin - min.x;
+                    right_edge = Some(max.x + x);
+                    x
+                }
+                (Some([_, max]), None) => {
+                    right_edge = Some(max.x);
+                    0.0
+                }
+                (None, edge) => edge.map(|edge| edge + margin).unwrap_or(0.0),
+            };
+            (key, FlattenedOffset([x, 0.0]))
+        })
+        .collect()
+}
+
+/// Elevations that are closer together than this, in meters, are considered
+/// to be the same.
+pub const LEVEL_ELEVATION_TOLERANCE: f32 = 0.01;
+
+/// Something that looks wrong about the elevations of a site's levels.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElevationWarning<K> {
+    /// The lifts that reach a level do not agree about its floor height.
+    LiftsDisagree {
+        level: K,
+        heights: Vec<(String, f32)>,
+    },
+    /// The elevation of a level is different from the floor height that the
+    /// lift data gives for it.
+    DiffersFromLifts {
+        level: K,
+        elevation: f32,
+        derived: f32,
+    },
+    /// Several levels have the same elevation, which usually means that
+    /// their elevations were never filled in.
+    SharedElevation { levels: Vec<K>, elevation: f32 },
+}
+
+/// The elevations that the lift data implies for each level, along with any
+/// inconsistencies that were found along the way.
+#[derive(Debug, Clone)]
+pub struct DerivedElevations<K> {
+    pub elevations: BTreeMap<K, f32>,
+    pub warnings: Vec<ElevationWarning<K>>,
+}
+
+impl<K> DerivedElevations<K> {
+    /// The levels whose elevation would change if the derived elevations
+    /// were applied, along with their new elevations.
+    pub fn changes(&self, current: impl IntoIterator<Item = (K, f32)>) -> Vec<(K, f32)>
+    where
+        K: Ord,
+    {
+        current
+            .into_iter()
+            .filter_map(|(level, elevation)| {
+                let derived = *self.elevations.get(&level)?;
+                ((derived - elevation).abs() > LEVEL_ELEVATION_TOLERANCE)
+                    .then_some((level, derived))
+            })
+            .collect()
+    }
+}
+
+/// Derive the elevation of each level from the floor heights that lifts give
+/// for the levels they travel to. When several lifts reach the same level,
+/// the median of their heights is used. Levels that no lift reaches are left
+/// out of the derived elevations, but their current elevation is still
+/// checked against the other levels.
+pub fn derive_level_elevations<'a, K: Ord + Copy + 'a>(
+    levels: impl IntoIterator<Item = (K, f32)>,
+    lifts: impl IntoIterator<Item = (&'a str, &'a BTreeMap<K, f32>)>,
+) -> DerivedElevations<K> {
+    let mut heights: BTreeMap<K, Vec<(String, f32)>> = BTreeMap::new();
+    for (lift, floors) in lifts {
+        for (level, height) in floors {
+            heights
+                .entry(*level)
+                .or_default()
+                .push((lift.to_owned(), *height));
+        }
+    }
+
+    let mut warnings = Vec::new();
+    let mut elevations = BTreeMap::new();
+    for (level, mut heights) in heights {
+        heights.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let spread = heights[heights.len() - 1].1 - heights[0].1;
+        elevations.insert(level, heights[(heights.len() - 1) / 2].1);
+        if spread > LEVEL_ELEVATION_TOLERANCE {
+            warnings.push(ElevationWarning::LiftsDisagree { level, heights });
+        }
+    }
+
+    let mut levels: Vec<_> = levels.into_iter().collect();
+    for (level, elevation) in &levels {
+        if let Some(derived) = elevations.get(level) {
+            if (derived - elevation).abs() > LEVEL_ELEVATION_TOLERANCE {
+                warnings.push(ElevationWarning::DiffersFromLifts {
+                    level: *level,
+                    elevation: *elevation,
+                    derived: *derived,
+                });
+            }
+        }
+    }
+
+    levels.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mut groups: Vec<(f32, Vec<K>)> = Vec::new();
+    for (level, elevation) in levels {
+        match groups.last_mut() {
+            Some((e, group)) if elevation - *e <= LEVEL_ELEVATION_TOLERANCE => group.push(level),
+            _ => groups.push((elevation, vec![level])),
+        }
+    }
+    for (elevation, levels) in groups {
+        if levels.len() > 1 {
+            warnings.push(ElevationWarning::SharedElevation { levels, elevation });
+        }
+    }
+
+    DerivedElevations {
+        elevations,
+        warnings,
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Level {
     pub properties: LevelProperties,
     pub anchors: BTreeMap<u32, Anchor>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom_entities: BTreeMap<u32, CustomEntity>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub doors: BTreeMap<u32, Door<u32>>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub drawings: BTreeMap<u32, Drawing>,
@@ -68,6 +232,14 @@ pub struct Level {
     pub rankings: RankingsInLevel,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub user_camera_poses: BTreeMap<u32, UserCameraPose>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub zones: BTreeMap<u32, Zone<u32>>,
+    #[serde(default, skip_serializing_if = "PaperSpace::is_default")]
+    pub paper_space: PaperSpace,
+    #[serde(default, skip_serializing_if = "FlattenedOffset::is_default")]
+    pub flattened_offset: FlattenedOffset,
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
 }
 
 impl Level {
@@ -76,6 +248,7 @@ impl Level {
             properties,
             rankings,
             anchors: Default::default(),
+            custom_entities: Default::default(),
             doors: Default::default(),
             drawings: Default::default(),
             floors: Default::default(),
@@ -83,6 +256,10 @@ impl Level {
             physical_cameras: Default::default(),
             walls: Default::default(),
             user_camera_poses: Default::default(),
+            extensions: Default::default(),
+            zones: Default::default(),
+            paper_space: Default::default(),
+            flattened_offset: Default::default(),
         }
     }
 }
@@ -100,3 +277,47 @@ impl RankingsInLevel {
         self.floors.is_empty() && self.drawings.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flattened_offsets_do_not_overlap() {
+        let offsets = compute_flattened_offsets(
+            [
+                ("L2", 4.0, Some([Vec2::new(-5.0, 0.0), Vec2::new(5.0, 8.0)])),
+                ("L1", 0.0, Some([Vec2::new(0.0, 0.0), Vec2::new(10.0, 8.0)])),
+                ("L3", 8.0, None),
+            ],
+            2.0,
+        );
+        assert_eq!(offsets[0], ("L1", FlattenedOffset([0.0, 0.0])));
+        // L2 starts 2m after the right edge of L1
+        assert_eq!(offsets[1], ("L2", FlattenedOffset([17.0, 0.0])));
+        assert_eq!(offsets[2], ("L3", FlattenedOffset([24.0, 0.0])));
+    }
+
+    #[test]
+    fn elevations_are_derived_from_lifts() {
+        let lift_a = BTreeMap::from([("L1", 0.0), ("L2", 4.0), ("L3", 8.0)]);
+        let lift_b = BTreeMap::from([("L2", 4.5)]);
+        let derived = derive_level_elevations(
+            [("L1", 0.0), ("L2", 0.0), ("L3", 0.0)],
+            [("lift_a", &lift_a), ("lift_b", &lift_b)],
+        );
+        assert_eq!(derived.elevations.get("L3"), Some(&8.0));
+        // The median of two heights is the lower one
+        assert_eq!(derived.elevations.get("L2"), Some(&4.0));
+        assert!(derived
+            .warnings
+            .iter()
+            .any(|w| matches!(w, ElevationWarning::LiftsDisagree { level: "L2", .. })));
+        assert!(derived.warnings.iter().any(|w| matches!(
+            w,
+            ElevationWarning::SharedElevation { levels, .. } if levels.len() == 3
+        )));
+        let changes = derived.changes([("L1", 0.0), ("L2", 0.0), ("L3", 0.0)]);
+        assert_eq!(changes, vec![("L2", 4.0), ("L3", 8.0)]);
+    }
+}
@@ -0,0 +1,289 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+use std::{collections::BTreeMap, ops::Range};
+
+/// The text of each level of a site file, keyed by the site ID of the level,
+/// exactly as it appears inside of the file.
+pub type LevelTexts = BTreeMap<u32, String>;
+
+impl Site {
+    /// Same as [`Site::to_string_ron`], except that the levels in `reuse` are
+    /// written with the text that they had in an earlier save instead of
+    /// being serialized again. Only reuse the text of levels that did not
+    /// change since it was produced.
+    ///
+    /// The text of every level of the result is returned as well, so that it
+    /// can be reused by the next save.
+    pub fn to_string_ron_reusing_levels(
+        &mut self,
+        reuse: &LevelTexts,
+    ) -> ron::Result<(String, LevelTexts)> {
+        self.serialize_reusing_levels(reuse, |site| site.to_string_ron())
+    }
+
+    /// Same as [`Site::to_string_ron_reusing_levels`] but for json.
+    pub fn to_string_json_reusing_levels(
+        &mut self,
+        reuse: &LevelTexts,
+    ) -> serde_json::Result<(String, LevelTexts)> {
+        self.serialize_reusing_levels(reuse, serde_json::to_string_pretty::<Site>)
+    }
+
+    fn serialize_reusing_levels<E>(
+        &mut self,
+        reuse: &LevelTexts,
+        serialize: impl Fn(&Site) -> Result<String, E>,
+    ) -> Result<(String, LevelTexts), E> {
+        // Reused levels are swapped out for empty ones while serializing, so
+        // that only their place in the file needs to be found afterwards.
+        let mut taken = BTreeMap::new();
+        for id in reuse.keys() {
+            if let Some(level) = self.levels.get_mut(id) {
+                taken.insert(*id, std::mem::take(level));
+            }
+        }
+        let result = serialize(self);
+        let reused: Vec<u32> = taken.keys().copied().collect();
+        for (id, level) in taken {
+            self.levels.insert(id, level);
+        }
+        let text = result?;
+
+        let Some(spans) = level_spans(&text) else {
+            // The layout of the file was not recognized, so nothing can be
+            // reused.
+            let text = if reused.is_empty() {
+                text
+            } else {
+                serialize(self)?
+            };
+            return Ok((text, LevelTexts::new()));
+        };
+
+        let mut output = String::with_capacity(text.len());
+        let mut texts = LevelTexts::new();
+        let mut cursor = 0;
+        for (id, span) in spans {
+            output.push_str(&text[cursor..span.start]);
+            let level_text = match reuse.get(&id).filter(|_| reused.contains(&id)) {
+                Some(cached) => cached.clone(),
+                None => text[span.clone()].to_owned(),
+            };
+            output.push_str(&level_text);
+            texts.insert(id, level_text);
+            cursor = span.end;
+        }
+        output.push_str(&text[cursor..]);
+        Ok((output, texts))
+    }
+}
+
+/// Find where the text of each level is inside of a site file that was
+/// written as ron or json, in the order that they appear.
+fn level_spans(text: &str) -> Option<Vec<(u32, Range<usize>)>> {
+    let bytes = text.as_bytes();
+    let mut i = skip_whitespace(bytes, 0);
+    if !matches!(bytes.get(i), Some(b'(' | b'{')) {
+        return None;
+    }
+    i += 1;
+    loop {
+        i = skip_separators(bytes, i);
+        match bytes.get(i)? {
+            b')' | b'}' => return Some(Vec::new()),
+            _ => {}
+        }
+        let (key, after_key) = read_key(text, i)?;
+        let value = skip_whitespace(bytes, after_key);
+        let end = value_end(bytes, value)?;
+        if key == "levels" {
+            return map_entry_spans(text, value);
+        }
+        i = end;
+    }
+}
+
+/// The spans of the values of a map whose opening brace is at `start`.
+fn map_entry_spans(text: &str, start: usize) -> Option<Vec<(u32, Range<usize>)>> {
+    let bytes = text.as_bytes();
+    if bytes.get(start) != Some(&b'{') {
+        return None;
+    }
+    let mut spans = Vec::new();
+    let mut i = start + 1;
+    loop {
+        i = skip_separators(bytes, i);
+        if *bytes.get(i)? == b'}' {
+            return Some(spans);
+        }
+        let (key, after_key) = read_key(text, i)?;
+        let id: u32 = key.parse().ok()?;
+        let value = skip_whitespace(bytes, after_key);
+        let end = value_end(bytes, value)?;
+        spans.push((id, value..end));
+        i = end;
+    }
+}
+
+/// Read a key that starts at `i` along with the colon after it. Keys are
+/// either bare, like the field names and integer keys of ron, or quoted,
+/// like every key of json.
+fn read_key(text: &str, i: usize) -> Option<(&str, usize)> {
+    let bytes = text.as_bytes();
+    let (key, end) = if bytes[i] == b'"' {
+        let end = quoted_end(bytes, i)?;
+        (&text[i + 1..end - 1], end)
+    } else {
+        let len = bytes[i..]
+            .iter()
+            .take_while(|b| b.is_ascii_alphanumeric() || **b == b'_')
+            .count();
+        if len == 0 {
+            return None;
+        }
+        (&text[i..i + len], i + len)
+    };
+    let colon = skip_whitespace(bytes, end);
+    (bytes.get(colon) == Some(&b':')).then_some((key, colon + 1))
+}
+
+/// Find the end of the value that starts at `start`. The value ends before
+/// the comma or closing bracket that follows it, without any whitespace in
+/// between.
+fn value_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = start;
+    let mut end = start;
+    while let Some(b) = bytes.get(i) {
+        match b {
+            b'"' | b'\'' => {
+                i = quoted_end(bytes, i)?;
+                end = i;
+                continue;
+            }
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                if depth == 0 {
+                    return Some(end);
+                }
+                depth -= 1;
+            }
+            b',' if depth == 0 => return Some(end),
+            _ => {}
+        }
+        i += 1;
+        if !b.is_ascii_whitespace() {
+            end = i;
+        }
+    }
+    None
+}
+
+/// The position just after the closing quote of the string or character
+/// that starts at `start`.
+fn quoted_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while let Some(b) = bytes.get(i) {
+        if *b == b'\\' {
+            i += 2;
+            continue;
+        }
+        if *b == quote {
+            return Some(i + 1);
+        }
+        i += 1;
+    }
+    None
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while bytes.get(i).is_some_and(|b| b.is_ascii_whitespace()) {
+        i += 1;
+    }
+    i
+}
+
+fn skip_separators(bytes: &[u8], mut i: usize) -> usize {
+    while bytes
+        .get(i)
+        .is_some_and(|b| b.is_ascii_whitespace() || *b == b',')
+    {
+        i += 1;
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_level_site() -> (Site, u32, u32) {
+        let mut site = Site::blank_L1("test".to_owned());
+        let first = *site.levels.keys().next().unwrap();
+        let mut level = site.levels[&first].clone();
+        level.properties.name = NameInSite("L2 \"(quoted)\"".to_owned());
+        level.anchors.insert(50, Anchor::Translate2D([1.0, 2.0]));
+        let second = first + 100;
+        site.levels.insert(second, level);
+        (site, first, second)
+    }
+
+    #[test]
+    fn reused_level_text_is_byte_identical() {
+        let (mut site, first, second) = two_level_site();
+        let full = site.to_string_ron().unwrap();
+        let (text, texts) = site
+            .to_string_ron_reusing_levels(&LevelTexts::new())
+            .unwrap();
+        assert_eq!(text, full);
+        assert_eq!(texts.len(), 2);
+
+        // Change one level and reuse the text of the other one
+        site.levels.get_mut(&second).unwrap().properties.name = NameInSite("L3".to_owned());
+        let full = site.to_string_ron().unwrap();
+        let reuse: LevelTexts = texts
+            .clone()
+            .into_iter()
+            .filter(|(id, _)| *id == first)
+            .collect();
+        let (text, new_texts) = site.to_string_ron_reusing_levels(&reuse).unwrap();
+        assert_eq!(text, full);
+        assert_eq!(new_texts[&first], texts[&first]);
+        assert!(Site::from_str_ron(&text).is_ok());
+
+        // Reused text really is written as it is, even if it went stale
+        let stale: LevelTexts = texts.into_iter().filter(|(id, _)| *id == second).collect();
+        let (text, _) = site.to_string_ron_reusing_levels(&stale).unwrap();
+        assert_ne!(text, full);
+        assert!(text.contains("(quoted)"));
+    }
+
+    #[test]
+    fn reused_level_text_is_byte_identical_in_json() {
+        let (mut site, first, _) = two_level_site();
+        let full = serde_json::to_string_pretty(&site).unwrap();
+        let (_, texts) = site
+            .to_string_json_reusing_levels(&LevelTexts::new())
+            .unwrap();
+        let reuse: LevelTexts = texts.into_iter().filter(|(id, _)| *id == first).collect();
+        let (text, _) = site.to_string_json_reusing_levels(&reuse).unwrap();
+        assert_eq!(text, full);
+    }
+}
@@ -29,9 +29,30 @@ pub use asset_source::*;
 pub mod category;
 pub use category::*;
 
+pub mod centerline;
+pub use centerline::*;
+
+pub mod collaboration;
+pub use collaboration::*;
+
 pub mod camera_poses;
 pub use camera_poses::*;
 
+pub mod cross_section;
+pub use cross_section::*;
+
+pub mod crowd_preview;
+pub use crowd_preview::*;
+
+pub mod crowd_sim;
+pub use crowd_sim::*;
+
+pub mod custom_entity;
+pub use custom_entity::*;
+
+pub mod digital_twin;
+pub use digital_twin::*;
+
 pub mod dock;
 pub use dock::*;
 
@@ -44,6 +65,18 @@ pub use drawing::*;
 pub mod edge;
 pub use edge::*;
 
+pub mod entity_group;
+pub use entity_group::*;
+
+pub mod evacuation;
+pub use evacuation::*;
+
+pub mod export;
+pub use export::*;
+
+pub mod extension;
+pub use extension::*;
+
 pub mod fiducial;
 pub use fiducial::*;
 
@@ -56,6 +89,9 @@ pub use issue::*;
 pub mod lane;
 pub use lane::*;
 
+pub mod lane_curve;
+pub use lane_curve::*;
+
 pub mod layer;
 pub use layer::*;
 
@@ -65,15 +101,24 @@ pub use location::*;
 pub mod level;
 pub use level::*;
 
+pub mod level_text;
+pub use level_text::*;
+
 pub mod lift;
 pub use lift::*;
 
 pub mod light;
 pub use light::*;
 
+pub mod material_library;
+pub use material_library::*;
+
 pub mod measurement;
 pub use measurement::*;
 
+pub mod menge;
+pub use menge::*;
+
 pub mod misc;
 pub use misc::*;
 
@@ -89,12 +134,27 @@ pub use nav_graph::*;
 pub mod navigation;
 pub use navigation::*;
 
+pub mod new_map;
+pub use new_map::*;
+
+pub mod occupancy_grid;
+pub use occupancy_grid::*;
+
+pub mod paper_space;
+pub use paper_space::*;
+
 pub mod path;
 pub use path::*;
 
+pub mod perturb;
+pub use perturb::*;
+
 pub mod physical_camera;
 pub use physical_camera::*;
 
+pub mod plan;
+pub use plan::*;
+
 pub mod point;
 pub use point::*;
 
@@ -107,6 +167,11 @@ pub use recall::*;
 pub mod scenario;
 pub use scenario::*;
 
+#[cfg(feature = "scripting")]
+pub mod scripting;
+#[cfg(feature = "scripting")]
+pub use scripting::*;
+
 pub mod sdf;
 pub use sdf::*;
 
@@ -116,15 +181,36 @@ pub use semver::*;
 pub mod site;
 pub use site::*;
 
+pub mod stats;
+pub use stats::*;
+
+pub mod subset;
+pub use subset::*;
+
+pub mod tag;
+pub use tag::*;
+
 pub mod task;
 pub use task::*;
 
+pub mod template;
+pub use template::*;
+
 pub mod texture;
 pub use texture::*;
 
+pub mod traffic_preview;
+pub use traffic_preview::*;
+
 pub mod wall;
 pub use wall::*;
 
+pub mod zone;
+pub use zone::*;
+
+pub mod geojson;
+pub use geojson::*;
+
 pub mod georeference;
 pub use georeference::*;
 
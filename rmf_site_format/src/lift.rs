@@ -113,6 +113,33 @@ pub struct LiftProperties<T: RefTrait> {
     /// lift will start on the lowest level.
     #[serde(skip_serializing_if = "is_default")]
     pub initial_level: InitialLevel<T>,
+    /// The floor heights of the levels that this lift travels between, as
+    /// given by the lift data. These are used to derive the elevations of
+    /// levels.
+    #[serde(default, skip_serializing_if = "LiftLevelElevations::is_empty")]
+    pub level_elevations: LiftLevelElevations<T>,
+    /// How fast the lift travels and how long its doors take to operate.
+    #[serde(default, skip_serializing_if = "LiftTiming::is_default")]
+    pub timing: LiftTiming,
+}
+
+/// Operational parameters of a lift. Anything that is not set falls back on
+/// the defaults of the lift plugin.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+pub struct LiftTiming {
+    /// The top speed of the cabin, in meters per second
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+    /// Timing of the cabin and shaft doors
+    #[serde(default, skip_serializing_if = "DoorTiming::is_default")]
+    pub doors: DoorTiming,
+}
+
+impl LiftTiming {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 impl LiftProperties<u32> {
@@ -180,6 +207,36 @@ impl<T: RefTrait> Default for InitialLevel<T> {
     }
 }
 
+/// The floor height of each level that a lift travels to, in meters.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
+pub struct LiftLevelElevations<T: RefTrait>(pub BTreeMap<T, f32>);
+
+impl<T: RefTrait> Default for LiftLevelElevations<T> {
+    fn default() -> Self {
+        Self(BTreeMap::new())
+    }
+}
+
+impl<T: RefTrait> LiftLevelElevations<T> {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn convert<U: RefTrait>(
+        &self,
+        id_map: &HashMap<T, U>,
+    ) -> Result<LiftLevelElevations<U>, T> {
+        Ok(LiftLevelElevations(
+            self.0
+                .iter()
+                .map(|(level, elevation)| id_map.get(level).map(|l| (*l, *elevation)).ok_or(*level))
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "bevy", derive(Component))]
 pub enum LiftCabin<T: RefTrait> {
@@ -559,6 +616,8 @@ impl<T: RefTrait> LiftProperties<T> {
                     .map(|id| id_map.get(&id).unwrap())
                     .copied(),
             ),
+            level_elevations: self.level_elevations.convert(id_map)?,
+            timing: self.timing,
         })
     }
 }
@@ -571,6 +630,8 @@ impl<T: RefTrait> From<Edge<T>> for LiftProperties<T> {
             cabin: LiftCabin::default(),
             is_static: Default::default(),
             initial_level: InitialLevel(None),
+            level_elevations: Default::default(),
+            timing: Default::default(),
         }
     }
 }
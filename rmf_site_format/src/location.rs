@@ -26,7 +26,47 @@ pub enum LocationTag {
     Charger,
     ParkingSpot,
     HoldingPoint,
+    /// A place where people gather after evacuating the building
+    AssemblyPoint,
     Workcell(Model),
+    /// A workcell where robots pick up items during delivery tasks
+    Dispenser(DeliveryWorkcell),
+    /// A workcell where robots drop off items during delivery tasks
+    Ingestor(DeliveryWorkcell),
+    /// A robot of a fleet starts out at this location in simulation
+    SpawnPoint(RobotSpawnPoint),
+}
+
+/// A dispenser or ingestor that delivery tasks refer to by name. The workcell
+/// is placed at the location that carries it.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct DeliveryWorkcell {
+    pub name: String,
+    /// What kind of workcell this is, e.g. the name of the simulation plugin
+    /// or device that implements it, such as `TeleportDispenser`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub workcell_type: String,
+}
+
+/// A robot that gets spawned at a location when the site is simulated.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct RobotSpawnPoint {
+    /// Name of the fleet that the robot belongs to
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub fleet: String,
+    /// Name of the robot that gets spawned
+    pub robot_name: String,
+    /// Name of the robot model, e.g. `TinyRobot`
+    pub robot_type: String,
+}
+
+impl DeliveryWorkcell {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            workcell_type: String::new(),
+        }
+    }
 }
 
 impl LocationTag {
@@ -35,7 +75,11 @@ impl LocationTag {
             Self::Charger => "Charger",
             Self::ParkingSpot => "Parking Spot",
             Self::HoldingPoint => "Holding Point",
+            Self::AssemblyPoint => "Assembly Point",
             Self::Workcell(_) => "Workcell",
+            Self::Dispenser(_) => "Dispenser",
+            Self::Ingestor(_) => "Ingestor",
+            Self::SpawnPoint(_) => "Robot Spawn Point",
         }
     }
 
@@ -48,12 +92,33 @@ impl LocationTag {
     pub fn is_holding_point(&self) -> bool {
         matches!(self, Self::HoldingPoint)
     }
+    pub fn is_assembly_point(&self) -> bool {
+        matches!(self, Self::AssemblyPoint)
+    }
     pub fn workcell(&self) -> Option<&Model> {
         match self {
             Self::Workcell(model) => Some(model),
             _ => None,
         }
     }
+    pub fn dispenser(&self) -> Option<&DeliveryWorkcell> {
+        match self {
+            Self::Dispenser(cell) => Some(cell),
+            _ => None,
+        }
+    }
+    pub fn ingestor(&self) -> Option<&DeliveryWorkcell> {
+        match self {
+            Self::Ingestor(cell) => Some(cell),
+            _ => None,
+        }
+    }
+    pub fn spawn_point(&self) -> Option<&RobotSpawnPoint> {
+        match self {
+            Self::SpawnPoint(spawn) => Some(spawn),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -115,8 +180,11 @@ impl RecallLocationTags {
     pub fn assume_tag(&self, current: &LocationTags) -> LocationTag {
         if let Some(tag) = &self.consider_tag {
             match tag {
-                LocationTag::Charger | LocationTag::HoldingPoint | LocationTag::ParkingSpot => {
-                    // If the tag to consider is one of these three values, then
+                LocationTag::Charger
+                | LocationTag::HoldingPoint
+                | LocationTag::ParkingSpot
+                | LocationTag::AssemblyPoint => {
+                    // If the tag to consider is one of these values, then
                     // only accept it if it does not already exist in the current
                     // tag list.
                     if current.0.iter().find(|t| **t == *tag).is_none() {
@@ -0,0 +1,117 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A manifest of materials that can be assigned to floors and walls.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct MaterialLibrary {
+    #[serde(default)]
+    pub materials: Vec<LibraryMaterial>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LibraryMaterial {
+    /// Name of the material. Texture groups that are made from the material
+    /// get this name, which building_map_tools uses as the texture name.
+    pub name: String,
+    /// The texture file, e.g. `rmf-server://OpenRobotics/RMF_Materials/textures/blue_linoleum.png`.
+    /// Paths without a scheme are local files relative to the manifest.
+    pub texture: String,
+    /// How many meters one tile of the texture covers
+    #[serde(default = "LibraryMaterial::default_scale")]
+    pub scale: f32,
+    /// Rotation of the texture in degrees
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub rotation: f32,
+}
+
+impl LibraryMaterial {
+    fn default_scale() -> f32 {
+        1.0
+    }
+
+    /// Where the texture of this material comes from, given the folder of
+    /// the manifest that the material was loaded from.
+    pub fn source(&self, manifest_folder: Option<&Path>) -> AssetSource {
+        if let Ok(source) = AssetSource::try_from(self.texture.as_str()) {
+            return source;
+        }
+        match manifest_folder {
+            Some(folder) if Path::new(&self.texture).is_relative() => {
+                AssetSource::Local(folder.join(&self.texture).to_string_lossy().into_owned())
+            }
+            _ => AssetSource::Local(self.texture.clone()),
+        }
+    }
+
+    pub fn to_texture_group(&self, manifest_folder: Option<&Path>) -> TextureGroup {
+        TextureGroup {
+            name: NameInSite(self.name.clone()),
+            texture: Texture {
+                source: self.source(manifest_folder),
+                alpha: None,
+                rotation: (self.rotation != 0.0).then_some(Angle::Deg(self.rotation)),
+                width: Some(self.scale),
+                height: Some(self.scale),
+            },
+            group: Default::default(),
+        }
+    }
+}
+
+impl MaterialLibrary {
+    pub fn from_bytes_yaml(data: &[u8]) -> serde_yaml::Result<MaterialLibrary> {
+        serde_yaml::from_slice(data)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&LibraryMaterial> {
+        self.materials.iter().find(|m| m.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn library_materials_become_texture_groups() {
+        let library = MaterialLibrary::from_bytes_yaml(
+            br#"
+materials:
+  - name: blue_linoleum
+    texture: rmf-server://OpenRobotics/RMF_Materials/textures/blue_linoleum.png
+    scale: 2.0
+  - name: carpet
+    texture: textures/carpet.png
+"#,
+        )
+        .unwrap();
+
+        let linoleum = library.get("blue_linoleum").unwrap().to_texture_group(None);
+        assert_eq!(linoleum.name.0, "blue_linoleum");
+        assert_eq!(linoleum.texture.width, Some(2.0));
+
+        let carpet = library.get("carpet").unwrap();
+        assert_eq!(carpet.scale, 1.0);
+        assert_eq!(
+            carpet.source(Some(Path::new("/materials"))),
+            AssetSource::Local("/materials/textures/carpet.png".to_owned())
+        );
+    }
+}
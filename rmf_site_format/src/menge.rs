@@ -0,0 +1,543 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::{plan::escape_xml, *};
+use glam::Vec2;
+use sdformat_rs::{ElementData, ElementMap, SdfPlugin, XmlElement};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+
+/// How wide the walkable strip along each human lane is, in meters.
+pub const DEFAULT_HUMAN_LANE_WIDTH: f32 = 1.0;
+/// How close an agent needs to get to its goal before it picks a new one.
+const GOAL_REACHED_DISTANCE: f32 = 0.5;
+
+/// The files of a Menge crowd simulation, as loaded by the crowd simulation
+/// plugin of RMF.
+#[derive(Debug, Clone, Default)]
+pub struct MengeExport {
+    /// Contents of the scene file, which describes the agents
+    pub scene: String,
+    /// Contents of the behavior file, which describes the goals and states
+    pub behavior: String,
+    /// Navigation mesh of each level that has human lanes, keyed by the name
+    /// of its file
+    pub navmeshes: BTreeMap<String, String>,
+}
+
+/// The name of the scene file of a Menge export.
+pub fn menge_scene_file(site_name: &str) -> String {
+    format!("{site_name}.scene.xml")
+}
+
+/// The name of the behavior file of a Menge export.
+pub fn menge_behavior_file(site_name: &str) -> String {
+    format!("{site_name}.behavior.xml")
+}
+
+/// The name of the navigation mesh file of a level, following the naming of
+/// the legacy crowd simulation tools.
+pub fn menge_navmesh_file(level_name: &str) -> String {
+    format!("{level_name}_navmesh.nav")
+}
+
+/// A navigation mesh made of convex polygons, in the ASCII format of Menge.
+#[derive(Debug, Clone, Default)]
+pub struct NavMesh {
+    pub vertices: Vec<Vec2>,
+    /// Each node is a convex polygon of vertex indices in counter-clockwise
+    /// order
+    pub nodes: Vec<Vec<usize>>,
+    /// Edges that agents can walk across, given as `(v0, v1, n0, n1)`
+    pub edges: Vec<[usize; 4]>,
+    /// Edges that agents cannot walk across, given as `(v0, v1, node)`
+    pub obstacles: Vec<[usize; 3]>,
+}
+
+impl NavMesh {
+    /// Build a navigation mesh that covers a strip of `width` along every
+    /// lane. Each lane is given as a pair of indices into `points`. Points
+    /// where several lanes meet get a junction polygon that connects the ends
+    /// of all of their lanes.
+    pub fn from_lanes(points: &[Vec2], lanes: &[(usize, usize)], width: f32) -> Self {
+        let half = width / 2.0;
+        let mut lanes: Vec<(usize, usize)> = lanes
+            .iter()
+            .filter(|(a, b)| {
+                *a < points.len() && *b < points.len() && points[*a].distance(points[*b]) > width
+            })
+            .map(|(a, b)| (*a.min(b), *a.max(b)))
+            .collect();
+        lanes.sort();
+        lanes.dedup();
+
+        // The outward direction of every lane end at each point
+        let mut ends: Vec<Vec<(usize, bool)>> = vec![Vec::new(); points.len()];
+        for (i, (a, b)) in lanes.iter().enumerate() {
+            ends[*a].push((i, true));
+            ends[*b].push((i, false));
+        }
+        let direction = |lane: usize, at_start: bool| -> Vec2 {
+            let (a, b) = lanes[lane];
+            let d = (points[b] - points[a]).normalize();
+            if at_start {
+                d
+            } else {
+                -d
+            }
+        };
+        let angle = |d: Vec2| d.y.atan2(d.x);
+        for point_ends in &mut ends {
+            point_ends
+                .sort_by(|l, r| angle(direction(l.0, l.1)).total_cmp(&angle(direction(r.0, r.1))));
+        }
+
+        // How far the lane ends are pulled back from each point to leave room
+        // for its junction. The corners of the junction all lie on a circle,
+        // which keeps the junction convex as long as the corners of
+        // neighboring lanes do not pass each other.
+        let radius: Vec<f32> = ends
+            .iter()
+            .map(|point_ends| {
+                if point_ends.len() < 2 {
+                    return 0.0;
+                }
+                let mut smallest_gap = std::f32::consts::TAU;
+                for (i, (lane, at_start)) in point_ends.iter().enumerate() {
+                    let (next_lane, next_at_start) = point_ends[(i + 1) % point_ends.len()];
+                    let mut gap = angle(direction(next_lane, next_at_start))
+                        - angle(direction(*lane, *at_start));
+                    if gap <= 0.0 {
+                        gap += std::f32::consts::TAU;
+                    }
+                    smallest_gap = smallest_gap.min(gap);
+                }
+                let shortest = point_ends
+                    .iter()
+                    .map(|(lane, _)| {
+                        let (a, b) = lanes[*lane];
+                        points[a].distance(points[b])
+                    })
+                    .fold(f32::INFINITY, f32::min);
+                (half / (0.4 * smallest_gap).tan())
+                    .max(half)
+                    .min(0.45 * shortest)
+            })
+            .collect();
+
+        let mut mesh = NavMesh::default();
+        // The [right, left] corners of each lane end, as seen when looking
+        // out of the point along the lane
+        let mut corners: HashMap<(usize, bool), [usize; 2]> = HashMap::new();
+        for (i, (a, b)) in lanes.iter().enumerate() {
+            for (p, at_start) in [(*a, true), (*b, false)] {
+                let d = direction(i, at_start);
+                let n = d.perp();
+                let center = points[p] + radius[p] * d;
+                let right = mesh.vertices.len();
+                mesh.vertices.push(center - half * n);
+                mesh.vertices.push(center + half * n);
+                corners.insert((i, at_start), [right, right + 1]);
+            }
+            let [a_right, a_left] = corners[&(i, true)];
+            let [b_right, b_left] = corners[&(i, false)];
+            mesh.nodes.push(vec![a_right, b_left, b_right, a_left]);
+        }
+
+        for point_ends in &ends {
+            if point_ends.len() < 2 {
+                continue;
+            }
+            let node = mesh.nodes.len();
+            let mut polygon = Vec::new();
+            for key in point_ends {
+                let [right, left] = corners[key];
+                polygon.push(right);
+                polygon.push(left);
+                // Lane nodes list their end corners in the opposite order
+                mesh.edges.push([left, right, key.0, node]);
+            }
+            mesh.nodes.push(polygon);
+        }
+
+        let walkable: HashSet<(usize, usize)> = mesh
+            .edges
+            .iter()
+            .flat_map(|[v0, v1, _, _]| [(*v0, *v1), (*v1, *v0)])
+            .collect();
+        for (node, polygon) in mesh.nodes.iter().enumerate() {
+            for (i, v0) in polygon.iter().enumerate() {
+                let v1 = polygon[(i + 1) % polygon.len()];
+                if !walkable.contains(&(*v0, v1)) {
+                    mesh.obstacles.push([*v0, v1, node]);
+                }
+            }
+        }
+        mesh
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Write the mesh in the ASCII navigation mesh format of Menge.
+    pub fn to_menge_ascii(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "{}", self.vertices.len());
+        for v in &self.vertices {
+            let _ = writeln!(out, "\t{} {}", v.x, v.y);
+        }
+        let _ = writeln!(out, "{}", self.edges.len());
+        for [v0, v1, n0, n1] in &self.edges {
+            let _ = writeln!(out, "\t{v0} {v1} {n0} {n1}");
+        }
+        let _ = writeln!(out, "{}", self.obstacles.len());
+        for (i, [v0, v1, node]) in self.obstacles.iter().enumerate() {
+            // Obstacles that continue each other form a chain around the
+            // boundary of the mesh
+            let next = self
+                .obstacles
+                .iter()
+                .position(|o| o[0] == *v1 && o != &self.obstacles[i])
+                .map(|n| n as i64)
+                .unwrap_or(-1);
+            let _ = writeln!(out, "\t{v0} {v1} {node} {next}");
+        }
+        let _ = writeln!(out, "walkable");
+        let _ = writeln!(out, "{}", self.nodes.len());
+        for (n, polygon) in self.nodes.iter().enumerate() {
+            let center = polygon
+                .iter()
+                .fold(Vec2::ZERO, |sum, v| sum + self.vertices[*v])
+                / polygon.len() as f32;
+            let _ = writeln!(out, "\t{} {}", center.x, center.y);
+            let vertices: Vec<String> = polygon.iter().map(|v| v.to_string()).collect();
+            let _ = writeln!(out, "\t{} {}", polygon.len(), vertices.join(" "));
+            // The mesh is flat, so every node lies on the plane z = 0
+            let _ = writeln!(out, "\t0 0 0");
+            let edges: Vec<String> = self
+                .edges
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| e[2] == n || e[3] == n)
+                .map(|(i, _)| i.to_string())
+                .collect();
+            let _ = writeln!(out, "\t{} {}", edges.len(), edges.join(" "));
+            let obstacles: Vec<String> = self
+                .obstacles
+                .iter()
+                .enumerate()
+                .filter(|(_, o)| o[2] == n)
+                .map(|(i, _)| i.to_string())
+                .collect();
+            let _ = writeln!(out, "\t{} {}", obstacles.len(), obstacles.join(" "));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl Site {
+    /// Make the Menge scene, behavior, and navigation mesh files of the crowd
+    /// simulation of this site. The navigation meshes cover the human lanes
+    /// of each level.
+    pub fn to_menge(&self) -> MengeExport {
+        let crowd_sim = &self.crowd_sim;
+        let mut export = MengeExport::default();
+
+        for level in self.levels.values() {
+            let mut points = Vec::new();
+            let mut indices: HashMap<u32, usize> = HashMap::new();
+            let mut lanes = Vec::new();
+            for lane in self.navigation.guided.human_lanes.values() {
+                let [start, end] = lane.anchors.array();
+                let mut index = |anchor: u32| -> Option<usize> {
+                    if let Some(i) = indices.get(&anchor) {
+                        return Some(*i);
+                    }
+                    let p = level
+                        .anchors
+                        .get(&anchor)?
+                        .translation_for_category(Category::Lane);
+                    points.push(Vec2::from(p));
+                    indices.insert(anchor, points.len() - 1);
+                    Some(points.len() - 1)
+                };
+                let (Some(start), Some(end)) = (index(start), index(end)) else {
+                    continue;
+                };
+                lanes.push((start, end));
+            }
+            let mesh = NavMesh::from_lanes(&points, &lanes, DEFAULT_HUMAN_LANE_WIDTH);
+            if !mesh.is_empty() {
+                export.navmeshes.insert(
+                    menge_navmesh_file(&level.properties.name.0),
+                    mesh.to_menge_ascii(),
+                );
+            }
+        }
+
+        let obstacle_file = if crowd_sim.obstacle_set.file_name.is_empty() {
+            export.navmeshes.keys().next().cloned().unwrap_or_default()
+        } else {
+            crowd_sim.obstacle_set.file_name.clone()
+        };
+
+        let mut scene = String::new();
+        scene.push_str("<?xml version=\"1.0\"?>\n");
+        scene.push_str("<Experiment version=\"2.0\">\n");
+        scene.push_str("  <SpatialQuery type=\"kd-tree\" test_visibility=\"false\"/>\n");
+        let _ = writeln!(
+            scene,
+            "  <Common time_step=\"{}\"/>",
+            crowd_sim.update_time_step
+        );
+        for profile in &crowd_sim.agent_profiles {
+            let _ = writeln!(
+                scene,
+                "  <AgentProfile name=\"{}\">",
+                escape_xml(&profile.name)
+            );
+            let _ = writeln!(
+                scene,
+                "    <Common class=\"{}\" max_accel=\"{}\" max_angle_vel=\"{}\" \
+                max_neighbors=\"{}\" max_speed=\"{}\" neighbor_dist=\"{}\" obstacleSet=\"{}\" \
+                pref_speed=\"{}\" r=\"{}\"/>",
+                profile.class,
+                profile.max_accel,
+                profile.max_angle_vel,
+                profile.max_neighbors,
+                profile.max_speed,
+                profile.neighbor_dist,
+                profile.obstacle_set,
+                profile.pref_speed,
+                profile.radius,
+            );
+            let _ = writeln!(
+                scene,
+                "    <ORCA tau=\"{}\" tauObst=\"{}\"/>",
+                profile.orca_tau, profile.orca_tau_obst
+            );
+            scene.push_str("  </AgentProfile>\n");
+        }
+        for group in &crowd_sim.agent_groups {
+            scene.push_str("  <AgentGroup>\n");
+            let _ = writeln!(
+                scene,
+                "    <ProfileSelector type=\"const\" name=\"{}\"/>",
+                escape_xml(&group.profile_selector)
+            );
+            let _ = writeln!(
+                scene,
+                "    <StateSelector type=\"const\" name=\"{}\"/>",
+                escape_xml(&group.state_selector)
+            );
+            scene.push_str("    <Generator type=\"explicit\">\n");
+            let [x, y] = group.spawn_point;
+            for _ in 0..group.agents_number {
+                let _ = writeln!(scene, "      <Agent p_x=\"{x}\" p_y=\"{y}\"/>");
+            }
+            scene.push_str("    </Generator>\n");
+            scene.push_str("  </AgentGroup>\n");
+        }
+        let _ = writeln!(
+            scene,
+            "  <ObstacleSet type=\"{}\" file_name=\"{}\" class=\"{}\"/>",
+            escape_xml(&crowd_sim.obstacle_set.kind),
+            escape_xml(&obstacle_file),
+            crowd_sim.obstacle_set.class
+        );
+        scene.push_str("</Experiment>\n");
+        export.scene = scene;
+
+        let mut behavior = String::new();
+        behavior.push_str("<?xml version=\"1.0\"?>\n");
+        behavior.push_str("<BFSM>\n");
+        for goal_set in &crowd_sim.goal_sets {
+            let _ = writeln!(behavior, "  <GoalSet id=\"{}\">", goal_set.set_id);
+            for (id, anchor) in goal_set.anchors.iter().enumerate() {
+                let Some(anchor) = self.get_anchor(*anchor) else {
+                    continue;
+                };
+                let [x, y] = anchor.translation_for_category(Category::General);
+                let _ = writeln!(
+                    behavior,
+                    "    <Goal type=\"point\" id=\"{id}\" x=\"{x}\" y=\"{y}\" capacity=\"{}\"/>",
+                    goal_set.capacity
+                );
+            }
+            behavior.push_str("  </GoalSet>\n");
+        }
+        for state in &crowd_sim.states {
+            let name = escape_xml(&state.name);
+            let _ = writeln!(
+                behavior,
+                "  <State name=\"{name}\" final=\"{}\">",
+                state.is_final as u8
+            );
+            match state.goal_set {
+                Some(goal_set) => {
+                    let navmesh = if state.navmesh_file_name.is_empty() {
+                        &obstacle_file
+                    } else {
+                        &state.navmesh_file_name
+                    };
+                    let _ = writeln!(
+                        behavior,
+                        "    <GoalSelector type=\"random\" goal_set=\"{goal_set}\"/>"
+                    );
+                    let _ = writeln!(
+                        behavior,
+                        "    <VelComponent type=\"nav_mesh\" heading_threshold=\"15\" \
+                        file_name=\"{}\"/>",
+                        escape_xml(navmesh)
+                    );
+                }
+                None => {
+                    // Agents without goals, such as robots that are driven
+                    // from outside of the crowd simulation, stand still
+                    behavior.push_str("    <GoalSelector type=\"identity\"/>\n");
+                    behavior.push_str("    <VelComponent type=\"zero\"/>\n");
+                }
+            }
+            behavior.push_str("  </State>\n");
+            // The site format does not describe transitions yet, so agents
+            // that reach a goal pick a new one from the same set.
+            if state.goal_set.is_some() && !state.is_final {
+                let _ = writeln!(
+                    behavior,
+                    "  <Transition from=\"{name}\" to=\"{name}\">\n    \
+                    <Condition type=\"goal_reached\" distance=\"{GOAL_REACHED_DISTANCE}\"/>\n  \
+                    </Transition>"
+                );
+            }
+        }
+        behavior.push_str("</BFSM>\n");
+        export.behavior = behavior;
+
+        export
+    }
+
+    /// The world plugin that runs the crowd simulation of the site in Gazebo.
+    /// `resource_path` is the folder that the files of [`Site::to_menge`] get
+    /// written to.
+    pub fn menge_sdf_plugin(&self, resource_path: &str) -> SdfPlugin {
+        let crowd_sim = &self.crowd_sim;
+        let site_name = &self.properties.name.0;
+        let text = |name: &str, value: String| XmlElement {
+            name: name.into(),
+            data: ElementData::String(value),
+            ..Default::default()
+        };
+        let mut plugin = SdfPlugin {
+            name: "crowd_simulation".into(),
+            filename: "libcrowd_simulator.so".into(),
+            ..Default::default()
+        };
+        plugin
+            .elements
+            .push(text("resource_path", resource_path.to_owned()));
+        plugin
+            .elements
+            .push(text("behavior_file", menge_behavior_file(site_name)));
+        plugin
+            .elements
+            .push(text("scene_file", menge_scene_file(site_name)));
+        plugin.elements.push(text(
+            "update_time_step",
+            crowd_sim.update_time_step.to_string(),
+        ));
+        for model_type in &crowd_sim.model_types {
+            let mut data = ElementMap::default();
+            data.push(text("model_uri", model_type.model_uri.clone()));
+            data.push(text("init_pose", "0 0 0 0 0 0".to_owned()));
+            plugin.elements.push(XmlElement {
+                name: "model_type".into(),
+                attributes: [
+                    ("typename".to_owned(), model_type.typename.clone()),
+                    ("animation".to_owned(), model_type.animation.clone()),
+                    (
+                        "animation_speed".to_owned(),
+                        model_type.animation_speed.to_string(),
+                    ),
+                ]
+                .into(),
+                data: ElementData::Nested(data),
+                ..Default::default()
+            });
+        }
+        for group in &crowd_sim.agent_groups {
+            for name in &group.agents_name {
+                plugin.elements.push(text("external_agent", name.clone()));
+            }
+        }
+        plugin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legacy::building_map::BuildingMap;
+
+    fn is_convex(mesh: &NavMesh, polygon: &[usize]) -> bool {
+        (0..polygon.len()).all(|i| {
+            let a = mesh.vertices[polygon[i]];
+            let b = mesh.vertices[polygon[(i + 1) % polygon.len()]];
+            let c = mesh.vertices[polygon[(i + 2) % polygon.len()]];
+            (b - a).perp_dot(c - b) >= -1e-5
+        })
+    }
+
+    #[test]
+    fn junctions_connect_lanes() {
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(-10.0, 0.0),
+            Vec2::new(0.0, 10.0),
+        ];
+        let mesh = NavMesh::from_lanes(&points, &[(0, 1), (0, 2), (3, 0)], 1.0);
+        // Three lanes and one junction where they meet
+        assert_eq!(mesh.nodes.len(), 4);
+        assert_eq!(mesh.edges.len(), 3);
+        for polygon in &mesh.nodes {
+            assert!(is_convex(&mesh, polygon));
+        }
+        // Two sides and a dead end of each lane, and the sides of the
+        // junction between its lanes
+        assert_eq!(mesh.obstacles.len(), 3 * 3 + 3);
+        let ascii = mesh.to_menge_ascii();
+        assert!(ascii.starts_with(&format!("{}\n", mesh.vertices.len())));
+        assert!(ascii.contains("walkable\n4\n"));
+    }
+
+    #[test]
+    fn office_crowd_sim_exports_to_menge() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let site = BuildingMap::from_bytes(&data).unwrap().to_site().unwrap();
+        let menge = site.to_menge();
+        assert!(menge
+            .scene
+            .contains("<AgentProfile name=\"external_agent\">"));
+        assert_eq!(menge.scene.matches("<Agent p_x").count(), 2);
+        assert!(menge.scene.contains("file_name=\"L1_navmesh.nav\""));
+        assert!(menge
+            .behavior
+            .contains("<State name=\"external_static\" final=\"1\">"));
+    }
+}
@@ -461,6 +461,15 @@ impl Default for IsStatic {
     }
 }
 
+/// How far above its pose a model gets placed when it is spawned in a
+/// simulation, in meters. This lets models whose origin is not at their base
+/// rest on the floor without changing the pose that is shown in the editor.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut, Reflect))]
+#[cfg_attr(feature = "bevy", reflect(Component))]
+pub struct ZOffset(pub f32);
+
 /// Marker component for previewable entities
 #[derive(Clone, Copy, Debug, Default)]
 #[cfg_attr(feature = "bevy", derive(Component))]
@@ -33,6 +33,9 @@ pub struct Model {
     #[serde(default, skip_serializing_if = "is_default")]
     /// Whether this model should be able to move in simulation
     pub is_static: IsStatic,
+    /// Height that the model is raised by when it is spawned in simulation
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub z_offset: ZOffset,
     /// Scale to be applied to the model
     #[serde(default, skip_serializing_if = "is_default")]
     pub scale: Scale,
@@ -53,6 +56,7 @@ impl Default for Model {
             source: AssetSource::default(),
             pose: Pose::default(),
             is_static: IsStatic(false),
+            z_offset: ZOffset::default(),
             scale: Scale::default(),
             marker: ModelMarker,
         }
@@ -73,6 +77,8 @@ pub struct ModelDescriptionBundle {
     #[serde(default, skip_serializing_if = "is_default")]
     pub is_static: ModelProperty<IsStatic>,
     #[serde(default, skip_serializing_if = "is_default")]
+    pub z_offset: ModelProperty<ZOffset>,
+    #[serde(default, skip_serializing_if = "is_default")]
     pub scale: ModelProperty<Scale>,
     #[serde(skip)]
     pub group: Group,
@@ -86,6 +92,7 @@ impl Default for ModelDescriptionBundle {
             name: NameInSite("<Unnamed>".to_string()),
             source: ModelProperty(AssetSource::default()),
             is_static: ModelProperty(IsStatic::default()),
+            z_offset: ModelProperty(ZOffset::default()),
             scale: ModelProperty(Scale::default()),
             group: Group,
             marker: ModelMarker,
@@ -41,12 +41,18 @@ pub struct Guided {
     pub ranking: Vec<u32>,
     /// Properties of each robot traffic lane
     pub lanes: BTreeMap<u32, Lane<u32>>,
+    /// Properties of each lane that humans walk along in a crowd simulation
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub human_lanes: BTreeMap<u32, Lane<u32>>,
     /// Properties of each special location
     pub locations: BTreeMap<u32, Location<u32>>,
 }
 
 impl Guided {
     pub fn is_empty(&self) -> bool {
-        self.graphs.is_empty() && self.lanes.is_empty() && self.locations.is_empty()
+        self.graphs.is_empty()
+            && self.lanes.is_empty()
+            && self.human_lanes.is_empty()
+            && self.locations.is_empty()
     }
 }
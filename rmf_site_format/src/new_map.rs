@@ -0,0 +1,125 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+
+/// A request to start a new site from scratch, with a single level and,
+/// optionally, a floorplan image to trace over.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewMap {
+    pub name: String,
+    /// Name of the level that the site starts with
+    pub first_level: String,
+    pub floorplan: Option<NewMapFloorplan>,
+}
+
+/// A floorplan image that becomes the drawing of the first level of a new
+/// map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewMapFloorplan {
+    pub source: AssetSource,
+    pub pixels_per_meter: f32,
+}
+
+impl NewMapFloorplan {
+    /// Calibrate the scale of the floorplan from a measurement, i.e. a
+    /// distance of `pixels` in the image is known to be `meters` long. Returns
+    /// None if the measurement cannot produce a valid scale.
+    pub fn from_calibration(source: AssetSource, pixels: f32, meters: f32) -> Option<Self> {
+        let pixels_per_meter = pixels / meters;
+        (pixels_per_meter.is_finite() && pixels_per_meter > 0.0).then_some(Self {
+            source,
+            pixels_per_meter,
+        })
+    }
+}
+
+impl NewMap {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            first_level: "L1".to_owned(),
+            floorplan: None,
+        }
+    }
+
+    pub fn to_site(&self) -> Site {
+        let mut site = Site::default();
+        site.properties.name = NameOfSite(self.name.clone());
+        const LEVEL_ID: u32 = 1;
+        const DRAWING_ID: u32 = 2;
+        let mut rankings = RankingsInLevel::default();
+        if self.floorplan.is_some() {
+            rankings.drawings.push(DRAWING_ID);
+        }
+        let mut level = Level::new(
+            LevelProperties {
+                name: NameInSite(self.first_level.clone()),
+                elevation: LevelElevation(0.0),
+                ..Default::default()
+            },
+            rankings,
+        );
+        if let Some(floorplan) = &self.floorplan {
+            level.drawings.insert(
+                DRAWING_ID,
+                Drawing {
+                    properties: DrawingProperties {
+                        name: NameInSite("Floorplan".to_owned()),
+                        source: floorplan.source.clone(),
+                        pixels_per_meter: PixelsPerMeter(floorplan.pixels_per_meter),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            );
+        }
+        site.levels.insert(LEVEL_ID, level);
+        site
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_map_has_one_level_with_floorplan() {
+        let map = NewMap {
+            name: "warehouse".to_owned(),
+            first_level: "ground".to_owned(),
+            floorplan: NewMapFloorplan::from_calibration(
+                AssetSource::Local("floorplan.png".to_owned()),
+                250.0,
+                5.0,
+            ),
+        };
+        let site = map.to_site();
+        assert_eq!(site.levels.len(), 1);
+        let level = site.levels.values().next().unwrap();
+        assert_eq!(level.properties.name.0, "ground");
+        let drawing = level.drawings.values().next().unwrap();
+        assert_eq!(drawing.properties.pixels_per_meter.0, 50.0);
+    }
+
+    #[test]
+    fn calibration_rejects_invalid_measurements() {
+        let source = AssetSource::Local("floorplan.png".to_owned());
+        assert!(NewMapFloorplan::from_calibration(source.clone(), 100.0, 0.0).is_none());
+        assert!(NewMapFloorplan::from_calibration(source, -10.0, 2.0).is_none());
+    }
+}
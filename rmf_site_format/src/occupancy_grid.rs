@@ -0,0 +1,301 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// How the occupancy grid of a level gets rasterized.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OccupancyGridOptions {
+    /// Size of each cell, in meters
+    pub resolution: f32,
+    /// Free space that gets added around the bounds of the level, in meters
+    pub padding: f32,
+    /// How thick walls are when they get rasterized, in meters
+    pub wall_thickness: f32,
+}
+
+impl Default for OccupancyGridOptions {
+    fn default() -> Self {
+        Self {
+            resolution: 0.05,
+            padding: 1.0,
+            wall_thickness: 0.1,
+        }
+    }
+}
+
+/// A two dimensional grid of occupied and free cells for one level, in the
+/// form that is used by nav2 map servers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OccupancyGrid {
+    pub width: u32,
+    pub height: u32,
+    /// Size of each cell, in meters
+    pub resolution: f32,
+    /// The position of the lower left corner of the grid, in meters
+    pub origin: [f32; 2],
+    /// Whether each cell is occupied, row by row starting from the top row
+    pub cells: Vec<bool>,
+}
+
+/// The metadata file that nav2 map servers load alongside the image of a
+/// grid.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OccupancyGridMetadata {
+    pub image: String,
+    pub mode: String,
+    pub resolution: f32,
+    pub origin: [f32; 3],
+    pub negate: u8,
+    pub occupied_thresh: f32,
+    pub free_thresh: f32,
+}
+
+impl OccupancyGrid {
+    /// Make an empty grid that covers the rectangle from `min` to `max`.
+    pub fn new(min: Vec2, max: Vec2, resolution: f32) -> Self {
+        let size = ((max - min) / resolution).ceil().max(Vec2::ONE);
+        let width = size.x as u32;
+        let height = size.y as u32;
+        Self {
+            width,
+            height,
+            resolution,
+            origin: min.to_array(),
+            cells: vec![false; (width * height) as usize],
+        }
+    }
+
+    /// The center of the cell in the given column and row.
+    pub fn cell_center(&self, col: u32, row: u32) -> Vec2 {
+        Vec2::new(
+            self.origin[0] + (col as f32 + 0.5) * self.resolution,
+            self.origin[1] + ((self.height - row) as f32 - 0.5) * self.resolution,
+        )
+    }
+
+    /// The column and row of the cell that contains a point, if the point is
+    /// inside of the grid.
+    pub fn cell_of(&self, p: Vec2) -> Option<(u32, u32)> {
+        let col = ((p.x - self.origin[0]) / self.resolution).floor();
+        let row_from_bottom = ((p.y - self.origin[1]) / self.resolution).floor();
+        if col < 0.0 || row_from_bottom < 0.0 {
+            return None;
+        }
+        let (col, row_from_bottom) = (col as u32, row_from_bottom as u32);
+        if col >= self.width || row_from_bottom >= self.height {
+            return None;
+        }
+        Some((col, self.height - 1 - row_from_bottom))
+    }
+
+    pub fn is_occupied(&self, p: Vec2) -> bool {
+        self.cell_of(p)
+            .is_some_and(|(col, row)| self.cells[(row * self.width + col) as usize])
+    }
+
+    pub fn occupied_count(&self) -> usize {
+        self.cells.iter().filter(|c| **c).count()
+    }
+
+    /// Mark every cell whose center is within half of `thickness` of the
+    /// segment from `p0` to `p1`.
+    pub fn fill_segment(&mut self, p0: Vec2, p1: Vec2, thickness: f32) {
+        // Cells that the segment passes through are always occupied, even
+        // when the segment is thinner than a cell.
+        let radius = (thickness / 2.0).max(self.resolution / 2.0);
+        let min = p0.min(p1) - Vec2::splat(radius);
+        let max = p0.max(p1) + Vec2::splat(radius);
+        self.fill_where(min, max, |p| {
+            let d = p1 - p0;
+            let t = if d.length_squared() > 0.0 {
+                ((p - p0).dot(d) / d.length_squared()).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            (p0 + t * d).distance(p) <= radius
+        });
+    }
+
+    /// Mark every cell whose center is inside of a polygon.
+    pub fn fill_polygon(&mut self, polygon: &[Vec2]) {
+        let Some((min, max)) = bounds(polygon.iter().copied()) else {
+            return;
+        };
+        self.fill_where(min, max, |p| contains(polygon, p));
+    }
+
+    fn fill_where(&mut self, min: Vec2, max: Vec2, occupied: impl Fn(Vec2) -> bool) {
+        let origin = Vec2::from(self.origin);
+        let lower = ((min - origin) / self.resolution).floor().max(Vec2::ZERO);
+        let upper = ((max - origin) / self.resolution)
+            .floor()
+            .min(Vec2::new(self.width as f32 - 1.0, self.height as f32 - 1.0));
+        if lower.x > upper.x || lower.y > upper.y {
+            return;
+        }
+        for row_from_bottom in lower.y as u32..=upper.y as u32 {
+            let row = self.height - 1 - row_from_bottom;
+            for col in lower.x as u32..=upper.x as u32 {
+                if occupied(self.cell_center(col, row)) {
+                    self.cells[(row * self.width + col) as usize] = true;
+                }
+            }
+        }
+    }
+
+    /// Encode the grid as a binary PGM image where occupied cells are black
+    /// and free cells are white.
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut data = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        data.extend(self.cells.iter().map(|c| if *c { 0 } else { 254 }));
+        data
+    }
+
+    pub fn metadata(&self, image: String) -> OccupancyGridMetadata {
+        OccupancyGridMetadata {
+            image,
+            mode: "trinary".to_owned(),
+            resolution: self.resolution,
+            origin: [self.origin[0], self.origin[1], 0.0],
+            negate: 0,
+            occupied_thresh: 0.65,
+            free_thresh: 0.25,
+        }
+    }
+
+    /// The contents of the map.yaml file that goes alongside the image.
+    pub fn to_map_yaml(&self, image: String) -> serde_yaml::Result<String> {
+        serde_yaml::to_string(&self.metadata(image))
+    }
+}
+
+/// The file name of the image of an occupancy grid for a level. The map.yaml
+/// file that describes it has the same stem.
+pub fn occupancy_grid_file(level_name: &str) -> String {
+    let name: String = level_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{name}.pgm")
+}
+
+fn bounds(points: impl Iterator<Item = Vec2>) -> Option<(Vec2, Vec2)> {
+    points.fold(None, |bounds, p| match bounds {
+        Some((min, max)) => Some((p.min(min), p.max(max))),
+        None => Some((p, p)),
+    })
+}
+
+/// Even-odd test for whether a point is inside of a polygon.
+fn contains(polygon: &[Vec2], p: Vec2) -> bool {
+    let mut inside = false;
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        if (a.y > p.y) != (b.y > p.y) {
+            let x = a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if p.x < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+impl Site {
+    /// Rasterize the walls of a level and a set of footprints into an
+    /// occupancy grid. Footprints are polygons in level coordinates, usually
+    /// of the static models on the level, which only the editor knows the
+    /// shapes of.
+    pub fn to_occupancy_grid(
+        &self,
+        level_id: u32,
+        options: &OccupancyGridOptions,
+        footprints: &[Vec<Vec2>],
+    ) -> Option<OccupancyGrid> {
+        let level = self.levels.get(&level_id)?;
+        let point = |anchor: u32| -> Option<Vec2> {
+            let p = level
+                .anchors
+                .get(&anchor)
+                .or_else(|| self.anchors.get(&anchor))?
+                .translation_for_category(Category::General);
+            Some(Vec2::from(p))
+        };
+
+        let walls: Vec<(Vec2, Vec2)> = level
+            .walls
+            .values()
+            .filter_map(|wall| {
+                let [a0, a1] = wall.anchors.array();
+                Some((point(a0)?, point(a1)?))
+            })
+            .collect();
+
+        let (min, max) = bounds(
+            level
+                .anchors
+                .values()
+                .map(|a| Vec2::from(a.translation_for_category(Category::General)))
+                .chain(footprints.iter().flatten().copied()),
+        )?;
+        let padding = Vec2::splat(options.padding.max(0.0));
+        let mut grid = OccupancyGrid::new(min - padding, max + padding, options.resolution);
+
+        for (p0, p1) in walls {
+            grid.fill_segment(p0, p1, options.wall_thickness);
+        }
+        for footprint in footprints {
+            grid.fill_polygon(footprint);
+        }
+        Some(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segment_and_footprint_are_rasterized() {
+        let mut grid = OccupancyGrid::new(Vec2::ZERO, Vec2::new(10.0, 5.0), 0.5);
+        assert_eq!((grid.width, grid.height), (20, 10));
+
+        grid.fill_segment(Vec2::new(1.0, 1.2), Vec2::new(9.0, 1.2), 0.1);
+        assert!(grid.is_occupied(Vec2::new(5.1, 1.1)));
+        assert!(!grid.is_occupied(Vec2::new(5.1, 2.1)));
+
+        grid.fill_polygon(&[
+            Vec2::new(2.0, 3.0),
+            Vec2::new(4.0, 3.0),
+            Vec2::new(4.0, 4.0),
+            Vec2::new(2.0, 4.0),
+        ]);
+        assert!(grid.is_occupied(Vec2::new(3.0, 3.5)));
+        assert!(!grid.is_occupied(Vec2::new(5.0, 3.5)));
+
+        // The top row of the image is the highest row of the grid
+        let (_, row) = grid.cell_of(Vec2::new(3.0, 4.9)).unwrap();
+        assert_eq!(row, 0);
+
+        let pgm = grid.to_pgm();
+        assert!(pgm.starts_with(b"P5\n20 10\n255\n"));
+        assert_eq!(pgm.len(), "P5\n20 10\n255\n".len() + 200);
+    }
+}
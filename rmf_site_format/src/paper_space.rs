@@ -0,0 +1,93 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+#[cfg(feature = "bevy")]
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+/// Describes the sheet that a level gets printed on when it is exported as a
+/// plan image or PDF.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+pub struct PaperSpace {
+    /// Color that fills the whole page behind the plan, as sRGB components
+    /// in the range [0, 1]
+    #[serde(default = "PaperSpace::default_background")]
+    pub background_color: [f32; 3],
+    /// Width and height of the page in millimeters
+    #[serde(default = "PaperSpace::default_page_size")]
+    pub page_size: [f32; 2],
+    /// Empty space between the edge of the page and the plan, in millimeters
+    #[serde(default = "PaperSpace::default_margin")]
+    pub margin: f32,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub title_block: TitleBlock,
+}
+
+impl PaperSpace {
+    pub fn default_background() -> [f32; 3] {
+        [1.0, 1.0, 1.0]
+    }
+
+    /// ISO A3 in landscape orientation
+    pub fn default_page_size() -> [f32; 2] {
+        [420.0, 297.0]
+    }
+
+    pub fn default_margin() -> f32 {
+        10.0
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Default for PaperSpace {
+    fn default() -> Self {
+        Self {
+            background_color: Self::default_background(),
+            page_size: Self::default_page_size(),
+            margin: Self::default_margin(),
+            title_block: Default::default(),
+        }
+    }
+}
+
+/// Print-style fields that are rendered in the corner of an exported plan.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TitleBlock {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub project: String,
+    /// Leave this empty to use the name of the level.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub level: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub revision: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub date: String,
+}
+
+impl TitleBlock {
+    pub fn is_empty(&self) -> bool {
+        self.project.is_empty()
+            && self.level.is_empty()
+            && self.revision.is_empty()
+            && self.date.is_empty()
+    }
+}
@@ -0,0 +1,158 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Options for generating randomized variants of a site. Planners can be
+/// tested against these variants to check how robust they are to noise in
+/// the map.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PerturbationOptions {
+    /// The seed of the first variant. Variant `i` uses `seed + i`, so the
+    /// same options always produce the same variants.
+    pub seed: u64,
+    /// How far, in meters, each vertex may be moved along each axis
+    pub max_displacement: f32,
+    /// The fraction of lanes, from 0.0 to 1.0, that get closed in each variant
+    pub lane_closure_ratio: f32,
+    /// How many variants to generate
+    pub variants: usize,
+}
+
+impl Default for PerturbationOptions {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            max_displacement: 0.1,
+            lane_closure_ratio: 0.1,
+            variants: 5,
+        }
+    }
+}
+
+impl PerturbationOptions {
+    pub fn variant_seed(&self, variant: usize) -> u64 {
+        self.seed.wrapping_add(variant as u64)
+    }
+}
+
+/// A small SplitMix64 generator. Variants need to be reproducible across
+/// platforms, so this avoids depending on the algorithm of an external crate.
+#[derive(Debug, Clone)]
+pub(crate) struct SeededRng(pub(crate) u64);
+
+impl SeededRng {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A value in the range [0, 1)
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// A value in the range [-max, max)
+    pub(crate) fn offset(&mut self, max: f32) -> f32 {
+        (2.0 * self.next_f32() - 1.0) * max
+    }
+}
+
+impl Site {
+    /// Make a copy of this site where the anchors of every level are moved by
+    /// a random offset and a random selection of lanes is removed. Anchors
+    /// that belong to the site or to lift cabins are left in place so that
+    /// lifts stay aligned across levels.
+    pub fn perturbed(&self, seed: u64, max_displacement: f32, lane_closure_ratio: f32) -> Site {
+        let mut rng = SeededRng(seed);
+        let mut site = self.clone();
+
+        for level in site.levels.values_mut() {
+            for anchor in level.anchors.values_mut() {
+                match anchor {
+                    Anchor::Translate2D(p) => {
+                        p[0] += rng.offset(max_displacement);
+                        p[1] += rng.offset(max_displacement);
+                    }
+                    Anchor::CategorizedTranslate2D(categories) => {
+                        let dx = rng.offset(max_displacement);
+                        let dy = rng.offset(max_displacement);
+                        for p in categories.0.values_mut() {
+                            p[0] += dx;
+                            p[1] += dy;
+                        }
+                    }
+                    Anchor::Pose3D(pose) => {
+                        pose.trans[0] += rng.offset(max_displacement);
+                        pose.trans[1] += rng.offset(max_displacement);
+                    }
+                }
+            }
+        }
+
+        // Shuffle the lane IDs and close the first ones
+        let mut lanes: Vec<u32> = site.navigation.guided.lanes.keys().copied().collect();
+        for i in (1..lanes.len()).rev() {
+            let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+            lanes.swap(i, j);
+        }
+        let closed = (lane_closure_ratio.clamp(0.0, 1.0) * lanes.len() as f32).round() as usize;
+        for lane in lanes.iter().take(closed) {
+            site.navigation.guided.lanes.remove(lane);
+        }
+
+        site
+    }
+
+    /// Generate every variant that is described by the options.
+    pub fn perturbed_variants(&self, options: &PerturbationOptions) -> Vec<Site> {
+        (0..options.variants)
+            .map(|i| {
+                self.perturbed(
+                    options.variant_seed(i),
+                    options.max_displacement,
+                    options.lane_closure_ratio,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perturbation_is_reproducible() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let site = legacy::building_map::BuildingMap::from_bytes(&data)
+            .unwrap()
+            .to_site()
+            .unwrap();
+        let lane_count = site.navigation.guided.lanes.len();
+
+        let a = site.perturbed(7, 0.2, 0.5);
+        let b = site.perturbed(7, 0.2, 0.5);
+        assert_eq!(a.to_string_ron().unwrap(), b.to_string_ron().unwrap());
+        let closed = (0.5 * lane_count as f32).round() as usize;
+        assert_eq!(a.navigation.guided.lanes.len(), lane_count - closed);
+    }
+}
@@ -0,0 +1,582 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+use thiserror::Error;
+
+/// Height of each row in the title block, in millimeters
+const TITLE_ROW_HEIGHT: f32 = 6.0;
+/// Width of the title block, in millimeters
+const TITLE_BLOCK_WIDTH: f32 = 90.0;
+/// Space between the plan and the title block, in millimeters
+const TITLE_BLOCK_GAP: f32 = 4.0;
+const MM_TO_PT: f32 = 72.0 / 25.4;
+
+const FLOOR_COLOR: [f32; 3] = [0.85, 0.85, 0.85];
+const WALL_COLOR: [f32; 3] = [0.1, 0.1, 0.1];
+const DOOR_COLOR: [f32; 3] = [0.6, 0.35, 0.1];
+const LANE_COLOR: [f32; 3] = [0.2, 0.4, 0.9];
+const LOCATION_COLOR: [f32; 3] = [0.85, 0.2, 0.2];
+const TEXT_COLOR: [f32; 3] = [0.0, 0.0, 0.0];
+const EVACUATION_COLOR: [f32; 3] = [0.1, 0.65, 0.2];
+
+#[derive(Debug, Error)]
+pub enum PlanExportError {
+    #[error("Level [{0}] does not exist")]
+    BrokenLevelReference(u32),
+    #[error("Entity [{0}] referenced a non existing anchor")]
+    BrokenAnchorReference(u32),
+    #[error("The page is too small to fit its margins and title block")]
+    PageTooSmall,
+}
+
+/// A primitive of an exported plan. All coordinates are in millimeters on
+/// the page, with the origin at the top left corner and y pointing down.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanShape {
+    Polygon {
+        points: Vec<[f32; 2]>,
+        fill: [f32; 3],
+    },
+    Line {
+        from: [f32; 2],
+        to: [f32; 2],
+        width: f32,
+        color: [f32; 3],
+    },
+    Circle {
+        center: [f32; 2],
+        radius: f32,
+        fill: [f32; 3],
+    },
+    Frame {
+        min: [f32; 2],
+        max: [f32; 2],
+        width: f32,
+        color: [f32; 3],
+    },
+    Text {
+        /// Left end of the text baseline
+        position: [f32; 2],
+        size: f32,
+        text: String,
+    },
+}
+
+/// Which elements of the navigation of a level are shown on a plan.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlanLayer {
+    /// Robot lanes and every location
+    #[default]
+    Traffic,
+    /// Evacuation routes, emergency exits, and assembly points
+    Evacuation,
+}
+
+/// A level laid out on the page described by its [`PaperSpace`], ready to be
+/// written as an SVG image or a PDF document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanDrawing {
+    pub page_size: [f32; 2],
+    pub background_color: [f32; 3],
+    pub shapes: Vec<PlanShape>,
+    /// How many meters of the site are covered by one meter of paper
+    pub scale: f32,
+}
+
+impl Site {
+    /// Lay out a top-down plan of a level using its paper space settings.
+    pub fn to_plan(&self, level_id: u32) -> Result<PlanDrawing, PlanExportError> {
+        self.to_plan_of_layer(level_id, PlanLayer::Traffic)
+    }
+
+    /// Lay out a top-down evacuation plan of a level, which highlights its
+    /// evacuation routes, emergency exits, and assembly points.
+    pub fn to_evacuation_plan(&self, level_id: u32) -> Result<PlanDrawing, PlanExportError> {
+        self.to_plan_of_layer(level_id, PlanLayer::Evacuation)
+    }
+
+    pub fn to_plan_of_layer(
+        &self,
+        level_id: u32,
+        layer: PlanLayer,
+    ) -> Result<PlanDrawing, PlanExportError> {
+        let level = self
+            .levels
+            .get(&level_id)
+            .ok_or(PlanExportError::BrokenLevelReference(level_id))?;
+        let paper = &level.paper_space;
+        let get_anchor = |id: u32| -> Result<[f32; 2], PlanExportError> {
+            self.get_anchor(id)
+                .map(|a| a.translation_for_category(Category::General))
+                .ok_or(PlanExportError::BrokenAnchorReference(id))
+        };
+        let on_level =
+            |edge: &Edge<u32>| edge.array().iter().all(|id| level.anchors.contains_key(id));
+
+        // Collect the geometry in site coordinates before fitting it to the page
+        let mut floors = Vec::new();
+        for floor in level.floors.values() {
+            let points = floor
+                .anchors
+                .0
+                .iter()
+                .map(|id| get_anchor(*id))
+                .collect::<Result<Vec<_>, _>>()?;
+            floors.push(points);
+        }
+        let mut edges = Vec::new();
+        for wall in level.walls.values() {
+            edges.push((wall.anchors.array(), 0.5, WALL_COLOR));
+        }
+        for door in level.doors.values() {
+            if layer == PlanLayer::Evacuation && door.emergency_exit.0 {
+                edges.push((door.anchors.array(), 0.7, EVACUATION_COLOR));
+            } else {
+                edges.push((door.anchors.array(), 0.35, DOOR_COLOR));
+            }
+        }
+        match layer {
+            PlanLayer::Traffic => {
+                for lane in self.navigation.guided.lanes.values() {
+                    if on_level(&lane.anchors) {
+                        edges.push((lane.anchors.array(), 0.25, LANE_COLOR));
+                    }
+                }
+            }
+            PlanLayer::Evacuation => {
+                let guided = &self.navigation.guided;
+                for lane in guided.lanes.values().chain(guided.human_lanes.values()) {
+                    if lane.evacuation.0 && on_level(&lane.anchors) {
+                        edges.push((lane.anchors.array(), 0.5, EVACUATION_COLOR));
+                    }
+                }
+            }
+        }
+        let mut edge_points = Vec::new();
+        for (anchors, width, color) in edges {
+            let [a, b] = anchors;
+            edge_points.push(([get_anchor(a)?, get_anchor(b)?], width, color));
+        }
+        let mut locations = Vec::new();
+        for location in self.navigation.guided.locations.values() {
+            if !level.anchors.contains_key(&location.anchor.0) {
+                continue;
+            }
+            let (radius, fill) = match layer {
+                PlanLayer::Traffic => (1.0, LOCATION_COLOR),
+                PlanLayer::Evacuation => {
+                    if !location.tags.0.iter().any(|t| t.is_assembly_point()) {
+                        continue;
+                    }
+                    (2.0, EVACUATION_COLOR)
+                }
+            };
+            locations.push((get_anchor(location.anchor.0)?, radius, fill));
+        }
+
+        let [page_width, page_height] = paper.page_size;
+        let margin = paper.margin;
+        let title_rows = 5;
+        let title_height = title_rows as f32 * TITLE_ROW_HEIGHT;
+        let area_min = [margin, margin];
+        let area_max = [
+            page_width - margin,
+            page_height - margin - title_height - TITLE_BLOCK_GAP,
+        ];
+        if area_max[0] <= area_min[0]
+            || area_max[1] <= area_min[1]
+            || page_width - 2.0 * margin < TITLE_BLOCK_WIDTH
+        {
+            return Err(PlanExportError::PageTooSmall);
+        }
+
+        let mut min = [f32::INFINITY; 2];
+        let mut max = [f32::NEG_INFINITY; 2];
+        for p in floors
+            .iter()
+            .flatten()
+            .chain(edge_points.iter().flat_map(|(e, ..)| e.iter()))
+            .chain(locations.iter().map(|(p, ..)| p))
+        {
+            min = [min[0].min(p[0]), min[1].min(p[1])];
+            max = [max[0].max(p[0]), max[1].max(p[1])];
+        }
+        if min[0] > max[0] {
+            // The level is empty, so just show the space around the origin
+            min = [-1.0, -1.0];
+            max = [1.0, 1.0];
+        }
+        let extent = [(max[0] - min[0]).max(1e-3), (max[1] - min[1]).max(1e-3)];
+        let area_size = [area_max[0] - area_min[0], area_max[1] - area_min[1]];
+        // Millimeters of paper per meter of site
+        let mm_per_m = (area_size[0] / extent[0]).min(area_size[1] / extent[1]);
+        let site_center = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+        let page_center = [
+            (area_min[0] + area_max[0]) / 2.0,
+            (area_min[1] + area_max[1]) / 2.0,
+        ];
+        let to_page = |p: [f32; 2]| {
+            [
+                page_center[0] + (p[0] - site_center[0]) * mm_per_m,
+                page_center[1] - (p[1] - site_center[1]) * mm_per_m,
+            ]
+        };
+
+        let mut shapes = Vec::new();
+        for points in floors {
+            shapes.push(PlanShape::Polygon {
+                points: points.into_iter().map(to_page).collect(),
+                fill: FLOOR_COLOR,
+            });
+        }
+        for ([a, b], width, color) in edge_points {
+            shapes.push(PlanShape::Line {
+                from: to_page(a),
+                to: to_page(b),
+                width,
+                color,
+            });
+        }
+        for (p, radius, fill) in locations {
+            shapes.push(PlanShape::Circle {
+                center: to_page(p),
+                radius,
+                fill,
+            });
+        }
+
+        let scale = 1000.0 / mm_per_m;
+        let title = &paper.title_block;
+        let level_name = if title.level.is_empty() {
+            &level.properties.name.0
+        } else {
+            &title.level
+        };
+        let rows = [
+            ("Project", title.project.clone()),
+            ("Level", level_name.clone()),
+            ("Revision", title.revision.clone()),
+            ("Date", title.date.clone()),
+            ("Scale", format!("1:{:.0}", scale)),
+        ];
+        let block_max = [page_width - margin, page_height - margin];
+        let block_min = [
+            block_max[0] - TITLE_BLOCK_WIDTH,
+            block_max[1] - title_height,
+        ];
+        shapes.push(PlanShape::Frame {
+            min: block_min,
+            max: block_max,
+            width: 0.35,
+            color: TEXT_COLOR,
+        });
+        for (i, (label, value)) in rows.into_iter().enumerate() {
+            let top = block_min[1] + i as f32 * TITLE_ROW_HEIGHT;
+            if i > 0 {
+                shapes.push(PlanShape::Line {
+                    from: [block_min[0], top],
+                    to: [block_max[0], top],
+                    width: 0.18,
+                    color: TEXT_COLOR,
+                });
+            }
+            shapes.push(PlanShape::Text {
+                position: [block_min[0] + 2.0, top + TITLE_ROW_HEIGHT - 1.8],
+                size: 3.0,
+                text: format!("{label}: {value}"),
+            });
+        }
+
+        Ok(PlanDrawing {
+            page_size: paper.page_size,
+            background_color: paper.background_color,
+            shapes,
+            scale,
+        })
+    }
+}
+
+impl PlanDrawing {
+    /// Write the plan as an SVG image whose user units are millimeters.
+    pub fn to_svg(&self) -> String {
+        let [w, h] = self.page_size;
+        let mut out = String::new();
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str(&format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}mm\" height=\"{h}mm\" \
+            viewBox=\"0 0 {w} {h}\">\n"
+        ));
+        out.push_str(&format!(
+            "<rect x=\"0\" y=\"0\" width=\"{w}\" height=\"{h}\" fill=\"{}\"/>\n",
+            svg_color(self.background_color)
+        ));
+        for shape in &self.shapes {
+            match shape {
+                PlanShape::Polygon { points, fill } => {
+                    let points: Vec<String> = points
+                        .iter()
+                        .map(|p| format!("{},{}", p[0], p[1]))
+                        .collect();
+                    out.push_str(&format!(
+                        "<polygon points=\"{}\" fill=\"{}\"/>\n",
+                        points.join(" "),
+                        svg_color(*fill)
+                    ));
+                }
+                PlanShape::Line {
+                    from,
+                    to,
+                    width,
+                    color,
+                } => {
+                    out.push_str(&format!(
+                        "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" \
+                        stroke-width=\"{width}\" stroke-linecap=\"round\"/>\n",
+                        from[0],
+                        from[1],
+                        to[0],
+                        to[1],
+                        svg_color(*color)
+                    ));
+                }
+                PlanShape::Circle {
+                    center,
+                    radius,
+                    fill,
+                } => {
+                    out.push_str(&format!(
+                        "<circle cx=\"{}\" cy=\"{}\" r=\"{radius}\" fill=\"{}\"/>\n",
+                        center[0],
+                        center[1],
+                        svg_color(*fill)
+                    ));
+                }
+                PlanShape::Frame {
+                    min,
+                    max,
+                    width,
+                    color,
+                } => {
+                    out.push_str(&format!(
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" \
+                        stroke=\"{}\" stroke-width=\"{width}\"/>\n",
+                        min[0],
+                        min[1],
+                        max[0] - min[0],
+                        max[1] - min[1],
+                        svg_color(*color)
+                    ));
+                }
+                PlanShape::Text {
+                    position,
+                    size,
+                    text,
+                } => {
+                    out.push_str(&format!(
+                        "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" \
+                        font-size=\"{size}\" fill=\"{}\">{}</text>\n",
+                        position[0],
+                        position[1],
+                        svg_color(TEXT_COLOR),
+                        escape_xml(text)
+                    ));
+                }
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// Write the plan as a single page PDF document.
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let width = self.page_size[0] * MM_TO_PT;
+        let height = self.page_size[1] * MM_TO_PT;
+        let pt = |p: &[f32; 2]| [p[0] * MM_TO_PT, height - p[1] * MM_TO_PT];
+
+        let mut content = String::new();
+        content.push_str(&format!(
+            "{} rg 0 0 {width} {height} re f\n1 J 1 j\n",
+            pdf_color(self.background_color)
+        ));
+        for shape in &self.shapes {
+            match shape {
+                PlanShape::Polygon { points, fill } => {
+                    if points.is_empty() {
+                        continue;
+                    }
+                    content.push_str(&format!("{} rg\n", pdf_color(*fill)));
+                    for (i, p) in points.iter().enumerate() {
+                        let [x, y] = pt(p);
+                        let op = if i == 0 { "m" } else { "l" };
+                        content.push_str(&format!("{x} {y} {op}\n"));
+                    }
+                    content.push_str("h f\n");
+                }
+                PlanShape::Line {
+                    from,
+                    to,
+                    width,
+                    color,
+                } => {
+                    let [x0, y0] = pt(from);
+                    let [x1, y1] = pt(to);
+                    content.push_str(&format!(
+                        "{} RG {} w {x0} {y0} m {x1} {y1} l S\n",
+                        pdf_color(*color),
+                        width * MM_TO_PT
+                    ));
+                }
+                PlanShape::Circle {
+                    center,
+                    radius,
+                    fill,
+                } => {
+                    // Approximate the circle with four cubic Bezier curves
+                    let [cx, cy] = pt(center);
+                    let r = radius * MM_TO_PT;
+                    let k = 0.5523 * r;
+                    content.push_str(&format!(
+                        "{} rg\n{} {cy} m\n\
+                        {} {} {} {} {cx} {} c\n\
+                        {} {} {} {} {} {cy} c\n\
+                        {} {} {} {} {cx} {} c\n\
+                        {} {} {} {} {} {cy} c\nf\n",
+                        pdf_color(*fill),
+                        cx + r,
+                        cx + r,
+                        cy + k,
+                        cx + k,
+                        cy + r,
+                        cy + r,
+                        cx - k,
+                        cy + r,
+                        cx - r,
+                        cy + k,
+                        cx - r,
+                        cx - r,
+                        cy - k,
+                        cx - k,
+                        cy - r,
+                        cy - r,
+                        cx + k,
+                        cy - r,
+                        cx + r,
+                        cy - k,
+                        cx + r,
+                    ));
+                }
+                PlanShape::Frame {
+                    min,
+                    max,
+                    width,
+                    color,
+                } => {
+                    let [x, y] = pt(&[min[0], max[1]]);
+                    content.push_str(&format!(
+                        "{} RG {} w {x} {y} {} {} re S\n",
+                        pdf_color(*color),
+                        width * MM_TO_PT,
+                        (max[0] - min[0]) * MM_TO_PT,
+                        (max[1] - min[1]) * MM_TO_PT
+                    ));
+                }
+                PlanShape::Text {
+                    position,
+                    size,
+                    text,
+                } => {
+                    let [x, y] = pt(position);
+                    content.push_str(&format!(
+                        "{} rg BT /F1 {} Tf {x} {y} Td ({}) Tj ET\n",
+                        pdf_color(TEXT_COLOR),
+                        size * MM_TO_PT,
+                        escape_pdf(text)
+                    ));
+                }
+            }
+        }
+
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R >>".to_owned(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_owned(),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {width} {height}] \
+                /Resources << /Font << /F1 5 0 R >> >> /Contents 4 0 R >>"
+            ),
+            format!(
+                "<< /Length {} >>\nstream\n{content}endstream",
+                content.len()
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_owned(),
+        ];
+
+        let mut out = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::new();
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.push_str(&format!("{} 0 obj\n{object}\nendobj\n", i + 1));
+        }
+        let xref = out.len();
+        out.push_str(&format!(
+            "xref\n0 {}\n0000000000 65535 f \n",
+            objects.len() + 1
+        ));
+        for offset in offsets {
+            out.push_str(&format!("{offset:010} 00000 n \n"));
+        }
+        out.push_str(&format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        ));
+        out.into_bytes()
+    }
+}
+
+fn svg_color(c: [f32; 3]) -> String {
+    let [r, g, b] = c.map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8);
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn pdf_color(c: [f32; 3]) -> String {
+    let [r, g, b] = c.map(|v| v.clamp(0.0, 1.0));
+    format!("{r} {g} {b}")
+}
+
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The built-in PDF fonts only cover latin characters, so anything else is
+/// replaced to keep the document valid.
+fn escape_pdf(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            ' '..='~' => out.push(c),
+            _ => out.push('?'),
+        }
+    }
+    out
+}
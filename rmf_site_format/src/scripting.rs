@@ -0,0 +1,581 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+//! Batch edits of sites through [rhai](https://rhai.rs) scripts. Scripts get
+//! handles to the vertices, lanes, walls, and models of a site, and every
+//! property that gets assigned through a handle changes the site:
+//!
+//! ```text
+//! // Shift all vertices on L2 by +0.3m in x
+//! for v in vertices("L2") { v.x += 0.3; }
+//!
+//! // Limit the speed of every lane in nav graph #1
+//! for lane in lanes() { if lane.in_graph(1) { lane.speed_limit = 0.5; } }
+//! ```
+//!
+//! `levels()` and `graphs()` list the names of levels and the IDs and names
+//! of nav graphs, and `print` writes into the output of the script.
+
+use crate::*;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map};
+use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
+use thiserror::Error as ThisError;
+
+/// Scripts are stopped after this many operations so that a script which
+/// never finishes, such as `loop {}`, cannot hang the editor.
+pub const MAX_SCRIPT_OPERATIONS: u64 = 10_000_000;
+
+/// How deeply expressions may be nested at the top level and inside of
+/// functions.
+const MAX_SCRIPT_EXPR_DEPTHS: (usize, usize) = (64, 32);
+
+#[derive(ThisError, Debug, Clone)]
+#[error("the script failed: {0}")]
+pub struct ScriptError(pub String);
+
+/// Site IDs of the elements whose properties were assigned by a script.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScriptChanges {
+    pub anchors: BTreeSet<u32>,
+    pub lanes: BTreeSet<u32>,
+    pub walls: BTreeSet<u32>,
+    pub model_instances: BTreeSet<u32>,
+}
+
+impl ScriptChanges {
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+            && self.lanes.is_empty()
+            && self.walls.is_empty()
+            && self.model_instances.is_empty()
+    }
+
+    pub fn count(&self) -> usize {
+        self.anchors.len() + self.lanes.len() + self.walls.len() + self.model_instances.len()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScriptOutcome {
+    /// The site after the script ran
+    pub site: Site,
+    pub changes: ScriptChanges,
+    /// Everything that the script printed
+    pub output: Vec<String>,
+}
+
+struct ScriptState {
+    site: Site,
+    changes: ScriptChanges,
+}
+
+type SharedState = Rc<RefCell<ScriptState>>;
+
+#[derive(Clone)]
+struct VertexHandle {
+    state: SharedState,
+    level: u32,
+    id: u32,
+}
+
+#[derive(Clone)]
+struct LaneHandle {
+    state: SharedState,
+    id: u32,
+}
+
+#[derive(Clone)]
+struct WallHandle {
+    state: SharedState,
+    level: u32,
+    id: u32,
+}
+
+#[derive(Clone)]
+struct ModelHandle {
+    state: SharedState,
+    id: u32,
+}
+
+/// Move an anchor to a new general position. Positions for other categories
+/// keep their offsets from the general position.
+fn move_anchor(anchor: &mut Anchor, to: [f32; 2]) {
+    let from = anchor.translation_for_category(Category::General);
+    let delta = [to[0] - from[0], to[1] - from[1]];
+    let shift = |p: &mut [f32; 2]| {
+        p[0] += delta[0];
+        p[1] += delta[1];
+    };
+    match anchor {
+        Anchor::Translate2D(p) => shift(p),
+        Anchor::CategorizedTranslate2D(categorized) => categorized.0.values_mut().for_each(shift),
+        Anchor::Pose3D(pose) => {
+            pose.trans[0] += delta[0];
+            pose.trans[1] += delta[1];
+        }
+    }
+}
+
+impl VertexHandle {
+    fn position(&mut self) -> [f32; 2] {
+        let state = self.state.borrow();
+        state
+            .site
+            .levels
+            .get(&self.level)
+            .and_then(|level| level.anchors.get(&self.id))
+            .map(|anchor| anchor.translation_for_category(Category::General))
+            .unwrap_or_default()
+    }
+
+    fn set_position(&mut self, p: [f32; 2]) {
+        let mut state = self.state.borrow_mut();
+        let Some(anchor) = state
+            .site
+            .levels
+            .get_mut(&self.level)
+            .and_then(|level| level.anchors.get_mut(&self.id))
+        else {
+            return;
+        };
+        move_anchor(anchor, p);
+        state.changes.anchors.insert(self.id);
+    }
+}
+
+impl LaneHandle {
+    fn lane<T>(&self, f: impl FnOnce(&Lane<u32>) -> T) -> Option<T> {
+        self.state
+            .borrow()
+            .site
+            .navigation
+            .guided
+            .lanes
+            .get(&self.id)
+            .map(f)
+    }
+}
+
+impl WallHandle {
+    fn wall<T>(&self, f: impl FnOnce(&mut Wall<u32>) -> T) -> Option<T> {
+        let mut state = self.state.borrow_mut();
+        state
+            .site
+            .levels
+            .get_mut(&self.level)
+            .and_then(|level| level.walls.get_mut(&self.id))
+            .map(f)
+    }
+}
+
+impl ModelHandle {
+    fn model<T>(&self, f: impl FnOnce(&mut ModelInstance<u32>) -> T) -> Option<T> {
+        let mut state = self.state.borrow_mut();
+        state
+            .site
+            .model_instances
+            .get_mut(&self.id)
+            .map(|instance| f(&mut instance.bundle))
+    }
+
+    fn set_translation(&mut self, axis: usize, value: f64) {
+        if self
+            .model(|model| model.pose.trans[axis] = value as f32)
+            .is_some()
+        {
+            self.state
+                .borrow_mut()
+                .changes
+                .model_instances
+                .insert(self.id);
+        }
+    }
+}
+
+fn find_level(state: &SharedState, name: &str) -> Result<u32, Box<EvalAltResult>> {
+    state
+        .borrow()
+        .site
+        .levels
+        .iter()
+        .find(|(_, level)| level.properties.name.0 == name)
+        .map(|(id, _)| *id)
+        .ok_or_else(|| format!("there is no level named [{name}]").into())
+}
+
+fn optional_float(value: Option<f32>) -> Dynamic {
+    value.map_or(Dynamic::UNIT, |v| Dynamic::from_float(v as f64))
+}
+
+fn float_from_dynamic(value: &Dynamic) -> Option<f32> {
+    value
+        .as_float()
+        .ok()
+        .or_else(|| value.as_int().ok().map(|v| v as f64))
+        .map(|v| v as f32)
+}
+
+fn register_handles(engine: &mut Engine) {
+    engine
+        .register_type_with_name::<VertexHandle>("Vertex")
+        .register_get("id", |v: &mut VertexHandle| v.id as i64)
+        .register_get_set(
+            "x",
+            |v: &mut VertexHandle| v.position()[0] as f64,
+            |v: &mut VertexHandle, x: f64| {
+                let [_, y] = v.position();
+                v.set_position([x as f32, y]);
+            },
+        )
+        .register_get_set(
+            "y",
+            |v: &mut VertexHandle| v.position()[1] as f64,
+            |v: &mut VertexHandle, y: f64| {
+                let [x, _] = v.position();
+                v.set_position([x, y as f32]);
+            },
+        );
+
+    engine
+        .register_type_with_name::<LaneHandle>("Lane")
+        .register_get("id", |l: &mut LaneHandle| l.id as i64)
+        .register_get("start", |l: &mut LaneHandle| {
+            l.lane(|lane| lane.anchors.start() as i64).unwrap_or(-1)
+        })
+        .register_get("end", |l: &mut LaneHandle| {
+            l.lane(|lane| lane.anchors.end() as i64).unwrap_or(-1)
+        })
+        .register_get_set(
+            "speed_limit",
+            |l: &mut LaneHandle| optional_float(l.lane(|lane| lane.forward.speed_limit).flatten()),
+            |l: &mut LaneHandle, value: Dynamic| {
+                let speed_limit = float_from_dynamic(&value);
+                let mut state = l.state.borrow_mut();
+                let Some(lane) = state.site.navigation.guided.lanes.get_mut(&l.id) else {
+                    return;
+                };
+                lane.forward.speed_limit = speed_limit;
+                if let ReverseLane::Different(motion) = &mut lane.reverse {
+                    motion.speed_limit = speed_limit;
+                }
+                state.changes.lanes.insert(l.id);
+            },
+        )
+        .register_fn("in_graph", |l: &mut LaneHandle, graph: i64| {
+            l.lane(|lane| lane.graphs.includes(graph as u32))
+                .unwrap_or(false)
+        });
+
+    engine
+        .register_type_with_name::<WallHandle>("Wall")
+        .register_get("id", |w: &mut WallHandle| w.id as i64)
+        .register_get_set(
+            "height",
+            |w: &mut WallHandle| w.wall(|wall| wall.height.0 as f64).unwrap_or(0.0),
+            |w: &mut WallHandle, height: f64| {
+                if w.wall(|wall| wall.height.0 = height as f32).is_some() {
+                    w.state.borrow_mut().changes.walls.insert(w.id);
+                }
+            },
+        );
+
+    engine
+        .register_type_with_name::<ModelHandle>("Model")
+        .register_get("id", |m: &mut ModelHandle| m.id as i64)
+        .register_get_set(
+            "name",
+            |m: &mut ModelHandle| m.model(|model| model.name.0.clone()).unwrap_or_default(),
+            |m: &mut ModelHandle, name: &str| {
+                if m.model(|model| model.name.0 = name.to_owned()).is_some() {
+                    m.state.borrow_mut().changes.model_instances.insert(m.id);
+                }
+            },
+        );
+    for (axis, property) in ["x", "y", "z"].into_iter().enumerate() {
+        engine.register_get_set(
+            property,
+            move |m: &mut ModelHandle| {
+                m.model(|model| model.pose.trans[axis] as f64)
+                    .unwrap_or(0.0)
+            },
+            move |m: &mut ModelHandle, value: f64| m.set_translation(axis, value),
+        );
+    }
+}
+
+fn register_queries(engine: &mut Engine, state: &SharedState) {
+    let s = state.clone();
+    engine.register_fn("levels", move || -> Array {
+        s.borrow()
+            .site
+            .levels
+            .values()
+            .map(|level| Dynamic::from(level.properties.name.0.clone()))
+            .collect()
+    });
+
+    let s = state.clone();
+    engine.register_fn("graphs", move || -> Array {
+        s.borrow()
+            .site
+            .navigation
+            .guided
+            .graphs
+            .iter()
+            .map(|(id, graph)| {
+                let mut map = Map::new();
+                map.insert("id".into(), Dynamic::from(*id as i64));
+                map.insert("name".into(), Dynamic::from(graph.name.0.clone()));
+                Dynamic::from_map(map)
+            })
+            .collect()
+    });
+
+    let s = state.clone();
+    engine.register_fn(
+        "vertices",
+        move |level: &str| -> Result<Array, Box<EvalAltResult>> {
+            let level = find_level(&s, level)?;
+            let ids: Vec<u32> = s.borrow().site.levels[&level]
+                .anchors
+                .keys()
+                .copied()
+                .collect();
+            Ok(ids
+                .into_iter()
+                .map(|id| {
+                    Dynamic::from(VertexHandle {
+                        state: s.clone(),
+                        level,
+                        id,
+                    })
+                })
+                .collect())
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn("lanes", move || -> Array {
+        let ids: Vec<u32> = s
+            .borrow()
+            .site
+            .navigation
+            .guided
+            .lanes
+            .keys()
+            .copied()
+            .collect();
+        ids.into_iter()
+            .map(|id| {
+                Dynamic::from(LaneHandle {
+                    state: s.clone(),
+                    id,
+                })
+            })
+            .collect()
+    });
+
+    let s = state.clone();
+    engine.register_fn(
+        "lanes",
+        move |level: &str| -> Result<Array, Box<EvalAltResult>> {
+            let level = find_level(&s, level)?;
+            let ids: Vec<u32> = {
+                let state = s.borrow();
+                let anchors = &state.site.levels[&level].anchors;
+                state
+                    .site
+                    .navigation
+                    .guided
+                    .lanes
+                    .iter()
+                    .filter(|(_, lane)| anchors.contains_key(&lane.anchors.start()))
+                    .map(|(id, _)| *id)
+                    .collect()
+            };
+            Ok(ids
+                .into_iter()
+                .map(|id| {
+                    Dynamic::from(LaneHandle {
+                        state: s.clone(),
+                        id,
+                    })
+                })
+                .collect())
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn(
+        "walls",
+        move |level: &str| -> Result<Array, Box<EvalAltResult>> {
+            let level = find_level(&s, level)?;
+            let ids: Vec<u32> = s.borrow().site.levels[&level]
+                .walls
+                .keys()
+                .copied()
+                .collect();
+            Ok(ids
+                .into_iter()
+                .map(|id| {
+                    Dynamic::from(WallHandle {
+                        state: s.clone(),
+                        level,
+                        id,
+                    })
+                })
+                .collect())
+        },
+    );
+
+    let s = state.clone();
+    engine.register_fn("models", move || -> Array {
+        let ids: Vec<u32> = s.borrow().site.model_instances.keys().copied().collect();
+        ids.into_iter()
+            .map(|id| {
+                Dynamic::from(ModelHandle {
+                    state: s.clone(),
+                    id,
+                })
+            })
+            .collect()
+    });
+
+    let s = state.clone();
+    engine.register_fn(
+        "models",
+        move |level: &str| -> Result<Array, Box<EvalAltResult>> {
+            let level = find_level(&s, level)?;
+            let ids: Vec<u32> = s
+                .borrow()
+                .site
+                .model_instances
+                .iter()
+                .filter(|(_, instance)| instance.parent == level)
+                .map(|(id, _)| *id)
+                .collect();
+            Ok(ids
+                .into_iter()
+                .map(|id| {
+                    Dynamic::from(ModelHandle {
+                        state: s.clone(),
+                        id,
+                    })
+                })
+                .collect())
+        },
+    );
+}
+
+impl Site {
+    /// Run a script that edits this site and return the edited site.
+    pub fn run_script(self, script: &str) -> Result<ScriptOutcome, ScriptError> {
+        let state: SharedState = Rc::new(RefCell::new(ScriptState {
+            site: self,
+            changes: ScriptChanges::default(),
+        }));
+        let output = Rc::new(RefCell::new(Vec::new()));
+
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_SCRIPT_OPERATIONS);
+        engine.set_max_expr_depths(MAX_SCRIPT_EXPR_DEPTHS.0, MAX_SCRIPT_EXPR_DEPTHS.1);
+        register_handles(&mut engine);
+        register_queries(&mut engine, &state);
+        let printed = output.clone();
+        engine.on_print(move |s| printed.borrow_mut().push(s.to_owned()));
+        let debugged = output.clone();
+        engine.on_debug(move |s, _, _| debugged.borrow_mut().push(s.to_owned()));
+
+        let result = engine.run(script);
+        // The engine holds onto clones of the state, so drop it before taking
+        // the state back.
+        drop(engine);
+        result.map_err(|err| match *err {
+            EvalAltResult::ErrorTooManyOperations(_) => ScriptError(format!(
+                "the script was stopped after {MAX_SCRIPT_OPERATIONS} operations, \
+                it might be stuck in a loop"
+            )),
+            err => ScriptError(err.to_string()),
+        })?;
+
+        let ScriptState { site, changes } = Rc::try_unwrap(state)
+            .map_err(|_| ScriptError("the script kept a handle to the site".to_owned()))?
+            .into_inner();
+        let output = output.take();
+        Ok(ScriptOutcome {
+            site,
+            changes,
+            output,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_site() -> Site {
+        let mut site = Site::blank_L1("test".to_owned());
+        let level = site.levels.get_mut(&1).unwrap();
+        level.anchors.insert(10, Anchor::Translate2D([1.0, 2.0]));
+        level.anchors.insert(11, Anchor::Translate2D([3.0, 2.0]));
+        site.navigation.guided.lanes.insert(
+            20,
+            Lane {
+                anchors: Edge::new(10, 11),
+                forward: Default::default(),
+                reverse: Default::default(),
+                graphs: AssociatedGraphs::Only([5].into()),
+                evacuation: Default::default(),
+                curve: Default::default(),
+                marker: LaneMarker,
+            },
+        );
+        site
+    }
+
+    #[test]
+    fn script_shifts_vertices_and_limits_lanes() {
+        let script = r#"
+            for v in vertices("L1") { v.x += 0.3; }
+            for lane in lanes() { if lane.in_graph(5) { lane.speed_limit = 0.5; } }
+            print(levels().len());
+        "#;
+        let outcome = test_site().run_script(script).unwrap();
+        let anchors = &outcome.site.levels[&1].anchors;
+        let [x, _] = anchors[&10].translation_for_category(Category::General);
+        assert!((x - 1.3).abs() < 1e-5);
+        let lane = &outcome.site.navigation.guided.lanes[&20];
+        assert_eq!(lane.forward.speed_limit, Some(0.5));
+        assert_eq!(outcome.changes.anchors.len(), 2);
+        assert_eq!(outcome.changes.lanes.len(), 1);
+        assert_eq!(outcome.output, vec!["1".to_owned()]);
+    }
+
+    #[test]
+    fn script_errors_are_reported() {
+        assert!(test_site().run_script("vertices(\"L9\");").is_err());
+    }
+
+    #[test]
+    fn endless_scripts_are_stopped() {
+        for script in ["loop {}", "while true {}"] {
+            let err = test_site().run_script(script).unwrap_err();
+            assert!(err.0.contains("stopped"), "{err}");
+        }
+    }
+}
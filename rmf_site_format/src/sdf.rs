@@ -16,7 +16,8 @@
 */
 
 use crate::{
-    Anchor, Angle, AssetSource, Category, DoorType, Level, LiftCabin, Pose, Rotation, Site, Swing,
+    Anchor, Angle, AssetSource, AxisConvention, Category, DoorType, ExportOptions, Level,
+    LiftCabin, Light, LightKind, Pose, Rotation, Site, Swing,
 };
 use glam::Vec3;
 use once_cell::sync::Lazy;
@@ -49,6 +50,8 @@ pub enum SdfConversionError {
     BrokenModelDescriptionReference(u32),
     #[error("Failed deserializing world template: {0}")]
     CorruptedWorldTemplate(String),
+    #[error("Unable to convert light [{0}]: {1}")]
+    LightConversion(String, String),
 }
 
 impl Pose {
@@ -107,6 +110,7 @@ fn make_sdf_door(
     offset: Vec3,
     ros_interface: bool,
     kind: &DoorType,
+    timing: &DoorTiming,
     mesh_prefix: &str,
     model_name: &str,
 ) -> Result<SdfModel, SdfConversionError> {
@@ -160,6 +164,9 @@ fn make_sdf_door(
         door_model.link.push(make_sdf_door_link(mesh_prefix, label));
     }
     let mut door_motion_params = vec![];
+    // How far the door moves to open, in meters for sliding doors and in
+    // radians for swing doors
+    let mut door_travel = door_length;
     let joints = match kind {
         DoorType::SingleSliding(door) => {
             door_plugin_inner
@@ -217,6 +224,7 @@ fn make_sdf_door(
             };
             let lower = 0.0;
             let upper = open.abs();
+            door_travel = upper as f32;
             let pose = Pose {
                 trans: [0.0, (door_length / 2.0) * door.pivot_on.sign(), 1.25],
                 ..Default::default()
@@ -277,6 +285,7 @@ fn make_sdf_door(
             .to_sdf();
             let left_length = (door.left_right_ratio / (1.0 + door.left_right_ratio)) * door_length;
             let right_length = door_length - left_length;
+            door_travel = left_length.max(right_length);
             vec![
                 SdfJoint {
                     name: model_name.to_owned() + "_right_joint",
@@ -338,6 +347,7 @@ fn make_sdf_door(
                 Swing::Both { forward, .. } => (forward.radians() as f64, -1.0),
             };
             let upper = open.abs();
+            door_travel = upper as f32;
             let right_pose = Pose {
                 trans: [0.0, -door_length / 2.0, 1.25],
                 ..Default::default()
@@ -402,6 +412,32 @@ fn make_sdf_door(
             }]
         }
     };
+    // The door plugins are configured by speed, so the open duration is
+    // turned into the speed that opens the door in that time.
+    let v_max = timing
+        .open_duration
+        .filter(|d| *d > 0.0)
+        .map(|d| (door_travel / d).to_string());
+    if let Some(v_max) = &v_max {
+        for (name, value) in &mut door_motion_params {
+            if *name == "v_max_door" {
+                *value = v_max.as_str();
+            }
+        }
+    }
+    let timing_params: Vec<(&str, String)> = [
+        ("open_duration", timing.open_duration),
+        ("close_duration", timing.close_duration),
+        ("dwell_time", timing.dwell),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| Some((name, value?.to_string())))
+    .collect();
+    door_motion_params.extend(
+        timing_params
+            .iter()
+            .map(|(name, value)| (*name, value.as_str())),
+    );
     let b = ros_interface.to_string();
     door_motion_params.push(("ros_interface", &b));
     door_model.joint.extend(joints);
@@ -419,6 +455,73 @@ fn make_sdf_door(
     Ok(door_model)
 }
 
+/// The intensity of a point or spot light that Gazebo treats as an intensity
+/// of 1.0, which is the default intensity of lights in the editor.
+const SDF_REFERENCE_LUMENS: f32 = 800.0;
+/// The illuminance of a directional light that Gazebo treats as an intensity
+/// of 1.0, which is the default illuminance of directional lights.
+const SDF_REFERENCE_LUX: f32 = 100000.0;
+
+fn make_sdf_light(
+    name: &str,
+    light: &Light,
+    elevation: f32,
+) -> Result<SdfLight, SdfConversionError> {
+    let rgba = |c: [f32; 4]| format!("{} {} {} {}", c[0], c[1], c[2], c[3]);
+    let (kind, color, intensity, shadows, range) = match &light.kind {
+        LightKind::Point(point) => (
+            "point",
+            point.color,
+            point.intensity / SDF_REFERENCE_LUMENS,
+            point.enable_shadows,
+            Some(point.range),
+        ),
+        LightKind::Spot(spot) => (
+            "spot",
+            spot.color,
+            spot.intensity / SDF_REFERENCE_LUMENS,
+            spot.enable_shadows,
+            Some(spot.range),
+        ),
+        LightKind::Directional(dir) => (
+            "directional",
+            dir.color,
+            dir.illuminance / SDF_REFERENCE_LUX,
+            dir.enable_shadows,
+            None,
+        ),
+    };
+    let attenuation = range
+        .map(|range| {
+            format!(
+                "<attenuation><range>{range}</range><constant>1</constant>\
+                <linear>0.01</linear><quadratic>0.001</quadratic></attenuation>"
+            )
+        })
+        .unwrap_or_default();
+    let spot = if kind == "spot" {
+        "<spot><inner_angle>0.6</inner_angle><outer_angle>1.0</outer_angle>\
+        <falloff>1</falloff></spot>"
+    } else {
+        ""
+    };
+    // Lights of the editor shine along their local -Z axis, which is also
+    // the default direction of SDF lights.
+    let xml = format!(
+        "<light name=\"{name}\" type=\"{kind}\"><cast_shadows>{shadows}</cast_shadows>\
+        <intensity>{intensity}</intensity><diffuse>{}</diffuse><specular>{}</specular>\
+        {attenuation}<direction>0 0 -1</direction>{spot}</light>",
+        rgba(color),
+        rgba([0.2, 0.2, 0.2, 1.0]),
+    );
+    let mut sdf_light: SdfLight = yaserde::de::from_str(&xml)
+        .map_err(|err| SdfConversionError::LightConversion(name.to_owned(), err))?;
+    let mut pose = light.pose.clone();
+    pose.trans[2] += elevation;
+    sdf_light.pose = Some(pose.to_sdf());
+    Ok(sdf_light)
+}
+
 impl Site {
     pub fn to_sdf(&self) -> Result<SdfRoot, SdfConversionError> {
         let get_anchor = |id: u32| -> Result<Anchor, SdfConversionError> {
@@ -512,6 +615,10 @@ impl Site {
                         SdfConversionError::BrokenModelDescriptionReference(*model_instance_id),
                     )?;
 
+                // Models are raised by their z offset when they are spawned so
+                // that their base can rest on the floor.
+                let mut sim_pose = parented_model_instance.bundle.pose.clone();
+                sim_pose.trans[2] += model_description_bundle.z_offset.0 .0;
                 let mut added = false;
                 if model_description_bundle.source.0
                     == AssetSource::Search("OpenRobotics/TeleportIngestor".to_string())
@@ -519,7 +626,7 @@ impl Site {
                     world.include.push(SdfWorldInclude {
                         uri: "model://TeleportIngestor".to_string(),
                         name: Some(parented_model_instance.bundle.name.0.clone()),
-                        pose: Some(parented_model_instance.bundle.pose.to_sdf()),
+                        pose: Some(sim_pose.to_sdf()),
                         ..Default::default()
                     });
                     added = true;
@@ -529,7 +636,7 @@ impl Site {
                     world.include.push(SdfWorldInclude {
                         uri: "model://TeleportDispenser".to_string(),
                         name: Some(parented_model_instance.bundle.name.0.clone()),
-                        pose: Some(parented_model_instance.bundle.pose.to_sdf()),
+                        pose: Some(sim_pose.to_sdf()),
                         ..Default::default()
                     });
                     added = true;
@@ -541,7 +648,7 @@ impl Site {
                     world.model.push(SdfModel {
                         name: parented_model_instance.bundle.name.0.clone(),
                         r#static: Some(model_description_bundle.is_static.0 .0),
-                        pose: Some(parented_model_instance.bundle.pose.to_sdf()),
+                        pose: Some(sim_pose.to_sdf()),
                         link: vec![SdfLink {
                             name: "link".into(),
                             collision: vec![SdfCollision {
@@ -576,6 +683,38 @@ impl Site {
                     level_model_names.push(model_description_bundle.name.0.clone());
                 }
             }
+            // Robots that are placed at spawn points, unless a model
+            // instance with the same name already stands in for them
+            for location in self.navigation.guided.locations.values() {
+                if !level.anchors.contains_key(&location.anchor.0) {
+                    continue;
+                }
+                for spawn in location.tags.0.iter().filter_map(|t| t.spawn_point()) {
+                    if spawn.robot_name.is_empty() || spawn.robot_type.is_empty() {
+                        continue;
+                    }
+                    let already_placed = self
+                        .model_instances
+                        .values()
+                        .any(|instance| instance.bundle.name.0 == spawn.robot_name);
+                    if already_placed {
+                        continue;
+                    }
+                    let [x, y] =
+                        get_anchor(location.anchor.0)?.translation_for_category(Category::Location);
+                    let pose = Pose {
+                        trans: [x, y, level.properties.elevation.0],
+                        ..Default::default()
+                    };
+                    world.include.push(SdfWorldInclude {
+                        uri: format!("model://{}", spawn.robot_type),
+                        name: Some(spawn.robot_name.clone()),
+                        pose: Some(pose.to_sdf()),
+                        ..Default::default()
+                    });
+                    level_model_names.push(spawn.robot_name.clone());
+                }
+            }
             // Now add all the doors
             for (door_id, door) in &level.doors {
                 let left_anchor = get_anchor(door.anchors.left())?;
@@ -586,12 +725,20 @@ impl Site {
                     Vec3::new(0.0, 0.0, level.properties.elevation.0),
                     true,
                     &door.kind,
+                    &door.timing,
                     format!("door_{door_id}").as_str(),
                     door.name.0.as_str(),
                 )?;
                 level_model_names.push(door_model.name.clone());
                 world.model.push(door_model);
             }
+            for (light_id, light) in &level.lights {
+                world.light.push(make_sdf_light(
+                    &format!("light_{light_id}"),
+                    light,
+                    level.properties.elevation.0,
+                )?);
+            }
             for model_name in level_model_names.into_iter() {
                 let model_element = XmlElement {
                     name: "model".into(),
@@ -640,7 +787,11 @@ impl Site {
                 .map(|level| level.properties.name.0.clone())
                 .ok_or(SdfConversionError::MissingInitialLevel(lift_name.clone()))?;
             elements.push(("initial_floor", initial_floor));
-            elements.push(("v_max_cabin", "2.0".to_string()));
+            let v_max_cabin = match lift.properties.timing.speed {
+                Some(speed) => speed.to_string(),
+                None => "2.0".to_string(),
+            };
+            elements.push(("v_max_cabin", v_max_cabin));
             elements.push(("a_max_cabin", "1.2".to_string()));
             elements.push(("a_nom_cabin", "1.0".to_string()));
             elements.push(("dx_min_cabin", "0.001".to_string()));
@@ -648,22 +799,7 @@ impl Site {
             elements.push(("cabin_joint_name", "cabin_joint".to_string()));
             let mut levels: BTreeMap<u32, ElementMap> = BTreeMap::new();
             let mut lift_models = Vec::new();
-            let mut lift_joints = vec![SdfJoint {
-                name: "cabin_joint".into(),
-                r#type: "prismatic".into(),
-                parent: "world".into(),
-                child: "platform".into(),
-                axis: Some(SdfJointAxis {
-                    xyz: Vector3d::new(0.0, 0.0, 1.0),
-                    limit: SdfJointAxisLimit {
-                        lower: -std::f64::INFINITY,
-                        upper: std::f64::INFINITY,
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                }),
-                ..Default::default()
-            }];
+            let mut lift_joints = Vec::new();
             for (face, door_placement) in cabin.doors().iter() {
                 let Some(door_placement) = door_placement else {
                     continue;
@@ -686,6 +822,7 @@ impl Site {
                     x_offset,
                     false,
                     &door.kind,
+                    &lift.properties.timing.doors,
                     &cabin_mesh_prefix,
                     &cabin_door_name,
                 )?;
@@ -712,6 +849,7 @@ impl Site {
                         Vec3::from(pose.trans) + Vec3::new(0.0, 0.0, level.properties.elevation.0),
                         false,
                         &door.kind,
+                        &lift.properties.timing.doors,
                         &cabin_mesh_prefix,
                         &shaft_door_name,
                     )?;
@@ -742,6 +880,35 @@ impl Site {
                     level.push(element);
                 }
             }
+            // The cabin starts at its initial level, so the travel of the
+            // cabin joint is measured from there to the lowest and highest
+            // floors that the lift visits.
+            let (lower, upper) = levels
+                .keys()
+                .filter_map(|id| self.levels.get(id))
+                .map(|level| (level.properties.elevation.0 - pose.trans[2]) as f64)
+                .fold((0.0_f64, 0.0_f64), |(lower, upper), dz| {
+                    (lower.min(dz), upper.max(dz))
+                });
+            lift_joints.insert(
+                0,
+                SdfJoint {
+                    name: "cabin_joint".into(),
+                    r#type: "prismatic".into(),
+                    parent: "world".into(),
+                    child: "platform".into(),
+                    axis: Some(SdfJointAxis {
+                        xyz: Vector3d::new(0.0, 0.0, 1.0),
+                        limit: SdfJointAxisLimit {
+                            lower,
+                            upper,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            );
             for (key, door_pairs) in levels.into_iter() {
                 let level = get_level(key)?;
                 component_data.push(XmlElement {
@@ -849,15 +1016,94 @@ impl Site {
             r#type: "adiabatic".to_string(),
             ..Default::default()
         };
-        world.gravity = Vector3d::new(0.0, 0.0, -9.80);
-        world.magnetic_field = Vector3d::new(5.64e-6, 2.29e-5, -4.24e-5);
+        let options = &self.properties.export_settings.sdf;
+        match options.axis {
+            AxisConvention::ZUp => {
+                world.gravity = Vector3d::new(0.0, 0.0, -9.80);
+                world.magnetic_field = Vector3d::new(5.64e-6, 2.29e-5, -4.24e-5);
+            }
+            AxisConvention::YUp => {
+                world.gravity = Vector3d::new(0.0, -9.80, 0.0);
+                world.magnetic_field = Vector3d::new(5.64e-6, -4.24e-5, -2.29e-5);
+            }
+        }
+        if !options.is_default() {
+            apply_export_options(world, options);
+        }
         Ok(root)
     }
 }
 
+/// Read a pose that was written by [`Pose::to_sdf`].
+fn parse_sdf_pose(pose: &SdfPose) -> Option<Pose> {
+    let values: Vec<f32> = pose
+        .data
+        .split_whitespace()
+        .map(|v| v.parse::<f32>())
+        .collect::<Result<_, _>>()
+        .ok()?;
+    let trans = [*values.get(0)?, *values.get(1)?, *values.get(2)?];
+    let rot = match values.len() {
+        6 => Rotation::EulerExtrinsicXYZ([
+            Angle::Rad(values[3]),
+            Angle::Rad(values[4]),
+            Angle::Rad(values[5]),
+        ]),
+        7 => Rotation::Quat([values[4], values[5], values[6], values[3]]),
+        _ => return None,
+    };
+    Some(Pose { trans, rot })
+}
+
+fn transform_sdf_pose(pose: Option<&SdfPose>, options: &ExportOptions) -> SdfPose {
+    let pose = match pose {
+        Some(pose) => match parse_sdf_pose(pose) {
+            Some(pose) => pose,
+            None => return pose.clone(),
+        },
+        None => Pose::default(),
+    };
+    options.transform_pose(&pose).to_sdf()
+}
+
+fn scale_sdf_geometry(geometry: &mut SdfGeometry, scale: f64) {
+    if let SdfGeometry::Mesh(mesh) = geometry {
+        mesh.scale = Vector3d::new(scale, scale, scale);
+    }
+}
+
+/// Move every top level element of the world into the coordinates requested
+/// by the export options. Meshes of the world are scaled along with their
+/// poses, but included models and the joint limits of doors and lifts keep
+/// their dimensions in meters.
+fn apply_export_options(world: &mut SdfWorld, options: &ExportOptions) {
+    let scale = options.scale as f64;
+    for model in &mut world.model {
+        model.pose = Some(transform_sdf_pose(model.pose.as_ref(), options));
+        for link in &mut model.link {
+            for collision in &mut link.collision {
+                scale_sdf_geometry(&mut collision.geometry, scale);
+            }
+            for visual in &mut link.visual {
+                scale_sdf_geometry(&mut visual.geometry, scale);
+            }
+        }
+    }
+    for include in &mut world.include {
+        include.pose = Some(transform_sdf_pose(include.pose.as_ref(), options));
+    }
+    for light in &mut world.light {
+        light.pose = Some(transform_sdf_pose(light.pose.as_ref(), options));
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::legacy::building_map::BuildingMap;
+    use super::*;
+    use crate::{
+        legacy::building_map::BuildingMap, IsStatic, LocationTag, ModelProperty, RobotSpawnPoint,
+        ZOffset,
+    };
 
     #[test]
     fn serialize_sdf() {
@@ -874,4 +1120,134 @@ mod tests {
         let s = yaserde::ser::to_string_with_config(&sdf, &config).unwrap();
         std::fs::write("test.sdf", s).unwrap();
     }
+
+    #[test]
+    fn z_offset_raises_dynamic_models() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let map = BuildingMap::from_bytes(&data).unwrap();
+        let mut site = map.to_site().unwrap();
+        let (instance_name, instance_z, description_id) = site
+            .model_instances
+            .values()
+            .find_map(|instance| {
+                let description = instance.bundle.description.0?;
+                Some((
+                    instance.bundle.name.0.clone(),
+                    instance.bundle.pose.trans[2],
+                    description,
+                ))
+            })
+            .unwrap();
+        let description = site.model_descriptions.get_mut(&description_id).unwrap();
+        description.source = ModelProperty(AssetSource::Search("Test/Cart".to_owned()));
+        description.is_static = ModelProperty(IsStatic(false));
+        description.z_offset = ModelProperty(ZOffset(0.5));
+
+        let sdf = site.to_sdf().unwrap();
+        let model = sdf.world[0]
+            .model
+            .iter()
+            .find(|m| m.name == instance_name)
+            .unwrap();
+        assert_eq!(model.r#static, Some(false));
+        let pose = parse_sdf_pose(model.pose.as_ref().unwrap()).unwrap();
+        assert!((pose.trans[2] - instance_z - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn spawn_points_are_included() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let mut site = BuildingMap::from_bytes(&data).unwrap().to_site().unwrap();
+        let location_id = *site
+            .navigation
+            .guided
+            .locations
+            .iter()
+            .find(|(_, l)| {
+                site.levels
+                    .values()
+                    .any(|level| level.anchors.contains_key(&l.anchor.0))
+            })
+            .unwrap()
+            .0;
+        let location = site
+            .navigation
+            .guided
+            .locations
+            .get_mut(&location_id)
+            .unwrap();
+        location
+            .tags
+            .0
+            .push(LocationTag::SpawnPoint(RobotSpawnPoint {
+                fleet: "tinyRobot".to_owned(),
+                robot_name: "spawn_test_robot".to_owned(),
+                robot_type: "TinyRobot".to_owned(),
+            }));
+
+        let sdf = site.to_sdf().unwrap();
+        let include = sdf.world[0]
+            .include
+            .iter()
+            .find(|i| i.name.as_deref() == Some("spawn_test_robot"))
+            .unwrap();
+        assert_eq!(include.uri, "model://TinyRobot");
+    }
+
+    #[test]
+    fn doors_are_controllable() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let site = BuildingMap::from_bytes(&data).unwrap().to_site().unwrap();
+        let sdf = site.to_sdf().unwrap();
+        let world = &sdf.world[0];
+        assert!(world.plugin.iter().any(|p| p.filename == "libdoor.so"));
+        let door_names: Vec<_> = site
+            .levels
+            .values()
+            .flat_map(|level| level.doors.values().map(|door| door.name.0.clone()))
+            .collect();
+        assert!(!door_names.is_empty());
+        for name in &door_names {
+            let model = world.model.iter().find(|m| m.name == *name).unwrap();
+            assert_eq!(model.r#static, Some(false));
+            let plugin = &model.plugin[0];
+            assert_eq!(plugin.filename, "libregister_component.so");
+            let component = plugin.elements.get("component").unwrap();
+            assert_eq!(
+                component.attributes.get("name").map(|s| s.as_str()),
+                Some("Door")
+            );
+            let ElementData::Nested(data) = &component.data else {
+                panic!("door component has no data");
+            };
+            let door = data.get("door").unwrap();
+            assert_eq!(door.attributes.get("name"), Some(name));
+            // Every joint that the door plugin drives must exist in the model
+            for key in ["left_joint_name", "right_joint_name"] {
+                let joint = door.attributes.get(key).unwrap();
+                if joint != "empty_joint" {
+                    assert!(model.joint.iter().any(|j| j.name == *joint));
+                }
+            }
+            assert!(data.get("v_max_door").is_some());
+        }
+    }
+
+    #[test]
+    fn lights_are_exported() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let map = BuildingMap::from_bytes(&data).unwrap();
+        let mut site = map.to_site().unwrap();
+        let lights_before = site.to_sdf().unwrap().world[0].light.len();
+        let level = site.levels.values_mut().next().unwrap();
+        level.lights.insert(
+            u32::MAX,
+            Light {
+                pose: Pose::default(),
+                kind: LightKind::default(),
+            },
+        );
+        let sdf = site.to_sdf().unwrap();
+        assert_eq!(sdf.world[0].light.len(), lights_before + 1);
+    }
 }
@@ -34,11 +34,15 @@ pub struct SiteProperties<T: RefTrait> {
     pub name: NameOfSite,
     #[serde(skip_serializing_if = "GeographicComponent::is_none")]
     pub geographic_offset: GeographicComponent,
+    #[serde(default, skip_serializing_if = "GeographicReferences::is_empty")]
+    pub geographic_references: GeographicReferences,
     // TODO(luca) group these into an IssueFilters?
     #[serde(default, skip_serializing_if = "FilteredIssues::is_empty")]
     pub filtered_issues: FilteredIssues<T>,
     #[serde(default, skip_serializing_if = "FilteredIssueKinds::is_empty")]
     pub filtered_issue_kinds: FilteredIssueKinds,
+    #[serde(default, skip_serializing_if = "ExportSettings::is_default")]
+    pub export_settings: ExportSettings,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -89,8 +93,10 @@ impl<T: RefTrait> Default for SiteProperties<T> {
         Self {
             name: NameOfSite("new_site".to_owned()),
             geographic_offset: GeographicComponent::default(),
+            geographic_references: GeographicReferences::default(),
             filtered_issues: FilteredIssues::default(),
             filtered_issue_kinds: FilteredIssueKinds::default(),
+            export_settings: ExportSettings::default(),
         }
     }
 }
@@ -100,8 +106,10 @@ impl<T: RefTrait> SiteProperties<T> {
         Ok(SiteProperties {
             name: self.name.clone(),
             geographic_offset: self.geographic_offset.clone(),
+            geographic_references: self.geographic_references.clone(),
             filtered_issues: self.filtered_issues.convert(id_map)?,
             filtered_issue_kinds: self.filtered_issue_kinds.clone(),
+            export_settings: self.export_settings.clone(),
         })
     }
 }
@@ -154,6 +162,19 @@ pub struct Site {
     /// Tasks available in this site
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub tasks: BTreeMap<u32, Tasks>,
+    /// The simulated crowd of humans in the site
+    #[serde(default, skip_serializing_if = "CrowdSim::is_default")]
+    pub crowd_sim: CrowdSim<u32>,
+    /// Logical groups of elements that are independent of the levels
+    #[serde(default, skip_serializing_if = "EntityGroups::is_empty")]
+    pub entity_groups: EntityGroups<u32>,
+    /// Schemas of the custom kinds of entities that are used in the site
+    #[serde(default, skip_serializing_if = "CustomEntitySchemas::is_empty")]
+    pub custom_entity_schemas: CustomEntitySchemas,
+    /// Tags that users attached to elements, keyed by the site ID of the
+    /// element
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<u32, EntityTags>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -161,7 +182,7 @@ pub struct Site {
 #[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
 pub struct NameOfSite(pub String);
 
-fn default_style_config() -> Style {
+pub(crate) fn default_style_config() -> Style {
     Style::new()
         .depth_limit(4)
         .new_line("\n".to_string())
@@ -244,20 +265,7 @@ impl Site {
 
     #[allow(non_snake_case)]
     pub fn blank_L1(name: String) -> Self {
-        let mut site = Site::default();
-        site.properties.name = NameOfSite(name);
-        site.levels.insert(
-            1,
-            Level::new(
-                LevelProperties {
-                    name: NameInSite("L1".to_owned()),
-                    elevation: LevelElevation(0.0),
-                    ..Default::default()
-                },
-                RankingsInLevel::default(),
-            ),
-        );
-        site
+        NewMap::new(name).to_site()
     }
 }
 
@@ -290,6 +298,29 @@ mod tests {
         Site::from_bytes_json(&site_string).unwrap();
     }
 
+    #[test]
+    fn ron_output_is_stable() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let map = BuildingMap::from_bytes(&data).unwrap();
+        let first = map.to_site().unwrap().to_string_ron().unwrap();
+        let second = map.to_site().unwrap().to_string_ron().unwrap();
+        assert_eq!(first, second);
+
+        // Saving a site that was loaded without any changes should reproduce
+        // the exact same file.
+        let original = map.to_site().unwrap();
+        let reloaded = Site::from_str_ron(&first).unwrap();
+        assert_eq!(first, reloaded.to_string_ron().unwrap());
+
+        let graphs = |site: &Site| {
+            crate::legacy::nav_graph::NavGraph::from_site(site)
+                .into_iter()
+                .map(|(name, graph)| (name, serde_yaml::to_string(&graph).unwrap()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(graphs(&original), graphs(&reloaded));
+    }
+
     #[test]
     fn produce_json_string() {
         let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
@@ -0,0 +1,187 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+use crate::*;
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, io};
+
+/// Counts and sizes of the elements of one level, or of a whole site.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ElementStats {
+    pub vertices: usize,
+    pub lanes: usize,
+    pub walls: usize,
+    pub models: usize,
+    pub chargers: usize,
+    /// Sum of the lengths of all robot lanes, in meters
+    pub lane_length: f32,
+    /// Sum of the areas of all floors, in square meters
+    pub floor_area: f32,
+}
+
+impl ElementStats {
+    fn add(&mut self, other: &ElementStats) {
+        self.vertices += other.vertices;
+        self.lanes += other.lanes;
+        self.walls += other.walls;
+        self.models += other.models;
+        self.chargers += other.chargers;
+        self.lane_length += other.lane_length;
+        self.floor_area += other.floor_area;
+    }
+}
+
+/// A summary of how much of a facility has been mapped, which can be tracked
+/// over time by saving it as JSON.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct MapStats {
+    pub site: String,
+    pub graphs: usize,
+    pub total: ElementStats,
+    /// Stats of each level, keyed by the name of the level
+    pub levels: BTreeMap<String, ElementStats>,
+}
+
+impl MapStats {
+    pub fn to_writer_json<W: io::Write>(&self, writer: W) -> serde_json::Result<()> {
+        serde_json::to_writer_pretty(writer, self)
+    }
+}
+
+impl Site {
+    /// Compute the stats of this site. Lanes and locations belong to the
+    /// level that holds their first anchor.
+    pub fn map_stats(&self) -> MapStats {
+        let mut stats = MapStats {
+            site: self.properties.name.0.clone(),
+            graphs: self.navigation.guided.graphs.len(),
+            ..Default::default()
+        };
+
+        for (level_id, level) in &self.levels {
+            let point = |anchor: &u32| -> Option<Vec2> {
+                let anchor = level
+                    .anchors
+                    .get(anchor)
+                    .or_else(|| self.anchors.get(anchor))?;
+                Some(Vec2::from(
+                    anchor.translation_for_category(Category::General),
+                ))
+            };
+
+            let mut level_stats = ElementStats {
+                vertices: level.anchors.len(),
+                walls: level.walls.len(),
+                ..Default::default()
+            };
+
+            for lane in self.navigation.guided.lanes.values() {
+                let [start, end] = lane.anchors.array();
+                if !level.anchors.contains_key(&start) {
+                    continue;
+                }
+                level_stats.lanes += 1;
+                if let (Some(p0), Some(p1)) = (point(&start), point(&end)) {
+                    level_stats.lane_length += p0.distance(p1);
+                }
+            }
+
+            level_stats.models = self
+                .model_instances
+                .values()
+                .filter(|instance| instance.parent == *level_id)
+                .count();
+
+            level_stats.chargers = self
+                .navigation
+                .guided
+                .locations
+                .values()
+                .filter(|location| level.anchors.contains_key(&location.anchor.0))
+                .filter(|location| location.tags.0.iter().any(|t| *t == LocationTag::Charger))
+                .count();
+
+            for floor in level.floors.values() {
+                let polygon: Option<Vec<Vec2>> = floor.anchors.0.iter().map(point).collect();
+                if let Some(polygon) = polygon {
+                    level_stats.floor_area += polygon_area(&polygon);
+                }
+            }
+
+            stats.total.add(&level_stats);
+            stats
+                .levels
+                .insert(level.properties.name.0.clone(), level_stats);
+        }
+
+        stats
+    }
+}
+
+/// Area of a simple polygon by the shoelace formula.
+fn polygon_area(polygon: &[Vec2]) -> f32 {
+    let n = polygon.len();
+    if n < 3 {
+        return 0.0;
+    }
+    let twice_area: f32 = (0..n)
+        .map(|i| polygon[i].perp_dot(polygon[(i + 1) % n]))
+        .sum();
+    twice_area.abs() / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_count_lanes_and_floor_area() {
+        let mut site = Site::blank_L1("test".to_owned());
+        let level = site.levels.get_mut(&1).unwrap();
+        for (id, p) in [
+            (10, [0.0, 0.0]),
+            (11, [4.0, 0.0]),
+            (12, [4.0, 3.0]),
+            (13, [0.0, 3.0]),
+        ] {
+            level.anchors.insert(id, Anchor::Translate2D(p));
+        }
+        level
+            .floors
+            .insert(20, Floor::from(Path(vec![10, 11, 12, 13])));
+        site.navigation.guided.lanes.insert(
+            21,
+            Lane {
+                anchors: Edge::new(10, 12),
+                forward: Default::default(),
+                reverse: Default::default(),
+                graphs: Default::default(),
+                evacuation: Default::default(),
+                curve: Default::default(),
+                marker: LaneMarker,
+            },
+        );
+
+        let stats = site.map_stats();
+        let l1 = &stats.levels["L1"];
+        assert_eq!(l1.vertices, 4);
+        assert_eq!(l1.lanes, 1);
+        assert!((l1.lane_length - 5.0).abs() < 1e-5);
+        assert!((l1.floor_area - 12.0).abs() < 1e-5);
+        assert_eq!(stats.total, *l1);
+    }
+}
@@ -0,0 +1,247 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+impl Site {
+    /// Make a smaller site that only contains the elements whose site IDs
+    /// are in `keep`, along with the anchors that they are attached to. The
+    /// result is still a valid site, so it can be shared on its own. Levels
+    /// are kept whole when they are listed themselves, and otherwise only
+    /// with the parts that are needed. Nav graphs, textures, and the
+    /// properties of the site are always kept, while lifts, models, and
+    /// scenarios are left out. Lanes and locations that are attached to the
+    /// cabin of a lift are left out along with the lift.
+    pub fn subset(&self, keep: &BTreeSet<u32>) -> Site {
+        // Selected anchors are kept even if nothing is attached to them
+        let mut anchors = keep.clone();
+        let lift_anchors: BTreeSet<u32> = self
+            .lifts
+            .values()
+            .flat_map(|lift| lift.cabin_anchors.keys().copied())
+            .collect();
+
+        let lanes = filter_lanes(
+            &self.navigation.guided.lanes,
+            keep,
+            &lift_anchors,
+            &mut anchors,
+        );
+        let human_lanes = filter_lanes(
+            &self.navigation.guided.human_lanes,
+            keep,
+            &lift_anchors,
+            &mut anchors,
+        );
+        let locations: BTreeMap<_, _> = self
+            .navigation
+            .guided
+            .locations
+            .iter()
+            .filter(|(id, location)| {
+                keep.contains(id) && !lift_anchors.contains(&location.anchor.0)
+            })
+            .map(|(id, location)| {
+                anchors.insert(location.anchor.0);
+                (*id, location.clone())
+            })
+            .collect();
+
+        let mut levels = BTreeMap::new();
+        for (level_id, level) in &self.levels {
+            if keep.contains(level_id) {
+                levels.insert(*level_id, level.clone());
+                continue;
+            }
+
+            let mut subset = Level::new(level.properties.clone(), RankingsInLevel::default());
+            subset.paper_space = level.paper_space.clone();
+            subset.flattened_offset = level.flattened_offset.clone();
+            let mut level_anchors = anchors.clone();
+            for (id, door) in &level.doors {
+                if keep.contains(id) {
+                    level_anchors.extend(door.anchors.array());
+                    subset.doors.insert(*id, door.clone());
+                }
+            }
+            for (id, wall) in &level.walls {
+                if keep.contains(id) {
+                    level_anchors.extend(wall.anchors.array());
+                    subset.walls.insert(*id, wall.clone());
+                }
+            }
+            for (id, floor) in &level.floors {
+                if keep.contains(id) {
+                    level_anchors.extend(floor.anchors.0.iter().copied());
+                    subset.floors.insert(*id, floor.clone());
+                }
+            }
+            for (id, zone) in &level.zones {
+                if keep.contains(id) {
+                    level_anchors.extend(zone.anchors.0.iter().copied());
+                    subset.zones.insert(*id, zone.clone());
+                }
+            }
+            subset.drawings = filter_ids(&level.drawings, keep);
+            subset.lights = filter_ids(&level.lights, keep);
+            subset.physical_cameras = filter_ids(&level.physical_cameras, keep);
+            subset.custom_entities = filter_ids(&level.custom_entities, keep);
+            subset.user_camera_poses = filter_ids(&level.user_camera_poses, keep);
+            subset.anchors = filter_ids(&level.anchors, &level_anchors);
+            // Elements on other levels may be attached to site anchors
+            anchors.extend(level_anchors.difference(&subset.anchors.keys().copied().collect()));
+
+            subset.rankings = RankingsInLevel {
+                floors: level
+                    .rankings
+                    .floors
+                    .iter()
+                    .filter(|id| subset.floors.contains_key(id))
+                    .copied()
+                    .collect(),
+                drawings: level
+                    .rankings
+                    .drawings
+                    .iter()
+                    .filter(|id| subset.drawings.contains_key(id))
+                    .copied()
+                    .collect(),
+            };
+
+            let is_empty = subset.anchors.is_empty()
+                && subset.drawings.is_empty()
+                && subset.lights.is_empty()
+                && subset.physical_cameras.is_empty()
+                && subset.custom_entities.is_empty()
+                && subset.user_camera_poses.is_empty();
+            if !is_empty {
+                levels.insert(*level_id, subset);
+            }
+        }
+
+        let mut properties = self.properties.clone();
+        properties.filtered_issues = Default::default();
+
+        Site {
+            format_version: self.format_version.clone(),
+            anchors: filter_ids(&self.anchors, &anchors),
+            properties,
+            levels,
+            textures: self.textures.clone(),
+            navigation: Navigation {
+                guided: Guided {
+                    graphs: self.navigation.guided.graphs.clone(),
+                    ranking: self.navigation.guided.ranking.clone(),
+                    lanes,
+                    human_lanes,
+                    locations,
+                },
+            },
+            custom_entity_schemas: self.custom_entity_schemas.clone(),
+            tags: filter_ids(&self.tags, keep),
+            ..Default::default()
+        }
+    }
+}
+
+fn filter_ids<T: Clone>(elements: &BTreeMap<u32, T>, keep: &BTreeSet<u32>) -> BTreeMap<u32, T> {
+    elements
+        .iter()
+        .filter(|(id, _)| keep.contains(id))
+        .map(|(id, element)| (*id, element.clone()))
+        .collect()
+}
+
+fn filter_lanes(
+    lanes: &BTreeMap<u32, Lane<u32>>,
+    keep: &BTreeSet<u32>,
+    lift_anchors: &BTreeSet<u32>,
+    anchors: &mut BTreeSet<u32>,
+) -> BTreeMap<u32, Lane<u32>> {
+    let mut lanes = filter_ids(lanes, keep);
+    lanes.retain(|_, lane| {
+        lane.anchors
+            .array()
+            .iter()
+            .all(|anchor| !lift_anchors.contains(anchor))
+    });
+    for lane in lanes.values() {
+        anchors.extend(lane.anchors.array());
+    }
+    lanes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legacy::building_map::BuildingMap;
+
+    #[test]
+    fn subset_keeps_referenced_anchors() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let site = BuildingMap::from_bytes(&data).unwrap().to_site().unwrap();
+        let (lane_id, lane) = site.navigation.guided.lanes.iter().next().unwrap();
+        let keep = BTreeSet::from([*lane_id]);
+
+        let subset = site.subset(&keep);
+        assert_eq!(subset.navigation.guided.lanes.len(), 1);
+        assert!(subset.navigation.guided.locations.is_empty());
+        let anchor_count: usize = subset.levels.values().map(|l| l.anchors.len()).sum();
+        assert_eq!(anchor_count + subset.anchors.len(), 2);
+        for anchor in lane.anchors.array() {
+            assert!(
+                subset.anchors.contains_key(&anchor)
+                    || subset
+                        .levels
+                        .values()
+                        .any(|l| l.anchors.contains_key(&anchor))
+            );
+        }
+        assert!(subset.levels.values().all(|l| l.walls.is_empty()));
+
+        // The subset has to be a site that can be loaded back
+        let mut bytes = Vec::new();
+        subset.to_writer_ron(&mut bytes).unwrap();
+        Site::from_bytes_ron(&bytes).unwrap();
+    }
+
+    #[test]
+    fn subset_keeps_selected_anchors_and_skips_lanes_into_lifts() {
+        let data = std::fs::read("../assets/demo_maps/office.building.yaml").unwrap();
+        let site = BuildingMap::from_bytes(&data).unwrap().to_site().unwrap();
+        let (level_id, level) = site.levels.iter().next().unwrap();
+        let anchor_id = *level.anchors.keys().next().unwrap();
+        let mut keep: BTreeSet<u32> = site.navigation.guided.lanes.keys().copied().collect();
+        keep.insert(anchor_id);
+
+        let subset = site.subset(&keep);
+        assert!(subset.levels[level_id].anchors.contains_key(&anchor_id));
+        for lane in subset.navigation.guided.lanes.values() {
+            for anchor in lane.anchors.array() {
+                assert!(
+                    subset.anchors.contains_key(&anchor)
+                        || subset
+                            .levels
+                            .values()
+                            .any(|l| l.anchors.contains_key(&anchor)),
+                    "Lane anchor {anchor} is missing from the subset",
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+#[cfg(feature = "bevy")]
+use bevy::prelude::{Component, Deref, DerefMut};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Free-form labels that users attach to elements of a site, such as
+/// "loading-dock" or "phase-2", to organize them across levels and element
+/// types. Tags are independent of the [`EntityGroups`](crate::EntityGroups)
+/// of a site.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
+pub struct EntityTags(pub BTreeSet<String>);
+
+impl EntityTags {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn has(&self, tag: &str) -> bool {
+        self.0.contains(tag)
+    }
+
+    /// Turn text that a user typed into a tag. Whitespace is trimmed and any
+    /// whitespace inside of the tag becomes a dash, so that a tag is always a
+    /// single search term.
+    pub fn parse_tag(text: &str) -> Option<String> {
+        let tag = text.split_whitespace().collect::<Vec<_>>().join("-");
+        (!tag.is_empty()).then_some(tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_single_terms() {
+        assert_eq!(
+            EntityTags::parse_tag("  phase 2 "),
+            Some("phase-2".to_owned())
+        );
+        assert_eq!(
+            EntityTags::parse_tag("loading-dock"),
+            Some("loading-dock".to_owned())
+        );
+        assert_eq!(EntityTags::parse_tag("   "), None);
+    }
+}
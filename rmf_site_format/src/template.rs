@@ -0,0 +1,51 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, io};
+
+/// A reusable sub-layout, such as a warehouse aisle or a hotel room, that can
+/// be stamped into any level of any site.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SiteTemplate {
+    pub name: String,
+    /// The anchors, walls, and doors of the template. Anchor coordinates are
+    /// relative to the center of the template.
+    pub level: Level,
+    /// Lanes of the template. Their anchors refer to the anchors of
+    /// [`SiteTemplate::level`].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lanes: BTreeMap<u32, Lane<u32>>,
+}
+
+impl SiteTemplate {
+    pub fn is_empty(&self) -> bool {
+        self.level.anchors.is_empty()
+    }
+
+    pub fn to_writer_ron<W: io::Write>(&self, mut writer: W) -> ron::Result<()> {
+        let contents = ron::ser::to_string_pretty(self, crate::site::default_style_config())?;
+        writer
+            .write_all(contents.as_bytes())
+            .map_err(ron::Error::from)
+    }
+
+    pub fn from_bytes_ron<'a>(s: &'a [u8]) -> ron::error::SpannedResult<Self> {
+        ron::de::from_bytes(s)
+    }
+}
@@ -0,0 +1,480 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::perturb::SeededRng;
+use glam::Vec2;
+use std::collections::{BTreeSet, VecDeque};
+
+/// One direction of travel along a lane of a [`TrafficGraph`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrafficLane {
+    pub start: usize,
+    pub end: usize,
+    /// The speed limit of this direction of the lane, in meters per second
+    pub speed_limit: Option<f32>,
+}
+
+/// The waypoints and lanes that robots of a traffic preview drive along.
+#[derive(Debug, Clone, Default)]
+pub struct TrafficGraph {
+    pub points: Vec<Vec2>,
+    pub lanes: Vec<TrafficLane>,
+    outgoing: Vec<Vec<usize>>,
+}
+
+impl TrafficGraph {
+    /// Each direction of travel needs its own lane, so a bidirectional lane
+    /// of a site becomes two lanes here.
+    pub fn new(points: Vec<Vec2>, lanes: Vec<TrafficLane>) -> Self {
+        let mut outgoing = vec![Vec::new(); points.len()];
+        let lanes: Vec<TrafficLane> = lanes
+            .into_iter()
+            .filter(|l| l.start < points.len() && l.end < points.len() && l.start != l.end)
+            .collect();
+        for (i, lane) in lanes.iter().enumerate() {
+            outgoing[lane.start].push(i);
+        }
+        Self {
+            points,
+            lanes,
+            outgoing,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lanes.is_empty()
+    }
+
+    fn lane_speed(&self, lane: &TrafficLane, speed: f32) -> f32 {
+        lane.speed_limit.map_or(speed, |limit| limit.min(speed))
+    }
+
+    fn lane_length(&self, lane: &TrafficLane) -> f32 {
+        self.points[lane.start].distance(self.points[lane.end])
+    }
+
+    /// The fastest sequence of waypoints from `from` to `to` for a robot that
+    /// drives at `speed`, excluding `from` itself.
+    pub fn fastest_route(&self, from: usize, to: usize, speed: f32) -> Option<VecDeque<usize>> {
+        self.route_by(from, to, |lane| {
+            self.lane_length(lane) / self.lane_speed(lane, speed).max(1e-3)
+        })
+    }
+
+    /// The shortest sequence of waypoints from `from` to `to`, excluding
+    /// `from` itself.
+    pub fn shortest_route(&self, from: usize, to: usize) -> Option<VecDeque<usize>> {
+        self.route_by(from, to, |lane| self.lane_length(lane))
+    }
+
+    /// The total length of a route that starts at `from`, in meters.
+    pub fn route_length(&self, from: usize, route: &VecDeque<usize>) -> f32 {
+        let mut previous = from;
+        let mut length = 0.0;
+        for waypoint in route {
+            length += self.points[previous].distance(self.points[*waypoint]);
+            previous = *waypoint;
+        }
+        length
+    }
+
+    /// How long a robot that drives at `speed` needs for a route that starts
+    /// at `from`, in seconds. This ignores acceleration and turning.
+    pub fn route_duration(&self, from: usize, route: &VecDeque<usize>, speed: f32) -> f32 {
+        let mut previous = from;
+        let mut duration = 0.0;
+        for waypoint in route {
+            let lane_speed = self
+                .find_lane(previous, *waypoint)
+                .map_or(speed, |lane| self.lane_speed(lane, speed));
+            duration +=
+                self.points[previous].distance(self.points[*waypoint]) / lane_speed.max(1e-3);
+            previous = *waypoint;
+        }
+        duration
+    }
+
+    /// Groups of waypoints where every waypoint of a group can be reached
+    /// from every other one, largest first. A robot that leaves its group
+    /// along a one-way lane can never come back to it.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let n = self.points.len();
+        // First pass: order the waypoints by when their depth-first search
+        // finishes
+        let mut visited = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        for root in 0..n {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut stack = vec![(root, 0)];
+            while let Some((node, next)) = stack.last_mut() {
+                if let Some(lane) = self.outgoing[*node].get(*next) {
+                    *next += 1;
+                    let end = self.lanes[*lane].end;
+                    if !visited[end] {
+                        visited[end] = true;
+                        stack.push((end, 0));
+                    }
+                } else {
+                    order.push(*node);
+                    stack.pop();
+                }
+            }
+        }
+
+        // Second pass: search backwards along the lanes in reverse finishing
+        // order, each search covers exactly one component
+        let mut incoming = vec![Vec::new(); n];
+        for lane in &self.lanes {
+            incoming[lane.end].push(lane.start);
+        }
+        let mut assigned = vec![false; n];
+        let mut components = Vec::new();
+        for root in order.into_iter().rev() {
+            if assigned[root] {
+                continue;
+            }
+            assigned[root] = true;
+            let mut members = Vec::new();
+            let mut stack = vec![root];
+            while let Some(node) = stack.pop() {
+                members.push(node);
+                for start in &incoming[node] {
+                    if !assigned[*start] {
+                        assigned[*start] = true;
+                        stack.push(*start);
+                    }
+                }
+            }
+            members.sort();
+            components.push(members);
+        }
+        components.sort_by_key(|c| (std::cmp::Reverse(c.len()), c[0]));
+        components
+    }
+
+    /// Dijkstra's algorithm with a cost for driving along each lane.
+    fn route_by(
+        &self,
+        from: usize,
+        to: usize,
+        lane_cost: impl Fn(&TrafficLane) -> f32,
+    ) -> Option<VecDeque<usize>> {
+        let n = self.points.len();
+        if from >= n || to >= n {
+            return None;
+        }
+        let mut cost = vec![f32::INFINITY; n];
+        let mut previous = vec![None; n];
+        let mut done = vec![false; n];
+        cost[from] = 0.0;
+        loop {
+            let current = (0..n)
+                .filter(|i| !done[*i] && cost[*i].is_finite())
+                .min_by(|a, b| {
+                    cost[*a]
+                        .partial_cmp(&cost[*b])
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })?;
+            if current == to {
+                break;
+            }
+            done[current] = true;
+            for lane in &self.outgoing[current] {
+                let lane = &self.lanes[*lane];
+                let c = cost[current] + lane_cost(lane);
+                if c < cost[lane.end] {
+                    cost[lane.end] = c;
+                    previous[lane.end] = Some(current);
+                }
+            }
+        }
+
+        let mut route = VecDeque::new();
+        let mut current = to;
+        while let Some(p) = previous[current] {
+            route.push_front(current);
+            current = p;
+        }
+        Some(route)
+    }
+
+    fn find_lane(&self, start: usize, end: usize) -> Option<&TrafficLane> {
+        self.outgoing
+            .get(start)?
+            .iter()
+            .map(|l| &self.lanes[*l])
+            .find(|l| l.end == end)
+    }
+}
+
+/// A placeholder robot driving around a traffic preview.
+#[derive(Debug, Clone)]
+pub struct PreviewRobot {
+    pub position: Vec2,
+    /// The waypoint that the robot is at, or last left from
+    pub waypoint: usize,
+    /// The lane the robot is driving along as `(start, end, progress)` with
+    /// progress going from 0 to 1
+    pub driving: Option<(usize, usize, f32)>,
+    /// Waypoints that the robot still needs to pass through
+    pub route: VecDeque<usize>,
+    /// The robot that is currently in the way of this one
+    pub waiting_for: Option<usize>,
+    /// How long the robot has been waiting, in seconds
+    pub wait_time: f32,
+}
+
+impl PreviewRobot {
+    /// The waypoint that this robot occupies or has reserved.
+    fn occupied(&self) -> usize {
+        self.driving.map_or(self.waypoint, |(_, end, _)| end)
+    }
+}
+
+/// A quick approximation of how robots move through a navigation graph.
+/// Robots drive from waypoint to waypoint towards randomly chosen
+/// destinations, respecting the direction and speed limits of lanes. A robot
+/// only enters a lane if the waypoint at its end is free and no robot is
+/// coming the other way, so robots that wait on each other in a loop are
+/// deadlocked.
+#[derive(Debug, Clone)]
+pub struct TrafficPreview {
+    pub graph: TrafficGraph,
+    pub robots: Vec<PreviewRobot>,
+    /// How fast robots drive when lanes do not limit them, in meters per
+    /// second
+    pub speed: f32,
+    /// Pairs of waypoints where robots had to wait for a robot coming from
+    /// the opposite direction, with the smaller waypoint first
+    pub head_on: BTreeSet<(usize, usize)>,
+    rng: SeededRng,
+}
+
+impl TrafficPreview {
+    /// Start robots at the given waypoints. Waypoints that appear more than
+    /// once only get one robot.
+    pub fn new(graph: TrafficGraph, spawn: &[usize], speed: f32, seed: u64) -> Self {
+        let mut used = BTreeSet::new();
+        let robots = spawn
+            .iter()
+            .filter(|w| **w < graph.points.len() && used.insert(**w))
+            .map(|w| PreviewRobot {
+                position: graph.points[*w],
+                waypoint: *w,
+                driving: None,
+                route: VecDeque::new(),
+                waiting_for: None,
+                wait_time: 0.0,
+            })
+            .collect();
+        Self {
+            graph,
+            robots,
+            speed,
+            head_on: BTreeSet::new(),
+            rng: SeededRng(seed),
+        }
+    }
+
+    /// Advance the preview by `dt` seconds.
+    pub fn step(&mut self, dt: f32) {
+        for i in 0..self.robots.len() {
+            if let Some((start, end, progress)) = self.robots[i].driving {
+                let Some(lane) = self.graph.find_lane(start, end) else {
+                    self.robots[i].driving = None;
+                    continue;
+                };
+                let (p0, p1) = (self.graph.points[start], self.graph.points[end]);
+                let length = p0.distance(p1).max(1e-3);
+                let speed = self.graph.lane_speed(lane, self.speed);
+                let progress = (progress + speed * dt / length).min(1.0);
+                let robot = &mut self.robots[i];
+                robot.position = p0.lerp(p1, progress);
+                if progress >= 1.0 {
+                    robot.driving = None;
+                    robot.waypoint = end;
+                } else {
+                    robot.driving = Some((start, end, progress));
+                }
+                continue;
+            }
+
+            if self.robots[i].route.is_empty() {
+                self.choose_destination(i);
+            }
+            let Some(next) = self.robots[i].route.front().copied() else {
+                continue;
+            };
+            let here = self.robots[i].waypoint;
+            let blocker = self.robots.iter().enumerate().find_map(|(j, other)| {
+                if i == j {
+                    return None;
+                }
+                if other.occupied() == next {
+                    return Some((j, false));
+                }
+                let head_on = other
+                    .driving
+                    .is_some_and(|(s, e, _)| s == next && e == here);
+                head_on.then_some((j, true))
+            });
+
+            if let Some((j, head_on)) = blocker {
+                if head_on || self.robots[j].route.front() == Some(&here) {
+                    self.head_on.insert((here.min(next), here.max(next)));
+                }
+                let robot = &mut self.robots[i];
+                robot.waiting_for = Some(j);
+                robot.wait_time += dt;
+            } else {
+                let robot = &mut self.robots[i];
+                robot.waiting_for = None;
+                robot.wait_time = 0.0;
+                robot.route.pop_front();
+                robot.driving = Some((here, next, 0.0));
+            }
+        }
+    }
+
+    fn choose_destination(&mut self, i: usize) {
+        let n = self.graph.points.len() as u64;
+        let here = self.robots[i].waypoint;
+        // Try a few destinations in case some cannot be reached from here
+        for _ in 0..8 {
+            let goal = (self.rng.next_u64() % n.max(1)) as usize;
+            if goal == here {
+                continue;
+            }
+            if let Some(route) = self.graph.fastest_route(here, goal, self.speed) {
+                self.robots[i].route = route;
+                return;
+            }
+        }
+    }
+
+    /// Robots that are waiting on each other in a loop and will never move
+    /// again.
+    pub fn deadlocked(&self) -> BTreeSet<usize> {
+        let mut deadlocked = BTreeSet::new();
+        for start in 0..self.robots.len() {
+            let mut visited = BTreeSet::new();
+            let mut current = start;
+            while let Some(next) = self.robots[current].waiting_for {
+                if next == start {
+                    deadlocked.insert(start);
+                    break;
+                }
+                if !visited.insert(next) {
+                    break;
+                }
+                current = next;
+            }
+        }
+        deadlocked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn robots_in_a_corridor_deadlock() {
+        // Two robots facing each other across a single waypoint with no room
+        // to pass
+        let points = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0),
+        ];
+        let lane = |start, end| TrafficLane {
+            start,
+            end,
+            speed_limit: None,
+        };
+        let graph = TrafficGraph::new(points, vec![lane(0, 1), lane(1, 0), lane(1, 2), lane(2, 1)]);
+        let mut preview = TrafficPreview::new(graph, &[0, 2], 1.0, 3);
+        preview.robots[0].route = VecDeque::from([1, 2]);
+        preview.robots[1].route = VecDeque::from([1, 0]);
+        for _ in 0..50 {
+            preview.step(0.1);
+        }
+        assert_eq!(preview.deadlocked(), BTreeSet::from([0, 1]));
+        assert!(!preview.head_on.is_empty());
+    }
+
+    #[test]
+    fn routes_follow_lane_directions() {
+        let points = vec![Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0)];
+        let graph = TrafficGraph::new(
+            points,
+            vec![TrafficLane {
+                start: 0,
+                end: 1,
+                speed_limit: Some(0.5),
+            }],
+        );
+        assert_eq!(graph.fastest_route(0, 1, 1.0), Some(VecDeque::from([1])));
+        assert_eq!(graph.fastest_route(1, 0, 1.0), None);
+    }
+
+    #[test]
+    fn shortest_route_can_be_slower() {
+        let points = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(1.0, 1.0),
+        ];
+        let lane = |start, end, speed_limit| TrafficLane {
+            start,
+            end,
+            speed_limit,
+        };
+        let graph = TrafficGraph::new(
+            points,
+            vec![lane(0, 1, Some(0.2)), lane(0, 2, None), lane(2, 1, None)],
+        );
+        let shortest = graph.shortest_route(0, 1).unwrap();
+        assert_eq!(shortest, VecDeque::from([1]));
+        assert_eq!(graph.fastest_route(0, 1, 1.0), Some(VecDeque::from([2, 1])));
+        assert!((graph.route_length(0, &shortest) - 2.0).abs() < 1e-5);
+        assert!((graph.route_duration(0, &shortest, 1.0) - 10.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn one_way_lanes_split_components() {
+        let points = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(5.0, 5.0),
+        ];
+        let lane = |start, end| TrafficLane {
+            start,
+            end,
+            speed_limit: None,
+        };
+        let graph = TrafficGraph::new(points, vec![lane(0, 1), lane(1, 0), lane(1, 2)]);
+        assert_eq!(
+            graph.strongly_connected_components(),
+            vec![vec![0, 1], vec![2], vec![3]],
+        );
+    }
+}
@@ -17,7 +17,7 @@
 
 use crate::*;
 #[cfg(feature = "bevy")]
-use bevy::prelude::{Bundle, Component};
+use bevy::prelude::{Bundle, Component, Deref, DerefMut};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -27,10 +27,40 @@ pub struct Wall<T: RefTrait> {
     pub anchors: Edge<T>,
     #[serde(skip_serializing_if = "is_default")]
     pub texture: Affiliation<T>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub height: WallHeight,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub alpha: WallAlpha,
     #[serde(skip)]
     pub marker: WallMarker,
 }
 
+/// How tall a wall is, in meters above the floor of its level.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
+pub struct WallHeight(pub f32);
+
+impl Default for WallHeight {
+    fn default() -> Self {
+        Self(DEFAULT_LEVEL_HEIGHT)
+    }
+}
+
+/// Opacity of a wall, from 0 (invisible) to 1 (opaque). This is multiplied
+/// with the alpha of the wall's texture, so one wall can be made see-through
+/// without changing every other wall that shares its texture.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "bevy", derive(Component, Deref, DerefMut))]
+pub struct WallAlpha(pub f32);
+
+impl Default for WallAlpha {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "bevy", derive(Component))]
 pub struct WallMarker;
@@ -40,6 +70,8 @@ impl<T: RefTrait> Wall<T> {
         Ok(Wall {
             anchors: self.anchors.convert(id_map)?,
             texture: self.texture.convert(id_map)?,
+            height: self.height,
+            alpha: self.alpha,
             marker: Default::default(),
         })
     }
@@ -50,6 +82,8 @@ impl<T: RefTrait> From<Edge<T>> for Wall<T> {
         Self {
             anchors,
             texture: Affiliation(None),
+            height: WallHeight::default(),
+            alpha: WallAlpha::default(),
             marker: Default::default(),
         }
     }
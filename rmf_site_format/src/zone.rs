@@ -0,0 +1,164 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+use crate::*;
+#[cfg(feature = "bevy")]
+use bevy::prelude::{Bundle, Component};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A named area of a level, outlined by a loop of anchors, that tells robots
+/// and planners how the area is meant to be used.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Bundle))]
+pub struct Zone<T: RefTrait> {
+    pub anchors: Path<T>,
+    pub name: NameInSite,
+    #[serde(default)]
+    pub kind: ZoneKind,
+    #[serde(skip)]
+    pub marker: ZoneMarker,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+pub struct ZoneMarker;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+pub enum ZoneKind {
+    /// Robots must not enter the zone.
+    #[default]
+    KeepOut,
+    /// Robots must not move faster than `max_speed`, in meters per second,
+    /// while inside the zone.
+    SpeedLimited { max_speed: f32 },
+    /// Robots may park inside the zone.
+    ParkingArea,
+    /// The zone is reserved for people.
+    HumanOnly,
+}
+
+impl ZoneKind {
+    pub const DEFAULT_MAX_SPEED: f32 = 0.5;
+
+    pub const ALL: [ZoneKind; 4] = [
+        ZoneKind::KeepOut,
+        ZoneKind::SpeedLimited {
+            max_speed: Self::DEFAULT_MAX_SPEED,
+        },
+        ZoneKind::ParkingArea,
+        ZoneKind::HumanOnly,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::KeepOut => "Keep Out",
+            Self::SpeedLimited { .. } => "Speed Limited",
+            Self::ParkingArea => "Parking Area",
+            Self::HumanOnly => "Human Only",
+        }
+    }
+
+    pub fn is_same_kind(&self, other: &ZoneKind) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// Whether robots are forbidden from driving through the zone.
+    pub fn excludes_robots(&self) -> bool {
+        matches!(self, Self::KeepOut | Self::HumanOnly)
+    }
+
+    /// The color used to fill the zone, as linear RGB.
+    pub fn color(&self) -> [f32; 3] {
+        match self {
+            Self::KeepOut => [0.9, 0.1, 0.1],
+            Self::SpeedLimited { .. } => [0.95, 0.7, 0.05],
+            Self::ParkingArea => [0.1, 0.4, 0.9],
+            Self::HumanOnly => [0.2, 0.8, 0.3],
+        }
+    }
+}
+
+impl<T: RefTrait> Zone<T> {
+    pub fn convert<U: RefTrait>(&self, id_map: &HashMap<T, U>) -> Result<Zone<U>, T> {
+        Ok(Zone {
+            anchors: self.anchors.convert(id_map)?,
+            name: self.name.clone(),
+            kind: self.kind,
+            marker: Default::default(),
+        })
+    }
+}
+
+impl<T: RefTrait> From<Path<T>> for Zone<T> {
+    fn from(path: Path<T>) -> Self {
+        Zone {
+            anchors: path,
+            name: NameInSite("<Unnamed Zone>".to_owned()),
+            kind: Default::default(),
+            marker: Default::default(),
+        }
+    }
+}
+
+/// Check whether a point lies inside of a polygon using the even-odd rule.
+/// The polygon is implicitly closed.
+pub fn point_in_polygon(point: [f32; 2], polygon: &[[f32; 2]]) -> bool {
+    let [x, y] = point;
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for i in 0..polygon.len() {
+        let [xi, yi] = polygon[i];
+        let [xj, yj] = polygon[j];
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_in_concave_polygon() {
+        // An L-shaped polygon
+        let polygon = [
+            [0.0, 0.0],
+            [2.0, 0.0],
+            [2.0, 1.0],
+            [1.0, 1.0],
+            [1.0, 2.0],
+            [0.0, 2.0],
+        ];
+        assert!(point_in_polygon([0.5, 0.5], &polygon));
+        assert!(point_in_polygon([0.5, 1.5], &polygon));
+        assert!(!point_in_polygon([1.5, 1.5], &polygon));
+        assert!(!point_in_polygon([3.0, 0.5], &polygon));
+        assert!(!point_in_polygon([0.5, 0.5], &[]));
+    }
+
+    #[test]
+    fn zone_kind_round_trip() {
+        let kind = ZoneKind::SpeedLimited { max_speed: 0.3 };
+        let text = ron::to_string(&kind).unwrap();
+        assert_eq!(ron::from_str::<ZoneKind>(&text).unwrap(), kind);
+    }
+}
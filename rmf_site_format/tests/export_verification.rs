@@ -0,0 +1,270 @@
+/*
+ * Copyright (C) 2025 Open Source Robotics Foundation
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+*/
+
+//! Runs every exporter on each of the demo maps and checks that the output is
+//! something that RMF and Gazebo will actually be able to load.
+
+use rmf_site_format::{legacy::building_map::BuildingMap, legacy::nav_graph::NavGraph, Site};
+use sdformat_rs::SdfRoot;
+use serde_yaml::Value;
+use std::collections::HashSet;
+
+const DEMO_MAPS: &str = "../assets/demo_maps";
+
+fn demo_sites() -> Vec<(String, Site)> {
+    let mut sites = Vec::new();
+    for entry in std::fs::read_dir(DEMO_MAPS).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let data = std::fs::read(&path).unwrap();
+        let site = if name.ends_with(".building.yaml") {
+            BuildingMap::from_bytes(&data).unwrap().to_site().unwrap()
+        } else if name.ends_with(".site.ron") {
+            Site::from_bytes_ron(&data).unwrap()
+        } else if name.ends_with(".site.json") {
+            Site::from_bytes_json(&data).unwrap()
+        } else {
+            continue;
+        };
+        sites.push((name, site));
+    }
+    assert!(!sites.is_empty(), "No demo maps found in {DEMO_MAPS}");
+    sites
+}
+
+fn as_f64(value: &Value, context: &str) -> f64 {
+    value
+        .as_f64()
+        .unwrap_or_else(|| panic!("{context}: expected a number, found {value:?}"))
+}
+
+fn as_seq<'a>(value: &'a Value, context: &str) -> &'a Vec<Value> {
+    value
+        .as_sequence()
+        .unwrap_or_else(|| panic!("{context}: expected a sequence, found {value:?}"))
+}
+
+/// Check a nav graph against the schema that rmf_traffic_ros2 parses in
+/// `parse_graph.cpp`.
+fn validate_nav_graph(context: &str, graph: &Value) {
+    let root = graph
+        .as_mapping()
+        .unwrap_or_else(|| panic!("{context}: the graph must be a mapping"));
+    let get = |key: &str| root.get(&Value::String(key.to_owned()));
+
+    get("building_name")
+        .and_then(|v| v.as_str())
+        .unwrap_or_else(|| panic!("{context}: missing building_name"));
+
+    let doors: HashSet<String> = match get("doors") {
+        Some(doors) => doors
+            .as_mapping()
+            .unwrap_or_else(|| panic!("{context}: doors must be a mapping"))
+            .iter()
+            .map(|(name, door)| {
+                let name = name.as_str().unwrap().to_owned();
+                let endpoints = as_seq(&door["endpoints"], &format!("{context} door {name}"));
+                assert_eq!(
+                    endpoints.len(),
+                    2,
+                    "{context}: door {name} needs two endpoints"
+                );
+                for p in endpoints {
+                    let p = as_seq(p, &format!("{context} door {name}"));
+                    assert_eq!(p.len(), 2);
+                    p.iter().for_each(|v| {
+                        as_f64(v, &format!("{context} door {name}"));
+                    });
+                }
+                door["map"]
+                    .as_str()
+                    .unwrap_or_else(|| panic!("{context}: door {name} is missing its map"));
+                name
+            })
+            .collect(),
+        None => HashSet::new(),
+    };
+
+    if let Some(lifts) = get("lifts") {
+        for (name, lift) in lifts.as_mapping().unwrap() {
+            let context = format!("{context} lift {}", name.as_str().unwrap());
+            assert_eq!(as_seq(&lift["position"], &context).len(), 3, "{context}");
+            assert_eq!(as_seq(&lift["dims"], &context).len(), 2, "{context}");
+        }
+    }
+
+    let levels = get("levels")
+        .and_then(|v| v.as_mapping())
+        .unwrap_or_else(|| panic!("{context}: levels must be a mapping"));
+    for (level_name, level) in levels {
+        let context = format!("{context} level {}", level_name.as_str().unwrap());
+        let vertices = as_seq(&level["vertices"], &context);
+        let mut vertex_names = HashSet::new();
+        for (i, vertex) in vertices.iter().enumerate() {
+            let vertex = as_seq(vertex, &format!("{context} vertex {i}"));
+            assert_eq!(
+                vertex.len(),
+                3,
+                "{context}: vertex {i} must be [x, y, props]"
+            );
+            let x = as_f64(&vertex[0], &context);
+            let y = as_f64(&vertex[1], &context);
+            assert!(x.is_finite() && y.is_finite(), "{context}: vertex {i}");
+            let name = vertex[2]["name"]
+                .as_str()
+                .unwrap_or_else(|| panic!("{context}: vertex {i} is missing a name"));
+            if !name.is_empty() {
+                assert!(
+                    vertex_names.insert(name.to_owned()),
+                    "{context}: duplicate vertex name {name}"
+                );
+            }
+        }
+
+        for (i, lane) in as_seq(&level["lanes"], &context).iter().enumerate() {
+            let lane = as_seq(lane, &format!("{context} lane {i}"));
+            assert_eq!(lane.len(), 3, "{context}: lane {i} must be [v0, v1, props]");
+            for v in &lane[0..2] {
+                let v = v
+                    .as_u64()
+                    .unwrap_or_else(|| panic!("{context}: lane {i} has a bad vertex index"));
+                assert!(
+                    (v as usize) < vertices.len(),
+                    "{context}: lane {i} refers to missing vertex {v}"
+                );
+            }
+            assert_ne!(lane[0], lane[1], "{context}: lane {i} starts where it ends");
+            as_f64(&lane[2]["speed_limit"], &context);
+            if let Some(door) = lane[2].get("door_name") {
+                let door = door.as_str().unwrap();
+                assert!(
+                    doors.contains(door),
+                    "{context}: lane {i} refers to missing door {door}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn nav_graph_export_matches_schema() {
+    for (map, site) in demo_sites() {
+        let graphs = NavGraph::from_site(&site);
+        assert!(!graphs.is_empty(), "{map}: no nav graphs were exported");
+        for (name, graph) in graphs {
+            let context = format!("{map} graph {name}");
+            let s = serde_yaml::to_string(&graph).unwrap();
+            let value: Value = serde_yaml::from_str(&s)
+                .unwrap_or_else(|err| panic!("{context}: output is not valid yaml: {err}"));
+            validate_nav_graph(&context, &value);
+        }
+    }
+}
+
+#[test]
+fn sdf_export_is_valid_sdformat() {
+    for (map, site) in demo_sites() {
+        let sdf = site
+            .to_sdf()
+            .unwrap_or_else(|err| panic!("{map}: failed to convert to sdf: {err}"));
+        let s = yaserde::ser::to_string(&sdf).unwrap();
+
+        // Parsing the output back through the SDFormat data model catches any
+        // missing required elements or malformed values.
+        let parsed: SdfRoot = yaserde::de::from_str(&s)
+            .unwrap_or_else(|err| panic!("{map}: exported sdf does not parse: {err}"));
+        assert_eq!(parsed.world.len(), 1, "{map}: expected exactly one world");
+        let world = &parsed.world[0];
+        assert_eq!(
+            world.name, site.properties.name.0,
+            "{map}: wrong world name"
+        );
+
+        let mut model_names = HashSet::new();
+        for model in &world.model {
+            assert!(
+                model_names.insert(model.name.clone()),
+                "{map}: duplicate model name {}",
+                model.name
+            );
+        }
+
+        for include in &world.include {
+            assert!(!include.uri.is_empty(), "{map}: include without a uri");
+            if let Some(name) = &include.name {
+                assert!(
+                    model_names.insert(name.clone()),
+                    "{map}: duplicate model name {name}"
+                );
+            }
+        }
+
+        for level in site.levels.values() {
+            assert!(
+                model_names.contains(&level.properties.name.0),
+                "{map}: level {} is missing from the world",
+                level.properties.name.0
+            );
+        }
+    }
+}
+
+#[test]
+fn plan_export_has_title_block_and_valid_pdf() {
+    for (map, mut site) in demo_sites() {
+        for level in site.levels.values_mut() {
+            level.paper_space.title_block.project = "Demo & <Test>".to_owned();
+            level.paper_space.title_block.revision = "B".to_owned();
+        }
+
+        // The paper space settings need to survive a save and load
+        let mut data = Vec::new();
+        site.to_writer_ron(&mut data).unwrap();
+        let site = Site::from_bytes_ron(&data).unwrap();
+
+        for (level_id, level) in &site.levels {
+            let context = format!("{map} level {}", level.properties.name.0);
+            assert_eq!(level.paper_space.title_block.revision, "B", "{context}");
+            let plan = site
+                .to_plan(*level_id)
+                .unwrap_or_else(|err| panic!("{context}: failed to lay out plan: {err}"));
+            assert!(plan.scale > 0.0, "{context}: bad scale {}", plan.scale);
+
+            let svg = plan.to_svg();
+            assert!(
+                svg.contains("Project: Demo &amp; &lt;Test&gt;"),
+                "{context}"
+            );
+            assert!(
+                svg.contains(&format!("Level: {}", level.properties.name.0)),
+                "{context}: title block is missing the level name"
+            );
+
+            let pdf = String::from_utf8(plan.to_pdf()).unwrap();
+            assert!(pdf.starts_with("%PDF-1.4"), "{context}");
+            assert!(pdf.ends_with("%%EOF\n"), "{context}");
+            let start_xref: usize = pdf
+                .rsplit("startxref\n")
+                .next()
+                .and_then(|tail| tail.lines().next())
+                .and_then(|offset| offset.parse().ok())
+                .unwrap_or_else(|| panic!("{context}: missing startxref"));
+            assert!(pdf[start_xref..].starts_with("xref"), "{context}");
+            assert!(pdf.contains("/MediaBox [0 0 "), "{context}");
+        }
+    }
+}